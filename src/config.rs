@@ -8,6 +8,7 @@ use std::fmt;
 use std::fs;
 use std::path::PathBuf;
 
+use crate::snippet::{language_to_extension, ColorData, SnippetKind};
 use crate::view::PickerMode;
 
 // === Keyboard Shortcuts ===
@@ -18,11 +19,29 @@ pub struct Modifiers {
     pub ctrl: bool,
     pub alt: bool,
     pub shift: bool,
+    /// The Super/Windows key. On macOS the Command key counts as `ctrl` instead.
+    #[serde(default)]
+    pub logo: bool,
 }
 
 impl Modifiers {
     pub const fn new(ctrl: bool, alt: bool, shift: bool) -> Self {
-        Self { ctrl, alt, shift }
+        Self {
+            ctrl,
+            alt,
+            shift,
+            logo: false,
+        }
+    }
+
+    /// Get the modifiers held during an iced key event.
+    pub fn from_iced(mods: iced::keyboard::Modifiers) -> Self {
+        Self {
+            ctrl: mods.command(),
+            alt: mods.alt(),
+            shift: mods.shift(),
+            logo: mods.logo() && !cfg!(target_os = "macos"),
+        }
     }
 
     pub const fn ctrl() -> Self {
@@ -35,7 +54,7 @@ impl Modifiers {
 
     /// Check if iced modifiers match this config.
     pub fn matches(&self, mods: iced::keyboard::Modifiers) -> bool {
-        self.ctrl == mods.command() && self.alt == mods.alt() && self.shift == mods.shift()
+        *self == Self::from_iced(mods)
     }
 }
 
@@ -51,6 +70,13 @@ impl fmt::Display for Modifiers {
         if self.shift {
             parts.push("Shift");
         }
+        if self.logo {
+            parts.push(if cfg!(target_os = "windows") {
+                "Win"
+            } else {
+                "Super"
+            });
+        }
         write!(f, "{}", parts.join("+"))
     }
 }
@@ -86,21 +112,69 @@ impl Shortcut {
         }
     }
 
-    /// Check if this shortcut matches a key press.
-    pub fn matches(&self, key: &iced::keyboard::Key, modifiers: iced::keyboard::Modifiers) -> bool {
-        if !self.modifiers.matches(modifiers) {
-            return false;
-        }
+    /// Build a shortcut from a key press while recording, or explain why the
+    /// combination can't be used.
+    pub fn from_key_press(
+        key: &iced::keyboard::Key,
+        location: iced::keyboard::Location,
+        modifiers: iced::keyboard::Modifiers,
+    ) -> Result<Self, String> {
+        use iced::keyboard::key::Named;
+        use iced::keyboard::Key;
 
+        let mods = Modifiers::from_iced(modifiers);
         match key {
-            iced::keyboard::Key::Character(c) => c.to_lowercase().eq(&self.key.to_lowercase()),
-            iced::keyboard::Key::Named(named) => {
-                let named_str = format!("{:?}", named);
-                named_str.eq_ignore_ascii_case(&self.key)
+            // A modifier on its own: show what is held so far
+            Key::Named(
+                Named::Control
+                | Named::Alt
+                | Named::AltGraph
+                | Named::Shift
+                | Named::Super
+                | Named::Meta
+                | Named::Hyper
+                | Named::Fn,
+            ) => Err(if mods == Modifiers::none() {
+                "Now press a key".to_string()
+            } else {
+                format!("{}+… now press a key", mods)
+            }),
+            // Typing keys on their own (or with Shift) would fire while typing
+            Key::Character(_) if !(mods.ctrl || mods.alt || mods.logo) => {
+                let name = key_name(key, location).unwrap_or_default();
+                Err(format!("{} needs a modifier other than Shift", name))
             }
-            _ => false,
+            _ => key_name(key, location)
+                .map(|name| Self::named(&name, mods))
+                .ok_or_else(|| "This key can't be used in a shortcut".to_string()),
         }
     }
+
+    /// Check if this shortcut matches a key press.
+    pub fn matches(
+        &self,
+        key: &iced::keyboard::Key,
+        location: iced::keyboard::Location,
+        modifiers: iced::keyboard::Modifiers,
+    ) -> bool {
+        self.modifiers.matches(modifiers)
+            && key_name(key, location).is_some_and(|name| name.eq_ignore_ascii_case(&self.key))
+    }
+}
+
+/// Get the name a key is stored under in a shortcut, e.g. `v`, `F5` or
+/// `Numpad7` (keypad keys are told apart from the main keys).
+fn key_name(key: &iced::keyboard::Key, location: iced::keyboard::Location) -> Option<String> {
+    let name = match key {
+        iced::keyboard::Key::Character(c) if !c.is_empty() => c.to_lowercase(),
+        iced::keyboard::Key::Named(named) => format!("{:?}", named),
+        _ => return None,
+    };
+    if location == iced::keyboard::Location::Numpad {
+        Some(format!("Numpad{}", name))
+    } else {
+        Some(name)
+    }
 }
 
 impl fmt::Display for Shortcut {
@@ -123,6 +197,10 @@ pub enum ShortcutAction {
     Escape,
     Delete,
     CopySnippet,
+    TogglePin,
+    Undo,
+    AssignPalette,
+    QuickPopup,
 }
 
 impl ShortcutAction {
@@ -134,16 +212,24 @@ impl ShortcutAction {
             ShortcutAction::Escape => "Close / Cancel",
             ShortcutAction::Delete => "Delete Selected",
             ShortcutAction::CopySnippet => "Copy Snippet",
+            ShortcutAction::TogglePin => "Pin / Unpin Selected",
+            ShortcutAction::Undo => "Undo Color Adjustment",
+            ShortcutAction::AssignPalette => "Assign Selected to Palette",
+            ShortcutAction::QuickPopup => "Quick Capture / Search",
         }
     }
 
     /// All available actions.
-    pub const ALL: [ShortcutAction; 5] = [
+    pub const ALL: [ShortcutAction; 9] = [
         ShortcutAction::Paste,
         ShortcutAction::NewColor,
         ShortcutAction::Escape,
         ShortcutAction::Delete,
         ShortcutAction::CopySnippet,
+        ShortcutAction::TogglePin,
+        ShortcutAction::Undo,
+        ShortcutAction::AssignPalette,
+        ShortcutAction::QuickPopup,
     ];
 }
 
@@ -156,12 +242,36 @@ pub struct KeyboardConfig {
     pub delete: Shortcut,
     #[serde(default = "default_copy_snippet_shortcut")]
     pub copy_snippet: Shortcut,
+    #[serde(default = "default_toggle_pin_shortcut")]
+    pub toggle_pin: Shortcut,
+    #[serde(default = "default_undo_shortcut")]
+    pub undo: Shortcut,
+    #[serde(default = "default_assign_palette_shortcut")]
+    pub assign_palette: Shortcut,
+    #[serde(default = "default_quick_popup_shortcut")]
+    pub quick_popup: Shortcut,
 }
 
 fn default_copy_snippet_shortcut() -> Shortcut {
     Shortcut::char_key('c', Modifiers::ctrl())
 }
 
+fn default_toggle_pin_shortcut() -> Shortcut {
+    Shortcut::char_key('p', Modifiers::ctrl())
+}
+
+fn default_undo_shortcut() -> Shortcut {
+    Shortcut::char_key('z', Modifiers::ctrl())
+}
+
+fn default_assign_palette_shortcut() -> Shortcut {
+    Shortcut::char_key('l', Modifiers::ctrl())
+}
+
+fn default_quick_popup_shortcut() -> Shortcut {
+    Shortcut::char_key('k', Modifiers::ctrl())
+}
+
 impl Default for KeyboardConfig {
     fn default() -> Self {
         Self {
@@ -170,6 +280,10 @@ impl Default for KeyboardConfig {
             escape: Shortcut::named("Escape", Modifiers::none()),
             delete: Shortcut::named("Delete", Modifiers::none()),
             copy_snippet: default_copy_snippet_shortcut(),
+            toggle_pin: default_toggle_pin_shortcut(),
+            undo: default_undo_shortcut(),
+            assign_palette: default_assign_palette_shortcut(),
+            quick_popup: default_quick_popup_shortcut(),
         }
     }
 }
@@ -183,6 +297,10 @@ impl KeyboardConfig {
             ShortcutAction::Escape => &self.escape,
             ShortcutAction::Delete => &self.delete,
             ShortcutAction::CopySnippet => &self.copy_snippet,
+            ShortcutAction::TogglePin => &self.toggle_pin,
+            ShortcutAction::Undo => &self.undo,
+            ShortcutAction::AssignPalette => &self.assign_palette,
+            ShortcutAction::QuickPopup => &self.quick_popup,
         }
     }
 
@@ -194,6 +312,10 @@ impl KeyboardConfig {
             ShortcutAction::Escape => self.escape = shortcut,
             ShortcutAction::Delete => self.delete = shortcut,
             ShortcutAction::CopySnippet => self.copy_snippet = shortcut,
+            ShortcutAction::TogglePin => self.toggle_pin = shortcut,
+            ShortcutAction::Undo => self.undo = shortcut,
+            ShortcutAction::AssignPalette => self.assign_palette = shortcut,
+            ShortcutAction::QuickPopup => self.quick_popup = shortcut,
         }
     }
 }
@@ -277,32 +399,702 @@ impl EditorConfig {
     /// Build the command line for opening a file.
     /// Returns (program, args) tuple.
     pub fn build_command(&self, file_path: &str) -> Option<(String, Vec<String>)> {
-        let cmd_template = self.effective_command();
-        if cmd_template.is_empty() {
-            return None;
+        command_line(self.effective_command(), file_path)
+    }
+}
+
+/// Split a command template into (program, args), filling in `{file}`.
+/// The template is split before the path goes in, so paths with spaces stay
+/// one argument.
+fn command_line(template: &str, file_path: &str) -> Option<(String, Vec<String>)> {
+    let mut parts = template
+        .split_whitespace()
+        .map(|part| part.replace("{file}", file_path));
+    let program = parts.next()?;
+    Some((program, parts.collect()))
+}
+
+// === Editing ===
+
+/// Tab widths offered in settings.
+pub const TAB_WIDTHS: [u8; 3] = [2, 4, 8];
+
+/// Behavior of the code and text editor modals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EditingConfig {
+    /// Wrap long lines in the text editor.
+    #[serde(default = "default_word_wrap")]
+    pub word_wrap: bool,
+    /// Wrap long lines in the code editor, which shows line numbers only without it.
+    #[serde(default)]
+    pub code_word_wrap: bool,
+    /// Columns a Tab press indents to with soft tabs.
+    #[serde(default = "default_tab_width")]
+    pub tab_width: u8,
+    /// Insert spaces for Tab instead of a tab character.
+    #[serde(default = "default_insert_spaces")]
+    pub insert_spaces: bool,
+}
+
+impl Default for EditingConfig {
+    fn default() -> Self {
+        Self {
+            word_wrap: default_word_wrap(),
+            code_word_wrap: false,
+            tab_width: default_tab_width(),
+            insert_spaces: default_insert_spaces(),
+        }
+    }
+}
+
+impl EditingConfig {
+    /// Get the text a Tab press inserts at a column: spaces up to the next
+    /// tab stop, or a tab character.
+    pub fn indent(&self, column: usize) -> String {
+        if self.insert_spaces {
+            let width = usize::from(self.tab_width.max(1));
+            " ".repeat(width - column % width)
+        } else {
+            "\t".to_string()
+        }
+    }
+}
+
+fn default_word_wrap() -> bool {
+    true
+}
+
+fn default_tab_width() -> u8 {
+    4
+}
+
+fn default_insert_spaces() -> bool {
+    true
+}
+
+// === Run Commands ===
+
+/// How to run code snippets of one language, e.g. `python3 {file}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunCommand {
+    pub language: String,
+    /// Command line; `{file}` is the snippet saved to a temporary file.
+    pub command: String,
+}
+
+impl RunCommand {
+    fn new(language: &str, command: &str) -> Self {
+        Self {
+            language: language.to_string(),
+            command: command.to_string(),
+        }
+    }
+
+    /// Check whether this runs snippets of `language`; aliases such as
+    /// "sh" and "bash" count as the same language.
+    pub fn runs(&self, language: &str) -> bool {
+        let extension = language_to_extension(&self.language);
+        self.language.trim().eq_ignore_ascii_case(language.trim())
+            || (extension != "txt" && extension == language_to_extension(language))
+    }
+
+    /// Build the command line for running a file, as (program, args).
+    pub fn build_command(&self, file_path: &str) -> Option<(String, Vec<String>)> {
+        command_line(&self.command, file_path)
+    }
+}
+
+fn default_run_commands() -> Vec<RunCommand> {
+    vec![
+        RunCommand::new("python", "python3 {file}"),
+        RunCommand::new("javascript", "node {file}"),
+        RunCommand::new("shell", "sh {file}"),
+        RunCommand::new("ruby", "ruby {file}"),
+        RunCommand::new("php", "php {file}"),
+    ]
+}
+
+// === Appearance ===
+
+/// When the action buttons on snippet cards are visible.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CardActions {
+    /// All actions are always shown.
+    #[default]
+    Always,
+    /// Actions appear while the card is hovered.
+    OnHover,
+    /// Only copy is shown; the other actions appear on hover.
+    Minimal,
+}
+
+impl CardActions {
+    /// All visibility modes for selection.
+    pub const ALL: [CardActions; 3] = [
+        CardActions::Always,
+        CardActions::OnHover,
+        CardActions::Minimal,
+    ];
+
+    /// Get display name for the mode.
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            CardActions::Always => "Always",
+            CardActions::OnHover => "On hover",
+            CardActions::Minimal => "Copy only",
+        }
+    }
+}
+
+/// Syntax highlighting theme of code previews and the code editor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CodeTheme {
+    #[default]
+    Base16Mocha,
+    Base16Ocean,
+    Base16Eighties,
+    SolarizedDark,
+    InspiredGithub,
+}
+
+impl CodeTheme {
+    /// All themes for selection.
+    pub const ALL: [CodeTheme; 5] = [
+        CodeTheme::Base16Mocha,
+        CodeTheme::Base16Ocean,
+        CodeTheme::Base16Eighties,
+        CodeTheme::SolarizedDark,
+        CodeTheme::InspiredGithub,
+    ];
+
+    /// Get display name for the theme.
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            CodeTheme::Base16Mocha => "Mocha",
+            CodeTheme::Base16Ocean => "Ocean",
+            CodeTheme::Base16Eighties => "Eighties",
+            CodeTheme::SolarizedDark => "Solarized Dark",
+            CodeTheme::InspiredGithub => "GitHub (light)",
+        }
+    }
+
+    /// Get the highlighter theme.
+    pub fn highlighter(&self) -> iced::highlighter::Theme {
+        match self {
+            CodeTheme::Base16Mocha => iced::highlighter::Theme::Base16Mocha,
+            CodeTheme::Base16Ocean => iced::highlighter::Theme::Base16Ocean,
+            CodeTheme::Base16Eighties => iced::highlighter::Theme::Base16Eighties,
+            CodeTheme::SolarizedDark => iced::highlighter::Theme::SolarizedDark,
+            CodeTheme::InspiredGithub => iced::highlighter::Theme::InspiredGitHub,
+        }
+    }
+}
+
+impl fmt::Display for CodeTheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.display_name())
+    }
+}
+
+/// How the snippet list is laid out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ListLayout {
+    /// One card per snippet.
+    #[default]
+    Cards,
+    /// Compact table with sortable columns.
+    Table,
+}
+
+/// Order of the snippet list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SnippetSort {
+    /// Stored position (most recently added or reused first).
+    #[default]
+    Manual,
+    /// Most recently created first.
+    Created,
+    /// Most recently changed first.
+    Updated,
+    /// Most often copied first.
+    MostUsed,
+}
+
+impl SnippetSort {
+    /// All sort orders for selection.
+    pub const ALL: [SnippetSort; 4] = [
+        SnippetSort::Manual,
+        SnippetSort::Created,
+        SnippetSort::Updated,
+        SnippetSort::MostUsed,
+    ];
+
+    /// Get display name for the sort order.
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            SnippetSort::Manual => "Default order",
+            SnippetSort::Created => "Newest",
+            SnippetSort::Updated => "Recently updated",
+            SnippetSort::MostUsed => "Most used",
         }
+    }
+}
+
+impl std::fmt::Display for SnippetSort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.display_name())
+    }
+}
+
+/// How long snippets stay in the trash before being purged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TrashRetention {
+    Week,
+    #[default]
+    Month,
+    Quarter,
+    /// Keep trashed snippets until the trash is emptied.
+    Forever,
+}
+
+impl TrashRetention {
+    /// All retention periods for selection.
+    pub const ALL: [TrashRetention; 4] = [
+        TrashRetention::Week,
+        TrashRetention::Month,
+        TrashRetention::Quarter,
+        TrashRetention::Forever,
+    ];
+
+    /// Get display name for the retention period.
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            TrashRetention::Week => "7 days",
+            TrashRetention::Month => "30 days",
+            TrashRetention::Quarter => "90 days",
+            TrashRetention::Forever => "Forever",
+        }
+    }
+
+    /// Get the number of days trashed snippets are kept (None = forever).
+    pub fn days(&self) -> Option<u32> {
+        match self {
+            TrashRetention::Week => Some(7),
+            TrashRetention::Month => Some(30),
+            TrashRetention::Quarter => Some(90),
+            TrashRetention::Forever => None,
+        }
+    }
+}
+
+/// What goes between snippets copied together with "Copy combined".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CombineSeparator {
+    #[default]
+    BlankLine,
+    NewLine,
+    /// A Markdown horizontal rule between blank lines.
+    Rule,
+    Comma,
+}
+
+impl CombineSeparator {
+    /// All separators for selection.
+    pub const ALL: [CombineSeparator; 4] = [
+        CombineSeparator::BlankLine,
+        CombineSeparator::NewLine,
+        CombineSeparator::Rule,
+        CombineSeparator::Comma,
+    ];
 
-        let cmd_with_file = cmd_template.replace("{file}", file_path);
-        let parts: Vec<&str> = cmd_with_file.split_whitespace().collect();
+    /// Get display name for the separator.
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            CombineSeparator::BlankLine => "Blank line",
+            CombineSeparator::NewLine => "New line",
+            CombineSeparator::Rule => "---",
+            CombineSeparator::Comma => "Comma",
+        }
+    }
 
-        if parts.is_empty() {
-            return None;
+    /// Get the text inserted between snippets.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CombineSeparator::BlankLine => "\n\n",
+            CombineSeparator::NewLine => "\n",
+            CombineSeparator::Rule => "\n\n---\n\n",
+            CombineSeparator::Comma => ", ",
         }
+    }
+}
 
-        let program = parts[0].to_string();
-        let args: Vec<String> = parts[1..].iter().map(|s| s.to_string()).collect();
+/// What "Copy" puts on the clipboard for code, JSON and text snippets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CopyFormat {
+    /// The content as stored.
+    #[default]
+    Plain,
+    /// Code in a fenced block tagged with its language, text per [`MarkdownText`].
+    Markdown,
+}
+
+impl CopyFormat {
+    /// All formats for selection.
+    pub const ALL: [CopyFormat; 2] = [CopyFormat::Plain, CopyFormat::Markdown];
 
-        Some((program, args))
+    /// Get display name for the format.
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            CopyFormat::Plain => "Plain",
+            CopyFormat::Markdown => "Markdown",
+        }
+    }
+}
+
+/// How text snippets are written when copied as Markdown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum MarkdownText {
+    #[default]
+    Blockquote,
+    Plain,
+}
+
+impl MarkdownText {
+    /// All styles for selection.
+    pub const ALL: [MarkdownText; 2] = [MarkdownText::Blockquote, MarkdownText::Plain];
+
+    /// Get display name for the style.
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            MarkdownText::Blockquote => "Blockquote",
+            MarkdownText::Plain => "Plain text",
+        }
+    }
+}
+
+/// When a new color counts as a duplicate of a saved one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ColorDedupe {
+    /// Same RGB and same alpha.
+    #[default]
+    Exact,
+    /// Same RGB, whatever the alpha.
+    IgnoreAlpha,
+    /// Colors that look the same (ΔE below 2) with nearly the same alpha.
+    Perceptual,
+}
+
+impl ColorDedupe {
+    /// All policies for selection.
+    pub const ALL: [ColorDedupe; 3] = [
+        ColorDedupe::Exact,
+        ColorDedupe::IgnoreAlpha,
+        ColorDedupe::Perceptual,
+    ];
+
+    /// Largest ΔE still treated as the same color by `Perceptual`.
+    const MAX_DELTA_E: f32 = 2.0;
+
+    /// Largest alpha difference still treated as the same by `Perceptual`.
+    const MAX_ALPHA_DELTA: f32 = 0.05;
+
+    /// Get display name for the policy.
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ColorDedupe::Exact => "Exact match",
+            ColorDedupe::IgnoreAlpha => "Ignore alpha",
+            ColorDedupe::Perceptual => "Looks the same",
+        }
+    }
+
+    /// Check if two colors are duplicates under this policy.
+    pub fn is_duplicate(&self, a: &ColorData, b: &ColorData) -> bool {
+        let same_rgb = a.r == b.r && a.g == b.g && a.b == b.b;
+        match self {
+            // Half an 8-bit step absorbs float rounding of stored alpha
+            ColorDedupe::Exact => same_rgb && (a.a - b.a).abs() < 0.5 / 255.0,
+            ColorDedupe::IgnoreAlpha => same_rgb,
+            ColorDedupe::Perceptual => {
+                a.delta_e(b) < Self::MAX_DELTA_E && (a.a - b.a).abs() <= Self::MAX_ALPHA_DELTA
+            }
+        }
+    }
+}
+
+// === Palette Boards ===
+
+/// Modifiers that, held with a number key in any application, copy from a
+/// pinned palette board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum BoardHotkey {
+    #[default]
+    Off,
+    CtrlAlt,
+    CtrlShift,
+    AltShift,
+    SuperAlt,
+}
+
+impl BoardHotkey {
+    /// All hotkeys for selection.
+    pub const ALL: [BoardHotkey; 5] = [
+        BoardHotkey::Off,
+        BoardHotkey::CtrlAlt,
+        BoardHotkey::CtrlShift,
+        BoardHotkey::AltShift,
+        BoardHotkey::SuperAlt,
+    ];
+
+    /// Get the modifiers held with the number key, if the hotkey is on.
+    pub fn modifiers(&self) -> Option<Modifiers> {
+        let (ctrl, alt, shift, logo) = match self {
+            BoardHotkey::Off => return None,
+            BoardHotkey::CtrlAlt => (true, true, false, false),
+            BoardHotkey::CtrlShift => (true, false, true, false),
+            BoardHotkey::AltShift => (false, true, true, false),
+            BoardHotkey::SuperAlt => (false, true, false, true),
+        };
+        Some(Modifiers {
+            ctrl,
+            alt,
+            shift,
+            logo,
+        })
+    }
+}
+
+impl fmt::Display for BoardHotkey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.modifiers() {
+            Some(modifiers) => write!(f, "{}+1–9", modifiers),
+            None => f.write_str("Off"),
+        }
+    }
+}
+
+/// The global hotkey of a palette's board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BoardHotkeyBinding {
+    pub palette_id: i64,
+    pub hotkey: BoardHotkey,
+}
+
+// === Copy Templates ===
+
+/// A user-defined way to copy a color, shown as an extra copy button, e.g.
+/// `vec4({r_f}, {g_f}, {b_f}, {a})` or `{label}: {hex}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CopyTemplate {
+    /// Button text.
+    pub name: String,
+    pub template: String,
+}
+
+impl CopyTemplate {
+    /// Placeholders and what they are replaced with, for the settings hint.
+    pub const PLACEHOLDERS: [(&'static str, &'static str); 10] = [
+        ("{label}", "snippet label"),
+        ("{hex}", "#RRGGBB"),
+        ("{r} {g} {b}", "0–255"),
+        ("{r_f} {g_f} {b_f}", "0–1"),
+        ("{a}", "alpha, 0–1"),
+        ("{rgb}", "rgb()"),
+        ("{hsl}", "hsl()"),
+        ("{hsv}", "hsv()"),
+        ("{oklch}", "oklch()"),
+        ("{{ }}", "literal braces"),
+    ];
+
+    /// Fill in the placeholders for a color. Unknown placeholders are kept
+    /// as written, so a typo shows in the copied text.
+    pub fn render(&self, label: &str, color: &ColorData) -> String {
+        let unit = |channel: u8| format!("{:.3}", channel as f32 / 255.0);
+        let mut output = String::with_capacity(self.template.len());
+        let mut rest = self.template.as_str();
+        while let Some(start) = rest.find(['{', '}']) {
+            output.push_str(&rest[..start]);
+            rest = &rest[start..];
+            // Doubled braces are literal
+            if rest.starts_with("{{") || rest.starts_with("}}") {
+                output.push_str(&rest[..1]);
+                rest = &rest[2..];
+                continue;
+            }
+            let Some(end) = rest.find('}').filter(|_| rest.starts_with('{')) else {
+                output.push_str(&rest[..1]);
+                rest = &rest[1..];
+                continue;
+            };
+            let value = match &rest[1..end] {
+                "label" => label.to_string(),
+                "hex" => color.to_hex(),
+                "r" => color.r.to_string(),
+                "g" => color.g.to_string(),
+                "b" => color.b.to_string(),
+                "r_f" => unit(color.r),
+                "g_f" => unit(color.g),
+                "b_f" => unit(color.b),
+                "a" => format!("{:.3}", color.a),
+                "rgb" => color.to_rgb(),
+                "hsl" => color.to_hsl(),
+                "hsv" => color.to_hsv(),
+                "oklch" => color.to_oklch(),
+                _ => {
+                    output.push('{');
+                    rest = &rest[1..];
+                    continue;
+                }
+            };
+            output.push_str(&value);
+            rest = &rest[end + 1..];
+        }
+        output.push_str(rest);
+        output
+    }
+}
+
+// === Capture ===
+
+/// What to do when auto-captured text contains a secret.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SecretPolicy {
+    /// Store the text unchanged and show a warning.
+    Warn,
+    /// Replace the secrets with a placeholder before storing.
+    #[default]
+    Redact,
+    /// Do not store the text at all.
+    Refuse,
+}
+
+impl SecretPolicy {
+    /// All policies for selection.
+    pub const ALL: [SecretPolicy; 3] = [
+        SecretPolicy::Warn,
+        SecretPolicy::Redact,
+        SecretPolicy::Refuse,
+    ];
+
+    /// Get display name for the policy.
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            SecretPolicy::Warn => "Warn",
+            SecretPolicy::Redact => "Redact",
+            SecretPolicy::Refuse => "Don't capture",
+        }
+    }
+}
+
+// === Windows ===
+
+/// Appearance of the small always-on-top popup windows: pinned snippets,
+/// palette boards and the quick capture/search popup.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WindowAppearance {
+    /// Draw the window background translucent.
+    pub translucent: bool,
+    /// Blur whatever is behind a translucent window (macOS and Linux only).
+    pub blur: bool,
+    /// Hide the title bar and native border; the window is dragged by its background.
+    pub borderless: bool,
+    /// Background opacity of a translucent window.
+    pub opacity: f32,
+    /// Corner radius of a borderless window.
+    pub corner_radius: f32,
+}
+
+impl Default for WindowAppearance {
+    fn default() -> Self {
+        Self {
+            translucent: false,
+            blur: false,
+            borderless: false,
+            opacity: 0.85,
+            corner_radius: 10.0,
+        }
+    }
+}
+
+impl WindowAppearance {
+    /// Lowest opacity allowed, so window contents stay legible.
+    pub const MIN_OPACITY: f32 = 0.3;
+
+    /// Whether the native window must be transparent. Rounded corners of
+    /// a borderless window need it too, even when fully opaque.
+    pub fn transparent(&self) -> bool {
+        self.translucent || self.borderless
+    }
+
+    /// Get the alpha of the window background.
+    pub fn background_alpha(&self) -> f32 {
+        if self.translucent {
+            self.opacity.clamp(Self::MIN_OPACITY, 1.0)
+        } else {
+            1.0
+        }
+    }
+
+    /// Get the corner radius of the window background.
+    pub fn background_radius(&self) -> f32 {
+        if self.borderless {
+            self.corner_radius.max(0.0)
+        } else {
+            0.0
+        }
+    }
+}
+
+// === Split Pane ===
+
+/// Second snippet list shown beside the main one, with its own filters.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SplitPane {
+    /// Show the second pane.
+    pub enabled: bool,
+    /// Snippet kind shown in the second pane (all kinds when unset).
+    pub kind: Option<SnippetKind>,
+    /// Palette shown in the second pane (all palettes when unset).
+    pub palette: Option<i64>,
+}
+
+impl Default for SplitPane {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            kind: Some(SnippetKind::Code),
+            palette: None,
+        }
     }
 }
 
 /// Application configuration.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
     /// External editor settings.
     #[serde(default)]
     pub editor: EditorConfig,
 
+    /// Show what changed in the external editor before saving it.
+    #[serde(default)]
+    pub review_external_edits: bool,
+
+    /// Wrapping and Tab behavior of the code and text editors.
+    #[serde(default)]
+    pub editing: EditingConfig,
+
     /// Keyboard shortcuts.
     #[serde(default)]
     pub keyboard: KeyboardConfig,
@@ -310,9 +1102,186 @@ pub struct Config {
     /// Default color picker mode (HSL or OKLCH).
     #[serde(default)]
     pub default_picker_mode: PickerMode,
+
+    /// Visibility of snippet card action buttons.
+    #[serde(default)]
+    pub card_actions: CardActions,
+
+    /// Syntax highlighting theme of code previews and the code editor.
+    #[serde(default)]
+    pub code_theme: CodeTheme,
+
+    /// Snippet list layout (cards or table).
+    #[serde(default)]
+    pub list_layout: ListLayout,
+
+    /// Snippet list order.
+    #[serde(default)]
+    pub sort_order: SnippetSort,
+
+    /// Palette that pasted and auto-captured snippets are assigned to.
+    #[serde(default)]
+    pub capture_palette: Option<i64>,
+
+    /// Whether capturing into `capture_palette` is currently on (header toggle).
+    #[serde(default)]
+    pub capture_to_palette: bool,
+
+    /// How auto-captured text containing secrets is handled.
+    #[serde(default)]
+    pub secret_policy: SecretPolicy,
+
+    /// How long deleted snippets stay in the trash.
+    #[serde(default)]
+    pub trash_retention: TrashRetention,
+
+    /// When a saved color counts as a duplicate of a new one.
+    #[serde(default)]
+    pub color_dedupe: ColorDedupe,
+
+    /// Separator between snippets copied with "Copy combined".
+    #[serde(default)]
+    pub combine_separator: CombineSeparator,
+
+    /// What "Copy" puts on the clipboard for code, JSON and text snippets.
+    #[serde(default)]
+    pub copy_format: CopyFormat,
+
+    /// How text snippets are written when copied as Markdown.
+    #[serde(default)]
+    pub markdown_text: MarkdownText,
+
+    /// Show the favorites bar under the header.
+    #[serde(default = "default_show_favorites_bar")]
+    pub show_favorites_bar: bool,
+
+    /// Show the application a snippet was captured from on its card.
+    #[serde(default = "default_show_source_badge")]
+    pub show_source_badge: bool,
+
+    /// Appearance of pinned snippet windows.
+    #[serde(default)]
+    pub window_appearance: WindowAppearance,
+
+    /// Second snippet pane with independent filters.
+    #[serde(default)]
+    pub split_pane: SplitPane,
+
+    /// Folder the snippet database is kept in; the platform data folder when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub database_path: Option<PathBuf>,
+
+    /// Write a crash report (without snippet content) when Shard panics.
+    #[serde(default)]
+    pub crash_reports: bool,
+
+    /// Ask GitHub for the latest release on startup.
+    #[serde(default)]
+    pub check_for_updates: bool,
+
+    /// Expand snippet abbreviations typed in any application.
+    #[serde(default)]
+    pub text_expansion: bool,
+
+    /// Extra ways to copy colors, shown on color cards and in the picker.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub copy_templates: Vec<CopyTemplate>,
+
+    /// Colors last confirmed in the color picker, newest first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub recent_colors: Vec<ColorData>,
+
+    /// Commands running code snippets, per language.
+    #[serde(default = "default_run_commands")]
+    pub run_commands: Vec<RunCommand>,
+
+    /// Global hotkeys of palette boards.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub board_hotkeys: Vec<BoardHotkeyBinding>,
+}
+
+fn default_show_favorites_bar() -> bool {
+    true
+}
+
+fn default_show_source_badge() -> bool {
+    true
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            editor: EditorConfig::default(),
+            review_external_edits: false,
+            editing: EditingConfig::default(),
+            keyboard: KeyboardConfig::default(),
+            default_picker_mode: PickerMode::default(),
+            card_actions: CardActions::default(),
+            code_theme: CodeTheme::default(),
+            list_layout: ListLayout::default(),
+            sort_order: SnippetSort::default(),
+            capture_palette: None,
+            capture_to_palette: false,
+            secret_policy: SecretPolicy::default(),
+            trash_retention: TrashRetention::default(),
+            color_dedupe: ColorDedupe::default(),
+            combine_separator: CombineSeparator::default(),
+            copy_format: CopyFormat::default(),
+            markdown_text: MarkdownText::default(),
+            show_favorites_bar: default_show_favorites_bar(),
+            show_source_badge: default_show_source_badge(),
+            window_appearance: WindowAppearance::default(),
+            split_pane: SplitPane::default(),
+            database_path: None,
+            crash_reports: false,
+            check_for_updates: false,
+            text_expansion: false,
+            copy_templates: Vec::new(),
+            recent_colors: Vec::new(),
+            run_commands: default_run_commands(),
+            board_hotkeys: Vec::new(),
+        }
+    }
 }
 
 impl Config {
+    /// Colors kept in `recent_colors`.
+    pub const MAX_RECENT_COLORS: usize = 10;
+
+    /// Find the command running code snippets of `language`.
+    pub fn run_command(&self, language: &str) -> Option<&RunCommand> {
+        self.run_commands
+            .iter()
+            .find(|command| !command.command.trim().is_empty() && command.runs(language))
+    }
+
+    /// Put a confirmed picker color first in `recent_colors`, dropping an
+    /// earlier use of the same color and the oldest past the limit.
+    pub fn remember_color(&mut self, color: ColorData) {
+        self.recent_colors.retain(|recent| *recent != color);
+        self.recent_colors.insert(0, color);
+        self.recent_colors.truncate(Self::MAX_RECENT_COLORS);
+    }
+
+    /// Get the global hotkey of a palette's board.
+    pub fn board_hotkey(&self, palette_id: i64) -> BoardHotkey {
+        self.board_hotkeys
+            .iter()
+            .find(|binding| binding.palette_id == palette_id)
+            .map_or(BoardHotkey::Off, |binding| binding.hotkey)
+    }
+
+    /// Set the global hotkey of a palette's board, taking it from any other
+    /// board that had it.
+    pub fn set_board_hotkey(&mut self, palette_id: i64, hotkey: BoardHotkey) {
+        self.board_hotkeys
+            .retain(|binding| binding.palette_id != palette_id && binding.hotkey != hotkey);
+        if hotkey != BoardHotkey::Off {
+            self.board_hotkeys
+                .push(BoardHotkeyBinding { palette_id, hotkey });
+        }
+    }
+
     /// Get the config file path.
     pub fn config_path() -> Option<PathBuf> {
         ProjectDirs::from("", "", "shard").map(|dirs| dirs.config_dir().join("config.toml"))
@@ -387,6 +1356,57 @@ mod tests {
         assert_eq!(args, vec!["-w", "/tmp/test.txt"]);
     }
 
+    #[test]
+    fn test_editing_indent() {
+        let spaces = EditingConfig::default();
+        assert_eq!(spaces.indent(0), "    ");
+        assert_eq!(spaces.indent(6), "  ");
+        let tabs = EditingConfig {
+            insert_spaces: false,
+            ..spaces
+        };
+        assert_eq!(tabs.indent(3), "\t");
+
+        // Missing fields keep their defaults
+        let config: Config = toml::from_str("[editing]\ntab_width = 2").unwrap();
+        assert_eq!(config.editing.tab_width, 2);
+        assert!(config.editing.word_wrap && config.editing.insert_spaces);
+        // Code keeps its line numbers unless wrapping is turned on for it
+        assert!(!config.editing.code_word_wrap);
+    }
+
+    #[test]
+    fn test_command_path_with_spaces() {
+        let config = EditorConfig {
+            preset: EditorPreset::Custom,
+            custom_command: "subl  -w {file}".to_string(),
+        };
+
+        let (prog, args) = config.build_command("/tmp/my notes.txt").unwrap();
+        assert_eq!(prog, "subl");
+        assert_eq!(args, vec!["-w", "/tmp/my notes.txt"]);
+    }
+
+    #[test]
+    fn test_run_commands() {
+        let config = Config::default();
+        let python = config.run_command("Python").unwrap();
+        assert_eq!(
+            python.build_command("/tmp/a.py"),
+            Some(("python3".to_string(), vec!["/tmp/a.py".to_string()]))
+        );
+        // Aliases share a command
+        assert_eq!(config.run_command("bash").unwrap().command, "sh {file}");
+        assert!(config.run_command("rust").is_none());
+        assert!(config.run_command("plain").is_none());
+
+        // Removing every command is kept
+        let parsed: Config = toml::from_str("run_commands = []").unwrap();
+        assert!(parsed.run_commands.is_empty());
+        let parsed: Config = toml::from_str("").unwrap();
+        assert_eq!(parsed.run_commands, default_run_commands());
+    }
+
     #[test]
     fn test_config_serialization() {
         let config = Config::default();
@@ -394,4 +1414,337 @@ mod tests {
         let parsed: Config = toml::from_str(&toml_str).unwrap();
         assert_eq!(parsed.editor.preset, EditorPreset::Helix);
     }
+
+    #[test]
+    fn test_keyboard_config_missing_toggle_pin_uses_default() {
+        let toml_str = r#"
+            [paste]
+            key = "v"
+            [new_color]
+            key = "n"
+            [escape]
+            key = "Escape"
+            [delete]
+            key = "Delete"
+        "#;
+        let keyboard: KeyboardConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(keyboard.toggle_pin, default_toggle_pin_shortcut());
+        assert_eq!(keyboard.undo, default_undo_shortcut());
+        assert_eq!(keyboard.assign_palette, default_assign_palette_shortcut());
+    }
+
+    #[test]
+    fn test_shortcut_without_logo_loads() {
+        let shortcut: Shortcut =
+            toml::from_str("key = \"v\"\n[modifiers]\nctrl = true\nalt = false\nshift = false")
+                .unwrap();
+        assert_eq!(shortcut, Shortcut::char_key('v', Modifiers::ctrl()));
+    }
+
+    #[test]
+    fn test_shortcut_from_key_press() {
+        use iced::keyboard::{key::Named, Key, Location, Modifiers as Held};
+
+        let shortcut = Shortcut::from_key_press(
+            &Key::Character("k".into()),
+            Location::Standard,
+            Held::COMMAND,
+        )
+        .unwrap();
+        assert_eq!(shortcut, Shortcut::char_key('k', Modifiers::ctrl()));
+        assert!(shortcut.matches(
+            &Key::Character("K".into()),
+            Location::Standard,
+            Held::COMMAND
+        ));
+
+        // Keypad keys are told apart from the main keys
+        let keypad =
+            Shortcut::from_key_press(&Key::Character("7".into()), Location::Numpad, Held::ALT)
+                .unwrap();
+        assert_eq!(keypad.key, "Numpad7");
+        assert!(!keypad.matches(&Key::Character("7".into()), Location::Standard, Held::ALT));
+
+        // Function keys work without modifiers
+        let f5 =
+            Shortcut::from_key_press(&Key::Named(Named::F5), Location::Standard, Held::empty());
+        assert_eq!(f5.unwrap().to_string(), "F5");
+
+        // Modifiers alone and bare typing keys are rejected with a reason
+        let held = Shortcut::from_key_press(
+            &Key::Named(Named::Shift),
+            Location::Left,
+            Held::COMMAND | Held::SHIFT,
+        );
+        assert_eq!(held.unwrap_err(), "Ctrl+Shift+… now press a key");
+        assert!(Shortcut::from_key_press(
+            &Key::Character("a".into()),
+            Location::Standard,
+            Held::SHIFT
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_card_actions_config() {
+        let parsed: Config = toml::from_str("").unwrap();
+        assert_eq!(parsed.card_actions, CardActions::Always);
+
+        let parsed: Config = toml::from_str(r#"card_actions = "on_hover""#).unwrap();
+        assert_eq!(parsed.card_actions, CardActions::OnHover);
+    }
+
+    #[test]
+    fn test_board_hotkeys() {
+        let mut config = Config::default();
+        assert_eq!(config.board_hotkey(1), BoardHotkey::Off);
+
+        config.set_board_hotkey(1, BoardHotkey::CtrlAlt);
+        config.set_board_hotkey(2, BoardHotkey::AltShift);
+        assert_eq!(config.board_hotkey(1), BoardHotkey::CtrlAlt);
+
+        // A hotkey belongs to one board at a time
+        config.set_board_hotkey(2, BoardHotkey::CtrlAlt);
+        assert_eq!(config.board_hotkey(1), BoardHotkey::Off);
+        assert_eq!(config.board_hotkey(2), BoardHotkey::CtrlAlt);
+
+        let saved = toml::to_string(&config).unwrap();
+        let parsed: Config = toml::from_str(&saved).unwrap();
+        assert_eq!(parsed.board_hotkeys, config.board_hotkeys);
+
+        config.set_board_hotkey(2, BoardHotkey::Off);
+        assert!(config.board_hotkeys.is_empty());
+        assert_eq!(BoardHotkey::SuperAlt.to_string(), "Alt+Super+1–9");
+    }
+
+    #[test]
+    fn test_capture_palette_config() {
+        let parsed: Config = toml::from_str("").unwrap();
+        assert_eq!(parsed.capture_palette, None);
+        assert!(!parsed.capture_to_palette);
+
+        let parsed: Config =
+            toml::from_str("capture_palette = 3\ncapture_to_palette = true").unwrap();
+        assert_eq!(parsed.capture_palette, Some(3));
+        assert!(parsed.capture_to_palette);
+    }
+
+    #[test]
+    fn test_sort_order_config() {
+        let parsed: Config = toml::from_str("").unwrap();
+        assert_eq!(parsed.sort_order, SnippetSort::Manual);
+
+        let parsed: Config = toml::from_str(r#"sort_order = "most_used""#).unwrap();
+        assert_eq!(parsed.sort_order, SnippetSort::MostUsed);
+    }
+
+    #[test]
+    fn test_secret_policy_config() {
+        let parsed: Config = toml::from_str("").unwrap();
+        assert_eq!(parsed.secret_policy, SecretPolicy::Redact);
+
+        let parsed: Config = toml::from_str(r#"secret_policy = "refuse""#).unwrap();
+        assert_eq!(parsed.secret_policy, SecretPolicy::Refuse);
+    }
+
+    #[test]
+    fn test_trash_retention_config() {
+        let parsed: Config = toml::from_str("").unwrap();
+        assert_eq!(parsed.trash_retention.days(), Some(30));
+
+        let parsed: Config = toml::from_str(r#"trash_retention = "forever""#).unwrap();
+        assert_eq!(parsed.trash_retention.days(), None);
+    }
+
+    #[test]
+    fn test_color_dedupe_policies() {
+        let parsed: Config = toml::from_str("").unwrap();
+        assert_eq!(parsed.color_dedupe, ColorDedupe::Exact);
+        let parsed: Config = toml::from_str(r#"color_dedupe = "ignore_alpha""#).unwrap();
+        assert_eq!(parsed.color_dedupe, ColorDedupe::IgnoreAlpha);
+
+        let red = ColorData::new(255, 0, 0, 1.0);
+        let translucent = ColorData::new(255, 0, 0, 128.0 / 255.0);
+        let nearly_red = ColorData::new(254, 1, 0, 1.0);
+
+        assert!(ColorDedupe::Exact.is_duplicate(&red, &red.clone()));
+        assert!(!ColorDedupe::Exact.is_duplicate(&red, &translucent));
+        assert!(!ColorDedupe::Exact.is_duplicate(&red, &nearly_red));
+        assert!(ColorDedupe::IgnoreAlpha.is_duplicate(&red, &translucent));
+        assert!(!ColorDedupe::IgnoreAlpha.is_duplicate(&red, &nearly_red));
+        assert!(ColorDedupe::Perceptual.is_duplicate(&red, &nearly_red));
+        assert!(!ColorDedupe::Perceptual.is_duplicate(&red, &translucent));
+    }
+
+    #[test]
+    fn test_favorites_bar_shown_by_default() {
+        assert!(Config::default().show_favorites_bar);
+        let parsed: Config = toml::from_str("").unwrap();
+        assert!(parsed.show_favorites_bar);
+
+        let parsed: Config = toml::from_str("show_favorites_bar = false").unwrap();
+        assert!(!parsed.show_favorites_bar);
+    }
+
+    #[test]
+    fn test_combine_separator_config() {
+        assert_eq!(Config::default().combine_separator.as_str(), "\n\n");
+        let parsed: Config = toml::from_str("combine_separator = \"rule\"").unwrap();
+        assert_eq!(parsed.combine_separator, CombineSeparator::Rule);
+    }
+
+    #[test]
+    fn test_copy_format_config() {
+        let parsed: Config = toml::from_str("").unwrap();
+        assert_eq!(parsed.copy_format, CopyFormat::Plain);
+        assert_eq!(parsed.markdown_text, MarkdownText::Blockquote);
+
+        let parsed: Config =
+            toml::from_str("copy_format = \"markdown\"\nmarkdown_text = \"plain\"").unwrap();
+        assert_eq!(parsed.copy_format, CopyFormat::Markdown);
+        assert_eq!(parsed.markdown_text, MarkdownText::Plain);
+    }
+
+    #[test]
+    fn test_source_badge_shown_by_default() {
+        assert!(Config::default().show_source_badge);
+        let parsed: Config = toml::from_str("show_source_badge = false").unwrap();
+        assert!(!parsed.show_source_badge);
+    }
+
+    #[test]
+    fn test_update_check_is_opt_in() {
+        assert!(!Config::default().check_for_updates);
+        let parsed: Config = toml::from_str("").unwrap();
+        assert!(!parsed.check_for_updates);
+    }
+
+    #[test]
+    fn test_crash_reports_are_opt_in() {
+        assert!(!Config::default().crash_reports);
+        let parsed: Config = toml::from_str("").unwrap();
+        assert!(!parsed.crash_reports);
+
+        let parsed: Config = toml::from_str("crash_reports = true").unwrap();
+        assert!(parsed.crash_reports);
+    }
+
+    #[test]
+    fn test_window_appearance_config() {
+        let parsed: Config = toml::from_str("").unwrap();
+        assert_eq!(parsed.window_appearance, WindowAppearance::default());
+        assert!(!parsed.window_appearance.transparent());
+        assert_eq!(parsed.window_appearance.background_alpha(), 1.0);
+
+        let parsed: Config = toml::from_str(
+            "[window_appearance]\ntranslucent = true\nborderless = true\nopacity = 0.1",
+        )
+        .unwrap();
+        let appearance = parsed.window_appearance;
+        assert!(appearance.transparent());
+        assert!(!appearance.blur);
+        // Opacity is clamped so the window never disappears entirely
+        assert_eq!(appearance.background_alpha(), WindowAppearance::MIN_OPACITY);
+        assert_eq!(appearance.background_radius(), 10.0);
+    }
+
+    #[test]
+    fn test_copy_templates_config() {
+        let parsed: Config = toml::from_str("").unwrap();
+        assert!(parsed.copy_templates.is_empty());
+        assert!(!toml::to_string(&parsed).unwrap().contains("copy_templates"));
+
+        let parsed: Config = toml::from_str(
+            "[[copy_templates]]\nname = \"GLSL\"\ntemplate = \"vec4({r_f}, {g_f}, {b_f}, {a})\"",
+        )
+        .unwrap();
+        let color = ColorData::new(255, 0, 51, 0.5);
+        assert_eq!(
+            parsed.copy_templates[0].render("Brand", &color),
+            "vec4(1.000, 0.000, 0.200, 0.500)"
+        );
+    }
+
+    #[test]
+    fn test_copy_template_placeholders() {
+        let template = |template: &str| CopyTemplate {
+            name: "Test".to_string(),
+            template: template.to_string(),
+        };
+        let color = ColorData::new(59, 130, 246, 1.0);
+        assert_eq!(
+            template("{label}: {hex};").render("Brand Blue", &color),
+            "Brand Blue: #3B82F6;"
+        );
+        assert_eq!(
+            template("({r}, {g}, {b}) {rgb}").render("", &color),
+            "(59, 130, 246) rgb(59, 130, 246)"
+        );
+        // Escaped, unknown and unclosed braces are kept
+        assert_eq!(
+            template("{{hex}} {nope} {hex").render("", &color),
+            "{hex} {nope} {hex"
+        );
+        assert_eq!(template("{a {hex}").render("", &color), "{a #3B82F6");
+        assert_eq!(template("a } b").render("", &color), "a } b");
+    }
+
+    #[test]
+    fn test_remember_recent_colors() {
+        let mut config = Config::default();
+        assert!(!toml::to_string(&config).unwrap().contains("recent_colors"));
+
+        for value in 0..12u8 {
+            config.remember_color(ColorData::new(value, 0, 0, 1.0));
+        }
+        assert_eq!(config.recent_colors.len(), Config::MAX_RECENT_COLORS);
+        assert_eq!(config.recent_colors[0], ColorData::new(11, 0, 0, 1.0));
+
+        // Reusing a color moves it to the front instead of repeating it
+        config.remember_color(ColorData::new(5, 0, 0, 1.0));
+        assert_eq!(config.recent_colors.len(), Config::MAX_RECENT_COLORS);
+        assert_eq!(config.recent_colors[0], ColorData::new(5, 0, 0, 1.0));
+        assert_eq!(config.recent_colors[1], ColorData::new(11, 0, 0, 1.0));
+
+        let saved = toml::to_string(&config).unwrap();
+        let parsed: Config = toml::from_str(&saved).unwrap();
+        assert_eq!(parsed.recent_colors, config.recent_colors);
+    }
+
+    #[test]
+    fn test_code_theme_config() {
+        let parsed: Config = toml::from_str("").unwrap();
+        assert_eq!(parsed.code_theme, CodeTheme::Base16Mocha);
+
+        let parsed: Config = toml::from_str("code_theme = \"solarized_dark\"").unwrap();
+        assert_eq!(
+            parsed.code_theme.highlighter(),
+            iced::highlighter::Theme::SolarizedDark
+        );
+    }
+
+    #[test]
+    fn test_split_pane_config() {
+        let parsed: Config = toml::from_str("").unwrap();
+        assert!(!parsed.split_pane.enabled);
+        assert_eq!(parsed.split_pane.kind, Some(SnippetKind::Code));
+
+        let parsed: Config =
+            toml::from_str("[split_pane]\nenabled = true\nkind = \"color\"\npalette = 3").unwrap();
+        assert!(parsed.split_pane.enabled);
+        assert_eq!(parsed.split_pane.kind, Some(SnippetKind::Color));
+        assert_eq!(parsed.split_pane.palette, Some(3));
+    }
+
+    #[test]
+    fn test_database_path_config() {
+        let parsed: Config = toml::from_str("").unwrap();
+        assert_eq!(parsed.database_path, None);
+
+        let parsed: Config = toml::from_str("database_path = \"/mnt/sync/shard\"").unwrap();
+        assert_eq!(parsed.database_path, Some(PathBuf::from("/mnt/sync/shard")));
+
+        let saved = toml::to_string(&Config::default()).unwrap();
+        assert!(!saved.contains("database_path"));
+    }
 }
@@ -0,0 +1,216 @@
+//! Opt-in crash reports.
+//!
+//! A panic hook writes the panic, a backtrace, the app version and the names
+//! of the last handled messages to a text file in the data folder. Message
+//! payloads are never recorded, so reports hold no snippet content.
+
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, PoisonError};
+
+/// Number of recent messages kept for a report.
+const LOG_LINES: usize = 50;
+
+/// Extension of reports not yet offered to the user.
+const NEW_REPORT_EXTENSION: &str = "txt";
+
+/// Extension of reports the user has seen.
+const SEEN_REPORT_EXTENSION: &str = "seen.txt";
+
+/// Whether a panic writes a report (the crash report setting).
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Names of the most recently handled messages, oldest first.
+static LOG: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+/// Turn writing crash reports on or off.
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Install the panic hook; it only writes reports while enabled.
+pub fn install_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        if ENABLED.load(Ordering::Relaxed) {
+            let _ = write_report(info);
+        }
+        default_hook(info);
+    }));
+}
+
+/// Record the name of a handled message, without its payload.
+pub fn record(message: &impl std::fmt::Debug) {
+    let line = format!("{} {}", crate::snippet::unix_now(), variant_name(message));
+    push_capped(
+        &mut LOG.lock().unwrap_or_else(PoisonError::into_inner),
+        line,
+    );
+}
+
+/// Get the variant name of an enum value from its `Debug` output.
+fn variant_name(value: &impl std::fmt::Debug) -> String {
+    let mut name = VariantName(String::new());
+    // Stops with an error once the variant name is written
+    let _ = write!(name, "{:?}", value);
+    name.0
+}
+
+/// Append a line to the log, dropping the oldest beyond `LOG_LINES`.
+fn push_capped(log: &mut VecDeque<String>, line: String) {
+    if log.len() == LOG_LINES {
+        log.pop_front();
+    }
+    log.push_back(line);
+}
+
+/// Collects formatted text up to the first `(`, `{` or space, so only the
+/// variant name of a message is kept and its payload is never formatted.
+struct VariantName(String);
+
+impl std::fmt::Write for VariantName {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        match s.find(['(', '{', ' ']) {
+            Some(end) => {
+                self.0.push_str(&s[..end]);
+                Err(std::fmt::Error)
+            }
+            None => {
+                self.0.push_str(s);
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Get the folder crash reports are written to.
+fn report_dir() -> Option<PathBuf> {
+    directories::ProjectDirs::from("", "", "Shard").map(|dirs| dirs.data_dir().join("crashes"))
+}
+
+/// Write a report for a panic, returning its path.
+fn write_report(info: &std::panic::PanicHookInfo) -> std::io::Result<PathBuf> {
+    let dir = report_dir().ok_or(std::io::ErrorKind::NotFound)?;
+    std::fs::create_dir_all(&dir)?;
+
+    let timestamp = crate::snippet::unix_now();
+    let path = dir.join(format!("crash-{:012}.{}", timestamp, NEW_REPORT_EXTENSION));
+    std::fs::write(&path, build_report(info, timestamp))?;
+    Ok(path)
+}
+
+/// Build the text of a crash report.
+fn build_report(info: &std::panic::PanicHookInfo, timestamp: i64) -> String {
+    let message = info
+        .payload()
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| info.payload().downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "(no message)".to_string());
+    let location = info
+        .location()
+        .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column()))
+        .unwrap_or_else(|| "unknown".to_string());
+    let backtrace = std::backtrace::Backtrace::force_capture();
+    let log = LOG.lock().unwrap_or_else(PoisonError::into_inner);
+
+    let mut report = String::new();
+    let _ = writeln!(report, "Shard {} crash report", env!("CARGO_PKG_VERSION"));
+    let _ = writeln!(
+        report,
+        "Time: {}\nPlatform: {} {}",
+        timestamp,
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    );
+    let _ = writeln!(
+        report,
+        "\nPanic: {}\nAt: {}",
+        redact_quoted(&message),
+        location
+    );
+    let _ = writeln!(report, "\nLast {} messages:", log.len());
+    for line in log.iter() {
+        let _ = writeln!(report, "  {}", line);
+    }
+    let _ = writeln!(report, "\nBacktrace:\n{}", backtrace);
+    report
+}
+
+/// Blank out text quoted in backticks, where panics quote the values
+/// (possibly snippet content) they failed on.
+fn redact_quoted(message: &str) -> String {
+    message
+        .split('`')
+        .enumerate()
+        .map(|(i, part)| if i % 2 == 1 { "…" } else { part })
+        .collect::<Vec<_>>()
+        .join("`")
+}
+
+/// Get the newest report not yet offered to the user.
+pub fn pending_report() -> Option<PathBuf> {
+    let entries = std::fs::read_dir(report_dir()?).ok()?;
+    entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.file_name().is_some_and(|name| {
+                let name = name.to_string_lossy();
+                name.ends_with(NEW_REPORT_EXTENSION) && !name.ends_with(SEEN_REPORT_EXTENSION)
+            })
+        })
+        .max()
+}
+
+/// Mark a report as seen so it is not offered again, returning its new path.
+pub fn mark_seen(path: &std::path::Path) -> PathBuf {
+    let seen = path.with_extension(SEEN_REPORT_EXTENSION);
+    match std::fs::rename(path, &seen) {
+        Ok(()) => seen,
+        Err(_) => path.to_path_buf(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    enum Sample {
+        Plain,
+        Tuple(String),
+        Struct { text: String },
+    }
+
+    #[test]
+    fn test_variant_name_skips_payload() {
+        assert_eq!(variant_name(&Sample::Plain), "Plain");
+        assert_eq!(variant_name(&Sample::Tuple("secret".to_string())), "Tuple");
+        let value = Sample::Struct {
+            text: "secret".to_string(),
+        };
+        assert_eq!(variant_name(&value), "Struct");
+    }
+
+    #[test]
+    fn test_log_is_capped() {
+        let mut log = VecDeque::new();
+        for i in 0..LOG_LINES + 10 {
+            push_capped(&mut log, i.to_string());
+        }
+        assert_eq!(log.len(), LOG_LINES);
+        assert_eq!(log.front().map(String::as_str), Some("10"));
+    }
+
+    #[test]
+    fn test_redact_quoted() {
+        assert_eq!(
+            redact_quoted("byte index 3 is not a char boundary of `héllo`"),
+            "byte index 3 is not a char boundary of `…`"
+        );
+        assert_eq!(redact_quoted("no quotes"), "no quotes");
+    }
+}
@@ -0,0 +1,3662 @@
+//! SQLite database module for persisting snippets.
+//!
+//! Supports four snippet types: Color, Code, Text, and File.
+//! All operations share one long-lived connection behind a mutex. It is opened
+//! (and migrated) on first use. The app runs operations on the `worker` thread,
+//! so they never block the UI.
+
+pub mod worker;
+
+use crate::config::ColorDedupe;
+use crate::snippet::{
+    code_snippets_file, parse_json, split_labels, stored_file_name, unix_now, CodeData, ColorData,
+    ColorSetData, FileData, GradientData, JsonData, PaletteQuery, Snippet, SnippetContent,
+    SnippetKind, TextData,
+};
+use rusqlite::{params, Connection, Result as SqlResult};
+use std::collections::HashMap;
+use std::io::Read;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+
+/// Schema version for migrations.
+const SCHEMA_VERSION: i32 = 25;
+
+/// How long a statement waits for another connection's lock before failing.
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Number of automatic backups kept before the oldest are removed.
+const MAX_BACKUPS: usize = 10;
+
+/// Backup taken before this session's schema migration, if one ran.
+static MIGRATION_BACKUP: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// Columns selected when loading snippets, in the order `snippet_from_row` expects.
+/// Timestamps are selected as Unix seconds.
+const SNIPPET_COLUMNS: &str =
+    "id, kind, label, position, r, g, b, a, code, language, text_content, inbox, \
+     CAST(strftime('%s', created_at) AS INTEGER), \
+     CAST(strftime('%s', COALESCE(updated_at, created_at)) AS INTEGER), \
+     CAST(strftime('%s', deleted_at) AS INTEGER), \
+     CAST(strftime('%s', favorite_at) AS INTEGER), copy_count, \
+     CAST(strftime('%s', last_copied_at) AS INTEGER), icon, revision, file_path, file_hash, \
+     attachment_name, attachment_path, attachment_size, color_set, \
+     rich_html, source_app, abbreviation, description, gradient";
+
+/// File name of the database inside its folder.
+const DATABASE_FILE_NAME: &str = "shard.db";
+
+/// Folder chosen for the database in place of the platform data folder.
+static DATABASE_DIR: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// Get the path to the database file.
+pub fn get_database_path() -> Result<PathBuf, String> {
+    if let Some(dir) = DATABASE_DIR
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .as_ref()
+    {
+        return Ok(dir.join(DATABASE_FILE_NAME));
+    }
+    default_database_path()
+}
+
+/// Get the folder file snippets are copied into, in the platform data folder.
+/// It stays there when the database is moved to another folder.
+pub fn attachments_dir() -> Result<PathBuf, String> {
+    let database_path = default_database_path()?;
+    Ok(database_path
+        .parent()
+        .unwrap_or_else(|| Path::new(""))
+        .join("files"))
+}
+
+/// Get the database path in the platform data folder.
+pub fn default_database_path() -> Result<PathBuf, String> {
+    if let Some(proj_dirs) = directories::ProjectDirs::from("", "", "Shard") {
+        let data_dir = proj_dirs.data_dir();
+        Ok(data_dir.join(DATABASE_FILE_NAME))
+    } else {
+        // Fallback to current directory
+        Ok(PathBuf::from(DATABASE_FILE_NAME))
+    }
+}
+
+/// Keep the database in `dir`, or the platform data folder when `None`.
+/// Set at startup from the config; the next operation opens the file there.
+pub fn set_database_dir(dir: Option<PathBuf>) {
+    close_connection();
+    *DATABASE_DIR.lock().unwrap_or_else(PoisonError::into_inner) = dir;
+}
+
+/// Move the database into `dir` (the platform data folder when `None`) and
+/// use it from then on. A Shard database already in that folder (e.g. synced
+/// from another machine) is used as is, leaving the current file in place.
+/// Returns whether the current database was moved.
+pub fn relocate_database(dir: Option<PathBuf>) -> Result<bool, String> {
+    let from = get_database_path()?;
+    let to = match &dir {
+        Some(dir) => dir.join(DATABASE_FILE_NAME),
+        None => default_database_path()?,
+    };
+    if to == from {
+        return Ok(false);
+    }
+
+    let moved = !to.exists() && from.exists();
+    if moved {
+        if let Some(parent) = to.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("IO error: {}", e))?;
+        }
+        // Fold the WAL into the main file, then release it
+        {
+            let conn = connection()?;
+            checkpoint(&conn).map_err(|e| format!("Move error: {}", e))?;
+        }
+        close_connection();
+        move_file(&from, &to)?;
+        remove_wal_files(&from);
+    }
+
+    set_database_dir(dir);
+    Ok(moved)
+}
+
+/// Move a file, copying it when the target is on another file system.
+fn move_file(from: &Path, to: &Path) -> Result<(), String> {
+    if std::fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    std::fs::copy(from, to).map_err(|e| format!("Move error: {}", e))?;
+    std::fs::remove_file(from).map_err(|e| format!("Move error: {}", e))
+}
+
+/// Remove the WAL and shared-memory files next to a database file.
+fn remove_wal_files(db_path: &Path) {
+    for suffix in ["-wal", "-shm"] {
+        let mut sidecar = db_path.as_os_str().to_owned();
+        sidecar.push(suffix);
+        let _ = std::fs::remove_file(sidecar);
+    }
+}
+
+/// The shared connection, opened on first use.
+static CONNECTION: Mutex<Option<Connection>> = Mutex::new(None);
+
+/// Exclusive access to the shared connection, held until dropped.
+struct ConnectionGuard(MutexGuard<'static, Option<Connection>>);
+
+impl Deref for ConnectionGuard {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        self.0
+            .as_ref()
+            .expect("connection is opened before the guard is handed out")
+    }
+}
+
+/// Lock the shared connection, opening the database first if needed.
+/// Callers must not call another `db` function while holding it.
+fn connection() -> Result<ConnectionGuard, String> {
+    // A panic mid-operation leaves the connection itself usable
+    let mut guard = CONNECTION.lock().unwrap_or_else(PoisonError::into_inner);
+    if guard.is_none() {
+        *guard = Some(open_connection()?);
+    }
+    Ok(ConnectionGuard(guard))
+}
+
+/// Close the shared connection, so the next operation reopens the database
+/// file. Needed whenever the file is replaced.
+fn close_connection() {
+    *CONNECTION.lock().unwrap_or_else(PoisonError::into_inner) = None;
+}
+
+/// Open a connection to the current database file.
+fn open_connection() -> Result<Connection, String> {
+    open_database(&get_database_path()?)
+}
+
+/// Open a connection to the database file at `path`, creating it and
+/// bringing its schema up to date as needed.
+fn open_database(path: &Path) -> Result<Connection, String> {
+    // Ensure parent directory exists
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("IO error: {}", e))?;
+    }
+
+    let conn = Connection::open(path).map_err(|e| format!("Database error: {}", e))?;
+    apply_key(&conn)?;
+
+    // Run migrations
+    run_migrations(&conn)?;
+    configure_connection(&conn)?;
+
+    Ok(conn)
+}
+
+/// Apply the per-connection settings every connection relies on.
+/// Foreign keys are enabled after migrations, so rebuilding a table during a
+/// migration never cascades into its children.
+fn configure_connection(conn: &Connection) -> Result<(), String> {
+    conn.busy_timeout(BUSY_TIMEOUT)
+        .map_err(|e| format!("Database error: {}", e))?;
+    // Readers no longer block the writer (in-memory databases stay in "memory" mode)
+    conn.pragma_update_and_check(None, "journal_mode", "WAL", |row| row.get::<_, String>(0))
+        .map_err(|e| format!("Journal mode error: {}", e))?;
+    conn.pragma_update(None, "foreign_keys", true)
+        .map_err(|e| format!("Foreign key error: {}", e))?;
+    Ok(())
+}
+
+/// Run database migrations.
+fn run_migrations(conn: &Connection) -> Result<(), String> {
+    // Create meta table for tracking schema version
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS meta (
+            key TEXT PRIMARY KEY,
+            value TEXT NOT NULL
+        )",
+        [],
+    )
+    .map_err(|e| format!("Meta table error: {}", e))?;
+
+    // Get current schema version
+    let current_version: i32 = conn
+        .query_row(
+            "SELECT value FROM meta WHERE key = 'schema_version'",
+            [],
+            |row| {
+                let v: String = row.get(0)?;
+                Ok(v.parse().unwrap_or(0))
+            },
+        )
+        .unwrap_or(0);
+
+    // Snapshot existing databases before changing their schema (once, so a retry
+    // after a failed migration does not back up the half-migrated file)
+    if current_version > 0 && current_version < SCHEMA_VERSION && migration_backup().is_none() {
+        let backup = snapshot_database(conn, "migration")?;
+        if let Ok(mut migration_backup) = MIGRATION_BACKUP.lock() {
+            *migration_backup = Some(backup);
+        }
+    }
+
+    // Run migrations based on version
+    if current_version < 1 {
+        migrate_v1(conn)?;
+    }
+
+    if current_version < 2 {
+        migrate_v2(conn)?;
+    }
+
+    if current_version < 3 {
+        migrate_v3(conn)?;
+    }
+
+    if current_version < 4 {
+        migrate_v4(conn)?;
+    }
+
+    if current_version < 5 {
+        migrate_v5(conn)?;
+    }
+
+    if current_version < 6 {
+        migrate_v6(conn)?;
+    }
+
+    if current_version < 7 {
+        migrate_v7(conn)?;
+    }
+
+    if current_version < 8 {
+        migrate_v8(conn)?;
+    }
+
+    if current_version < 9 {
+        migrate_v9(conn)?;
+    }
+
+    if current_version < 10 {
+        migrate_v10(conn)?;
+    }
+
+    if current_version < 11 {
+        migrate_v11(conn)?;
+    }
+
+    if current_version < 12 {
+        migrate_v12(conn)?;
+    }
+
+    if current_version < 13 {
+        migrate_v13(conn)?;
+    }
+
+    if current_version < 14 {
+        migrate_v14(conn)?;
+    }
+
+    if current_version < 15 {
+        migrate_v15(conn)?;
+    }
+
+    if current_version < 16 {
+        migrate_v16(conn)?;
+    }
+
+    if current_version < 17 {
+        migrate_v17(conn)?;
+    }
+
+    if current_version < 18 {
+        migrate_v18(conn)?;
+    }
+
+    if current_version < 19 {
+        migrate_v19(conn)?;
+    }
+
+    if current_version < 20 {
+        migrate_v20(conn)?;
+    }
+
+    if current_version < 21 {
+        migrate_v21(conn)?;
+    }
+
+    if current_version < 22 {
+        migrate_v22(conn)?;
+    }
+
+    if current_version < 23 {
+        migrate_v23(conn)?;
+    }
+
+    if current_version < 24 {
+        migrate_v24(conn)?;
+    }
+
+    if current_version < 25 {
+        migrate_v25(conn)?;
+    }
+
+    // Update schema version
+    conn.execute(
+        "INSERT OR REPLACE INTO meta (key, value) VALUES ('schema_version', ?1)",
+        params![SCHEMA_VERSION.to_string()],
+    )
+    .map_err(|e| format!("Version update error: {}", e))?;
+
+    Ok(())
+}
+
+// ============================================================================
+// Backups
+// ============================================================================
+
+/// Get the directory automatic backups of the database at `db_path` are
+/// written to.
+fn backup_dir_for(db_path: &Path) -> PathBuf {
+    db_path
+        .parent()
+        .map(|parent| parent.join("backups"))
+        .unwrap_or_else(|| PathBuf::from("backups"))
+}
+
+/// Get the file a connection has open; in-memory databases have none.
+fn database_file(conn: &Connection) -> Result<PathBuf, String> {
+    conn.path()
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from)
+        .ok_or_else(|| "Backup error: the database is not a file".to_string())
+}
+
+/// Build a backup file name; names sort by the time they were taken.
+fn backup_file_name(timestamp: u64, reason: &str) -> String {
+    format!("shard-{:012}-{}.db", timestamp, reason)
+}
+
+/// Snapshot the database file to a timestamped backup and return its path.
+pub fn backup_database(reason: &str) -> Result<PathBuf, String> {
+    let conn = connection()?;
+    snapshot_database(&conn, reason)
+}
+
+/// Write all committed pages from the WAL into the main database file.
+fn checkpoint(conn: &Connection) -> SqlResult<()> {
+    conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))
+}
+
+/// Copy the database file to a timestamped backup through an open connection.
+fn snapshot_database(conn: &Connection, reason: &str) -> Result<PathBuf, String> {
+    // Move committed pages out of the WAL so the main file is complete
+    checkpoint(conn).map_err(|e| format!("Backup error: {}", e))?;
+
+    let db_path = database_file(conn)?;
+    let backup_dir = backup_dir_for(&db_path);
+    std::fs::create_dir_all(&backup_dir).map_err(|e| format!("IO error: {}", e))?;
+
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let backup_path = backup_dir.join(backup_file_name(timestamp, reason));
+    std::fs::copy(&db_path, &backup_path).map_err(|e| format!("Backup error: {}", e))?;
+
+    prune_backups(&backup_dir);
+    Ok(backup_path)
+}
+
+/// Remove the oldest backups beyond `MAX_BACKUPS`.
+fn prune_backups(backup_dir: &Path) {
+    let Ok(entries) = std::fs::read_dir(backup_dir) else {
+        return;
+    };
+    let mut backups: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "db"))
+        .collect();
+    backups.sort();
+
+    let excess = backups.len().saturating_sub(MAX_BACKUPS);
+    for path in backups.into_iter().take(excess) {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+/// Get the backup taken before this session's schema migration, if one ran.
+pub fn migration_backup() -> Option<PathBuf> {
+    MIGRATION_BACKUP
+        .lock()
+        .ok()
+        .and_then(|backup| backup.clone())
+}
+
+/// Replace the database file with a backup.
+pub fn restore_backup(backup_path: PathBuf) -> Result<PathBuf, String> {
+    let db_path = get_database_path()?;
+    close_connection();
+    // A leftover WAL would be replayed onto the restored file
+    remove_wal_files(&db_path);
+    std::fs::copy(&backup_path, &db_path).map_err(|e| format!("Restore error: {}", e))?;
+    Ok(backup_path)
+}
+
+// ============================================================================
+// Maintenance
+// ============================================================================
+
+/// Row counts and size of the database.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DatabaseStats {
+    pub snippets: i64,
+    pub trashed: i64,
+    pub palettes: i64,
+    pub sections: i64,
+    /// Size of the database pages in bytes.
+    pub size: u64,
+}
+
+impl std::fmt::Display for DatabaseStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} snippets, {} in trash, {} palettes, {} sections · {}",
+            self.snippets,
+            self.trashed,
+            self.palettes,
+            self.sections,
+            format_size(self.size)
+        )
+    }
+}
+
+/// Format a byte count for display, e.g. `1.5 MB`.
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 3] = ["KB", "MB", "GB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit + 1 < UNITS.len() {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+/// Get the row counts and size of the database.
+pub fn database_stats() -> Result<DatabaseStats, String> {
+    let conn = connection()?;
+    stats_on(&conn)
+}
+
+fn stats_on(conn: &Connection) -> Result<DatabaseStats, String> {
+    let count = |sql: &str| -> Result<i64, String> {
+        conn.query_row(sql, [], |row| row.get(0))
+            .map_err(|e| format!("Query error: {}", e))
+    };
+
+    Ok(DatabaseStats {
+        snippets: count("SELECT COUNT(*) FROM snippets WHERE deleted_at IS NULL")?,
+        trashed: count("SELECT COUNT(*) FROM snippets WHERE deleted_at IS NOT NULL")?,
+        palettes: count("SELECT COUNT(*) FROM palettes")?,
+        sections: count("SELECT COUNT(*) FROM sections")?,
+        size: database_size(conn)?,
+    })
+}
+
+/// Get the size of the database pages in bytes.
+fn database_size(conn: &Connection) -> Result<u64, String> {
+    let pages: i64 = conn
+        .query_row("PRAGMA page_count", [], |row| row.get(0))
+        .map_err(|e| format!("Query error: {}", e))?;
+    let page_size: i64 = conn
+        .query_row("PRAGMA page_size", [], |row| row.get(0))
+        .map_err(|e| format!("Query error: {}", e))?;
+    Ok((pages * page_size) as u64)
+}
+
+/// Run SQLite's integrity check, describing any problems found as the error.
+pub fn check_integrity() -> Result<String, String> {
+    let conn = connection()?;
+    integrity_check_on(&conn)
+}
+
+fn integrity_check_on(conn: &Connection) -> Result<String, String> {
+    let mut stmt = conn
+        .prepare("PRAGMA integrity_check")
+        .map_err(|e| format!("Query error: {}", e))?;
+    let problems = stmt
+        .query_map([], |row| row.get::<_, String>(0))
+        .map_err(|e| format!("Query error: {}", e))?
+        .collect::<SqlResult<Vec<_>>>()
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    if problems == ["ok"] {
+        Ok("Integrity check passed".to_string())
+    } else {
+        Err(format!("Integrity check failed: {}", problems.join("; ")))
+    }
+}
+
+/// Rebuild the database file to reclaim space left by deleted rows.
+pub fn vacuum() -> Result<String, String> {
+    let conn = connection()?;
+    let before = database_size(&conn)?;
+    conn.execute_batch("VACUUM")
+        .map_err(|e| format!("Vacuum error: {}", e))?;
+    // The rebuilt pages land in the WAL first
+    checkpoint(&conn).map_err(|e| format!("Vacuum error: {}", e))?;
+    let after = database_size(&conn)?;
+
+    Ok(format!(
+        "Compacted database from {} to {}",
+        format_size(before),
+        format_size(after)
+    ))
+}
+
+// ============================================================================
+// Encryption
+// ============================================================================
+
+/// Whether this build can read and write encrypted (SQLCipher) databases.
+pub const ENCRYPTION_SUPPORTED: bool = cfg!(feature = "encryption");
+
+/// Header every plaintext SQLite database file starts with.
+const SQLITE_HEADER: &[u8; 16] = b"SQLite format 3\0";
+
+/// Passphrase of the encrypted database, once unlocked this session.
+static PASSPHRASE: Mutex<Option<String>> = Mutex::new(None);
+
+/// Check if the database file is encrypted (exists but is not plaintext SQLite).
+pub fn is_encrypted() -> bool {
+    let Ok(path) = get_database_path() else {
+        return false;
+    };
+    let mut header = [0u8; 16];
+    match std::fs::File::open(&path).and_then(|mut file| file.read_exact(&mut header)) {
+        Ok(()) => &header != SQLITE_HEADER,
+        Err(_) => false,
+    }
+}
+
+/// Check if the database is encrypted and still waiting for its passphrase.
+pub fn needs_unlock() -> bool {
+    is_encrypted() && passphrase().is_none()
+}
+
+fn passphrase() -> Option<String> {
+    PASSPHRASE
+        .lock()
+        .ok()
+        .and_then(|passphrase| passphrase.clone())
+}
+
+/// Key a fresh connection with the session passphrase, if the database is encrypted.
+/// A plaintext file (e.g. a restored backup) is opened without a key.
+fn apply_key(conn: &Connection) -> Result<(), String> {
+    if let Some(passphrase) = passphrase().filter(|_| is_encrypted()) {
+        conn.pragma_update(None, "key", passphrase)
+            .map_err(|e| format!("Key error: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Unlock the encrypted database for this session.
+pub fn unlock_database(passphrase: String) -> Result<(), String> {
+    if !ENCRYPTION_SUPPORTED {
+        return Err("This build of Shard has no encryption support".to_string());
+    }
+
+    let conn =
+        Connection::open(get_database_path()?).map_err(|e| format!("Database error: {}", e))?;
+    conn.pragma_update(None, "key", &passphrase)
+        .map_err(|e| format!("Key error: {}", e))?;
+    // Reading the schema fails unless the key is right
+    conn.query_row("SELECT count(*) FROM sqlite_master", [], |row| {
+        row.get::<_, i64>(0)
+    })
+    .map_err(|_| "Wrong passphrase".to_string())?;
+
+    if let Ok(mut current) = PASSPHRASE.lock() {
+        *current = Some(passphrase);
+    }
+    // Reopen with the key on next use
+    close_connection();
+    Ok(())
+}
+
+/// What happened to the plaintext backups when the database was encrypted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BackupsEncrypted {
+    /// Backups now encrypted with the database's passphrase.
+    pub encrypted: usize,
+    /// Backups that could not be encrypted and were deleted instead.
+    pub removed: usize,
+}
+
+/// Encrypt the plaintext database with a passphrase, then its backups, so
+/// no plaintext copy stays on disk. The plaintext file is only replaced once
+/// the encrypted copy is complete.
+pub fn encrypt_database(passphrase: String) -> Result<BackupsEncrypted, String> {
+    if !ENCRYPTION_SUPPORTED {
+        return Err("This build of Shard has no encryption support".to_string());
+    }
+    if is_encrypted() {
+        return Err("The database is already encrypted".to_string());
+    }
+
+    let path = get_database_path()?;
+    close_connection();
+    encrypt_file(&path, &passphrase)?;
+    if let Ok(mut current) = PASSPHRASE.lock() {
+        *current = Some(passphrase.clone());
+    }
+
+    Ok(encrypt_backups(&backup_dir_for(&path), &passphrase))
+}
+
+/// Encrypt a plaintext SQLite file in place: export it into a new encrypted
+/// file, then swap that in.
+fn encrypt_file(path: &Path, passphrase: &str) -> Result<(), String> {
+    let encrypted_path = path.with_extension("db.encrypting");
+    let _ = std::fs::remove_file(&encrypted_path);
+    let export = || -> SqlResult<()> {
+        let conn = Connection::open(path)?;
+        conn.execute(
+            "ATTACH DATABASE ?1 AS encrypted KEY ?2",
+            params![encrypted_path.to_string_lossy(), passphrase],
+        )?;
+        conn.query_row("SELECT sqlcipher_export('encrypted')", [], |_| Ok(()))?;
+        conn.execute("DETACH DATABASE encrypted", [])?;
+        Ok(())
+    };
+    if let Err(e) = export() {
+        let _ = std::fs::remove_file(&encrypted_path);
+        return Err(format!("Encrypt error: {}", e));
+    }
+
+    // The plaintext WAL would be replayed onto the encrypted file
+    remove_wal_files(path);
+    std::fs::rename(&encrypted_path, path).map_err(|e| format!("IO error: {}", e))
+}
+
+/// Encrypt the plaintext backups in a folder with the database's passphrase,
+/// so restoring them keeps working. Backups that fail are deleted.
+fn encrypt_backups(backup_dir: &Path, passphrase: &str) -> BackupsEncrypted {
+    let mut result = BackupsEncrypted::default();
+    let Ok(entries) = std::fs::read_dir(backup_dir) else {
+        return result;
+    };
+    for path in entries.filter_map(|entry| entry.ok().map(|e| e.path())) {
+        let mut header = [0u8; 16];
+        let is_plaintext = std::fs::File::open(&path)
+            .and_then(|mut file| file.read_exact(&mut header))
+            .is_ok_and(|()| &header == SQLITE_HEADER);
+        if !is_plaintext {
+            continue;
+        }
+        if encrypt_file(&path, passphrase).is_ok() {
+            result.encrypted += 1;
+        } else {
+            let _ = std::fs::remove_file(&path);
+            remove_wal_files(&path);
+            result.removed += 1;
+        }
+    }
+    result
+}
+
+/// Migration v1: Create initial snippets table.
+fn migrate_v1(conn: &Connection) -> Result<(), String> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS snippets (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            kind TEXT NOT NULL,
+            label TEXT NOT NULL,
+            position INTEGER NOT NULL,
+            created_at TEXT DEFAULT CURRENT_TIMESTAMP,
+            
+            -- Color-specific (NULL for non-color)
+            r INTEGER,
+            g INTEGER,
+            b INTEGER,
+            a REAL,
+            
+            -- Code-specific (NULL for non-code)
+            code TEXT,
+            language TEXT,
+            
+            -- Text-specific (NULL for non-text)
+            text_content TEXT
+        )",
+        [],
+    )
+    .map_err(|e| format!("Snippets table error: {}", e))?;
+
+    Ok(())
+}
+
+/// Migration v2: Migrate data from old colors table if exists.
+fn migrate_v2(conn: &Connection) -> Result<(), String> {
+    // Check if old colors table exists
+    let has_colors_table: bool = conn
+        .query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='colors'",
+            [],
+            |row| {
+                let count: i32 = row.get(0)?;
+                Ok(count > 0)
+            },
+        )
+        .unwrap_or(false);
+
+    if has_colors_table {
+        // Migrate colors to snippets
+        conn.execute(
+            "INSERT INTO snippets (kind, label, position, r, g, b, a, created_at)
+             SELECT 'color', label, position, r, g, b, a, created_at FROM colors
+             WHERE NOT EXISTS (
+                 SELECT 1 FROM snippets WHERE kind = 'color' AND r = colors.r AND g = colors.g AND b = colors.b
+             )",
+            [],
+        )
+        .map_err(|e| format!("Migration error: {}", e))?;
+
+        // Optionally drop old table (commented out for safety)
+        // conn.execute("DROP TABLE colors", []).ok();
+    }
+
+    Ok(())
+}
+
+/// Migration v3: Create palettes and palette_snippets tables.
+fn migrate_v3(conn: &Connection) -> Result<(), String> {
+    // Create palettes table
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS palettes (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL UNIQUE,
+            created_at TEXT DEFAULT CURRENT_TIMESTAMP
+        )",
+        [],
+    )
+    .map_err(|e| format!("Palettes table error: {}", e))?;
+
+    // Create junction table for many-to-many relationship
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS palette_snippets (
+            palette_id INTEGER NOT NULL,
+            snippet_id INTEGER NOT NULL,
+            PRIMARY KEY (palette_id, snippet_id),
+            FOREIGN KEY (palette_id) REFERENCES palettes(id) ON DELETE CASCADE,
+            FOREIGN KEY (snippet_id) REFERENCES snippets(id) ON DELETE CASCADE
+        )",
+        [],
+    )
+    .map_err(|e| format!("Palette_snippets table error: {}", e))?;
+
+    Ok(())
+}
+
+/// Migration v4: Add inbox flag for auto-captured snippets awaiting triage.
+fn migrate_v4(conn: &Connection) -> Result<(), String> {
+    conn.execute(
+        "ALTER TABLE snippets ADD COLUMN inbox INTEGER NOT NULL DEFAULT 0",
+        [],
+    )
+    .map_err(|e| format!("Inbox column error: {}", e))?;
+
+    Ok(())
+}
+
+/// Migration v5: Track when each snippet was last changed.
+fn migrate_v5(conn: &Connection) -> Result<(), String> {
+    conn.execute("ALTER TABLE snippets ADD COLUMN updated_at TEXT", [])
+        .map_err(|e| format!("Updated column error: {}", e))?;
+
+    conn.execute("UPDATE snippets SET updated_at = created_at", [])
+        .map_err(|e| format!("Updated column error: {}", e))?;
+
+    Ok(())
+}
+
+/// Migration v6: Soft delete into the trash.
+fn migrate_v6(conn: &Connection) -> Result<(), String> {
+    conn.execute("ALTER TABLE snippets ADD COLUMN deleted_at TEXT", [])
+        .map_err(|e| format!("Deleted column error: {}", e))?;
+
+    Ok(())
+}
+
+/// Migration v7: Favorites bar.
+fn migrate_v7(conn: &Connection) -> Result<(), String> {
+    conn.execute("ALTER TABLE snippets ADD COLUMN favorite_at TEXT", [])
+        .map_err(|e| format!("Favorite column error: {}", e))?;
+
+    Ok(())
+}
+
+/// Migration v8: Usage tracking.
+fn migrate_v8(conn: &Connection) -> Result<(), String> {
+    conn.execute(
+        "ALTER TABLE snippets ADD COLUMN copy_count INTEGER NOT NULL DEFAULT 0",
+        [],
+    )
+    .map_err(|e| format!("Copy count column error: {}", e))?;
+
+    conn.execute("ALTER TABLE snippets ADD COLUMN last_copied_at TEXT", [])
+        .map_err(|e| format!("Last copied column error: {}", e))?;
+
+    Ok(())
+}
+
+/// Migration v9: Custom snippet icons.
+fn migrate_v9(conn: &Connection) -> Result<(), String> {
+    conn.execute("ALTER TABLE snippets ADD COLUMN icon TEXT", [])
+        .map_err(|e| format!("Icon column error: {}", e))?;
+
+    Ok(())
+}
+
+/// Migration v10: Revisions for conflict-safe updates.
+fn migrate_v10(conn: &Connection) -> Result<(), String> {
+    conn.execute(
+        "ALTER TABLE snippets ADD COLUMN revision INTEGER NOT NULL DEFAULT 0",
+        [],
+    )
+    .map_err(|e| format!("Revision column error: {}", e))?;
+
+    Ok(())
+}
+
+/// Migration v11: Drop palette links left behind while foreign keys were off.
+fn migrate_v11(conn: &Connection) -> Result<(), String> {
+    conn.execute(
+        "DELETE FROM palette_snippets
+         WHERE palette_id NOT IN (SELECT id FROM palettes)
+            OR snippet_id NOT IN (SELECT id FROM snippets)",
+        [],
+    )
+    .map_err(|e| format!("Palette link cleanup error: {}", e))?;
+
+    Ok(())
+}
+
+/// Migration v12: Smart palettes defined by a saved query.
+fn migrate_v12(conn: &Connection) -> Result<(), String> {
+    conn.execute("ALTER TABLE palettes ADD COLUMN query TEXT", [])
+        .map_err(|e| format!("Palette query column error: {}", e))?;
+
+    Ok(())
+}
+
+/// Migration v13: Nested palettes.
+fn migrate_v13(conn: &Connection) -> Result<(), String> {
+    conn.execute(
+        "ALTER TABLE palettes ADD COLUMN parent_id INTEGER REFERENCES palettes(id) ON DELETE SET NULL",
+        [],
+    )
+    .map_err(|e| format!("Palette parent column error: {}", e))?;
+
+    Ok(())
+}
+
+/// Migration v14: Section headers in the manual order.
+fn migrate_v14(conn: &Connection) -> Result<(), String> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS sections (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL,
+            position INTEGER NOT NULL
+        )",
+        [],
+    )
+    .map_err(|e| format!("Sections table error: {}", e))?;
+
+    Ok(())
+}
+
+/// Migration v15: Files snippets were imported from or exported to.
+fn migrate_v15(conn: &Connection) -> Result<(), String> {
+    conn.execute("ALTER TABLE snippets ADD COLUMN file_path TEXT", [])
+        .map_err(|e| format!("File path column error: {}", e))?;
+
+    conn.execute("ALTER TABLE snippets ADD COLUMN file_hash TEXT", [])
+        .map_err(|e| format!("File hash column error: {}", e))?;
+
+    Ok(())
+}
+
+/// Migration v16: File attachment snippets.
+fn migrate_v16(conn: &Connection) -> Result<(), String> {
+    conn.execute("ALTER TABLE snippets ADD COLUMN attachment_name TEXT", [])
+        .map_err(|e| format!("Attachment name column error: {}", e))?;
+
+    conn.execute("ALTER TABLE snippets ADD COLUMN attachment_path TEXT", [])
+        .map_err(|e| format!("Attachment path column error: {}", e))?;
+
+    conn.execute(
+        "ALTER TABLE snippets ADD COLUMN attachment_size INTEGER",
+        [],
+    )
+    .map_err(|e| format!("Attachment size column error: {}", e))?;
+
+    Ok(())
+}
+
+/// Migration v17: Code snippets synced into project snippet files.
+fn migrate_v17(conn: &Connection) -> Result<(), String> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS project_syncs (
+            snippet_id INTEGER NOT NULL,
+            target_path TEXT NOT NULL,
+            PRIMARY KEY (snippet_id, target_path),
+            FOREIGN KEY (snippet_id) REFERENCES snippets(id) ON DELETE CASCADE
+        )",
+        [],
+    )
+    .map_err(|e| format!("Project syncs table error: {}", e))?;
+
+    Ok(())
+}
+
+/// Migration v18: Color set snippets.
+fn migrate_v18(conn: &Connection) -> Result<(), String> {
+    conn.execute("ALTER TABLE snippets ADD COLUMN color_set TEXT", [])
+        .map_err(|e| format!("Color set column error: {}", e))?;
+
+    Ok(())
+}
+
+/// Migration v19: Rich text (HTML) captured with text snippets.
+fn migrate_v19(conn: &Connection) -> Result<(), String> {
+    conn.execute("ALTER TABLE snippets ADD COLUMN rich_html TEXT", [])
+        .map_err(|e| format!("Rich text column error: {}", e))?;
+
+    Ok(())
+}
+
+/// Migration v20: JSON snippets. Valid JSON code snippets become JSON
+/// snippets; the document stays in the code column.
+fn migrate_v20(conn: &Connection) -> Result<(), String> {
+    conn.execute(
+        "UPDATE snippets SET kind = 'json' \
+         WHERE kind = 'code' AND lower(language) = 'json' AND json_valid(code)",
+        [],
+    )
+    .map_err(|e| format!("JSON snippet migration error: {}", e))?;
+
+    Ok(())
+}
+
+/// Migration v21: Application auto-captured snippets were copied from.
+fn migrate_v21(conn: &Connection) -> Result<(), String> {
+    conn.execute("ALTER TABLE snippets ADD COLUMN source_app TEXT", [])
+        .map_err(|e| format!("Source app column error: {}", e))?;
+
+    Ok(())
+}
+
+/// Migration v22: Palettes holding the dark-mode variants of another palette.
+fn migrate_v22(conn: &Connection) -> Result<(), String> {
+    conn.execute(
+        "ALTER TABLE palettes ADD COLUMN dark_palette_id INTEGER \
+         REFERENCES palettes(id) ON DELETE SET NULL",
+        [],
+    )
+    .map_err(|e| format!("Dark palette column error: {}", e))?;
+
+    Ok(())
+}
+
+/// Migration v23: Text-expander abbreviations.
+fn migrate_v23(conn: &Connection) -> Result<(), String> {
+    conn.execute("ALTER TABLE snippets ADD COLUMN abbreviation TEXT", [])
+        .map_err(|e| format!("Abbreviation column error: {}", e))?;
+
+    Ok(())
+}
+
+/// Migration v24: Snippet descriptions.
+fn migrate_v24(conn: &Connection) -> Result<(), String> {
+    conn.execute("ALTER TABLE snippets ADD COLUMN description TEXT", [])
+        .map_err(|e| format!("Description column error: {}", e))?;
+
+    Ok(())
+}
+
+/// Migration v25: Gradient snippets.
+fn migrate_v25(conn: &Connection) -> Result<(), String> {
+    conn.execute("ALTER TABLE snippets ADD COLUMN gradient TEXT", [])
+        .map_err(|e| format!("Gradient column error: {}", e))?;
+
+    Ok(())
+}
+
+/// Build a snippet from a row selected with `SNIPPET_COLUMNS`.
+fn snippet_from_row(row: &rusqlite::Row) -> SqlResult<Snippet> {
+    let id: i64 = row.get(0)?;
+    let kind_str: String = row.get(1)?;
+    let label: String = row.get(2)?;
+    let position: i64 = row.get(3)?;
+
+    let content = match kind_str.as_str() {
+        "color" => {
+            let r: i32 = row.get(4)?;
+            let g: i32 = row.get(5)?;
+            let b: i32 = row.get(6)?;
+            let a: f64 = row.get(7)?;
+            SnippetContent::Color(ColorData::new(r as u8, g as u8, b as u8, a as f32))
+        }
+        "colorset" => {
+            let json: String = row.get(25)?;
+            SnippetContent::ColorSet(ColorSetData::from_json(&json))
+        }
+        "gradient" => {
+            let json: String = row.get(30)?;
+            SnippetContent::Gradient(GradientData::from_json(&json))
+        }
+        "code" => {
+            let code: String = row.get(8)?;
+            let language: String = row.get(9)?;
+            SnippetContent::Code(CodeData::new(code, language))
+        }
+        "json" => {
+            let json: String = row.get(8)?;
+            SnippetContent::Json(JsonData::new(json))
+        }
+        "text" => {
+            let text: String = row.get(10)?;
+            let html: Option<String> = row.get(26)?;
+            SnippetContent::Text(TextData::with_html(text, html))
+        }
+        "file" => {
+            let name: String = row.get(22)?;
+            let path: String = row.get(23)?;
+            let size: i64 = row.get(24)?;
+            SnippetContent::File(FileData::new(name, path, size as u64))
+        }
+        _ => {
+            // Fallback to text for unknown types
+            SnippetContent::Text(TextData::new(label.clone()))
+        }
+    };
+
+    // Rows migrated from the old colors table may have no timestamps
+    let created_at: Option<i64> = row.get(12)?;
+    let updated_at: Option<i64> = row.get(13)?;
+
+    Ok(Snippet {
+        id,
+        label,
+        content,
+        position,
+        in_inbox: row.get(11)?,
+        created_at: created_at.unwrap_or_default(),
+        updated_at: updated_at.or(created_at).unwrap_or_default(),
+        deleted_at: row.get(14)?,
+        favorite_at: row.get(15)?,
+        copy_count: row.get(16)?,
+        last_copied_at: row.get(17)?,
+        icon: row.get(18)?,
+        revision: row.get(19)?,
+        file_path: row.get(20)?,
+        file_hash: row.get(21)?,
+        source_app: row.get(27)?,
+        abbreviation: row.get(28)?,
+        description: row.get(29)?,
+        section: None,
+    })
+}
+
+/// Load all snippets from the database, ordered by position (newest first).
+pub fn load_snippets() -> Result<Vec<Snippet>, String> {
+    let conn = connection()?;
+    load_snippets_on(&conn)
+}
+
+fn load_snippets_on(conn: &Connection) -> Result<Vec<Snippet>, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM snippets WHERE deleted_at IS NULL ORDER BY position DESC",
+            SNIPPET_COLUMNS
+        ))
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    let snippets = stmt
+        .query_map([], snippet_from_row)
+        .map_err(|e| format!("Query error: {}", e))?
+        .collect::<SqlResult<Vec<_>>>()
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    Ok(snippets)
+}
+
+/// Load snippets filtered by kind.
+#[allow(dead_code)]
+pub fn load_snippets_by_kind(kind: SnippetKind) -> Result<Vec<Snippet>, String> {
+    let conn = connection()?;
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM snippets WHERE kind = ?1 AND deleted_at IS NULL ORDER BY position DESC",
+            SNIPPET_COLUMNS
+        ))
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    let snippets = stmt
+        .query_map(params![kind.as_db_str()], snippet_from_row)
+        .map_err(|e| format!("Query error: {}", e))?
+        .collect::<SqlResult<Vec<_>>>()
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    Ok(snippets)
+}
+
+/// Get the next position value.
+/// Snippets and section headers share one position sequence.
+fn get_next_position(conn: &Connection) -> Result<i64, String> {
+    let max_position: i64 = conn
+        .query_row(
+            "SELECT MAX(COALESCE((SELECT MAX(position) FROM snippets), 0),
+                        COALESCE((SELECT MAX(position) FROM sections), 0))",
+            [],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    Ok(max_position + 1)
+}
+
+/// Insert a new snippet and return the snippet with its new ID.
+pub fn insert_snippet(snippet: Snippet) -> Result<Snippet, String> {
+    let conn = connection()?;
+    insert_snippet_on(&conn, snippet)
+}
+
+fn insert_snippet_on(conn: &Connection, snippet: Snippet) -> Result<Snippet, String> {
+    let position = get_next_position(conn)?;
+
+    match &snippet.content {
+        SnippetContent::Color(color) => {
+            conn.execute(
+                "INSERT INTO snippets (kind, label, position, r, g, b, a, inbox, icon, description, updated_at) 
+                 VALUES ('color', ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, CURRENT_TIMESTAMP)",
+                params![
+                    snippet.label,
+                    position,
+                    color.r as i32,
+                    color.g as i32,
+                    color.b as i32,
+                    color.a,
+                    snippet.in_inbox,
+                    snippet.icon,
+                    snippet.description
+                ],
+            )
+            .map_err(|e| format!("Insert error: {}", e))?;
+        }
+        SnippetContent::ColorSet(set) => {
+            conn.execute(
+                "INSERT INTO snippets (kind, label, position, color_set, inbox, icon, description, updated_at) 
+                 VALUES ('colorset', ?1, ?2, ?3, ?4, ?5, ?6, CURRENT_TIMESTAMP)",
+                params![
+                    snippet.label,
+                    position,
+                    set.to_json(),
+                    snippet.in_inbox,
+                    snippet.icon,
+                    snippet.description
+                ],
+            )
+            .map_err(|e| format!("Insert error: {}", e))?;
+        }
+        SnippetContent::Gradient(gradient) => {
+            conn.execute(
+                "INSERT INTO snippets (kind, label, position, gradient, inbox, icon, description, updated_at) 
+                 VALUES ('gradient', ?1, ?2, ?3, ?4, ?5, ?6, CURRENT_TIMESTAMP)",
+                params![
+                    snippet.label,
+                    position,
+                    gradient.to_json(),
+                    snippet.in_inbox,
+                    snippet.icon,
+                    snippet.description
+                ],
+            )
+            .map_err(|e| format!("Insert error: {}", e))?;
+        }
+        SnippetContent::Code(code) => {
+            conn.execute(
+                "INSERT INTO snippets (kind, label, position, code, language, inbox, icon, description, updated_at) 
+                 VALUES ('code', ?1, ?2, ?3, ?4, ?5, ?6, ?7, CURRENT_TIMESTAMP)",
+                params![
+                    snippet.label,
+                    position,
+                    code.code,
+                    code.language,
+                    snippet.in_inbox,
+                    snippet.icon,
+                    snippet.description
+                ],
+            )
+            .map_err(|e| format!("Insert error: {}", e))?;
+        }
+        SnippetContent::Json(json) => {
+            conn.execute(
+                "INSERT INTO snippets (kind, label, position, code, language, inbox, icon, description, updated_at) 
+                 VALUES ('json', ?1, ?2, ?3, 'json', ?4, ?5, ?6, CURRENT_TIMESTAMP)",
+                params![
+                    snippet.label,
+                    position,
+                    json.json,
+                    snippet.in_inbox,
+                    snippet.icon,
+                    snippet.description
+                ],
+            )
+            .map_err(|e| format!("Insert error: {}", e))?;
+        }
+        SnippetContent::Text(text) => {
+            conn.execute(
+                "INSERT INTO snippets (kind, label, position, text_content, rich_html, inbox, icon, description, updated_at) 
+                 VALUES ('text', ?1, ?2, ?3, ?4, ?5, ?6, ?7, CURRENT_TIMESTAMP)",
+                params![
+                    snippet.label,
+                    position,
+                    text.text,
+                    text.html,
+                    snippet.in_inbox,
+                    snippet.icon,
+                    snippet.description
+                ],
+            )
+            .map_err(|e| format!("Insert error: {}", e))?;
+        }
+        SnippetContent::File(file) => {
+            conn.execute(
+                "INSERT INTO snippets (kind, label, position, attachment_name, attachment_path, attachment_size, inbox, icon, description, updated_at) 
+                 VALUES ('file', ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, CURRENT_TIMESTAMP)",
+                params![
+                    snippet.label,
+                    position,
+                    file.name,
+                    file.path,
+                    file.size as i64,
+                    snippet.in_inbox,
+                    snippet.icon,
+                    snippet.description
+                ],
+            )
+            .map_err(|e| format!("Insert error: {}", e))?;
+        }
+    }
+
+    let id = conn.last_insert_rowid();
+    if let Some(source_app) = &snippet.source_app {
+        conn.execute(
+            "UPDATE snippets SET source_app = ?1 WHERE id = ?2",
+            params![source_app, id],
+        )
+        .map_err(|e| format!("Insert error: {}", e))?;
+    }
+    let now = unix_now();
+    Ok(Snippet {
+        id,
+        position,
+        created_at: now,
+        updated_at: now,
+        ..snippet
+    })
+}
+
+/// Why saving an edit to a snippet failed.
+#[derive(Debug, Clone)]
+pub enum SaveError {
+    /// The snippet was changed elsewhere since the edit started.
+    Conflict(Box<SaveConflict>),
+    Failed(String),
+}
+
+/// Two diverging versions of a snippet.
+#[derive(Debug, Clone)]
+pub struct SaveConflict {
+    /// The version in the database.
+    pub stored: Snippet,
+    /// The edit that could not be saved, based on an older revision.
+    pub edited: Snippet,
+}
+
+impl From<String> for SaveError {
+    fn from(error: String) -> Self {
+        SaveError::Failed(error)
+    }
+}
+
+impl std::fmt::Display for SaveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SaveError::Conflict(_) => write!(f, "The snippet was changed elsewhere"),
+            SaveError::Failed(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+/// Finish a content update that was guarded by `revision = expected`.
+/// If no row changed, the snippet was edited elsewhere (or deleted) since.
+fn finish_update(
+    conn: &Connection,
+    id: i64,
+    changed: usize,
+    edit: impl FnOnce(&Snippet) -> Snippet,
+) -> Result<Snippet, SaveError> {
+    if changed > 0 {
+        return Ok(fetch_snippet(conn, id)?);
+    }
+    match query_snippet(conn, id)? {
+        Some(stored) => Err(SaveError::Conflict(Box::new(SaveConflict {
+            edited: edit(&stored),
+            stored,
+        }))),
+        None => Err(SaveError::Failed("Snippet not found".to_string())),
+    }
+}
+
+/// Update a snippet's label.
+#[allow(dead_code)]
+pub fn update_label(id: i64, label: String) -> Result<(i64, String), String> {
+    let conn = connection()?;
+    conn.execute(
+        "UPDATE snippets SET label = ?1, updated_at = CURRENT_TIMESTAMP, revision = revision + 1 WHERE id = ?2",
+        params![label, id],
+    )
+    .map_err(|e| format!("Update error: {}", e))?;
+    Ok((id, label))
+}
+
+/// Update a color snippet, unless it changed since `revision`.
+pub fn update_color(
+    id: i64,
+    color: ColorData,
+    label: String,
+    description: Option<String>,
+    revision: i64,
+) -> Result<Snippet, SaveError> {
+    let conn = connection()?;
+    let changed = conn
+        .execute(
+            "UPDATE snippets SET r = ?1, g = ?2, b = ?3, a = ?4, label = ?5, description = ?6, updated_at = CURRENT_TIMESTAMP, revision = revision + 1 \
+             WHERE id = ?7 AND kind = 'color' AND revision = ?8",
+            params![color.r as i32, color.g as i32, color.b as i32, color.a, label, description, id, revision],
+        )
+        .map_err(|e| format!("Update error: {}", e))?;
+
+    finish_update(&conn, id, changed, |stored| Snippet {
+        label,
+        description,
+        content: SnippetContent::Color(color),
+        ..stored.clone()
+    })
+}
+
+/// Update a code snippet, unless it changed since `revision`.
+pub fn update_code(
+    id: i64,
+    code: String,
+    language: String,
+    label: String,
+    description: Option<String>,
+    revision: i64,
+) -> Result<Snippet, SaveError> {
+    let conn = connection()?;
+    let changed = conn
+        .execute(
+            "UPDATE snippets SET code = ?1, language = ?2, label = ?3, description = ?4, updated_at = CURRENT_TIMESTAMP, revision = revision + 1 \
+             WHERE id = ?5 AND kind = 'code' AND revision = ?6",
+            params![code, language, label, description, id, revision],
+        )
+        .map_err(|e| format!("Update error: {}", e))?;
+
+    finish_update(&conn, id, changed, |stored| Snippet {
+        label,
+        description,
+        content: SnippetContent::Code(CodeData::new(code, language)),
+        ..stored.clone()
+    })
+}
+
+/// Set the language of several code snippets in one transaction.
+/// Other kinds are skipped; returns the updated snippets.
+pub fn set_code_language(ids: Vec<i64>, language: String) -> Result<Vec<Snippet>, String> {
+    let conn = connection()?;
+    set_language_all(&conn, &ids, &language)
+}
+
+fn set_language_all(
+    conn: &Connection,
+    ids: &[i64],
+    language: &str,
+) -> Result<Vec<Snippet>, String> {
+    let tx = conn
+        .unchecked_transaction()
+        .map_err(|e| format!("Transaction error: {}", e))?;
+    let mut updated = Vec::new();
+    {
+        let mut stmt = tx
+            .prepare(
+                "UPDATE snippets SET language = ?1, updated_at = CURRENT_TIMESTAMP, revision = revision + 1 \
+                 WHERE id = ?2 AND kind = 'code'",
+            )
+            .map_err(|e| format!("Update error: {}", e))?;
+        for id in ids {
+            let changed = stmt
+                .execute(params![language, id])
+                .map_err(|e| format!("Update error: {}", e))?;
+            if changed > 0 {
+                updated.push(fetch_snippet(&tx, *id)?);
+            }
+        }
+    }
+    tx.commit()
+        .map_err(|e| format!("Transaction error: {}", e))?;
+
+    Ok(updated)
+}
+
+/// Update only the code content of a code snippet (preserves language and label),
+/// unless it changed since `revision`.
+pub fn update_code_content(id: i64, code: String, revision: i64) -> Result<Snippet, SaveError> {
+    let conn = connection()?;
+    let changed = conn
+        .execute(
+            "UPDATE snippets SET code = ?1, updated_at = CURRENT_TIMESTAMP, revision = revision + 1 \
+             WHERE id = ?2 AND kind = 'code' AND revision = ?3",
+            params![code, id, revision],
+        )
+        .map_err(|e| format!("Update error: {}", e))?;
+
+    finish_update(&conn, id, changed, |stored| {
+        let language = match &stored.content {
+            SnippetContent::Code(stored_code) => stored_code.language.clone(),
+            _ => "plain".to_string(),
+        };
+        Snippet {
+            content: SnippetContent::Code(CodeData::new(code, language)),
+            ..stored.clone()
+        }
+    })
+}
+
+/// Update a JSON snippet, unless it changed since `revision`.
+/// Documents that do not parse are rejected.
+pub fn update_json(
+    id: i64,
+    json: String,
+    label: String,
+    description: Option<String>,
+    revision: i64,
+) -> Result<Snippet, SaveError> {
+    parse_json(&json)?;
+    let conn = connection()?;
+    let changed = conn
+        .execute(
+            "UPDATE snippets SET code = ?1, label = ?2, description = ?3, updated_at = CURRENT_TIMESTAMP, revision = revision + 1 \
+             WHERE id = ?4 AND kind = 'json' AND revision = ?5",
+            params![json, label, description, id, revision],
+        )
+        .map_err(|e| format!("Update error: {}", e))?;
+
+    finish_update(&conn, id, changed, |stored| Snippet {
+        label,
+        description,
+        content: SnippetContent::Json(JsonData::new(json)),
+        ..stored.clone()
+    })
+}
+
+/// Update only the document of a JSON snippet (preserves label), unless
+/// it changed since `revision`. Documents that do not parse are rejected.
+pub fn update_json_content(id: i64, json: String, revision: i64) -> Result<Snippet, SaveError> {
+    parse_json(&json)?;
+    let conn = connection()?;
+    let changed = conn
+        .execute(
+            "UPDATE snippets SET code = ?1, updated_at = CURRENT_TIMESTAMP, revision = revision + 1 \
+             WHERE id = ?2 AND kind = 'json' AND revision = ?3",
+            params![json, id, revision],
+        )
+        .map_err(|e| format!("Update error: {}", e))?;
+
+    finish_update(&conn, id, changed, |stored| Snippet {
+        content: SnippetContent::Json(JsonData::new(json)),
+        ..stored.clone()
+    })
+}
+
+/// SET clause keeping a text snippet's rich text only while its plain text
+/// (`?1`) is unchanged.
+const KEEP_MATCHING_HTML: &str = "rich_html = CASE WHEN text_content = ?1 THEN rich_html END";
+
+/// Update only the text content of a text snippet (preserves label),
+/// unless it changed since `revision`. Rich text no longer matching the
+/// edited text is dropped.
+pub fn update_text_content(id: i64, text: String, revision: i64) -> Result<Snippet, SaveError> {
+    let conn = connection()?;
+    let changed = conn
+        .execute(
+            &format!(
+                "UPDATE snippets SET text_content = ?1, updated_at = CURRENT_TIMESTAMP, revision = revision + 1, {} \
+                 WHERE id = ?2 AND kind = 'text' AND revision = ?3",
+                KEEP_MATCHING_HTML
+            ),
+            params![text, id, revision],
+        )
+        .map_err(|e| format!("Update error: {}", e))?;
+
+    finish_update(&conn, id, changed, |stored| Snippet {
+        content: SnippetContent::Text(TextData::new(text)),
+        ..stored.clone()
+    })
+}
+
+/// Update a text snippet, unless it changed since `revision`.
+/// Rich text no longer matching the edited text is dropped.
+pub fn update_text(
+    id: i64,
+    text: String,
+    label: String,
+    description: Option<String>,
+    revision: i64,
+) -> Result<Snippet, SaveError> {
+    let conn = connection()?;
+    let changed = conn
+        .execute(
+            &format!(
+                "UPDATE snippets SET text_content = ?1, label = ?2, description = ?3, updated_at = CURRENT_TIMESTAMP, revision = revision + 1, {} \
+                 WHERE id = ?4 AND kind = 'text' AND revision = ?5",
+                KEEP_MATCHING_HTML
+            ),
+            params![text, label, description, id, revision],
+        )
+        .map_err(|e| format!("Update error: {}", e))?;
+
+    finish_update(&conn, id, changed, |stored| Snippet {
+        label,
+        description,
+        content: SnippetContent::Text(TextData::new(text)),
+        ..stored.clone()
+    })
+}
+
+/// Save the label, description and content of an edited snippet, unless it
+/// changed since `revision`.
+pub fn update_snippet(snippet: Snippet, revision: i64) -> Result<Snippet, SaveError> {
+    let (id, label, description) = (snippet.id, snippet.label, snippet.description);
+    match snippet.content {
+        SnippetContent::Color(color) => update_color(id, color, label, description, revision),
+        SnippetContent::ColorSet(set) => update_color_set(id, set, label, revision),
+        SnippetContent::Gradient(gradient) => {
+            update_gradient(id, gradient, label, description, revision)
+        }
+        SnippetContent::Code(code) => {
+            update_code(id, code.code, code.language, label, description, revision)
+        }
+        SnippetContent::Json(json) => update_json(id, json.json, label, description, revision),
+        SnippetContent::Text(text) => update_text(id, text.text, label, description, revision),
+        SnippetContent::File(_) => update_file_label(id, label, revision),
+    }
+}
+
+/// Update a color set snippet, unless it changed since `revision`.
+pub fn update_color_set(
+    id: i64,
+    set: ColorSetData,
+    label: String,
+    revision: i64,
+) -> Result<Snippet, SaveError> {
+    let conn = connection()?;
+    let changed = conn
+        .execute(
+            "UPDATE snippets SET color_set = ?1, label = ?2, updated_at = CURRENT_TIMESTAMP, revision = revision + 1 \
+             WHERE id = ?3 AND kind = 'colorset' AND revision = ?4",
+            params![set.to_json(), label, id, revision],
+        )
+        .map_err(|e| format!("Update error: {}", e))?;
+
+    finish_update(&conn, id, changed, |stored| Snippet {
+        label,
+        content: SnippetContent::ColorSet(set),
+        ..stored.clone()
+    })
+}
+
+/// Update a gradient snippet, unless it changed since `revision`.
+pub fn update_gradient(
+    id: i64,
+    gradient: GradientData,
+    label: String,
+    description: Option<String>,
+    revision: i64,
+) -> Result<Snippet, SaveError> {
+    let conn = connection()?;
+    update_gradient_on(&conn, id, gradient, label, description, revision)
+}
+
+fn update_gradient_on(
+    conn: &Connection,
+    id: i64,
+    gradient: GradientData,
+    label: String,
+    description: Option<String>,
+    revision: i64,
+) -> Result<Snippet, SaveError> {
+    let changed = conn
+        .execute(
+            "UPDATE snippets SET gradient = ?1, label = ?2, description = ?3, updated_at = CURRENT_TIMESTAMP, revision = revision + 1 \
+             WHERE id = ?4 AND kind = 'gradient' AND revision = ?5",
+            params![gradient.to_json(), label, description, id, revision],
+        )
+        .map_err(|e| format!("Update error: {}", e))?;
+
+    finish_update(conn, id, changed, |stored| Snippet {
+        label,
+        description,
+        content: SnippetContent::Gradient(gradient),
+        ..stored.clone()
+    })
+}
+
+/// Combine colors into a new color set snippet, keeping them in the given
+/// order. The color snippets themselves are kept.
+pub fn combine_colors(ids: Vec<i64>, label: String) -> Result<Snippet, String> {
+    let conn = connection()?;
+    let mut colors = Vec::new();
+    for id in ids {
+        if let SnippetContent::Color(color) = fetch_snippet(&conn, id)?.content {
+            colors.push(color);
+        }
+    }
+    if colors.len() < 2 {
+        return Err("Select at least two colors".to_string());
+    }
+    insert_snippet_on(
+        &conn,
+        Snippet::new(label, SnippetContent::ColorSet(ColorSetData::new(colors))),
+    )
+}
+
+/// Insert several snippets in one transaction, the first one on top.
+pub fn insert_snippets(snippets: Vec<Snippet>) -> Result<Vec<Snippet>, String> {
+    let conn = connection()?;
+    insert_snippets_on(&conn, snippets)
+}
+
+fn insert_snippets_on(conn: &Connection, snippets: Vec<Snippet>) -> Result<Vec<Snippet>, String> {
+    let tx = conn
+        .unchecked_transaction()
+        .map_err(|e| format!("Transaction error: {}", e))?;
+    let mut inserted = Vec::new();
+    for snippet in snippets.into_iter().rev() {
+        inserted.push(insert_snippet_on(&tx, snippet)?);
+    }
+    tx.commit()
+        .map_err(|e| format!("Transaction error: {}", e))?;
+
+    inserted.reverse();
+    Ok(inserted)
+}
+
+/// Split a color set into one color snippet per color and move the set to
+/// the trash. Returns the new color snippets, first color first.
+pub fn split_color_set(id: i64) -> Result<Vec<Snippet>, String> {
+    let conn = connection()?;
+    split_color_set_on(&conn, id)
+}
+
+fn split_color_set_on(conn: &Connection, id: i64) -> Result<Vec<Snippet>, String> {
+    let set = fetch_snippet(conn, id)?;
+    let SnippetContent::ColorSet(data) = &set.content else {
+        return Err("Not a color set".to_string());
+    };
+
+    let tx = conn
+        .unchecked_transaction()
+        .map_err(|e| format!("Transaction error: {}", e))?;
+    // Insert the last color first, so the first one ends up on top
+    let labels = split_labels(&set.label, data.colors.len());
+    let mut colors = Vec::new();
+    for (color, label) in data.colors.iter().zip(labels).rev() {
+        colors.push(insert_snippet_on(
+            &tx,
+            Snippet::new(label, SnippetContent::Color(color.clone())),
+        )?);
+    }
+    tx.execute(
+        "UPDATE snippets SET deleted_at = CURRENT_TIMESTAMP WHERE id = ?1",
+        params![id],
+    )
+    .map_err(|e| format!("Delete error: {}", e))?;
+    tx.commit()
+        .map_err(|e| format!("Transaction error: {}", e))?;
+
+    colors.reverse();
+    Ok(colors)
+}
+
+/// Update the label of a file snippet (the stored file never changes),
+/// unless it changed since `revision`.
+fn update_file_label(id: i64, label: String, revision: i64) -> Result<Snippet, SaveError> {
+    let conn = connection()?;
+    let changed = conn
+        .execute(
+            "UPDATE snippets SET label = ?1, updated_at = CURRENT_TIMESTAMP, revision = revision + 1 \
+             WHERE id = ?2 AND kind = 'file' AND revision = ?3",
+            params![label, id, revision],
+        )
+        .map_err(|e| format!("Update error: {}", e))?;
+
+    finish_update(&conn, id, changed, |stored| Snippet {
+        label,
+        ..stored.clone()
+    })
+}
+
+/// Copy a file into the attachments folder and add it as a file snippet.
+pub fn attach_file(source: PathBuf, description: Option<String>) -> Result<Snippet, String> {
+    let metadata = std::fs::metadata(&source).map_err(|e| format!("IO error: {}", e))?;
+    if !metadata.is_file() {
+        return Err(format!("{} is not a file", source.display()));
+    }
+    let name = source
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .ok_or_else(|| "File has no name".to_string())?;
+
+    let dir = attachments_dir()?;
+    std::fs::create_dir_all(&dir).map_err(|e| format!("IO error: {}", e))?;
+    let stored = dir.join(stored_file_name(&nanoid::nanoid!(8), &name));
+    std::fs::copy(&source, &stored).map_err(|e| format!("Copy error: {}", e))?;
+
+    let file = FileData::new(
+        name.clone(),
+        stored.to_string_lossy().into_owned(),
+        metadata.len(),
+    );
+    insert_snippet(Snippet::new(name, SnippetContent::File(file)).with_description(description))
+        .inspect_err(|_| {
+            let _ = std::fs::remove_file(&stored);
+        })
+}
+
+/// Get the stored copies of the file snippets matching a SQL condition,
+/// to remove once their rows are deleted.
+fn stored_files_where(
+    conn: &Connection,
+    condition: &str,
+    params: impl rusqlite::Params,
+) -> Result<Vec<PathBuf>, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT attachment_path FROM snippets WHERE kind = 'file' AND attachment_path IS NOT NULL AND {}",
+            condition
+        ))
+        .map_err(|e| format!("Query error: {}", e))?;
+    let paths = stmt
+        .query_map(params, |row| row.get::<_, String>(0))
+        .map_err(|e| format!("Query error: {}", e))?
+        .collect::<SqlResult<Vec<_>>>()
+        .map_err(|e| format!("Query error: {}", e))?;
+    Ok(paths.into_iter().map(PathBuf::from).collect())
+}
+
+/// Remove stored copies of deleted file snippets; missing files are ignored.
+fn remove_stored_files(paths: &[PathBuf]) {
+    for path in paths {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+/// Get a snippet by ID.
+#[allow(dead_code)]
+pub fn get_snippet_by_id(id: i64) -> Result<Option<Snippet>, String> {
+    let conn = connection()?;
+    query_snippet(&conn, id)
+}
+
+/// Get a snippet that was just updated, failing if it is gone.
+fn fetch_snippet(conn: &Connection, id: i64) -> Result<Snippet, String> {
+    query_snippet(conn, id)?.ok_or_else(|| "Snippet not found".to_string())
+}
+
+/// Query a snippet by ID on a connection.
+fn query_snippet(conn: &Connection, id: i64) -> Result<Option<Snippet>, String> {
+    let result = conn.query_row(
+        &format!("SELECT {} FROM snippets WHERE id = ?1", SNIPPET_COLUMNS),
+        params![id],
+        snippet_from_row,
+    );
+
+    match result {
+        Ok(snippet) => Ok(Some(snippet)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(format!("Query error: {}", e)),
+    }
+}
+
+/// Permanently delete a snippet by ID.
+pub fn delete_snippet(id: i64) -> Result<i64, String> {
+    let conn = connection()?;
+    let files = stored_files_where(&conn, "id = ?1", params![id])?;
+    conn.execute("DELETE FROM snippets WHERE id = ?1", params![id])
+        .map_err(|e| format!("Delete error: {}", e))?;
+    remove_stored_files(&files);
+    Ok(id)
+}
+
+/// Count a copy of a snippet.
+pub fn record_copy(id: i64) -> Result<(), String> {
+    let conn = connection()?;
+    conn.execute(
+        "UPDATE snippets SET copy_count = copy_count + 1, last_copied_at = CURRENT_TIMESTAMP WHERE id = ?1",
+        params![id],
+    )
+    .map_err(|e| format!("Update error: {}", e))?;
+    Ok(())
+}
+
+/// Add a snippet to or remove it from the favorites bar.
+pub fn set_favorite(id: i64, favorite: bool) -> Result<Snippet, String> {
+    let conn = connection()?;
+    let sql = if favorite {
+        "UPDATE snippets SET favorite_at = CURRENT_TIMESTAMP WHERE id = ?1"
+    } else {
+        "UPDATE snippets SET favorite_at = NULL WHERE id = ?1"
+    };
+    conn.execute(sql, params![id])
+        .map_err(|e| format!("Update error: {}", e))?;
+
+    fetch_snippet(&conn, id)
+}
+
+/// Remember the file each snippet was imported from or exported to,
+/// as `(snippet_id, path, hash of the file contents)`.
+pub fn link_files(links: Vec<(i64, String, String)>) -> Result<(), String> {
+    let conn = connection()?;
+    link_files_on(&conn, &links)
+}
+
+fn link_files_on(conn: &Connection, links: &[(i64, String, String)]) -> Result<(), String> {
+    let tx = conn
+        .unchecked_transaction()
+        .map_err(|e| format!("Transaction error: {}", e))?;
+
+    for (id, path, hash) in links {
+        tx.execute(
+            "UPDATE snippets SET file_path = ?1, file_hash = ?2 WHERE id = ?3",
+            params![path, hash, id],
+        )
+        .map_err(|e| format!("Update error: {}", e))?;
+    }
+
+    tx.commit().map_err(|e| format!("Transaction error: {}", e))
+}
+
+/// Set or clear (None) the custom icon of a snippet.
+pub fn set_icon(id: i64, icon: Option<String>) -> Result<Snippet, String> {
+    let conn = connection()?;
+    conn.execute(
+        "UPDATE snippets SET icon = ?1 WHERE id = ?2",
+        params![icon, id],
+    )
+    .map_err(|e| format!("Update error: {}", e))?;
+
+    fetch_snippet(&conn, id)
+}
+
+/// Set or clear (None) the text-expander abbreviation of a snippet.
+pub fn set_abbreviation(id: i64, abbreviation: Option<String>) -> Result<Snippet, String> {
+    let conn = connection()?;
+    set_abbreviation_on(&conn, id, abbreviation)
+}
+
+fn set_abbreviation_on(
+    conn: &Connection,
+    id: i64,
+    abbreviation: Option<String>,
+) -> Result<Snippet, String> {
+    conn.execute(
+        "UPDATE snippets SET abbreviation = ?1 WHERE id = ?2",
+        params![abbreviation, id],
+    )
+    .map_err(|e| format!("Update error: {}", e))?;
+
+    fetch_snippet(conn, id)
+}
+
+// ============================================================================
+// Trash
+// ============================================================================
+
+/// Move a snippet to the trash. Palette memberships are kept for restoring.
+pub fn trash_snippet(id: i64) -> Result<i64, String> {
+    let conn = connection()?;
+    conn.execute(
+        "UPDATE snippets SET deleted_at = CURRENT_TIMESTAMP WHERE id = ?1",
+        params![id],
+    )
+    .map_err(|e| format!("Delete error: {}", e))?;
+    Ok(id)
+}
+
+/// Move several snippets to the trash at once; either all of them move or none do.
+pub fn trash_snippets(ids: Vec<i64>) -> Result<Vec<i64>, String> {
+    let conn = connection()?;
+    trash_all(&conn, &ids)?;
+    Ok(ids)
+}
+
+fn trash_all(conn: &Connection, ids: &[i64]) -> Result<(), String> {
+    let tx = conn
+        .unchecked_transaction()
+        .map_err(|e| format!("Transaction error: {}", e))?;
+    {
+        let mut stmt = tx
+            .prepare("UPDATE snippets SET deleted_at = CURRENT_TIMESTAMP WHERE id = ?1")
+            .map_err(|e| format!("Delete error: {}", e))?;
+        for id in ids {
+            stmt.execute(params![id])
+                .map_err(|e| format!("Delete error: {}", e))?;
+        }
+    }
+    tx.commit().map_err(|e| format!("Transaction error: {}", e))
+}
+
+/// Load trashed snippets, most recently deleted first.
+pub fn load_trash() -> Result<Vec<Snippet>, String> {
+    let conn = connection()?;
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM snippets WHERE deleted_at IS NOT NULL ORDER BY deleted_at DESC",
+            SNIPPET_COLUMNS
+        ))
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    let snippets = stmt
+        .query_map([], snippet_from_row)
+        .map_err(|e| format!("Query error: {}", e))?
+        .collect::<SqlResult<Vec<_>>>()
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    Ok(snippets)
+}
+
+/// Take a snippet out of the trash.
+pub fn restore_snippet(id: i64) -> Result<Snippet, String> {
+    let conn = connection()?;
+    conn.execute(
+        "UPDATE snippets SET deleted_at = NULL WHERE id = ?1",
+        params![id],
+    )
+    .map_err(|e| format!("Restore error: {}", e))?;
+
+    fetch_snippet(&conn, id)
+}
+
+/// Permanently delete snippets trashed more than `days` days ago.
+/// Returns the number of snippets removed.
+pub fn purge_trash(days: u32) -> Result<usize, String> {
+    let conn = connection()?;
+    let condition = "deleted_at IS NOT NULL AND deleted_at < datetime('now', ?1)";
+    let age = format!("-{} days", days);
+    let files = stored_files_where(&conn, condition, params![age])?;
+    let removed = conn
+        .execute(
+            &format!("DELETE FROM snippets WHERE {}", condition),
+            params![age],
+        )
+        .map_err(|e| format!("Purge error: {}", e))?;
+    remove_stored_files(&files);
+    Ok(removed)
+}
+
+/// Permanently delete every trashed snippet.
+pub fn empty_trash() -> Result<usize, String> {
+    let conn = connection()?;
+    let files = stored_files_where(&conn, "deleted_at IS NOT NULL", [])?;
+    let removed = conn
+        .execute("DELETE FROM snippets WHERE deleted_at IS NOT NULL", [])
+        .map_err(|e| format!("Purge error: {}", e))?;
+    remove_stored_files(&files);
+    Ok(removed)
+}
+
+/// Move a snippet to the top (highest position).
+fn move_to_top_on(conn: &Connection, id: i64) -> Result<(), String> {
+    let next_pos = get_next_position(conn)?;
+
+    conn.execute(
+        "UPDATE snippets SET position = ?1 WHERE id = ?2",
+        params![next_pos, id],
+    )
+    .map_err(|e| format!("Update error: {}", e))?;
+
+    Ok(())
+}
+
+/// Find a saved color that duplicates `color` under `dedupe`. Returns the ID if found.
+/// When several match, the perceptually closest wins.
+fn find_duplicate_color_on(
+    conn: &Connection,
+    color: &ColorData,
+    dedupe: ColorDedupe,
+) -> Result<Option<i64>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, r, g, b, a FROM snippets WHERE kind = 'color' AND deleted_at IS NULL ORDER BY position DESC",
+        )
+        .map_err(|e| format!("Query error: {}", e))?;
+    let rows = stmt
+        .query_map([], |row| {
+            let r: i32 = row.get(1)?;
+            let g: i32 = row.get(2)?;
+            let b: i32 = row.get(3)?;
+            let a: f64 = row.get(4)?;
+            Ok((
+                row.get::<_, i64>(0)?,
+                ColorData::new(r as u8, g as u8, b as u8, a as f32),
+            ))
+        })
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    let mut best: Option<(i64, f32)> = None;
+    for row in rows {
+        let (id, saved) = row.map_err(|e| format!("Row error: {}", e))?;
+        if !dedupe.is_duplicate(color, &saved) {
+            continue;
+        }
+        let distance = color.delta_e(&saved);
+        if best.is_none_or(|(_, best_distance)| distance < best_distance) {
+            best = Some((id, distance));
+        }
+    }
+    Ok(best.map(|(id, _)| id))
+}
+
+/// Add a color snippet, handling duplicates by moving existing to top.
+pub fn add_or_move_color(
+    r: u8,
+    g: u8,
+    b: u8,
+    a: f32,
+    label: String,
+    dedupe: ColorDedupe,
+) -> Result<Snippet, String> {
+    let conn = connection()?;
+    add_or_move_color_on(&conn, ColorData::new(r, g, b, a), label, dedupe)
+}
+
+/// Add a snippet made in an editor or imported. Colors that already exist
+/// are moved to the top instead, keeping their own label and description;
+/// JSON documents that do not parse are rejected.
+pub fn add_snippet(snippet: Snippet, dedupe: ColorDedupe) -> Result<Snippet, String> {
+    let conn = connection()?;
+    add_snippet_on(&conn, snippet, dedupe)
+}
+
+fn add_snippet_on(
+    conn: &Connection,
+    snippet: Snippet,
+    dedupe: ColorDedupe,
+) -> Result<Snippet, String> {
+    match &snippet.content {
+        SnippetContent::Color(color) => {
+            if let Some(existing_id) = find_duplicate_color_on(conn, color, dedupe)? {
+                move_to_top_on(conn, existing_id)?;
+                return fetch_snippet(conn, existing_id);
+            }
+        }
+        SnippetContent::Json(json) => {
+            parse_json(&json.json)?;
+        }
+        _ => {}
+    }
+    insert_snippet_on(conn, snippet)
+}
+
+fn add_or_move_color_on(
+    conn: &Connection,
+    color: ColorData,
+    label: String,
+    dedupe: ColorDedupe,
+) -> Result<Snippet, String> {
+    // Snippet::color handles empty label with nanoid
+    add_snippet_on(
+        conn,
+        Snippet::color(color.r, color.g, color.b, color.a, label),
+        dedupe,
+    )
+}
+
+/// Add a code snippet.
+pub fn add_code_snippet(code: String, language: String, label: String) -> Result<Snippet, String> {
+    insert_snippet(Snippet::code(code, language, label))
+}
+
+/// Add a JSON snippet; documents that do not parse are rejected.
+pub fn add_json_snippet(json: String, label: String) -> Result<Snippet, String> {
+    parse_json(&json)?;
+    insert_snippet(Snippet::json(json, label))
+}
+
+/// Add a text snippet.
+pub fn add_text_snippet(text: String, label: String) -> Result<Snippet, String> {
+    insert_snippet(Snippet::text(text, label))
+}
+
+/// Add an auto-captured snippet to the inbox.
+/// Colors that already exist are moved to the top and keep their triage state.
+pub fn add_captured_snippet(snippet: Snippet, dedupe: ColorDedupe) -> Result<Snippet, String> {
+    let conn = connection()?;
+    add_captured_snippet_on(&conn, snippet, dedupe)
+}
+
+fn add_captured_snippet_on(
+    conn: &Connection,
+    snippet: Snippet,
+    dedupe: ColorDedupe,
+) -> Result<Snippet, String> {
+    if let SnippetContent::Color(color) = &snippet.content {
+        if let Some(existing_id) = find_duplicate_color_on(conn, color, dedupe)? {
+            move_to_top_on(conn, existing_id)?;
+            return fetch_snippet(conn, existing_id);
+        }
+    }
+
+    insert_snippet_on(
+        conn,
+        Snippet {
+            in_inbox: true,
+            ..snippet
+        },
+    )
+}
+
+/// Move a snippet out of the inbox into the library.
+pub fn keep_inbox_snippet(id: i64) -> Result<i64, String> {
+    let conn = connection()?;
+    conn.execute("UPDATE snippets SET inbox = 0 WHERE id = ?1", params![id])
+        .map_err(|e| format!("Update error: {}", e))?;
+    Ok(id)
+}
+
+// ============================================================================
+// Palette Operations
+// ============================================================================
+
+/// A palette for organizing snippets.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Palette {
+    pub id: i64,
+    pub name: String,
+    /// Query selecting the members of a smart palette; regular palettes
+    /// have their members assigned by hand.
+    pub query: Option<PaletteQuery>,
+    /// Palette this one is nested in; top-level palettes have none.
+    pub parent_id: Option<i64>,
+    /// Palette holding the dark-mode variants of this one's colors, used
+    /// when exporting mobile assets.
+    pub dark_palette_id: Option<i64>,
+}
+
+impl Palette {
+    /// Check if the palette's members come from its query.
+    pub fn is_smart(&self) -> bool {
+        self.query.is_some()
+    }
+}
+
+/// Get a palette and all palettes nested below it, parents before children.
+pub fn palette_with_descendants(palettes: &[Palette], id: i64) -> Vec<&Palette> {
+    let mut found: Vec<&Palette> = palettes.iter().filter(|p| p.id == id).collect();
+    let mut next = 0;
+    while next < found.len() {
+        let parent = found[next].id;
+        // Guard against cycles in hand-edited databases
+        found.extend(
+            palettes
+                .iter()
+                .filter(|p| p.parent_id == Some(parent) && p.id != id),
+        );
+        next += 1;
+    }
+    found
+}
+
+/// Get a palette's name prefixed with its ancestors, e.g. `Brand / Web`.
+pub fn palette_path(palettes: &[Palette], id: i64) -> String {
+    let mut names = Vec::new();
+    let mut current = palettes.iter().find(|p| p.id == id);
+    while let Some(palette) = current {
+        if names.len() > palettes.len() {
+            break;
+        }
+        names.push(palette.name.as_str());
+        current = palette
+            .parent_id
+            .and_then(|parent| palettes.iter().find(|p| p.id == parent));
+    }
+    names.reverse();
+    names.join(" / ")
+}
+
+/// Load all palettes from the database.
+pub fn load_palettes() -> Result<Vec<Palette>, String> {
+    let conn = connection()?;
+    load_palettes_on(&conn)
+}
+
+fn load_palettes_on(conn: &Connection) -> Result<Vec<Palette>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, name, query, parent_id, dark_palette_id FROM palettes ORDER BY name ASC",
+        )
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    let palettes = stmt
+        .query_map([], |row| {
+            let query: Option<String> = row.get(2)?;
+            Ok(Palette {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                // Queries are validated when saved
+                query: query.and_then(|q| PaletteQuery::parse(&q).ok()),
+                parent_id: row.get(3)?,
+                dark_palette_id: row.get(4)?,
+            })
+        })
+        .map_err(|e| format!("Query error: {}", e))?
+        .collect::<SqlResult<Vec<_>>>()
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    Ok(palettes)
+}
+
+/// Create a new palette.
+pub fn create_palette(name: String) -> Result<Palette, String> {
+    let conn = connection()?;
+
+    conn.execute("INSERT INTO palettes (name) VALUES (?1)", params![name])
+        .map_err(|e| format!("Insert error: {}", e))?;
+
+    let id = conn.last_insert_rowid();
+    Ok(Palette {
+        id,
+        name,
+        query: None,
+        parent_id: None,
+        dark_palette_id: None,
+    })
+}
+
+/// Create a smart palette whose members are the snippets matching `query`.
+pub fn create_smart_palette(name: String, query: PaletteQuery) -> Result<Palette, String> {
+    let conn = connection()?;
+
+    conn.execute(
+        "INSERT INTO palettes (name, query) VALUES (?1, ?2)",
+        params![name, query.to_string()],
+    )
+    .map_err(|e| format!("Insert error: {}", e))?;
+
+    let id = conn.last_insert_rowid();
+    Ok(Palette {
+        id,
+        name,
+        query: Some(query),
+        parent_id: None,
+        dark_palette_id: None,
+    })
+}
+
+/// Rename a palette.
+pub fn rename_palette(id: i64, new_name: String) -> Result<Palette, String> {
+    let conn = connection()?;
+
+    conn.execute(
+        "UPDATE palettes SET name = ?1 WHERE id = ?2",
+        params![new_name, id],
+    )
+    .map_err(|e| format!("Update error: {}", e))?;
+
+    let (query, parent_id, dark_palette_id): (Option<String>, Option<i64>, Option<i64>) = conn
+        .query_row(
+            "SELECT query, parent_id, dark_palette_id FROM palettes WHERE id = ?1",
+            params![id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    Ok(Palette {
+        id,
+        name: new_name,
+        query: query.and_then(|q| PaletteQuery::parse(&q).ok()),
+        parent_id,
+        dark_palette_id,
+    })
+}
+
+/// Nest a palette inside another one, or move it to the top level with `None`.
+/// Returns the palette's id and new parent.
+pub fn set_palette_parent(id: i64, parent_id: Option<i64>) -> Result<(i64, Option<i64>), String> {
+    let conn = connection()?;
+    move_palette(&conn, id, parent_id)?;
+    Ok((id, parent_id))
+}
+
+/// Set a palette's parent, refusing moves that would nest it inside itself.
+fn move_palette(conn: &Connection, id: i64, parent_id: Option<i64>) -> Result<(), String> {
+    if let Some(parent_id) = parent_id {
+        let is_descendant: bool = conn
+            .query_row(
+                "WITH RECURSIVE subtree(id) AS (
+                    SELECT ?1
+                    UNION SELECT palettes.id FROM palettes JOIN subtree ON palettes.parent_id = subtree.id
+                 )
+                 SELECT EXISTS (SELECT 1 FROM subtree WHERE id = ?2)",
+                params![id, parent_id],
+                |row| row.get(0),
+            )
+            .map_err(|e| format!("Query error: {}", e))?;
+        if is_descendant {
+            return Err("A palette can't be moved inside itself".to_string());
+        }
+    }
+
+    conn.execute(
+        "UPDATE palettes SET parent_id = ?1 WHERE id = ?2",
+        params![parent_id, id],
+    )
+    .map_err(|e| format!("Update error: {}", e))?;
+
+    Ok(())
+}
+
+/// Link a palette to the palette holding its dark-mode variants, or unlink
+/// it with `None`. Returns the palette's id and its new dark variant.
+pub fn set_dark_palette(id: i64, dark_id: Option<i64>) -> Result<(i64, Option<i64>), String> {
+    let conn = connection()?;
+    link_dark_palette(&conn, id, dark_id)?;
+    Ok((id, dark_id))
+}
+
+fn link_dark_palette(conn: &Connection, id: i64, dark_id: Option<i64>) -> Result<(), String> {
+    if dark_id == Some(id) {
+        return Err("A palette can't be its own dark variant".to_string());
+    }
+    conn.execute(
+        "UPDATE palettes SET dark_palette_id = ?1 WHERE id = ?2",
+        params![dark_id, id],
+    )
+    .map_err(|e| format!("Update error: {}", e))?;
+
+    Ok(())
+}
+
+/// Delete a palette (cascade deletes palette_snippets entries).
+/// Nested palettes move up to the deleted palette's parent.
+pub fn delete_palette(id: i64) -> Result<i64, String> {
+    let conn = connection()?;
+    remove_palette(&conn, id)?;
+    Ok(id)
+}
+
+fn remove_palette(conn: &Connection, id: i64) -> Result<(), String> {
+    conn.execute(
+        "UPDATE palettes SET parent_id = (SELECT parent_id FROM palettes WHERE id = ?1)
+         WHERE parent_id = ?1",
+        params![id],
+    )
+    .map_err(|e| format!("Update error: {}", e))?;
+    conn.execute("DELETE FROM palettes WHERE id = ?1", params![id])
+        .map_err(|e| format!("Delete error: {}", e))?;
+
+    Ok(())
+}
+
+/// Add a snippet to a palette.
+pub fn add_snippet_to_palette(palette_id: i64, snippet_id: i64) -> Result<(), String> {
+    let conn = connection()?;
+
+    conn.execute(
+        "INSERT OR IGNORE INTO palette_snippets (palette_id, snippet_id) VALUES (?1, ?2)",
+        params![palette_id, snippet_id],
+    )
+    .map_err(|e| format!("Insert error: {}", e))?;
+
+    Ok(())
+}
+
+/// Add several snippets to a palette in one transaction.
+/// Returns the palette's id and the snippets added.
+pub fn add_snippets_to_palette(
+    palette_id: i64,
+    snippet_ids: Vec<i64>,
+) -> Result<(i64, Vec<i64>), String> {
+    let conn = connection()?;
+    let tx = conn
+        .unchecked_transaction()
+        .map_err(|e| format!("Transaction error: {}", e))?;
+    {
+        let mut stmt = tx
+            .prepare(
+                "INSERT OR IGNORE INTO palette_snippets (palette_id, snippet_id) VALUES (?1, ?2)",
+            )
+            .map_err(|e| format!("Insert error: {}", e))?;
+        for snippet_id in &snippet_ids {
+            stmt.execute(params![palette_id, snippet_id])
+                .map_err(|e| format!("Insert error: {}", e))?;
+        }
+    }
+    tx.commit()
+        .map_err(|e| format!("Transaction error: {}", e))?;
+
+    Ok((palette_id, snippet_ids))
+}
+
+/// Remove a snippet from a palette.
+pub fn remove_snippet_from_palette(palette_id: i64, snippet_id: i64) -> Result<(), String> {
+    let conn = connection()?;
+
+    conn.execute(
+        "DELETE FROM palette_snippets WHERE palette_id = ?1 AND snippet_id = ?2",
+        params![palette_id, snippet_id],
+    )
+    .map_err(|e| format!("Delete error: {}", e))?;
+
+    Ok(())
+}
+
+/// Get all palette IDs for a snippet.
+#[allow(dead_code)]
+pub fn get_palettes_for_snippet(snippet_id: i64) -> Result<Vec<i64>, String> {
+    let conn = connection()?;
+
+    let mut stmt = conn
+        .prepare("SELECT palette_id FROM palette_snippets WHERE snippet_id = ?1")
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    let palette_ids = stmt
+        .query_map(params![snippet_id], |row| row.get(0))
+        .map_err(|e| format!("Query error: {}", e))?
+        .collect::<SqlResult<Vec<i64>>>()
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    Ok(palette_ids)
+}
+
+/// Get the palette IDs of every snippet that belongs to a palette.
+pub fn load_snippet_palettes() -> Result<HashMap<i64, Vec<i64>>, String> {
+    let conn = connection()?;
+
+    let mut stmt = conn
+        .prepare("SELECT snippet_id, palette_id FROM palette_snippets")
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    let pairs = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+        .map_err(|e| format!("Query error: {}", e))?
+        .collect::<SqlResult<Vec<(i64, i64)>>>()
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    let mut snippet_palettes: HashMap<i64, Vec<i64>> = HashMap::new();
+    for (snippet_id, palette_id) in pairs {
+        snippet_palettes
+            .entry(snippet_id)
+            .or_default()
+            .push(palette_id);
+    }
+    Ok(snippet_palettes)
+}
+
+/// Get all snippet IDs in a palette.
+#[allow(dead_code)]
+pub fn get_snippets_in_palette(palette_id: i64) -> Result<Vec<i64>, String> {
+    let conn = connection()?;
+
+    let mut stmt = conn
+        .prepare("SELECT snippet_id FROM palette_snippets WHERE palette_id = ?1")
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    let snippet_ids = stmt
+        .query_map(params![palette_id], |row| row.get(0))
+        .map_err(|e| format!("Query error: {}", e))?
+        .collect::<SqlResult<Vec<i64>>>()
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    Ok(snippet_ids)
+}
+
+// ============================================================================
+// Sections
+// ============================================================================
+
+/// A named header in the manual order, grouping the snippets below it
+/// up to the next header.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Section {
+    pub id: i64,
+    pub name: String,
+    /// Shares the position sequence of snippets; higher is nearer the top.
+    pub position: i64,
+}
+
+/// Get the section a snippet at `position` falls under, if any.
+/// `sections` must be ordered by position, highest first.
+pub fn section_at(sections: &[Section], position: i64) -> Option<&Section> {
+    sections.iter().rev().find(|s| s.position > position)
+}
+
+/// Load all sections, highest position first.
+pub fn load_sections() -> Result<Vec<Section>, String> {
+    let conn = connection()?;
+
+    let mut stmt = conn
+        .prepare("SELECT id, name, position FROM sections ORDER BY position DESC")
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    let sections = stmt
+        .query_map([], |row| {
+            Ok(Section {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                position: row.get(2)?,
+            })
+        })
+        .map_err(|e| format!("Query error: {}", e))?
+        .collect::<SqlResult<Vec<_>>>()
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    Ok(sections)
+}
+
+/// Create a section right above a snippet, or at the top without one.
+pub fn create_section(name: String, above_snippet: Option<i64>) -> Result<(), String> {
+    let conn = connection()?;
+    insert_section(&conn, &name, above_snippet)
+}
+
+fn insert_section(conn: &Connection, name: &str, above_snippet: Option<i64>) -> Result<(), String> {
+    let tx = conn
+        .unchecked_transaction()
+        .map_err(|e| format!("Transaction error: {}", e))?;
+
+    let position = match above_snippet {
+        Some(id) => {
+            let below: i64 = tx
+                .query_row(
+                    "SELECT position FROM snippets WHERE id = ?1",
+                    params![id],
+                    |row| row.get(0),
+                )
+                .map_err(|e| format!("Query error: {}", e))?;
+            // Make room directly above the snippet
+            for table in ["snippets", "sections"] {
+                tx.execute(
+                    &format!(
+                        "UPDATE {} SET position = position + 1 WHERE position > ?1",
+                        table
+                    ),
+                    params![below],
+                )
+                .map_err(|e| format!("Update error: {}", e))?;
+            }
+            below + 1
+        }
+        None => get_next_position(&tx)?,
+    };
+
+    tx.execute(
+        "INSERT INTO sections (name, position) VALUES (?1, ?2)",
+        params![name, position],
+    )
+    .map_err(|e| format!("Insert error: {}", e))?;
+
+    tx.commit().map_err(|e| format!("Transaction error: {}", e))
+}
+
+/// Rename a section.
+pub fn rename_section(id: i64, name: String) -> Result<(), String> {
+    let conn = connection()?;
+    conn.execute(
+        "UPDATE sections SET name = ?1 WHERE id = ?2",
+        params![name, id],
+    )
+    .map_err(|e| format!("Update error: {}", e))?;
+    Ok(())
+}
+
+/// Delete a section header; its snippets join the section above.
+pub fn delete_section(id: i64) -> Result<i64, String> {
+    let conn = connection()?;
+    conn.execute("DELETE FROM sections WHERE id = ?1", params![id])
+        .map_err(|e| format!("Delete error: {}", e))?;
+    Ok(id)
+}
+
+// ============================================================================
+// Project Sync
+// ============================================================================
+
+/// Sync the given snippets into a project snippet file, replacing the ones
+/// synced there before, and write the file. Returns how many were written.
+pub fn sync_to_project(target: String, ids: Vec<i64>) -> Result<usize, String> {
+    let conn = connection()?;
+    set_project_sync(&conn, &target, &ids)?;
+
+    let (contents, count) = project_file_contents(&conn, &target)?;
+    let path = Path::new(&target);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("IO error: {}", e))?;
+    }
+    std::fs::write(path, contents).map_err(|e| format!("Write failed: {}", e))?;
+    Ok(count)
+}
+
+fn set_project_sync(conn: &Connection, target: &str, ids: &[i64]) -> Result<(), String> {
+    let tx = conn
+        .unchecked_transaction()
+        .map_err(|e| format!("Transaction error: {}", e))?;
+
+    tx.execute(
+        "DELETE FROM project_syncs WHERE target_path = ?1",
+        params![target],
+    )
+    .map_err(|e| format!("Delete error: {}", e))?;
+    for id in ids {
+        tx.execute(
+            "INSERT OR IGNORE INTO project_syncs (snippet_id, target_path) VALUES (?1, ?2)",
+            params![id, target],
+        )
+        .map_err(|e| format!("Insert error: {}", e))?;
+    }
+
+    tx.commit().map_err(|e| format!("Transaction error: {}", e))
+}
+
+/// Rewrite the project snippet files whose snippets changed since they were
+/// last written. Files of projects that no longer exist are skipped.
+/// Returns how many files were rewritten.
+pub fn sync_project_files() -> Result<usize, String> {
+    let conn = connection()?;
+    let mut stmt = conn
+        .prepare("SELECT DISTINCT target_path FROM project_syncs")
+        .map_err(|e| format!("Query error: {}", e))?;
+    let targets = stmt
+        .query_map([], |row| row.get::<_, String>(0))
+        .map_err(|e| format!("Query error: {}", e))?
+        .collect::<SqlResult<Vec<_>>>()
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    let mut rewritten = 0;
+    for target in targets {
+        let path = Path::new(&target);
+        if !path.parent().is_some_and(Path::is_dir) {
+            continue;
+        }
+        let (contents, _) = project_file_contents(&conn, &target)?;
+        if std::fs::read_to_string(path).ok().as_deref() == Some(contents.as_str()) {
+            continue;
+        }
+        std::fs::write(path, contents).map_err(|e| format!("Write failed: {}", e))?;
+        rewritten += 1;
+    }
+    Ok(rewritten)
+}
+
+/// Build a project snippet file from the code snippets synced to it,
+/// leaving out trashed ones. Returns the contents and the snippet count.
+fn project_file_contents(conn: &Connection, target: &str) -> Result<(String, usize), String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM snippets
+             WHERE kind = 'code' AND deleted_at IS NULL
+               AND id IN (SELECT snippet_id FROM project_syncs WHERE target_path = ?1)
+             ORDER BY position DESC",
+            SNIPPET_COLUMNS
+        ))
+        .map_err(|e| format!("Query error: {}", e))?;
+    let snippets = stmt
+        .query_map(params![target], snippet_from_row)
+        .map_err(|e| format!("Query error: {}", e))?
+        .collect::<SqlResult<Vec<_>>>()
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    let entries: Vec<(&str, &CodeData)> = snippets
+        .iter()
+        .filter_map(|s| match &s.content {
+            SnippetContent::Code(code) => Some((s.label.as_str(), code)),
+            _ => None,
+        })
+        .collect();
+    Ok((code_snippets_file(&entries), entries.len()))
+}
+
+// ============================================================================
+// Scratchpad
+// ============================================================================
+
+/// Load the scratchpad text (empty if nothing has been jotted yet).
+pub fn load_scratchpad() -> Result<String, String> {
+    let conn = connection()?;
+    let result = conn.query_row(
+        "SELECT value FROM meta WHERE key = 'scratchpad'",
+        [],
+        |row| row.get(0),
+    );
+
+    match result {
+        Ok(text) => Ok(text),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(String::new()),
+        Err(e) => Err(format!("Query error: {}", e)),
+    }
+}
+
+/// Save the scratchpad text.
+pub fn save_scratchpad(text: String) -> Result<(), String> {
+    let conn = connection()?;
+    conn.execute(
+        "INSERT OR REPLACE INTO meta (key, value) VALUES ('scratchpad', ?1)",
+        params![text],
+    )
+    .map_err(|e| format!("Scratchpad save error: {}", e))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backup_file_names_sort_by_time() {
+        let older = backup_file_name(999, "import");
+        let newer = backup_file_name(1_000, "migration");
+        assert_eq!(older, "shard-000000000999-import.db");
+        assert!(older < newer);
+    }
+
+    #[test]
+    fn test_snippet_kind_db_strings() {
+        assert_eq!(SnippetKind::Color.as_db_str(), "color");
+        assert_eq!(SnippetKind::Code.as_db_str(), "code");
+        assert_eq!(SnippetKind::Text.as_db_str(), "text");
+    }
+
+    /// An in-memory database set up like the shared connection.
+    fn test_connection() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        run_migrations(&conn).unwrap();
+        configure_connection(&conn).unwrap();
+        conn
+    }
+
+    /// Insert a snippet and a palette containing it, returning their ids.
+    fn insert_linked_snippet(conn: &Connection) -> (i64, i64) {
+        conn.execute(
+            "INSERT INTO snippets (kind, label, position, text_content) VALUES ('text', 'Note', 0, 'hi')",
+            [],
+        )
+        .unwrap();
+        let snippet_id = conn.last_insert_rowid();
+        conn.execute("INSERT INTO palettes (name) VALUES ('Work')", [])
+            .unwrap();
+        let palette_id = conn.last_insert_rowid();
+        conn.execute(
+            "INSERT INTO palette_snippets (palette_id, snippet_id) VALUES (?1, ?2)",
+            params![palette_id, snippet_id],
+        )
+        .unwrap();
+        (snippet_id, palette_id)
+    }
+
+    fn palette_link_count(conn: &Connection) -> i64 {
+        conn.query_row("SELECT COUNT(*) FROM palette_snippets", [], |row| {
+            row.get(0)
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_deleting_palette_cascades_to_links() {
+        let conn = test_connection();
+        let (snippet_id, palette_id) = insert_linked_snippet(&conn);
+
+        conn.execute("DELETE FROM palettes WHERE id = ?1", params![palette_id])
+            .unwrap();
+
+        assert_eq!(palette_link_count(&conn), 0);
+        let snippets: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM snippets WHERE id = ?1",
+                params![snippet_id],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(snippets, 1);
+    }
+
+    #[test]
+    fn test_deleting_snippet_cascades_to_links() {
+        let conn = test_connection();
+        let (snippet_id, _) = insert_linked_snippet(&conn);
+
+        conn.execute("DELETE FROM snippets WHERE id = ?1", params![snippet_id])
+            .unwrap();
+
+        assert_eq!(palette_link_count(&conn), 0);
+    }
+
+    #[test]
+    fn test_links_to_missing_rows_are_rejected() {
+        let conn = test_connection();
+        let result = conn.execute(
+            "INSERT INTO palette_snippets (palette_id, snippet_id) VALUES (1, 1)",
+            [],
+        );
+        assert!(result.is_err());
+    }
+
+    fn insert_palette(conn: &Connection, name: &str, parent_id: Option<i64>) -> i64 {
+        conn.execute(
+            "INSERT INTO palettes (name, parent_id) VALUES (?1, ?2)",
+            params![name, parent_id],
+        )
+        .unwrap();
+        conn.last_insert_rowid()
+    }
+
+    fn parent_of(conn: &Connection, id: i64) -> Option<i64> {
+        conn.query_row(
+            "SELECT parent_id FROM palettes WHERE id = ?1",
+            params![id],
+            |row| row.get(0),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_nested_palettes_reject_cycles() {
+        let conn = test_connection();
+        let brand = insert_palette(&conn, "Brand", None);
+        let web = insert_palette(&conn, "Web", Some(brand));
+        let buttons = insert_palette(&conn, "Buttons", Some(web));
+
+        assert!(move_palette(&conn, brand, Some(buttons)).is_err());
+        assert!(move_palette(&conn, brand, Some(brand)).is_err());
+        assert_eq!(parent_of(&conn, brand), None);
+
+        move_palette(&conn, buttons, Some(brand)).unwrap();
+        assert_eq!(parent_of(&conn, buttons), Some(brand));
+    }
+
+    #[test]
+    fn test_deleting_palette_moves_children_up() {
+        let conn = test_connection();
+        let brand = insert_palette(&conn, "Brand", None);
+        let web = insert_palette(&conn, "Web", Some(brand));
+        let buttons = insert_palette(&conn, "Buttons", Some(web));
+
+        remove_palette(&conn, web).unwrap();
+
+        assert_eq!(parent_of(&conn, buttons), Some(brand));
+    }
+
+    #[test]
+    fn test_deleting_dark_palette_unlinks_it() {
+        let conn = test_connection();
+        let brand = insert_palette(&conn, "Brand", None);
+        let brand_dark = insert_palette(&conn, "Brand Dark", None);
+
+        assert!(link_dark_palette(&conn, brand, Some(brand)).is_err());
+        link_dark_palette(&conn, brand, Some(brand_dark)).unwrap();
+        remove_palette(&conn, brand_dark).unwrap();
+
+        let dark: Option<i64> = conn
+            .query_row(
+                "SELECT dark_palette_id FROM palettes WHERE id = ?1",
+                params![brand],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(dark, None);
+    }
+
+    #[test]
+    fn test_palette_tree_helpers() {
+        let palette = |id, name: &str, parent_id| Palette {
+            id,
+            name: name.to_string(),
+            query: None,
+            parent_id,
+            dark_palette_id: None,
+        };
+        let palettes = vec![
+            palette(1, "Brand", None),
+            palette(2, "Buttons", Some(3)),
+            palette(3, "Web", Some(1)),
+            palette(4, "Print", Some(1)),
+            palette(5, "Other", None),
+        ];
+
+        let ids: Vec<i64> = palette_with_descendants(&palettes, 1)
+            .iter()
+            .map(|p| p.id)
+            .collect();
+        assert_eq!(ids, vec![1, 3, 4, 2]);
+        assert_eq!(palette_with_descendants(&palettes, 5).len(), 1);
+        assert_eq!(palette_path(&palettes, 2), "Brand / Web / Buttons");
+        assert_eq!(palette_path(&palettes, 5), "Other");
+    }
+
+    #[test]
+    fn test_trash_all_moves_every_snippet() {
+        let conn = test_connection();
+        let (first, _) = insert_linked_snippet(&conn);
+        conn.execute(
+            "INSERT INTO snippets (kind, label, position, text_content) VALUES ('text', 'Kept', 1, 'hi')",
+            [],
+        )
+        .unwrap();
+        let kept = conn.last_insert_rowid();
+
+        trash_all(&conn, &[first]).unwrap();
+
+        let trashed: Vec<i64> = conn
+            .prepare("SELECT id FROM snippets WHERE deleted_at IS NOT NULL")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<SqlResult<_>>()
+            .unwrap();
+        assert_eq!(trashed, vec![first]);
+        assert_ne!(first, kept);
+        // Palette links are kept for restoring
+        assert_eq!(palette_link_count(&conn), 1);
+    }
+
+    #[test]
+    fn test_set_language_all_skips_other_kinds() {
+        let conn = test_connection();
+        let (text_id, _) = insert_linked_snippet(&conn);
+        conn.execute(
+            "INSERT INTO snippets (kind, label, position, code, language) VALUES ('code', 'q', 1, 'SELECT 1', 'plain')",
+            [],
+        )
+        .unwrap();
+        let code_id = conn.last_insert_rowid();
+
+        let updated = set_language_all(&conn, &[text_id, code_id], "sql").unwrap();
+
+        assert_eq!(updated.len(), 1);
+        assert_eq!(updated[0].id, code_id);
+        assert!(
+            matches!(&updated[0].content, SnippetContent::Code(code) if code.language == "sql")
+        );
+        assert_eq!(updated[0].revision, 1);
+    }
+
+    #[test]
+    fn test_section_inserted_above_snippet() {
+        let conn = test_connection();
+        for position in 1..=3 {
+            conn.execute(
+                "INSERT INTO snippets (kind, label, position, text_content) VALUES ('text', 'n', ?1, 'hi')",
+                params![position],
+            )
+            .unwrap();
+        }
+        let middle: i64 = conn
+            .query_row("SELECT id FROM snippets WHERE position = 2", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+
+        insert_section(&conn, "Brand", Some(middle)).unwrap();
+        insert_section(&conn, "Top", None).unwrap();
+
+        let positions: Vec<i64> = conn
+            .prepare("SELECT position FROM snippets ORDER BY position")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<SqlResult<_>>()
+            .unwrap();
+        assert_eq!(positions, vec![1, 2, 4]);
+        let sections: Vec<(String, i64)> = conn
+            .prepare("SELECT name, position FROM sections ORDER BY position")
+            .unwrap()
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .collect::<SqlResult<_>>()
+            .unwrap();
+        assert_eq!(
+            sections,
+            vec![("Brand".to_string(), 3), ("Top".to_string(), 5)]
+        );
+    }
+
+    #[test]
+    fn test_section_at() {
+        let section = |id, position| Section {
+            id,
+            name: String::new(),
+            position,
+        };
+        let sections = vec![section(1, 10), section(2, 5)];
+
+        assert_eq!(section_at(&sections, 12), None);
+        assert_eq!(section_at(&sections, 9).map(|s| s.id), Some(1));
+        assert_eq!(section_at(&sections, 4).map(|s| s.id), Some(2));
+    }
+
+    #[test]
+    fn test_link_files_is_loaded_with_snippet() {
+        let conn = test_connection();
+        let (snippet_id, _) = insert_linked_snippet(&conn);
+
+        link_files_on(
+            &conn,
+            &[(
+                snippet_id,
+                "/tmp/note.txt".to_string(),
+                "abc123".to_string(),
+            )],
+        )
+        .unwrap();
+
+        let snippet = fetch_snippet(&conn, snippet_id).unwrap();
+        assert_eq!(snippet.file_path.as_deref(), Some("/tmp/note.txt"));
+        assert_eq!(snippet.file_hash.as_deref(), Some("abc123"));
+    }
+
+    #[test]
+    fn test_description_is_loaded_with_snippet() {
+        let conn = test_connection();
+        let description = Some("Works around a tokio bug\nRemove after 1.39".to_string());
+        let snippet = add_snippet_on(
+            &conn,
+            Snippet::code("spawn()".into(), "rust".into(), "Spawn".into())
+                .with_description(description.clone()),
+            ColorDedupe::Exact,
+        )
+        .unwrap();
+        assert_eq!(
+            fetch_snippet(&conn, snippet.id).unwrap().description,
+            description
+        );
+
+        let snippet = add_snippet_on(
+            &conn,
+            Snippet::text("hi".into(), "Note".into()),
+            ColorDedupe::Exact,
+        )
+        .unwrap();
+        assert_eq!(fetch_snippet(&conn, snippet.id).unwrap().description, None);
+    }
+
+    #[test]
+    fn test_duplicate_color_keeps_its_description() {
+        let conn = test_connection();
+        let red = add_snippet_on(
+            &conn,
+            Snippet::color(255, 0, 0, 1.0, "Red".into()).with_description(Some("Brand".into())),
+            ColorDedupe::Exact,
+        )
+        .unwrap();
+
+        let again = add_snippet_on(
+            &conn,
+            Snippet::color(255, 0, 0, 1.0, "Again".into()).with_description(Some("Other".into())),
+            ColorDedupe::Exact,
+        )
+        .unwrap();
+        assert_eq!(again.id, red.id);
+        assert_eq!(again.description.as_deref(), Some("Brand"));
+    }
+
+    #[test]
+    fn test_abbreviation_is_loaded_with_snippet() {
+        let conn = test_connection();
+        let (snippet_id, _) = insert_linked_snippet(&conn);
+
+        let snippet = set_abbreviation_on(&conn, snippet_id, Some(";hi".to_string())).unwrap();
+        assert_eq!(snippet.abbreviation.as_deref(), Some(";hi"));
+
+        let snippet = set_abbreviation_on(&conn, snippet_id, None).unwrap();
+        assert_eq!(snippet.abbreviation, None);
+    }
+
+    #[test]
+    fn test_file_snippet_is_loaded() {
+        let conn = test_connection();
+        conn.execute(
+            "INSERT INTO snippets (kind, label, position, attachment_name, attachment_path, attachment_size)
+             VALUES ('file', 'Font', 0, 'Inter.ttf', '/data/files/abc-Inter.ttf', 2048)",
+            [],
+        )
+        .unwrap();
+
+        let snippet = fetch_snippet(&conn, conn.last_insert_rowid()).unwrap();
+        assert_eq!(snippet.kind(), SnippetKind::File);
+        assert_eq!(snippet.file_on_disk(), Some("/data/files/abc-Inter.ttf"));
+        assert!(matches!(
+            &snippet.content,
+            SnippetContent::File(file) if file.name == "Inter.ttf" && file.size == 2048
+        ));
+    }
+
+    #[test]
+    fn test_stored_files_of_trashed_snippets() {
+        let conn = test_connection();
+        conn.execute(
+            "INSERT INTO snippets (kind, label, position, attachment_name, attachment_path, attachment_size, deleted_at)
+             VALUES ('file', 'Old', 0, 'a.pdf', '/data/files/a.pdf', 1, CURRENT_TIMESTAMP),
+                    ('file', 'Kept', 1, 'b.pdf', '/data/files/b.pdf', 1, NULL)",
+            [],
+        )
+        .unwrap();
+
+        let files = stored_files_where(&conn, "deleted_at IS NOT NULL", []).unwrap();
+        assert_eq!(files, vec![PathBuf::from("/data/files/a.pdf")]);
+    }
+
+    #[test]
+    fn test_rich_text_is_dropped_when_text_changes() {
+        let conn = test_connection();
+        let note = insert_snippet_on(
+            &conn,
+            Snippet::rich_text(
+                "Total".to_string(),
+                Some("<b>Total</b>".to_string()),
+                "Note".to_string(),
+            ),
+        )
+        .unwrap();
+        let html_of = |conn: &Connection| match fetch_snippet(conn, note.id).unwrap().content {
+            SnippetContent::Text(text) => text.html,
+            _ => None,
+        };
+        assert_eq!(html_of(&conn).as_deref(), Some("<b>Total</b>"));
+
+        let update = format!(
+            "UPDATE snippets SET text_content = ?1, {} WHERE id = ?2",
+            KEEP_MATCHING_HTML
+        );
+        conn.execute(&update, params!["Total", note.id]).unwrap();
+        assert!(html_of(&conn).is_some());
+        conn.execute(&update, params!["Sum", note.id]).unwrap();
+        assert_eq!(html_of(&conn), None);
+    }
+
+    #[test]
+    fn test_gradient_snippet_round_trip() {
+        let conn = test_connection();
+        let mut gradient = GradientData::new(
+            ColorData::new(255, 0, 0, 1.0),
+            ColorData::new(0, 0, 255, 0.5),
+        );
+        gradient.angle = 45.0;
+        let stored =
+            insert_snippet_on(&conn, Snippet::gradient(gradient, "Dusk".to_string())).unwrap();
+        let loaded = fetch_snippet(&conn, stored.id).unwrap();
+        assert!(matches!(
+            &loaded.content,
+            SnippetContent::Gradient(g) if g.angle == 45.0 && g.stops[1].color.a == 0.5
+        ));
+
+        let mut edited = GradientData::from_json("{}");
+        edited.insert_stop(0.5);
+        let updated = update_gradient_on(
+            &conn,
+            stored.id,
+            edited,
+            "Dawn".to_string(),
+            None,
+            loaded.revision,
+        )
+        .unwrap();
+        assert_eq!(updated.label, "Dawn");
+        assert!(matches!(&updated.content, SnippetContent::Gradient(g) if g.stops.len() == 3));
+
+        // Saving over a newer revision is a conflict
+        let stale = update_gradient_on(
+            &conn,
+            stored.id,
+            GradientData::from_json("{}"),
+            "Stale".to_string(),
+            None,
+            loaded.revision,
+        );
+        assert!(matches!(stale, Err(SaveError::Conflict(_))));
+    }
+
+    #[test]
+    fn test_split_color_set() {
+        let conn = test_connection();
+        let set = ColorSetData::new(vec![
+            ColorData::new(255, 0, 0, 1.0),
+            ColorData::new(0, 0, 255, 0.5),
+        ]);
+        let set = insert_snippet_on(
+            &conn,
+            Snippet::new("Flag".to_string(), SnippetContent::ColorSet(set.clone())),
+        )
+        .unwrap();
+        assert!(matches!(
+            &fetch_snippet(&conn, set.id).unwrap().content,
+            SnippetContent::ColorSet(stored) if stored.colors.len() == 2
+        ));
+
+        let colors = split_color_set_on(&conn, set.id).unwrap();
+        let labels: Vec<&str> = colors.iter().map(|s| s.label.as_str()).collect();
+        assert_eq!(labels, vec!["Flag 1", "Flag 2"]);
+        // The first color lands on top
+        assert!(colors[0].position > colors[1].position);
+        assert!(matches!(
+            &fetch_snippet(&conn, colors[1].id).unwrap().content,
+            SnippetContent::Color(color) if color.b == 255 && color.a == 0.5
+        ));
+        assert!(fetch_snippet(&conn, set.id).unwrap().deleted_at.is_some());
+    }
+
+    #[test]
+    fn test_json_code_snippets_migrate() {
+        let conn = test_connection();
+        conn.execute(
+            "INSERT INTO snippets (kind, label, position, code, language)
+             VALUES ('code', 'Config', 0, '{\"a\": 1}', 'JSON'),
+                    ('code', 'Broken', 1, '{\"a\": }', 'json')",
+            [],
+        )
+        .unwrap();
+        migrate_v20(&conn).unwrap();
+
+        assert!(matches!(
+            fetch_snippet(&conn, 1).unwrap().content,
+            SnippetContent::Json(json) if json.json == "{\"a\": 1}"
+        ));
+        assert_eq!(fetch_snippet(&conn, 2).unwrap().kind(), SnippetKind::Code);
+    }
+
+    #[test]
+    fn test_project_file_contents() {
+        let conn = test_connection();
+        conn.execute(
+            "INSERT INTO snippets (kind, label, position, code, language, deleted_at)
+             VALUES ('code', 'Log', 0, 'log()', 'javascript', NULL),
+                    ('code', 'Gone', 1, 'x', 'rust', CURRENT_TIMESTAMP),
+                    ('code', 'Other', 2, 'y', 'rust', NULL)",
+            [],
+        )
+        .unwrap();
+        let (text_id, _) = insert_linked_snippet(&conn);
+
+        set_project_sync(&conn, "/p/a.code-snippets", &[1, 2]).unwrap();
+        set_project_sync(&conn, "/p/b.code-snippets", &[3]).unwrap();
+        // Syncing again replaces the snippets synced to the file
+        set_project_sync(&conn, "/p/a.code-snippets", &[1, 2, text_id]).unwrap();
+
+        let (contents, count) = project_file_contents(&conn, "/p/a.code-snippets").unwrap();
+        let value: serde_json::Value = serde_json::from_str(&contents).unwrap();
+        assert_eq!(count, 1);
+        assert_eq!(value["Log"]["body"][0], "log()");
+
+        conn.execute("DELETE FROM snippets WHERE id = 3", [])
+            .unwrap();
+        let (_, count) = project_file_contents(&conn, "/p/b.code-snippets").unwrap();
+        assert_eq!(count, 0);
+    }
+
+    #[test]
+    fn test_database_stats() {
+        let conn = test_connection();
+        insert_linked_snippet(&conn);
+        conn.execute(
+            "INSERT INTO snippets (kind, label, position, text_content, deleted_at)
+             VALUES ('text', 'Old', 1, 'bye', CURRENT_TIMESTAMP)",
+            [],
+        )
+        .unwrap();
+
+        let stats = stats_on(&conn).unwrap();
+        assert_eq!(
+            (
+                stats.snippets,
+                stats.trashed,
+                stats.palettes,
+                stats.sections
+            ),
+            (1, 1, 1, 0)
+        );
+        assert!(stats.size > 0);
+        assert!(integrity_check_on(&conn).is_ok());
+    }
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(1536), "1.5 KB");
+        assert_eq!(format_size(5 * 1024 * 1024), "5.0 MB");
+    }
+
+    #[test]
+    fn test_move_file_replaces_source() {
+        let dir = std::env::temp_dir().join(format!("shard-move-test-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("synced")).unwrap();
+        let from = dir.join(DATABASE_FILE_NAME);
+        let to = dir.join("synced").join(DATABASE_FILE_NAME);
+        std::fs::write(&from, b"data").unwrap();
+
+        move_file(&from, &to).unwrap();
+        let moved = std::fs::read(&to).unwrap();
+        let source_left = from.exists();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(moved, b"data");
+        assert!(!source_left);
+    }
+
+    #[test]
+    fn test_file_connection_uses_wal() {
+        let path = std::env::temp_dir().join(format!("shard-wal-test-{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let conn = Connection::open(&path).unwrap();
+        configure_connection(&conn).unwrap();
+
+        let mode: String = conn
+            .query_row("PRAGMA journal_mode", [], |row| row.get(0))
+            .unwrap();
+        let foreign_keys: bool = conn
+            .query_row("PRAGMA foreign_keys", [], |row| row.get(0))
+            .unwrap();
+        drop(conn);
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(mode, "wal");
+        assert!(foreign_keys);
+    }
+
+    #[cfg(feature = "encryption")]
+    #[test]
+    fn test_encrypt_backups_leaves_no_plaintext() {
+        let dir = temp_database_dir("encrypt-backups");
+        let backup = dir.join(backup_file_name(1, "import"));
+        {
+            let conn = Connection::open(&backup).unwrap();
+            conn.execute("CREATE TABLE notes (text TEXT)", []).unwrap();
+            conn.execute("INSERT INTO notes VALUES ('sk-secret')", [])
+                .unwrap();
+        }
+        // Not a database, so left alone
+        std::fs::write(dir.join("notes.txt"), "hi").unwrap();
+
+        let result = encrypt_backups(&dir, "hunter2");
+        assert_eq!(
+            result,
+            BackupsEncrypted {
+                encrypted: 1,
+                removed: 0
+            }
+        );
+        let bytes = std::fs::read(&backup).unwrap();
+        assert!(!bytes.starts_with(SQLITE_HEADER));
+        assert!(!bytes.windows(9).any(|w| w == b"sk-secret"));
+
+        // Still restorable with the passphrase
+        let conn = Connection::open(&backup).unwrap();
+        conn.pragma_update(None, "key", "hunter2").unwrap();
+        let text: String = conn
+            .query_row("SELECT text FROM notes", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(text, "sk-secret");
+        assert!(dir.join("notes.txt").exists());
+    }
+
+    /// An empty folder in the temp directory for a test's database files.
+    fn temp_database_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("shard-{}-test-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn schema_version(conn: &Connection) -> String {
+        conn.query_row(
+            "SELECT value FROM meta WHERE key = 'schema_version'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_first_schema_migrates_to_current() {
+        let dir = temp_database_dir("migrate");
+        let path = dir.join(DATABASE_FILE_NAME);
+        {
+            let conn = Connection::open(&path).unwrap();
+            conn.execute(
+                "CREATE TABLE meta (key TEXT PRIMARY KEY, value TEXT NOT NULL)",
+                [],
+            )
+            .unwrap();
+            conn.execute("INSERT INTO meta VALUES ('schema_version', '1')", [])
+                .unwrap();
+            migrate_v1(&conn).unwrap();
+            conn.execute(
+                "INSERT INTO snippets (kind, label, position, r, g, b, a)
+                 VALUES ('color', 'Brand', 1, 255, 128, 0, 1.0)",
+                [],
+            )
+            .unwrap();
+        }
+
+        let conn = open_database(&path).unwrap();
+        let version = schema_version(&conn);
+        let snippets = load_snippets_on(&conn).unwrap();
+        let backups = std::fs::read_dir(backup_dir_for(&path)).unwrap().count();
+        drop(conn);
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(version, SCHEMA_VERSION.to_string());
+        assert_eq!(snippets.len(), 1);
+        assert_eq!(snippets[0].label, "Brand");
+        assert!(matches!(
+            &snippets[0].content,
+            SnippetContent::Color(color) if *color == ColorData::new(255, 128, 0, 1.0)
+        ));
+        assert_eq!(backups, 1);
+    }
+
+    #[test]
+    fn test_reopening_current_schema_keeps_data() {
+        let dir = temp_database_dir("reopen");
+        let path = dir.join(DATABASE_FILE_NAME);
+        {
+            let conn = open_database(&path).unwrap();
+            insert_linked_snippet(&conn);
+        }
+
+        let conn = open_database(&path).unwrap();
+        let snippets = load_snippets_on(&conn).unwrap();
+        let palettes = load_palettes_on(&conn).unwrap();
+        let links = palette_link_count(&conn);
+        drop(conn);
+        let backed_up = backup_dir_for(&path).exists();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(snippets.len(), 1);
+        assert_eq!(palettes.len(), 1);
+        assert_eq!(links, 1);
+        assert!(!backed_up);
+    }
+
+    #[test]
+    fn test_in_memory_database_cannot_be_snapshot() {
+        let conn = test_connection();
+        assert!(snapshot_database(&conn, "manual").is_err());
+    }
+
+    fn snippet_ids(conn: &Connection) -> Vec<i64> {
+        load_snippets_on(conn)
+            .unwrap()
+            .iter()
+            .map(|snippet| snippet.id)
+            .collect()
+    }
+
+    #[test]
+    fn test_snippets_load_newest_first() {
+        let conn = test_connection();
+        let first = insert_snippet_on(&conn, Snippet::text("a".into(), "A".into())).unwrap();
+        let second = insert_snippet_on(&conn, Snippet::text("b".into(), "B".into())).unwrap();
+        let third = insert_snippet_on(&conn, Snippet::text("c".into(), "C".into())).unwrap();
+        assert_eq!(snippet_ids(&conn), vec![third.id, second.id, first.id]);
+
+        move_to_top_on(&conn, first.id).unwrap();
+        trash_all(&conn, &[second.id]).unwrap();
+        assert_eq!(snippet_ids(&conn), vec![first.id, third.id]);
+    }
+
+    #[test]
+    fn test_insert_snippets_keeps_first_on_top() {
+        let conn = test_connection();
+        let inserted = insert_snippets_on(
+            &conn,
+            vec![
+                Snippet::text("a".into(), "Brand 50".into()),
+                Snippet::text("b".into(), "Brand 100".into()),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(inserted[0].label, "Brand 50");
+        assert_eq!(
+            snippet_ids(&conn),
+            inserted.iter().map(|s| s.id).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_duplicate_color_moves_to_top() {
+        let conn = test_connection();
+        let red = ColorData::new(255, 0, 0, 1.0);
+        let added =
+            add_or_move_color_on(&conn, red.clone(), "Red".into(), ColorDedupe::Exact).unwrap();
+        let blue = add_or_move_color_on(
+            &conn,
+            ColorData::new(0, 0, 255, 1.0),
+            "Blue".into(),
+            ColorDedupe::Exact,
+        )
+        .unwrap();
+
+        let again = add_or_move_color_on(&conn, red, "Again".into(), ColorDedupe::Exact).unwrap();
+
+        assert_eq!(again.id, added.id);
+        assert_eq!(again.label, "Red");
+        assert_eq!(snippet_ids(&conn), vec![added.id, blue.id]);
+    }
+
+    #[test]
+    fn test_dedupe_policies() {
+        let conn = test_connection();
+        let saved = add_or_move_color_on(
+            &conn,
+            ColorData::new(100, 150, 200, 1.0),
+            "Sky".into(),
+            ColorDedupe::Exact,
+        )
+        .unwrap();
+
+        let translucent = ColorData::new(100, 150, 200, 0.5);
+        let near = ColorData::new(101, 150, 200, 1.0);
+        let find =
+            |color: &ColorData, dedupe| find_duplicate_color_on(&conn, color, dedupe).unwrap();
+        assert_eq!(find(&translucent, ColorDedupe::Exact), None);
+        assert_eq!(find(&translucent, ColorDedupe::IgnoreAlpha), Some(saved.id));
+        assert_eq!(find(&near, ColorDedupe::IgnoreAlpha), None);
+        assert_eq!(find(&near, ColorDedupe::Perceptual), Some(saved.id));
+
+        // Trashed colors are not duplicates
+        trash_all(&conn, &[saved.id]).unwrap();
+        assert_eq!(find(&near, ColorDedupe::Perceptual), None);
+    }
+
+    #[test]
+    fn test_captured_duplicate_stays_out_of_inbox() {
+        let conn = test_connection();
+        let kept = add_or_move_color_on(
+            &conn,
+            ColorData::new(10, 20, 30, 1.0),
+            "Ink".into(),
+            ColorDedupe::Exact,
+        )
+        .unwrap();
+
+        let duplicate = add_captured_snippet_on(
+            &conn,
+            Snippet::color(10, 20, 30, 1.0, "Captured".into()),
+            ColorDedupe::Exact,
+        )
+        .unwrap();
+        let new = add_captured_snippet_on(
+            &conn,
+            Snippet::text("note".into(), "Captured".into()),
+            ColorDedupe::Exact,
+        )
+        .unwrap();
+
+        assert_eq!(duplicate.id, kept.id);
+        assert!(!duplicate.in_inbox);
+        assert!(new.in_inbox);
+    }
+
+    #[test]
+    fn test_palettes_load_by_name_with_links() {
+        let conn = test_connection();
+        let (snippet_id, work) = insert_linked_snippet(&conn);
+        let archive = insert_palette(&conn, "Archive", None);
+        let nested = insert_palette(&conn, "Old", Some(work));
+        conn.execute(
+            "INSERT INTO palette_snippets (palette_id, snippet_id) VALUES (?1, ?2)",
+            params![archive, snippet_id],
+        )
+        .unwrap();
+
+        let names: Vec<String> = load_palettes_on(&conn)
+            .unwrap()
+            .into_iter()
+            .map(|palette| palette.name)
+            .collect();
+        assert_eq!(names, vec!["Archive", "Old", "Work"]);
+
+        remove_palette(&conn, work).unwrap();
+        let palettes = load_palettes_on(&conn).unwrap();
+        assert_eq!(palettes.len(), 2);
+        assert_eq!(parent_of(&conn, nested), None);
+        // The snippet keeps its link to the other palette
+        assert_eq!(palette_link_count(&conn), 1);
+        assert_eq!(snippet_ids(&conn), vec![snippet_id]);
+    }
+}
@@ -0,0 +1,89 @@
+//! Background thread that runs database operations.
+//!
+//! The `db` functions block on SQLite. Running them inside `Task::perform`
+//! futures would stall the async executor the UI depends on, so `update.rs`
+//! queues them here instead and receives their results as messages. Jobs run
+//! one at a time, in the order they were queued. Jobs report failures through
+//! the `Result` they return; release builds abort on panic, so a panicking job
+//! ends the app rather than just the worker.
+
+use std::future::Future;
+use std::sync::mpsc::{self, Sender};
+use std::sync::OnceLock;
+
+use iced::Task;
+use tokio::sync::oneshot;
+
+/// A queued database operation.
+type Job = Box<dyn FnOnce() + Send>;
+
+/// Queue of the worker thread, started on first use.
+static JOBS: OnceLock<Sender<Job>> = OnceLock::new();
+
+/// Get the job queue, starting the worker thread if needed.
+fn jobs() -> &'static Sender<Job> {
+    JOBS.get_or_init(|| {
+        let (sender, receiver) = mpsc::channel::<Job>();
+        std::thread::Builder::new()
+            .name("shard-db".to_string())
+            .spawn(move || {
+                for job in receiver {
+                    job();
+                }
+            })
+            .expect("failed to start the database worker");
+        sender
+    })
+}
+
+/// Queue a job on the worker. The job is queued right away; the returned
+/// future resolves to its result.
+pub fn run<R>(job: impl FnOnce() -> R + Send + 'static) -> impl Future<Output = Result<R, String>>
+where
+    R: Send + 'static,
+{
+    let (result_sender, result_receiver) = oneshot::channel();
+    let queued = jobs()
+        .send(Box::new(move || {
+            let _ = result_sender.send(job());
+        }))
+        .is_ok();
+
+    async move {
+        if !queued {
+            return Err("The database worker has stopped".to_string());
+        }
+        result_receiver
+            .await
+            .map_err(|_| "The database operation failed unexpectedly".to_string())
+    }
+}
+
+/// Run a database operation on the worker and turn its result into a
+/// message, like `Task::perform` does for a future.
+pub fn perform<T, E, M>(
+    job: impl FnOnce() -> Result<T, E> + Send + 'static,
+    to_message: impl FnOnce(Result<T, E>) -> M + Send + 'static,
+) -> Task<M>
+where
+    T: Send + 'static,
+    E: From<String> + Send + 'static,
+    M: Send + 'static,
+{
+    Task::perform(run(job), move |result| {
+        to_message(result.unwrap_or_else(|e| Err(E::from(e))))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_jobs_run_in_order_on_worker_thread() {
+        let first = run(|| std::thread::current().name().map(str::to_string));
+        let second = run(|| 2 + 2);
+        assert_eq!(first.await.unwrap().as_deref(), Some("shard-db"));
+        assert_eq!(second.await, Ok(4));
+    }
+}
@@ -0,0 +1,471 @@
+//! Text expander: abbreviations typed in any application are replaced with
+//! the content of the snippet they belong to.
+//!
+//! Keys are read from the keyboards' evdev devices, which needs read access
+//! to `/dev/input` (usually membership of the `input` group), and the
+//! expansion is typed with `wtype` on Wayland or `xdotool` on X11. Typed keys
+//! are mapped with a US layout. Other platforms are not supported yet.
+//!
+//! The same listener reports number keys pressed with modifiers, which copy
+//! from pinned palette boards without focusing them.
+
+use std::collections::HashSet;
+use std::io::Read;
+#[cfg(target_os = "linux")]
+use std::process::Command;
+
+use iced::futures::{SinkExt, Stream};
+
+use crate::config::Modifiers;
+use crate::message::Message;
+use crate::snippet::MAX_ABBREVIATION_CHARS;
+
+/// Shown when expansion is turned on where it can't work.
+#[cfg(not(target_os = "linux"))]
+const UNSUPPORTED: &str = "Text expansion is only available on Linux for now";
+
+/// A key typed in another application, as far as abbreviations care.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypedKey {
+    Char(char),
+    Backspace,
+    /// A key that moves the cursor or runs a shortcut; what came before it
+    /// can no longer end in an abbreviation.
+    Break,
+}
+
+/// The last characters typed, matched against abbreviations.
+#[derive(Debug, Clone, Default)]
+pub struct TypedBuffer {
+    typed: String,
+}
+
+impl TypedBuffer {
+    pub fn handle(&mut self, key: TypedKey) {
+        match key {
+            TypedKey::Char(c) => {
+                self.typed.push(c);
+                // Nothing longer than an abbreviation is needed
+                if self.typed.chars().count() > MAX_ABBREVIATION_CHARS {
+                    self.typed.remove(0);
+                }
+            }
+            TypedKey::Backspace => {
+                self.typed.pop();
+            }
+            TypedKey::Break => self.typed.clear(),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.typed.clear();
+    }
+
+    /// Find the abbreviation the typed text ends with; the longest one wins
+    /// when one ends another.
+    pub fn ending<'a>(&self, abbreviations: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+        abbreviations
+            .into_iter()
+            .filter(|abbreviation| !abbreviation.is_empty() && self.typed.ends_with(abbreviation))
+            .max_by_key(|abbreviation| abbreviation.len())
+    }
+}
+
+// evdev key codes
+const KEY_BACKSPACE: u16 = 14;
+const KEY_CAPSLOCK: u16 = 58;
+const SHIFT_KEYS: [u16; 2] = [42, 54];
+const CTRL_KEYS: [u16; 2] = [29, 97];
+const ALT_KEYS: [u16; 2] = [56, 100];
+const META_KEYS: [u16; 2] = [125, 126];
+/// Control, Alt and Meta on both sides.
+const SHORTCUT_KEYS: [u16; 6] = [29, 97, 56, 100, 125, 126];
+/// The number keys 1 to 9 of the main block.
+const NUMBER_KEYS: std::ops::RangeInclusive<u16> = 2..=10;
+
+/// A key event read from a keyboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum KeyEvent {
+    Typed(TypedKey),
+    /// Number key 1–9 (as an index from 0) pressed with Control, Alt or Meta.
+    Number(Modifiers, usize),
+}
+
+/// Keys typing a character on a US layout, as (code, character, with shift).
+const US_KEYMAP: [(u16, char, char); 48] = [
+    (2, '1', '!'),
+    (3, '2', '@'),
+    (4, '3', '#'),
+    (5, '4', '$'),
+    (6, '5', '%'),
+    (7, '6', '^'),
+    (8, '7', '&'),
+    (9, '8', '*'),
+    (10, '9', '('),
+    (11, '0', ')'),
+    (12, '-', '_'),
+    (13, '=', '+'),
+    (16, 'q', 'Q'),
+    (17, 'w', 'W'),
+    (18, 'e', 'E'),
+    (19, 'r', 'R'),
+    (20, 't', 'T'),
+    (21, 'y', 'Y'),
+    (22, 'u', 'U'),
+    (23, 'i', 'I'),
+    (24, 'o', 'O'),
+    (25, 'p', 'P'),
+    (26, '[', '{'),
+    (27, ']', '}'),
+    (30, 'a', 'A'),
+    (31, 's', 'S'),
+    (32, 'd', 'D'),
+    (33, 'f', 'F'),
+    (34, 'g', 'G'),
+    (35, 'h', 'H'),
+    (36, 'j', 'J'),
+    (37, 'k', 'K'),
+    (38, 'l', 'L'),
+    (39, ';', ':'),
+    (40, '\'', '"'),
+    (41, '`', '~'),
+    (43, '\\', '|'),
+    (44, 'z', 'Z'),
+    (45, 'x', 'X'),
+    (46, 'c', 'C'),
+    (47, 'v', 'V'),
+    (48, 'b', 'B'),
+    (49, 'n', 'N'),
+    (50, 'm', 'M'),
+    (51, ',', '<'),
+    (52, '.', '>'),
+    (53, '/', '?'),
+    (57, ' ', ' '),
+];
+
+/// Modifier state of one keyboard, turning its key events into typed keys.
+#[derive(Debug, Default)]
+struct KeyState {
+    held: HashSet<u16>,
+    caps_lock: bool,
+}
+
+impl KeyState {
+    /// Get the modifier chord of a number key press, if one is held.
+    fn number_chord(&self, code: u16, value: i32) -> Option<(Modifiers, usize)> {
+        if value != 1 || !NUMBER_KEYS.contains(&code) {
+            return None;
+        }
+        let held = |keys: [u16; 2]| keys.iter().any(|key| self.held.contains(key));
+        let modifiers = Modifiers {
+            ctrl: held(CTRL_KEYS),
+            alt: held(ALT_KEYS),
+            shift: held(SHIFT_KEYS),
+            logo: held(META_KEYS),
+        };
+        (modifiers.ctrl || modifiers.alt || modifiers.logo)
+            .then_some((modifiers, usize::from(code - NUMBER_KEYS.start())))
+    }
+
+    /// Handle a key event; `value` is 0 for release, 1 for press and 2 for repeat.
+    fn handle(&mut self, code: u16, value: i32) -> Option<TypedKey> {
+        let is_modifier = SHIFT_KEYS.contains(&code) || SHORTCUT_KEYS.contains(&code);
+        if value == 0 {
+            self.held.remove(&code);
+            return None;
+        }
+        if is_modifier {
+            self.held.insert(code);
+            return None;
+        }
+        if code == KEY_CAPSLOCK {
+            if value == 1 {
+                self.caps_lock = !self.caps_lock;
+            }
+            return None;
+        }
+        if SHORTCUT_KEYS.iter().any(|key| self.held.contains(key)) {
+            return Some(TypedKey::Break);
+        }
+        if code == KEY_BACKSPACE {
+            return Some(TypedKey::Backspace);
+        }
+        let shift = SHIFT_KEYS.iter().any(|key| self.held.contains(key));
+        let key = US_KEYMAP.iter().find(|(key, _, _)| *key == code);
+        Some(match key {
+            Some((_, plain, shifted)) => {
+                let upper = if plain.is_ascii_alphabetic() {
+                    shift != self.caps_lock
+                } else {
+                    shift
+                };
+                TypedKey::Char(if upper { *shifted } else { *plain })
+            }
+            None => TypedKey::Break,
+        })
+    }
+}
+
+/// Size of `struct input_event`: a timeval, then type, code and value.
+const TIMEVAL_SIZE: usize = 2 * std::mem::size_of::<std::ffi::c_long>();
+const INPUT_EVENT_SIZE: usize = TIMEVAL_SIZE + 8;
+const EV_KEY: u16 = 1;
+
+/// Read key events from a keyboard device until it goes away or nobody listens.
+fn read_keyboard(mut device: std::fs::File, keys: tokio::sync::mpsc::UnboundedSender<KeyEvent>) {
+    let mut state = KeyState::default();
+    let mut event = [0u8; INPUT_EVENT_SIZE];
+    while device.read_exact(&mut event).is_ok() {
+        let field = |at: usize| [event[TIMEVAL_SIZE + at], event[TIMEVAL_SIZE + at + 1]];
+        if u16::from_ne_bytes(field(0)) != EV_KEY {
+            continue;
+        }
+        let code = u16::from_ne_bytes(field(2));
+        let value = i32::from_ne_bytes([
+            event[TIMEVAL_SIZE + 4],
+            event[TIMEVAL_SIZE + 5],
+            event[TIMEVAL_SIZE + 6],
+            event[TIMEVAL_SIZE + 7],
+        ]);
+        if let Some((modifiers, index)) = state.number_chord(code, value) {
+            if keys.send(KeyEvent::Number(modifiers, index)).is_err() {
+                return;
+            }
+        }
+        if let Some(key) = state.handle(code, value) {
+            if keys.send(KeyEvent::Typed(key)).is_err() {
+                return;
+            }
+        }
+    }
+}
+
+/// Watch every keyboard, reporting typed keys and number key chords; reports
+/// once and stops if the keyboards can't be read.
+pub fn listen() -> impl Stream<Item = Message> {
+    iced::stream::channel(64, async |mut output| {
+        let (sender, mut keys) = tokio::sync::mpsc::unbounded_channel();
+        match open_keyboards() {
+            Ok(devices) => {
+                for device in devices {
+                    let sender = sender.clone();
+                    std::thread::spawn(move || read_keyboard(device, sender));
+                }
+            }
+            Err(e) => {
+                let _ = output.send(Message::ExpanderFailed(e)).await;
+            }
+        }
+        drop(sender);
+
+        while let Some(event) = keys.recv().await {
+            let message = match event {
+                KeyEvent::Typed(key) => Message::ExpanderKey(key),
+                KeyEvent::Number(modifiers, index) => Message::GlobalNumberKey(modifiers, index),
+            };
+            if output.send(message).await.is_err() {
+                return;
+            }
+        }
+        // Not restarted until expansion is turned off and on again
+        std::future::pending::<()>().await;
+    })
+}
+
+/// Find the event devices of keyboards in `/proc/bus/input/devices`.
+#[cfg(target_os = "linux")]
+fn keyboard_devices(devices: &str) -> Vec<String> {
+    devices
+        .lines()
+        .filter_map(|line| line.strip_prefix("H: Handlers="))
+        .filter(|handlers| handlers.split_whitespace().any(|h| h == "kbd"))
+        .filter_map(|handlers| {
+            handlers
+                .split_whitespace()
+                .find(|h| h.starts_with("event"))
+                .map(|event| format!("/dev/input/{}", event))
+        })
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn open_keyboards() -> Result<Vec<std::fs::File>, String> {
+    let devices = std::fs::read_to_string("/proc/bus/input/devices")
+        .map_err(|e| format!("Failed to list input devices: {}", e))?;
+    let paths = keyboard_devices(&devices);
+    let opened: Vec<_> = paths
+        .iter()
+        .filter_map(|path| std::fs::File::open(path).ok())
+        .collect();
+    if paths.is_empty() {
+        Err("No keyboard found".to_string())
+    } else if opened.is_empty() {
+        Err("Can't read the keyboard; add yourself to the input group".to_string())
+    } else {
+        Ok(opened)
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn open_keyboards() -> Result<Vec<std::fs::File>, String> {
+    Err(UNSUPPORTED.to_string())
+}
+
+/// Erase the typed abbreviation in the focused application and type `text`.
+#[cfg(target_os = "linux")]
+pub fn expand(erase: usize, text: &str) -> Result<(), String> {
+    if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        let mut command = Command::new("wtype");
+        for _ in 0..erase {
+            command.args(["-k", "BackSpace"]);
+        }
+        run(command.arg("--").arg(text))
+    } else {
+        if erase > 0 {
+            run(Command::new("xdotool")
+                .args(["key", "--clearmodifiers"])
+                .args(std::iter::repeat_n("BackSpace", erase)))?;
+        }
+        run(Command::new("xdotool")
+            .args(["type", "--clearmodifiers", "--"])
+            .arg(text))
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn expand(_erase: usize, _text: &str) -> Result<(), String> {
+    Err(UNSUPPORTED.to_string())
+}
+
+/// Run a typing command, describing why it failed.
+#[cfg(target_os = "linux")]
+fn run(command: &mut Command) -> Result<(), String> {
+    let program = command.get_program().to_string_lossy().into_owned();
+    let output = command
+        .output()
+        .map_err(|e| format!("Failed to run {}: {}", program, e))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(format!("{} failed: {}", program, stderr.trim()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn typed(text: &str) -> TypedBuffer {
+        let mut buffer = TypedBuffer::default();
+        for c in text.chars() {
+            buffer.handle(TypedKey::Char(c));
+        }
+        buffer
+    }
+
+    #[test]
+    fn test_typed_buffer_matches_abbreviation_at_end() {
+        let abbreviations = [";sig", ";s", "addr"];
+        assert_eq!(typed("hello ;sig").ending(abbreviations), Some(";sig"));
+        assert_eq!(typed("hello ;s").ending(abbreviations), Some(";s"));
+        assert_eq!(typed(";sig ").ending(abbreviations), None);
+
+        let mut buffer = typed(";sx");
+        buffer.handle(TypedKey::Backspace);
+        buffer.handle(TypedKey::Char('i'));
+        buffer.handle(TypedKey::Char('g'));
+        assert_eq!(buffer.ending(abbreviations), Some(";sig"));
+
+        // Moving the cursor splits what was typed
+        let mut buffer = typed("ad");
+        buffer.handle(TypedKey::Break);
+        buffer.handle(TypedKey::Char('d'));
+        buffer.handle(TypedKey::Char('r'));
+        assert_eq!(buffer.ending(abbreviations), None);
+    }
+
+    #[test]
+    fn test_typed_buffer_keeps_only_recent_keys() {
+        let long = "x".repeat(MAX_ABBREVIATION_CHARS * 2);
+        let buffer = typed(&format!("{};sig", long));
+        assert_eq!(buffer.typed.chars().count(), MAX_ABBREVIATION_CHARS);
+        assert_eq!(buffer.ending([";sig"]), Some(";sig"));
+    }
+
+    #[test]
+    fn test_key_state_maps_us_layout() {
+        let mut state = KeyState::default();
+        assert_eq!(state.handle(39, 1), Some(TypedKey::Char(';')));
+        assert_eq!(state.handle(39, 0), None);
+        // Shift
+        assert_eq!(state.handle(42, 1), None);
+        assert_eq!(state.handle(31, 1), Some(TypedKey::Char('S')));
+        assert_eq!(state.handle(2, 2), Some(TypedKey::Char('!')));
+        assert_eq!(state.handle(42, 0), None);
+        // Caps Lock only changes letters, and shift undoes it
+        state.handle(KEY_CAPSLOCK, 1);
+        assert_eq!(state.handle(31, 1), Some(TypedKey::Char('S')));
+        assert_eq!(state.handle(2, 1), Some(TypedKey::Char('1')));
+        state.handle(54, 1);
+        assert_eq!(state.handle(31, 1), Some(TypedKey::Char('s')));
+        state.handle(54, 0);
+        state.handle(KEY_CAPSLOCK, 1);
+        // Shortcuts, Enter and arrows break the typed text
+        state.handle(29, 1);
+        assert_eq!(state.handle(46, 1), Some(TypedKey::Break));
+        state.handle(29, 0);
+        assert_eq!(state.handle(28, 1), Some(TypedKey::Break));
+        assert_eq!(state.handle(105, 1), Some(TypedKey::Break));
+        assert_eq!(state.handle(KEY_BACKSPACE, 2), Some(TypedKey::Backspace));
+    }
+
+    #[test]
+    fn test_key_state_number_chords() {
+        let mut state = KeyState::default();
+        // Plain and shifted number keys are typing
+        assert_eq!(state.number_chord(2, 1), None);
+        state.handle(42, 1);
+        assert_eq!(state.number_chord(2, 1), None);
+        state.handle(42, 0);
+
+        state.handle(29, 1);
+        state.handle(56, 1);
+        assert_eq!(
+            state.number_chord(4, 1),
+            Some((Modifiers::new(true, true, false), 2))
+        );
+        // Repeats and releases don't copy again
+        assert_eq!(state.number_chord(4, 2), None);
+        assert_eq!(state.number_chord(4, 0), None);
+        // 0 is not a board key
+        assert_eq!(state.number_chord(11, 1), None);
+        state.handle(29, 0);
+        state.handle(56, 0);
+
+        state.handle(125, 1);
+        state.handle(100, 1);
+        let (modifiers, index) = state.number_chord(10, 1).unwrap();
+        assert!(modifiers.logo && modifiers.alt && !modifiers.ctrl);
+        assert_eq!(index, 8);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_keyboard_devices() {
+        let devices = "I: Bus=0011 Vendor=0001 Product=0001 Version=ab41\n\
+                       N: Name=\"AT Translated Set 2 keyboard\"\n\
+                       H: Handlers=sysrq kbd leds event3 \n\
+                       B: EV=120013\n\
+                       \n\
+                       N: Name=\"Power Button\"\n\
+                       H: Handlers=kbd event1\n\
+                       \n\
+                       N: Name=\"PS/2 Mouse\"\n\
+                       H: Handlers=mouse0 event4\n";
+        assert_eq!(
+            keyboard_devices(devices),
+            vec!["/dev/input/event3", "/dev/input/event1"]
+        );
+    }
+}
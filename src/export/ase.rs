@@ -0,0 +1,96 @@
+//! Adobe Swatch Exchange (`.ase`): a big-endian binary list of blocks, with
+//! the colors in a group named after the palette.
+
+use crate::snippet::ColorData;
+
+const SIGNATURE: &[u8; 4] = b"ASEF";
+const GROUP_START: u16 = 0xC001;
+const GROUP_END: u16 = 0xC002;
+const COLOR_ENTRY: u16 = 0x0001;
+/// Color type of a swatch: 0 is global, 1 spot, 2 normal (process).
+const NORMAL_COLOR: u16 = 2;
+
+/// Write colors as an ASE file in a group called `group`. ASE has no alpha,
+/// so colors are written opaque.
+pub fn ase_bytes(group: &str, colors: &[(String, ColorData)]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(SIGNATURE);
+    // Version 1.0
+    bytes.extend_from_slice(&1u16.to_be_bytes());
+    bytes.extend_from_slice(&0u16.to_be_bytes());
+    let blocks = colors.len() as u32 + 2;
+    bytes.extend_from_slice(&blocks.to_be_bytes());
+
+    push_block(&mut bytes, GROUP_START, &name_bytes(group));
+    for (label, color) in colors {
+        let mut body = name_bytes(label);
+        body.extend_from_slice(b"RGB ");
+        for channel in [color.r, color.g, color.b] {
+            body.extend_from_slice(&(channel as f32 / 255.0).to_be_bytes());
+        }
+        body.extend_from_slice(&NORMAL_COLOR.to_be_bytes());
+        push_block(&mut bytes, COLOR_ENTRY, &body);
+    }
+    push_block(&mut bytes, GROUP_END, &[]);
+    bytes
+}
+
+/// Append a block: its type, the length of its body, and the body.
+fn push_block(bytes: &mut Vec<u8>, block_type: u16, body: &[u8]) {
+    bytes.extend_from_slice(&block_type.to_be_bytes());
+    bytes.extend_from_slice(&(body.len() as u32).to_be_bytes());
+    bytes.extend_from_slice(body);
+}
+
+/// Encode a name: its length in UTF-16 units including the terminating
+/// zero, then the UTF-16BE text and the zero.
+fn name_bytes(name: &str) -> Vec<u8> {
+    let units: Vec<u16> = name.encode_utf16().chain(std::iter::once(0)).collect();
+    let mut bytes = (units.len() as u16).to_be_bytes().to_vec();
+    for unit in units {
+        bytes.extend_from_slice(&unit.to_be_bytes());
+    }
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ase_bytes() {
+        let bytes = ase_bytes(
+            "Brand",
+            &[("Red".to_string(), ColorData::new(255, 0, 0, 0.5))],
+        );
+        assert_eq!(&bytes[0..4], b"ASEF");
+        assert_eq!(&bytes[4..8], &[0, 1, 0, 0]);
+        // Group start, one color, group end
+        assert_eq!(&bytes[8..12], &[0, 0, 0, 3]);
+
+        // Group start: "Brand" is 6 UTF-16 units with the terminator
+        assert_eq!(&bytes[12..14], &[0xC0, 0x01]);
+        assert_eq!(&bytes[14..18], &[0, 0, 0, 14]);
+        assert_eq!(&bytes[18..20], &[0, 6]);
+        assert_eq!(&bytes[20..22], &[0, b'B']);
+        let color = 18 + 14;
+
+        // Color entry: name, model, three floats, type
+        assert_eq!(&bytes[color..color + 2], &[0, 1]);
+        assert_eq!(&bytes[color + 2..color + 6], &[0, 0, 0, 2 + 8 + 4 + 12 + 2]);
+        let model = color + 6 + 2 + 8;
+        assert_eq!(&bytes[model..model + 4], b"RGB ");
+        assert_eq!(&bytes[model + 4..model + 8], &1.0f32.to_be_bytes());
+        assert_eq!(&bytes[model + 8..model + 12], &0.0f32.to_be_bytes());
+        assert_eq!(&bytes[model + 16..model + 18], &[0, 2]);
+
+        // Group end, with an empty body
+        assert_eq!(&bytes[bytes.len() - 6..], &[0xC0, 0x02, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_name_bytes_utf16() {
+        assert_eq!(name_bytes("é"), vec![0, 2, 0x00, 0xE9, 0, 0]);
+        assert_eq!(name_bytes(""), vec![0, 1, 0, 0]);
+    }
+}
@@ -0,0 +1,35 @@
+//! CSS custom properties: one `--name: value;` declaration per color inside
+//! `:root {}`.
+
+use super::{unique_identifiers, ColorValue};
+use crate::snippet::ColorData;
+
+/// Write colors as CSS custom properties on `:root`.
+pub fn css_variables(colors: &[(String, ColorData)], value: ColorValue) -> String {
+    let mut css = String::from(":root {\n");
+    for ((_, color), name) in colors.iter().zip(unique_identifiers(colors, '-')) {
+        css.push_str(&format!("  --{}: {};\n", name, value.format(color)));
+    }
+    css.push_str("}\n");
+    css
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_css_variables() {
+        let colors = vec![
+            ("Brand Red".to_string(), ColorData::new(255, 0, 0, 1.0)),
+            ("brand red".to_string(), ColorData::new(0, 0, 0, 0.5)),
+            ("500".to_string(), ColorData::new(0, 0, 255, 1.0)),
+        ];
+        assert_eq!(
+            css_variables(&colors, ColorValue::Hex),
+            ":root {\n  --brand-red: #FF0000;\n  --brand-red-2: #00000080;\n  --500: #0000FF;\n}\n"
+        );
+        let oklch = css_variables(&colors[..1], ColorValue::Oklch);
+        assert!(oklch.contains("--brand-red: oklch(62.8% 0.258 29);"));
+    }
+}
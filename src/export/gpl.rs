@@ -0,0 +1,120 @@
+//! GIMP palettes (`.gpl`): a text header, then one `R G B name` line per
+//! color. Inkscape and Krita read and write the same format.
+
+use crate::snippet::ColorData;
+
+const HEADER: &str = "GIMP Palette";
+
+/// Write colors as a GIMP palette called `name`. GIMP palettes have no
+/// alpha, so colors are written opaque.
+pub fn gpl_text(name: &str, colors: &[(String, ColorData)]) -> String {
+    let mut gpl = format!("{}\nName: {}\nColumns: 0\n#\n", HEADER, one_line(name));
+    for (label, color) in colors {
+        gpl.push_str(&format!(
+            "{:>3} {:>3} {:>3}\t{}\n",
+            color.r,
+            color.g,
+            color.b,
+            one_line(label)
+        ));
+    }
+    gpl
+}
+
+/// A parsed GIMP palette.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GplPalette {
+    pub name: Option<String>,
+    pub colors: Vec<(String, ColorData)>,
+}
+
+/// Parse a GIMP palette. Unnamed colors, which GIMP calls "Untitled", are
+/// labeled with their hex.
+pub fn parse_gpl(text: &str) -> Result<GplPalette, String> {
+    let mut lines = text.lines();
+    if lines
+        .next()
+        .map(|line| line.trim_start_matches('\u{feff}').trim())
+        != Some(HEADER)
+    {
+        return Err("Not a GIMP palette: the first line must be \"GIMP Palette\"".to_string());
+    }
+
+    let mut name = None;
+    let mut colors = Vec::new();
+    for (number, line) in lines.enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some(value) = line.strip_prefix("Name:") {
+            name = Some(value.trim().to_string()).filter(|name| !name.is_empty());
+            continue;
+        }
+        if line.starts_with("Columns:") {
+            continue;
+        }
+
+        let mut parts = line.split_whitespace();
+        let mut channel = || -> Result<u8, String> {
+            parts
+                .next()
+                .and_then(|value| value.parse().ok())
+                .ok_or_else(|| format!("Line {}: expected \"R G B name\"", number + 2))
+        };
+        let color = ColorData::new(channel()?, channel()?, channel()?, 1.0);
+        let label = parts.collect::<Vec<_>>().join(" ");
+        let label = if label.is_empty() || label == "Untitled" {
+            color.to_hex()
+        } else {
+            label
+        };
+        colors.push((label, color));
+    }
+    Ok(GplPalette { name, colors })
+}
+
+/// Keep a name on one line, as the format is line based.
+fn one_line(name: &str) -> String {
+    name.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gpl_round_trip() {
+        let colors = vec![
+            ("Brand Red".to_string(), ColorData::new(255, 0, 0, 1.0)),
+            ("Ink".to_string(), ColorData::new(10, 20, 30, 1.0)),
+        ];
+        let gpl = gpl_text("Brand", &colors);
+        assert!(gpl.starts_with("GIMP Palette\nName: Brand\n"));
+        assert!(gpl.contains("255   0   0\tBrand Red\n"));
+
+        let parsed = parse_gpl(&gpl).unwrap();
+        assert_eq!(parsed.name.as_deref(), Some("Brand"));
+        assert_eq!(parsed.colors, colors);
+    }
+
+    #[test]
+    fn test_parse_gpl_from_gimp() {
+        let gpl = "GIMP Palette\r\nName: Web\r\nColumns: 8\r\n# A comment\r\n\
+                   0 128 255 Untitled\r\n  12  34  56\r\n";
+        let GplPalette { name, colors } = parse_gpl(gpl).unwrap();
+        assert_eq!(name.as_deref(), Some("Web"));
+        assert_eq!(colors[0].0, "#0080FF");
+        assert_eq!(
+            colors[1],
+            ("#0C2238".to_string(), ColorData::new(12, 34, 56, 1.0))
+        );
+    }
+
+    #[test]
+    fn test_parse_gpl_errors() {
+        assert!(parse_gpl("JASC-PAL\n0100\n").is_err());
+        let error = parse_gpl("GIMP Palette\nName: X\n300 0 0 Too red\n").unwrap_err();
+        assert!(error.starts_with("Line 3"));
+    }
+}
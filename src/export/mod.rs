@@ -0,0 +1,233 @@
+//! Palette exports for other design tools: the named colors of a palette
+//! (or of the whole library) written in each tool's own file format.
+
+mod ase;
+mod css;
+mod gpl;
+mod scss;
+mod tailwind;
+mod tokens;
+
+use std::collections::HashSet;
+
+pub use gpl::{parse_gpl, GplPalette};
+pub use tokens::parse_tokens;
+
+use crate::snippet::ColorData;
+
+/// A palette file format of another tool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// Adobe Swatch Exchange, for Photoshop and Illustrator.
+    Ase,
+    /// GIMP palette, also read by Inkscape and Krita.
+    Gpl,
+    /// W3C design tokens JSON, for Figma Tokens and Style Dictionary.
+    Tokens,
+}
+
+impl ExportFormat {
+    /// Formats offered in the palette manager.
+    pub const ALL: [ExportFormat; 3] = [ExportFormat::Ase, ExportFormat::Gpl, ExportFormat::Tokens];
+
+    /// File extension, without the dot.
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Ase => "ase",
+            ExportFormat::Gpl => "gpl",
+            ExportFormat::Tokens => "json",
+        }
+    }
+
+    /// Name of the format in the save dialog's filter.
+    pub fn file_type(self) -> &'static str {
+        match self {
+            ExportFormat::Ase => "Adobe Swatch Exchange",
+            ExportFormat::Gpl => "GIMP Palette",
+            ExportFormat::Tokens => "Design Tokens",
+        }
+    }
+
+    /// Write named colors as a file, grouped under `name` where the format
+    /// has groups.
+    pub fn render(self, name: &str, colors: &[(String, ColorData)]) -> Vec<u8> {
+        match self {
+            ExportFormat::Ase => ase::ase_bytes(name, colors),
+            ExportFormat::Gpl => gpl::gpl_text(name, colors).into_bytes(),
+            ExportFormat::Tokens => tokens::tokens_json(colors).into_bytes(),
+        }
+    }
+}
+
+impl std::fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ExportFormat::Ase => "Adobe (.ase)",
+            ExportFormat::Gpl => "GIMP (.gpl)",
+            ExportFormat::Tokens => "Design tokens (.json)",
+        })
+    }
+}
+
+/// A format a palette is exported to as code, to paste into a project.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CodeFormat {
+    /// CSS custom properties on `:root`.
+    Css,
+    /// The theme colors of a Tailwind v3 `tailwind.config.js`.
+    TailwindConfig,
+    /// A Tailwind v4 `@theme` block.
+    TailwindTheme,
+    /// Sass variables, for SCSS stylesheets.
+    Scss,
+}
+
+impl CodeFormat {
+    /// Formats offered in the export dialog.
+    pub const ALL: [CodeFormat; 4] = [
+        CodeFormat::Css,
+        CodeFormat::Scss,
+        CodeFormat::TailwindConfig,
+        CodeFormat::TailwindTheme,
+    ];
+
+    /// File extension, without the dot.
+    pub fn extension(self) -> &'static str {
+        match self {
+            CodeFormat::Css | CodeFormat::TailwindTheme => "css",
+            CodeFormat::TailwindConfig => "js",
+            CodeFormat::Scss => "scss",
+        }
+    }
+
+    /// Name of the format in the save dialog's filter.
+    pub fn file_type(self) -> &'static str {
+        match self {
+            CodeFormat::Css | CodeFormat::TailwindTheme => "CSS",
+            CodeFormat::TailwindConfig => "JavaScript",
+            CodeFormat::Scss => "SCSS",
+        }
+    }
+
+    /// Write named colors as code, naming anything that groups them after
+    /// the palette.
+    pub fn render(
+        self,
+        name: &str,
+        colors: &[(String, ColorData)],
+        options: &CodeOptions,
+    ) -> String {
+        match self {
+            CodeFormat::Css => css::css_variables(colors, options.value),
+            CodeFormat::TailwindConfig => tailwind::tailwind_config(colors, options),
+            CodeFormat::TailwindTheme => tailwind::tailwind_theme(colors, options),
+            CodeFormat::Scss => scss::scss_variables(name, colors, options),
+        }
+    }
+
+    /// Check if the format can add a shade scale for each color.
+    pub fn has_shades(self) -> bool {
+        matches!(self, CodeFormat::TailwindConfig | CodeFormat::TailwindTheme)
+    }
+
+    /// Check if the format can add a map of every color.
+    pub fn has_map(self) -> bool {
+        self == CodeFormat::Scss
+    }
+}
+
+impl std::fmt::Display for CodeFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            CodeFormat::Css => "CSS variables",
+            CodeFormat::TailwindConfig => "Tailwind v3",
+            CodeFormat::TailwindTheme => "Tailwind v4",
+            CodeFormat::Scss => "SCSS",
+        })
+    }
+}
+
+/// Options of a code export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CodeOptions {
+    pub value: ColorValue,
+    /// Add a 50–950 shade scale for each color, where the format has them.
+    pub shades: bool,
+    /// Add a map of every color, where the format has them.
+    pub map: bool,
+}
+
+/// How color values are written in code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorValue {
+    /// `#RRGGBB`, or `#RRGGBBAA` when translucent.
+    #[default]
+    Hex,
+    /// `oklch(L% C H)`, for wide-gamut aware stylesheets.
+    Oklch,
+}
+
+impl ColorValue {
+    pub const ALL: [ColorValue; 2] = [ColorValue::Hex, ColorValue::Oklch];
+
+    fn format(self, color: &ColorData) -> String {
+        match self {
+            ColorValue::Hex => color.to_hex(),
+            ColorValue::Oklch => color.to_oklch(),
+        }
+    }
+}
+
+impl std::fmt::Display for ColorValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ColorValue::Hex => "Hex",
+            ColorValue::Oklch => "OKLCH",
+        })
+    }
+}
+
+/// Turn a label into a code identifier: lowercase ASCII words joined by
+/// `separator`, e.g. "Brand / Primary" → `brand-primary`.
+fn identifier(label: &str, separator: char) -> String {
+    let words: Vec<String> = label
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_ascii_lowercase)
+        .collect();
+    if words.is_empty() {
+        "color".to_string()
+    } else {
+        words.join(&separator.to_string())
+    }
+}
+
+/// Name every color as an identifier, numbering repeated names.
+fn unique_identifiers(colors: &[(String, ColorData)], separator: char) -> Vec<String> {
+    let mut taken = HashSet::new();
+    colors
+        .iter()
+        .map(|(label, _)| {
+            let base = identifier(label, separator);
+            let mut name = base.clone();
+            let mut n = 2;
+            while !taken.insert(name.clone()) {
+                name = format!("{}{}{}", base, separator, n);
+                n += 1;
+            }
+            name
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identifier() {
+        assert_eq!(identifier("Brand / Primary 2", '-'), "brand-primary-2");
+        assert_eq!(identifier("Ink_Dark", '_'), "ink_dark");
+        assert_eq!(identifier("★", '-'), "color");
+    }
+}
@@ -0,0 +1,62 @@
+//! Sass variables: one `$name: value;` line per color, optionally followed
+//! by a map of them all for `@each` loops.
+
+use super::{identifier, unique_identifiers, CodeOptions};
+use crate::snippet::ColorData;
+
+/// Sass names may not start with a digit, so "500" becomes `color-500`.
+fn sass_name(name: String) -> String {
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("color-{}", name)
+    } else {
+        name
+    }
+}
+
+/// Write colors as Sass variables, with a map named after the palette when
+/// asked.
+pub fn scss_variables(name: &str, colors: &[(String, ColorData)], options: &CodeOptions) -> String {
+    let names: Vec<String> = unique_identifiers(colors, '-')
+        .into_iter()
+        .map(sass_name)
+        .collect();
+    let mut scss = String::new();
+    for ((_, color), name) in colors.iter().zip(&names) {
+        scss.push_str(&format!("${}: {};\n", name, options.value.format(color)));
+    }
+    if options.map && !names.is_empty() {
+        scss.push_str(&format!("\n${}: (\n", sass_name(identifier(name, '-'))));
+        for name in &names {
+            scss.push_str(&format!("  \"{}\": ${},\n", name, name));
+        }
+        scss.push_str(");\n");
+    }
+    scss
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scss_variables() {
+        let colors = vec![
+            ("Brand Red".to_string(), ColorData::new(255, 0, 0, 1.0)),
+            ("500".to_string(), ColorData::new(0, 0, 255, 1.0)),
+        ];
+        assert_eq!(
+            scss_variables("Brand", &colors, &CodeOptions::default()),
+            "$brand-red: #FF0000;\n$color-500: #0000FF;\n"
+        );
+
+        let options = CodeOptions {
+            map: true,
+            ..CodeOptions::default()
+        };
+        assert_eq!(
+            scss_variables("Brand", &colors, &options),
+            "$brand-red: #FF0000;\n$color-500: #0000FF;\n\n$brand: (\n  \"brand-red\": $brand-red,\n  \"color-500\": $color-500,\n);\n"
+        );
+        assert_eq!(scss_variables("Brand", &[], &options), "");
+    }
+}
@@ -0,0 +1,115 @@
+//! Tailwind CSS themes: the `colors` of a `tailwind.config.js` (v3), or an
+//! `@theme` block of `--color-*` variables (v4). With shades, each color
+//! becomes a 50–950 scale built around it.
+
+use super::{unique_identifiers, CodeOptions};
+use crate::snippet::{color_scale, scale_step_names, ColorData};
+
+/// Steps of a Tailwind shade scale, 50 to 950.
+const SHADE_STEPS: usize = 11;
+
+/// The shades of a color as (step, color), or none without shades.
+fn shades(color: &ColorData, options: &CodeOptions) -> Vec<(u32, ColorData)> {
+    if !options.shades {
+        return Vec::new();
+    }
+    scale_step_names(SHADE_STEPS)
+        .into_iter()
+        .zip(color_scale(color, SHADE_STEPS))
+        .collect()
+}
+
+/// Write colors as a Tailwind v3 config extending the theme's colors. With
+/// shades, the color itself is the scale's `DEFAULT`.
+pub fn tailwind_config(colors: &[(String, ColorData)], options: &CodeOptions) -> String {
+    let mut js = String::from(
+        "/** @type {import('tailwindcss').Config} */\nmodule.exports = {\n  theme: {\n    extend: {\n      colors: {\n",
+    );
+    for ((_, color), name) in colors.iter().zip(unique_identifiers(colors, '-')) {
+        let shades = shades(color, options);
+        if shades.is_empty() {
+            js.push_str(&format!(
+                "        '{}': '{}',\n",
+                name,
+                options.value.format(color)
+            ));
+            continue;
+        }
+        js.push_str(&format!("        '{}': {{\n", name));
+        for (step, shade) in shades {
+            js.push_str(&format!(
+                "          {}: '{}',\n",
+                step,
+                options.value.format(&shade)
+            ));
+        }
+        js.push_str(&format!(
+            "          DEFAULT: '{}',\n        }},\n",
+            options.value.format(color)
+        ));
+    }
+    js.push_str("      },\n    },\n  },\n};\n");
+    js
+}
+
+/// Write colors as a Tailwind v4 `@theme` block.
+pub fn tailwind_theme(colors: &[(String, ColorData)], options: &CodeOptions) -> String {
+    let mut css = String::from("@theme {\n");
+    for ((_, color), name) in colors.iter().zip(unique_identifiers(colors, '-')) {
+        css.push_str(&format!(
+            "  --color-{}: {};\n",
+            name,
+            options.value.format(color)
+        ));
+        for (step, shade) in shades(color, options) {
+            css.push_str(&format!(
+                "  --color-{}-{}: {};\n",
+                name,
+                step,
+                options.value.format(&shade)
+            ));
+        }
+    }
+    css.push_str("}\n");
+    css
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::ColorValue;
+
+    fn brand() -> Vec<(String, ColorData)> {
+        vec![("Brand Blue".to_string(), ColorData::new(59, 130, 246, 1.0))]
+    }
+
+    #[test]
+    fn test_tailwind_config() {
+        let js = tailwind_config(&brand(), &CodeOptions::default());
+        assert!(js.starts_with("/** @type {import('tailwindcss').Config} */\nmodule.exports = {"));
+        assert!(js.contains("      colors: {\n        'brand-blue': '#3B82F6',\n      },"));
+
+        let options = CodeOptions {
+            shades: true,
+            ..CodeOptions::default()
+        };
+        let js = tailwind_config(&brand(), &options);
+        assert!(js.contains("        'brand-blue': {\n          50: '#"));
+        assert!(js.contains("          950: '#"));
+        assert!(js.contains("          DEFAULT: '#3B82F6',\n        },"));
+    }
+
+    #[test]
+    fn test_tailwind_theme() {
+        let options = CodeOptions {
+            value: ColorValue::Oklch,
+            shades: true,
+            ..CodeOptions::default()
+        };
+        let css = tailwind_theme(&brand(), &options);
+        assert!(css.starts_with("@theme {\n  --color-brand-blue: oklch("));
+        assert!(css.contains("  --color-brand-blue-50: oklch("));
+        assert!(css.contains("  --color-brand-blue-950: oklch("));
+        assert_eq!(css.lines().count(), 1 + 1 + SHADE_STEPS + 1);
+    }
+}
@@ -0,0 +1,225 @@
+//! Design tokens JSON, as drafted by the W3C Design Tokens Community Group
+//! and read by Figma Tokens and Style Dictionary: a `color` group of
+//! `{"$value": "#hex", "$type": "color"}` tokens.
+
+use serde_json::{json, Map, Value};
+
+use crate::snippet::ColorData;
+
+/// Group holding the exported colors.
+const GROUP: &str = "color";
+/// Aliases are followed this many times before giving up on a cycle.
+const MAX_ALIAS_DEPTH: usize = 16;
+
+/// Write colors as design tokens named after their labels.
+pub fn tokens_json(colors: &[(String, ColorData)]) -> String {
+    let mut group = Map::new();
+    for (label, color) in colors {
+        let base = token_name(label);
+        let mut name = base.clone();
+        let mut n = 2;
+        while group.contains_key(&name) {
+            name = format!("{} {}", base, n);
+            n += 1;
+        }
+        group.insert(name, json!({ "$value": color.to_hex(), "$type": "color" }));
+    }
+    let tokens = json!({ GROUP: group });
+    let mut json = serde_json::to_string_pretty(&tokens).unwrap_or_default();
+    json.push('\n');
+    json
+}
+
+/// Token names may not contain `.`, `{` or `}` (used by aliases) nor start
+/// with `$`.
+fn token_name(label: &str) -> String {
+    let name: String = label
+        .trim()
+        .trim_start_matches('$')
+        .chars()
+        .map(|c| if matches!(c, '.' | '{' | '}') { '-' } else { c })
+        .collect();
+    if name.is_empty() {
+        "color".to_string()
+    } else {
+        name
+    }
+}
+
+/// Read the color tokens of a design tokens file, in file order, labeled by
+/// their path without a leading `color` group, e.g. `brand primary`.
+///
+/// Tokens are colors when they or a group around them have `"$type":
+/// "color"`, or when untyped and their value parses as a color. Aliases
+/// such as `{color.brand.primary}` are resolved; other tokens are skipped.
+pub fn parse_tokens(json: &str) -> Result<Vec<(String, ColorData)>, String> {
+    let root: Value =
+        serde_json::from_str(json).map_err(|e| format!("Invalid design tokens JSON: {}", e))?;
+    if !root.is_object() {
+        return Err("Invalid design tokens JSON: expected an object".to_string());
+    }
+
+    let mut tokens = Vec::new();
+    collect_tokens(&root, &mut Vec::new(), None, &mut tokens);
+
+    let values: std::collections::HashMap<String, &Value> = tokens
+        .iter()
+        .map(|token| (token.path.join("."), token.value))
+        .collect();
+    let colors = tokens
+        .iter()
+        .filter(|token| token.kind.is_none_or(|kind| kind == "color"))
+        .filter_map(|token| {
+            let color = resolve(token.value, &values, 0)?;
+            let path = match token.path.split_first() {
+                Some((first, rest)) if !rest.is_empty() && is_color_group(first) => rest,
+                _ => &token.path[..],
+            };
+            Some((path.join(" "), color))
+        })
+        .collect();
+    Ok(colors)
+}
+
+/// A token found in the tree, with the `$type` it has or inherits.
+struct Token<'a> {
+    path: Vec<String>,
+    kind: Option<&'a str>,
+    value: &'a Value,
+}
+
+/// Walk a group, collecting its tokens depth first.
+fn collect_tokens<'a>(
+    group: &'a Value,
+    path: &mut Vec<String>,
+    kind: Option<&'a str>,
+    tokens: &mut Vec<Token<'a>>,
+) {
+    let Some(members) = group.as_object() else {
+        return;
+    };
+    let kind = members.get("$type").and_then(Value::as_str).or(kind);
+    if let Some(value) = members.get("$value") {
+        tokens.push(Token {
+            path: path.clone(),
+            kind,
+            value,
+        });
+        return;
+    }
+    for (name, member) in members {
+        if name.starts_with('$') {
+            continue;
+        }
+        path.push(name.clone());
+        collect_tokens(member, path, kind, tokens);
+        path.pop();
+    }
+}
+
+/// Read a token value as a color, following aliases.
+fn resolve(
+    value: &Value,
+    values: &std::collections::HashMap<String, &Value>,
+    depth: usize,
+) -> Option<ColorData> {
+    match value {
+        Value::String(text) => {
+            let text = text.trim();
+            if let Some(alias) = text.strip_prefix('{').and_then(|t| t.strip_suffix('}')) {
+                if depth >= MAX_ALIAS_DEPTH {
+                    return None;
+                }
+                return resolve(values.get(alias)?, values, depth + 1);
+            }
+            ColorData::parse(text).ok()
+        }
+        // The newer object form: {"colorSpace": "srgb", "components": [...], "hex": ...}
+        Value::Object(color) => {
+            let alpha = color.get("alpha").and_then(Value::as_f64).unwrap_or(1.0) as f32;
+            if let Some(hex) = color.get("hex").and_then(Value::as_str) {
+                let parsed = ColorData::parse(hex).ok()?;
+                return Some(ColorData::new(parsed.r, parsed.g, parsed.b, alpha));
+            }
+            if color.get("colorSpace").and_then(Value::as_str) != Some("srgb") {
+                return None;
+            }
+            let components = color.get("components")?.as_array()?;
+            let channel = |i: usize| -> Option<u8> {
+                let value = components.get(i)?.as_f64()?;
+                Some((value.clamp(0.0, 1.0) * 255.0).round() as u8)
+            };
+            Some(ColorData::new(channel(0)?, channel(1)?, channel(2)?, alpha))
+        }
+        _ => None,
+    }
+}
+
+/// Check if a top-level group only says its tokens are colors.
+fn is_color_group(name: &str) -> bool {
+    matches!(name.to_ascii_lowercase().as_str(), "color" | "colors")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokens_round_trip() {
+        let colors = vec![
+            ("Brand Red".to_string(), ColorData::new(255, 0, 0, 1.0)),
+            ("Brand Red".to_string(), ColorData::new(0, 0, 0, 0.5)),
+            ("v1.2".to_string(), ColorData::new(0, 0, 255, 1.0)),
+        ];
+        let json = tokens_json(&colors);
+        assert!(json.contains(
+            "\"Brand Red\": {\n      \"$value\": \"#FF0000\",\n      \"$type\": \"color\"\n    }"
+        ));
+        assert_eq!(
+            parse_tokens(&json).unwrap(),
+            vec![
+                ("Brand Red".to_string(), ColorData::new(255, 0, 0, 1.0)),
+                (
+                    "Brand Red 2".to_string(),
+                    ColorData::new(0, 0, 0, 128.0 / 255.0)
+                ),
+                ("v1-2".to_string(), ColorData::new(0, 0, 255, 1.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_tokens_groups_types_and_aliases() {
+        let json = r##"{
+            "brand": {
+                "$type": "color",
+                "primary": { "$value": "#3B82F6" },
+                "accent": { "$value": "{brand.primary}" },
+                "loop": { "$value": "{brand.loop}" }
+            },
+            "spacing": { "sm": { "$value": "4px", "$type": "dimension" } },
+            "ink": { "$value": "rgb(0, 0, 0)" },
+            "wide": {
+                "$type": "color",
+                "$value": { "colorSpace": "srgb", "components": [1, 0.5, 0], "alpha": 0.5 }
+            }
+        }"##;
+        assert_eq!(
+            parse_tokens(json).unwrap(),
+            vec![
+                (
+                    "brand primary".to_string(),
+                    ColorData::new(59, 130, 246, 1.0)
+                ),
+                (
+                    "brand accent".to_string(),
+                    ColorData::new(59, 130, 246, 1.0)
+                ),
+                ("ink".to_string(), ColorData::new(0, 0, 0, 1.0)),
+                ("wide".to_string(), ColorData::new(255, 128, 0, 0.5)),
+            ]
+        );
+        assert!(parse_tokens("[]").is_err());
+        assert!(parse_tokens("{").is_err());
+    }
+}
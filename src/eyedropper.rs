@@ -0,0 +1,162 @@
+//! Screen eyedropper: a screenshot of the screen, sampled under a magnifier
+//! drawn in a fullscreen overlay window.
+//!
+//! Captured with the tools each platform ships: `screencapture` on macOS,
+//! System.Drawing through PowerShell on Windows, and `grim` on Wayland or
+//! ImageMagick's `import` on X11. Only the primary display is captured.
+
+use std::path::Path;
+use std::process::Command;
+
+use crate::snippet::{decode_png, ColorData};
+
+/// A captured screen, as 8-bit RGBA rows.
+#[derive(Clone)]
+pub struct Screenshot {
+    pub width: u32,
+    pub height: u32,
+    rgba: Vec<u8>,
+}
+
+impl std::fmt::Debug for Screenshot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Screenshot({}x{})", self.width, self.height)
+    }
+}
+
+impl Screenshot {
+    /// Decode a PNG screenshot.
+    pub fn from_png(bytes: &[u8]) -> Result<Self, String> {
+        let (width, height, rgba) = decode_png(bytes)?;
+        Ok(Self {
+            width,
+            height,
+            rgba,
+        })
+    }
+
+    /// Get the color of a pixel, if it is inside the screenshot.
+    pub fn pixel(&self, x: u32, y: u32) -> Option<ColorData> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let start = (y as usize * self.width as usize + x as usize) * 4;
+        let p = self.rgba.get(start..start + 4)?;
+        // Screens are opaque; a translucent pixel is a capture artifact
+        Some(ColorData::new(p[0], p[1], p[2], 1.0))
+    }
+
+    /// Get the pixel under a point of a window covering the screen. The
+    /// screenshot is in physical pixels, so on scaled displays one window
+    /// point covers several of them.
+    pub fn pixel_at(&self, point: iced::Point, window: iced::Size) -> Option<(u32, u32)> {
+        if window.width <= 0.0 || window.height <= 0.0 || point.x < 0.0 || point.y < 0.0 {
+            return None;
+        }
+        let x = (point.x * self.width as f32 / window.width) as u32;
+        let y = (point.y * self.height as f32 / window.height) as u32;
+        (x < self.width && y < self.height).then_some((x, y))
+    }
+}
+
+/// Capture the primary display.
+pub fn capture_screen() -> Result<Screenshot, String> {
+    let path = std::env::temp_dir().join(format!("shard-eyedropper-{}.png", std::process::id()));
+    let captured = run_capture(&path);
+    let bytes = captured.and_then(|_| {
+        std::fs::read(&path).map_err(|e| format!("Failed to read the screenshot: {}", e))
+    });
+    let _ = std::fs::remove_file(&path);
+    Screenshot::from_png(&bytes?)
+}
+
+#[cfg(target_os = "macos")]
+fn run_capture(path: &Path) -> Result<(), String> {
+    run(Command::new("screencapture")
+        .args(["-x", "-t", "png"])
+        .arg(path))
+}
+
+#[cfg(target_os = "windows")]
+fn run_capture(path: &Path) -> Result<(), String> {
+    let script = format!(
+        "Add-Type -AssemblyName System.Windows.Forms, System.Drawing; \
+         $b = [System.Windows.Forms.Screen]::PrimaryScreen.Bounds; \
+         $i = New-Object System.Drawing.Bitmap $b.Width, $b.Height; \
+         $g = [System.Drawing.Graphics]::FromImage($i); \
+         $g.CopyFromScreen($b.Location, [System.Drawing.Point]::Empty, $b.Size); \
+         $i.Save('{}', [System.Drawing.Imaging.ImageFormat]::Png)",
+        path.display().to_string().replace('\'', "''")
+    );
+    run(Command::new("powershell").args(["-NoProfile", "-Command", &script]))
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn run_capture(path: &Path) -> Result<(), String> {
+    if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        run(Command::new("grim").arg(path))
+    } else {
+        run(Command::new("import").args(["-window", "root"]).arg(path))
+    }
+}
+
+/// Run a capture command, describing why it failed.
+fn run(command: &mut Command) -> Result<(), String> {
+    let program = command.get_program().to_string_lossy().into_owned();
+    let output = command
+        .output()
+        .map_err(|e| format!("Failed to run {}: {}", program, e))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(format!("{} failed: {}", program, stderr.trim()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Encode a 2x2 RGB image: red, green / blue, white.
+    fn test_png() -> Vec<u8> {
+        let mut bytes = Vec::new();
+        let mut encoder = png::Encoder::new(&mut bytes, 2, 2);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().unwrap();
+        writer
+            .write_image_data(&[255, 0, 0, 0, 255, 0, 0, 0, 255, 255, 255, 255])
+            .unwrap();
+        writer.finish().unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_from_png() {
+        let shot = Screenshot::from_png(&test_png()).unwrap();
+        assert_eq!((shot.width, shot.height), (2, 2));
+        assert_eq!(shot.pixel(0, 0), Some(ColorData::new(255, 0, 0, 1.0)));
+        assert_eq!(shot.pixel(1, 0), Some(ColorData::new(0, 255, 0, 1.0)));
+        assert_eq!(shot.pixel(0, 1), Some(ColorData::new(0, 0, 255, 1.0)));
+        assert_eq!(shot.pixel(2, 0), None);
+        assert!(Screenshot::from_png(b"not a png").is_err());
+    }
+
+    #[test]
+    fn test_pixel_at_scales_to_physical_pixels() {
+        let shot = Screenshot::from_png(&test_png()).unwrap();
+        // A 1x1 point window over a 2x2 pixel screen
+        let window = iced::Size::new(1.0, 1.0);
+        assert_eq!(
+            shot.pixel_at(iced::Point::new(0.2, 0.7), window),
+            Some((0, 1))
+        );
+        assert_eq!(
+            shot.pixel_at(iced::Point::new(0.9, 0.1), window),
+            Some((1, 0))
+        );
+        assert_eq!(shot.pixel_at(iced::Point::new(1.0, 0.5), window), None);
+        assert_eq!(shot.pixel_at(iced::Point::new(-0.1, 0.5), window), None);
+    }
+}
@@ -5,6 +5,8 @@
 use iced::widget::text;
 use iced::Font;
 
+use crate::snippet::PHOSPHOR_ICON_PREFIX;
+
 /// Phosphor Light icon font.
 pub const ICON_FONT: Font = Font::with_name("Phosphor-Light");
 
@@ -105,6 +107,46 @@ pub fn text_icon() -> text::Text<'static> {
     icon('\u{e48a}')
 }
 
+/// Text Aa icon (for copying rich text)
+pub fn text_aa() -> text::Text<'static> {
+    icon('\u{e6ee}')
+}
+
+/// File icon (for file snippets), picked by file extension
+pub fn file_icon(extension: Option<&str>) -> text::Text<'static> {
+    icon(file_icon_codepoint(extension))
+}
+
+/// Get the file icon codepoint for a file extension.
+pub fn file_icon_codepoint(extension: Option<&str>) -> char {
+    match extension {
+        Some("pdf") => '\u{e702}',
+        Some("zip" | "gz" | "tar" | "7z" | "rar" | "xz") => '\u{e958}',
+        Some("png" | "jpg" | "jpeg" | "gif" | "webp" | "svg" | "bmp" | "ico") => '\u{ea24}',
+        Some("mp3" | "wav" | "flac" | "ogg" | "m4a") => '\u{ea20}',
+        Some("mp4" | "mov" | "mkv" | "webm" | "avi") => '\u{ea22}',
+        Some("txt" | "md" | "csv" | "log" | "rtf") => '\u{e23a}',
+        Some("key" | "pem" | "p12" | "gpg") => '\u{e95c}',
+        Some(extension) if crate::snippet::extension_to_language(extension).is_some() => '\u{e914}',
+        _ => '\u{e230}',
+    }
+}
+
+/// Arrow circle up icon (for an available update)
+pub fn arrow_circle_up() -> text::Text<'static> {
+    icon('\u{e030}')
+}
+
+/// Download icon (for downloading a release)
+pub fn download() -> text::Text<'static> {
+    icon('\u{e20c}')
+}
+
+/// Paperclip icon (for attaching a file)
+pub fn paperclip() -> text::Text<'static> {
+    icon('\u{e39a}')
+}
+
 /// Arrow square out icon (for open in external editor)
 pub fn arrow_square_out() -> text::Text<'static> {
     icon('\u{e5de}')
@@ -115,6 +157,11 @@ pub fn gear() -> text::Text<'static> {
     icon('\u{e87c}')
 }
 
+/// Timer icon (for capture sessions)
+pub fn timer() -> text::Text<'static> {
+    icon('\u{e492}')
+}
+
 /// Tag icon (for palettes/categories)
 pub fn tag() -> text::Text<'static> {
     icon('\u{e478}')
@@ -130,6 +177,11 @@ pub fn push_pin() -> text::Text<'static> {
     icon('\u{e3e2}')
 }
 
+/// Columns icon (for the split pane layout)
+pub fn columns() -> text::Text<'static> {
+    icon('\u{e546}')
+}
+
 /// Keyboard icon (for keyboard shortcuts)
 pub fn keyboard() -> text::Text<'static> {
     icon('\u{e2d8}')
@@ -140,16 +192,196 @@ pub fn swatches() -> text::Text<'static> {
     icon('\u{e5b8}')
 }
 
+/// Arrows split icon (for splitting a color set)
+pub fn arrows_split() -> text::Text<'static> {
+    icon('\u{ed3c}')
+}
+
 /// Export icon (for data export)
 pub fn export() -> text::Text<'static> {
     icon('\u{eaf0}')
 }
 
+/// Half-filled circle icon (for the contrast checker)
+pub fn circle_half() -> text::Text<'static> {
+    icon('\u{e18c}')
+}
+
+/// Up and down arrows icon (for swapping two values)
+pub fn arrows_down_up() -> text::Text<'static> {
+    icon('\u{e098}')
+}
+
+/// Stack icon (for color scales)
+pub fn stack() -> text::Text<'static> {
+    icon('\u{e466}')
+}
+
+/// Eyedropper icon (for sampling a color from the screen)
+pub fn eyedropper() -> text::Text<'static> {
+    icon('\u{e568}')
+}
+
+/// Mobile device icon (for mobile asset export)
+pub fn device_mobile() -> text::Text<'static> {
+    icon('\u{e1e0}')
+}
+
 /// Sliders icon (for settings/adjustments)
 pub fn sliders() -> text::Text<'static> {
     icon('\u{e432}')
 }
 
+/// Tray icon (for the auto-capture inbox)
+pub fn tray() -> text::Text<'static> {
+    icon('\u{e4aa}')
+}
+
+/// Table icon (for the table list layout)
+pub fn table() -> text::Text<'static> {
+    icon('\u{e476}')
+}
+
+/// Cards icon (for the card list layout)
+pub fn cards() -> text::Text<'static> {
+    icon('\u{e0f8}')
+}
+
+/// Caret up icon (for ascending sort)
+pub fn caret_up() -> text::Text<'static> {
+    icon('\u{e13c}')
+}
+
+/// Caret down icon (for descending sort)
+pub fn caret_down() -> text::Text<'static> {
+    icon('\u{e136}')
+}
+
+/// Check square icon (for picking several snippets)
+pub fn check_square() -> text::Text<'static> {
+    icon('\u{e186}')
+}
+
+/// Caret right icon (for a collapsed palette branch)
+pub fn caret_right() -> text::Text<'static> {
+    icon('\u{e13a}')
+}
+
+/// Caret left icon (for an expanded palette branch)
+pub fn caret_left() -> text::Text<'static> {
+    icon('\u{e138}')
+}
+
+/// Folder open icon (for revealing a linked file)
+pub fn folder_open() -> text::Text<'static> {
+    icon('\u{e256}')
+}
+
+/// Warning icon (for a linked file changed on disk)
+pub fn warning() -> text::Text<'static> {
+    icon('\u{e4e0}')
+}
+
+/// Rows icon (for section headers)
+pub fn rows() -> text::Text<'static> {
+    icon('\u{e5a2}')
+}
+
+/// Counter-clockwise arrow icon (for undo)
+pub fn arrow_counter_clockwise() -> text::Text<'static> {
+    icon('\u{e038}')
+}
+
+/// Notepad icon (for the scratchpad)
+pub fn notepad() -> text::Text<'static> {
+    icon('\u{e63e}')
+}
+
+/// Image icon (for share images)
+pub fn image() -> text::Text<'static> {
+    icon('\u{e2ca}')
+}
+
+/// Three dots icon (for a card's overflow menu)
+pub fn dots_three() -> text::Text<'static> {
+    icon('\u{e1fe}')
+}
+
+/// Gradient icon (for gradient snippets and their editor)
+pub fn gradient() -> text::Text<'static> {
+    icon('\u{eb42}')
+}
+
+/// Sun icon (for lighter color variants)
+pub fn sun() -> text::Text<'static> {
+    icon('\u{e472}')
+}
+
+/// Moon icon (for darker color variants)
+pub fn moon() -> text::Text<'static> {
+    icon('\u{e330}')
+}
+
+/// Fire icon (for warmer color variants)
+pub fn fire() -> text::Text<'static> {
+    icon('\u{e242}')
+}
+
+/// Snowflake icon (for cooler color variants)
+pub fn snowflake() -> text::Text<'static> {
+    icon('\u{e5aa}')
+}
+
+/// Play icon (for running code snippets)
+pub fn play() -> text::Text<'static> {
+    icon('\u{e3d0}')
+}
+
+/// Smiley icon (for choosing a snippet icon)
+pub fn smiley() -> text::Text<'static> {
+    icon('\u{e436}')
+}
+
+/// Question icon (for the color picker tour)
+pub fn question() -> text::Text<'static> {
+    icon('\u{e3e8}')
+}
+
+/// Database icon (for database maintenance)
+pub fn database() -> text::Text<'static> {
+    icon('\u{e1de}')
+}
+
+/// Star icon (for favorites)
+pub fn star() -> text::Text<'static> {
+    icon('\u{e46a}')
+}
+
+/// Curly brackets icon (for JSON snippets)
+pub fn brackets_curly() -> text::Text<'static> {
+    icon('\u{e860}')
+}
+
+/// Tree structure icon (for the JSON tree view)
+pub fn tree_structure() -> text::Text<'static> {
+    icon('\u{e67c}')
+}
+
+/// Text indent icon (for pretty-printing)
+pub fn text_indent() -> text::Text<'static> {
+    icon('\u{ea1e}')
+}
+
+/// Arrows in line icon (for minifying)
+pub fn arrows_in_line_horizontal() -> text::Text<'static> {
+    icon('\u{e530}')
+}
+
+/// Markdown logo icon (for copying as Markdown)
+pub fn markdown_logo() -> text::Text<'static> {
+    icon('\u{e508}')
+}
+
 // === Icon constants for use in text widgets ===
 
 /// Code icon codepoint
@@ -157,3 +389,48 @@ pub const CODE_ICON: char = '\u{e1bc}';
 
 /// Text icon codepoint
 pub const TEXT_ICON: char = '\u{e48a}';
+
+/// JSON (curly brackets) icon codepoint
+pub const JSON_ICON: char = '\u{e860}';
+
+// === Snippet Icons ===
+
+/// Phosphor icons offered as custom snippet icons, by name.
+pub const SNIPPET_ICONS: [(&str, char); 20] = [
+    ("star", '\u{e46a}'),
+    ("heart", '\u{e2a8}'),
+    ("lightning", '\u{e2de}'),
+    ("fire", '\u{e242}'),
+    ("bug", '\u{e5f4}'),
+    ("terminal", '\u{e47e}'),
+    ("database", '\u{e1de}'),
+    ("globe", '\u{e288}'),
+    ("key", '\u{e2d6}'),
+    ("lock", '\u{e2fa}'),
+    ("envelope", '\u{e214}'),
+    ("rocket", '\u{e3fc}'),
+    ("flag", '\u{e244}'),
+    ("bookmark", '\u{e0e8}'),
+    ("lightbulb", '\u{e2dc}'),
+    ("coffee", '\u{e1c2}'),
+    ("house", '\u{e2c2}'),
+    ("link", '\u{e2e2}'),
+    ("warning", '\u{e4e0}'),
+    ("cloud", '\u{e1aa}'),
+];
+
+/// Create a Phosphor snippet icon by name.
+pub fn named(name: &str) -> Option<text::Text<'static>> {
+    SNIPPET_ICONS
+        .iter()
+        .find(|(icon_name, _)| *icon_name == name)
+        .map(|(_, codepoint)| icon(*codepoint))
+}
+
+/// Render a snippet's custom icon: a named Phosphor icon, or an emoji.
+pub fn snippet_icon(value: &str) -> text::Text<'static> {
+    value
+        .strip_prefix(PHOSPHOR_ICON_PREFIX)
+        .and_then(named)
+        .unwrap_or_else(|| text(value.to_string()))
+}
@@ -5,30 +5,40 @@
 //! A desktop application for managing colors, code snippets, and text snippets.
 
 mod config;
+mod crash;
 mod db;
+mod expander;
+mod export;
+mod eyedropper;
 mod icons;
 mod message;
+mod releases;
+mod share_image;
 mod snippet;
+mod source_app;
 mod theme;
 mod update;
 mod view;
 mod widgets;
 
-use config::{Modifiers, Shortcut};
+use config::Shortcut;
 use iced::keyboard;
 use iced::window;
 use iced::{Element, Subscription, Theme};
+use view::labeled;
 
 pub use message::Message;
 pub use update::{Shard, WindowKind};
 
 pub fn main() -> iced::Result {
+    crash::install_hook();
     iced::daemon(Shard::new, Shard::update, Shard::view)
         .font(icons::ICON_FONT_BYTES)
         .font(icons::TEXT_FONT_BYTES)
         .default_font(icons::TEXT_FONT)
         .title(Shard::title)
         .theme(Shard::theme)
+        .style(Shard::style)
         .subscription(Shard::subscription)
         .run()
 }
@@ -42,6 +52,9 @@ impl Shard {
                 // Render pinned snippet view (minimal)
                 self.view_pinned_snippet(*snippet_id, window_id)
             }
+            Some(WindowKind::Board(palette_id)) => self.view_palette_board(*palette_id, window_id),
+            Some(WindowKind::Eyedropper) => self.view_eyedropper(),
+            Some(WindowKind::QuickPopup) => self.view_quick_popup(window_id),
             Some(WindowKind::Main) | None => {
                 // Render main application view
                 view::view(view::ViewContext {
@@ -50,6 +63,8 @@ impl Shard {
                     status_message: self.status_message.as_deref(),
                     filter_text: &self.filter_text,
                     filter_kind: self.filter_kind.as_ref(),
+                    filter_language: self.filter_language.as_deref(),
+                    filter_source_app: self.filter_source_app.as_deref(),
                     selected_snippet: self.selected_snippet,
                     color_picker: self.color_picker.as_ref(),
                     code_editor: self.code_editor.as_ref(),
@@ -60,8 +75,70 @@ impl Shard {
                     filter_palette: self.filter_palette,
                     palette_manager_open: self.palette_manager_open,
                     palette_dropdown_snippet: self.palette_dropdown_snippet,
+                    palette_dropdown_query: &self.palette_dropdown_query,
+                    palette_dropdown_cursor: self.palette_dropdown_cursor,
                     snippet_palettes: &self.snippet_palettes,
                     new_palette_name: &self.new_palette_name,
+                    new_palette_query: &self.new_palette_query,
+                    pinned_panel_open: self.pinned_panel_open,
+                    pinned_windows: self.pinned_windows(),
+                    color_value_drafts: &self.color_value_drafts,
+                    inbox_open: self.inbox_open,
+                    inbox_cursor: self.inbox_cursor,
+                    card_actions: self.config.card_actions,
+                    code_theme: self.config.code_theme,
+                    editing: self.config.editing,
+                    list_layout: self.config.list_layout,
+                    sort_order: self.config.sort_order,
+                    table: &self.table,
+                    capture_palette: self.config.capture_palette,
+                    capture_to_palette: self.config.capture_to_palette,
+                    capture_session: self.capture_session.as_ref(),
+                    capture_session_draft: self.capture_session_draft.as_ref(),
+                    scratchpad_open: self.scratchpad_open,
+                    scratchpad: &self.scratchpad,
+                    region_scan: self.region_scan.as_ref(),
+                    show_favorites_bar: self.config.show_favorites_bar,
+                    show_source_badge: self.config.show_source_badge,
+                    copy_templates: &self.config.copy_templates,
+                    recent_colors: &self.config.recent_colors,
+                    card_menu_snippet: self.card_menu_snippet,
+                    hovered_snippet: self.hovered_snippet,
+                    changed_files: &self.changed_files,
+                    expanded_palettes: &self.expanded_palettes,
+                    selection_mode: self.selection_mode,
+                    selected_snippets: &self.selected_snippets,
+                    batch_language: &self.batch_language,
+                    split_pane: &self.config.split_pane,
+                    split_pane_filter_text: &self.split_pane_filter_text,
+                    icon_picker: self.icon_picker.as_ref(),
+                    abbreviation_editor: self.abbreviation_editor.as_ref(),
+                    text_expansion: self.config.text_expansion,
+                    json_tree: self.json_tree.as_ref(),
+                    save_conflict: self.save_conflict.as_ref(),
+                    edit_review: self.edit_review.as_ref(),
+                    bulk_delete: self.bulk_delete.as_ref(),
+                    sections: &self.sections,
+                    section_draft: self.section_draft.as_ref(),
+                    unlock: self.unlock.as_ref(),
+                    encrypt_database: self.encrypt_database.as_ref(),
+                    database_encrypted: self.database_encrypted,
+                    trash: &self.trash,
+                    trash_open: self.trash_open,
+                    trash_retention: self.config.trash_retention,
+                    restore_available: self.restore_backup.is_some(),
+                    reformat_undo_available: self.reformat_undo.is_some(),
+                    crash_report_available: self.crash_report.is_some(),
+                    available_update: self.available_update.as_ref(),
+                    release_notes_open: self.release_notes_open,
+                    share_image: self.share_image.as_ref(),
+                    contrast: self.contrast.as_ref(),
+                    similar_colors: self.similar_colors.as_ref(),
+                    template_fill: self.template_fill.as_ref(),
+                    run_output: self.run_output.as_ref(),
+                    scale: self.scale.as_ref(),
+                    code_export: self.code_export.as_ref(),
+                    gradient_editor: self.gradient_editor.as_ref(),
                 })
             }
         }
@@ -69,16 +146,18 @@ impl Shard {
 
     /// Render a pinned snippet window.
     fn view_pinned_snippet(&self, snippet_id: i64, window_id: window::Id) -> Element<'_, Message> {
-        use iced::widget::{button, center, column, container, row, text, Canvas};
         use crate::snippet::SnippetContent;
         use crate::theme::{
-            BG_BASE, SPACE_MD, SPACE_SM, TEXT_MUTED, TEXT_PRIMARY, TEXT_SECONDARY,
-            danger_button_style,
+            danger_button_style, BG_BASE, SPACE_MD, SPACE_SM, TEXT_MUTED, TEXT_PRIMARY,
+            TEXT_SECONDARY,
         };
-        use crate::widgets::ColorSwatch;
+        use crate::widgets::{ColorSwatch, GradientPreview};
+        use iced::widget::{button, center, column, row, text, Canvas};
 
         let Some(snippet) = self.snippets.iter().find(|s| s.id == snippet_id) else {
-            return center(text("Snippet not found").size(14).color(TEXT_MUTED)).into();
+            return center(text("Snippet not found").size(14).color(TEXT_MUTED))
+                .style(|_| iced::widget::container::Style::default().background(BG_BASE))
+                .into();
         };
 
         // Content based on snippet type
@@ -99,38 +178,82 @@ impl Shard {
                     .align_x(iced::Alignment::Center)
                     .into()
             }
+            SnippetContent::ColorSet(set) => {
+                let hex_text = text(set.to_hex_list()).size(11).color(TEXT_PRIMARY);
+                let label_text = pinned_label(snippet);
+
+                column![label_text, view::view_swatch_strip(set, 24.0), hex_text]
+                    .spacing(SPACE_SM)
+                    .into()
+            }
+            SnippetContent::Gradient(gradient) => {
+                let preview = Canvas::new(GradientPreview { gradient })
+                    .width(iced::Length::Fill)
+                    .height(64);
+                let css_text = text(gradient.to_css()).size(11).color(TEXT_PRIMARY);
+                let label_text = pinned_label(snippet);
+
+                column![label_text, preview, css_text]
+                    .spacing(SPACE_SM)
+                    .into()
+            }
             SnippetContent::Code(code) => {
                 let preview = code.preview(4);
                 let lang_text = text(&code.language).size(10).color(TEXT_MUTED);
                 let code_text = text(preview).size(11).color(TEXT_PRIMARY);
-                let label_text = text(&snippet.label).size(12).color(TEXT_SECONDARY);
+                let label_text = pinned_label(snippet);
 
                 column![label_text, lang_text, code_text]
                     .spacing(SPACE_SM)
                     .into()
             }
+            SnippetContent::Json(json) => {
+                let summary_text = text(json.summary()).size(10).color(TEXT_MUTED);
+                let json_text = text(json.preview(4)).size(11).color(TEXT_PRIMARY);
+                let label_text = pinned_label(snippet);
+
+                column![label_text, summary_text, json_text]
+                    .spacing(SPACE_SM)
+                    .into()
+            }
             SnippetContent::Text(text_data) => {
                 let preview = text_data.preview(4);
                 let preview_text = text(preview).size(11).color(TEXT_PRIMARY);
-                let label_text = text(&snippet.label).size(12).color(TEXT_SECONDARY);
+                let label_text = pinned_label(snippet);
 
-                column![label_text, preview_text]
+                column![label_text, preview_text].spacing(SPACE_SM).into()
+            }
+            SnippetContent::File(file) => {
+                let icon = icons::file_icon(file.extension().as_deref())
+                    .size(32)
+                    .color(TEXT_SECONDARY);
+                let name_text = text(&file.name).size(11).color(TEXT_PRIMARY);
+                let label_text = pinned_label(snippet);
+
+                column![icon, label_text, name_text]
                     .spacing(SPACE_SM)
+                    .align_x(iced::Alignment::Center)
                     .into()
             }
         };
 
         // Close button
-        let close_btn = button(icons::x().size(12))
-            .on_press(Message::UnpinSnippet(window_id))
-            .padding(SPACE_SM)
-            .style(danger_button_style);
+        let close_btn = labeled(
+            button(icons::x().size(12))
+                .on_press(Message::UnpinSnippet(window_id))
+                .padding(SPACE_SM)
+                .style(danger_button_style),
+            "Unpin",
+        );
 
         // Copy button
-        let copy_btn = button(icons::copy().size(12))
-            .on_press(Message::CopySnippet(snippet_id))
-            .padding(SPACE_SM)
-            .style(crate::theme::subtle_button_style);
+        let copy_btn = labeled(
+            button(icons::copy().size(12))
+                .on_press(Message::CopySnippet(snippet_id))
+                .padding(SPACE_SM)
+                .style(crate::theme::subtle_button_style),
+            "Copy",
+        );
 
         let header = row![copy_btn, close_btn]
             .spacing(SPACE_SM)
@@ -141,11 +264,108 @@ impl Shard {
             .padding(SPACE_MD)
             .align_x(iced::Alignment::Center);
 
-        container(layout)
+        self.pinned_window_frame(layout, window_id)
+    }
+
+    /// Render a pinned palette board window.
+    fn view_palette_board(&self, palette_id: i64, window_id: window::Id) -> Element<'_, Message> {
+        use crate::theme::{danger_button_style, SPACE_MD, SPACE_SM, TEXT_SECONDARY};
+        use iced::widget::{button, column, row, text};
+
+        let name = self
+            .palettes
+            .iter()
+            .find(|p| p.id == palette_id)
+            .map(|p| p.name.as_str())
+            .unwrap_or("(deleted palette)");
+
+        let close_btn = labeled(
+            button(icons::x().size(12))
+                .on_press(Message::UnpinSnippet(window_id))
+                .padding(SPACE_SM)
+                .style(danger_button_style),
+            "Unpin",
+        );
+
+        let header = row![
+            text(name)
+                .size(13)
+                .color(TEXT_SECONDARY)
+                .width(iced::Length::Fill),
+            close_btn
+        ]
+        .spacing(SPACE_SM)
+        .align_y(iced::Alignment::Center);
+
+        let snippets = self.board_snippets(palette_id);
+        let hotkey = self.config.board_hotkey(palette_id);
+        let layout = column![
+            header,
+            view::palette_board::view_board_snippets(&snippets, hotkey),
+            view::palette_board::view_board_hotkey(palette_id, hotkey),
+        ]
+        .spacing(SPACE_SM)
+        .padding(SPACE_MD);
+
+        self.pinned_window_frame(layout, window_id)
+    }
+
+    /// Render the quick capture/search popup, framed like the pinned windows.
+    fn view_quick_popup(&self, window_id: window::Id) -> Element<'_, Message> {
+        use crate::theme::SPACE_MD;
+        use iced::widget::container;
+
+        let matches = self.quick_popup_matches();
+        let layout = container(view::quick_popup::view_quick_popup(
+            &self.quick_query,
+            &matches,
+        ))
+        .padding(SPACE_MD);
+        self.pinned_window_frame(layout, window_id)
+    }
+
+    /// Render the eyedropper overlay: the screen as captured is still there
+    /// behind the transparent window, with a magnifier following the cursor.
+    fn view_eyedropper(&self) -> Element<'_, Message> {
+        use iced::widget::{canvas, text};
+
+        match &self.eyedropper {
+            Some(screenshot) => canvas(widgets::Magnifier { screenshot })
+                .width(iced::Length::Fill)
+                .height(iced::Length::Fill)
+                .into(),
+            None => text("").into(),
+        }
+    }
+
+    /// Wrap the content of a pinned window in its background, draggable when
+    /// the window has no title bar.
+    fn pinned_window_frame<'a>(
+        &self,
+        layout: impl Into<Element<'a, Message>>,
+        window_id: window::Id,
+    ) -> Element<'a, Message> {
+        use crate::theme::BG_BASE;
+        use iced::widget::{container, mouse_area};
+
+        let appearance = self.config.window_appearance;
+        let background = container(layout)
             .width(iced::Length::Fill)
             .height(iced::Length::Fill)
-            .style(|_| iced::widget::container::Style::default().background(BG_BASE))
-            .into()
+            .style(move |_| {
+                iced::widget::container::Style::default()
+                    .background(BG_BASE.scale_alpha(appearance.background_alpha()))
+                    .border(iced::Border::default().rounded(appearance.background_radius()))
+            });
+
+        if appearance.borderless {
+            // Without a title bar, the window is moved by dragging its background
+            mouse_area(background)
+                .on_press(Message::DragPinnedWindow(window_id))
+                .into()
+        } else {
+            background.into()
+        }
     }
 
     /// Get the window title for a specific window.
@@ -153,11 +373,25 @@ impl Shard {
         match self.windows.get(&window_id) {
             Some(WindowKind::Pinned(snippet_id)) => {
                 if let Some(snippet) = self.snippets.iter().find(|s| s.id == *snippet_id) {
-                    format!("📌 {}", snippet.label)
+                    // Phosphor icons can't be drawn in the title bar, only emoji
+                    let icon = snippet
+                        .icon
+                        .as_deref()
+                        .filter(|icon| !icon.starts_with(snippet::PHOSPHOR_ICON_PREFIX))
+                        .unwrap_or("📌");
+                    format!("{} {}", icon, snippet.label)
                 } else {
                     "Pinned Snippet".to_string()
                 }
             }
+            Some(WindowKind::Board(palette_id)) => self
+                .palettes
+                .iter()
+                .find(|p| p.id == *palette_id)
+                .map(|p| format!("📋 {}", p.name))
+                .unwrap_or_else(|| "Palette Board".to_string()),
+            Some(WindowKind::Eyedropper) => "Pick a Color".to_string(),
+            Some(WindowKind::QuickPopup) => "Quick Capture".to_string(),
             Some(WindowKind::Main) | None => "Shard - Snippet Manager".to_string(),
         }
     }
@@ -167,46 +401,134 @@ impl Shard {
         Theme::Dark
     }
 
+    /// Get the application style. The window background is left transparent
+    /// so translucent pinned windows show through; every view paints its own.
+    pub fn style(&self, theme: &Theme) -> iced::theme::Style {
+        iced::theme::Style {
+            background_color: iced::Color::TRANSPARENT,
+            text_color: theme.palette().text,
+        }
+    }
+
     /// Handle keyboard and clipboard subscriptions.
     pub fn subscription(&self) -> Subscription<Message> {
         // Get recording action and keyboard config
         let recording_action = self.settings.as_ref().and_then(|s| s.recording_action);
 
         let keyboard_config = self.config.keyboard.clone();
+        let inbox_open = self.inbox_open;
+        let palette_dropdown_open = self.palette_dropdown_snippet.is_some();
 
         // Use Subscription::with to pass captured state
         let keyboard_sub = keyboard::listen()
-            .with((recording_action, keyboard_config))
-            .filter_map(|((recording_action, keyboard_config), event)| {
-                let keyboard::Event::KeyPressed { key, modifiers, .. } = event else {
-                    return None;
-                };
-
-                // If recording, capture the key press for shortcut assignment
-                if let Some(action) = recording_action {
-                    // Create shortcut from key press
-                    let shortcut = create_shortcut_from_key(&key, modifiers);
-                    if let Some(shortcut) = shortcut {
-                        return Some(Message::ShortcutRecorded(action, shortcut));
+            .with((
+                recording_action,
+                keyboard_config,
+                inbox_open,
+                palette_dropdown_open,
+            ))
+            .filter_map(
+                |(
+                    (recording_action, keyboard_config, inbox_open, palette_dropdown_open),
+                    event,
+                )| {
+                    let keyboard::Event::KeyPressed {
+                        key,
+                        location,
+                        modifiers,
+                        ..
+                    } = event
+                    else {
+                        return None;
+                    };
+
+                    // If recording, capture the key press for shortcut assignment
+                    if let Some(action) = recording_action {
+                        return Some(match Shortcut::from_key_press(&key, location, modifiers) {
+                            Ok(shortcut) => Message::ShortcutRecorded(action, shortcut),
+                            Err(reason) => Message::ShortcutRejected(reason),
+                        });
                     }
-                    return None;
-                }
 
-                // Normal mode - check configured shortcuts
-                if keyboard_config.paste.matches(&key, modifiers) {
-                    Some(Message::PasteFromClipboard)
-                } else if keyboard_config.new_color.matches(&key, modifiers) {
-                    Some(Message::OpenColorPicker(None))
-                } else if keyboard_config.escape.matches(&key, modifiers) {
-                    Some(Message::EscapePressed)
-                } else if keyboard_config.delete.matches(&key, modifiers) {
-                    Some(Message::DeleteSelectedSnippet)
-                } else if keyboard_config.copy_snippet.matches(&key, modifiers) {
-                    Some(Message::CopySelectedSnippet)
-                } else {
-                    None
-                }
-            });
+                    // Inbox triage keys (the inbox modal has no text inputs)
+                    if inbox_open && modifiers.is_empty() {
+                        let triage = match key.as_ref() {
+                            keyboard::Key::Character("j") => Some(Message::InboxNext),
+                            keyboard::Key::Character("k") => Some(Message::InboxPrevious),
+                            keyboard::Key::Named(keyboard::key::Named::Enter) => {
+                                Some(Message::KeepInboxCurrent)
+                            }
+                            keyboard::Key::Character("x")
+                            | keyboard::Key::Named(keyboard::key::Named::Backspace) => {
+                                Some(Message::DiscardInboxCurrent)
+                            }
+                            _ => None,
+                        };
+                        if triage.is_some() {
+                            return triage;
+                        }
+                    }
+
+                    // Palette dropdown navigation (the filter input ignores Up/Down)
+                    if palette_dropdown_open {
+                        match key.as_ref() {
+                            keyboard::Key::Named(keyboard::key::Named::ArrowDown) => {
+                                return Some(Message::PaletteDropdownNext);
+                            }
+                            keyboard::Key::Named(keyboard::key::Named::ArrowUp) => {
+                                return Some(Message::PaletteDropdownPrevious);
+                            }
+                            _ => {}
+                        }
+                    }
+
+                    // Tab moves focus through the fields in reading order
+                    if key == keyboard::Key::Named(keyboard::key::Named::Tab)
+                        && !(modifiers.control() || modifiers.alt() || modifiers.logo())
+                    {
+                        return Some(if modifiers.shift() {
+                            Message::FocusPrevious
+                        } else {
+                            Message::FocusNext
+                        });
+                    }
+
+                    // Normal mode - check configured shortcuts
+                    if keyboard_config.paste.matches(&key, location, modifiers) {
+                        Some(Message::PasteFromClipboard)
+                    } else if keyboard_config.new_color.matches(&key, location, modifiers) {
+                        Some(Message::OpenColorPicker(None))
+                    } else if keyboard_config.escape.matches(&key, location, modifiers) {
+                        Some(Message::EscapePressed)
+                    } else if keyboard_config.delete.matches(&key, location, modifiers) {
+                        Some(Message::DeleteSelectedSnippet)
+                    } else if keyboard_config
+                        .copy_snippet
+                        .matches(&key, location, modifiers)
+                    {
+                        Some(Message::CopySelectedSnippet)
+                    } else if keyboard_config
+                        .toggle_pin
+                        .matches(&key, location, modifiers)
+                    {
+                        Some(Message::TogglePinSelectedSnippet)
+                    } else if keyboard_config.undo.matches(&key, location, modifiers) {
+                        Some(Message::PickerUndo)
+                    } else if keyboard_config
+                        .assign_palette
+                        .matches(&key, location, modifiers)
+                    {
+                        Some(Message::OpenPaletteAssignment)
+                    } else if keyboard_config
+                        .quick_popup
+                        .matches(&key, location, modifiers)
+                    {
+                        Some(Message::OpenQuickPopup)
+                    } else {
+                        None
+                    }
+                },
+            );
 
         let clipboard_sub = if self.is_listening_clipboard {
             iced::time::every(std::time::Duration::from_millis(500)).map(|_| Message::ClipboardTick)
@@ -214,34 +536,71 @@ impl Shard {
             Subscription::none()
         };
 
+        // Capture sessions end on their own once their time is up
+        let capture_session_sub = if self.capture_session.is_some() {
+            iced::time::every(std::time::Duration::from_secs(1))
+                .map(|_| Message::CaptureSessionTick)
+        } else {
+            Subscription::none()
+        };
+
         // Subscribe to window close events
         let window_close_sub = window::close_events().map(Message::WindowClosed);
 
-        Subscription::batch([keyboard_sub, clipboard_sub, window_close_sub])
-    }
-}
+        // Files dropped on any window are attached as file snippets, or
+        // pasted into the open code editor
+        let file_drop_sub = window::events().filter_map(|(_, event)| match event {
+            window::Event::FileDropped(path) => Some(Message::FileDropped(path)),
+            _ => None,
+        });
 
-/// Create a Shortcut from a key press event.
-fn create_shortcut_from_key(
-    key: &keyboard::Key,
-    modifiers: keyboard::Modifiers,
-) -> Option<Shortcut> {
-    let mods = Modifiers::new(modifiers.command(), modifiers.alt(), modifiers.shift());
-
-    match key {
-        keyboard::Key::Character(c) => {
-            // Don't capture pure modifier keys
-            let c_str = c.to_string();
-            if c_str.is_empty() {
+        // Number keys copy from palette boards; the window decides if it is one
+        let board_keys_sub = iced::event::listen_with(|event, status, window_id| {
+            let iced::Event::Keyboard(keyboard::Event::KeyPressed { key, modifiers, .. }) = event
+            else {
+                return None;
+            };
+            if status == iced::event::Status::Captured || !modifiers.is_empty() {
                 return None;
             }
-            Some(Shortcut::char_key(c_str.chars().next()?, mods))
-        }
-        keyboard::Key::Named(named) => {
-            // Allow named keys like Escape, Delete, Enter, etc.
-            let name = format!("{:?}", named);
-            Some(Shortcut::named(&name, mods))
-        }
-        _ => None,
+            let keyboard::Key::Character(c) = key.as_ref() else {
+                return None;
+            };
+            let digit = c.parse::<usize>().ok().filter(|d| (1..=9).contains(d))?;
+            Some(Message::BoardKeyPressed(window_id, digit - 1))
+        });
+
+        // Abbreviations typed in any app are expanded while this is on; the
+        // same listener serves the global hotkeys of palette boards
+        let expander_sub = if self.config.text_expansion || self.board_hotkeys_active() {
+            Subscription::run(expander::listen)
+        } else {
+            Subscription::none()
+        };
+
+        Subscription::batch([
+            keyboard_sub,
+            clipboard_sub,
+            expander_sub,
+            capture_session_sub,
+            window_close_sub,
+            file_drop_sub,
+            board_keys_sub,
+        ])
+    }
+}
+
+/// Render a pinned window's label, led by the snippet's custom icon.
+fn pinned_label(snippet: &snippet::Snippet) -> Element<'_, Message> {
+    use crate::theme::{SPACE_XS, TEXT_SECONDARY};
+    use iced::widget::{row, text};
+
+    let label = text(&snippet.label).size(12).color(TEXT_SECONDARY);
+    match &snippet.icon {
+        Some(icon) => row![icons::snippet_icon(icon).size(12), label]
+            .spacing(SPACE_XS)
+            .align_y(iced::Alignment::Center)
+            .into(),
+        None => label.into(),
     }
 }
@@ -1,11 +1,23 @@
 //! Application messages for the Shard snippet manager.
 
+use std::sync::Arc;
+
 use iced::window;
 
-use crate::config::{EditorPreset, Shortcut, ShortcutAction};
-use crate::db::Palette;
-use crate::snippet::{Snippet, SnippetKind};
-use crate::view::PickerMode;
+use crate::config::{
+    BoardHotkey, CardActions, CodeTheme, ColorDedupe, CombineSeparator, CopyFormat, EditorPreset,
+    MarkdownText, Modifiers, SecretPolicy, Shortcut, ShortcutAction, SnippetSort, TrashRetention,
+    WindowAppearance,
+};
+use crate::db::{BackupsEncrypted, Palette, SaveError, Section};
+use crate::expander::TypedKey;
+use crate::export::{CodeFormat, ColorValue, ExportFormat};
+use crate::eyedropper::Screenshot;
+use crate::snippet::{
+    ColorAdjustment, ColorData, JsonFormat, Snippet, SnippetKind, WhitespaceTransform,
+};
+use crate::update::{ClipboardContent, RunOutput};
+use crate::view::{PickerMode, TableColumn};
 
 /// All messages that can be sent in the application.
 #[derive(Debug, Clone)]
@@ -19,43 +31,242 @@ pub enum Message {
     PinSnippet(i64),
     /// Unpin (close) a pinned snippet window.
     UnpinSnippet(window::Id),
+    /// Pin the selected snippet, or unpin it if it is already pinned.
+    TogglePinSelectedSnippet,
+    /// Bring a pinned snippet window to the front.
+    FocusPinnedWindow(window::Id),
+    /// Move a borderless pinned window with the mouse.
+    DragPinnedWindow(window::Id),
+    /// Open a palette's snippets in an always-on-top board window.
+    PinPaletteBoard(i64),
+    /// A number key (as a 0-based index) was pressed in a window.
+    BoardKeyPressed(window::Id, usize),
+    /// Set the global hotkey of a palette's board.
+    BoardHotkeyChanged(i64, BoardHotkey),
+    /// Show the pinned windows management panel.
+    OpenPinnedPanel,
+    /// Hide the pinned windows management panel.
+    ClosePinnedPanel,
+
+    // === Quick Popup ===
+    /// Open the quick capture/search popup, or focus it if open.
+    OpenQuickPopup,
+    QuickQueryChanged(String),
+    /// Copy the first match, or capture the text if nothing matches.
+    SubmitQuickPopup,
+    QuickCopy(i64),
+    /// Add what was typed as a new snippet.
+    QuickCapture,
+    CloseQuickPopup,
 
     // === Initialization ===
     SnippetsLoaded(Result<Vec<Snippet>, String>),
     SnippetAdded(Result<Snippet, String>),
+    /// A pasted or auto-captured snippet was added (assigned to the capture palette).
+    SnippetCaptured(Result<Snippet, String>),
 
     // === Unified Snippet Actions ===
     CopySnippet(i64),
     CopyHex(i64),
     CopyRgb(i64),
     CopyHsl(i64),
+    CopyHsv(i64),
     CopyOklch(i64),
+    CopyLab(i64),
+    CopyLch(i64),
+    CopyDisplayP3(i64),
+    CopyCmyk(i64),
+    /// Copy a color as code for a UI toolkit.
+    CopySwiftUi(i64),
+    CopyFlutter(i64),
+    CopyCompose(i64),
+    CopyIced(i64),
+    /// Copy a color with a configured copy template (snippet id, template index).
+    CopyColorTemplate(i64, usize),
+    /// Add a color found in a code or text preview as a color snippet.
+    AddInlineColor(ColorData),
+    /// Copy a text snippet as rich text (HTML), with its plain text.
+    CopyRichText(i64),
+    /// Copy a code, JSON or text snippet as Markdown.
+    CopyMarkdown(i64),
+    /// Copy one color of a color set (set id, color index).
+    CopySetColor(i64, usize),
+    /// Replace a color set with one color snippet per color.
+    SplitColorSet(i64),
+    ColorSetSplit(Result<(i64, Vec<Snippet>), String>),
     CopyFinished(Result<String, String>),
+    CopyRecorded(Result<(), String>),
     DeleteSnippet(i64),
     SnippetDeleted(Result<i64, String>),
+    /// Choose files to copy into the data folder as file snippets.
+    PickFilesToAttach,
+    /// Copy files (picked or dropped on a window) in as file snippets.
+    AttachFiles(Vec<std::path::PathBuf>),
+    /// A file was dropped on a window: pasted into the open code editor, or
+    /// attached otherwise.
+    FileDropped(std::path::PathBuf),
+    /// Text of a file dropped on the code editor, with the language of its
+    /// extension.
+    DroppedCodeRead(Result<(String, Option<String>), String>),
+
+    // === Favorites & Card Menu ===
+    /// Open (Some) or close (None) the context menu of a snippet card.
+    ToggleCardMenu(Option<i64>),
+    /// Run an action picked from the card context menu, closing the menu.
+    CardMenuSelected(Box<Message>),
+    /// The cursor entered or left a snippet card (its preview is shown on hover).
+    SnippetHovered(i64),
+    SnippetUnhovered(i64),
+    SetFavorite(i64, bool),
+    FavoriteUpdated(Result<Snippet, String>),
+
+    // === Snippet Icons ===
+    OpenIconPicker(i64),
+    CloseIconPicker,
+    IconEmojiChanged(String),
+    SubmitIconEmoji,
+    /// Set (Some) or remove (None) the custom icon of a snippet.
+    SetSnippetIcon(i64, Option<String>),
+    SnippetIconSet(Result<Snippet, String>),
+
+    // === Text Expander ===
+    OpenAbbreviationEditor(i64),
+    CloseAbbreviationEditor,
+    AbbreviationChanged(String),
+    SubmitAbbreviation,
+    /// Set (Some) or remove (None) the abbreviation of a snippet.
+    SetSnippetAbbreviation(i64, Option<String>),
+    SnippetAbbreviationSet(Result<Snippet, String>),
+    /// A key typed in any application while text expansion is on.
+    ExpanderKey(TypedKey),
+    /// Typed keys can't be read, so nothing is expanded.
+    ExpanderFailed(String),
+    /// A number key (index from 0) pressed with modifiers in any application,
+    /// while a palette board with a global hotkey is open.
+    GlobalNumberKey(Modifiers, usize),
+    AbbreviationExpanded(Result<(), String>),
+
+    // === Multi-select ===
+    ToggleSelectionMode,
+    ToggleSnippetChecked(i64),
+    SelectAllVisible,
+    ClearSelection,
+    BatchDelete,
+    BatchAddToPalette(i64),
+    BatchAddedToPalette(Result<(i64, Vec<i64>), String>),
+    BatchExport,
+    BatchLanguageInput(String),
+    BatchSetLanguage,
+    BatchLanguageSet(Result<Vec<Snippet>, String>),
+    /// Combine the checked colors into one color set snippet.
+    BatchCombineColors,
+    /// Copy the checked snippets as one text, in the order they were checked.
+    BatchCopyCombined,
+    /// Sync the checked code snippets into a project's `.code-snippets` file.
+    BatchSyncToProject,
+    ProjectSynced(Result<String, String>),
+    /// Project snippet files were rewritten after synced snippets changed.
+    ProjectFilesSynced(Result<usize, String>),
+
+    // === Trash ===
+    ShowTrash,
+    TrashLoaded(Result<Vec<Snippet>, String>),
+    RestoreSnippet(i64),
+    SnippetRestored(Result<Snippet, String>),
+    OpenBulkDelete,
+    CancelBulkDelete,
+    ConfirmBulkDelete,
+    SnippetsDeleted(Result<Vec<i64>, String>),
+    DeleteSnippetForever(i64),
+    SnippetDeletedForever(Result<i64, String>),
+    EmptyTrash,
+    TrashEmptied(Result<usize, String>),
     SelectSnippet(Option<i64>),
+    /// Compare the clipboard content to a stored snippet.
+    VerifyAgainstClipboard(i64),
+    /// Clipboard content read for verification (snippet_id, clipboard text).
+    ClipboardVerified(i64, Option<String>),
+    /// Inline color value edited on a color card (snippet_id, draft value).
+    ColorValueEdited(i64, String),
+    /// Commit the inline color value draft for a color card.
+    CommitColorValue(i64),
+
+    // === Inbox Triage ===
+    /// Show the inbox of auto-captured snippets.
+    OpenInbox,
+    /// Hide the inbox.
+    CloseInbox,
+    /// Move the inbox cursor down (J).
+    InboxNext,
+    /// Move the inbox cursor up (K).
+    InboxPrevious,
+    /// Keep the snippet under the inbox cursor (Enter).
+    KeepInboxCurrent,
+    /// Discard the snippet under the inbox cursor (X / Backspace).
+    DiscardInboxCurrent,
+    /// Move a snippet from the inbox into the library.
+    KeepInboxSnippet(i64),
+    InboxSnippetKept(Result<i64, String>),
 
     // === External Editor ===
     /// Open snippet in external editor (snippet_id, is_code: true=code, false=text)
     OpenInExternalEditor(i64, bool),
     /// Editor closed, content returned (snippet_id, new_content, is_code)
     ExternalEditorClosed(Result<(i64, String, bool), String>),
+    /// Save the reviewed external edit.
+    SaveExternalEdit,
+    DiscardExternalEdit,
 
     // === Clipboard Listening ===
     ToggleClipboard(bool),
     ClipboardTick,
-    ClipboardContentReceived(Option<String>),
+    /// Open the dialog starting a capture session.
+    OpenCaptureSessionDialog,
+    CloseCaptureSessionDialog,
+    CaptureSessionNameChanged(String),
+    /// Session length picked in the dialog, in minutes.
+    CaptureSessionLengthChanged(u64),
+    StartCaptureSession,
+    /// The session's palette was found or created; the session starts.
+    CaptureSessionPaletteReady(Result<Palette, String>),
+    /// Checked every second while a session runs.
+    CaptureSessionTick,
+    StopCaptureSession,
+    ClipboardContentReceived(Option<ClipboardContent>),
 
     // === Filtering ===
     FilterChanged(String),
     FilterKindChanged(Option<SnippetKind>),
+    /// Narrow code snippets to a single language (None = all languages).
+    FilterLanguageChanged(Option<String>),
+    /// Show only snippets captured from one application.
+    FilterSourceAppChanged(Option<String>),
+
+    // === List Layout ===
+    /// Switch between card and table layouts.
+    ToggleListLayout,
+    /// Show or hide the second snippet pane.
+    ToggleSplitPane,
+    SplitPaneFilterChanged(String),
+    SplitPaneKindChanged(Option<SnippetKind>),
+    SplitPanePaletteChanged(Option<i64>),
+    SortOrderChanged(SnippetSort),
+    /// Sort the table by a column (toggles direction if already sorted).
+    SortTable(TableColumn),
+    TableColumnResizeStarted(TableColumn),
+    /// Cursor moved over the table while resizing (x position).
+    TableColumnResizeMoved(f32),
+    TableColumnResizeEnded,
 
     // === Keyboard Shortcuts ===
     PasteFromClipboard,
-    PasteContentReceived(Option<String>),
+    PasteContentReceived(Option<ClipboardContent>),
     EscapePressed,
     DeleteSelectedSnippet,
     CopySelectedSnippet,
+    /// Move keyboard focus to the next or previous field (Tab / Shift+Tab).
+    FocusNext,
+    FocusPrevious,
 
     // === Color Picker ===
     OpenColorPicker(Option<i64>), // None = new color, Some(id) = edit existing
@@ -65,30 +276,90 @@ pub enum Message {
     PickerSaturationChanged(f32),
     PickerLightnessChanged(f32),
     PickerSLChanged(f32, f32),
+    PickerSVChanged(f32, f32),
+    PickerHsvSaturationChanged(f32),
+    PickerHsvValueChanged(f32),
     PickerAlphaChanged(f32),
     PickerLabelChanged(String),
+    PickerDescriptionChanged(iced::widget::text_editor::Action),
+    /// Hex or CSS color text typed into the picker.
+    PickerColorInputChanged(String),
+    /// Reuse one of the recently confirmed colors.
+    PickerRecentColorSelected(usize),
     // OKLCH mode
     PickerOklchLChanged(f32),
     PickerOklchCChanged(f32),
     PickerOklchHChanged(f32),
     PickerCLChanged(f32, f32),
+    /// Set an RGB channel (0 = red, 1 = green, 2 = blue).
+    PickerRgbChanged(usize, u8),
+    /// Text typed into an RGB channel's number field.
+    PickerRgbInputChanged(usize, String),
+    /// Step OKLCH lightness and chroma, keeping hue (delta_l, delta_c).
+    PickerAdjustOklch(f32, f32),
+    /// Make the picker color warmer or cooler (or lighter or darker).
+    PickerAdjustColor(ColorAdjustment),
+    /// A slider or canvas drag finished (closes one undo step).
+    PickerAdjustmentEnded,
+    /// Step back to the color before the last adjustment.
+    PickerUndo,
+    /// Show a step of the picker tour, or close it (None).
+    PickerTourStep(Option<usize>),
+    /// Copy the picker's color with a configured copy template.
+    CopyPickerTemplate(usize),
     ConfirmColorPicker,
     SaveColorAsNew,
-    SnippetUpdated(Result<Snippet, String>),
+    SnippetUpdated(Result<Snippet, SaveError>),
+    // Save conflict resolution
+    KeepStoredVersion,
+    OverwriteStoredVersion,
+    /// Reopen the unsaved edit in its editor, based on the stored version.
+    MergeConflictInEditor,
+
+    // === Eyedropper ===
+    /// Capture the screen and open the overlay sampling it.
+    StartEyedropper,
+    ScreenCaptured(Result<Arc<Screenshot>, String>),
+    /// A pixel was picked in the overlay.
+    EyedropperPicked(ColorData),
+    CancelEyedropper,
 
     // === Code Editor ===
     OpenCodeEditor(Option<i64>), // None = new, Some(id) = edit existing
     CloseCodeEditor,
     CodeEditorContentChanged(iced::widget::text_editor::Action),
+    CodeEditorScrolled(iced::widget::scrollable::Viewport),
     CodeEditorLanguageChanged(String),
     CodeEditorLabelChanged(String),
+    CodeEditorDescriptionChanged(iced::widget::text_editor::Action),
     ConfirmCodeEditor,
+    /// Clean up whitespace of the code in the editor.
+    CodeEditorTransform(WhitespaceTransform),
+    /// Clean up whitespace of a stored code snippet.
+    TransformSnippet(i64, WhitespaceTransform),
+    /// Minify or pretty-print a stored JSON, CSS, HTML or SQL snippet.
+    ReformatSnippet(i64, JsonFormat),
+    /// Put back the content a snippet had before its last reformat.
+    UndoReformat,
+
+    // === JSON ===
+    /// Open the code editor for a new JSON snippet.
+    OpenJsonEditor,
+    /// Reformat the document in the code editor.
+    CodeEditorFormatJson(JsonFormat),
+    /// Reformat a stored JSON snippet.
+    FormatJson(i64, JsonFormat),
+    OpenJsonTree(i64),
+    CloseJsonTree,
+    /// Collapse or expand the tree node at a path.
+    ToggleJsonNode(String),
 
     // === Text Editor ===
     OpenTextEditor(Option<i64>), // None = new, Some(id) = edit existing
     CloseTextEditor,
     TextEditorContentChanged(iced::widget::text_editor::Action),
     TextEditorLabelChanged(String),
+    TextEditorDescriptionChanged(iced::widget::text_editor::Action),
     ConfirmTextEditor,
 
     // === Settings ===
@@ -96,15 +367,197 @@ pub enum Message {
     CloseSettings,
     SettingsEditorPresetChanged(EditorPreset),
     SettingsCustomCommandChanged(String),
+    SettingsReviewExternalEditsChanged(bool),
+    SettingsWordWrapChanged(bool),
+    SettingsCodeWordWrapChanged(bool),
+    SettingsTabWidthChanged(u8),
+    SettingsInsertSpacesChanged(bool),
     SettingsDefaultPickerModeChanged(PickerMode),
+    SettingsCardActionsChanged(CardActions),
+    SettingsSecretPolicyChanged(SecretPolicy),
+    SettingsCapturePaletteChanged(Option<i64>),
+    SettingsTrashRetentionChanged(TrashRetention),
+    SettingsColorDedupeChanged(ColorDedupe),
+    SettingsCombineSeparatorChanged(CombineSeparator),
+    SettingsCopyFormatChanged(CopyFormat),
+    SettingsMarkdownTextChanged(MarkdownText),
+    SettingsCodeThemeChanged(CodeTheme),
+    SettingsShowFavoritesBarChanged(bool),
+    SettingsShowSourceBadgeChanged(bool),
+    SettingsCrashReportsChanged(bool),
+    SettingsCheckForUpdatesChanged(bool),
+    SettingsTextExpansionChanged(bool),
+    SettingsWindowAppearanceChanged(WindowAppearance),
+    SettingsCopyTemplateAdded,
+    SettingsCopyTemplateNameChanged(usize, String),
+    SettingsCopyTemplateChanged(usize, String),
+    SettingsCopyTemplateRemoved(usize),
+    SettingsRunCommandAdded,
+    SettingsRunCommandLanguageChanged(usize, String),
+    SettingsRunCommandChanged(usize, String),
+    SettingsRunCommandRemoved(usize),
     ConfirmSettings,
     ConfigSaved(Result<(), String>),
+    /// Run `PRAGMA integrity_check` on the database.
+    CheckDatabaseIntegrity,
+    /// Rebuild the database file to reclaim unused space.
+    VacuumDatabase,
+    /// Report row counts and the database size.
+    ShowDatabaseStats,
+    DatabaseMaintenanceFinished(Result<String, String>),
 
     // === Export/Import ===
     ExportSnippetsJson,
     ExportFinished(Result<String, String>),
+    /// Export filtered code snippets as files (true = per-palette subfolders).
+    ExportCodeFiles(bool),
+    /// Code files were written; the snippets are now linked to them.
+    CodeFilesExported(Result<String, String>),
     ImportSnippetsJson,
-    ImportFinished(Result<String, String>),
+    /// Import files chosen in a file picker as code snippets linked to them.
+    ImportCodeFiles,
+    /// Show the file a snippet was imported from or exported to.
+    RevealSnippetFile(i64),
+    /// Snippets whose linked file was edited or removed since it was linked.
+    LinkedFilesChecked(std::collections::HashSet<i64>),
+    /// Export a palette and its snippets as a `.shardpalette` file.
+    ExportPalette(i64),
+    /// Export the code snippets of a palette (None = the checked snippets) as a
+    /// `.code-snippets` file for editor completions.
+    ExportEditorSnippets(Option<i64>),
+    /// Export a palette's colors as an Xcode asset catalog and Android `colors.xml`.
+    ExportPaletteAssets(i64),
+    /// Export the colors of a palette (None = the whole library) for another tool.
+    ExportColors(Option<i64>, ExportFormat),
+    /// Open the dialog exporting a palette's colors as code.
+    OpenCodeExport(i64),
+    CloseCodeExport,
+    CodeExportFormatChanged(CodeFormat),
+    CodeExportValueChanged(ColorValue),
+    CodeExportShadesToggled(bool),
+    CodeExportMapToggled(bool),
+    CopyCodeExport,
+    SaveCodeExport,
+    /// Import a `.shardpalette` file chosen in a file picker.
+    ImportPaletteFile,
+    /// Import a GIMP `.gpl` palette chosen in a file picker.
+    ImportGplPalette,
+    ImportTokensPalette,
+    /// Import links from exported browser bookmarks (true = palettes per folder).
+    ImportBookmarks(bool),
+    ImportFinished(Result<String, (String, Option<std::path::PathBuf>)>),
+    /// Restore the backup taken before a failed migration or import.
+    RestoreBackup,
+    /// Open the crash report left by the last session.
+    OpenCrashReport,
+    DismissCrashReport,
+    BackupRestored(Result<std::path::PathBuf, String>),
+    /// Choose a folder to move the database into.
+    PickDatabaseFolder,
+    DatabaseFolderPicked(Option<std::path::PathBuf>),
+    /// Move the database back to the platform data folder.
+    ResetDatabaseFolder,
+    /// Result of moving the database: the new folder and whether the file was
+    /// moved (false when a database already in that folder was opened instead).
+    DatabaseRelocated(Result<(Option<std::path::PathBuf>, bool), String>),
+
+    // === Encryption ===
+    UnlockPassphraseChanged(String),
+    SubmitUnlock,
+    DatabaseUnlocked(Result<(), String>),
+    OpenEncryptDatabase,
+    EncryptPassphraseChanged(String),
+    EncryptConfirmChanged(String),
+    SubmitEncrypt,
+    CloseEncryptDatabase,
+    DatabaseEncrypted(Result<BackupsEncrypted, String>),
+
+    // === Contrast Checker ===
+    /// Check the contrast of a color snippet.
+    OpenContrastChecker(i64),
+    CloseContrastChecker,
+    ContrastBackgroundSelected(i64),
+    ContrastCustomBackgroundChanged(String),
+    /// Swap the text and background snippets.
+    SwapContrastColors,
+
+    // === Color Variants ===
+    /// Save a lighter, darker, warmer or cooler copy of a color snippet.
+    DeriveColor(i64, ColorAdjustment),
+
+    // === Similar Colors ===
+    /// Rank the other color snippets by how close they look to one.
+    OpenSimilarColors(i64),
+    CloseSimilarColors,
+    /// Select a listed color and close the panel.
+    ShowSimilarColor(i64),
+
+    // === Run Snippets ===
+    /// Run a code snippet with the command configured for its language.
+    RunSnippet(i64),
+    SnippetRunFinished(i64, Result<RunOutput, String>),
+    /// Copy what the last run printed.
+    CopyRunOutput,
+    CloseRunOutput,
+
+    // === Template Placeholders ===
+    /// Change the value of a placeholder in the fill-in form.
+    TemplateValueChanged(usize, String),
+    /// Copy the snippet with the placeholders filled in.
+    CopyTemplateFill,
+    CloseTemplateFill,
+
+    // === Color Scale ===
+    /// Generate a tint and shade scale from a color snippet.
+    OpenScaleDialog(i64),
+    CloseScaleDialog,
+    ScaleStepsChanged(usize),
+    /// Save the scale as one color snippet per step.
+    SaveScaleColors,
+    /// Save the scale as a single color set.
+    SaveScaleSet,
+    ScaleSaved(Result<Vec<Snippet>, String>),
+
+    // === Share Image ===
+    OpenShareImage(i64),
+    CloseShareImage,
+    ShareImageThemeChanged(iced::highlighter::Theme),
+    ShareImageScaleChanged(u32),
+    ShareImagePaddingChanged(u32),
+    ShareImageChromeToggled(bool),
+    SaveShareImage,
+    ShareImageSaved(Result<String, String>),
+
+    // === Gradient Editor ===
+    OpenGradientEditor(Option<i64>), // None = new, Some(id) = edit existing
+    CloseGradientEditor,
+    GradientStopSelected(usize),
+    /// Add a stop at a position (0-1) and select it.
+    GradientStopAdded(f32),
+    /// Drag the selected stop to a position (0-1).
+    GradientStopMoved(f32),
+    GradientStopRemoved,
+    GradientAngleChanged(f32),
+    /// Open the color picker on the selected stop's color.
+    EditGradientStopColor,
+    CopyGradientCss,
+    GradientLabelChanged(String),
+    /// Add the gradient as a snippet, or save the one being edited.
+    SaveGradient,
+
+    // === Region Scan ===
+    /// Scan the screen region copied to the clipboard for colors.
+    ScanRegion,
+    RegionScanned(Result<Vec<(ColorData, usize)>, String>),
+    ToggleScannedColor(usize),
+    SelectAllScannedColors(bool),
+    AddScannedColors,
+    CloseRegionScan,
+    /// Pick an image file and extract its dominant colors.
+    ExtractImagePalette,
+    /// The file's name and its dominant colors with their pixel counts.
+    ImagePaletteExtracted(Result<(String, Vec<(ColorData, usize)>), String>),
+    ImagePaletteNameChanged(String),
 
     // === Add Menu Dropdown ===
     ToggleAddMenu,
@@ -114,22 +567,70 @@ pub enum Message {
     StartRecordingShortcut(ShortcutAction),
     StopRecordingShortcut,
     ShortcutRecorded(ShortcutAction, Shortcut),
+    /// A key press while recording that can't become a shortcut, and why.
+    ShortcutRejected(String),
     ResetShortcutToDefault(ShortcutAction),
 
     // === Palettes ===
     PalettesLoaded(Result<Vec<Palette>, String>),
+    SnippetPalettesLoaded(Result<std::collections::HashMap<i64, Vec<i64>>, String>),
     FilterPaletteChanged(Option<i64>),
     OpenPaletteManager,
     ClosePaletteManager,
     NewPaletteNameChanged(String),
+    NewPaletteQueryChanged(String),
     CreatePalette(String),
     PaletteCreated(Result<Palette, String>),
     RenamePalette(i64, String),
     PaletteRenamed(Result<Palette, String>),
+    SetPaletteParent(i64, Option<i64>), // (palette_id, new parent)
+    PaletteParentChanged(Result<(i64, Option<i64>), String>),
+    SetPaletteDarkVariant(i64, Option<i64>), // (palette_id, dark variant palette)
+    PaletteDarkVariantChanged(Result<(i64, Option<i64>), String>),
+    TogglePaletteExpanded(i64),
     DeletePalette(i64),
     PaletteDeleted(Result<i64, String>),
     AddSnippetToPalette(i64, i64),      // (snippet_id, palette_id)
     RemoveSnippetFromPalette(i64, i64), // (snippet_id, palette_id)
     SnippetPaletteUpdated(Result<(), String>),
     TogglePaletteDropdown(Option<i64>), // snippet_id to show dropdown for
+    /// Open the palette dropdown for the selected snippet (keyboard shortcut).
+    OpenPaletteAssignment,
+    PaletteDropdownQueryChanged(String),
+    PaletteDropdownNext,
+    PaletteDropdownPrevious,
+    /// Toggle membership of the highlighted palette in the dropdown.
+    ToggleHighlightedPalette,
+    /// Turn capturing into the configured palette on or off.
+    ToggleCapturePalette,
+
+    // === Sections ===
+    SectionsLoaded(Result<Vec<Section>, String>),
+    /// Open the naming dialog for a section above the snippet, or at the top.
+    NewSection(Option<i64>),
+    EditSection(i64),
+    SectionNameChanged(String),
+    SaveSection,
+    CloseSectionDialog,
+    SectionSaved(Result<(), String>),
+    DeleteSection(i64),
+    SectionDeleted(Result<i64, String>),
+
+    // === Scratchpad ===
+    ScratchpadLoaded(Result<String, String>),
+    OpenScratchpad,
+    CloseScratchpad,
+    ScratchpadEdited(iced::widget::text_editor::Action),
+    ScratchpadSaved(Result<(), String>),
+    /// The scratchpad was saved after the main window closed.
+    ScratchpadSavedOnExit(Result<(), String>),
+    /// Turn the scratchpad text into a text snippet and clear the scratchpad.
+    PromoteScratchpad,
+
+    // === Updates ===
+    /// The release check finished; `Some` when a newer version exists.
+    UpdateChecked(Result<Option<crate::releases::Release>, String>),
+    OpenReleaseNotes,
+    CloseReleaseNotes,
+    OpenReleasePage,
 }
@@ -0,0 +1,103 @@
+//! Opt-in check for new Shard releases on GitHub.
+//!
+//! The check asks the GitHub API for the latest release with the system
+//! `curl`, so no request is made unless the user turned it on and nothing
+//! about the library is sent.
+
+use serde::Deserialize;
+
+/// GitHub API endpoint for the latest published (non-prerelease) release.
+const LATEST_RELEASE_URL: &str = "https://api.github.com/repos/hewel/Shard/releases/latest";
+
+/// Seconds before an unanswered check gives up.
+const TIMEOUT_SECS: &str = "10";
+
+/// A published release, as returned by the GitHub API.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Release {
+    /// Tag name, e.g. `v0.2.0`.
+    #[serde(rename = "tag_name")]
+    pub tag: String,
+    /// Release notes in Markdown.
+    #[serde(rename = "body", default)]
+    pub notes: Option<String>,
+    /// Release page with the downloads.
+    #[serde(rename = "html_url")]
+    pub url: String,
+}
+
+impl Release {
+    /// Get the version without the tag's `v` prefix.
+    pub fn version(&self) -> &str {
+        self.tag.trim_start_matches('v')
+    }
+}
+
+/// Get the running version of Shard.
+pub fn current_version() -> &'static str {
+    env!("CARGO_PKG_VERSION")
+}
+
+/// Fetch the latest release, returning it only if it is newer than this build.
+pub fn check_for_update() -> Result<Option<Release>, String> {
+    let output = std::process::Command::new("curl")
+        .args([
+            "--silent",
+            "--fail",
+            "--location",
+            "--max-time",
+            TIMEOUT_SECS,
+        ])
+        .args(["--header", "Accept: application/vnd.github+json"])
+        .args(["--user-agent", concat!("Shard/", env!("CARGO_PKG_VERSION"))])
+        .arg(LATEST_RELEASE_URL)
+        .output()
+        .map_err(|e| format!("Failed to run curl: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("Update check failed ({})", output.status));
+    }
+
+    let release: Release = serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("Unexpected release data: {}", e))?;
+    Ok(is_newer(release.version(), current_version()).then_some(release))
+}
+
+/// Check if version `candidate` is newer than `current` (both `major.minor.patch`,
+/// any `-suffix` ignored; missing parts count as 0).
+pub fn is_newer(candidate: &str, current: &str) -> bool {
+    fn parts(version: &str) -> [u64; 3] {
+        let mut parts = [0; 3];
+        let core = version.split(['-', '+']).next().unwrap_or_default();
+        for (part, value) in parts.iter_mut().zip(core.split('.')) {
+            *part = value.trim().parse().unwrap_or(0);
+        }
+        parts
+    }
+    parts(candidate) > parts(current)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_newer() {
+        assert!(is_newer("0.2.0", "0.1.9"));
+        assert!(is_newer("1.0", "0.9.9"));
+        assert!(is_newer("0.1.10", "0.1.9"));
+        assert!(!is_newer("0.1.0", "0.1.0"));
+        assert!(!is_newer("0.1.0-beta", "0.1.0"));
+        assert!(!is_newer("0.0.9", "0.1.0"));
+    }
+
+    #[test]
+    fn test_parse_release() {
+        let release: Release = serde_json::from_str(
+            r#"{"tag_name": "v0.3.1", "body": null, "html_url": "https://example.com/r", "draft": false}"#,
+        )
+        .unwrap();
+        assert_eq!(release.version(), "0.3.1");
+        assert_eq!(release.notes, None);
+        assert_eq!(release.url, "https://example.com/r");
+    }
+}
@@ -0,0 +1,300 @@
+//! Rendering code snippets to shareable PNG images.
+//!
+//! Draws a window-style card with syntax-highlighted code on a colored
+//! backdrop, rasterizing text with the bundled Lilex font.
+
+use ab_glyph::{Font, FontRef, ScaleFont};
+use iced::advanced::text::Highlighter as _;
+use iced::highlighter::{self, Highlighter};
+use iced::Color;
+
+use crate::icons;
+use crate::snippet::language_to_extension;
+
+/// Font size of the code at 1x scale, in pixels.
+const FONT_SIZE: f32 = 14.0;
+/// Line height as a multiple of the font size.
+const LINE_HEIGHT: f32 = 1.5;
+/// Height of the window title bar at 1x scale.
+const TITLE_BAR_HEIGHT: f32 = 32.0;
+/// Corner radius of the window at 1x scale.
+const CORNER_RADIUS: f32 = 8.0;
+/// Padding between the window edge and the code at 1x scale.
+const WINDOW_PADDING: f32 = 20.0;
+/// Spaces a tab is expanded to.
+const TAB_WIDTH: usize = 4;
+
+/// Backdrop around the window.
+const BACKDROP: Color = Color::from_rgb(0.42, 0.36, 0.9);
+/// Window control dots (close, minimize, maximize).
+const CHROME_DOTS: [Color; 3] = [
+    Color::from_rgb(1.0, 0.37, 0.34),
+    Color::from_rgb(1.0, 0.74, 0.18),
+    Color::from_rgb(0.15, 0.79, 0.25),
+];
+
+/// Options for a share image.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShareImageOptions {
+    pub theme: highlighter::Theme,
+    /// Pixel density multiplier (1x, 2x, ...).
+    pub scale: u32,
+    /// Backdrop padding around the window at 1x scale.
+    pub padding: u32,
+    /// Draw the title bar with window controls.
+    pub window_chrome: bool,
+}
+
+impl Default for ShareImageOptions {
+    fn default() -> Self {
+        Self {
+            theme: highlighter::Theme::Base16Mocha,
+            scale: 2,
+            padding: 48,
+            window_chrome: true,
+        }
+    }
+}
+
+/// Get the window background and default text color for a theme.
+fn theme_colors(theme: highlighter::Theme) -> (Color, Color) {
+    match theme {
+        highlighter::Theme::SolarizedDark => (
+            Color::from_rgb8(0x00, 0x2b, 0x36),
+            Color::from_rgb8(0x83, 0x94, 0x96),
+        ),
+        highlighter::Theme::Base16Mocha => (
+            Color::from_rgb8(0x3b, 0x32, 0x28),
+            Color::from_rgb8(0xd0, 0xc8, 0xc6),
+        ),
+        highlighter::Theme::Base16Ocean => (
+            Color::from_rgb8(0x2b, 0x30, 0x3b),
+            Color::from_rgb8(0xc0, 0xc5, 0xce),
+        ),
+        highlighter::Theme::Base16Eighties => (
+            Color::from_rgb8(0x2d, 0x2d, 0x2d),
+            Color::from_rgb8(0xd3, 0xd0, 0xc8),
+        ),
+        highlighter::Theme::InspiredGitHub => (
+            Color::from_rgb8(0xff, 0xff, 0xff),
+            Color::from_rgb8(0x32, 0x32, 0x32),
+        ),
+    }
+}
+
+/// An RGBA pixel buffer with source-over blending.
+struct Canvas {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    fn new(width: u32, height: u32, background: Color) -> Self {
+        let [r, g, b, a] = background.into_rgba8();
+        Self {
+            width,
+            height,
+            pixels: [r, g, b, a].repeat((width * height) as usize),
+        }
+    }
+
+    /// Blend a color into a pixel with the given coverage (0.0 - 1.0).
+    fn blend(&mut self, x: i64, y: i64, color: Color, coverage: f32) {
+        if x < 0 || y < 0 || x >= self.width as i64 || y >= self.height as i64 {
+            return;
+        }
+        let alpha = (color.a * coverage).clamp(0.0, 1.0);
+        if alpha <= 0.0 {
+            return;
+        }
+        let index = ((y as u32 * self.width + x as u32) * 4) as usize;
+        let src = [color.r, color.g, color.b];
+        for (channel, value) in src.iter().enumerate() {
+            let dst = self.pixels[index + channel] as f32 / 255.0;
+            self.pixels[index + channel] = ((value * alpha + dst * (1.0 - alpha)) * 255.0) as u8;
+        }
+        let dst_alpha = self.pixels[index + 3] as f32 / 255.0;
+        self.pixels[index + 3] = ((alpha + dst_alpha * (1.0 - alpha)) * 255.0) as u8;
+    }
+
+    /// Fill a rectangle with rounded corners (anti-aliased).
+    fn fill_rounded_rect(&mut self, x: f32, y: f32, w: f32, h: f32, radius: f32, color: Color) {
+        for py in y.floor() as i64..(y + h).ceil() as i64 {
+            for px in x.floor() as i64..(x + w).ceil() as i64 {
+                // Distance from the pixel center to the inner (radius-shrunk) rectangle
+                let cx = px as f32 + 0.5;
+                let cy = py as f32 + 0.5;
+                let dx = (x + radius - cx).max(cx - (x + w - radius)).max(0.0);
+                let dy = (y + radius - cy).max(cy - (y + h - radius)).max(0.0);
+                let outside = (dx * dx + dy * dy).sqrt() - radius;
+                let edge_x = (cx - x).min(x + w - cx);
+                let edge_y = (cy - y).min(y + h - cy);
+                let coverage = (0.5 - outside).clamp(0.0, 1.0)
+                    * (edge_x + 0.5).clamp(0.0, 1.0)
+                    * (edge_y + 0.5).clamp(0.0, 1.0);
+                self.blend(px, py, color, coverage);
+            }
+        }
+    }
+
+    /// Fill a circle (anti-aliased).
+    fn fill_circle(&mut self, cx: f32, cy: f32, radius: f32, color: Color) {
+        self.fill_rounded_rect(
+            cx - radius,
+            cy - radius,
+            radius * 2.0,
+            radius * 2.0,
+            radius,
+            color,
+        );
+    }
+
+    /// Encode the buffer as a PNG.
+    fn encode_png(&self) -> Result<Vec<u8>, String> {
+        let mut bytes = Vec::new();
+        let mut encoder = png::Encoder::new(&mut bytes, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder
+            .write_header()
+            .map_err(|e| format!("PNG error: {}", e))?;
+        writer
+            .write_image_data(&self.pixels)
+            .map_err(|e| format!("PNG error: {}", e))?;
+        writer.finish().map_err(|e| format!("PNG error: {}", e))?;
+        Ok(bytes)
+    }
+}
+
+/// Render code as a syntax-highlighted PNG image.
+pub fn render_code_image(
+    code: &str,
+    language: &str,
+    options: &ShareImageOptions,
+) -> Result<Vec<u8>, String> {
+    let font = FontRef::try_from_slice(icons::TEXT_FONT_BYTES)
+        .map_err(|e| format!("Font error: {}", e))?;
+    let scale = options.scale.max(1) as f32;
+    let font = font.as_scaled(FONT_SIZE * scale);
+    let advance = font.h_advance(font.glyph_id('M'));
+    let line_height = (FONT_SIZE * LINE_HEIGHT * scale).round();
+
+    let lines: Vec<String> = code
+        .trim_end()
+        .lines()
+        .map(|line| line.replace('\t', &" ".repeat(TAB_WIDTH)))
+        .collect();
+    let longest = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+
+    // Layout
+    let backdrop_padding = options.padding as f32 * scale;
+    let window_padding = WINDOW_PADDING * scale;
+    let title_bar = if options.window_chrome {
+        TITLE_BAR_HEIGHT * scale
+    } else {
+        0.0
+    };
+    let window_width = (longest.max(20) as f32 * advance + window_padding * 2.0).ceil();
+    let window_height =
+        (title_bar + lines.len().max(1) as f32 * line_height + window_padding * 2.0).ceil();
+    let width = (window_width + backdrop_padding * 2.0) as u32;
+    let height = (window_height + backdrop_padding * 2.0) as u32;
+
+    let (window_background, text_color) = theme_colors(options.theme);
+    let mut canvas = Canvas::new(width, height, BACKDROP);
+    canvas.fill_rounded_rect(
+        backdrop_padding,
+        backdrop_padding,
+        window_width,
+        window_height,
+        CORNER_RADIUS * scale,
+        window_background,
+    );
+
+    if options.window_chrome {
+        let dot_radius = 6.0 * scale;
+        for (index, color) in CHROME_DOTS.iter().enumerate() {
+            canvas.fill_circle(
+                backdrop_padding + window_padding + dot_radius + index as f32 * 20.0 * scale,
+                backdrop_padding + title_bar / 2.0 + 4.0 * scale,
+                dot_radius,
+                *color,
+            );
+        }
+    }
+
+    // Code
+    let mut highlighter = Highlighter::new(&highlighter::Settings {
+        theme: options.theme,
+        token: language_to_extension(language).to_string(),
+    });
+    let origin_x = backdrop_padding + window_padding;
+    let mut baseline = backdrop_padding + title_bar + window_padding + font.ascent();
+
+    for line in &lines {
+        let highlights: Vec<_> = highlighter
+            .highlight_line(line)
+            .map(|(range, highlight)| (range, highlight.color()))
+            .collect();
+
+        for (column, (byte_index, c)) in line.char_indices().enumerate() {
+            let color = highlights
+                .iter()
+                .rev()
+                .find(|(range, _)| range.contains(&byte_index))
+                .and_then(|(_, color)| *color)
+                .unwrap_or(text_color);
+
+            let glyph = font.glyph_id(c).with_scale_and_position(
+                font.scale(),
+                ab_glyph::point(origin_x + column as f32 * advance, baseline),
+            );
+            if let Some(outlined) = font.outline_glyph(glyph) {
+                let bounds = outlined.px_bounds();
+                outlined.draw(|gx, gy, coverage| {
+                    canvas.blend(
+                        bounds.min.x as i64 + gx as i64,
+                        bounds.min.y as i64 + gy as i64,
+                        color,
+                        coverage,
+                    );
+                });
+            }
+        }
+        baseline += line_height;
+    }
+
+    canvas.encode_png()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_code_image_is_png() {
+        let options = ShareImageOptions {
+            scale: 1,
+            ..ShareImageOptions::default()
+        };
+        let png =
+            render_code_image("fn main() {\n\tprintln!(\"hi\");\n}", "rust", &options).unwrap();
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+
+        // Wider padding yields a bigger image
+        let padded = render_code_image(
+            "fn main() {}",
+            "rust",
+            &ShareImageOptions {
+                padding: 96,
+                ..options
+            },
+        )
+        .unwrap();
+        let narrow = render_code_image("fn main() {}", "rust", &options).unwrap();
+        let png_width =
+            |bytes: &[u8]| u32::from_be_bytes([bytes[16], bytes[17], bytes[18], bytes[19]]);
+        assert_eq!(png_width(&padded), png_width(&narrow) + 96);
+    }
+}
@@ -0,0 +1,260 @@
+//! Mobile color assets: Xcode `.xcassets` color sets and Android `colors.xml`.
+
+use std::collections::HashSet;
+
+use serde_json::json;
+
+use super::color::ColorData;
+
+/// A named color with an optional dark-mode variant.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AssetColor {
+    pub name: String,
+    pub light: ColorData,
+    pub dark: Option<ColorData>,
+}
+
+/// Pair the colors of a palette with those of its dark variant palette,
+/// matching them by name (ignoring case). Colors without a match have no
+/// dark variant; unmatched dark colors are left out.
+pub fn pair_variants(
+    light: Vec<(String, ColorData)>,
+    dark: &[(String, ColorData)],
+) -> Vec<AssetColor> {
+    light
+        .into_iter()
+        .map(|(name, color)| AssetColor {
+            dark: dark
+                .iter()
+                .find(|(dark_name, _)| dark_name.trim().eq_ignore_ascii_case(name.trim()))
+                .map(|(_, dark)| dark.clone()),
+            name: name.trim().to_string(),
+            light: color,
+        })
+        .collect()
+}
+
+/// Contents.json at the root of an asset catalog and of its folders.
+fn catalog_info() -> serde_json::Value {
+    json!({ "info": { "author": "xcode", "version": 1 } })
+}
+
+/// Build the files of an `.xcassets` catalog as (relative path, contents),
+/// one `.colorset` folder per color.
+pub fn xcassets_files(colors: &[AssetColor]) -> Vec<(String, String)> {
+    let mut files = vec![("Contents.json".to_string(), pretty(&catalog_info()))];
+    for (color, name) in colors.iter().zip(unique_names(colors, xcassets_name)) {
+        files.push((
+            format!("{}.colorset/Contents.json", name),
+            colorset_contents(color),
+        ));
+    }
+    files
+}
+
+/// Build the Contents.json of one color set, with a dark appearance when
+/// the color has a dark variant.
+pub fn colorset_contents(color: &AssetColor) -> String {
+    let mut entries = vec![json!({
+        "color": xcassets_color(&color.light),
+        "idiom": "universal",
+    })];
+    if let Some(dark) = &color.dark {
+        entries.push(json!({
+            "appearances": [{ "appearance": "luminosity", "value": "dark" }],
+            "color": xcassets_color(dark),
+            "idiom": "universal",
+        }));
+    }
+    let mut contents = catalog_info();
+    contents["colors"] = serde_json::Value::Array(entries);
+    pretty(&contents)
+}
+
+fn xcassets_color(color: &ColorData) -> serde_json::Value {
+    json!({
+        "color-space": "srgb",
+        "components": {
+            "alpha": format!("{:.3}", color.a),
+            "blue": format!("0x{:02X}", color.b),
+            "green": format!("0x{:02X}", color.g),
+            "red": format!("0x{:02X}", color.r),
+        }
+    })
+}
+
+fn pretty(value: &serde_json::Value) -> String {
+    let mut json = serde_json::to_string_pretty(value).unwrap_or_else(|_| "{}".to_string());
+    json.push('\n');
+    json
+}
+
+/// Build an Android `colors.xml` resource file. With `night`, only the dark
+/// variants are written, for the `values-night` folder.
+pub fn android_colors_xml(colors: &[AssetColor], night: bool) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<resources>\n");
+    for (color, name) in colors.iter().zip(unique_names(colors, android_name)) {
+        let value = if night {
+            match &color.dark {
+                Some(dark) => dark,
+                None => continue,
+            }
+        } else {
+            &color.light
+        };
+        xml.push_str(&format!(
+            "    <color name=\"{}\">{}</color>\n",
+            name,
+            android_hex(value)
+        ));
+    }
+    xml.push_str("</resources>\n");
+    xml
+}
+
+/// Format a color the way Android resources take it: `#RRGGBB`, or
+/// `#AARRGGBB` when translucent.
+fn android_hex(color: &ColorData) -> String {
+    let alpha = (color.a.clamp(0.0, 1.0) * 255.0).round() as u8;
+    if alpha == 255 {
+        format!("#{:02X}{:02X}{:02X}", color.r, color.g, color.b)
+    } else {
+        format!(
+            "#{:02X}{:02X}{:02X}{:02X}",
+            alpha, color.r, color.g, color.b
+        )
+    }
+}
+
+/// Name a color set folder: the label, with `/` (which Xcode reads as a
+/// folder) and other path characters replaced.
+fn xcassets_name(label: &str) -> String {
+    let name: String = label
+        .chars()
+        .map(|c| {
+            if matches!(c, '/' | '\\' | ':') {
+                '-'
+            } else {
+                c
+            }
+        })
+        .collect();
+    if name.trim().is_empty() {
+        "Color".to_string()
+    } else {
+        name.trim().to_string()
+    }
+}
+
+/// Name an Android resource: lowercase letters, digits and underscores,
+/// starting with a letter, e.g. "Brand Primary" → `brand_primary`.
+fn android_name(label: &str) -> String {
+    let words: Vec<String> = label
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_ascii_lowercase)
+        .collect();
+    let name = words.join("_");
+    if name.starts_with(|c: char| c.is_ascii_lowercase()) {
+        name
+    } else {
+        format!("color_{}", name).trim_end_matches('_').to_string()
+    }
+}
+
+/// Name every color with `name_of`, numbering repeated names.
+fn unique_names(colors: &[AssetColor], name_of: fn(&str) -> String) -> Vec<String> {
+    let mut taken = HashSet::new();
+    colors
+        .iter()
+        .map(|color| {
+            let base = name_of(&color.name);
+            let mut name = base.clone();
+            let mut n = 2;
+            while !taken.insert(name.to_lowercase()) {
+                name = format!("{}_{}", base, n);
+                n += 1;
+            }
+            name
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn brand() -> Vec<AssetColor> {
+        pair_variants(
+            vec![
+                (
+                    "Brand Primary".to_string(),
+                    ColorData::new(255, 128, 0, 1.0),
+                ),
+                ("overlay".to_string(), ColorData::new(0, 0, 0, 0.5)),
+            ],
+            &[(
+                "brand primary".to_string(),
+                ColorData::new(200, 100, 0, 1.0),
+            )],
+        )
+    }
+
+    #[test]
+    fn test_pair_variants() {
+        let colors = brand();
+        assert_eq!(colors[0].dark, Some(ColorData::new(200, 100, 0, 1.0)));
+        assert_eq!(colors[1].dark, None);
+    }
+
+    #[test]
+    fn test_xcassets_files() {
+        let files = xcassets_files(&brand());
+        let paths: Vec<&str> = files.iter().map(|(path, _)| path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "Contents.json",
+                "Brand Primary.colorset/Contents.json",
+                "overlay.colorset/Contents.json"
+            ]
+        );
+
+        let set: serde_json::Value = serde_json::from_str(&files[1].1).unwrap();
+        assert_eq!(set["colors"][0]["color"]["components"]["red"], "0xFF");
+        assert_eq!(set["colors"][1]["appearances"][0]["value"], "dark");
+        assert_eq!(set["colors"][1]["color"]["components"]["red"], "0xC8");
+        let overlay: serde_json::Value = serde_json::from_str(&files[2].1).unwrap();
+        assert_eq!(overlay["colors"].as_array().unwrap().len(), 1);
+        assert_eq!(
+            overlay["colors"][0]["color"]["components"]["alpha"],
+            "0.500"
+        );
+    }
+
+    #[test]
+    fn test_android_colors_xml() {
+        let colors = brand();
+        let day = android_colors_xml(&colors, false);
+        assert!(day.contains("<color name=\"brand_primary\">#FF8000</color>"));
+        assert!(day.contains("<color name=\"overlay\">#80000000</color>"));
+
+        let night = android_colors_xml(&colors, true);
+        assert!(night.contains("<color name=\"brand_primary\">#C86400</color>"));
+        assert!(!night.contains("overlay"));
+    }
+
+    #[test]
+    fn test_android_names() {
+        assert_eq!(android_name("Brand / Primary 2"), "brand_primary_2");
+        assert_eq!(android_name("500"), "color_500");
+        let colors = pair_variants(
+            vec![
+                ("Red".to_string(), ColorData::new(255, 0, 0, 1.0)),
+                ("red".to_string(), ColorData::new(250, 0, 0, 1.0)),
+            ],
+            &[],
+        );
+        assert_eq!(unique_names(&colors, android_name), vec!["red", "red_2"]);
+    }
+}
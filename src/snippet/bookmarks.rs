@@ -0,0 +1,98 @@
+//! Parsing of exported browser bookmarks (Netscape bookmark HTML).
+
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// A bookmarked link.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bookmark {
+    pub title: String,
+    pub url: String,
+    /// Innermost folder the bookmark was filed under, if any.
+    pub folder: Option<String>,
+}
+
+static FOLDER_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)<H3[^>]*>(.*?)</H3>").expect("Invalid folder regex"));
+
+static LINK_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?i)<A\s[^>]*HREF="([^"]*)"[^>]*>(.*?)</A>"#).expect("Invalid link regex")
+});
+
+static LIST_END_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)</DL>").expect("Invalid list end regex"));
+
+/// Parse the bookmarks in an exported bookmarks HTML file.
+/// Links without an http(s) URL (bookmarklets, `place:` queries) are skipped.
+pub fn parse_bookmarks_html(html: &str) -> Vec<Bookmark> {
+    let mut bookmarks = Vec::new();
+    // Folder headings open a nested list, which `</DL>` closes again
+    let mut folders: Vec<String> = Vec::new();
+
+    for line in html.lines() {
+        if let Some(cap) = FOLDER_REGEX.captures(line) {
+            folders.push(decode_entities(cap[1].trim()));
+        } else if let Some(cap) = LINK_REGEX.captures(line) {
+            let url = decode_entities(&cap[1]);
+            if !(url.starts_with("http://") || url.starts_with("https://")) {
+                continue;
+            }
+            let title = decode_entities(cap[2].trim());
+            bookmarks.push(Bookmark {
+                title: if title.is_empty() { url.clone() } else { title },
+                url,
+                folder: folders.last().cloned(),
+            });
+        }
+
+        for _ in LIST_END_REGEX.find_iter(line) {
+            folders.pop();
+        }
+    }
+
+    bookmarks
+}
+
+/// Decode the HTML entities browsers escape in bookmark exports.
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_bookmarks_html() {
+        let html = r#"<!DOCTYPE NETSCAPE-Bookmark-file-1>
+<TITLE>Bookmarks</TITLE>
+<H1>Bookmarks</H1>
+<DL><p>
+    <DT><H3 ADD_DATE="1">Dev</H3>
+    <DL><p>
+        <DT><A HREF="https://docs.rs/" ADD_DATE="1">Docs.rs</A>
+        <DT><H3>Rust</H3>
+        <DL><p>
+            <DT><A HREF="https://iced.rs/?a=1&amp;b=2">iced &amp; friends</A>
+        </DL><p>
+        <DT><A HREF="javascript:alert(1)">Bookmarklet</A>
+    </DL><p>
+    <DT><A HREF="https://example.com/"></A>
+</DL><p>"#;
+
+        let bookmarks = parse_bookmarks_html(html);
+        assert_eq!(bookmarks.len(), 3);
+        assert_eq!(bookmarks[0].title, "Docs.rs");
+        assert_eq!(bookmarks[0].folder.as_deref(), Some("Dev"));
+        assert_eq!(bookmarks[1].title, "iced & friends");
+        assert_eq!(bookmarks[1].url, "https://iced.rs/?a=1&b=2");
+        assert_eq!(bookmarks[1].folder.as_deref(), Some("Rust"));
+        // Untitled links fall back to their URL, outside any folder
+        assert_eq!(bookmarks[2].title, "https://example.com/");
+        assert_eq!(bookmarks[2].folder, None);
+    }
+}
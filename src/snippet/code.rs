@@ -1,8 +1,10 @@
-//! Code snippet data and language detection.
+//! Code snippet data and language names.
 
 use regex::Regex;
 use serde::{Deserialize, Serialize};
-use std::sync::LazyLock;
+
+use super::json::{JsonData, JsonFormat};
+use super::language::detect_language;
 
 /// Code data with content and language.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -37,139 +39,751 @@ impl CodeData {
     }
 }
 
-// Language detection patterns
-static RUST_PATTERNS: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r"(?m)^\s*(fn\s+\w+|impl\s+|struct\s+|enum\s+|trait\s+|mod\s+|use\s+|let\s+mut|pub\s+fn|#\[derive)")
-        .expect("Invalid rust regex")
-});
-
-static PYTHON_PATTERNS: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r"(?m)^\s*(def\s+\w+|class\s+\w+|import\s+|from\s+\w+\s+import|if\s+__name__|@\w+)")
-        .expect("Invalid python regex")
-});
-
-static JAVASCRIPT_PATTERNS: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r"(?m)(function\s+\w+|const\s+\w+\s*=|let\s+\w+\s*=|var\s+\w+\s*=|=>\s*\{|export\s+(default\s+)?|import\s+.*from|require\()")
-        .expect("Invalid javascript regex")
-});
-
-static TYPESCRIPT_PATTERNS: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(
-        r"(?m)(:\s*(string|number|boolean|any|void|never)|interface\s+\w+|type\s+\w+\s*=|<\w+>)",
-    )
-    .expect("Invalid typescript regex")
-});
+/// Languages offered for code snippets: those `language_to_extension`
+/// knows, under their canonical names, and plain text.
+pub const LANGUAGES: [&str; 28] = [
+    "plain",
+    "c",
+    "cpp",
+    "csharp",
+    "css",
+    "go",
+    "haskell",
+    "html",
+    "java",
+    "javascript",
+    "json",
+    "kotlin",
+    "lua",
+    "markdown",
+    "perl",
+    "php",
+    "python",
+    "r",
+    "ruby",
+    "rust",
+    "scala",
+    "shell",
+    "sql",
+    "swift",
+    "toml",
+    "typescript",
+    "xml",
+    "yaml",
+];
 
-static JSON_PATTERNS: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r#"^\s*[\{\[]"#).expect("Invalid json regex"));
+/// Map language name to file extension for syntax highlighting.
+pub fn language_to_extension(language: &str) -> &'static str {
+    match language.to_lowercase().as_str() {
+        "rust" => "rs",
+        "python" => "py",
+        "javascript" => "js",
+        "typescript" => "ts",
+        "json" => "json",
+        "html" => "html",
+        "css" => "css",
+        "sql" => "sql",
+        "shell" | "bash" | "sh" => "sh",
+        "go" => "go",
+        "c" => "c",
+        "cpp" | "c++" => "cpp",
+        "java" => "java",
+        "ruby" => "rb",
+        "php" => "php",
+        "swift" => "swift",
+        "kotlin" => "kt",
+        "scala" => "scala",
+        "yaml" | "yml" => "yaml",
+        "toml" => "toml",
+        "markdown" | "md" => "md",
+        "xml" => "xml",
+        "csharp" | "c#" => "cs",
+        "lua" => "lua",
+        "perl" => "pl",
+        "haskell" => "hs",
+        "r" => "r",
+        _ => "txt",
+    }
+}
 
-static HTML_PATTERNS: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r"(?i)^\s*<!DOCTYPE|<html|<div|<span|<p\s|<a\s|<script|<style")
-        .expect("Invalid html regex")
-});
+/// Map a file extension to the language name used for code snippets.
+pub fn extension_to_language(extension: &str) -> Option<&'static str> {
+    let language = match extension.to_lowercase().as_str() {
+        "rs" => "rust",
+        "py" => "python",
+        "js" | "mjs" | "cjs" | "jsx" => "javascript",
+        "ts" | "tsx" => "typescript",
+        "json" => "json",
+        "html" | "htm" => "html",
+        "css" => "css",
+        "sql" => "sql",
+        "sh" | "bash" | "zsh" => "shell",
+        "go" => "go",
+        "c" | "h" => "c",
+        "cpp" | "cc" | "cxx" | "hpp" => "cpp",
+        "java" => "java",
+        "rb" => "ruby",
+        "php" => "php",
+        "swift" => "swift",
+        "kt" | "kts" => "kotlin",
+        "scala" => "scala",
+        "yaml" | "yml" => "yaml",
+        "toml" => "toml",
+        "md" | "markdown" => "markdown",
+        "xml" => "xml",
+        "cs" => "csharp",
+        "lua" => "lua",
+        "pl" | "pm" => "perl",
+        "hs" => "haskell",
+        "r" => "r",
+        _ => return None,
+    };
+    Some(language)
+}
 
-static CSS_PATTERNS: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r"(?m)^\s*(\.|#|@media|@keyframes|[a-z-]+\s*:\s*[^;]+;)").expect("Invalid css regex")
-});
+/// Make a label safe to use as a file or folder name.
+pub fn sanitize_file_stem(label: &str) -> String {
+    let stem: String = label
+        .trim()
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+    let stem = stem.trim_matches(|c: char| c == '.' || c.is_whitespace());
+    if stem.is_empty() {
+        "snippet".to_string()
+    } else {
+        stem.to_string()
+    }
+}
 
-static SQL_PATTERNS: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r"(?i)^\s*(SELECT|INSERT|UPDATE|DELETE|CREATE|ALTER|DROP|FROM|WHERE|JOIN)")
-        .expect("Invalid sql regex")
-});
+/// Build a file name for exporting a code snippet from its label and language.
+pub fn export_file_name(label: &str, language: &str) -> String {
+    format!(
+        "{}.{}",
+        sanitize_file_stem(label),
+        language_to_extension(language)
+    )
+}
 
-static SHELL_PATTERNS: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(
-        r"(?m)^(\s*#!\/bin\/(ba)?sh|^\s*\$\s+|\becho\s+|\bcd\s+|\bls\s|\bmkdir\s|\bgrep\s|\bsed\s)",
+/// Check if `reformat_code` can lay out code in a language.
+pub fn can_reformat(language: &str) -> bool {
+    matches!(
+        language.to_lowercase().as_str(),
+        "json" | "css" | "html" | "sql"
     )
-    .expect("Invalid shell regex")
-});
+}
+
+/// Pretty-print or minify JSON, CSS, HTML or SQL. Strings, comments in
+/// pretty SQL, and the contents of `<pre>`, `<script>`, `<style>` and
+/// `<textarea>` are kept as they are.
+pub fn reformat_code(code: &str, language: &str, format: JsonFormat) -> Result<String, String> {
+    match (language.to_lowercase().as_str(), format) {
+        ("json", format) => format.apply(&JsonData::new(code.to_string())),
+        ("css", JsonFormat::Pretty) => Ok(pretty_css(&minify_css(code))),
+        ("css", JsonFormat::Minified) => Ok(minify_css(code)),
+        ("html", JsonFormat::Pretty) => Ok(pretty_html(code)),
+        ("html", JsonFormat::Minified) => Ok(minify_html(code)),
+        ("sql", JsonFormat::Pretty) => Ok(pretty_sql(code)),
+        ("sql", JsonFormat::Minified) => Ok(minify_sql(code)),
+        (language, _) => Err(format!("Can't reformat {} code", language)),
+    }
+}
 
-static GO_PATTERNS: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r"(?m)^\s*(package\s+\w+|func\s+\w+|import\s+\(|type\s+\w+\s+struct)")
-        .expect("Invalid go regex")
-});
+/// Copy a quoted string starting at `chars[start]` into `out`; returns the
+/// index after it. Backslashes escape in CSS, doubled quotes in SQL.
+fn copy_quoted(chars: &[char], start: usize, out: &mut String) -> usize {
+    let quote = chars[start];
+    out.push(quote);
+    let mut i = start + 1;
+    while i < chars.len() {
+        let c = chars[i];
+        out.push(c);
+        i += 1;
+        if c == '\\' && quote != '`' {
+            if let Some(&next) = chars.get(i) {
+                out.push(next);
+                i += 1;
+            }
+        } else if c == quote {
+            if chars.get(i) == Some(&quote) {
+                out.push(quote);
+                i += 1;
+            } else {
+                break;
+            }
+        }
+    }
+    i
+}
 
-/// Detect the programming language of a code snippet.
-pub fn detect_language(code: &str) -> String {
-    // Check patterns in order of specificity
+// === CSS ===
 
-    // TypeScript before JavaScript (more specific)
-    if TYPESCRIPT_PATTERNS.is_match(code) && JAVASCRIPT_PATTERNS.is_match(code) {
-        return "typescript".to_string();
+/// Drop comments and the whitespace CSS doesn't need.
+fn minify_css(code: &str) -> String {
+    const TIGHT: &str = "{};,>";
+    let chars: Vec<char> = code.chars().collect();
+    // A space before `:` in a selector is a descendant combinator (`a :hover`),
+    // so only declarations lose it: those run up to a `;` or `}`, not a `{`
+    let in_declaration = |from: usize, depth: usize| {
+        depth > 0
+            && chars[from..]
+                .iter()
+                .find(|c| matches!(c, '{' | ';' | '}'))
+                .is_none_or(|c| *c != '{')
+    };
+    let mut out = String::new();
+    let mut pending_space = false;
+    let mut depth = 0usize;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '/' && chars.get(i + 1) == Some(&'*') {
+            i += 2;
+            while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                i += 1;
+            }
+            i += 2;
+            pending_space = true;
+            continue;
+        }
+        if c.is_whitespace() {
+            pending_space = true;
+            i += 1;
+            continue;
+        }
+        let tight = |c: char| TIGHT.contains(c) || c == ':' && in_declaration(i, depth);
+        if pending_space && !out.is_empty() && !tight(c) && !out.ends_with(tight) {
+            out.push(' ');
+        }
+        pending_space = false;
+        if c == '"' || c == '\'' {
+            i = copy_quoted(&chars, i, &mut out);
+            continue;
+        }
+        // The last declaration of a block needs no semicolon
+        if c == '}' && out.ends_with(';') {
+            out.pop();
+        }
+        match c {
+            '{' => depth += 1,
+            '}' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+        out.push(c);
+        i += 1;
     }
+    out
+}
 
-    if RUST_PATTERNS.is_match(code) {
-        return "rust".to_string();
+/// Lay out minified CSS one declaration per line.
+fn pretty_css(minified: &str) -> String {
+    let chars: Vec<char> = minified.chars().collect();
+    let mut out = String::new();
+    let mut depth = 0usize;
+    let newline = |out: &mut String, depth: usize| {
+        let trimmed = out.trim_end().len();
+        out.truncate(trimmed);
+        out.push('\n');
+        out.push_str(&"  ".repeat(depth));
+    };
+    // A selector runs up to a `{`, a declaration up to a `;` or `}`
+    let in_selector = |from: usize| {
+        chars[from..]
+            .iter()
+            .find(|c| matches!(c, '{' | ';' | '}'))
+            .is_none_or(|c| *c == '{')
+    };
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '"' | '\'' => {
+                i = copy_quoted(&chars, i, &mut out);
+                continue;
+            }
+            '{' => {
+                out.push_str(" {");
+                depth += 1;
+                newline(&mut out, depth);
+            }
+            ';' => {
+                out.push(';');
+                newline(&mut out, depth);
+            }
+            '}' => {
+                // Minified CSS drops the last semicolon; put it back
+                let last = out.trim_end().chars().last();
+                if !matches!(last, Some('{' | ';' | '}') | None) {
+                    let trimmed = out.trim_end().len();
+                    out.truncate(trimmed);
+                    out.push(';');
+                }
+                depth = depth.saturating_sub(1);
+                newline(&mut out, depth);
+                out.push('}');
+                if depth == 0 {
+                    out.push('\n');
+                }
+                newline(&mut out, depth);
+            }
+            ':' if depth > 0 && !in_selector(i) => out.push_str(": "),
+            ',' if in_selector(i) => {
+                out.push(',');
+                newline(&mut out, depth);
+            }
+            ',' => out.push_str(", "),
+            c => out.push(c),
+        }
+        i += 1;
     }
+    out.trim().to_string()
+}
+
+// === HTML ===
+
+/// A piece of an HTML document.
+enum HtmlToken {
+    /// A tag or doctype, with its name lowercased (`/div` for closing tags).
+    Tag(String, String),
+    Text(String),
+    Comment(String),
+    /// Contents of an element kept as is.
+    Raw(String),
+}
+
+/// Elements whose contents are not reformatted.
+const RAW_ELEMENTS: [&str; 4] = ["pre", "script", "style", "textarea"];
+
+/// Elements without a closing tag.
+const VOID_ELEMENTS: [&str; 14] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr", "param",
+];
 
-    if PYTHON_PATTERNS.is_match(code) {
-        return "python".to_string();
+fn html_tokens(code: &str) -> Vec<HtmlToken> {
+    let mut tokens = Vec::new();
+    let mut rest = code;
+    while !rest.is_empty() {
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            let end = comment.find("-->").map_or(comment.len(), |end| end + 3);
+            tokens.push(HtmlToken::Comment(format!("<!--{}", &comment[..end])));
+            rest = &comment[end..];
+            continue;
+        }
+        let opens_tag = rest.starts_with('<')
+            && rest[1..]
+                .chars()
+                .next()
+                .is_some_and(|c| c.is_ascii_alphabetic() || matches!(c, '/' | '!' | '?'));
+        if !opens_tag {
+            // Text runs to the next `<` after its first character
+            let first = rest.chars().next().map_or(1, char::len_utf8);
+            let end = rest[first..]
+                .find('<')
+                .map_or(rest.len(), |end| end + first);
+            tokens.push(HtmlToken::Text(rest[..end].to_string()));
+            rest = &rest[end..];
+            continue;
+        }
+
+        // Find the closing `>` outside attribute quotes
+        let mut quote = None;
+        let mut end = rest.len();
+        for (index, c) in rest.char_indices().skip(1) {
+            match (quote, c) {
+                (Some(q), c) if c == q => quote = None,
+                (None, '"' | '\'') => quote = Some(c),
+                (None, '>') => {
+                    end = index + 1;
+                    break;
+                }
+                _ => {}
+            }
+        }
+        let tag = &rest[..end];
+        let name: String = tag[1..]
+            .chars()
+            .take_while(|c| !c.is_whitespace() && !matches!(c, '>' | '/') || *c == '/')
+            .collect::<String>()
+            .to_lowercase();
+        let name = name.trim_end_matches('/').to_string();
+        tokens.push(HtmlToken::Tag(tag.to_string(), name.clone()));
+        rest = &rest[end..];
+
+        if RAW_ELEMENTS.contains(&name.as_str()) {
+            let closing = format!("</{}", name);
+            // ASCII lowercasing keeps byte offsets in line with `rest`
+            let end = rest
+                .to_ascii_lowercase()
+                .find(&closing)
+                .unwrap_or(rest.len());
+            if end > 0 {
+                tokens.push(HtmlToken::Raw(rest[..end].to_string()));
+            }
+            rest = &rest[end..];
+        }
     }
+    tokens
+}
 
-    if GO_PATTERNS.is_match(code) {
-        return "go".to_string();
+/// Collapse whitespace runs in a tag outside attribute quotes.
+fn collapse_tag(tag: &str) -> String {
+    let mut out = String::new();
+    let mut quote = None;
+    for c in tag.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (None, '"' | '\'') => quote = Some(c),
+            (None, c) if c.is_whitespace() => {
+                if !out.ends_with(' ') {
+                    out.push(' ');
+                }
+                continue;
+            }
+            _ => {}
+        }
+        out.push(c);
     }
+    out.replace(" >", ">").replace(" />", "/>")
+}
 
-    if JAVASCRIPT_PATTERNS.is_match(code) {
-        return "javascript".to_string();
+/// Collapse whitespace runs in text to single spaces.
+fn collapse_text(text: &str) -> String {
+    let mut out = String::new();
+    for c in text.chars() {
+        if c.is_whitespace() {
+            if !out.ends_with(' ') {
+                out.push(' ');
+            }
+        } else {
+            out.push(c);
+        }
     }
+    out
+}
 
-    // JSON check - must be valid-ish JSON structure
-    if JSON_PATTERNS.is_match(code) {
-        let trimmed = code.trim();
-        if (trimmed.starts_with('{') && trimmed.ends_with('}'))
-            || (trimmed.starts_with('[') && trimmed.ends_with(']'))
-        {
-            return "json".to_string();
+/// Drop comments and formatting whitespace; a space between inline text
+/// on one line is kept, as it shows.
+fn minify_html(code: &str) -> String {
+    let mut out = String::new();
+    for token in html_tokens(code) {
+        match token {
+            HtmlToken::Tag(tag, _) => out.push_str(&collapse_tag(&tag)),
+            HtmlToken::Text(text) if text.trim().is_empty() => {
+                if !text.contains('\n') && !out.is_empty() {
+                    out.push(' ');
+                }
+            }
+            HtmlToken::Text(text) => {
+                // Whitespace up to a line break is only layout
+                let text = match text.find(|c: char| !c.is_whitespace()) {
+                    Some(start) if text[..start].contains('\n') => &text[start..],
+                    _ => text.as_str(),
+                };
+                let text = match text.rfind(|c: char| !c.is_whitespace()) {
+                    Some(end) if text[end..].contains('\n') => &text[..=end],
+                    _ => text,
+                };
+                out.push_str(&collapse_text(text));
+            }
+            // Conditional comments still do something
+            HtmlToken::Comment(comment) if comment.starts_with("<!--[if") => out.push_str(&comment),
+            HtmlToken::Comment(_) => {}
+            HtmlToken::Raw(raw) => out.push_str(&raw),
         }
     }
+    out.trim().to_string()
+}
 
-    if HTML_PATTERNS.is_match(code) {
-        return "html".to_string();
+/// Put each tag and text on its own line, indented by nesting.
+fn pretty_html(code: &str) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    let mut depth = 0usize;
+    for token in html_tokens(code) {
+        let indent = |depth: usize| "  ".repeat(depth);
+        match token {
+            HtmlToken::Tag(tag, name) => {
+                let tag = collapse_tag(&tag);
+                if let Some(name) = name.strip_prefix('/') {
+                    depth = depth.saturating_sub(1);
+                    // Elements holding only text, or nothing, stay on one line
+                    let opening = format!("{}<{}", indent(depth), name);
+                    let opened_on = |line: &String| {
+                        line.strip_prefix(&opening)
+                            .is_some_and(|rest| rest.starts_with(['>', ' ', '/']))
+                    };
+                    let count = lines.len();
+                    if lines.last().is_some_and(opened_on) {
+                        lines[count - 1].push_str(&tag);
+                    } else if count >= 2
+                        && opened_on(&lines[count - 2])
+                        && !lines[count - 1].trim_start().starts_with('<')
+                    {
+                        let text = lines.pop().unwrap_or_default();
+                        lines[count - 2].push_str(text.trim_start());
+                        lines[count - 2].push_str(&tag);
+                    } else {
+                        lines.push(format!("{}{}", indent(depth), tag));
+                    }
+                } else {
+                    lines.push(format!("{}{}", indent(depth), tag));
+                    let opens = !name.starts_with('!')
+                        && !name.starts_with('?')
+                        && !tag.ends_with("/>")
+                        && !VOID_ELEMENTS.contains(&name.as_str());
+                    if opens {
+                        depth += 1;
+                    }
+                }
+            }
+            HtmlToken::Text(text) => {
+                let text = collapse_text(&text);
+                let text = text.trim();
+                if !text.is_empty() {
+                    lines.push(format!("{}{}", indent(depth), text));
+                }
+            }
+            HtmlToken::Comment(comment) => lines.push(format!("{}{}", indent(depth), comment)),
+            HtmlToken::Raw(raw) => {
+                if let Some(last) = lines.last_mut() {
+                    last.push_str(&raw);
+                }
+            }
+        }
     }
+    lines.join("\n")
+}
 
-    if CSS_PATTERNS.is_match(code) {
-        return "css".to_string();
+// === SQL ===
+
+/// A piece of an SQL statement.
+#[derive(Debug, PartialEq)]
+enum SqlToken {
+    Word(String),
+    /// A quoted string or identifier.
+    Quoted(String),
+    /// Operators run together, e.g. `<=`.
+    Symbol(String),
+    LineComment(String),
+    BlockComment(String),
+}
+
+/// Keywords followed by a space even before `(`.
+const SQL_SPACED_KEYWORDS: [&str; 17] = [
+    "IN", "VALUES", "AS", "ON", "AND", "OR", "NOT", "EXISTS", "FROM", "JOIN", "WHERE", "SELECT",
+    "USING", "OVER", "THEN", "ELSE", "WHEN",
+];
+
+/// Other keywords after which a `-` or `+` is a sign, not an operator.
+const SQL_SIGN_KEYWORDS: [&str; 10] = [
+    "LIMIT", "OFFSET", "BY", "SET", "HAVING", "BETWEEN", "IS", "LIKE", "RETURN", "CASE",
+];
+
+/// Check if a `-` or `+` after the tokens so far is a sign.
+fn sql_sign_follows(tokens: &[SqlToken]) -> bool {
+    match tokens.last() {
+        None => true,
+        Some(SqlToken::Symbol(symbol)) => symbol != ")",
+        Some(SqlToken::Word(word)) => {
+            let upper = word.to_uppercase();
+            SQL_SPACED_KEYWORDS.contains(&upper.as_str())
+                || SQL_SIGN_KEYWORDS.contains(&upper.as_str())
+        }
+        Some(_) => false,
     }
+}
 
-    if SQL_PATTERNS.is_match(code) {
-        return "sql".to_string();
+fn sql_tokens(code: &str) -> Vec<SqlToken> {
+    const OPERATORS: &str = "<>=!|:";
+    let chars: Vec<char> = code.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '-' && chars.get(i + 1) == Some(&'-') {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+            tokens.push(SqlToken::LineComment(chars[start..i].iter().collect()));
+        } else if c == '/' && chars.get(i + 1) == Some(&'*') {
+            i += 2;
+            while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                i += 1;
+            }
+            i = (i + 2).min(chars.len());
+            tokens.push(SqlToken::BlockComment(chars[start..i].iter().collect()));
+        } else if matches!(c, '\'' | '"' | '`') {
+            let mut quoted = String::new();
+            i = copy_quoted(&chars, i, &mut quoted);
+            tokens.push(SqlToken::Quoted(quoted));
+        } else if c.is_alphanumeric()
+            || matches!(c, '_' | '$' | '@' | '#')
+            // A sign stays with its operand: `x = -1`
+            || matches!(c, '-' | '+')
+                && chars
+                    .get(i + 1)
+                    .is_some_and(|next| next.is_alphanumeric() || *next == '_')
+                && sql_sign_follows(&tokens)
+        {
+            i += 1;
+            while i < chars.len()
+                && (chars[i].is_alphanumeric() || matches!(chars[i], '_' | '$' | '@' | '#'))
+            {
+                i += 1;
+            }
+            tokens.push(SqlToken::Word(chars[start..i].iter().collect()));
+        } else if OPERATORS.contains(c) {
+            while i < chars.len() && OPERATORS.contains(chars[i]) {
+                i += 1;
+            }
+            tokens.push(SqlToken::Symbol(chars[start..i].iter().collect()));
+        } else {
+            i += 1;
+            tokens.push(SqlToken::Symbol(c.to_string()));
+        }
     }
+    tokens
+}
 
-    if SHELL_PATTERNS.is_match(code) {
-        return "shell".to_string();
+impl SqlToken {
+    fn text(&self) -> &str {
+        match self {
+            SqlToken::Word(text)
+            | SqlToken::Quoted(text)
+            | SqlToken::Symbol(text)
+            | SqlToken::LineComment(text)
+            | SqlToken::BlockComment(text) => text,
+        }
     }
 
-    "plain".to_string()
+    fn is_word(&self, word: &str) -> bool {
+        matches!(self, SqlToken::Word(w) if w.eq_ignore_ascii_case(word))
+    }
 }
 
-/// Map language name to file extension for syntax highlighting.
-pub fn language_to_extension(language: &str) -> &'static str {
-    match language.to_lowercase().as_str() {
-        "rust" => "rs",
-        "python" => "py",
-        "javascript" => "js",
-        "typescript" => "ts",
-        "json" => "json",
-        "html" => "html",
-        "css" => "css",
-        "sql" => "sql",
-        "shell" | "bash" | "sh" => "sh",
-        "go" => "go",
-        "c" => "c",
-        "cpp" | "c++" => "cpp",
-        "java" => "java",
-        "ruby" => "rb",
-        "php" => "php",
-        "swift" => "swift",
-        "kotlin" => "kt",
-        "scala" => "scala",
-        "yaml" | "yml" => "yaml",
-        "toml" => "toml",
-        "markdown" | "md" => "md",
-        "xml" => "xml",
-        _ => "txt",
+/// Check if a space goes between two tokens.
+fn sql_spaced(previous: &str, next: &str) -> bool {
+    // Function calls keep their parenthesis, keywords get a space
+    let call = next == "("
+        && previous
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_alphanumeric() || c == '_')
+        && !SQL_SPACED_KEYWORDS.contains(&previous.to_uppercase().as_str());
+    !call && !matches!(previous, "(" | ".") && !matches!(next, "," | ")" | ";" | ".")
+}
+
+/// Drop comments and join the statement on one line.
+fn minify_sql(code: &str) -> String {
+    let mut out = String::new();
+    let mut previous = String::new();
+    for token in sql_tokens(code) {
+        if matches!(token, SqlToken::LineComment(_) | SqlToken::BlockComment(_)) {
+            continue;
+        }
+        let text = token.text();
+        let tight = matches!(previous.as_str(), "," | "=") || matches!(text, "(" | "=");
+        if !out.is_empty() && sql_spaced(&previous, text) && !tight {
+            out.push(' ');
+        }
+        out.push_str(text);
+        previous = text.to_string();
     }
+    out
+}
+
+/// Start each clause on a new line, with `AND`/`OR` conditions and selected
+/// columns indented below it.
+fn pretty_sql(code: &str) -> String {
+    const CLAUSES: [&str; 15] = [
+        "SELECT",
+        "FROM",
+        "WHERE",
+        "GROUP",
+        "ORDER",
+        "HAVING",
+        "LIMIT",
+        "OFFSET",
+        "UNION",
+        "INSERT",
+        "VALUES",
+        "UPDATE",
+        "SET",
+        "DELETE",
+        "RETURNING",
+    ];
+    const JOINS: [&str; 6] = ["JOIN", "LEFT", "RIGHT", "INNER", "FULL", "CROSS"];
+
+    let tokens = sql_tokens(code);
+    let mut out = String::new();
+    let mut depth = 0usize;
+    let mut clause = String::new();
+    let mut previous = String::new();
+    for (index, token) in tokens.iter().enumerate() {
+        let text = token.text();
+        let upper = text.to_uppercase();
+        let after_join_word = tokens[..index].last().is_some_and(|t| {
+            ["LEFT", "RIGHT", "INNER", "FULL", "CROSS", "OUTER"]
+                .iter()
+                .any(|w| t.is_word(w))
+        });
+        let starts_clause = depth == 0
+            && matches!(token, SqlToken::Word(_))
+            && (CLAUSES.contains(&upper.as_str())
+                || JOINS.contains(&upper.as_str()) && !after_join_word)
+            // `DELETE FROM` and `INSERT INTO` read as one clause; UNION ALL too
+            && !(upper == "FROM" && tokens[..index].last().is_some_and(|t| t.is_word("DELETE")));
+        let is_condition = depth == 0 && (upper == "AND" || upper == "OR")
+            && matches!(token, SqlToken::Word(_))
+            && !clause.is_empty()
+            // BETWEEN x AND y stays on one line
+            && !tokens[..index]
+                .iter()
+                .rev()
+                .take(4)
+                .any(|t| t.is_word("BETWEEN"));
+
+        if starts_clause {
+            if !out.is_empty() && !out.ends_with('\n') {
+                out.push('\n');
+            }
+            clause = upper.clone();
+        } else if is_condition {
+            out.push_str("\n  ");
+        } else if !out.is_empty()
+            && !out.ends_with('\n')
+            && !out.ends_with("  ")
+            && sql_spaced(&previous, text)
+        {
+            out.push(' ');
+        }
+        out.push_str(text);
+
+        match text {
+            "(" => depth += 1,
+            ")" => depth = depth.saturating_sub(1),
+            "," if depth == 0 && matches!(clause.as_str(), "SELECT" | "SET") => {
+                out.push_str("\n  ");
+            }
+            ";" => {
+                out.push_str("\n\n");
+                clause.clear();
+            }
+            _ => {}
+        }
+        if matches!(token, SqlToken::LineComment(_)) {
+            out.push('\n');
+        }
+        previous = text.to_string();
+    }
+    out.trim().to_string()
 }
 
 /// Check if text looks like code (heuristics).
@@ -252,6 +866,39 @@ pub fn looks_like_code(text: &str) -> bool {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_export_file_name() {
+        assert_eq!(export_file_name("main", "rust"), "main.rs");
+        assert_eq!(export_file_name("a/b: c?", "python"), "a_b_ c_.py");
+        assert_eq!(export_file_name("  ..  ", "unknown"), "snippet.txt");
+    }
+
+    #[test]
+    fn test_extension_to_language() {
+        assert_eq!(extension_to_language("RS"), Some("rust"));
+        assert_eq!(extension_to_language("tsx"), Some("typescript"));
+        assert_eq!(extension_to_language("exe"), None);
+        // Round-trips with the export extension
+        for language in ["rust", "python", "shell", "cpp", "yaml", "markdown"] {
+            assert_eq!(
+                extension_to_language(language_to_extension(language)),
+                Some(language)
+            );
+        }
+    }
+
+    #[test]
+    fn test_languages_are_canonical() {
+        // Every offered language is highlighted and keeps its name through a file
+        for language in &LANGUAGES[1..] {
+            assert_eq!(
+                extension_to_language(language_to_extension(language)),
+                Some(*language)
+            );
+        }
+        assert_eq!(language_to_extension(LANGUAGES[0]), "txt");
+    }
+
     #[test]
     fn test_detect_rust() {
         let code = r#"
@@ -307,4 +954,119 @@ const greeting = () => {
         assert_eq!(code.preview(2), "line 1\nline 2");
         assert_eq!(code.line_count(), 5);
     }
+
+    #[test]
+    fn test_reformat_css() {
+        let css = "a, b {\n  color: red;\n  /* note */\n  content: \"x ; y\";\n}\n@media (max-width: 600px) { p { margin: 0 } }";
+        assert_eq!(
+            reformat_code(css, "css", JsonFormat::Minified).unwrap(),
+            "a,b{color:red;content:\"x ; y\"}@media (max-width: 600px){p{margin:0}}"
+        );
+        assert_eq!(
+            reformat_code("a{color:red;margin:0}", "css", JsonFormat::Pretty).unwrap(),
+            "a {\n  color: red;\n  margin: 0;\n}"
+        );
+        // The space before `:hover` selects descendants of `a`
+        assert_eq!(
+            reformat_code(
+                "a :hover { color : red }\nb:focus {}",
+                "css",
+                JsonFormat::Minified
+            )
+            .unwrap(),
+            "a :hover{color:red}b:focus{}"
+        );
+        assert_eq!(
+            reformat_code(
+                ".é::after { content : \"日本\" ; }",
+                "css",
+                JsonFormat::Minified
+            )
+            .unwrap(),
+            ".é::after{content:\"日本\"}"
+        );
+    }
+
+    #[test]
+    fn test_reformat_html() {
+        let html = "<div class=\"a\">\n  Hello <b>world</b>\n  <!-- note -->\n</div>\n<pre> keep\n  this</pre>";
+        assert_eq!(
+            reformat_code(html, "html", JsonFormat::Minified).unwrap(),
+            "<div class=\"a\">Hello <b>world</b></div><pre> keep\n  this</pre>"
+        );
+        // Contents of raw elements may change length when lowercased
+        assert_eq!(
+            reformat_code("<pre>\u{212A}\u{20AC}</pre>", "html", JsonFormat::Minified).unwrap(),
+            "<pre>\u{212A}\u{20AC}</pre>"
+        );
+        assert_eq!(
+            reformat_code(
+                "<div><script>var s=\"\u{130}\";</SCRIPT></div>",
+                "html",
+                JsonFormat::Pretty
+            )
+            .unwrap(),
+            "<div>\n  <script>var s=\"\u{130}\";</SCRIPT>\n</div>"
+        );
+        assert_eq!(
+            reformat_code(
+                "<ul><li>One</li><li><br></li></ul>",
+                "html",
+                JsonFormat::Pretty
+            )
+            .unwrap(),
+            "<ul>\n  <li>One</li>\n  <li>\n    <br>\n  </li>\n</ul>"
+        );
+        // Text nodes may start with a multibyte character
+        assert_eq!(
+            reformat_code("<p>é</p>\n<p>日本</p>", "html", JsonFormat::Minified).unwrap(),
+            "<p>é</p><p>日本</p>"
+        );
+        assert_eq!(
+            reformat_code("<div><p>日本</p>é</div>", "html", JsonFormat::Pretty).unwrap(),
+            "<div>\n  <p>日本</p>\n  é\n</div>"
+        );
+    }
+
+    #[test]
+    fn test_reformat_sql() {
+        let sql = "select a, count(b) as n\nfrom t where x = 'it''s' and y in (1, 2)\ngroup by a";
+        assert_eq!(
+            reformat_code(sql, "sql", JsonFormat::Minified).unwrap(),
+            "select a,count(b) as n from t where x='it''s' and y in(1,2) group by a"
+        );
+        assert_eq!(
+            reformat_code(sql, "sql", JsonFormat::Pretty).unwrap(),
+            "select a,\n  count(b) as n\nfrom t\nwhere x = 'it''s'\n  and y in (1, 2)\ngroup by a"
+        );
+        let sql = "select -1, a - 1 from t where x = -1 and y between -2 and +z";
+        assert_eq!(
+            reformat_code(sql, "sql", JsonFormat::Minified).unwrap(),
+            "select -1,a - 1 from t where x=-1 and y between -2 and +z"
+        );
+        assert_eq!(
+            reformat_code(sql, "sql", JsonFormat::Pretty).unwrap(),
+            "select -1,\n  a - 1\nfrom t\nwhere x = -1\n  and y between -2 and +z"
+        );
+        let sql = "select é, \"日本\" from t where name = 'Zoë'";
+        assert_eq!(
+            reformat_code(sql, "sql", JsonFormat::Minified).unwrap(),
+            "select é,\"日本\" from t where name='Zoë'"
+        );
+        assert_eq!(
+            reformat_code(sql, "sql", JsonFormat::Pretty).unwrap(),
+            "select é,\n  \"日本\"\nfrom t\nwhere name = 'Zoë'"
+        );
+    }
+
+    #[test]
+    fn test_reformat_json_and_unsupported() {
+        assert_eq!(
+            reformat_code("{\"a\": [1, 2]}", "json", JsonFormat::Minified).unwrap(),
+            "{\"a\":[1,2]}"
+        );
+        assert!(reformat_code("{", "json", JsonFormat::Pretty).is_err());
+        assert!(!can_reformat("rust"));
+        assert!(reformat_code("fn a() {}", "rust", JsonFormat::Pretty).is_err());
+    }
 }
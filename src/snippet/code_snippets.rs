@@ -0,0 +1,143 @@
+//! Editor snippet files (`.code-snippets`) as read by VS Code.
+
+use std::collections::HashSet;
+
+use serde::Serialize;
+use serde_json::Map;
+
+use super::code::CodeData;
+
+/// File extension of VS Code snippet files.
+pub const CODE_SNIPPETS_EXTENSION: &str = "code-snippets";
+
+/// One entry of a `.code-snippets` file.
+#[derive(Serialize)]
+struct EditorSnippet {
+    prefix: String,
+    body: Vec<String>,
+    description: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scope: Option<&'static str>,
+}
+
+/// Build the contents of a `.code-snippets` file from labelled code snippets.
+/// Entries are keyed by label, with a number added to repeated labels and
+/// prefixes.
+pub fn code_snippets_file(snippets: &[(&str, &CodeData)]) -> String {
+    let mut entries = Map::new();
+    let mut prefixes = HashSet::new();
+    for (label, code) in snippets {
+        let mut key = label.trim().to_string();
+        let mut n = 2;
+        while entries.contains_key(&key) {
+            key = format!("{} ({})", label.trim(), n);
+            n += 1;
+        }
+        let base = snippet_prefix(label);
+        let mut prefix = base.clone();
+        let mut n = 2;
+        while !prefixes.insert(prefix.clone()) {
+            prefix = format!("{}-{}", base, n);
+            n += 1;
+        }
+        let entry = EditorSnippet {
+            prefix,
+            // `$` starts a tab stop in the editor's snippet syntax
+            body: code
+                .code
+                .lines()
+                .map(|line| line.replace('\\', "\\\\").replace('$', "\\$"))
+                .collect(),
+            description: label.trim().to_string(),
+            scope: editor_language_id(&code.language),
+        };
+        entries.insert(
+            key,
+            serde_json::to_value(entry).unwrap_or(serde_json::Value::Null),
+        );
+    }
+    let mut json = serde_json::to_string_pretty(&entries).unwrap_or_else(|_| "{}".to_string());
+    json.push('\n');
+    json
+}
+
+/// Get the completion prefix for a label: lowercase words joined by dashes.
+pub fn snippet_prefix(label: &str) -> String {
+    let words: Vec<String> = label
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    if words.is_empty() {
+        "snippet".to_string()
+    } else {
+        words.join("-")
+    }
+}
+
+/// Map a snippet language to the editor's language identifier, if known.
+fn editor_language_id(language: &str) -> Option<&'static str> {
+    let id = match language.to_lowercase().as_str() {
+        "rust" => "rust",
+        "python" => "python",
+        "javascript" => "javascript",
+        "typescript" => "typescript",
+        "json" => "json",
+        "html" => "html",
+        "css" => "css",
+        "sql" => "sql",
+        "shell" | "bash" | "sh" => "shellscript",
+        "go" => "go",
+        "c" => "c",
+        "cpp" | "c++" => "cpp",
+        "java" => "java",
+        "ruby" => "ruby",
+        "php" => "php",
+        "swift" => "swift",
+        "kotlin" => "kotlin",
+        "scala" => "scala",
+        "yaml" | "yml" => "yaml",
+        "toml" => "toml",
+        "markdown" | "md" => "markdown",
+        "xml" => "xml",
+        "csharp" | "c#" => "csharp",
+        "lua" => "lua",
+        "perl" => "perl",
+        "haskell" => "haskell",
+        "r" => "r",
+        _ => return None,
+    };
+    Some(id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn code(code: &str, language: &str) -> CodeData {
+        CodeData::new(code.to_string(), language.to_string())
+    }
+
+    #[test]
+    fn test_snippet_prefix() {
+        assert_eq!(snippet_prefix("Fetch JSON (async)"), "fetch-json-async");
+        assert_eq!(snippet_prefix("  "), "snippet");
+    }
+
+    #[test]
+    fn test_code_snippets_file() {
+        let log = code("console.log($x);\n", "javascript");
+        let other = code("echo \"$HOME\"", "shell");
+        let plain = code("a\\b", "plain");
+        let json = code_snippets_file(&[("Log", &log), ("Log", &other), ("Path", &plain)]);
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(value["Log"]["prefix"], "log");
+        assert_eq!(value["Log"]["body"][0], "console.log(\\$x);");
+        assert_eq!(value["Log"]["scope"], "javascript");
+        assert_eq!(value["Log (2)"]["prefix"], "log-2");
+        assert_eq!(value["Log (2)"]["scope"], "shellscript");
+        assert_eq!(value["Path"]["body"][0], "a\\\\b");
+        assert!(value["Path"].get("scope").is_none());
+    }
+}
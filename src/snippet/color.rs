@@ -5,11 +5,21 @@
 //! - RGB: `rgb(r, g, b)`, `rgba(r, g, b, a)`
 //! - HSL: `hsl(h, s%, l%)`, `hsla(h, s%, l%, a)`
 //! - OKLCH: `oklch(l% c h)`, `oklch(l% c h / a)`
+//! - CIELAB: `lab(l% a b)`, `lch(l% c h)`, with an optional `/ a`
+//! - Display P3: `color(display-p3 r g b)`, `color(display-p3 r g b / a)`
+//! - CMYK: `cmyk(c%, m%, y%, k%)`, `device-cmyk(c m y k)`
+//! - Named: the CSS named colors, e.g. `rebeccapurple`
+//!
+//! Colors are stored as 8-bit sRGB, so wide-gamut input is clamped to sRGB.
 
 use regex::Regex;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::ops::Range;
 use std::sync::LazyLock;
 
+use super::named_colors::named_color;
+
 /// Color data with RGBA components.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ColorData {
@@ -30,7 +40,8 @@ impl ColorData {
         }
     }
 
-    /// Parse a color from a string. Supports hex, rgb, rgba, hsl, hsla, oklch formats.
+    /// Parse a color from a string. Supports hex, rgb, rgba, hsl, hsla, oklch, lab, lch,
+    /// display-p3 and cmyk formats.
     pub fn parse(input: &str) -> Result<Self, ColorParseError> {
         let input = input.trim();
 
@@ -50,6 +61,26 @@ impl ColorData {
             return Ok(color);
         }
 
+        if let Some(color) = parse_lab(input) {
+            return Ok(color);
+        }
+
+        if let Some(color) = parse_lch(input) {
+            return Ok(color);
+        }
+
+        if let Some(color) = parse_display_p3(input) {
+            return Ok(color);
+        }
+
+        if let Some(color) = parse_cmyk(input) {
+            return Ok(color);
+        }
+
+        if let Some([r, g, b]) = named_color(input) {
+            return Ok(Self::new(r, g, b, 1.0));
+        }
+
         Err(ColorParseError::InvalidFormat(input.to_string()))
     }
 
@@ -93,6 +124,27 @@ impl ColorData {
         }
     }
 
+    /// Convert to hsv string, as used by design tools (not a CSS function).
+    pub fn to_hsv(&self) -> String {
+        let (h, s, v) = rgb_to_hsv(self.r, self.g, self.b);
+        if (self.a - 1.0).abs() < f32::EPSILON {
+            format!(
+                "hsv({}, {}%, {}%)",
+                h.round() as i32,
+                (s * 100.0).round() as i32,
+                (v * 100.0).round() as i32
+            )
+        } else {
+            format!(
+                "hsva({}, {}%, {}%, {:.2})",
+                h.round() as i32,
+                (s * 100.0).round() as i32,
+                (v * 100.0).round() as i32,
+                self.a
+            )
+        }
+    }
+
     /// Convert to oklch string.
     pub fn to_oklch(&self) -> String {
         let (l, c, h) = rgb_to_oklch(self.r, self.g, self.b);
@@ -103,6 +155,128 @@ impl ColorData {
         }
     }
 
+    /// Convert to CIE Lab string.
+    pub fn to_lab(&self) -> String {
+        let (l, a, b) = rgb_to_lab(self.r, self.g, self.b);
+        if (self.a - 1.0).abs() < f32::EPSILON {
+            format!("lab({:.2}% {:.2} {:.2})", l, a, b)
+        } else {
+            format!("lab({:.2}% {:.2} {:.2} / {:.2})", l, a, b, self.a)
+        }
+    }
+
+    /// Convert to CIE LCH string.
+    pub fn to_lch(&self) -> String {
+        let (l, c, h) = rgb_to_lch(self.r, self.g, self.b);
+        if (self.a - 1.0).abs() < f32::EPSILON {
+            format!("lch({:.2}% {:.2} {:.2})", l, c, h)
+        } else {
+            format!("lch({:.2}% {:.2} {:.2} / {:.2})", l, c, h, self.a)
+        }
+    }
+
+    /// Convert to CSS `color(display-p3 …)` string.
+    pub fn to_display_p3(&self) -> String {
+        let (r, g, b) = rgb_to_display_p3(self.r, self.g, self.b);
+        if (self.a - 1.0).abs() < f32::EPSILON {
+            format!("color(display-p3 {:.4} {:.4} {:.4})", r, g, b)
+        } else {
+            format!(
+                "color(display-p3 {:.4} {:.4} {:.4} / {:.2})",
+                r, g, b, self.a
+            )
+        }
+    }
+
+    /// Convert to cmyk string. Print colors have no alpha, so it is left out.
+    pub fn to_cmyk(&self) -> String {
+        let (c, m, y, k) = rgb_to_cmyk(self.r, self.g, self.b);
+        format!(
+            "cmyk({}%, {}%, {}%, {}%)",
+            (c * 100.0).round() as i32,
+            (m * 100.0).round() as i32,
+            (y * 100.0).round() as i32,
+            (k * 100.0).round() as i32
+        )
+    }
+
+    /// Convert to a SwiftUI `Color` initializer.
+    pub fn to_swiftui(&self) -> String {
+        let [r, g, b] = self.unit_channels();
+        if (self.a - 1.0).abs() < f32::EPSILON {
+            format!("Color(red: {:.3}, green: {:.3}, blue: {:.3})", r, g, b)
+        } else {
+            format!(
+                "Color(red: {:.3}, green: {:.3}, blue: {:.3}, opacity: {:.2})",
+                r, g, b, self.a
+            )
+        }
+    }
+
+    /// Convert to an ARGB hex literal as Flutter and Jetpack Compose write
+    /// it, e.g. `Color(0xFF3B82F6)`.
+    pub fn to_argb_literal(&self) -> String {
+        format!(
+            "Color(0x{:02X}{:02X}{:02X}{:02X})",
+            (self.a * 255.0).round() as u8,
+            self.r,
+            self.g,
+            self.b
+        )
+    }
+
+    /// Convert to an `iced::Color` constructor.
+    pub fn to_iced_literal(&self) -> String {
+        let [r, g, b] = self.unit_channels();
+        if (self.a - 1.0).abs() < f32::EPSILON {
+            format!("iced::Color::from_rgb({:.3}, {:.3}, {:.3})", r, g, b)
+        } else {
+            format!(
+                "iced::Color::from_rgba({:.3}, {:.3}, {:.3}, {:.2})",
+                r, g, b, self.a
+            )
+        }
+    }
+
+    /// The red, green and blue channels from 0 to 1.
+    fn unit_channels(&self) -> [f32; 3] {
+        [self.r, self.g, self.b].map(|c| c as f32 / 255.0)
+    }
+
+    /// WCAG relative luminance, from 0 (black) to 1 (white), ignoring alpha.
+    pub fn relative_luminance(&self) -> f32 {
+        let [r, g, b] = [self.r, self.g, self.b].map(|c| srgb_to_linear(c as f32 / 255.0));
+        0.2126 * r + 0.7152 * g + 0.0722 * b
+    }
+
+    /// Blend this color over an opaque background.
+    pub fn over(&self, background: &ColorData) -> ColorData {
+        let blend =
+            |fg: u8, bg: u8| (fg as f32 * self.a + bg as f32 * (1.0 - self.a)).round() as u8;
+        ColorData::new(
+            blend(self.r, background.r),
+            blend(self.g, background.g),
+            blend(self.b, background.b),
+            1.0,
+        )
+    }
+
+    /// WCAG contrast ratio of this color shown on `background`, from 1 to 21.
+    /// A translucent color is blended over the background first.
+    pub fn contrast_ratio(&self, background: &ColorData) -> f32 {
+        let foreground = self.over(background).relative_luminance();
+        let background = background.relative_luminance();
+        (foreground.max(background) + 0.05) / (foreground.min(background) + 0.05)
+    }
+
+    /// Perceptual difference to another color, ignoring alpha.
+    /// Euclidean distance in OKLab scaled by 100, so about 2 is a just noticeable difference.
+    pub fn delta_e(&self, other: &ColorData) -> f32 {
+        let (l1, a1, b1) = rgb_to_oklab(self.r, self.g, self.b);
+        let (l2, a2, b2) = rgb_to_oklab(other.r, other.g, other.b);
+        ((l1 - l2).powi(2) + (a1 - a2).powi(2) + (b1 - b2).powi(2)).sqrt() * 100.0
+    }
+
     /// Convert to iced::Color for rendering.
     pub fn to_iced_color(&self) -> iced::Color {
         iced::Color::from_rgba8(self.r, self.g, self.b, self.a)
@@ -128,24 +302,114 @@ static HEX_REGEX: LazyLock<Regex> = LazyLock::new(|| {
         .expect("Invalid hex regex")
 });
 
+// RGB format, legacy `rgb(255, 87, 51, 0.5)` or modern `rgb(255 87 51 / 50%)`;
+// channels may be percentages
 static RGB_REGEX: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new(
-        r"(?i)^rgba?\s*\(\s*(\d{1,3})\s*,\s*(\d{1,3})\s*,\s*(\d{1,3})\s*(?:,\s*([\d.]+))?\s*\)$",
+        r"(?i)^rgba?\s*\(\s*([\d.]+%?)(?:\s*,\s*|\s+)([\d.]+%?)(?:\s*,\s*|\s+)([\d.]+%?)\s*(?:[,/]\s*([\d.]+%?))?\s*\)$",
     )
     .expect("Invalid rgb regex")
 });
 
+// HSL format, legacy `hsl(11, 100%, 60%)` or modern `hsl(11deg 100% 60% / 0.5)`
 static HSL_REGEX: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r"(?i)^hsla?\s*\(\s*(\d{1,3}(?:\.\d+)?)\s*,\s*(\d{1,3}(?:\.\d+)?)%?\s*,\s*(\d{1,3}(?:\.\d+)?)%?\s*(?:,\s*([\d.]+))?\s*\)$")
+    Regex::new(r"(?i)^hsla?\s*\(\s*(-?[\d.]+)(deg|grad|rad|turn)?(?:\s*,\s*|\s+)([\d.]+)%?(?:\s*,\s*|\s+)([\d.]+)%?\s*(?:[,/]\s*([\d.]+%?))?\s*\)$")
         .expect("Invalid hsl regex")
 });
 
 // OKLCH format: oklch(L% C H) or oklch(L% C H / A)
 static OKLCH_REGEX: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r"(?i)^oklch\s*\(\s*([\d.]+)%?\s+([\d.]+)\s+([\d.]+)\s*(?:/\s*([\d.]+))?\s*\)$")
+    Regex::new(r"(?i)^oklch\s*\(\s*([\d.]+)%?\s+([\d.]+)\s+([\d.]+)\s*(?:/\s*([\d.]+%?))?\s*\)$")
         .expect("Invalid oklch regex")
 });
 
+// CIE Lab format: lab(L% a b) or lab(L% a b / A)
+static LAB_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)^lab\s*\(\s*([\d.]+)%?\s+(-?[\d.]+)\s+(-?[\d.]+)\s*(?:/\s*([\d.]+%?))?\s*\)$")
+        .expect("Invalid lab regex")
+});
+
+// CIE LCH format: lch(L% C H) or lch(L% C H / A)
+static LCH_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)^lch\s*\(\s*([\d.]+)%?\s+([\d.]+)\s+([\d.]+)(?:deg)?\s*(?:/\s*([\d.]+%?))?\s*\)$",
+    )
+    .expect("Invalid lch regex")
+});
+
+// Display P3 format: color(display-p3 R G B) or color(display-p3 R G B / A),
+// channels 0-1 or percentages
+static DISPLAY_P3_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)^color\s*\(\s*display-p3\s+([\d.]+%?)\s+([\d.]+%?)\s+([\d.]+%?)\s*(?:/\s*([\d.]+%?))?\s*\)$")
+        .expect("Invalid display-p3 regex")
+});
+
+// CMYK format: cmyk(C%, M%, Y%, K%) or device-cmyk(C M Y K / A), channels
+// 0-1 or percentages
+static CMYK_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)^(?:device-)?cmyk\s*\(\s*([\d.]+%?)(?:\s*,\s*|\s+)([\d.]+%?)(?:\s*,\s*|\s+)([\d.]+%?)(?:\s*,\s*|\s+)([\d.]+%?)\s*(?:/\s*([\d.]+%?))?\s*\)$")
+        .expect("Invalid cmyk regex")
+});
+
+// Any color literal inside running text (validated by `ColorData::parse`)
+static COLOR_LITERAL_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)#[0-9a-f]{3,8}\b|\b(?:rgba?|hsla?|oklch|lab|lch|color|(?:device-)?cmyk)\s*\([^)]+\)",
+    )
+    .expect("Invalid color literal regex")
+});
+
+// A CSS declaration, `property: value;`, whose value may hold named colors
+static CSS_DECLARATION_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b[a-z-]+\s*:\s*([^;{}:\n]+?)\s*[;}]").expect("Invalid CSS declaration regex")
+});
+
+/// Parse an RGB channel: 0–255, or a percentage of 255.
+fn parse_channel(value: &str) -> Option<u8> {
+    let channel = match value.strip_suffix('%') {
+        Some(percent) => percent.parse::<f32>().ok().filter(|p| *p <= 100.0)? * 2.55,
+        None => value.parse::<f32>().ok().filter(|c| *c <= 255.0)?,
+    };
+    Some(channel.round() as u8)
+}
+
+/// Parse an optional alpha value (a 0–1 number or a percentage); opaque when absent.
+fn parse_alpha(value: Option<&str>) -> f32 {
+    let Some(value) = value else {
+        return 1.0;
+    };
+    match value.strip_suffix('%') {
+        Some(percent) => percent.parse::<f32>().map_or(1.0, |p| p / 100.0),
+        None => value.parse().unwrap_or(1.0),
+    }
+}
+
+/// Parse a channel written as 0-1 or as a percentage, e.g. `0.5` or `50%`.
+fn parse_unit_channel(value: &str) -> Option<f32> {
+    let channel = match value.strip_suffix('%') {
+        Some(percent) => percent.parse::<f32>().ok()? / 100.0,
+        None => value.parse::<f32>().ok()?,
+    };
+    (channel <= 1.0).then_some(channel)
+}
+
+/// Convert a hue with an optional CSS angle unit to degrees in 0–360.
+fn parse_hue(value: &str, unit: Option<&str>) -> Option<f32> {
+    let value: f32 = value.parse().ok()?;
+    let degrees = match unit.map(str::to_lowercase).as_deref() {
+        Some("turn") => value * 360.0,
+        Some("rad") => value.to_degrees(),
+        Some("grad") => value * 0.9,
+        _ => value,
+    };
+    // 360 itself is kept as is, like before units were supported
+    if degrees == 360.0 {
+        Some(degrees)
+    } else {
+        Some(degrees.rem_euclid(360.0))
+    }
+}
+
 fn parse_hex(input: &str) -> Option<ColorData> {
     let caps = HEX_REGEX.captures(input)?;
     let hex = caps.get(1)?.as_str();
@@ -186,13 +450,10 @@ fn parse_hex(input: &str) -> Option<ColorData> {
 fn parse_rgb(input: &str) -> Option<ColorData> {
     let caps = RGB_REGEX.captures(input)?;
 
-    let r: u8 = caps.get(1)?.as_str().parse().ok()?;
-    let g: u8 = caps.get(2)?.as_str().parse().ok()?;
-    let b: u8 = caps.get(3)?.as_str().parse().ok()?;
-    let a: f32 = caps
-        .get(4)
-        .map(|m| m.as_str().parse().unwrap_or(1.0))
-        .unwrap_or(1.0);
+    let r = parse_channel(caps.get(1)?.as_str())?;
+    let g = parse_channel(caps.get(2)?.as_str())?;
+    let b = parse_channel(caps.get(3)?.as_str())?;
+    let a = parse_alpha(caps.get(4).map(|m| m.as_str()));
 
     Some(ColorData::new(r, g, b, a))
 }
@@ -200,13 +461,10 @@ fn parse_rgb(input: &str) -> Option<ColorData> {
 fn parse_hsl(input: &str) -> Option<ColorData> {
     let caps = HSL_REGEX.captures(input)?;
 
-    let h: f32 = caps.get(1)?.as_str().parse().ok()?;
-    let s: f32 = caps.get(2)?.as_str().parse().ok()?;
-    let l: f32 = caps.get(3)?.as_str().parse().ok()?;
-    let a: f32 = caps
-        .get(4)
-        .map(|m| m.as_str().parse().unwrap_or(1.0))
-        .unwrap_or(1.0);
+    let h = parse_hue(caps.get(1)?.as_str(), caps.get(2).map(|m| m.as_str()))?;
+    let s: f32 = caps.get(3)?.as_str().parse().ok()?;
+    let l: f32 = caps.get(4)?.as_str().parse().ok()?;
+    let a = parse_alpha(caps.get(5).map(|m| m.as_str()));
 
     let s = s / 100.0;
     let l = l / 100.0;
@@ -225,10 +483,7 @@ fn parse_oklch(input: &str) -> Option<ColorData> {
     let l: f32 = caps.get(1)?.as_str().parse().ok()?;
     let c: f32 = caps.get(2)?.as_str().parse().ok()?;
     let h: f32 = caps.get(3)?.as_str().parse().ok()?;
-    let a: f32 = caps
-        .get(4)
-        .map(|m| m.as_str().parse().unwrap_or(1.0))
-        .unwrap_or(1.0);
+    let a = parse_alpha(caps.get(4).map(|m| m.as_str()));
 
     let l = l / 100.0;
 
@@ -240,6 +495,58 @@ fn parse_oklch(input: &str) -> Option<ColorData> {
     Some(ColorData::new(r, g, b, a))
 }
 
+fn parse_lab(input: &str) -> Option<ColorData> {
+    let caps = LAB_REGEX.captures(input)?;
+
+    let l: f32 = caps.get(1)?.as_str().parse().ok()?;
+    let a: f32 = caps.get(2)?.as_str().parse().ok()?;
+    let b: f32 = caps.get(3)?.as_str().parse().ok()?;
+    let alpha = parse_alpha(caps.get(4).map(|m| m.as_str()));
+
+    if l > 100.0 {
+        return None;
+    }
+
+    let (r, g, b) = lab_to_rgb(l, a, b);
+    Some(ColorData::new(r, g, b, alpha))
+}
+
+fn parse_lch(input: &str) -> Option<ColorData> {
+    let caps = LCH_REGEX.captures(input)?;
+
+    let l: f32 = caps.get(1)?.as_str().parse().ok()?;
+    let c: f32 = caps.get(2)?.as_str().parse().ok()?;
+    let h: f32 = caps.get(3)?.as_str().parse().ok()?;
+    let alpha = parse_alpha(caps.get(4).map(|m| m.as_str()));
+
+    if l > 100.0 || h > 360.0 {
+        return None;
+    }
+
+    let (r, g, b) = lch_to_rgb(l, c, h);
+    Some(ColorData::new(r, g, b, alpha))
+}
+
+fn parse_display_p3(input: &str) -> Option<ColorData> {
+    let caps = DISPLAY_P3_REGEX.captures(input)?;
+
+    let channel = |index: usize| parse_unit_channel(caps.get(index)?.as_str());
+    let (r, g, b) = display_p3_to_rgb(channel(1)?, channel(2)?, channel(3)?);
+    let a = parse_alpha(caps.get(4).map(|m| m.as_str()));
+
+    Some(ColorData::new(r, g, b, a))
+}
+
+fn parse_cmyk(input: &str) -> Option<ColorData> {
+    let caps = CMYK_REGEX.captures(input)?;
+
+    let channel = |index: usize| parse_unit_channel(caps.get(index)?.as_str());
+    let (r, g, b) = cmyk_to_rgb(channel(1)?, channel(2)?, channel(3)?, channel(4)?);
+    let a = parse_alpha(caps.get(5).map(|m| m.as_str()));
+
+    Some(ColorData::new(r, g, b, a))
+}
+
 /// Convert HSL to RGB.
 pub fn hsl_to_rgb(h: f32, s: f32, l: f32) -> (u8, u8, u8) {
     if s == 0.0 {
@@ -317,6 +624,34 @@ pub fn rgb_to_hsl(r: u8, g: u8, b: u8) -> (f32, f32, f32) {
     (h * 60.0, s, l)
 }
 
+/// Convert HSV to RGB. Hue in degrees, saturation and value 0-1.
+pub fn hsv_to_rgb(h: f32, s: f32, v: f32) -> (u8, u8, u8) {
+    let chroma = v * s;
+    let sector = h.rem_euclid(360.0) / 60.0;
+    let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+    let (r, g, b) = match sector as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = v - chroma;
+    let channel = |c: f32| ((c + m) * 255.0).round().clamp(0.0, 255.0) as u8;
+    (channel(r), channel(g), channel(b))
+}
+
+/// Convert RGB to HSV. Hue in degrees, saturation and value 0-1.
+pub fn rgb_to_hsv(r: u8, g: u8, b: u8) -> (f32, f32, f32) {
+    // Hue is the same as in HSL
+    let (h, _, _) = rgb_to_hsl(r, g, b);
+    let max = r.max(g).max(b) as f32 / 255.0;
+    let min = r.min(g).min(b) as f32 / 255.0;
+    let s = if max > 0.0 { (max - min) / max } else { 0.0 };
+    (h, s, max)
+}
+
 // ============================================================================
 // OKLCH Color Space Support
 // ============================================================================
@@ -339,10 +674,10 @@ fn linear_to_srgb(c: f32) -> f32 {
     }
 }
 
-/// Convert RGB to OKLCH.
-/// Returns (L: 0-1, C: 0-0.4+, H: 0-360).
+/// Convert RGB to OKLab.
+/// Returns (L: 0-1, a, b), with a and b roughly within ±0.4.
 #[allow(clippy::excessive_precision)]
-pub fn rgb_to_oklch(r: u8, g: u8, b: u8) -> (f32, f32, f32) {
+pub fn rgb_to_oklab(r: u8, g: u8, b: u8) -> (f32, f32, f32) {
     let r_lin = srgb_to_linear(r as f32 / 255.0);
     let g_lin = srgb_to_linear(g as f32 / 255.0);
     let b_lin = srgb_to_linear(b as f32 / 255.0);
@@ -355,9 +690,17 @@ pub fn rgb_to_oklch(r: u8, g: u8, b: u8) -> (f32, f32, f32) {
     let m_ = m.cbrt();
     let s_ = s.cbrt();
 
-    let ok_l = 0.2104542553 * l_ + 0.7936177850 * m_ - 0.0040720468 * s_;
-    let ok_a = 1.9779984951 * l_ - 2.4285922050 * m_ + 0.4505937099 * s_;
-    let ok_b = 0.0259040371 * l_ + 0.7827717662 * m_ - 0.8086757660 * s_;
+    (
+        0.2104542553 * l_ + 0.7936177850 * m_ - 0.0040720468 * s_,
+        1.9779984951 * l_ - 2.4285922050 * m_ + 0.4505937099 * s_,
+        0.0259040371 * l_ + 0.7827717662 * m_ - 0.8086757660 * s_,
+    )
+}
+
+/// Convert RGB to OKLCH.
+/// Returns (L: 0-1, C: 0-0.4+, H: 0-360).
+pub fn rgb_to_oklch(r: u8, g: u8, b: u8) -> (f32, f32, f32) {
+    let (ok_l, ok_a, ok_b) = rgb_to_oklab(r, g, b);
 
     let c = (ok_a * ok_a + ok_b * ok_b).sqrt();
     let h = if c < 1e-8 {
@@ -377,8 +720,15 @@ pub fn rgb_to_oklch(r: u8, g: u8, b: u8) -> (f32, f32, f32) {
 
 /// Convert OKLCH to RGB.
 /// L: 0-1 (lightness), C: 0-0.4+ (chroma), H: 0-360 (hue).
-#[allow(clippy::excessive_precision)]
 pub fn oklch_to_rgb(l: f32, c: f32, h: f32) -> (u8, u8, u8) {
+    let [r, g, b] = oklch_to_linear_srgb(l, c, h)
+        .map(|c| (linear_to_srgb(c).clamp(0.0, 1.0) * 255.0).round() as u8);
+    (r, g, b)
+}
+
+/// Convert OKLCH to linear sRGB, without clamping colors outside sRGB.
+#[allow(clippy::excessive_precision)]
+fn oklch_to_linear_srgb(l: f32, c: f32, h: f32) -> [f32; 3] {
     let h_rad = h.to_radians();
     let ok_a = c * h_rad.cos();
     let ok_b = c * h_rad.sin();
@@ -395,15 +745,267 @@ pub fn oklch_to_rgb(l: f32, c: f32, h: f32) -> (u8, u8, u8) {
     let g_lin = -1.2684380046 * lms_l + 2.6097574011 * lms_m - 0.3413193965 * lms_s;
     let b_lin = -0.0041960863 * lms_l - 0.7034186147 * lms_m + 1.7076147010 * lms_s;
 
-    let r = linear_to_srgb(r_lin).clamp(0.0, 1.0);
-    let g = linear_to_srgb(g_lin).clamp(0.0, 1.0);
-    let b = linear_to_srgb(b_lin).clamp(0.0, 1.0);
+    [r_lin, g_lin, b_lin]
+}
 
-    (
-        (r * 255.0).round() as u8,
-        (g * 255.0).round() as u8,
-        (b * 255.0).round() as u8,
-    )
+// ============================================================================
+// Relative Adjustments
+// ============================================================================
+//
+// Small steps in OKLCH for deriving hover and active variants of a color:
+// lightness changes alone keep the hue, and temperature turns the hue toward
+// orange or blue without changing lightness or chroma.
+
+/// OKLCH lightness step of lighter and darker variants (5% of the 0-1 range).
+const ADJUST_LIGHTNESS_STEP: f32 = 0.05;
+/// OKLCH hue step of warmer and cooler variants, in degrees.
+const ADJUST_HUE_STEP: f32 = 10.0;
+/// OKLCH hues warmer and cooler variants turn toward (orange and blue).
+const WARM_HUE: f32 = 55.0;
+const COOL_HUE: f32 = 250.0;
+
+/// A relative change to a color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorAdjustment {
+    Lighter,
+    Darker,
+    Warmer,
+    Cooler,
+}
+
+impl ColorAdjustment {
+    pub const ALL: [ColorAdjustment; 4] = [
+        ColorAdjustment::Lighter,
+        ColorAdjustment::Darker,
+        ColorAdjustment::Warmer,
+        ColorAdjustment::Cooler,
+    ];
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ColorAdjustment::Lighter => "Lighter",
+            ColorAdjustment::Darker => "Darker",
+            ColorAdjustment::Warmer => "Warmer",
+            ColorAdjustment::Cooler => "Cooler",
+        }
+    }
+
+    /// Apply the adjustment to a color, keeping its alpha. Grays have no hue,
+    /// so they don't change temperature.
+    pub fn apply(&self, color: &ColorData) -> ColorData {
+        let (l, c, h) = rgb_to_oklch(color.r, color.g, color.b);
+        let (l, h) = match self {
+            ColorAdjustment::Lighter => ((l + ADJUST_LIGHTNESS_STEP).min(1.0), h),
+            ColorAdjustment::Darker => ((l - ADJUST_LIGHTNESS_STEP).max(0.0), h),
+            ColorAdjustment::Warmer => (l, turn_hue_toward(h, WARM_HUE)),
+            ColorAdjustment::Cooler => (l, turn_hue_toward(h, COOL_HUE)),
+        };
+        let (r, g, b) = oklch_to_rgb(l, c, h);
+        ColorData::new(r, g, b, color.a)
+    }
+}
+
+/// Turn a hue one step toward a target hue, the short way around.
+fn turn_hue_toward(hue: f32, target: f32) -> f32 {
+    let difference = (target - hue + 540.0).rem_euclid(360.0) - 180.0;
+    (hue + difference.clamp(-ADJUST_HUE_STEP, ADJUST_HUE_STEP)).rem_euclid(360.0)
+}
+
+// ============================================================================
+// CIELAB / LCH Color Space Support
+// ============================================================================
+//
+// Lab as in CSS Color 4: relative to the D50 white point, with sRGB
+// converted through XYZ and a Bradford adaptation from D65.
+
+/// Linear sRGB to XYZ (D50).
+#[allow(clippy::excessive_precision)]
+const LINEAR_SRGB_TO_XYZ_D50: [[f32; 3]; 3] = [
+    [0.436_065_74, 0.385_151_47, 0.143_078_45],
+    [0.222_493_19, 0.716_887_05, 0.060_619_79],
+    [0.013_923_9, 0.097_081_29, 0.714_099_36],
+];
+
+/// XYZ (D50) to linear sRGB.
+#[allow(clippy::excessive_precision)]
+const XYZ_D50_TO_LINEAR_SRGB: [[f32; 3]; 3] = [
+    [3.134_135_8, -1.617_385_7, -0.490_662_42],
+    [-0.978_795_46, 1.916_254_2, 0.033_442_984],
+    [0.071_955_41, -0.228_976_67, 1.405_386],
+];
+
+/// D50 reference white.
+const D50_WHITE: [f32; 3] = [0.964_295_7, 1.0, 0.825_104_6];
+
+/// CIE ε and κ, as exact ratios.
+const LAB_EPSILON: f32 = 216.0 / 24389.0;
+const LAB_KAPPA: f32 = 24389.0 / 27.0;
+
+fn mat3_mul(m: &[[f32; 3]; 3], v: [f32; 3]) -> [f32; 3] {
+    [
+        m[0][0] * v[0] + m[0][1] * v[1] + m[0][2] * v[2],
+        m[1][0] * v[0] + m[1][1] * v[1] + m[1][2] * v[2],
+        m[2][0] * v[0] + m[2][1] * v[1] + m[2][2] * v[2],
+    ]
+}
+
+/// Convert RGB to CIE Lab.
+/// Returns (L: 0-100, a, b), with a and b roughly within ±128.
+pub fn rgb_to_lab(r: u8, g: u8, b: u8) -> (f32, f32, f32) {
+    let linear = [r, g, b].map(|c| srgb_to_linear(c as f32 / 255.0));
+    let xyz = mat3_mul(&LINEAR_SRGB_TO_XYZ_D50, linear);
+
+    let f = |t: f32| {
+        if t > LAB_EPSILON {
+            t.cbrt()
+        } else {
+            (LAB_KAPPA * t + 16.0) / 116.0
+        }
+    };
+    let fx = f(xyz[0] / D50_WHITE[0]);
+    let fy = f(xyz[1] / D50_WHITE[1]);
+    let fz = f(xyz[2] / D50_WHITE[2]);
+
+    (116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz))
+}
+
+/// Convert CIE Lab to RGB, clamping colors outside sRGB.
+pub fn lab_to_rgb(l: f32, a: f32, b: f32) -> (u8, u8, u8) {
+    let fy = (l + 16.0) / 116.0;
+    let fx = a / 500.0 + fy;
+    let fz = fy - b / 200.0;
+
+    let f_inv = |f: f32| {
+        let cubed = f * f * f;
+        if cubed > LAB_EPSILON {
+            cubed
+        } else {
+            (116.0 * f - 16.0) / LAB_KAPPA
+        }
+    };
+    let y = if l > LAB_KAPPA * LAB_EPSILON {
+        fy * fy * fy
+    } else {
+        l / LAB_KAPPA
+    };
+    let xyz = [
+        f_inv(fx) * D50_WHITE[0],
+        y * D50_WHITE[1],
+        f_inv(fz) * D50_WHITE[2],
+    ];
+
+    let [r, g, b] = mat3_mul(&XYZ_D50_TO_LINEAR_SRGB, xyz)
+        .map(|c| (linear_to_srgb(c).clamp(0.0, 1.0) * 255.0).round() as u8);
+    (r, g, b)
+}
+
+/// Convert RGB to CIE LCH.
+/// Returns (L: 0-100, C: 0-150, H: 0-360).
+pub fn rgb_to_lch(r: u8, g: u8, b: u8) -> (f32, f32, f32) {
+    let (l, a, b) = rgb_to_lab(r, g, b);
+    let c = (a * a + b * b).sqrt();
+    // Hue is meaningless for grays; report 0 rather than noise
+    let h = if c < 1e-3 {
+        0.0
+    } else {
+        b.atan2(a).to_degrees().rem_euclid(360.0)
+    };
+    (l, c, h)
+}
+
+/// Convert CIE LCH to RGB, clamping colors outside sRGB.
+pub fn lch_to_rgb(l: f32, c: f32, h: f32) -> (u8, u8, u8) {
+    let h_rad = h.to_radians();
+    lab_to_rgb(l, c * h_rad.cos(), c * h_rad.sin())
+}
+
+// ============================================================================
+// CMYK Support
+// ============================================================================
+//
+// The naive device conversion, without an ink profile: good for handing
+// values to a designer, not for proofing print.
+
+/// Convert RGB to CMYK (each 0-1).
+pub fn rgb_to_cmyk(r: u8, g: u8, b: u8) -> (f32, f32, f32, f32) {
+    let [r, g, b] = [r, g, b].map(|c| c as f32 / 255.0);
+    let k = 1.0 - r.max(g).max(b);
+    if k >= 1.0 {
+        return (0.0, 0.0, 0.0, 1.0);
+    }
+    let ink = |c: f32| (1.0 - c - k) / (1.0 - k);
+    (ink(r), ink(g), ink(b), k)
+}
+
+/// Convert CMYK (each 0-1) to RGB.
+pub fn cmyk_to_rgb(c: f32, m: f32, y: f32, k: f32) -> (u8, u8, u8) {
+    let channel = |ink: f32| ((1.0 - ink) * (1.0 - k) * 255.0).round().clamp(0.0, 255.0) as u8;
+    (channel(c), channel(m), channel(y))
+}
+
+// ============================================================================
+// Display P3 Support
+// ============================================================================
+//
+// Display P3 shares sRGB's white point and transfer curve, so only the
+// primaries differ: one matrix each way between the linear spaces.
+
+/// Linear sRGB to linear Display P3.
+const LINEAR_SRGB_TO_P3: [[f32; 3]; 3] = [
+    [0.822_462, 0.177_538, 0.0],
+    [0.033_194_2, 0.966_805_8, 0.0],
+    [0.017_082_63, 0.072_397_44, 0.910_519_9],
+];
+
+/// Linear Display P3 to linear sRGB.
+const LINEAR_P3_TO_SRGB: [[f32; 3]; 3] = [
+    [1.224_940_2, -0.224_940_18, 0.0],
+    [-0.042_056_955, 1.042_057, 0.0],
+    [-0.019_637_555, -0.078_636_05, 1.098_273_6],
+];
+
+/// Slack for rounding when checking that channels are within 0-1.
+const GAMUT_EPSILON: f32 = 1e-4;
+
+/// The smallest gamut a color fits in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Gamut {
+    Srgb,
+    DisplayP3,
+    /// Outside Display P3 as well.
+    Wider,
+}
+
+/// Convert RGB to Display P3 channels (0-1, gamma encoded).
+pub fn rgb_to_display_p3(r: u8, g: u8, b: u8) -> (f32, f32, f32) {
+    let linear = [r, g, b].map(|c| srgb_to_linear(c as f32 / 255.0));
+    let [r, g, b] = mat3_mul(&LINEAR_SRGB_TO_P3, linear).map(|c| linear_to_srgb(c.max(0.0)));
+    (r, g, b)
+}
+
+/// Convert Display P3 channels (0-1) to RGB, clamping colors outside sRGB.
+pub fn display_p3_to_rgb(r: f32, g: f32, b: f32) -> (u8, u8, u8) {
+    let linear = [r, g, b].map(srgb_to_linear);
+    let [r, g, b] = mat3_mul(&LINEAR_P3_TO_SRGB, linear)
+        .map(|c| (linear_to_srgb(c).clamp(0.0, 1.0) * 255.0).round() as u8);
+    (r, g, b)
+}
+
+/// Find the smallest gamut an OKLCH color fits in.
+pub fn oklch_gamut(l: f32, c: f32, h: f32) -> Gamut {
+    let in_range = |channels: [f32; 3]| {
+        channels
+            .iter()
+            .all(|c| (-GAMUT_EPSILON..=1.0 + GAMUT_EPSILON).contains(c))
+    };
+    let srgb = oklch_to_linear_srgb(l, c, h);
+    if in_range(srgb) {
+        Gamut::Srgb
+    } else if in_range(mat3_mul(&LINEAR_SRGB_TO_P3, srgb)) {
+        Gamut::DisplayP3
+    } else {
+        Gamut::Wider
+    }
 }
 
 /// Extract all color values from a text string.
@@ -442,13 +1044,88 @@ pub fn extract_colors_from_text(text: &str) -> Vec<ColorData> {
         }
     }
 
+    // Try to find display-p3 colors
+    let p3_finder = Regex::new(r"(?i)\bcolor\s*\(\s*display-p3[^)]+\)")
+        .expect("Invalid display-p3 finder regex");
+    for cap in p3_finder.find_iter(text) {
+        if let Ok(color) = ColorData::parse(cap.as_str()) {
+            colors.push(color);
+        }
+    }
+
+    // Try to find cmyk colors
+    let cmyk_finder =
+        Regex::new(r"(?i)\b(?:device-)?cmyk\s*\([^)]+\)").expect("Invalid cmyk finder regex");
+    for cap in cmyk_finder.find_iter(text) {
+        if let Ok(color) = ColorData::parse(cap.as_str()) {
+            colors.push(color);
+        }
+    }
+
+    // Try to find CIE lab/lch colors
+    let lab_finder =
+        Regex::new(r"(?i)\b(?:lab|lch)\s*\([^)]+\)").expect("Invalid lab finder regex");
+    for cap in lab_finder.find_iter(text) {
+        if let Ok(color) = ColorData::parse(cap.as_str()) {
+            colors.push(color);
+        }
+    }
+
+    // Named colors count only as CSS values, so prose mentioning "red" or
+    // "tan" stays text
+    for cap in CSS_DECLARATION_REGEX.captures_iter(text) {
+        let value = &cap[1];
+        colors.extend(
+            value
+                .split(|c: char| c.is_whitespace() || c == ',' || c == '/')
+                .filter_map(named_color)
+                .map(|[r, g, b]| ColorData::new(r, g, b, 1.0)),
+        );
+    }
+
     colors
 }
 
+/// Find the color literals in a text, in order, with their byte ranges.
+pub fn find_color_literals(text: &str) -> Vec<(Range<usize>, ColorData)> {
+    COLOR_LITERAL_REGEX
+        .find_iter(text)
+        .filter_map(|m| Some((m.range(), ColorData::parse(m.as_str()).ok()?)))
+        .collect()
+}
+
+/// Count the distinct colors in RGBA pixel data, most frequent first.
+/// Fully transparent pixels are skipped and alpha is otherwise ignored.
+pub fn count_image_colors(rgba: &[u8]) -> Vec<(ColorData, usize)> {
+    let mut counts: HashMap<[u8; 3], usize> = HashMap::new();
+    for pixel in rgba.chunks_exact(4) {
+        if pixel[3] == 0 {
+            continue;
+        }
+        *counts.entry([pixel[0], pixel[1], pixel[2]]).or_default() += 1;
+    }
+
+    let mut colors: Vec<([u8; 3], usize)> = counts.into_iter().collect();
+    colors
+        .sort_by(|(a_rgb, a_count), (b_rgb, b_count)| b_count.cmp(a_count).then(a_rgb.cmp(b_rgb)));
+    colors
+        .into_iter()
+        .map(|([r, g, b], count)| (ColorData::new(r, g, b, 1.0), count))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_delta_e() {
+        let red = ColorData::new(255, 0, 0, 1.0);
+        assert!(red.delta_e(&red) < f32::EPSILON);
+        assert!(red.delta_e(&ColorData::new(254, 1, 0, 0.5)) < 1.0);
+        assert!(red.delta_e(&ColorData::new(0, 0, 255, 1.0)) > 50.0);
+    }
+
     #[test]
     fn test_parse_hex() {
         let color = ColorData::parse("#FF5733").unwrap();
@@ -496,6 +1173,55 @@ mod tests {
         assert!(color.r > 250);
     }
 
+    #[test]
+    fn test_parse_space_separated_rgb() {
+        let color = ColorData::parse("rgb(255 87 51 / 50%)").unwrap();
+        assert_eq!((color.r, color.g, color.b), (255, 87, 51));
+        assert!((color.a - 0.5).abs() < f32::EPSILON);
+
+        let color = ColorData::parse("rgb(100% 0% 50%)").unwrap();
+        assert_eq!((color.r, color.g, color.b), (255, 0, 128));
+        assert!(ColorData::parse("rgb(256 0 0)").is_err());
+        assert!(ColorData::parse("rgb(101% 0% 0%)").is_err());
+    }
+
+    #[test]
+    fn test_parse_space_separated_hsl() {
+        let legacy = ColorData::parse("hsl(11, 100%, 60%)").unwrap();
+        assert_eq!(ColorData::parse("hsl(11deg 100% 60%)").unwrap(), legacy);
+        assert_eq!(
+            ColorData::parse("hsl(0.0305556turn 100% 60%)").unwrap(),
+            legacy
+        );
+
+        let translucent = ColorData::parse("hsl(11 100% 60% / 0.25)").unwrap();
+        assert!((translucent.a - 0.25).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_extract_space_separated_colors() {
+        let colors =
+            extract_colors_from_text("color: rgb(0 128 255 / 80%); fill: hsl(120deg 50% 50%)");
+        assert_eq!(colors.len(), 2);
+        assert_eq!((colors[0].r, colors[0].g, colors[0].b), (0, 128, 255));
+    }
+
+    #[test]
+    fn test_parse_named_color() {
+        let color = ColorData::parse(" Coral ").unwrap();
+        assert_eq!((color.r, color.g, color.b), (255, 127, 80));
+        assert!(ColorData::parse("notacolor").is_err());
+    }
+
+    #[test]
+    fn test_extract_named_colors() {
+        let colors = extract_colors_from_text(".a { color: tomato; border: 1px solid slategray }");
+        assert_eq!(colors.len(), 2);
+        assert_eq!((colors[0].r, colors[0].g, colors[0].b), (255, 99, 71));
+        assert!(extract_colors_from_text("Buy red apples and a tan coat").is_empty());
+        assert!(extract_colors_from_text("a { color: var(--red); }").is_empty());
+    }
+
     #[test]
     fn test_to_hex() {
         let color = ColorData::new(255, 87, 51, 1.0);
@@ -508,6 +1234,73 @@ mod tests {
         assert_eq!(color.to_rgb(), "rgb(255, 87, 51)");
     }
 
+    #[test]
+    fn test_hsv_conversions() {
+        assert_eq!(rgb_to_hsv(255, 0, 0), (0.0, 1.0, 1.0));
+        assert_eq!(rgb_to_hsv(0, 0, 0), (0.0, 0.0, 0.0));
+        assert_eq!(hsv_to_rgb(120.0, 1.0, 0.5), (0, 128, 0));
+        assert_eq!(hsv_to_rgb(360.0, 0.0, 1.0), (255, 255, 255));
+        for (r, g, b) in [
+            (59, 130, 246),
+            (200, 180, 20),
+            (12, 34, 56),
+            (128, 128, 128),
+        ] {
+            let (h, s, v) = rgb_to_hsv(r, g, b);
+            assert_eq!(hsv_to_rgb(h, s, v), (r, g, b));
+        }
+
+        assert_eq!(
+            ColorData::new(59, 130, 246, 1.0).to_hsv(),
+            "hsv(217, 76%, 96%)"
+        );
+        assert_eq!(
+            ColorData::new(255, 0, 0, 0.5).to_hsv(),
+            "hsva(0, 100%, 100%, 0.50)"
+        );
+    }
+
+    #[test]
+    fn test_color_adjustments() {
+        let blue = ColorData::new(59, 130, 246, 0.5);
+        let (l, c, h) = rgb_to_oklch(blue.r, blue.g, blue.b);
+
+        let lighter = ColorAdjustment::Lighter.apply(&blue);
+        let (lighter_l, _, lighter_h) = rgb_to_oklch(lighter.r, lighter.g, lighter.b);
+        assert!((lighter_l - l - 0.05).abs() < 0.01);
+        assert!((lighter_h - h).abs() < 2.0);
+        assert_eq!(lighter.a, 0.5);
+        let darker = ColorAdjustment::Darker.apply(&blue);
+        assert!(rgb_to_oklch(darker.r, darker.g, darker.b).0 < l);
+
+        // Blue is already near the cool hue, so warming turns it the other way
+        let cooler = ColorAdjustment::Cooler.apply(&blue);
+        let (_, cooler_c, cooler_h) = rgb_to_oklch(cooler.r, cooler.g, cooler.b);
+        assert!((cooler_h - 250.0).abs() < (h - 250.0).abs());
+        assert!((cooler_c - c).abs() < 0.01);
+        let warmer = ColorAdjustment::Warmer.apply(&blue);
+        assert!(rgb_to_oklch(warmer.r, warmer.g, warmer.b).2 > h + 5.0);
+
+        // Grays have no hue to turn
+        let gray = ColorData::new(128, 128, 128, 1.0);
+        assert_eq!(ColorAdjustment::Warmer.apply(&gray), gray);
+        assert_eq!(
+            ColorAdjustment::Lighter
+                .apply(&ColorData::new(255, 255, 255, 1.0))
+                .r,
+            255
+        );
+    }
+
+    #[test]
+    fn test_turn_hue_toward() {
+        assert_eq!(turn_hue_toward(30.0, 55.0), 40.0);
+        assert_eq!(turn_hue_toward(50.0, 55.0), 55.0);
+        // The short way around passes 0°
+        assert_eq!(turn_hue_toward(355.0, 55.0), 5.0);
+        assert_eq!(turn_hue_toward(300.0, 250.0), 290.0);
+    }
+
     #[test]
     fn test_extract_colors() {
         let text = "Colors: #FF5733 and rgb(0, 128, 255) and hsl(120, 50%, 50%)";
@@ -515,6 +1308,17 @@ mod tests {
         assert_eq!(colors.len(), 3);
     }
 
+    #[test]
+    fn test_find_color_literals() {
+        let line = "color: #f00; border: 1px solid rgb(0 128 255 / 50%); id: #12345";
+        let found = find_color_literals(line);
+        assert_eq!(found.len(), 2);
+        assert_eq!(&line[found[0].0.clone()], "#f00");
+        assert_eq!(found[0].1, ColorData::new(255, 0, 0, 1.0));
+        assert_eq!(&line[found[1].0.clone()], "rgb(0 128 255 / 50%)");
+        assert!(find_color_literals("no colors here").is_empty());
+    }
+
     #[test]
     fn test_parse_oklch() {
         let color = ColorData::parse("oklch(70% 0.15 30)").unwrap();
@@ -559,4 +1363,189 @@ mod tests {
         let colors = extract_colors_from_text(text);
         assert_eq!(colors.len(), 2);
     }
+
+    #[test]
+    fn test_rgb_to_lab() {
+        // Reference values from CSS Color 4 (D50)
+        let (l, a, b) = rgb_to_lab(255, 0, 0);
+        assert!((l - 54.29).abs() < 0.05, "{}", l);
+        assert!((a - 80.80).abs() < 0.1, "{}", a);
+        assert!((b - 69.89).abs() < 0.1, "{}", b);
+
+        let (l, a, b) = rgb_to_lab(255, 255, 255);
+        assert!((l - 100.0).abs() < 0.01);
+        assert!(a.abs() < 0.01 && b.abs() < 0.01);
+    }
+
+    #[test]
+    fn test_lab_lch_roundtrip() {
+        for original in [
+            (128_u8, 64_u8, 192_u8),
+            (0, 0, 0),
+            (12, 200, 90),
+            (250, 250, 240),
+        ] {
+            let (l, a, b) = rgb_to_lab(original.0, original.1, original.2);
+            assert_eq!(lab_to_rgb(l, a, b), original);
+            let (l, c, h) = rgb_to_lch(original.0, original.1, original.2);
+            assert_eq!(lch_to_rgb(l, c, h), original);
+        }
+    }
+
+    #[test]
+    fn test_parse_lab_and_lch() {
+        let color = ColorData::parse("lab(54.29% 80.8 69.89)").unwrap();
+        assert_eq!((color.r, color.g, color.b), (255, 0, 0));
+        let color = ColorData::parse("lch(54.29% 106.84 40.85deg / 0.5)").unwrap();
+        assert_eq!((color.r, color.g, color.b), (255, 0, 0));
+        assert!((color.a - 0.5).abs() < f32::EPSILON);
+        assert!(ColorData::parse("lab(120% 0 0)").is_err());
+    }
+
+    #[test]
+    fn test_to_lab_and_lch() {
+        let color = ColorData::new(255, 0, 0, 1.0);
+        assert_eq!(color.to_lab(), "lab(54.29% 80.80 69.89)");
+        assert!(color.to_lch().starts_with("lch(54.29% 106.84 40.8"));
+        for output in [color.to_lab(), color.to_lch()] {
+            assert_eq!(ColorData::parse(&output).unwrap(), color);
+        }
+        assert!(ColorData::new(255, 0, 0, 0.5).to_lab().contains("/ 0.50"));
+    }
+
+    #[test]
+    fn test_extract_colors_with_lab() {
+        let text = "a: lab(54.29% 80.8 69.89); b: oklch(70% 0.15 30); c: lch(50% 30 120)";
+        assert_eq!(extract_colors_from_text(text).len(), 3);
+    }
+
+    #[test]
+    fn test_display_p3_roundtrip() {
+        // sRGB red in P3, from CSS Color 4
+        let (r, g, b) = rgb_to_display_p3(255, 0, 0);
+        assert!((r - 0.9175).abs() < 0.001, "{}", r);
+        assert!((g - 0.2003).abs() < 0.001, "{}", g);
+        assert!((b - 0.1386).abs() < 0.001, "{}", b);
+
+        for original in [
+            (128_u8, 64_u8, 192_u8),
+            (0, 0, 0),
+            (255, 255, 255),
+            (12, 200, 90),
+        ] {
+            let (r, g, b) = rgb_to_display_p3(original.0, original.1, original.2);
+            assert_eq!(display_p3_to_rgb(r, g, b), original);
+        }
+    }
+
+    #[test]
+    fn test_parse_display_p3() {
+        let color = ColorData::parse("color(display-p3 0.9175 0.2003 0.1386 / 50%)").unwrap();
+        assert_eq!((color.r, color.g, color.b), (255, 0, 0));
+        assert!((color.a - 0.5).abs() < f32::EPSILON);
+        // P3 red is outside sRGB and clamps to it
+        let color = ColorData::parse("color(display-p3 100% 0% 0%)").unwrap();
+        assert_eq!(color.r, 255);
+        assert!(ColorData::parse("color(display-p3 2 0 0)").is_err());
+
+        let color = ColorData::new(255, 0, 0, 1.0);
+        assert_eq!(ColorData::parse(&color.to_display_p3()).unwrap(), color);
+    }
+
+    #[test]
+    fn test_contrast_ratio() {
+        let black = ColorData::new(0, 0, 0, 1.0);
+        let white = ColorData::new(255, 255, 255, 1.0);
+        assert!((black.contrast_ratio(&white) - 21.0).abs() < 0.01);
+        assert!((white.contrast_ratio(&black) - 21.0).abs() < 0.01);
+        assert!((white.contrast_ratio(&white) - 1.0).abs() < f32::EPSILON);
+
+        // #777 on white just fails AA for body text
+        let gray = ColorData::new(0x77, 0x77, 0x77, 1.0);
+        assert!((gray.contrast_ratio(&white) - 4.48).abs() < 0.01);
+
+        // Half-transparent black on white reads as mid gray
+        let translucent = ColorData::new(0, 0, 0, 0.5);
+        assert_eq!(translucent.over(&white), ColorData::new(128, 128, 128, 1.0));
+        assert!(translucent.contrast_ratio(&white) < black.contrast_ratio(&white));
+    }
+
+    #[test]
+    fn test_cmyk_conversion() {
+        assert_eq!(rgb_to_cmyk(0, 0, 0), (0.0, 0.0, 0.0, 1.0));
+        assert_eq!(rgb_to_cmyk(255, 255, 255), (0.0, 0.0, 0.0, 0.0));
+        assert_eq!(
+            ColorData::new(255, 128, 0, 1.0).to_cmyk(),
+            "cmyk(0%, 50%, 100%, 0%)"
+        );
+
+        for original in [(128_u8, 64_u8, 192_u8), (12, 200, 90), (0, 0, 0)] {
+            let (c, m, y, k) = rgb_to_cmyk(original.0, original.1, original.2);
+            assert_eq!(cmyk_to_rgb(c, m, y, k), original);
+        }
+    }
+
+    #[test]
+    fn test_platform_literals() {
+        let blue = ColorData::new(59, 130, 246, 1.0);
+        assert_eq!(
+            blue.to_swiftui(),
+            "Color(red: 0.231, green: 0.510, blue: 0.965)"
+        );
+        assert_eq!(blue.to_argb_literal(), "Color(0xFF3B82F6)");
+        assert_eq!(
+            blue.to_iced_literal(),
+            "iced::Color::from_rgb(0.231, 0.510, 0.965)"
+        );
+
+        let translucent = ColorData::new(255, 0, 0, 0.5);
+        assert_eq!(
+            translucent.to_swiftui(),
+            "Color(red: 1.000, green: 0.000, blue: 0.000, opacity: 0.50)"
+        );
+        assert_eq!(translucent.to_argb_literal(), "Color(0x80FF0000)");
+        assert_eq!(
+            translucent.to_iced_literal(),
+            "iced::Color::from_rgba(1.000, 0.000, 0.000, 0.50)"
+        );
+    }
+
+    #[test]
+    fn test_parse_cmyk() {
+        let color = ColorData::parse("cmyk(0%, 50%, 100%, 0%)").unwrap();
+        assert_eq!((color.r, color.g, color.b), (255, 128, 0));
+        let color = ColorData::parse("device-cmyk(0 0 0 0.5 / 50%)").unwrap();
+        assert_eq!((color.r, color.g, color.b), (128, 128, 128));
+        assert!((color.a - 0.5).abs() < f32::EPSILON);
+        assert!(ColorData::parse("cmyk(0%, 150%, 0%, 0%)").is_err());
+        assert_eq!(
+            extract_colors_from_text("ink: cmyk(100%, 0%, 0%, 0%);"),
+            vec![ColorData::new(0, 255, 255, 1.0)]
+        );
+    }
+
+    #[test]
+    fn test_oklch_gamut() {
+        assert_eq!(oklch_gamut(0.6, 0.1, 30.0), Gamut::Srgb);
+        // More saturated than sRGB red, within P3
+        assert_eq!(oklch_gamut(0.64, 0.28, 29.0), Gamut::DisplayP3);
+        assert_eq!(oklch_gamut(0.6, 0.4, 150.0), Gamut::Wider);
+    }
+
+    #[test]
+    fn test_count_image_colors() {
+        let pixels = [
+            255, 0, 0, 255, // red
+            0, 0, 255, 255, // blue
+            255, 0, 0, 255, // red
+            0, 255, 0, 0, // transparent, skipped
+            255, 0, 0, 128, // red, alpha ignored
+        ];
+        let colors = count_image_colors(&pixels);
+        assert_eq!(colors.len(), 2);
+        assert_eq!(colors[0].0.to_hex(), "#FF0000");
+        assert_eq!(colors[0].1, 3);
+        assert_eq!(colors[1].0.to_hex(), "#0000FF");
+        assert_eq!(colors[1].1, 1);
+    }
 }
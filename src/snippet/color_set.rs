@@ -0,0 +1,64 @@
+//! Color set snippet data: several colors kept together as one scheme.
+
+use serde::{Deserialize, Serialize};
+
+use super::color::ColorData;
+
+/// An ordered set of colors, such as a five-color scheme.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ColorSetData {
+    pub colors: Vec<ColorData>,
+}
+
+impl ColorSetData {
+    /// Create new color set data.
+    pub fn new(colors: Vec<ColorData>) -> Self {
+        Self { colors }
+    }
+
+    /// Get the colors as hex values separated by commas.
+    pub fn to_hex_list(&self) -> String {
+        self.colors
+            .iter()
+            .map(ColorData::to_hex)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Serialize the colors for the database.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(&self.colors).unwrap_or_else(|_| "[]".to_string())
+    }
+
+    /// Parse colors stored with `to_json`; unreadable data gives an empty set.
+    pub fn from_json(json: &str) -> Self {
+        Self::new(serde_json::from_str(json).unwrap_or_default())
+    }
+}
+
+/// Get the labels of the colors a set is split into: the set's label
+/// numbered in order, e.g. "Sunset 1", "Sunset 2".
+pub fn split_labels(label: &str, count: usize) -> Vec<String> {
+    (1..=count).map(|n| format!("{} {}", label, n)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_color_set_round_trip() {
+        let set = ColorSetData::new(vec![
+            ColorData::new(255, 0, 0, 1.0),
+            ColorData::new(0, 128, 255, 0.5),
+        ]);
+        assert_eq!(ColorSetData::from_json(&set.to_json()), set);
+        assert_eq!(set.to_hex_list(), "#FF0000, #0080FF80");
+        assert!(ColorSetData::from_json("not json").colors.is_empty());
+    }
+
+    #[test]
+    fn test_split_labels() {
+        assert_eq!(split_labels("Sunset", 2), vec!["Sunset 1", "Sunset 2"]);
+    }
+}
@@ -0,0 +1,176 @@
+//! Line diffs between two versions of a snippet.
+
+/// One line of a diff.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffLine<'a> {
+    /// In both versions.
+    Same(&'a str),
+    /// Only in the old version.
+    Removed(&'a str),
+    /// Only in the new version.
+    Added(&'a str),
+}
+
+/// Largest LCS table built; bigger changes are shown as a full replacement.
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+/// Diff two texts line by line (longest common subsequence).
+pub fn diff_lines<'a>(old: &'a str, new: &'a str) -> Vec<DiffLine<'a>> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // Only the middle part between a common prefix and suffix needs the table
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+
+    let mut diff: Vec<DiffLine<'a>> = old[..prefix].iter().map(|l| DiffLine::Same(l)).collect();
+
+    if (old_mid.len() + 1) * (new_mid.len() + 1) > MAX_DIFF_CELLS {
+        diff.extend(old_mid.iter().map(|l| DiffLine::Removed(l)));
+        diff.extend(new_mid.iter().map(|l| DiffLine::Added(l)));
+    } else {
+        // lcs[i][j] = length of the LCS of old_mid[i..] and new_mid[j..]
+        let mut lcs = vec![vec![0usize; new_mid.len() + 1]; old_mid.len() + 1];
+        for i in (0..old_mid.len()).rev() {
+            for j in (0..new_mid.len()).rev() {
+                lcs[i][j] = if old_mid[i] == new_mid[j] {
+                    lcs[i + 1][j + 1] + 1
+                } else {
+                    lcs[i + 1][j].max(lcs[i][j + 1])
+                };
+            }
+        }
+
+        let (mut i, mut j) = (0, 0);
+        while i < old_mid.len() && j < new_mid.len() {
+            if old_mid[i] == new_mid[j] {
+                diff.push(DiffLine::Same(old_mid[i]));
+                i += 1;
+                j += 1;
+            } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+                diff.push(DiffLine::Removed(old_mid[i]));
+                i += 1;
+            } else {
+                diff.push(DiffLine::Added(new_mid[j]));
+                j += 1;
+            }
+        }
+        diff.extend(old_mid[i..].iter().map(|l| DiffLine::Removed(l)));
+        diff.extend(new_mid[j..].iter().map(|l| DiffLine::Added(l)));
+    }
+
+    diff.extend(old[old.len() - suffix..].iter().map(|l| DiffLine::Same(l)));
+    diff
+}
+
+/// Lay a diff out in two columns: unchanged lines on both sides, and each run
+/// of removed lines next to the added lines replacing it.
+pub fn side_by_side<'a>(
+    diff: &[DiffLine<'a>],
+) -> Vec<(Option<DiffLine<'a>>, Option<DiffLine<'a>>)> {
+    fn flush<'a>(
+        rows: &mut Vec<(Option<DiffLine<'a>>, Option<DiffLine<'a>>)>,
+        removed: &mut Vec<DiffLine<'a>>,
+        added: &mut Vec<DiffLine<'a>>,
+    ) {
+        let len = removed.len().max(added.len());
+        let mut left = removed.drain(..);
+        let mut right = added.drain(..);
+        for _ in 0..len {
+            rows.push((left.next(), right.next()));
+        }
+    }
+
+    let mut rows = Vec::new();
+    let mut removed = Vec::new();
+    let mut added = Vec::new();
+
+    for &line in diff {
+        match line {
+            DiffLine::Removed(_) => {
+                // A removal after additions starts a new change
+                if !added.is_empty() {
+                    flush(&mut rows, &mut removed, &mut added);
+                }
+                removed.push(line);
+            }
+            DiffLine::Added(_) => added.push(line),
+            DiffLine::Same(_) => {
+                flush(&mut rows, &mut removed, &mut added);
+                rows.push((Some(line), Some(line)));
+            }
+        }
+    }
+    flush(&mut rows, &mut removed, &mut added);
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_lines() {
+        let old = "fn main() {\n    println!(\"hi\");\n}";
+        let new = "fn main() {\n    let x = 1;\n    println!(\"{}\", x);\n}";
+
+        assert_eq!(
+            diff_lines(old, new),
+            vec![
+                DiffLine::Same("fn main() {"),
+                DiffLine::Removed("    println!(\"hi\");"),
+                DiffLine::Added("    let x = 1;"),
+                DiffLine::Added("    println!(\"{}\", x);"),
+                DiffLine::Same("}"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_lines_identical_and_empty() {
+        assert_eq!(
+            diff_lines("a\nb", "a\nb"),
+            vec![DiffLine::Same("a"), DiffLine::Same("b")]
+        );
+        assert_eq!(diff_lines("", "a"), vec![DiffLine::Added("a")]);
+        assert_eq!(diff_lines("a", ""), vec![DiffLine::Removed("a")]);
+    }
+
+    #[test]
+    fn test_side_by_side_pairs_changes() {
+        let diff = diff_lines("a\nb\nc\nd", "a\nB\nB2\nd\ne");
+        assert_eq!(
+            side_by_side(&diff),
+            vec![
+                (Some(DiffLine::Same("a")), Some(DiffLine::Same("a"))),
+                (Some(DiffLine::Removed("b")), Some(DiffLine::Added("B"))),
+                (Some(DiffLine::Removed("c")), Some(DiffLine::Added("B2"))),
+                (Some(DiffLine::Same("d")), Some(DiffLine::Same("d"))),
+                (None, Some(DiffLine::Added("e"))),
+            ]
+        );
+        assert!(side_by_side(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_diff_lines_keeps_unchanged_middle() {
+        let diff = diff_lines("a\nx\nb\nc", "a\nb\ny\nc");
+        assert_eq!(
+            diff,
+            vec![
+                DiffLine::Same("a"),
+                DiffLine::Removed("x"),
+                DiffLine::Same("b"),
+                DiffLine::Added("y"),
+                DiffLine::Same("c"),
+            ]
+        );
+    }
+}
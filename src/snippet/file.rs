@@ -0,0 +1,65 @@
+//! File attachment snippet data.
+
+use serde::{Deserialize, Serialize};
+
+/// A file copied into the data folder.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FileData {
+    /// Original file name, shown on the card.
+    pub name: String,
+    /// Path of the stored copy.
+    pub path: String,
+    /// Size in bytes.
+    pub size: u64,
+}
+
+impl FileData {
+    /// Create new file data.
+    pub fn new(name: String, path: String, size: u64) -> Self {
+        Self { name, path, size }
+    }
+
+    /// Get the lowercase extension of the file name, if it has one.
+    /// Dotfiles such as `.env` count as having no extension.
+    pub fn extension(&self) -> Option<String> {
+        let (stem, extension) = self.name.rsplit_once('.')?;
+        if stem.is_empty() || extension.is_empty() {
+            None
+        } else {
+            Some(extension.to_lowercase())
+        }
+    }
+}
+
+/// Get the name to store a copy of `name` under, prefixed with `id` so files
+/// with the same name do not overwrite each other.
+pub fn stored_file_name(id: &str, name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| if c == '/' || c == '\\' { '_' } else { c })
+        .collect();
+    format!("{}-{}", id, name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(name: &str) -> FileData {
+        FileData::new(name.to_string(), String::new(), 0)
+    }
+
+    #[test]
+    fn test_extension() {
+        assert_eq!(file("Report.PDF").extension().as_deref(), Some("pdf"));
+        assert_eq!(file("archive.tar.gz").extension().as_deref(), Some("gz"));
+        assert_eq!(file(".env").extension(), None);
+        assert_eq!(file("Makefile").extension(), None);
+    }
+
+    #[test]
+    fn test_stored_file_name() {
+        assert_eq!(stored_file_name("abc", "font.ttf"), "abc-font.ttf");
+        assert_eq!(stored_file_name("abc", "../x"), "abc-.._x");
+    }
+}
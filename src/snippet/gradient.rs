@@ -0,0 +1,186 @@
+//! Linear gradients: color stops along a line at an angle, written as CSS
+//! `linear-gradient()`.
+
+use serde::{Deserialize, Serialize};
+
+use super::color::ColorData;
+
+/// A color at a position along a gradient, from 0 (start) to 1 (end).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GradientStop {
+    pub color: ColorData,
+    pub position: f32,
+}
+
+/// A linear gradient, its stops kept in position order.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GradientData {
+    /// Direction in degrees, as in CSS: 0 points up, 90 to the right.
+    pub angle: f32,
+    pub stops: Vec<GradientStop>,
+}
+
+impl GradientData {
+    /// Create a left-to-right gradient between two colors.
+    pub fn new(from: ColorData, to: ColorData) -> Self {
+        Self {
+            angle: 90.0,
+            stops: vec![
+                GradientStop {
+                    color: from,
+                    position: 0.0,
+                },
+                GradientStop {
+                    color: to,
+                    position: 1.0,
+                },
+            ],
+        }
+    }
+
+    /// Get the color at a position, mixing the stops around it in sRGB as
+    /// browsers do by default.
+    pub fn color_at(&self, position: f32) -> ColorData {
+        let Some(first) = self.stops.first() else {
+            return ColorData::new(0, 0, 0, 0.0);
+        };
+        let Some(after) = self.stops.iter().position(|s| s.position > position) else {
+            return self.stops[self.stops.len() - 1].color.clone();
+        };
+        if after == 0 {
+            return first.color.clone();
+        }
+        let (from, to) = (&self.stops[after - 1], &self.stops[after]);
+        let t = (position - from.position) / (to.position - from.position).max(f32::EPSILON);
+        let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+        ColorData::new(
+            mix(from.color.r, to.color.r),
+            mix(from.color.g, to.color.g),
+            mix(from.color.b, to.color.b),
+            from.color.a + (to.color.a - from.color.a) * t,
+        )
+    }
+
+    /// Add a stop with the color the gradient already has there, returning
+    /// its index.
+    pub fn insert_stop(&mut self, position: f32) -> usize {
+        let position = position.clamp(0.0, 1.0);
+        let color = self.color_at(position);
+        let index = self.stops.partition_point(|s| s.position <= position);
+        self.stops.insert(index, GradientStop { color, position });
+        index
+    }
+
+    /// Move a stop, keeping the stops in order, returning its new index.
+    pub fn move_stop(&mut self, index: usize, position: f32) -> usize {
+        if index >= self.stops.len() {
+            return index;
+        }
+        let mut stop = self.stops.remove(index);
+        stop.position = position.clamp(0.0, 1.0);
+        let index = self.stops.partition_point(|s| s.position <= stop.position);
+        self.stops.insert(index, stop);
+        index
+    }
+
+    /// Remove a stop, keeping at least two.
+    pub fn remove_stop(&mut self, index: usize) -> bool {
+        if self.stops.len() <= 2 || index >= self.stops.len() {
+            return false;
+        }
+        self.stops.remove(index);
+        true
+    }
+
+    /// Serialize the gradient for the database.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_else(|_| "{}".to_string())
+    }
+
+    /// Parse a gradient stored with `to_json`; unreadable data gives a
+    /// transparent one.
+    pub fn from_json(json: &str) -> Self {
+        serde_json::from_str(json).unwrap_or_else(|_| {
+            let clear = ColorData::new(0, 0, 0, 0.0);
+            Self::new(clear.clone(), clear)
+        })
+    }
+
+    /// Write the gradient as a CSS `linear-gradient()`.
+    pub fn to_css(&self) -> String {
+        let stops: Vec<String> = self
+            .stops
+            .iter()
+            .map(|stop| {
+                format!(
+                    "{} {}%",
+                    stop.color.to_hex(),
+                    (stop.position * 100.0).round()
+                )
+            })
+            .collect();
+        format!(
+            "linear-gradient({}deg, {})",
+            self.angle.round().rem_euclid(360.0),
+            stops.join(", ")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn black_to_white() -> GradientData {
+        GradientData::new(
+            ColorData::new(0, 0, 0, 1.0),
+            ColorData::new(255, 255, 255, 1.0),
+        )
+    }
+
+    #[test]
+    fn test_color_at() {
+        let gradient = black_to_white();
+        assert_eq!(gradient.color_at(-1.0), ColorData::new(0, 0, 0, 1.0));
+        assert_eq!(gradient.color_at(0.5), ColorData::new(128, 128, 128, 1.0));
+        assert_eq!(gradient.color_at(2.0), ColorData::new(255, 255, 255, 1.0));
+    }
+
+    #[test]
+    fn test_edit_stops() {
+        let mut gradient = black_to_white();
+        assert_eq!(gradient.insert_stop(0.25), 1);
+        assert_eq!(gradient.stops[1].color, ColorData::new(64, 64, 64, 1.0));
+
+        // Dragging past a neighbour reorders the stops
+        assert_eq!(gradient.move_stop(1, 1.5), 2);
+        assert_eq!(gradient.stops[2].position, 1.0);
+
+        assert!(gradient.remove_stop(2));
+        assert!(!gradient.remove_stop(0));
+        assert_eq!(gradient.stops.len(), 2);
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let mut gradient = black_to_white();
+        gradient.angle = 135.0;
+        gradient.insert_stop(0.4);
+        assert_eq!(GradientData::from_json(&gradient.to_json()), gradient);
+
+        let fallback = GradientData::from_json("not json");
+        assert_eq!(fallback.stops.len(), 2);
+        assert_eq!(fallback.stops[0].color.a, 0.0);
+    }
+
+    #[test]
+    fn test_to_css() {
+        let mut gradient = black_to_white();
+        gradient.angle = -45.0;
+        gradient.stops[1].color.a = 0.5;
+        assert_eq!(
+            gradient.to_css(),
+            "linear-gradient(315deg, #000000 0%, #FFFFFF80 100%)"
+        );
+    }
+}
@@ -0,0 +1,296 @@
+//! Dominant colors of an image: PNG and JPEG decoding and median-cut
+//! quantization.
+
+use std::collections::HashMap;
+
+use zune_jpeg::zune_core::colorspace::ColorSpace;
+use zune_jpeg::zune_core::options::DecoderOptions;
+use zune_jpeg::JpegDecoder;
+
+use super::color::ColorData;
+
+/// Dominant colors extracted from an image file.
+pub const IMAGE_PALETTE_SIZE: usize = 12;
+
+/// Pixels less opaque than this are left out, so the background of a
+/// transparent logo or icon is not taken for one of its colors.
+const MIN_ALPHA: u8 = 128;
+
+/// Decode a PNG into (width, height, 8-bit RGBA rows).
+pub fn decode_png(bytes: &[u8]) -> Result<(u32, u32, Vec<u8>), String> {
+    let mut decoder = png::Decoder::new(std::io::Cursor::new(bytes));
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder
+        .read_info()
+        .map_err(|e| format!("PNG error: {}", e))?;
+    let size = reader
+        .output_buffer_size()
+        .ok_or_else(|| "The image is too large".to_string())?;
+    let mut buffer = vec![0; size];
+    let info = reader
+        .next_frame(&mut buffer)
+        .map_err(|e| format!("PNG error: {}", e))?;
+    buffer.truncate(info.buffer_size());
+
+    let rgba = match info.color_type {
+        png::ColorType::Rgba => buffer,
+        png::ColorType::Rgb => buffer
+            .chunks_exact(3)
+            .flat_map(|p| [p[0], p[1], p[2], 255])
+            .collect(),
+        png::ColorType::GrayscaleAlpha => buffer
+            .chunks_exact(2)
+            .flat_map(|p| [p[0], p[0], p[0], p[1]])
+            .collect(),
+        png::ColorType::Grayscale => buffer.iter().flat_map(|&v| [v, v, v, 255]).collect(),
+        png::ColorType::Indexed => return Err("Unexpected indexed PNG".to_string()),
+    };
+    Ok((info.width, info.height, rgba))
+}
+
+/// Decode a PNG or JPEG, told apart by their signatures, into (width,
+/// height, 8-bit RGBA rows).
+pub fn decode_image(bytes: &[u8]) -> Result<(u32, u32, Vec<u8>), String> {
+    if bytes.starts_with(b"\x89PNG") {
+        decode_png(bytes)
+    } else if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        decode_jpeg(bytes)
+    } else {
+        Err("Unsupported image format; use a PNG or JPEG file".to_string())
+    }
+}
+
+/// Decode a JPEG into (width, height, 8-bit RGBA rows).
+fn decode_jpeg(bytes: &[u8]) -> Result<(u32, u32, Vec<u8>), String> {
+    let options = DecoderOptions::default().jpeg_set_out_colorspace(ColorSpace::RGBA);
+    let mut decoder = JpegDecoder::new_with_options(bytes, options);
+    decoder
+        .decode_headers()
+        .map_err(|e| format!("JPEG error: {:?}", e))?;
+    let info = decoder
+        .info()
+        .ok_or_else(|| "JPEG error: no image size".to_string())?;
+    let size = decoder
+        .output_buffer_size()
+        .ok_or_else(|| "The image is too large".to_string())?;
+    let mut pixels = vec![0; size];
+    decoder
+        .decode_into(&mut pixels)
+        .map_err(|e| format!("JPEG error: {:?}", e))?;
+
+    // Grayscale images stay grayscale whatever output was asked for
+    let rgba = match decoder.get_output_colorspace() {
+        Some(ColorSpace::RGBA) => pixels,
+        Some(ColorSpace::Luma) => pixels.iter().flat_map(|&v| [v, v, v, 255]).collect(),
+        other => return Err(format!("Unexpected JPEG color space {:?}", other)),
+    };
+    Ok((u32::from(info.width), u32::from(info.height), rgba))
+}
+
+/// Reduce RGBA pixels to at most `max_colors` dominant colors with median
+/// cut, as (average color, pixel count), most common first.
+///
+/// The pixels start in one box; the box spanning the widest range of one
+/// channel is split at the median pixel along it until there are enough
+/// boxes, and each box becomes the average of its pixels.
+pub fn median_cut(rgba: &[u8], max_colors: usize) -> Vec<(ColorData, usize)> {
+    let mut histogram: HashMap<[u8; 3], usize> = HashMap::new();
+    for pixel in rgba.chunks_exact(4) {
+        if pixel[3] >= MIN_ALPHA {
+            *histogram.entry([pixel[0], pixel[1], pixel[2]]).or_default() += 1;
+        }
+    }
+    if histogram.is_empty() || max_colors == 0 {
+        return Vec::new();
+    }
+
+    let mut boxes: Vec<Vec<([u8; 3], usize)>> = vec![histogram.into_iter().collect()];
+    while boxes.len() < max_colors {
+        let widest = boxes
+            .iter()
+            .enumerate()
+            .filter(|(_, colors)| colors.len() > 1)
+            .map(|(index, colors)| (index, widest_channel(colors)))
+            .max_by_key(|(_, (_, range))| *range);
+        let Some((index, (channel, _))) = widest else {
+            // Every box is down to a single color
+            break;
+        };
+
+        let mut colors = boxes.swap_remove(index);
+        colors.sort_unstable_by_key(|(rgb, _)| (rgb[channel], *rgb));
+        let total: usize = colors.iter().map(|(_, count)| count).sum();
+        // The median pixel's color starts the upper half
+        let mut seen = 0;
+        let split = colors
+            .iter()
+            .position(|(_, count)| {
+                seen += count;
+                seen * 2 > total
+            })
+            .unwrap_or(1)
+            .clamp(1, colors.len() - 1);
+        let upper = colors.split_off(split);
+        boxes.push(colors);
+        boxes.push(upper);
+    }
+
+    let mut palette: Vec<(ColorData, usize)> = boxes.iter().map(|colors| average(colors)).collect();
+    palette.sort_by(|(a, a_count), (b, b_count)| {
+        b_count
+            .cmp(a_count)
+            .then((a.r, a.g, a.b).cmp(&(b.r, b.g, b.b)))
+    });
+    palette
+}
+
+/// Find the channel with the widest range of values, as (channel, range).
+fn widest_channel(colors: &[([u8; 3], usize)]) -> (usize, u8) {
+    (0..3)
+        .map(|channel| {
+            let values = colors.iter().map(|(rgb, _)| rgb[channel]);
+            let range = values.clone().max().unwrap_or(0) - values.min().unwrap_or(0);
+            (channel, range)
+        })
+        .max_by_key(|(_, range)| *range)
+        .unwrap_or((0, 0))
+}
+
+/// Average the colors of a box, weighted by pixel count.
+fn average(colors: &[([u8; 3], usize)]) -> (ColorData, usize) {
+    let total: usize = colors.iter().map(|(_, count)| count).sum();
+    let mut sums = [0usize; 3];
+    for (rgb, count) in colors {
+        for channel in 0..3 {
+            sums[channel] += rgb[channel] as usize * count;
+        }
+    }
+    let channel = |c: usize| ((sums[c] as f64 / total.max(1) as f64).round()) as u8;
+    (
+        ColorData::new(channel(0), channel(1), channel(2), 1.0),
+        total,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// RGBA pixels: `count` copies of each color.
+    fn pixels(colors: &[([u8; 4], usize)]) -> Vec<u8> {
+        colors
+            .iter()
+            .flat_map(|(rgba, count)| std::iter::repeat_n(*rgba, *count).flatten())
+            .collect()
+    }
+
+    #[test]
+    fn test_median_cut_keeps_distinct_colors() {
+        let rgba = pixels(&[
+            ([0, 0, 255, 255], 30),
+            ([255, 0, 0, 255], 60),
+            ([0, 255, 0, 255], 10),
+        ]);
+        let palette = median_cut(&rgba, 3);
+        assert_eq!(
+            palette,
+            vec![
+                (ColorData::new(255, 0, 0, 1.0), 60),
+                (ColorData::new(0, 0, 255, 1.0), 30),
+                (ColorData::new(0, 255, 0, 1.0), 10),
+            ]
+        );
+        // Asking for more colors than there are changes nothing
+        assert_eq!(median_cut(&rgba, 8), palette);
+    }
+
+    #[test]
+    fn test_median_cut_averages_similar_colors() {
+        let rgba = pixels(&[
+            ([250, 0, 0, 255], 50),
+            ([240, 10, 0, 255], 50),
+            ([0, 0, 200, 255], 20),
+        ]);
+        let palette = median_cut(&rgba, 2);
+        assert_eq!(
+            palette,
+            vec![
+                (ColorData::new(245, 5, 0, 1.0), 100),
+                (ColorData::new(0, 0, 200, 1.0), 20),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_median_cut_skips_transparent_pixels() {
+        let rgba = pixels(&[([255, 255, 255, 0], 500), ([10, 20, 30, 255], 5)]);
+        assert_eq!(
+            median_cut(&rgba, 4),
+            vec![(ColorData::new(10, 20, 30, 1.0), 5)]
+        );
+        assert!(median_cut(&[0, 0, 0, 0], 4).is_empty());
+    }
+
+    #[test]
+    fn test_decode_png() {
+        let mut bytes = Vec::new();
+        let mut encoder = png::Encoder::new(&mut bytes, 1, 2);
+        encoder.set_color(png::ColorType::GrayscaleAlpha);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().unwrap();
+        writer.write_image_data(&[100, 255, 200, 0]).unwrap();
+        writer.finish().unwrap();
+
+        let (width, height, rgba) = decode_png(&bytes).unwrap();
+        assert_eq!((width, height), (1, 2));
+        assert_eq!(rgba, vec![100, 100, 100, 255, 200, 200, 200, 0]);
+        assert!(decode_png(b"GIF89a").is_err());
+    }
+
+    /// An 8x8 baseline JPEG of one gray level: a DC coefficient per block
+    /// and nothing else, with quantization steps of 1.
+    fn gray_jpeg() -> Vec<u8> {
+        let mut bytes = vec![0xFF, 0xD8];
+        // Quantization table 0, all ones
+        bytes.extend([0xFF, 0xDB, 0x00, 0x43, 0x00]);
+        bytes.extend([1; 64]);
+        // Baseline frame: 8 bits, 8x8, one component using table 0
+        bytes.extend([0xFF, 0xC0, 0x00, 0x0B, 8, 0, 8, 0, 8, 1, 1, 0x11, 0]);
+        // DC table 0: one 1-bit code for a 10-bit difference
+        bytes.extend([0xFF, 0xC4, 0x00, 0x14, 0x00, 1]);
+        bytes.extend([0; 15]);
+        bytes.push(10);
+        // AC table 0: one 1-bit code for end of block
+        bytes.extend([0xFF, 0xC4, 0x00, 0x14, 0x10, 1]);
+        bytes.extend([0; 15]);
+        bytes.push(0);
+        // Scan: DC code, 576 = 8 * (200 - 128) in 10 bits, EOB, 1s padding
+        bytes.extend([0xFF, 0xDA, 0x00, 0x08, 1, 1, 0x00, 0, 63, 0]);
+        bytes.extend([0b0100_1000, 0b0000_1111]);
+        bytes.extend([0xFF, 0xD9]);
+        bytes
+    }
+
+    #[test]
+    fn test_decode_image() {
+        let (width, height, rgba) = decode_image(&gray_jpeg()).unwrap();
+        assert_eq!((width, height), (8, 8));
+        assert_eq!(rgba.len(), 8 * 8 * 4);
+        assert!(rgba.chunks_exact(4).all(|p| p == [200, 200, 200, 255]));
+        assert_eq!(
+            median_cut(&rgba, IMAGE_PALETTE_SIZE),
+            vec![(ColorData::new(200, 200, 200, 1.0), 64)]
+        );
+
+        let mut png = Vec::new();
+        let mut encoder = png::Encoder::new(&mut png, 1, 1);
+        encoder.set_color(png::ColorType::Rgb);
+        let mut writer = encoder.write_header().unwrap();
+        writer.write_image_data(&[1, 2, 3]).unwrap();
+        writer.finish().unwrap();
+        assert_eq!(decode_image(&png).unwrap(), (1, 1, vec![1, 2, 3, 255]));
+
+        assert!(decode_image(b"GIF89a").is_err());
+        assert!(decode_image(&gray_jpeg()[..40]).is_err());
+    }
+}
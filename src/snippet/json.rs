@@ -0,0 +1,220 @@
+//! JSON snippet data: validated documents with formatting and a tree view.
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Path of the document root in tree views.
+pub const JSON_ROOT_PATH: &str = "$";
+
+/// JSON snippet data. The document is kept as written; it is only
+/// reformatted by the pretty-print and minify actions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsonData {
+    pub json: String,
+}
+
+impl JsonData {
+    /// Create new JSON data.
+    pub fn new(json: String) -> Self {
+        Self { json }
+    }
+
+    /// Parse the document.
+    pub fn parse(&self) -> Result<Value, String> {
+        parse_json(&self.json)
+    }
+
+    /// Get the document indented, two spaces per level.
+    pub fn pretty(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(&self.parse()?).map_err(|e| e.to_string())
+    }
+
+    /// Get the document without any whitespace.
+    pub fn minified(&self) -> Result<String, String> {
+        serde_json::to_string(&self.parse()?).map_err(|e| e.to_string())
+    }
+
+    /// Describe the top-level value, e.g. "Object · 3 keys".
+    pub fn summary(&self) -> String {
+        match self.parse() {
+            Ok(Value::Object(map)) => format!("Object · {} keys", map.len()),
+            Ok(Value::Array(items)) => format!("Array · {} items", items.len()),
+            Ok(_) => "Value".to_string(),
+            Err(_) => "Invalid".to_string(),
+        }
+    }
+
+    /// Get the first N lines of the document for preview.
+    pub fn preview(&self, lines: usize) -> String {
+        self.json.lines().take(lines).collect::<Vec<_>>().join("\n")
+    }
+}
+
+/// How a JSON document is reformatted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsonFormat {
+    Pretty,
+    Minified,
+}
+
+impl JsonFormat {
+    /// Reformat a document; fails if it does not parse.
+    pub fn apply(self, data: &JsonData) -> Result<String, String> {
+        match self {
+            JsonFormat::Pretty => data.pretty(),
+            JsonFormat::Minified => data.minified(),
+        }
+    }
+}
+
+/// Parse JSON text, describing errors with their line and column.
+pub fn parse_json(json: &str) -> Result<Value, String> {
+    serde_json::from_str(json).map_err(|e| {
+        let message = e.to_string();
+        // serde_json appends " at line L column C" itself
+        let reason = message
+            .split(" at line ")
+            .next()
+            .unwrap_or(&message)
+            .to_string();
+        format!("Line {}, column {}: {}", e.line(), e.column(), reason)
+    })
+}
+
+/// Check if text is a JSON object or array, as copied from an API response
+/// or a config file. Bare values such as `42` are left to the other kinds.
+pub fn looks_like_json(text: &str) -> bool {
+    let trimmed = text.trim();
+    let bracketed = (trimmed.starts_with('{') && trimmed.ends_with('}'))
+        || (trimmed.starts_with('[') && trimmed.ends_with(']'));
+    bracketed && matches!(parse_json(trimmed), Ok(Value::Object(_) | Value::Array(_)))
+}
+
+/// One visible line of a JSON tree view.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonTreeRow {
+    /// Path of the value, e.g. `$.items[0].name`; collapsed nodes are kept by path.
+    pub path: String,
+    pub depth: usize,
+    /// Object key or array index; None for the root.
+    pub key: Option<String>,
+    /// A leaf as JSON, or the size of an object or array.
+    pub value: String,
+    /// Whether the value is a non-empty object or array.
+    pub expandable: bool,
+    pub expanded: bool,
+}
+
+/// Flatten a JSON value into tree rows, skipping the children of the
+/// nodes whose paths are in `collapsed`.
+pub fn json_tree_rows(value: &Value, collapsed: &HashSet<String>) -> Vec<JsonTreeRow> {
+    let mut rows = Vec::new();
+    push_tree_rows(
+        &mut rows,
+        value,
+        JSON_ROOT_PATH.to_string(),
+        None,
+        0,
+        collapsed,
+    );
+    rows
+}
+
+fn push_tree_rows(
+    rows: &mut Vec<JsonTreeRow>,
+    value: &Value,
+    path: String,
+    key: Option<String>,
+    depth: usize,
+    collapsed: &HashSet<String>,
+) {
+    let children: Vec<(String, String, &Value)> = match value {
+        Value::Object(map) => map
+            .iter()
+            .map(|(k, v)| (format!("{}.{}", path, k), k.clone(), v))
+            .collect(),
+        Value::Array(items) => items
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (format!("{}[{}]", path, i), i.to_string(), v))
+            .collect(),
+        _ => Vec::new(),
+    };
+    let summary = match value {
+        Value::Object(map) => format!("{{{}}}", map.len()),
+        Value::Array(items) => format!("[{}]", items.len()),
+        leaf => leaf.to_string(),
+    };
+    let expandable = !children.is_empty();
+    let expanded = expandable && !collapsed.contains(&path);
+
+    rows.push(JsonTreeRow {
+        path,
+        depth,
+        key,
+        value: summary,
+        expandable,
+        expanded,
+    });
+    if expanded {
+        for (child_path, child_key, child) in children {
+            push_tree_rows(
+                rows,
+                child,
+                child_path,
+                Some(child_key),
+                depth + 1,
+                collapsed,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_json() {
+        let data = JsonData::new("{ \"b\": [1, 2], \"a\": null }".to_string());
+        assert_eq!(data.minified().unwrap(), "{\"b\":[1,2],\"a\":null}");
+        assert_eq!(
+            data.pretty().unwrap(),
+            "{\n  \"b\": [\n    1,\n    2\n  ],\n  \"a\": null\n}"
+        );
+        assert_eq!(data.summary(), "Object · 2 keys");
+
+        let error = JsonData::new("{\n  \"a\": 1,\n}".to_string())
+            .parse()
+            .unwrap_err();
+        assert!(error.starts_with("Line 3, column 1: "), "{}", error);
+    }
+
+    #[test]
+    fn test_looks_like_json() {
+        assert!(looks_like_json("  [1, {\"a\": true}]\n"));
+        assert!(!looks_like_json("{ not: json }"));
+        assert!(!looks_like_json("42"));
+    }
+
+    #[test]
+    fn test_json_tree_rows() {
+        let value = parse_json("{\"name\": \"Shard\", \"tags\": [\"a\", \"b\"]}").unwrap();
+        let rows = json_tree_rows(&value, &HashSet::new());
+        let paths: Vec<&str> = rows.iter().map(|row| row.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec!["$", "$.name", "$.tags", "$.tags[0]", "$.tags[1]"]
+        );
+        assert_eq!(rows[1].value, "\"Shard\"");
+        assert_eq!(rows[2].value, "[2]");
+        assert_eq!(rows[3].depth, 2);
+
+        let collapsed = HashSet::from(["$.tags".to_string()]);
+        let rows = json_tree_rows(&value, &collapsed);
+        assert_eq!(rows.len(), 3);
+        assert!(rows[2].expandable && !rows[2].expanded);
+    }
+}
@@ -0,0 +1,632 @@
+//! Language detection for code snippets.
+//!
+//! Each language has weighted token rules; a snippet scores the weight of a
+//! rule for each match (up to a few), and the best scoring language wins.
+//! Shebangs and valid JSON decide on their own.
+
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// Only the start of long snippets is scored; JSON is parsed whole.
+const MAX_DETECT_BYTES: usize = 16 * 1024;
+
+/// Matches of one rule counted at most, so one repeated token can't decide.
+const MAX_RULE_HITS: usize = 3;
+
+/// Lowest score a language needs; below it the snippet is plain text.
+const MIN_SCORE: u32 = 4;
+
+/// Languages whose specific tokens also let them score the rules of another.
+const SUPERSETS: [(&str, &str); 2] = [("typescript", "javascript"), ("cpp", "c")];
+
+/// Token rules: language, pattern (multi-line mode), weight.
+/// On a tie the language listed first wins.
+const RULES: &[(&str, &str, u32)] = &[
+    // Rust
+    ("rust", r"^\s*(pub(\([\w:]+\))?\s+)?(async\s+)?fn\s+\w+", 4),
+    ("rust", r"\blet\s+mut\b", 4),
+    ("rust", r"^\s*impl(<.*>)?\s+[\w:<>, ]+\{", 4),
+    ("rust", r"#!?\[(derive|cfg|allow|test)\b", 5),
+    (
+        "rust",
+        r"\b(println|format|vec|panic|assert_eq|write)!\(",
+        4,
+    ),
+    ("rust", r"^\s*(pub\s+)?use\s+\w+(::[\w{}*, ]+)+;", 4),
+    ("rust", r"&(mut\s+)?self\b", 4),
+    ("rust", r"\b(Some|Ok|Err)\(", 2),
+    ("rust", r"^\s*(pub\s+)?(struct|enum|trait|mod)\s+\w+", 2),
+    ("rust", r"\.unwrap\(\)|\?;", 3),
+    // Python
+    (
+        "python",
+        r"^\s*(async\s+)?def\s+\w+\s*\(.*\)\s*(->\s*[^:]+)?:\s*$",
+        5,
+    ),
+    ("python", r"^\s*class\s+\w+(\(.*\))?:\s*$", 5),
+    ("python", r"^\s*from\s+[\w.]+\s+import\s", 5),
+    ("python", r"^\s*import\s+[\w.]+(\s+as\s+\w+)?\s*$", 2),
+    ("python", r"\bif\s+__name__\s*==", 5),
+    (
+        "python",
+        r"^\s*(if|elif|else|for|while|with|try|except|finally)\b.*:\s*$",
+        3,
+    ),
+    ("python", r"\bself\.\w+", 2),
+    ("python", r"\b(elif|True|False|None)\b", 2),
+    ("python", r"\bprint\(", 1),
+    ("python", r"\blambda\s+\w*:", 3),
+    // JavaScript (TypeScript scores these too)
+    ("javascript", r"\bfunction\s*\w*\s*\(", 3),
+    ("javascript", r"\b(const|let|var)\s+[\w{}\[\], ]+=", 2),
+    ("javascript", r"=>", 1),
+    ("javascript", r"\bconsole\.\w+\(", 5),
+    ("javascript", r#"\brequire\(['"]"#, 5),
+    ("javascript", r#"^\s*import\s+.+\s+from\s+['"]"#, 4),
+    (
+        "javascript",
+        r"^\s*export\s+(default|const|function|class|async)\b",
+        3,
+    ),
+    ("javascript", r"\b(document|window)\.\w+", 4),
+    ("javascript", r"===|!==", 2),
+    ("javascript", r"\.then\(|\basync\s+function\b|\bawait\s", 1),
+    // TypeScript
+    (
+        "typescript",
+        r":\s*(string|number|boolean|any|void|unknown|never)\b",
+        5,
+    ),
+    (
+        "typescript",
+        r"^\s*(export\s+)?interface\s+\w+(<.*>)?\s*(extends\s+[\w, <>]+)?\s*\{",
+        4,
+    ),
+    ("typescript", r"^\s*(export\s+)?type\s+\w+(<.*>)?\s*=", 5),
+    ("typescript", r"\b(private|public|readonly)\s+\w+\s*[?:]", 3),
+    (
+        "typescript",
+        r"\bas\s+(string|number|const|any|unknown)\b",
+        3,
+    ),
+    // HTML
+    ("html", r"(?i)<!DOCTYPE\s+html", 10),
+    (
+        "html",
+        r"(?i)</?(html|head|body|div|span|p|a|ul|ol|li|table|tr|td|form|input|button|section|nav|header|footer|h[1-6])\b[^>]*>",
+        3,
+    ),
+    ("html", r"(?i)<(script|style|link|meta|img|br)\b", 3),
+    ("html", r#"\b(class|href|src|id)=""#, 1),
+    // XML
+    ("xml", r"^\s*<\?xml\b", 10),
+    ("xml", r"</?\w+:\w+[\s>]", 3),
+    ("xml", r#"<[A-Za-z][\w.-]*(\s+[\w:-]+="[^"]*")*\s*/?>"#, 1),
+    // CSS
+    ("css", r"^\s*[\w.#:\-\[\]=*>+~, ]+\{\s*$", 1),
+    ("css", r"^\s*[a-z-]+\s*:\s*[^;{}]+;\s*$", 3),
+    ("css", r"@(media|keyframes|import|font-face|supports)\b", 5),
+    ("css", r"\d(px|em|rem|vh|vw)\b", 3),
+    ("css", r"#[0-9a-fA-F]{3,8}\b", 1),
+    ("css", r"!important", 4),
+    ("css", r"^\s*[.#][\w-]+", 1),
+    // SQL
+    ("sql", r"(?is)\bSELECT\b.+?\bFROM\b", 6),
+    (
+        "sql",
+        r"(?i)^\s*(INSERT\s+INTO|UPDATE\s+\w+\s+SET|DELETE\s+FROM|CREATE\s+(TABLE|INDEX|VIEW|UNIQUE)|ALTER\s+TABLE|DROP\s+(TABLE|INDEX))\b",
+        6,
+    ),
+    (
+        "sql",
+        r"(?i)\b(WHERE|INNER\s+JOIN|LEFT\s+JOIN|GROUP\s+BY|ORDER\s+BY|PRIMARY\s+KEY|VARCHAR|NOT\s+NULL)\b",
+        2,
+    ),
+    // Shell
+    ("shell", r"^\s*\$\s+\w+", 3),
+    (
+        "shell",
+        r"^\s*(sudo\s+)?(echo|cd|ls|mkdir|rm|cp|mv|grep|sed|awk|curl|wget|chmod|chown|apt(-get)?|brew|git|npm|cargo|docker|export|source|tar|ssh)\s",
+        2,
+    ),
+    ("shell", r"^\s*(if|while|until)\s+\[\[?\s", 4),
+    ("shell", r"^\s*(fi|esac|done)\s*$", 4),
+    (
+        "shell",
+        r"\|\s*(grep|awk|sed|xargs|sort|uniq|head|tail|wc|tr|cut)\b",
+        3,
+    ),
+    ("shell", r#""\$\{?\w+\}?"|\$\(\w+"#, 2),
+    ("shell", r"^\s*\w+=\S*$", 1),
+    // Go
+    ("go", r"^\s*package\s+\w+\s*$", 4),
+    ("go", r"^\s*func\s+\(\w+\s+\*?\w+\)\s*\w+\(", 6),
+    ("go", r"^\s*func\s+\w+\(", 3),
+    ("go", r":=", 3),
+    ("go", r"\bfmt\.\w+\(", 5),
+    ("go", r"^\s*import\s+\($", 4),
+    ("go", r"\btype\s+\w+\s+(struct|interface)\s*\{", 6),
+    ("go", r"\berr\s*!=\s*nil\b", 6),
+    ("go", r"\bchan\b|\bgo\s+func\b|\bdefer\s", 2),
+    // C (C++ scores these too)
+    ("c", r"^\s*#include\s*<\w+\.h>", 5),
+    ("c", r#"^\s*#include\s*"[\w/.]+\.h""#, 3),
+    ("c", r"^\s*#(define|ifndef|ifdef|endif|pragma)\b", 2),
+    (
+        "c",
+        r"\b(printf|scanf|malloc|calloc|free|fprintf|strcpy|strlen|memcpy)\s*\(",
+        3,
+    ),
+    ("c", r"\bint\s+main\s*\(", 3),
+    (
+        "c",
+        r"^\s*(static\s+)?(unsigned\s+|const\s+)?(int|char|void|float|double|long|short|size_t|struct\s+\w+)\s*\**\s*\w+\s*\([^)]*\)\s*\{?\s*$",
+        3,
+    ),
+    ("c", r"\bNULL\b", 2),
+    ("c", r"\btypedef\s+(struct|enum|union)\b", 5),
+    ("c", r"\w->\w", 1),
+    // C++
+    (
+        "cpp",
+        r"^\s*#include\s*<(iostream|vector|string|map|set|memory|algorithm|unordered_map|cstdio|cstdlib|cstring|fstream|sstream)>",
+        6,
+    ),
+    ("cpp", r"\bstd::\w+", 5),
+    ("cpp", r"\b(cout|cin|cerr|endl)\b", 4),
+    ("cpp", r"\btemplate\s*<", 5),
+    ("cpp", r"\bnamespace\s+\w+\s*\{|\busing\s+namespace\b", 5),
+    ("cpp", r"^\s*(public|private|protected):\s*$", 4),
+    ("cpp", r"\bnullptr\b|\bauto\s+\w+\s*=|\bconst\s+auto\b", 3),
+    (
+        "cpp",
+        r"\bclass\s+\w+\s*(:\s*(public|private)\s+\w+)?\s*\{",
+        2,
+    ),
+    // Java
+    (
+        "java",
+        r"^\s*(public|private|protected)\s+(static\s+)?(final\s+)?(abstract\s+)?(class|interface|enum|void|[\w<>\[\]]+)\s+\w+",
+        4,
+    ),
+    ("java", r"\bpublic\s+static\s+void\s+main\s*\(\s*String", 10),
+    ("java", r"\bSystem\.(out|err)\.print", 8),
+    ("java", r"^\s*import\s+javax?\.", 8),
+    ("java", r"^\s*import\s+(static\s+)?[\w.]+(\.\*)?;\s*$", 3),
+    ("java", r"^\s*package\s+[\w.]+;\s*$", 5),
+    ("java", r"@Override\b", 4),
+    ("java", r"\b(extends|implements)\s+\w+", 2),
+    ("java", r"\bString\[\]", 3),
+    ("java", r"\bthrows\s+\w+", 4),
+    ("java", r"\bfinal\s+\w+", 2),
+    // C#
+    ("csharp", r"^\s*using\s+System(\.[\w.]+)?;", 8),
+    ("csharp", r"^\s*using\s+[\w.]+;\s*$", 3),
+    ("csharp", r"^\s*namespace\s+[\w.]+\s*(;|\{)?\s*$", 3),
+    ("csharp", r"\bConsole\.(Write|WriteLine|ReadLine)\(", 8),
+    ("csharp", r"\{\s*get;\s*(private\s+)?(set;|init;)?\s*\}", 8),
+    ("csharp", r"\bvar\s+\w+\s*=\s*new\b", 2),
+    ("csharp", r"\bstring\[\]|\bstring\s+\w+\s*[=;,)]", 2),
+    ("csharp", r"\basync\s+Task\b|\bTask<", 5),
+    ("csharp", r"\bforeach\s*\(\s*var\b", 5),
+    (
+        "csharp",
+        r"\bpublic\s+(override|virtual|async|sealed|partial)\b",
+        3,
+    ),
+    // Ruby
+    ("ruby", r"^\s*def\s+(self\.)?\w+[?!]?(\s*\(.*\))?\s*$", 3),
+    ("ruby", r"^\s*end\s*$", 3),
+    ("ruby", r#"^\s*require(_relative)?\s+['"]"#, 4),
+    ("ruby", r"^\s*puts\s", 4),
+    ("ruby", r"\battr_(accessor|reader|writer)\b", 6),
+    ("ruby", r"\bdo\s*(\|[\w, ]+\|)?\s*$", 4),
+    ("ruby", r"\.each(_with_index)?\s*(do|\{)", 5),
+    (
+        "ruby",
+        r"^\s*(module|class)\s+[A-Z]\w*(\s*<\s*[\w:]+)?\s*$",
+        3,
+    ),
+    ("ruby", r"\b(unless|elsif)\b", 4),
+    ("ruby", r"[\s(,]:\w+\s*=>|\w+:\s+:\w+", 3),
+    ("ruby", r"@\w+\s*=", 2),
+    // PHP
+    ("php", r"<\?php", 15),
+    ("php", r"\$\w+\s*=", 2),
+    ("php", r"\$this->", 6),
+    ("php", r"\bfunction\s+\w+\s*\(\s*(\??\w+\s+)?\$", 5),
+    ("php", r"\bnamespace\s+[\w\\]+;", 5),
+    ("php", r"^\s*use\s+[\w\\]+\\[\w\\]+;", 5),
+    ("php", r"::class\b", 4),
+    ("php", r"\barray\(", 3),
+    ("php", r"\bforeach\s*\(\s*\$\w+\s+as\b", 8),
+    ("php", r"\$\w+->\w+", 3),
+    // Swift
+    (
+        "swift",
+        r"^\s*import\s+(UIKit|SwiftUI|Foundation|Combine|AppKit)\b",
+        8,
+    ),
+    ("swift", r"\bfunc\s+\w+\s*(<.*>)?\([^)]*:\s*[A-Z]\w*", 5),
+    ("swift", r"\b(let|var)\s+\w+\s*:\s*[A-Z]\w*[?!]?", 2),
+    ("swift", r"\bguard\s+(let|var)?\b", 6),
+    ("swift", r"\bif\s+let\s+\w+\s*=", 3),
+    ("swift", r"\bstruct\s+\w+\s*:\s*\w+", 4),
+    (
+        "swift",
+        r"@(State|Published|Binding|objc|IBOutlet|IBAction|MainActor|escaping)\b",
+        6,
+    ),
+    ("swift", r"\bvar\s+body\s*:\s*some\s+View", 10),
+    ("swift", r"\bprotocol\s+\w+", 5),
+    ("swift", r"\bextension\s+\w+", 4),
+    ("swift", r"\bfunc\s+\w+", 1),
+    // Kotlin
+    (
+        "kotlin",
+        r"^\s*((private|public|internal|override|suspend|inline|open)\s+)*fun\s+(<.*>\s*)?[\w.]+\s*\(",
+        6,
+    ),
+    ("kotlin", r"\bval\s+\w+", 3),
+    ("kotlin", r"\bprintln\(", 2),
+    ("kotlin", r"\bdata\s+class\b", 8),
+    ("kotlin", r"\bwhen\s*(\(.*\))?\s*\{", 5),
+    ("kotlin", r"\bcompanion\s+object\b", 8),
+    (
+        "kotlin",
+        r"^\s*import\s+(kotlin|kotlinx|android|androidx)\.",
+        8,
+    ),
+    ("kotlin", r"\bobject\s+\w+", 2),
+    ("kotlin", r"!!\.|\?:", 2),
+    // Scala
+    (
+        "scala",
+        r"\bdef\s+\w+(\[.*\])?\s*(\(.*\))?\s*(:\s*[\w\[\], ]+)?\s*=",
+        6,
+    ),
+    ("scala", r"\bobject\s+\w+(\s+extends\s+\w+)?\s*\{", 4),
+    ("scala", r"\bcase\s+class\b", 8),
+    ("scala", r"\bval\s+\w+", 3),
+    ("scala", r"\bimplicit\b", 6),
+    ("scala", r"^\s*import\s+scala\.", 8),
+    ("scala", r"\bcase\s+[\w(), ]+=>", 3),
+    ("scala", r"\bextends\s+App\b", 6),
+    ("scala", r"\bmatch\s*\{", 4),
+    ("scala", r"\bprintln\(", 2),
+    // YAML
+    ("yaml", r"^---\s*$", 3),
+    ("yaml", r"^\s*[\w.-]+:\s*$", 2),
+    ("yaml", r"^\s*[\w.-]+:\s+[^\s{;][^;{}]*$", 2),
+    ("yaml", r"^\s*-\s+[\w.-]+:\s", 4),
+    ("yaml", r#"^\s*-\s+[\w"'\[{]"#, 1),
+    // TOML
+    ("toml", r#"^\s*\[[\w.\-"]+\]\s*$"#, 5),
+    ("toml", r"^\s*\[\[[\w.\-]+\]\]\s*$", 8),
+    (
+        "toml",
+        r#"^\s*[\w.\-]+\s*=\s*("|'|\d|true\b|false\b|\[|\{)"#,
+        2,
+    ),
+    // Markdown
+    ("markdown", r"^#{1,6}\s+\S", 2),
+    ("markdown", r"\[[^\]]+\]\([^)]+\)", 5),
+    ("markdown", r"^\s*```", 5),
+    ("markdown", r"\*\*[^*\n]+\*\*|__[^_\n]+__", 3),
+    ("markdown", r"^\s*[-*+]\s+\S", 1),
+    ("markdown", r"^\s*\d+\.\s+\S", 1),
+    ("markdown", r"^>\s", 2),
+    ("markdown", r"`[^`\n]+`", 1),
+    // Lua
+    ("lua", r"^\s*local\s+\w+(\s*,\s*\w+)*\s*=", 5),
+    ("lua", r"^\s*local\s+function\b", 8),
+    ("lua", r"\bfunction\s+[\w.:]+\s*\([^)]*\)\s*$", 3),
+    ("lua", r"\bthen\s*$", 3),
+    ("lua", r"\belseif\b", 3),
+    ("lua", r"~=", 4),
+    ("lua", r#"\s\.\.\s*['"\w]"#, 2),
+    ("lua", r"--\[\[|^\s*--\s", 1),
+    ("lua", r"\b(i?pairs|setmetatable|tostring)\(", 6),
+    ("lua", r"^\s*end\s*$", 1),
+    // Perl
+    ("perl", r"^\s*use\s+(strict|warnings)\s*;", 10),
+    ("perl", r"\bmy\s+[$@%]\w+", 6),
+    ("perl", r"\$_\b|@_\b", 4),
+    ("perl", r"=~\s*[sm]?/", 4),
+    ("perl", r"\bsub\s+\w+\s*\{", 6),
+    ("perl", r"\bforeach\s+my\b", 6),
+    ("perl", r"\bdie\s", 2),
+    ("perl", r"^\s*package\s+[\w:]+;", 4),
+    ("perl", r"\bqw\(", 6),
+    // Haskell
+    ("haskell", r"^\s*module\s+[\w.]+(\s*\(.*\))?\s+where\b", 10),
+    ("haskell", r"^\s*import\s+(qualified\s+)?[A-Z][\w.]*", 3),
+    ("haskell", r"^\s*[a-z][\w']*\s+::\s+", 6),
+    ("haskell", r"\bwhere\s*$", 3),
+    ("haskell", r"\bputStrLn\b", 8),
+    ("haskell", r"^\s*data\s+\w+.*=", 5),
+    ("haskell", r"\bderiving\s*\(", 8),
+    ("haskell", r"^\s*instance\s+\w+", 6),
+    ("haskell", r"\b(Maybe|Just|Nothing)\b", 3),
+    ("haskell", r"<-", 1),
+    // R
+    ("r", r"\w\s*<-\s*", 4),
+    ("r", r"<-\s*function\s*\(", 8),
+    ("r", r"\blibrary\(\w+\)", 8),
+    (
+        "r",
+        r"\b(data\.frame|ggplot|read\.csv|aes|summary|nrow)\(",
+        6,
+    ),
+    ("r", r"\bc\(", 2),
+    ("r", r"\b(TRUE|FALSE|NA)\b", 3),
+    ("r", r"%>%|%in%", 6),
+];
+
+static COMPILED_RULES: LazyLock<Vec<(&'static str, Regex, u32)>> = LazyLock::new(|| {
+    RULES
+        .iter()
+        .map(|(language, pattern, weight)| {
+            let regex = Regex::new(&format!("(?m){}", pattern)).expect("Invalid language rule");
+            (*language, regex, *weight)
+        })
+        .collect()
+});
+
+/// Detect the programming language of a code snippet.
+pub fn detect_language(code: &str) -> String {
+    // JSON only parses whole, so it is checked before the input is cut
+    let trimmed = code.trim();
+    if (trimmed.starts_with('{') || trimmed.starts_with('['))
+        && serde_json::from_str::<serde_json::Value>(trimmed).is_ok()
+    {
+        return "json".to_string();
+    }
+
+    let mut end = code.len().min(MAX_DETECT_BYTES);
+    while !code.is_char_boundary(end) {
+        end -= 1;
+    }
+    let code = &code[..end];
+
+    if let Some(language) = shebang_language(code) {
+        return language.to_string();
+    }
+
+    let mut scores: Vec<(&str, u32)> = Vec::new();
+    for (language, regex, weight) in COMPILED_RULES.iter() {
+        let hits = regex.find_iter(code).take(MAX_RULE_HITS).count() as u32;
+        match scores.iter_mut().find(|(l, _)| l == language) {
+            Some((_, score)) => *score += hits * weight,
+            None => scores.push((language, hits * weight)),
+        }
+    }
+    for (superset, subset) in SUPERSETS {
+        let score_of = |language: &str| {
+            scores
+                .iter()
+                .find(|(l, _)| *l == language)
+                .map_or(0, |(_, score)| *score)
+        };
+        if score_of(superset) > 0 {
+            let extra = score_of(subset);
+            if let Some((_, score)) = scores.iter_mut().find(|(l, _)| *l == superset) {
+                *score += extra;
+            }
+        }
+    }
+
+    // First listed language wins a tie
+    let mut best: Option<(&str, u32)> = None;
+    for (language, score) in scores {
+        if score >= MIN_SCORE && best.is_none_or(|(_, top)| score > top) {
+            best = Some((language, score));
+        }
+    }
+    best.map_or("plain", |(language, _)| language).to_string()
+}
+
+/// Get the language named by a `#!` line's interpreter.
+pub fn shebang_language(code: &str) -> Option<&'static str> {
+    let line = code.trim_start().lines().next()?.strip_prefix("#!")?;
+    let mut parts = line.split_whitespace();
+    let mut program = parts.next()?.rsplit('/').next()?;
+    if program == "env" {
+        program = parts.find(|part| !part.starts_with('-'))?;
+    }
+    let program = program.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+    let language = match program {
+        "sh" | "bash" | "zsh" | "dash" | "ksh" | "fish" => "shell",
+        "python" => "python",
+        "node" | "nodejs" => "javascript",
+        "deno" | "ts-node" | "tsx" => "typescript",
+        "ruby" => "ruby",
+        "perl" => "perl",
+        "php" => "php",
+        "lua" | "luajit" => "lua",
+        "Rscript" => "r",
+        "runhaskell" | "runghc" => "haskell",
+        "kotlin" => "kotlin",
+        "scala" => "scala",
+        _ => return None,
+    };
+    Some(language)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLES: &[(&str, &str)] = &[
+        (
+            "rust",
+            "use std::collections::HashMap;\n\nfn count(words: &[&str]) -> HashMap<&str, usize> {\n    let mut counts = HashMap::new();\n    for word in words {\n        *counts.entry(*word).or_default() += 1;\n    }\n    counts\n}",
+        ),
+        (
+            "python",
+            "import os\n\ndef list_files(path):\n    for name in os.listdir(path):\n        if name.endswith('.py'):\n            print(name)",
+        ),
+        (
+            "javascript",
+            "const button = document.querySelector('#save');\nbutton.addEventListener('click', () => {\n  console.log('saved');\n});",
+        ),
+        (
+            "typescript",
+            "interface User {\n  id: number;\n  name: string;\n}\n\nexport function greet(user: User): string {\n  return `Hello, ${user.name}`;\n}",
+        ),
+        ("json", "[{\"id\": 1, \"tags\": [\"a\", \"b\"]}]"),
+        (
+            "html",
+            "<!DOCTYPE html>\n<html>\n  <body>\n    <div class=\"card\"><p>Hi</p></div>\n  </body>\n</html>",
+        ),
+        (
+            "xml",
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<project>\n  <modelVersion>4.0.0</modelVersion>\n</project>",
+        ),
+        (
+            "css",
+            ".card {\n  padding: 8px;\n  color: #333;\n  border-radius: 4px;\n}\n\n@media (max-width: 600px) {\n  .card { padding: 4px; }\n}",
+        ),
+        (
+            "sql",
+            "SELECT u.name, COUNT(o.id)\nFROM users u\nLEFT JOIN orders o ON o.user_id = u.id\nGROUP BY u.name\nORDER BY 2 DESC;",
+        ),
+        (
+            "shell",
+            "for f in *.log; do\n  if [ -s \"$f\" ]; then\n    gzip \"$f\"\n  fi\ndone\nls -la | grep gz",
+        ),
+        (
+            "go",
+            "package main\n\nimport \"fmt\"\n\nfunc main() {\n\tname := \"gopher\"\n\tfmt.Println(\"hi\", name)\n}",
+        ),
+        (
+            "c",
+            "#include <stdio.h>\n#include <stdlib.h>\n\nint main(void) {\n    char *buf = malloc(64);\n    if (buf == NULL) return 1;\n    printf(\"%s\\n\", buf);\n    free(buf);\n    return 0;\n}",
+        ),
+        (
+            "cpp",
+            "#include <iostream>\n#include <vector>\n\nint main() {\n    std::vector<int> xs{1, 2, 3};\n    for (auto x : xs) std::cout << x << std::endl;\n}",
+        ),
+        (
+            "java",
+            "package com.example;\n\nimport java.util.List;\n\npublic class Main {\n    public static void main(String[] args) {\n        System.out.println(\"Hello\");\n    }\n}",
+        ),
+        (
+            "csharp",
+            "using System;\n\nnamespace Demo\n{\n    public class Person\n    {\n        public string Name { get; set; }\n    }\n}",
+        ),
+        (
+            "ruby",
+            "class Greeter\n  attr_reader :name\n\n  def initialize(name)\n    @name = name\n  end\n\n  def greet\n    puts \"Hello #{name}\"\n  end\nend",
+        ),
+        (
+            "php",
+            "<?php\n\nfunction total(array $items): int {\n    $sum = 0;\n    foreach ($items as $item) {\n        $sum += $item->price;\n    }\n    return $sum;\n}",
+        ),
+        (
+            "swift",
+            "import SwiftUI\n\nstruct ContentView: View {\n    @State private var count = 0\n    var body: some View {\n        Text(\"\\(count)\")\n    }\n}",
+        ),
+        (
+            "kotlin",
+            "data class User(val id: Int, val name: String)\n\nfun main() {\n    val user = User(1, \"Ada\")\n    println(user.name)\n}",
+        ),
+        (
+            "scala",
+            "object Main extends App {\n  case class Point(x: Int, y: Int)\n  def norm(p: Point): Double = math.sqrt(p.x * p.x + p.y * p.y)\n  println(norm(Point(3, 4)))\n}",
+        ),
+        (
+            "yaml",
+            "version: 2\nservices:\n  web:\n    image: nginx:latest\n    ports:\n      - \"80:80\"\n  db:\n    image: postgres",
+        ),
+        (
+            "toml",
+            "[package]\nname = \"shard\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[dependencies]\nregex = \"1\"",
+        ),
+        (
+            "markdown",
+            "# Shard\n\nA **snippet** manager. See [the docs](https://example.com).\n\n- Colors\n- Code\n\n```sh\ncargo run\n```",
+        ),
+        (
+            "lua",
+            "local function greet(name)\n  if name ~= nil then\n    print(\"Hello \" .. name)\n  end\nend\n\nfor i, v in ipairs({1, 2}) do print(v) end",
+        ),
+        (
+            "perl",
+            "use strict;\nuse warnings;\n\nmy @names = qw(ada grace);\nforeach my $name (@names) {\n    print \"$name\\n\" if $name =~ /a/;\n}",
+        ),
+        (
+            "haskell",
+            "module Main where\n\nsquare :: Int -> Int\nsquare x = x * x\n\nmain :: IO ()\nmain = putStrLn (show (square 4))",
+        ),
+        (
+            "r",
+            "library(ggplot2)\n\ndf <- read.csv(\"data.csv\")\nsummary(df)\nggplot(df, aes(x = age)) + geom_histogram()",
+        ),
+    ];
+
+    #[test]
+    fn test_detect_samples() {
+        for (language, code) in SAMPLES {
+            assert_eq!(detect_language(code), *language, "sample:\n{}", code);
+        }
+        let languages: std::collections::HashSet<_> = SAMPLES.iter().map(|(l, _)| l).collect();
+        assert!(languages.len() >= 25);
+    }
+
+    #[test]
+    fn test_detect_shebang() {
+        assert_eq!(detect_language("#!/usr/bin/env python3\nx = 1"), "python");
+        assert_eq!(detect_language("#!/bin/bash\nx=1"), "shell");
+        assert_eq!(
+            detect_language("#!/usr/bin/env -S node --harmony\n1"),
+            "javascript"
+        );
+        assert_eq!(detect_language("#!/usr/bin/perl -w\n1"), "perl");
+    }
+
+    #[test]
+    fn test_detect_long_json() {
+        let items: Vec<String> = (0..2000)
+            .map(|i| format!("{{\"id\": {}, \"name\": \"item\"}}", i))
+            .collect();
+        let json = format!("[{}]", items.join(",\n"));
+        assert!(json.len() > MAX_DETECT_BYTES);
+        assert_eq!(detect_language(&json), "json");
+    }
+
+    #[test]
+    fn test_detect_plain() {
+        assert_eq!(detect_language("Remember to buy milk"), "plain");
+        assert_eq!(detect_language(""), "plain");
+        assert_eq!(
+            detect_language("Meeting notes\nWe agreed to ship on Friday."),
+            "plain"
+        );
+    }
+
+    #[test]
+    fn test_detect_confusable_configs() {
+        // YAML and TOML without headers, C without includes, Java without main
+        assert_eq!(
+            detect_language("name: shard\nenabled: true\nitems:\n  - one\n  - two"),
+            "yaml"
+        );
+        assert_eq!(
+            detect_language("title = \"Shard\"\ncount = 3\nenabled = true"),
+            "toml"
+        );
+        assert_eq!(
+            detect_language(
+                "static int add(int a, int b) {\n    return a + b;\n}\ntypedef struct node { int v; } node;"
+            ),
+            "c"
+        );
+        assert_eq!(
+            detect_language(
+                "public interface Shape {\n    double area();\n}\n\n@Override\npublic String toString() {\n    return \"shape\";\n}"
+            ),
+            "java"
+        );
+    }
+}
@@ -1,30 +1,80 @@
 //! Unified snippet module for managing different content types.
 //!
-//! Supports three snippet kinds:
+//! Supports seven snippet kinds:
 //! - **Color**: Color values with RGBA components
+//! - **Color set**: Several colors kept together as one scheme
+//! - **Gradient**: Linear gradients, copied as CSS
 //! - **Code**: Code snippets with syntax highlighting
+//! - **JSON**: Validated JSON documents with a tree view
 //! - **Text**: Plain text notes
+//! - **File**: Files copied into the data folder
+
+use std::collections::HashMap;
 
 use nanoid::nanoid;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
+mod asset_catalog;
+mod bookmarks;
 mod code;
+mod code_snippets;
 mod color;
+mod color_set;
+mod diff;
+mod file;
+mod gradient;
+mod image_palette;
+mod json;
+mod language;
+mod named_colors;
+mod palette_file;
+mod query;
+mod scale;
+mod secret;
+mod template;
 mod text;
+mod whitespace;
 
-pub use code::{detect_language, language_to_extension, CodeData};
+pub use asset_catalog::{android_colors_xml, pair_variants, xcassets_files, AssetColor};
+pub use bookmarks::parse_bookmarks_html;
+pub use code::{
+    can_reformat, export_file_name, extension_to_language, language_to_extension, reformat_code,
+    sanitize_file_stem, CodeData, LANGUAGES,
+};
+pub use code_snippets::{code_snippets_file, CODE_SNIPPETS_EXTENSION};
 pub use color::{
-    extract_colors_from_text, hsl_to_rgb, oklch_to_rgb, rgb_to_hsl, rgb_to_oklch, ColorData,
+    count_image_colors, extract_colors_from_text, find_color_literals, hsl_to_rgb, hsv_to_rgb,
+    oklch_gamut, oklch_to_rgb, rgb_to_hsl, rgb_to_hsv, rgb_to_oklch, ColorAdjustment, ColorData,
+    Gamut,
 };
+pub use color_set::{split_labels, ColorSetData};
+pub use diff::{diff_lines, side_by_side, DiffLine};
+pub use file::{stored_file_name, FileData};
+pub use gradient::GradientData;
+pub use image_palette::{decode_image, decode_png, median_cut, IMAGE_PALETTE_SIZE};
+pub use json::{json_tree_rows, looks_like_json, parse_json, JsonData, JsonFormat};
+pub use language::{detect_language, shebang_language};
+pub use named_colors::nearest_named_color;
+pub use palette_file::{PaletteFile, PALETTE_FILE_EXTENSION};
+pub use query::PaletteQuery;
+pub use scale::{color_scale, scale_step_names, SCALE_SIZES};
+pub use secret::{find_secrets, redact_secrets};
+pub use template::{fill_placeholders, find_placeholders, Placeholder};
 pub use text::TextData;
+pub use whitespace::WhitespaceTransform;
 
 /// The type of snippet.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum SnippetKind {
     Color,
+    ColorSet,
+    Gradient,
     Code,
+    Json,
     Text,
+    File,
 }
 
 impl SnippetKind {
@@ -32,8 +82,12 @@ impl SnippetKind {
     pub fn display_name(&self) -> &'static str {
         match self {
             SnippetKind::Color => "Color",
+            SnippetKind::ColorSet => "Color Set",
+            SnippetKind::Gradient => "Gradient",
             SnippetKind::Code => "Code",
+            SnippetKind::Json => "JSON",
             SnippetKind::Text => "Text",
+            SnippetKind::File => "File",
         }
     }
 
@@ -41,8 +95,12 @@ impl SnippetKind {
     pub fn as_db_str(&self) -> &'static str {
         match self {
             SnippetKind::Color => "color",
+            SnippetKind::ColorSet => "colorset",
+            SnippetKind::Gradient => "gradient",
             SnippetKind::Code => "code",
+            SnippetKind::Json => "json",
             SnippetKind::Text => "text",
+            SnippetKind::File => "file",
         }
     }
 
@@ -50,8 +108,12 @@ impl SnippetKind {
     pub fn from_db_str(s: &str) -> Option<Self> {
         match s {
             "color" => Some(SnippetKind::Color),
+            "colorset" => Some(SnippetKind::ColorSet),
+            "gradient" => Some(SnippetKind::Gradient),
             "code" => Some(SnippetKind::Code),
+            "json" => Some(SnippetKind::Json),
             "text" => Some(SnippetKind::Text),
+            "file" => Some(SnippetKind::File),
             _ => None,
         }
     }
@@ -62,8 +124,12 @@ impl SnippetKind {
 #[serde(tag = "type", rename_all = "lowercase")]
 pub enum SnippetContent {
     Color(ColorData),
+    ColorSet(ColorSetData),
+    Gradient(GradientData),
     Code(CodeData),
+    Json(JsonData),
     Text(TextData),
+    File(FileData),
 }
 
 impl SnippetContent {
@@ -71,8 +137,12 @@ impl SnippetContent {
     pub fn kind(&self) -> SnippetKind {
         match self {
             SnippetContent::Color(_) => SnippetKind::Color,
+            SnippetContent::ColorSet(_) => SnippetKind::ColorSet,
+            SnippetContent::Gradient(_) => SnippetKind::Gradient,
             SnippetContent::Code(_) => SnippetKind::Code,
+            SnippetContent::Json(_) => SnippetKind::Json,
             SnippetContent::Text(_) => SnippetKind::Text,
+            SnippetContent::File(_) => SnippetKind::File,
         }
     }
 
@@ -80,35 +150,177 @@ impl SnippetContent {
     pub fn preview(&self, max_len: usize) -> String {
         match self {
             SnippetContent::Color(c) => c.to_hex(),
-            SnippetContent::Code(c) => {
-                let first_line = c.code.lines().next().unwrap_or("");
-                if first_line.len() > max_len {
-                    format!("{}...", &first_line[..max_len])
-                } else {
-                    first_line.to_string()
-                }
-            }
-            SnippetContent::Text(t) => {
-                let first_line = t.text.lines().next().unwrap_or("");
-                if first_line.len() > max_len {
-                    format!("{}...", &first_line[..max_len])
-                } else {
-                    first_line.to_string()
-                }
-            }
+            SnippetContent::ColorSet(set) => set.to_hex_list(),
+            SnippetContent::Gradient(g) => g.to_css(),
+            SnippetContent::Code(c) => preview_first_line(&c.code, max_len),
+            SnippetContent::Json(j) => preview_first_line(&j.json, max_len),
+            SnippetContent::Text(t) => preview_first_line(&t.text, max_len),
+            SnippetContent::File(f) => f.name.clone(),
+        }
+    }
+
+    /// Get the text copied as Markdown: code and JSON in fenced blocks, text
+    /// as a blockquote when `quote_text` is set.
+    pub fn to_markdown(&self, quote_text: bool) -> String {
+        match self {
+            SnippetContent::Code(c) => fenced_code_block(&c.code, &c.language),
+            SnippetContent::Json(j) => fenced_code_block(&j.json, "json"),
+            SnippetContent::Text(t) if quote_text => markdown_blockquote(&t.text),
+            content => content.to_copyable_string(),
         }
     }
 
-    /// Get the copyable text representation.
+    /// Get the copyable text representation (the stored path for files).
     pub fn to_copyable_string(&self) -> String {
         match self {
             SnippetContent::Color(c) => c.to_hex(),
+            SnippetContent::ColorSet(set) => set.to_hex_list(),
+            SnippetContent::Gradient(g) => g.to_css(),
             SnippetContent::Code(c) => c.code.clone(),
+            SnippetContent::Json(j) => j.json.clone(),
             SnippetContent::Text(t) => t.text.clone(),
+            SnippetContent::File(f) => f.path.clone(),
         }
     }
 }
 
+/// Get the first line of some text, cut to `max_len` characters.
+fn preview_first_line(text: &str, max_len: usize) -> String {
+    let first_line = text.lines().next().unwrap_or("");
+    if first_line.chars().count() > max_len {
+        let cut: String = first_line.chars().take(max_len).collect();
+        format!("{}...", cut)
+    } else {
+        first_line.to_string()
+    }
+}
+
+/// Fence code as a Markdown code block tagged with its language. The fence is
+/// longer than any run of backticks in the code.
+fn fenced_code_block(code: &str, language: &str) -> String {
+    let longest_run = code.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    let fence = "`".repeat(longest_run.max(2) + 1);
+    let tag = if language == "plain" { "" } else { language };
+    format!("{fence}{tag}\n{}\n{fence}", code.trim_end())
+}
+
+/// Quote text as a Markdown blockquote.
+fn markdown_blockquote(text: &str) -> String {
+    text.trim_end()
+        .lines()
+        .map(|line| {
+            if line.trim().is_empty() {
+                ">".to_string()
+            } else {
+                format!("> {}", line)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Join snippets into one text, in the given order. Code and JSON are
+/// fenced as Markdown code blocks tagged with their language.
+pub fn combined_copy_text(snippets: &[&Snippet], separator: &str) -> String {
+    snippets
+        .iter()
+        .map(|snippet| match &snippet.content {
+            SnippetContent::Text(_) => snippet.content.to_copyable_string(),
+            content => content.to_markdown(false),
+        })
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+/// Get the current time as seconds since the Unix epoch.
+pub fn unix_now() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+/// Describe how long ago a timestamp was, e.g. "5m ago" (both in Unix seconds).
+pub fn relative_time(timestamp: i64, now: i64) -> String {
+    const MINUTE: i64 = 60;
+    const HOUR: i64 = 60 * MINUTE;
+    const DAY: i64 = 24 * HOUR;
+    const MONTH: i64 = 30 * DAY;
+    const YEAR: i64 = 365 * DAY;
+
+    let elapsed = (now - timestamp).max(0);
+    match elapsed {
+        e if e < MINUTE => "just now".to_string(),
+        e if e < HOUR => format!("{}m ago", e / MINUTE),
+        e if e < DAY => format!("{}h ago", e / HOUR),
+        e if e < MONTH => format!("{}d ago", e / DAY),
+        e if e < YEAR => format!("{}mo ago", e / MONTH),
+        e => format!("{}y ago", e / YEAR),
+    }
+}
+
+/// Get a short SHA-256 fingerprint (first 12 hex digits) of some text.
+pub fn short_hash(text: &str) -> String {
+    Sha256::digest(text.as_bytes())
+        .iter()
+        .take(6)
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Check if a linked file was edited or removed since `hash` was taken of it.
+pub fn linked_file_changed(path: &std::path::Path, hash: &str) -> bool {
+    std::fs::read_to_string(path).map_or(true, |contents| short_hash(&contents) != hash)
+}
+
+/// Prefix of snippet icons that name a Phosphor icon instead of holding an emoji.
+pub const PHOSPHOR_ICON_PREFIX: &str = "ph:";
+
+/// Longest emoji sequence kept as a snippet icon, in chars.
+/// ZWJ sequences such as flags and families take several chars.
+const MAX_ICON_CHARS: usize = 12;
+
+/// Clean up a snippet icon entered by the user.
+/// Returns None for blank input, which removes the icon.
+pub fn normalize_icon(input: &str) -> Option<String> {
+    let icon = input.split_whitespace().next()?;
+    if icon.starts_with(PHOSPHOR_ICON_PREFIX) {
+        Some(icon.to_string())
+    } else {
+        Some(icon.chars().take(MAX_ICON_CHARS).collect())
+    }
+}
+
+/// Clean up a snippet description entered by the user.
+/// Returns None for blank input, which removes the description.
+pub fn normalize_description(input: &str) -> Option<String> {
+    let description = input.trim();
+    (!description.is_empty()).then(|| description.to_string())
+}
+
+/// Longest text-expander abbreviation, in chars.
+pub const MAX_ABBREVIATION_CHARS: usize = 24;
+
+/// Check a text-expander abbreviation entered by the user.
+/// Returns None for blank input, which removes the abbreviation.
+pub fn normalize_abbreviation(input: &str) -> Result<Option<String>, String> {
+    let abbreviation = input.trim();
+    if abbreviation.is_empty() {
+        return Ok(None);
+    }
+    if abbreviation.chars().count() < 2 {
+        return Err("Use at least 2 characters".to_string());
+    }
+    if abbreviation.chars().count() > MAX_ABBREVIATION_CHARS {
+        return Err(format!("Use at most {} characters", MAX_ABBREVIATION_CHARS));
+    }
+    // Only keys of a US keyboard are recognized as they are typed
+    if !abbreviation.chars().all(|c| c.is_ascii_graphic()) {
+        return Err("Use letters, digits and punctuation without spaces".to_string());
+    }
+    Ok(Some(abbreviation.to_string()))
+}
+
 /// A unified snippet that can hold different content types.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Snippet {
@@ -117,6 +329,53 @@ pub struct Snippet {
     pub content: SnippetContent,
     #[serde(skip)]
     pub position: i64,
+    /// Auto-captured and still awaiting keep/discard triage.
+    #[serde(skip)]
+    pub in_inbox: bool,
+    /// Creation time in Unix seconds.
+    #[serde(skip)]
+    pub created_at: i64,
+    /// Last content or label change in Unix seconds.
+    #[serde(skip)]
+    pub updated_at: i64,
+    /// When the snippet was moved to the trash, in Unix seconds.
+    #[serde(skip)]
+    pub deleted_at: Option<i64>,
+    /// When the snippet was added to the favorites bar, in Unix seconds.
+    #[serde(skip)]
+    pub favorite_at: Option<i64>,
+    /// Number of times the snippet was copied.
+    #[serde(skip)]
+    pub copy_count: i64,
+    /// Last copy in Unix seconds.
+    #[serde(skip)]
+    pub last_copied_at: Option<i64>,
+    /// Custom icon shown instead of the kind icon: an emoji, or a
+    /// Phosphor icon name after `PHOSPHOR_ICON_PREFIX`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    /// Notes on the snippet, e.g. why it exists; shown under the label.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Incremented by every content or label change; edits based on an
+    /// older revision are rejected instead of overwriting newer changes.
+    #[serde(skip)]
+    pub revision: i64,
+    /// File the snippet was imported from or exported to.
+    #[serde(skip)]
+    pub file_path: Option<String>,
+    /// Hash of that file's contents when it was linked, to notice later edits.
+    #[serde(skip)]
+    pub file_hash: Option<String>,
+    /// Application in front when the snippet was auto-captured, where known.
+    #[serde(skip)]
+    pub source_app: Option<String>,
+    /// Typed anywhere, expands into the snippet's content.
+    #[serde(skip)]
+    pub abbreviation: Option<String>,
+    /// Name of the section the snippet sits in; only filled in exports.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub section: Option<String>,
 }
 
 impl Snippet {
@@ -127,6 +386,21 @@ impl Snippet {
             label,
             content,
             position: 0,
+            in_inbox: false,
+            created_at: 0,
+            updated_at: 0,
+            deleted_at: None,
+            favorite_at: None,
+            copy_count: 0,
+            last_copied_at: None,
+            icon: None,
+            description: None,
+            revision: 0,
+            file_path: None,
+            file_hash: None,
+            source_app: None,
+            abbreviation: None,
+            section: None,
         }
     }
 
@@ -148,10 +422,49 @@ impl Snippet {
         Self::new(label, SnippetContent::Code(CodeData::new(code, language)))
     }
 
+    /// Create a new JSON snippet.
+    pub fn json(json: String, label: String) -> Self {
+        let label = if label.is_empty() { nanoid!(8) } else { label };
+        Self::new(label, SnippetContent::Json(JsonData::new(json)))
+    }
+
+    /// Create a new gradient snippet.
+    pub fn gradient(gradient: GradientData, label: String) -> Self {
+        let label = if label.is_empty() { nanoid!(8) } else { label };
+        Self::new(label, SnippetContent::Gradient(gradient))
+    }
+
     /// Create a new text snippet.
     pub fn text(text: String, label: String) -> Self {
+        Self::rich_text(text, None, label)
+    }
+
+    /// Create a new text snippet keeping the rich text (HTML) it was copied with.
+    pub fn rich_text(text: String, html: Option<String>, label: String) -> Self {
         let label = if label.is_empty() { nanoid!(8) } else { label };
-        Self::new(label, SnippetContent::Text(TextData::new(text)))
+        Self::new(label, SnippetContent::Text(TextData::with_html(text, html)))
+    }
+
+    /// Give the snippet a description.
+    pub fn with_description(self, description: Option<String>) -> Self {
+        Self {
+            description,
+            ..self
+        }
+    }
+
+    /// Record the application the snippet was copied from.
+    pub fn with_source_app(self, source_app: Option<String>) -> Self {
+        Self { source_app, ..self }
+    }
+
+    /// Get the file on disk behind this snippet: the stored copy of a file
+    /// snippet, or the file it was imported from or exported to.
+    pub fn file_on_disk(&self) -> Option<&str> {
+        match &self.content {
+            SnippetContent::File(file) => Some(&file.path),
+            _ => self.file_path.as_deref(),
+        }
     }
 
     /// Get the default label for this snippet based on content.
@@ -166,8 +479,22 @@ impl Snippet {
         }
         let filter_lower = filter.to_lowercase();
 
-        // Check label
-        if self.label.to_lowercase().contains(&filter_lower) {
+        // Check label and description
+        if self.label.to_lowercase().contains(&filter_lower)
+            || self
+                .description
+                .as_ref()
+                .is_some_and(|description| description.to_lowercase().contains(&filter_lower))
+        {
+            return true;
+        }
+
+        // Check the application it was copied from
+        if self
+            .source_app
+            .as_ref()
+            .is_some_and(|app| app.to_lowercase().contains(&filter_lower))
+        {
             return true;
         }
 
@@ -178,30 +505,168 @@ impl Snippet {
                 c.to_hex().to_lowercase().contains(&filter_lower)
                     || c.to_rgb().to_lowercase().contains(&filter_lower)
             }
+            SnippetContent::ColorSet(set) => {
+                set.to_hex_list().to_lowercase().contains(&filter_lower)
+            }
+            SnippetContent::Gradient(g) => g.to_css().to_lowercase().contains(&filter_lower),
             SnippetContent::Code(c) => {
                 c.code.to_lowercase().contains(&filter_lower)
                     || c.language.to_lowercase().contains(&filter_lower)
             }
+            SnippetContent::Json(j) => j.json.to_lowercase().contains(&filter_lower),
             SnippetContent::Text(t) => t.text.to_lowercase().contains(&filter_lower),
+            SnippetContent::File(f) => f.name.to_lowercase().contains(&filter_lower),
+        }
+    }
+}
+
+/// How a palette selects its snippets.
+#[derive(Debug, Clone, Copy)]
+pub enum PaletteRule<'a> {
+    /// Snippets assigned to the palette with this ID.
+    Members(i64),
+    /// Snippets matching a smart palette's query.
+    Query(&'a PaletteQuery),
+}
+
+impl PaletteRule<'_> {
+    /// Check if a snippet belongs to the palette.
+    pub fn matches(&self, snippet: &Snippet, snippet_palettes: &HashMap<i64, Vec<i64>>) -> bool {
+        match self {
+            PaletteRule::Members(palette_id) => snippet_palettes
+                .get(&snippet.id)
+                .is_some_and(|palette_ids| palette_ids.contains(palette_id)),
+            PaletteRule::Query(query) => query.matches(snippet),
+        }
+    }
+}
+
+/// Combined filter criteria for the snippet list (header search, tabs, facets).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SnippetFilter<'a> {
+    /// Free-text search.
+    pub text: &'a str,
+    /// Restrict to one snippet kind.
+    pub kind: Option<&'a SnippetKind>,
+    /// Restrict to code snippets of one language.
+    pub language: Option<&'a str>,
+    /// Restrict to snippets captured from one application.
+    pub source_app: Option<&'a str>,
+    /// Restrict to members of a palette, given as the rules of the palette
+    /// and its nested palettes; a snippet picked by any of them matches.
+    pub palette: Option<&'a [PaletteRule<'a>]>,
+    /// Show inbox snippets instead of the library.
+    pub inbox: bool,
+}
+
+impl SnippetFilter<'_> {
+    /// Check if any filter criterion is set.
+    pub fn is_active(&self) -> bool {
+        !self.text.trim().is_empty()
+            || self.kind.is_some()
+            || self.language.is_some()
+            || self.source_app.is_some()
+            || self.palette.is_some()
+    }
+
+    /// Check if a snippet passes all criteria.
+    /// `snippet_palettes` maps snippet IDs to the palettes they belong to.
+    pub fn matches(&self, snippet: &Snippet, snippet_palettes: &HashMap<i64, Vec<i64>>) -> bool {
+        // Inbox snippets stay out of the library until kept
+        if snippet.in_inbox != self.inbox {
+            return false;
+        }
+        // Filter by kind
+        if let Some(kind) = self.kind {
+            if &snippet.kind() != kind {
+                return false;
+            }
+        }
+        // Filter by language (code snippets only)
+        if let Some(language) = self.language {
+            match &snippet.content {
+                SnippetContent::Code(code) if code.language == language => {}
+                _ => return false,
+            }
+        }
+        // Filter by source application
+        if self.source_app.is_some() && snippet.source_app.as_deref() != self.source_app {
+            return false;
+        }
+        // Filter by palette
+        if let Some(rules) = self.palette {
+            if !rules
+                .iter()
+                .any(|rule| rule.matches(snippet, snippet_palettes))
+            {
+                return false;
+            }
         }
+        // Filter by text
+        snippet.matches_filter(self.text.trim())
     }
 }
 
+/// Count library code snippets per language, most common first (ties sorted by name).
+pub fn language_counts(snippets: &[Snippet]) -> Vec<(String, usize)> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for snippet in snippets.iter().filter(|s| !s.in_inbox) {
+        if let SnippetContent::Code(code) = &snippet.content {
+            *counts.entry(code.language.as_str()).or_default() += 1;
+        }
+    }
+
+    let mut counts: Vec<(String, usize)> = counts
+        .into_iter()
+        .map(|(language, count)| (language.to_string(), count))
+        .collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts
+}
+
+/// Count library snippets per source application, most common first
+/// (ties sorted by name).
+pub fn source_app_counts(snippets: &[Snippet]) -> Vec<(String, usize)> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for snippet in snippets.iter().filter(|s| !s.in_inbox) {
+        if let Some(app) = &snippet.source_app {
+            *counts.entry(app.as_str()).or_default() += 1;
+        }
+    }
+
+    let mut counts: Vec<(String, usize)> = counts
+        .into_iter()
+        .map(|(app, count)| (app.to_string(), count))
+        .collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts
+}
+
 /// Detect what kind of snippet the given text might be.
 pub fn detect_snippet_type(text: &str) -> Option<SnippetKind> {
     let trimmed = text.trim();
 
-    // 1. Try color formats first (most specific patterns)
+    // 1. Scripts naming their interpreter
+    if shebang_language(trimmed).is_some() {
+        return Some(SnippetKind::Code);
+    }
+
+    // 2. Valid JSON documents, which may contain color values
+    if looks_like_json(trimmed) {
+        return Some(SnippetKind::Json);
+    }
+
+    // 3. Try color formats (most specific patterns)
     if !extract_colors_from_text(trimmed).is_empty() {
         return Some(SnippetKind::Color);
     }
 
-    // 2. Detect code (heuristics)
+    // 4. Detect code (heuristics)
     if code::looks_like_code(trimmed) {
         return Some(SnippetKind::Code);
     }
 
-    // 3. Default to text if multi-line or substantial
+    // 5. Default to text if multi-line or substantial
     if trimmed.lines().count() > 1 || trimmed.len() > 20 {
         return Some(SnippetKind::Text);
     }
@@ -215,22 +680,76 @@ mod tests {
 
     #[test]
     fn test_snippet_kind_db_roundtrip() {
-        for kind in [SnippetKind::Color, SnippetKind::Code, SnippetKind::Text] {
+        for kind in [
+            SnippetKind::Color,
+            SnippetKind::ColorSet,
+            SnippetKind::Gradient,
+            SnippetKind::Code,
+            SnippetKind::Json,
+            SnippetKind::Text,
+            SnippetKind::File,
+        ] {
             let s = kind.as_db_str();
             let parsed = SnippetKind::from_db_str(s).unwrap();
             assert_eq!(kind, parsed);
         }
     }
 
+    #[test]
+    fn test_preview_cuts_on_characters() {
+        let line = format!("{}é and more", "a".repeat(59));
+        let snippet = Snippet::text(line, "Note".to_string());
+        assert_eq!(
+            snippet.content.preview(60),
+            format!("{}é...", "a".repeat(59))
+        );
+        let snippet = Snippet::json("[\"日本語\"]".to_string(), "Words".to_string());
+        assert_eq!(snippet.content.preview(3), "[\"日...");
+        assert_eq!(snippet.content.preview(7), "[\"日本語\"]");
+    }
+
+    #[test]
+    fn test_gradient_content() {
+        let gradient = GradientData::new(
+            ColorData::new(255, 0, 0, 1.0),
+            ColorData::new(0, 0, 255, 1.0),
+        );
+        let snippet = Snippet::gradient(gradient, "Dusk".to_string());
+        assert_eq!(snippet.kind(), SnippetKind::Gradient);
+        assert_eq!(
+            snippet.content.to_copyable_string(),
+            "linear-gradient(90deg, #FF0000 0%, #0000FF 100%)"
+        );
+        assert!(snippet.matches_filter("0000ff"));
+
+        // Exports tag the content with its kind
+        let json = serde_json::to_string(&snippet.content).unwrap();
+        assert!(json.starts_with("{\"type\":\"gradient\""));
+        let parsed: SnippetContent = serde_json::from_str(&json).unwrap();
+        assert!(matches!(parsed, SnippetContent::Gradient(g) if g.stops.len() == 2));
+    }
+
     #[test]
     fn test_detect_color_snippet() {
         assert_eq!(detect_snippet_type("#FF5733"), Some(SnippetKind::Color));
+        assert_eq!(
+            detect_snippet_type("color: tomato;"),
+            Some(SnippetKind::Color)
+        );
         assert_eq!(
             detect_snippet_type("rgb(255, 0, 0)"),
             Some(SnippetKind::Color)
         );
     }
 
+    #[test]
+    fn test_detect_json_snippet() {
+        assert_eq!(
+            detect_snippet_type("{\"accent\": \"#FF5733\"}"),
+            Some(SnippetKind::Json)
+        );
+    }
+
     #[test]
     fn test_detect_code_snippet() {
         assert_eq!(
@@ -241,6 +760,11 @@ mod tests {
             detect_snippet_type("def hello():\n    print('world')"),
             Some(SnippetKind::Code)
         );
+        // A #! line wins over the colors in the script
+        assert_eq!(
+            detect_snippet_type("#!/bin/sh\necho '#FF5733'"),
+            Some(SnippetKind::Code)
+        );
     }
 
     #[test]
@@ -261,5 +785,186 @@ mod tests {
         assert!(snippet.matches_filter("orange"));
         assert!(snippet.matches_filter("FF5733"));
         assert!(!snippet.matches_filter("blue"));
+
+        let mut snippet = Snippet::code("spawn(task)".to_string(), "rust".to_string(), "a".into());
+        snippet.description = normalize_description("  Works around tokio 1.38 bug\n\n");
+        assert_eq!(
+            snippet.description.as_deref(),
+            Some("Works around tokio 1.38 bug")
+        );
+        assert!(snippet.matches_filter("TOKIO"));
+        assert_eq!(normalize_description(" \n "), None);
+    }
+
+    #[test]
+    fn test_combined_copy_text() {
+        let code = Snippet::code(
+            "fn main() {}\n".to_string(),
+            "rust".to_string(),
+            String::new(),
+        );
+        let note = Snippet::text("Remember".to_string(), String::new());
+        let color = Snippet::color(255, 0, 0, 1.0, String::new());
+        assert_eq!(
+            combined_copy_text(&[&note, &code, &color], "\n\n"),
+            "Remember\n\n```rust\nfn main() {}\n```\n\n#FF0000"
+        );
+    }
+
+    #[test]
+    fn test_fenced_code_block() {
+        assert_eq!(
+            fenced_code_block("let a = 1;\n\n", "rust"),
+            "```rust\nlet a = 1;\n```"
+        );
+        assert_eq!(fenced_code_block("notes", "plain"), "```\nnotes\n```");
+        // Backticks in the code need a longer fence
+        assert_eq!(
+            fenced_code_block("```sh\nls\n```", "markdown"),
+            "````markdown\n```sh\nls\n```\n````"
+        );
+    }
+
+    #[test]
+    fn test_to_markdown() {
+        let note = Snippet::text("First\n\nSecond\n".to_string(), String::new());
+        assert_eq!(note.content.to_markdown(true), "> First\n>\n> Second");
+        assert_eq!(note.content.to_markdown(false), "First\n\nSecond\n");
+        let color = Snippet::color(255, 0, 0, 1.0, String::new());
+        assert_eq!(color.content.to_markdown(true), "#FF0000");
+    }
+
+    #[test]
+    fn test_relative_time() {
+        let now = 1_000_000_000;
+        assert_eq!(relative_time(now - 30, now), "just now");
+        assert_eq!(relative_time(now - 5 * 60, now), "5m ago");
+        assert_eq!(relative_time(now - 3 * 3600, now), "3h ago");
+        assert_eq!(relative_time(now - 2 * 86400, now), "2d ago");
+        assert_eq!(relative_time(now - 65 * 86400, now), "2mo ago");
+        assert_eq!(relative_time(now - 800 * 86400, now), "2y ago");
+        // Clock skew never produces negative ages
+        assert_eq!(relative_time(now + 100, now), "just now");
+    }
+
+    #[test]
+    fn test_normalize_icon() {
+        assert_eq!(normalize_icon("  "), None);
+        assert_eq!(normalize_icon(" 🚀 "), Some("🚀".to_string()));
+        assert_eq!(normalize_icon("ph:rocket"), Some("ph:rocket".to_string()));
+        // Only the first word is kept
+        assert_eq!(normalize_icon("🔥 hot"), Some("🔥".to_string()));
+        assert_eq!(
+            normalize_icon("abcdefghijklmnop"),
+            Some("abcdefghijkl".to_string())
+        );
+    }
+
+    #[test]
+    fn test_normalize_abbreviation() {
+        assert_eq!(normalize_abbreviation("  "), Ok(None));
+        assert_eq!(
+            normalize_abbreviation(" ;sig "),
+            Ok(Some(";sig".to_string()))
+        );
+        assert!(normalize_abbreviation(";").is_err());
+        assert!(normalize_abbreviation(";my sig").is_err());
+        assert!(normalize_abbreviation(";café").is_err());
+        assert!(normalize_abbreviation(&"x".repeat(MAX_ABBREVIATION_CHARS + 1)).is_err());
+    }
+
+    #[test]
+    fn test_short_hash() {
+        assert_eq!(short_hash("abc"), "ba7816bf8f01");
+        assert_ne!(short_hash("abc"), short_hash("abc\n"));
+    }
+
+    #[test]
+    fn test_linked_file_changed() {
+        let path =
+            std::env::temp_dir().join(format!("shard-linked-test-{}.txt", std::process::id()));
+        std::fs::write(&path, "fn main() {}").unwrap();
+        let hash = short_hash("fn main() {}");
+
+        let unchanged = linked_file_changed(&path, &hash);
+        std::fs::write(&path, "fn main() { todo!() }").unwrap();
+        let edited = linked_file_changed(&path, &hash);
+        std::fs::remove_file(&path).unwrap();
+        let removed = linked_file_changed(&path, &hash);
+
+        assert!(!unchanged);
+        assert!(edited);
+        assert!(removed);
+    }
+
+    #[test]
+    fn test_snippet_filter() {
+        let mut rust = Snippet::code("fn a() {}".to_string(), "rust".to_string(), "a".to_string());
+        rust.id = 1;
+        let mut sql = Snippet::code("SELECT 1".to_string(), "sql".to_string(), "b".to_string());
+        sql.id = 2;
+        let snippet_palettes = HashMap::from([(1, vec![10])]);
+
+        let filter = SnippetFilter::default();
+        assert!(!filter.is_active());
+        assert!(filter.matches(&rust, &snippet_palettes));
+
+        let filter = SnippetFilter {
+            language: Some("rust"),
+            ..Default::default()
+        };
+        assert!(filter.matches(&rust, &snippet_palettes));
+        assert!(!filter.matches(&sql, &snippet_palettes));
+
+        let rules = [PaletteRule::Members(10)];
+        let filter = SnippetFilter {
+            palette: Some(&rules),
+            ..Default::default()
+        };
+        assert!(filter.matches(&rust, &snippet_palettes));
+        assert!(!filter.matches(&sql, &snippet_palettes));
+
+        // Smart palettes match by query instead of membership
+        let query = PaletteQuery::parse("language=sql").unwrap();
+        let rules = [PaletteRule::Query(&query)];
+        let filter = SnippetFilter {
+            palette: Some(&rules),
+            ..Default::default()
+        };
+        assert!(filter.matches(&sql, &snippet_palettes));
+        assert!(!filter.matches(&rust, &snippet_palettes));
+
+        // A parent palette also picks the snippets of its nested palettes
+        let rules = [PaletteRule::Members(30), PaletteRule::Query(&query)];
+        let filter = SnippetFilter {
+            palette: Some(&rules),
+            ..Default::default()
+        };
+        assert!(filter.matches(&sql, &snippet_palettes));
+        assert!(!filter.matches(&rust, &snippet_palettes));
+
+        // Inbox snippets are hidden from the library
+        sql.in_inbox = true;
+        assert!(!SnippetFilter::default().matches(&sql, &snippet_palettes));
+        let filter = SnippetFilter {
+            inbox: true,
+            ..Default::default()
+        };
+        assert!(filter.matches(&sql, &snippet_palettes));
+    }
+
+    #[test]
+    fn test_language_counts() {
+        let snippets = vec![
+            Snippet::code("fn a() {}".to_string(), "rust".to_string(), "a".to_string()),
+            Snippet::code("SELECT 1".to_string(), "sql".to_string(), "b".to_string()),
+            Snippet::code("fn b() {}".to_string(), "rust".to_string(), "c".to_string()),
+            Snippet::text("not code".to_string(), "d".to_string()),
+        ];
+        let counts = language_counts(&snippets);
+        assert_eq!(
+            counts,
+            vec![("rust".to_string(), 2), ("sql".to_string(), 1)]
+        );
     }
 }
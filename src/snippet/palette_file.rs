@@ -0,0 +1,92 @@
+//! `.shardpalette` files: one palette and its snippets, for sharing.
+
+use serde::{Deserialize, Serialize};
+
+use super::Snippet;
+
+/// File extension of shared palettes.
+pub const PALETTE_FILE_EXTENSION: &str = "shardpalette";
+
+/// Format version written by this build.
+const PALETTE_FILE_VERSION: u32 = 1;
+
+/// A palette with its member snippets, as stored in a `.shardpalette` file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaletteFile {
+    /// Format version; files from a newer version are rejected.
+    #[serde(rename = "shardpalette")]
+    pub version: u32,
+    /// Name of the palette.
+    pub name: String,
+    pub snippets: Vec<Snippet>,
+}
+
+impl PaletteFile {
+    /// Bundle a palette's snippets for export.
+    pub fn new(name: String, snippets: Vec<Snippet>) -> Self {
+        Self {
+            version: PALETTE_FILE_VERSION,
+            name,
+            snippets,
+        }
+    }
+
+    /// Serialize to the compact JSON written to disk.
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string(self).map_err(|e| format!("Serialization failed: {}", e))
+    }
+
+    /// Parse a `.shardpalette` file.
+    pub fn parse(contents: &str) -> Result<Self, String> {
+        let file: PaletteFile = serde_json::from_str(contents)
+            .map_err(|e| format!("Not a Shard palette file: {}", e))?;
+        if file.version > PALETTE_FILE_VERSION {
+            return Err(format!(
+                "The palette file needs a newer Shard (format {})",
+                file.version
+            ));
+        }
+        if file.name.trim().is_empty() {
+            return Err("The palette file has no name".to_string());
+        }
+        Ok(file)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snippet::{ColorData, SnippetContent, TextData};
+
+    #[test]
+    fn test_palette_file_round_trip() {
+        let snippets = vec![
+            Snippet::new(
+                "Brand".to_string(),
+                SnippetContent::Color(ColorData::new(255, 0, 128, 1.0)),
+            ),
+            Snippet::new(
+                "Greeting".to_string(),
+                SnippetContent::Text(TextData::new("Hello".to_string())),
+            ),
+        ];
+        let json = PaletteFile::new("Launch".to_string(), snippets)
+            .to_json()
+            .unwrap();
+
+        assert!(json.starts_with("{\"shardpalette\":1,"));
+        assert!(!json.contains('\n'));
+
+        let file = PaletteFile::parse(&json).unwrap();
+        assert_eq!(file.name, "Launch");
+        assert_eq!(file.snippets.len(), 2);
+        assert_eq!(file.snippets[1].label, "Greeting");
+    }
+
+    #[test]
+    fn test_palette_file_rejects_invalid_files() {
+        assert!(PaletteFile::parse("[]").is_err());
+        assert!(PaletteFile::parse(r#"{"shardpalette":99,"name":"X","snippets":[]}"#).is_err());
+        assert!(PaletteFile::parse(r#"{"shardpalette":1,"name":" ","snippets":[]}"#).is_err());
+    }
+}
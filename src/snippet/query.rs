@@ -0,0 +1,243 @@
+//! Saved filter queries that define smart palettes.
+//!
+//! A query is one or more conditions joined by `AND`, e.g.
+//! `kind=code AND language=rust AND label contains "sqlx"`.
+//! Fields are `kind`, `language`, `label`, `content` and `source` (the app
+//! a snippet was captured from); operators are
+//! `=`, `!=` and `contains`. Matching ignores case.
+
+use std::fmt;
+
+use super::{Snippet, SnippetContent, SnippetKind};
+
+/// Field a query condition looks at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Kind,
+    Language,
+    Label,
+    Content,
+    Source,
+}
+
+/// How a condition compares its field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Is,
+    IsNot,
+    Contains,
+}
+
+/// One `field op value` condition, with the value lowercased.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Condition {
+    field: Field,
+    op: Op,
+    value: String,
+}
+
+/// A parsed smart palette query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaletteQuery {
+    source: String,
+    conditions: Vec<Condition>,
+}
+
+impl PaletteQuery {
+    /// Parse a query, describing the first problem on failure.
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let tokens = tokenize(source)?;
+        let mut tokens = tokens.into_iter();
+        let mut conditions = Vec::new();
+
+        loop {
+            let Some(field) = tokens.next() else {
+                return Err("Expected a condition like kind=code".to_string());
+            };
+            let field = match field.to_lowercase().as_str() {
+                "kind" => Field::Kind,
+                "language" | "lang" => Field::Language,
+                "label" => Field::Label,
+                "content" => Field::Content,
+                "source" | "app" => Field::Source,
+                other => {
+                    return Err(format!(
+                        "Unknown field \"{}\" (use kind, language, label, content or source)",
+                        other
+                    ))
+                }
+            };
+            let op = match tokens.next().map(|t| t.to_lowercase()).as_deref() {
+                Some("=") => Op::Is,
+                Some("!=") => Op::IsNot,
+                Some("contains") => Op::Contains,
+                _ => return Err("Expected =, != or contains after a field".to_string()),
+            };
+            let Some(value) = tokens.next() else {
+                return Err("Expected a value".to_string());
+            };
+            let value = value.to_lowercase();
+            if field == Field::Kind && SnippetKind::from_db_str(&value).is_none() {
+                return Err(format!(
+                    "Unknown kind \"{}\" (use color, code, text or file)",
+                    value
+                ));
+            }
+            conditions.push(Condition { field, op, value });
+
+            match tokens.next() {
+                None => break,
+                Some(token) if token.eq_ignore_ascii_case("and") => {}
+                Some(token) => return Err(format!("Expected AND, found \"{}\"", token)),
+            }
+        }
+
+        Ok(Self {
+            source: source.trim().to_string(),
+            conditions,
+        })
+    }
+
+    /// Check if a snippet satisfies every condition.
+    pub fn matches(&self, snippet: &Snippet) -> bool {
+        self.conditions.iter().all(|condition| {
+            let actual = match condition.field {
+                Field::Kind => snippet.kind().as_db_str().to_string(),
+                Field::Language => match &snippet.content {
+                    SnippetContent::Code(code) => code.language.to_lowercase(),
+                    _ => String::new(),
+                },
+                Field::Label => snippet.label.to_lowercase(),
+                Field::Content => snippet.content.to_copyable_string().to_lowercase(),
+                Field::Source => snippet
+                    .source_app
+                    .as_deref()
+                    .unwrap_or_default()
+                    .to_lowercase(),
+            };
+            match condition.op {
+                Op::Is => actual == condition.value,
+                Op::IsNot => actual != condition.value,
+                Op::Contains => actual.contains(&condition.value),
+            }
+        })
+    }
+}
+
+impl fmt::Display for PaletteQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+/// Split a query into words, quoted strings and the `=` / `!=` operators.
+fn tokenize(source: &str) -> Result<Vec<String>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = source.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '"' => {
+                chars.next();
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some(c) => value.push(c),
+                        None => return Err("Missing closing quote".to_string()),
+                    }
+                }
+                tokens.push(value);
+            }
+            '=' => {
+                chars.next();
+                tokens.push("=".to_string());
+            }
+            '!' => {
+                chars.next();
+                if chars.next() != Some('=') {
+                    return Err("Expected = after !".to_string());
+                }
+                tokens.push("!=".to_string());
+            }
+            _ => {
+                let mut word = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() || matches!(c, '"' | '=' | '!') {
+                        break;
+                    }
+                    word.push(c);
+                    chars.next();
+                }
+                tokens.push(word);
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query_matches_conditions() {
+        let query =
+            PaletteQuery::parse(r#"kind=code AND language = Rust and label contains "SQLx""#)
+                .unwrap();
+        let hit = Snippet::code(
+            "sqlx::query!()".to_string(),
+            "rust".to_string(),
+            "sqlx query".to_string(),
+        );
+        let wrong_label = Snippet::code(
+            "fn main() {}".to_string(),
+            "rust".to_string(),
+            "main".to_string(),
+        );
+        let wrong_kind = Snippet::text("sqlx".to_string(), "sqlx notes".to_string());
+
+        assert!(query.matches(&hit));
+        assert!(!query.matches(&wrong_label));
+        assert!(!query.matches(&wrong_kind));
+        assert_eq!(
+            query.to_string(),
+            r#"kind=code AND language = Rust and label contains "SQLx""#
+        );
+    }
+
+    #[test]
+    fn test_query_negation_and_content() {
+        let query = PaletteQuery::parse("kind != color AND content contains todo").unwrap();
+        let note = Snippet::text("TODO: ship it".to_string(), "Plan".to_string());
+        let done = Snippet::text("shipped".to_string(), "Plan".to_string());
+
+        assert!(query.matches(&note));
+        assert!(!query.matches(&done));
+    }
+
+    #[test]
+    fn test_query_source_app() {
+        let query = PaletteQuery::parse("source contains firefox").unwrap();
+        let copied = Snippet::text("notes".to_string(), "Notes".to_string())
+            .with_source_app(Some("Firefox".to_string()));
+        let typed = Snippet::text("notes".to_string(), "Notes".to_string());
+
+        assert!(query.matches(&copied));
+        assert!(!query.matches(&typed));
+    }
+
+    #[test]
+    fn test_query_parse_errors() {
+        assert!(PaletteQuery::parse("").is_err());
+        assert!(PaletteQuery::parse("size=3").is_err());
+        assert!(PaletteQuery::parse("kind=image").is_err());
+        assert!(PaletteQuery::parse("kind code").is_err());
+        assert!(PaletteQuery::parse("kind=code OR kind=text").is_err());
+        assert!(PaletteQuery::parse("label contains \"open").is_err());
+        assert!(PaletteQuery::parse("kind=code AND").is_err());
+    }
+}
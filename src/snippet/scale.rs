@@ -0,0 +1,132 @@
+//! Color scales: tints and shades of one color as a 50–900 style ramp,
+//! spaced evenly in OKLCH lightness so the steps look evenly spaced.
+
+use super::color::{oklch_gamut, oklch_to_rgb, rgb_to_oklch, ColorData, Gamut};
+
+/// Step counts offered for a scale.
+pub const SCALE_SIZES: [usize; 4] = [5, 9, 10, 11];
+
+/// OKLCH lightness of the lightest and darkest steps.
+const LIGHTEST: f32 = 0.97;
+const DARKEST: f32 = 0.25;
+
+/// Chroma kept at the ends of the ramp, relative to the base color;
+/// near-white tints and near-black shades look garish at full chroma.
+const END_CHROMA: f32 = 0.6;
+
+/// Name the steps of a scale the way design systems do: 100–900, with 50
+/// and 950 added for the longer scales.
+pub fn scale_step_names(steps: usize) -> Vec<u32> {
+    match steps {
+        5 => vec![100, 300, 500, 700, 900],
+        9 => (1..=9).map(|n| n * 100).collect(),
+        10 => std::iter::once(50)
+            .chain((1..=9).map(|n| n * 100))
+            .collect(),
+        11 => std::iter::once(50)
+            .chain((1..=9).map(|n| n * 100))
+            .chain(std::iter::once(950))
+            .collect(),
+        _ => (1..=steps as u32).map(|n| n * 100).collect(),
+    }
+}
+
+/// Build a scale of `steps` colors from light to dark around `base`.
+/// The base color itself takes the step nearest its lightness; the others
+/// keep its hue and alpha and are brought into sRGB by lowering chroma.
+pub fn color_scale(base: &ColorData, steps: usize) -> Vec<ColorData> {
+    if steps < 2 {
+        return vec![base.clone(); steps];
+    }
+    let (l, c, h) = rgb_to_oklch(base.r, base.g, base.b);
+    let last = steps - 1;
+    let lightest = LIGHTEST.max(l);
+    let darkest = DARKEST.min(l);
+
+    // The step whose evenly spread lightness is nearest the base's
+    let anchor = (0..steps)
+        .min_by(|a, b| {
+            let distance = |i: usize| (lerp(LIGHTEST, DARKEST, i as f32 / last as f32) - l).abs();
+            distance(*a).total_cmp(&distance(*b))
+        })
+        .unwrap_or(0);
+
+    (0..steps)
+        .map(|i| {
+            if i == anchor {
+                return base.clone();
+            }
+            let (lightness, t) = if i < anchor {
+                let t = 1.0 - i as f32 / anchor as f32;
+                (lerp(l, lightest, t), t)
+            } else {
+                let t = (i - anchor) as f32 / (last - anchor) as f32;
+                (lerp(l, darkest, t), t)
+            };
+            let chroma = c * lerp(1.0, END_CHROMA, t);
+            let (r, g, b) = oklch_to_rgb(lightness, fit_chroma(lightness, chroma, h), h);
+            ColorData::new(r, g, b, base.a)
+        })
+        .collect()
+}
+
+fn lerp(from: f32, to: f32, t: f32) -> f32 {
+    from + (to - from) * t
+}
+
+/// Lower chroma until the color is within sRGB.
+fn fit_chroma(l: f32, c: f32, h: f32) -> f32 {
+    if oklch_gamut(l, c, h) == Gamut::Srgb {
+        return c;
+    }
+    let (mut low, mut high) = (0.0, c);
+    for _ in 0..16 {
+        let mid = (low + high) / 2.0;
+        if oklch_gamut(l, mid, h) == Gamut::Srgb {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    low
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scale_step_names() {
+        assert_eq!(scale_step_names(5), vec![100, 300, 500, 700, 900]);
+        assert_eq!(scale_step_names(10)[0], 50);
+        assert_eq!(scale_step_names(11).last(), Some(&950));
+        for steps in SCALE_SIZES {
+            assert_eq!(scale_step_names(steps).len(), steps);
+        }
+    }
+
+    #[test]
+    fn test_color_scale() {
+        let base = ColorData::new(59, 130, 246, 1.0);
+        let scale = color_scale(&base, 10);
+        assert_eq!(scale.len(), 10);
+        assert!(scale.contains(&base));
+
+        // Light to dark, keeping the hue
+        let lightness: Vec<f32> = scale
+            .iter()
+            .map(|color| rgb_to_oklch(color.r, color.g, color.b).0)
+            .collect();
+        assert!(lightness.windows(2).all(|pair| pair[0] > pair[1]));
+        assert!(lightness[0] > 0.95);
+        let (_, _, base_hue) = rgb_to_oklch(base.r, base.g, base.b);
+        let (_, _, hue) = rgb_to_oklch(scale[7].r, scale[7].g, scale[7].b);
+        assert!((hue - base_hue).abs() < 5.0);
+    }
+
+    #[test]
+    fn test_color_scale_keeps_alpha() {
+        let base = ColorData::new(200, 40, 40, 0.5);
+        assert!(color_scale(&base, 5).iter().all(|color| color.a == 0.5));
+    }
+}
@@ -0,0 +1,117 @@
+//! Fill-in placeholders in code and text snippets, written `${name:default}`.
+//!
+//! The default may be empty (`${name:}`). A bare `${name}` is left alone, as
+//! it is a JavaScript template literal or a shell variable far more often
+//! than a placeholder, and so are shell expansions like `${name:-default}`.
+//! A backslash keeps a placeholder literal: `\${name:default}`.
+
+use regex::{Captures, Regex};
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+static PLACEHOLDER_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(\\)?\$\{(\w+):([^}]*)\}").expect("Invalid placeholder regex"));
+
+/// A value to fill in, with the text used when it is left unchanged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Placeholder {
+    pub name: String,
+    pub default: String,
+}
+
+/// Check whether a match is a placeholder, rather than escaped or a shell expansion.
+fn is_placeholder(caps: &Captures) -> bool {
+    caps.get(1).is_none() && !caps[3].starts_with(['-', '=', '?', '+'])
+}
+
+/// Find the placeholders of a snippet, once per name in order of first use.
+/// A name used several times takes the first default given for it.
+pub fn find_placeholders(text: &str) -> Vec<Placeholder> {
+    let mut placeholders: Vec<Placeholder> = Vec::new();
+    for caps in PLACEHOLDER_REGEX.captures_iter(text) {
+        if !is_placeholder(&caps) {
+            continue;
+        }
+        match placeholders.iter_mut().find(|p| p.name == caps[2]) {
+            Some(existing) if existing.default.is_empty() => existing.default = caps[3].to_string(),
+            Some(_) => {}
+            None => placeholders.push(Placeholder {
+                name: caps[2].to_string(),
+                default: caps[3].to_string(),
+            }),
+        }
+    }
+    placeholders
+}
+
+/// Replace the placeholders with their values, or their own default where
+/// no value is given, and unescape escaped ones.
+pub fn fill_placeholders(text: &str, values: &HashMap<String, String>) -> String {
+    PLACEHOLDER_REGEX
+        .replace_all(text, |caps: &Captures| {
+            if caps.get(1).is_some() {
+                // Drop the escaping backslash
+                caps[0][1..].to_string()
+            } else if !is_placeholder(caps) {
+                caps[0].to_string()
+            } else {
+                values
+                    .get(&caps[2])
+                    .cloned()
+                    .unwrap_or_else(|| caps[3].to_string())
+            }
+        })
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn placeholder(name: &str, default: &str) -> Placeholder {
+        Placeholder {
+            name: name.to_string(),
+            default: default.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_find_placeholders() {
+        let text = "fn ${name:main}() -> ${ret:} {\n    ${name:other}(${arg:})\n}";
+        assert_eq!(
+            find_placeholders(text),
+            vec![
+                placeholder("name", "main"),
+                placeholder("ret", ""),
+                placeholder("arg", "")
+            ]
+        );
+        // A later default fills in an empty one
+        assert_eq!(
+            find_placeholders("${a:} ${a:x}"),
+            vec![placeholder("a", "x")]
+        );
+    }
+
+    #[test]
+    fn test_template_literals_and_shell_are_not_placeholders() {
+        let text = "`Hi ${name}` ${HOME:-/root} ${X:=1} ${Y:?unset} \\${kept:literal}";
+        assert!(find_placeholders(text).is_empty());
+        assert_eq!(
+            fill_placeholders(text, &HashMap::new()),
+            "`Hi ${name}` ${HOME:-/root} ${X:=1} ${Y:?unset} ${kept:literal}"
+        );
+    }
+
+    #[test]
+    fn test_fill_placeholders() {
+        let values = HashMap::from([("name".to_string(), "run".to_string())]);
+        assert_eq!(
+            fill_placeholders("fn ${name:main}() { ${name:} ${body:todo!()} }", &values),
+            "fn run() { run todo!() }"
+        );
+        // An emptied value stays empty
+        let values = HashMap::from([("x".to_string(), String::new())]);
+        assert_eq!(fill_placeholders("[${x:default}]", &values), "[]");
+    }
+}
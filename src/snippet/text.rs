@@ -2,16 +2,27 @@
 
 use serde::{Deserialize, Serialize};
 
+/// Average silent reading speed used for reading-time estimates.
+const WORDS_PER_MINUTE: usize = 200;
+
 /// Plain text data.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TextData {
     pub text: String,
+    /// Rich text (HTML) copied together with the plain text, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub html: Option<String>,
 }
 
 impl TextData {
     /// Create new text data.
     pub fn new(text: String) -> Self {
-        Self { text }
+        Self { text, html: None }
+    }
+
+    /// Create new text data with its rich text representation.
+    pub fn with_html(text: String, html: Option<String>) -> Self {
+        Self { text, html }
     }
 
     /// Get the number of lines in the text.
@@ -24,6 +35,16 @@ impl TextData {
         self.text.chars().count()
     }
 
+    /// Get the number of whitespace-separated words.
+    pub fn word_count(&self) -> usize {
+        self.text.split_whitespace().count()
+    }
+
+    /// Get the estimated reading time in minutes (rounded up, 0 for empty text).
+    pub fn reading_minutes(&self) -> usize {
+        self.word_count().div_ceil(WORDS_PER_MINUTE)
+    }
+
     /// Get a preview of the text (first few lines).
     pub fn preview(&self, max_lines: usize) -> String {
         self.text
@@ -33,6 +54,18 @@ impl TextData {
             .join("\n")
     }
 
+    /// Get the first paragraph (up to the first blank line), cut to `max_chars`.
+    pub fn first_paragraph(&self, max_chars: usize) -> String {
+        let trimmed = self.text.trim_start();
+        let paragraph = trimmed.split("\n\n").next().unwrap_or_default().trim_end();
+        if paragraph.chars().count() > max_chars {
+            let cut: String = paragraph.chars().take(max_chars).collect();
+            format!("{}…", cut.trim_end())
+        } else {
+            paragraph.to_string()
+        }
+    }
+
     /// Check if the text is empty or whitespace only.
     pub fn is_empty(&self) -> bool {
         self.text.trim().is_empty()
@@ -49,6 +82,17 @@ mod tests {
         assert_eq!(text.text, "Hello, world!");
     }
 
+    #[test]
+    fn test_html_is_optional() {
+        let plain: TextData = serde_json::from_str(r#"{"text": "hi"}"#).unwrap();
+        assert_eq!(plain.html, None);
+        assert_eq!(serde_json::to_string(&plain).unwrap(), r#"{"text":"hi"}"#);
+
+        let rich = TextData::with_html("hi".to_string(), Some("<b>hi</b>".to_string()));
+        let json = serde_json::to_string(&rich).unwrap();
+        assert_eq!(serde_json::from_str::<TextData>(&json).unwrap(), rich);
+    }
+
     #[test]
     fn test_line_count() {
         let text = TextData::new("line 1\nline 2\nline 3".to_string());
@@ -61,6 +105,17 @@ mod tests {
         assert_eq!(text.char_count(), 5);
     }
 
+    #[test]
+    fn test_word_count_and_reading_time() {
+        let text = TextData::new("one two\nthree  four".to_string());
+        assert_eq!(text.word_count(), 4);
+        assert_eq!(text.reading_minutes(), 1);
+
+        let long = TextData::new("word ".repeat(401));
+        assert_eq!(long.reading_minutes(), 3);
+        assert_eq!(TextData::new(String::new()).reading_minutes(), 0);
+    }
+
     #[test]
     fn test_preview() {
         let text = TextData::new("line 1\nline 2\nline 3\nline 4".to_string());
@@ -73,4 +128,12 @@ mod tests {
         assert!(TextData::new("   ".to_string()).is_empty());
         assert!(!TextData::new("hello".to_string()).is_empty());
     }
+
+    #[test]
+    fn test_first_paragraph() {
+        let text = TextData::new("\n  Intro line\nstill intro\n\nSecond paragraph".to_string());
+        assert_eq!(text.first_paragraph(100), "Intro line\nstill intro");
+        assert_eq!(text.first_paragraph(5), "Intro…");
+        assert_eq!(TextData::new(String::new()).first_paragraph(10), "");
+    }
 }
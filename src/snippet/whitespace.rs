@@ -0,0 +1,135 @@
+//! Whitespace cleanups for code: indentation conversion, trailing
+//! whitespace and line endings.
+
+use std::fmt;
+
+/// A whitespace cleanup applied to a whole snippet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WhitespaceTransform {
+    /// Indent with spaces instead of tabs.
+    TabsToSpaces,
+    /// Indent with tabs, keeping spaces for what doesn't fill a tab.
+    SpacesToTabs,
+    StripTrailing,
+    /// Use `\n` line endings only.
+    NormalizeLineEndings,
+}
+
+impl WhitespaceTransform {
+    pub const ALL: [WhitespaceTransform; 4] = [
+        WhitespaceTransform::TabsToSpaces,
+        WhitespaceTransform::SpacesToTabs,
+        WhitespaceTransform::StripTrailing,
+        WhitespaceTransform::NormalizeLineEndings,
+    ];
+
+    /// Get display name for the transform.
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            WhitespaceTransform::TabsToSpaces => "Tabs to Spaces",
+            WhitespaceTransform::SpacesToTabs => "Spaces to Tabs",
+            WhitespaceTransform::StripTrailing => "Strip Trailing Whitespace",
+            WhitespaceTransform::NormalizeLineEndings => "Normalize Line Endings",
+        }
+    }
+
+    /// Apply the transform, with tab stops every `tab_width` columns.
+    pub fn apply(&self, code: &str, tab_width: usize) -> String {
+        let tab_width = tab_width.max(1);
+        match self {
+            WhitespaceTransform::TabsToSpaces => map_lines(code, |line| {
+                let (indent, rest) = split_indent(line);
+                format!("{}{}", " ".repeat(indent_width(indent, tab_width)), rest)
+            }),
+            WhitespaceTransform::SpacesToTabs => map_lines(code, |line| {
+                let (indent, rest) = split_indent(line);
+                let width = indent_width(indent, tab_width);
+                format!(
+                    "{}{}{}",
+                    "\t".repeat(width / tab_width),
+                    " ".repeat(width % tab_width),
+                    rest
+                )
+            }),
+            WhitespaceTransform::StripTrailing => {
+                map_lines(code, |line| line.trim_end_matches([' ', '\t']).to_string())
+            }
+            WhitespaceTransform::NormalizeLineEndings => {
+                code.replace("\r\n", "\n").replace('\r', "\n")
+            }
+        }
+    }
+}
+
+impl fmt::Display for WhitespaceTransform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.display_name())
+    }
+}
+
+/// Rewrite each line, keeping its line ending.
+fn map_lines(code: &str, f: impl Fn(&str) -> String) -> String {
+    code.split('\n')
+        .map(|line| match line.strip_suffix('\r') {
+            Some(line) => format!("{}\r", f(line)),
+            None => f(line),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Split a line into its leading spaces and tabs and the rest.
+fn split_indent(line: &str) -> (&str, &str) {
+    let end = line
+        .find(|c: char| c != ' ' && c != '\t')
+        .unwrap_or(line.len());
+    line.split_at(end)
+}
+
+/// Get the columns an indentation takes up, tabs going to the next stop.
+fn indent_width(indent: &str, tab_width: usize) -> usize {
+    indent.chars().fold(0, |width, c| match c {
+        '\t' => (width / tab_width + 1) * tab_width,
+        _ => width + 1,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tabs_to_spaces() {
+        let code = "fn a() {\n\tif x {\n\t\ty(\"\\t\");\n  \t}\n}";
+        assert_eq!(
+            WhitespaceTransform::TabsToSpaces.apply(code, 4),
+            "fn a() {\n    if x {\n        y(\"\\t\");\n    }\n}"
+        );
+    }
+
+    #[test]
+    fn test_spaces_to_tabs() {
+        let code = "a\n    b\n      c\n  \td";
+        assert_eq!(
+            WhitespaceTransform::SpacesToTabs.apply(code, 4),
+            "a\n\tb\n\t  c\n\td"
+        );
+        assert_eq!(WhitespaceTransform::SpacesToTabs.apply("  x", 2), "\tx");
+    }
+
+    #[test]
+    fn test_strip_trailing_keeps_line_endings() {
+        assert_eq!(
+            WhitespaceTransform::StripTrailing.apply("a  \r\nb\t\n  \nc ", 4),
+            "a\r\nb\n\nc"
+        );
+    }
+
+    #[test]
+    fn test_normalize_line_endings() {
+        assert_eq!(
+            WhitespaceTransform::NormalizeLineEndings.apply("a\r\nb\rc\n", 4),
+            "a\nb\nc\n"
+        );
+    }
+}
@@ -0,0 +1,73 @@
+//! The application in front when something is copied, recorded with
+//! auto-captured snippets.
+//!
+//! Looked up with the tools each platform ships: System Events on macOS,
+//! the foreground window's process on Windows, and `xdotool` on X11.
+//! Wayland offers no way to ask, so captures there have no source.
+
+use std::process::Command;
+
+/// Name of the application in front, if it can be found.
+pub fn frontmost_app() -> Option<String> {
+    clean_app_name(&query_frontmost_app()?)
+}
+
+#[cfg(target_os = "macos")]
+fn query_frontmost_app() -> Option<String> {
+    command_output(Command::new("osascript").args([
+        "-e",
+        "tell application \"System Events\" to get name of first application process whose frontmost is true",
+    ]))
+}
+
+#[cfg(target_os = "windows")]
+fn query_frontmost_app() -> Option<String> {
+    const SCRIPT: &str = r#"Add-Type -Name W -Namespace U -MemberDefinition '[DllImport("user32.dll")] public static extern System.IntPtr GetForegroundWindow(); [DllImport("user32.dll")] public static extern int GetWindowThreadProcessId(System.IntPtr h, out int p);'; $p = 0; [void][U.W]::GetWindowThreadProcessId([U.W]::GetForegroundWindow(), [ref]$p); (Get-Process -Id $p).ProcessName"#;
+    command_output(Command::new("powershell").args(["-NoProfile", "-Command", SCRIPT]))
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn query_frontmost_app() -> Option<String> {
+    if std::env::var_os("WAYLAND_DISPLAY").is_some() && std::env::var_os("DISPLAY").is_none() {
+        return None;
+    }
+    let pid = command_output(Command::new("xdotool").args(["getactivewindow", "getwindowpid"]))?;
+    std::fs::read_to_string(format!("/proc/{}/comm", pid.trim())).ok()
+}
+
+/// Run a command and get its standard output, if it succeeded.
+fn command_output(command: &mut Command) -> Option<String> {
+    let output = command.output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Tidy a reported application name. Shard itself is not a source: its
+/// own copies are not captured.
+pub fn clean_app_name(raw: &str) -> Option<String> {
+    let name = raw.trim();
+    let name = name
+        .strip_suffix(".exe")
+        .or_else(|| name.strip_suffix(".app"))
+        .unwrap_or(name);
+    if name.is_empty() || name.eq_ignore_ascii_case(env!("CARGO_PKG_NAME")) {
+        None
+    } else {
+        Some(name.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clean_app_name() {
+        assert_eq!(clean_app_name("Firefox\n").as_deref(), Some("Firefox"));
+        assert_eq!(clean_app_name("Code.exe").as_deref(), Some("Code"));
+        assert_eq!(clean_app_name("  "), None);
+        assert_eq!(clean_app_name("shard"), None);
+    }
+}
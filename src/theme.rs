@@ -2,7 +2,7 @@
 
 #![allow(dead_code)]
 
-use iced::widget::{button, container, scrollable, text_input};
+use iced::widget::{button, container, pick_list, scrollable, text_input};
 use iced::{Border, Color, Theme};
 
 // === Color Palette ===
@@ -226,6 +226,25 @@ pub fn input_style(
     }
 }
 
+/// Styled pick list (dropdown selector)
+pub fn pick_list_style(_theme: &Theme, status: pick_list::Status) -> pick_list::Style {
+    let border_color = match status {
+        pick_list::Status::Hovered | pick_list::Status::Opened { .. } => BORDER_ACCENT,
+        pick_list::Status::Active => BORDER_SUBTLE,
+    };
+    pick_list::Style {
+        text_color: TEXT_PRIMARY,
+        placeholder_color: TEXT_MUTED,
+        handle_color: TEXT_SECONDARY,
+        background: BG_ELEVATED.into(),
+        border: Border {
+            color: border_color,
+            width: 1.0,
+            radius: RADIUS_MD.into(),
+        },
+    }
+}
+
 /// Modal overlay background style
 pub fn modal_overlay_style(_theme: &Theme) -> container::Style {
     container::Style::default().background(Color::from_rgba(0.0, 0.0, 0.0, 0.7))
@@ -1,19 +1,43 @@
 //! Update logic for the Shard application.
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
 
+use iced::widget;
 use iced::window;
 use iced::Task;
 
-use crate::config::{Config, KeyboardConfig};
-use crate::db::{self, Palette};
+use crate::config::{
+    BoardHotkey, ColorDedupe, Config, CopyFormat, CopyTemplate, KeyboardConfig, ListLayout,
+    MarkdownText, RunCommand, SecretPolicy,
+};
+use crate::db::{self, worker, Palette, SaveConflict, SaveError, Section};
+use crate::expander::{self, TypedBuffer};
+use crate::export::{parse_gpl, parse_tokens, GplPalette};
+use crate::eyedropper::Screenshot;
 use crate::message::Message;
+use crate::releases::Release;
+use crate::share_image::{render_code_image, ShareImageOptions};
 use crate::snippet::{
-    detect_snippet_type, extract_colors_from_text, language_to_extension, ColorData, Snippet,
-    SnippetContent, SnippetKind,
+    android_colors_xml, code_snippets_file, combined_copy_text, count_image_colors, decode_image,
+    detect_language, detect_snippet_type, export_file_name, extension_to_language,
+    extract_colors_from_text, fill_placeholders, find_placeholders, find_secrets,
+    language_to_extension, linked_file_changed, median_cut, normalize_abbreviation,
+    normalize_description, normalize_icon, pair_variants, parse_bookmarks_html, parse_json,
+    redact_secrets, reformat_code, sanitize_file_stem, shebang_language, short_hash, unix_now,
+    xcassets_files, AssetColor, CodeData, ColorData, ColorSetData, JsonData, PaletteFile,
+    PaletteQuery, PaletteRule, Snippet, SnippetContent, SnippetFilter, SnippetKind,
+    CODE_SNIPPETS_EXTENSION, IMAGE_PALETTE_SIZE, PALETTE_FILE_EXTENSION,
 };
+use crate::view::quick_popup::{QUICK_POPUP_INPUT_ID, QUICK_POPUP_RESULTS};
 use crate::view::{
-    CodeEditorState, ColorPickerState, PickerMode, SettingsState, TextEditorState,
+    filter_palettes, palette_rules, AbbreviationState, BulkDeleteState, CaptureSession,
+    CaptureSessionDraft, CodeEditorState, CodeExportState, ColorPickerState, ContrastState,
+    EditReviewState, EncryptState, GradientEditorState, IconPickerState, JsonTreeState, PickerMode,
+    RegionScanState, RunState, ScaleState, SectionDraft, SettingsState, ShareImageState,
+    SimilarState, TableState, TemplateFillState, TextEditorState, UnlockState,
+    CAPTURE_SESSION_NAME_ID, CODE_EDITOR_SCROLL_ID, MAX_FAVORITES, PALETTE_SEARCH_ID,
+    SECTION_NAME_ID,
 };
 
 /// Represents the kind of window in the application.
@@ -23,6 +47,12 @@ pub enum WindowKind {
     Main,
     /// A pinned snippet window displaying a specific snippet.
     Pinned(i64), // snippet_id
+    /// A pinned palette board listing a palette's snippets.
+    Board(i64), // palette_id
+    /// The fullscreen eyedropper overlay.
+    Eyedropper,
+    /// The quick capture/search popup.
+    QuickPopup,
 }
 
 /// Application state.
@@ -31,11 +61,19 @@ pub struct Shard {
     pub windows: BTreeMap<window::Id, WindowKind>,
     pub snippets: Vec<Snippet>,
     pub is_listening_clipboard: bool,
+    /// Running capture session, and the dialog starting one.
+    pub capture_session: Option<CaptureSession>,
+    pub capture_session_draft: Option<CaptureSessionDraft>,
     pub last_clipboard_content: Option<String>,
     pub status_message: Option<String>,
     pub filter_text: String,
     pub filter_kind: Option<SnippetKind>,
+    pub filter_language: Option<String>,
+    /// Show only snippets captured from this application.
+    pub filter_source_app: Option<String>,
     pub filter_palette: Option<i64>,
+    /// Search text of the second pane (its other filters are in the config).
+    pub split_pane_filter_text: String,
     pub selected_snippet: Option<i64>,
     pub color_picker: Option<ColorPickerState>,
     pub code_editor: Option<CodeEditorState>,
@@ -44,10 +82,91 @@ pub struct Shard {
     pub config: Config,
     pub add_menu_open: bool,
     pub palettes: Vec<Palette>,
+    /// Palettes whose nested palettes are shown in the filter bar.
+    pub expanded_palettes: HashSet<i64>,
     pub palette_manager_open: bool,
     pub palette_dropdown_snippet: Option<i64>,
+    /// Type-to-filter text in the palette dropdown.
+    pub palette_dropdown_query: String,
+    /// Index of the highlighted palette among the filtered dropdown entries.
+    pub palette_dropdown_cursor: usize,
     pub snippet_palettes: std::collections::HashMap<i64, Vec<i64>>,
     pub new_palette_name: String,
+    /// Query for the palette being created; non-empty makes it a smart palette.
+    pub new_palette_query: String,
+    pub pinned_panel_open: bool,
+    /// Uncommitted inline color value edits, keyed by snippet ID.
+    pub color_value_drafts: std::collections::HashMap<i64, String>,
+    /// Snippets open in the external editor, with their revision when opened.
+    pub external_edits: std::collections::HashMap<i64, i64>,
+    /// Edit that collided with a newer stored version, awaiting resolution.
+    pub save_conflict: Option<SaveConflict>,
+    /// External edit shown as a diff before it is saved.
+    pub edit_review: Option<EditReviewState>,
+    /// Filter matches awaiting confirmation before they move to the trash.
+    pub bulk_delete: Option<BulkDeleteState>,
+    /// Section headers in the manual order, highest position first.
+    pub sections: Vec<Section>,
+    pub section_draft: Option<SectionDraft>,
+    /// Whether cards show checkboxes for batch operations.
+    pub selection_mode: bool,
+    /// Snippets checked in selection mode, in the order they were checked.
+    pub selected_snippets: Vec<i64>,
+    /// Language typed into the selection bar for a batch change.
+    pub batch_language: String,
+    pub inbox_open: bool,
+    /// Index of the highlighted snippet in the inbox.
+    pub inbox_cursor: usize,
+    pub table: TableState,
+    pub scratchpad_open: bool,
+    /// What is typed in the quick capture/search popup.
+    pub quick_query: String,
+    /// Scratchpad text, saved to the database when the panel closes.
+    pub scratchpad: iced::widget::text_editor::Content,
+    /// Backup offered for one-click restore after a failed migration or import.
+    pub restore_backup: Option<std::path::PathBuf>,
+    /// Last one-click reformat, offered to undo.
+    pub reformat_undo: Option<ReformatUndo>,
+    /// Crash report left by the last session, offered to open.
+    pub crash_report: Option<std::path::PathBuf>,
+    /// Newer release found by the update check.
+    pub available_update: Option<Release>,
+    pub release_notes_open: bool,
+    pub share_image: Option<ShareImageState>,
+    pub contrast: Option<ContrastState>,
+    pub similar_colors: Option<SimilarState>,
+    /// Placeholder form shown before copying a template snippet.
+    pub template_fill: Option<TemplateFillState>,
+    /// Results of the last code snippet run.
+    pub run_output: Option<RunState>,
+    pub scale: Option<ScaleState>,
+    pub code_export: Option<CodeExportState>,
+    pub gradient_editor: Option<GradientEditorState>,
+    pub region_scan: Option<RegionScanState>,
+    /// Screen captured for the eyedropper overlay while it is open.
+    pub eyedropper: Option<Arc<Screenshot>>,
+    /// Snippet whose card context menu is open.
+    pub card_menu_snippet: Option<i64>,
+    /// Snippet card under the cursor, whose preview is shown after a delay.
+    pub hovered_snippet: Option<i64>,
+    /// Snippets whose linked file was edited or removed since it was linked.
+    pub changed_files: HashSet<i64>,
+    pub icon_picker: Option<IconPickerState>,
+    pub abbreviation_editor: Option<AbbreviationState>,
+    /// Keys last typed in any app, for text expansion.
+    pub typed_keys: TypedBuffer,
+    /// Tree view of a JSON snippet.
+    pub json_tree: Option<JsonTreeState>,
+    /// Passphrase prompt shown while the encrypted database is locked.
+    pub unlock: Option<UnlockState>,
+    pub encrypt_database: Option<EncryptState>,
+    pub database_encrypted: bool,
+    /// Soft-deleted snippets, most recently deleted first.
+    pub trash: Vec<Snippet>,
+    /// Show the trash in place of the snippet list.
+    pub trash_open: bool,
+    /// `.shardpalette` file Shard was opened with, imported once the library loads.
+    pub pending_palette_import: Option<std::path::PathBuf>,
 }
 
 impl Default for Shard {
@@ -56,11 +175,16 @@ impl Default for Shard {
             windows: BTreeMap::new(),
             snippets: Vec::new(),
             is_listening_clipboard: false,
+            capture_session: None,
+            capture_session_draft: None,
             last_clipboard_content: None,
             status_message: None,
             filter_text: String::new(),
             filter_kind: None,
+            filter_language: None,
+            filter_source_app: None,
             filter_palette: None,
+            split_pane_filter_text: String::new(),
             selected_snippet: None,
             color_picker: None,
             code_editor: None,
@@ -69,44 +193,232 @@ impl Default for Shard {
             config: Config::load(),
             add_menu_open: false,
             palettes: Vec::new(),
+            expanded_palettes: HashSet::new(),
             palette_manager_open: false,
             palette_dropdown_snippet: None,
+            palette_dropdown_query: String::new(),
+            palette_dropdown_cursor: 0,
             snippet_palettes: std::collections::HashMap::new(),
             new_palette_name: String::new(),
+            new_palette_query: String::new(),
+            pinned_panel_open: false,
+            color_value_drafts: std::collections::HashMap::new(),
+            external_edits: std::collections::HashMap::new(),
+            save_conflict: None,
+            edit_review: None,
+            bulk_delete: None,
+            sections: Vec::new(),
+            section_draft: None,
+            selection_mode: false,
+            selected_snippets: Vec::new(),
+            batch_language: String::new(),
+            inbox_open: false,
+            inbox_cursor: 0,
+            table: TableState::default(),
+            scratchpad_open: false,
+            scratchpad: iced::widget::text_editor::Content::new(),
+            restore_backup: None,
+            reformat_undo: None,
+            crash_report: None,
+            available_update: None,
+            release_notes_open: false,
+            share_image: None,
+            contrast: None,
+            similar_colors: None,
+            template_fill: None,
+            run_output: None,
+            scale: None,
+            code_export: None,
+            gradient_editor: None,
+            region_scan: None,
+            eyedropper: None,
+            card_menu_snippet: None,
+            hovered_snippet: None,
+            changed_files: HashSet::new(),
+            icon_picker: None,
+            abbreviation_editor: None,
+            typed_keys: TypedBuffer::default(),
+            json_tree: None,
+            unlock: None,
+            encrypt_database: None,
+            database_encrypted: false,
+            trash: Vec::new(),
+            trash_open: false,
+            pending_palette_import: None,
+            quick_query: String::new(),
         }
     }
 }
 
 impl Shard {
+    /// Get the palette new captures are assigned to, if capturing into a palette is on.
+    pub fn capture_palette(&self) -> Option<i64> {
+        self.config
+            .capture_palette
+            .filter(|_| self.config.capture_to_palette)
+            .filter(|id| self.palettes.iter().any(|p| p.id == *id))
+    }
+
+    /// Get the auto-captured snippets awaiting triage, newest first.
+    pub fn inbox_snippets(&self) -> Vec<&Snippet> {
+        self.snippets.iter().filter(|s| s.in_inbox).collect()
+    }
+
+    /// Keep the inbox cursor within the current inbox bounds.
+    fn clamp_inbox_cursor(&mut self) {
+        let len = self.inbox_snippets().len();
+        self.inbox_cursor = self.inbox_cursor.min(len.saturating_sub(1));
+    }
+
+    /// Get the current snippet list filter, given the rules of the filtered palette.
+    pub fn snippet_filter<'a>(
+        &'a self,
+        palette_rules: Option<&'a [PaletteRule<'a>]>,
+    ) -> SnippetFilter<'a> {
+        SnippetFilter {
+            text: &self.filter_text,
+            kind: self.filter_kind.as_ref(),
+            language: self.filter_language.as_deref(),
+            source_app: self.filter_source_app.as_deref(),
+            palette: palette_rules,
+            inbox: false,
+        }
+    }
+
+    /// Rewrite the project snippet files of synced snippets that changed.
+    fn sync_project_files(&self) -> Task<Message> {
+        worker::perform(db::sync_project_files, Message::ProjectFilesSynced)
+    }
+
+    /// Look for linked files that were edited or removed since they were linked.
+    fn check_linked_files(&self) -> Task<Message> {
+        let links: Vec<(i64, String, String)> = self
+            .snippets
+            .iter()
+            .filter_map(|s| Some((s.id, s.file_path.clone()?, s.file_hash.clone()?)))
+            .collect();
+        Task::perform(
+            async move {
+                links
+                    .into_iter()
+                    .filter(|(_, path, hash)| linked_file_changed(std::path::Path::new(path), hash))
+                    .map(|(id, _, _)| id)
+                    .collect()
+            },
+            Message::LinkedFilesChecked,
+        )
+    }
+
+    /// Describe the current filter for confirmations, e.g. `"todo" · Text · in Work`.
+    fn filter_description(&self) -> String {
+        let mut parts = Vec::new();
+        if !self.filter_text.trim().is_empty() {
+            parts.push(format!("\"{}\"", self.filter_text.trim()));
+        }
+        if let Some(kind) = &self.filter_kind {
+            parts.push(kind.display_name().to_string());
+        }
+        if let Some(language) = &self.filter_language {
+            parts.push(language.clone());
+        }
+        if let Some(source_app) = &self.filter_source_app {
+            parts.push(format!("from {}", source_app));
+        }
+        if let Some(palette_id) = self.filter_palette {
+            parts.push(format!(
+                "in {}",
+                db::palette_path(&self.palettes, palette_id)
+            ));
+        }
+        parts.join(" · ")
+    }
+
     /// Create a new application instance.
     /// Opens the main window on startup (daemon mode doesn't open windows automatically).
     pub fn new() -> (Self, Task<Message>) {
-        let load_snippets = Task::perform(async { db::load_snippets() }, Message::SnippetsLoaded);
-        let load_palettes = Task::perform(async { db::load_palettes() }, Message::PalettesLoaded);
-        
-        // Open main window (daemon mode requires explicit window creation)
-        let (main_window_id, open_main) = window::open(window::Settings {
-            size: iced::Size::new(900.0, 700.0),
-            position: window::Position::Centered,
-            ..window::Settings::default()
-        });
-        
         // Create state with main window tracked
-        let mut state = Self::default();
-        state.windows.insert(main_window_id, WindowKind::Main);
-        
+        let mut state = Self {
+            // Opening a shared palette file (e.g. by double-clicking it) imports it.
+            // Only Linux and Windows pass the file as an argument; macOS sends an
+            // open-document event, which isn't handled (see the README)
+            pending_palette_import: std::env::args_os()
+                .skip(1)
+                .map(std::path::PathBuf::from)
+                .find(|path| {
+                    path.extension()
+                        .is_some_and(|ext| ext == PALETTE_FILE_EXTENSION)
+                }),
+            ..Self::default()
+        };
+        crate::crash::set_enabled(state.config.crash_reports);
+        state.crash_report = crate::crash::pending_report();
+        db::set_database_dir(state.config.database_path.clone());
+        state.database_encrypted = db::is_encrypted();
+
+        // An encrypted library is loaded once the passphrase is entered
+        let load_library = if db::needs_unlock() {
+            state.unlock = Some(UnlockState::default());
+            Task::none()
+        } else {
+            state.load_library()
+        };
+
+        // Open main window (daemon mode requires explicit window creation)
+        let open_main = state.open_main_window();
+
+        let check_for_update = if state.config.check_for_updates {
+            check_for_update()
+        } else {
+            Task::none()
+        };
+
         (
             state,
-            Task::batch([
-                load_snippets,
-                load_palettes,
-                open_main.map(Message::WindowOpened),
-            ]),
+            Task::batch([load_library, open_main, check_for_update]),
         )
     }
 
+    /// Load snippets, palettes, sections, the scratchpad and the trash from the
+    /// database, then import the palette file Shard was opened with, if any.
+    fn load_library(&mut self) -> Task<Message> {
+        let load_snippets = worker::perform(db::load_snippets, Message::SnippetsLoaded);
+        let load_palettes = worker::perform(db::load_palettes, Message::PalettesLoaded);
+        let load_sections = worker::perform(db::load_sections, Message::SectionsLoaded);
+        let load_scratchpad = worker::perform(db::load_scratchpad, Message::ScratchpadLoaded);
+
+        // Purge expired trash before loading it
+        let retention_days = self.config.trash_retention.days();
+        let load_trash = worker::perform(
+            move || {
+                if let Some(days) = retention_days {
+                    db::purge_trash(days)?;
+                }
+                db::load_trash()
+            },
+            Message::TrashLoaded,
+        );
+
+        let import_palette = match self.pending_palette_import.take() {
+            Some(path) => Task::perform(
+                import_palette_file(Some(path), self.config.color_dedupe),
+                Message::ImportFinished,
+            ),
+            None => Task::none(),
+        };
+
+        Task::batch([
+            load_snippets,
+            load_palettes,
+            load_sections,
+            load_scratchpad,
+            load_trash,
+            import_palette,
+        ])
+    }
+
     /// Handle application messages.
     pub fn update(&mut self, message: Message) -> Task<Message> {
+        crate::crash::record(&message);
         match message {
             Message::SnippetsLoaded(result) => {
                 match result {
@@ -114,15 +426,31 @@ impl Shard {
                         self.status_message = Some(format!("{} snippets loaded", snippets.len()));
                         self.snippets = snippets;
                         // Load palette assignments for all snippets
-                        return self.load_all_snippet_palettes();
+                        return Task::batch([
+                            self.load_all_snippet_palettes(),
+                            self.check_linked_files(),
+                            self.sync_project_files(),
+                        ]);
                     }
                     Err(e) => {
                         self.status_message = Some(format!("Load error: {}", e));
+                        // A migration may have failed after taking a backup
+                        if self.restore_backup.is_none() {
+                            self.restore_backup = db::migration_backup();
+                        }
                     }
                 }
                 Task::none()
             }
 
+            Message::SnippetPalettesLoaded(result) => {
+                match result {
+                    Ok(snippet_palettes) => self.snippet_palettes = snippet_palettes,
+                    Err(e) => self.status_message = Some(format!("Load error: {}", e)),
+                }
+                Task::none()
+            }
+
             Message::SnippetAdded(result) => {
                 match result {
                     Ok(snippet) => {
@@ -139,22 +467,232 @@ impl Shard {
                 Task::none()
             }
 
-            Message::CopySnippet(id) => {
-                if let Some(snippet) = self.snippets.iter().find(|s| s.id == id) {
-                    let text = snippet.content.to_copyable_string();
+            Message::SnippetCaptured(result) => {
+                let captured_id = result.as_ref().ok().map(|snippet| snippet.id);
+                // Name the language of captured code, and the #! line it came from
+                let captured_language = result.as_ref().ok().and_then(|snippet| {
+                    let SnippetContent::Code(code) = &snippet.content else {
+                        return None;
+                    };
+                    Some(match shebang_language(&code.code) {
+                        Some(language) if language == code.language => {
+                            format!("Captured {} code (from its #! line)", language)
+                        }
+                        _ => format!("Captured {} code", code.language),
+                    })
+                });
+                let task = self.update(Message::SnippetAdded(result));
+                let Some(snippet_id) = captured_id else {
+                    return task;
+                };
+                if captured_language.is_some() {
+                    self.status_message = captured_language;
+                }
+                let session_palette = self.capture_session.as_mut().map(|session| {
+                    session.captured += 1;
+                    session.palette_id
+                });
+                let mut palette_ids: Vec<i64> = self
+                    .capture_palette()
+                    .into_iter()
+                    .chain(session_palette)
+                    .filter(|palette_id| {
+                        !self
+                            .snippet_palettes
+                            .get(&snippet_id)
+                            .is_some_and(|ids| ids.contains(palette_id))
+                    })
+                    .collect();
+                palette_ids.dedup();
+                let mut tasks = vec![task];
+                for palette_id in palette_ids {
+                    tasks.push(self.update(Message::AddSnippetToPalette(snippet_id, palette_id)));
+                }
+                Task::batch(tasks)
+            }
+
+            Message::PickFilesToAttach => {
+                self.add_menu_open = false;
+                Task::perform(
+                    async {
+                        rfd::AsyncFileDialog::new()
+                            .set_title("Attach Files")
+                            .pick_files()
+                            .await
+                            .map(|files| files.iter().map(|f| f.path().to_path_buf()).collect())
+                            .unwrap_or_default()
+                    },
+                    Message::AttachFiles,
+                )
+            }
+
+            Message::AttachFiles(paths) => Task::batch(paths.into_iter().map(|path| {
+                worker::perform(move || db::attach_file(path, None), Message::SnippetAdded)
+            })),
+
+            Message::FileDropped(path) => {
+                if self
+                    .code_editor
+                    .as_ref()
+                    .is_some_and(|editor| !editor.is_json)
+                {
+                    // Reading the file isn't a database call, so it stays off the DB worker
                     Task::perform(
-                        async move { copy_to_clipboard(&text).await },
-                        Message::CopyFinished,
+                        async move {
+                            tokio::task::spawn_blocking(move || read_dropped_code(&path))
+                                .await
+                                .map_err(|e| e.to_string())?
+                        },
+                        Message::DroppedCodeRead,
                     )
                 } else {
-                    Task::none()
+                    self.update(Message::AttachFiles(vec![path]))
+                }
+            }
+
+            Message::DroppedCodeRead(result) => {
+                let Some(editor) = &mut self.code_editor else {
+                    return Task::none();
+                };
+                match result {
+                    Ok((code, extension_language)) => {
+                        let was_empty = editor.content.text().trim().is_empty();
+                        editor.content.perform(widget::text_editor::Action::Edit(
+                            widget::text_editor::Edit::Paste(Arc::new(code.clone())),
+                        ));
+                        // The extension names the language; without one, the
+                        // code does when it is all there is
+                        let language = extension_language.or_else(|| {
+                            was_empty
+                                .then(|| detect_language(&code))
+                                .filter(|language| language != "plain")
+                        });
+                        if let Some(language) = language {
+                            self.status_message =
+                                Some(format!("Pasted the dropped file as {}", language));
+                            editor.language = language;
+                        }
+                    }
+                    Err(e) => self.status_message = Some(e),
                 }
+                Task::none()
             }
 
+            Message::ToggleCapturePalette => {
+                self.config.capture_to_palette = !self.config.capture_to_palette;
+                let config = self.config.clone();
+                Task::perform(async move { config.save() }, Message::ConfigSaved)
+            }
+
+            Message::CopySnippet(id) => {
+                self.copy_snippet(id, self.config.copy_format == CopyFormat::Markdown)
+            }
+
+            Message::CopyMarkdown(id) => self.copy_snippet(id, true),
+
             Message::CopyHex(id) => self.copy_color_format(id, |c| c.to_hex()),
             Message::CopyRgb(id) => self.copy_color_format(id, |c| c.to_rgb()),
             Message::CopyHsl(id) => self.copy_color_format(id, |c| c.to_hsl()),
+            Message::CopyHsv(id) => self.copy_color_format(id, |c| c.to_hsv()),
             Message::CopyOklch(id) => self.copy_color_format(id, |c| c.to_oklch()),
+            Message::CopyLab(id) => self.copy_color_format(id, |c| c.to_lab()),
+            Message::CopyLch(id) => self.copy_color_format(id, |c| c.to_lch()),
+            Message::CopyDisplayP3(id) => self.copy_color_format(id, |c| c.to_display_p3()),
+            Message::CopyCmyk(id) => self.copy_color_format(id, |c| c.to_cmyk()),
+            Message::CopySwiftUi(id) => self.copy_color_format(id, |c| c.to_swiftui()),
+            Message::CopyFlutter(id) | Message::CopyCompose(id) => {
+                self.copy_color_format(id, |c| c.to_argb_literal())
+            }
+            Message::CopyIced(id) => self.copy_color_format(id, |c| c.to_iced_literal()),
+
+            Message::CopyColorTemplate(id, index) => {
+                let Some(template) = self.config.copy_templates.get(index) else {
+                    return Task::none();
+                };
+                let Some(text) =
+                    self.snippets
+                        .iter()
+                        .find(|s| s.id == id)
+                        .and_then(|s| match &s.content {
+                            SnippetContent::Color(color) => Some(template.render(&s.label, color)),
+                            _ => None,
+                        })
+                else {
+                    return Task::none();
+                };
+                self.copy_snippet_text(id, text)
+            }
+
+            Message::AddInlineColor(color) => {
+                let label = color.to_hex();
+                let dedupe = self.config.color_dedupe;
+                worker::perform(
+                    move || {
+                        db::add_or_move_color(color.r, color.g, color.b, color.a, label, dedupe)
+                    },
+                    Message::SnippetAdded,
+                )
+            }
+
+            Message::CopyRichText(id) => {
+                let Some((text, html)) =
+                    self.snippets
+                        .iter()
+                        .find(|s| s.id == id)
+                        .and_then(|s| match &s.content {
+                            SnippetContent::Text(data) => {
+                                Some((data.text.clone(), data.html.clone()?))
+                            }
+                            _ => None,
+                        })
+                else {
+                    return Task::none();
+                };
+                self.copy_snippet_content(id, text, Some(html))
+            }
+
+            Message::CopySetColor(id, index) => {
+                let hex = self
+                    .snippets
+                    .iter()
+                    .find(|s| s.id == id)
+                    .and_then(|s| match &s.content {
+                        SnippetContent::ColorSet(set) => set.colors.get(index),
+                        _ => None,
+                    })
+                    .map(ColorData::to_hex);
+                match hex {
+                    Some(hex) => self.copy_snippet_text(id, hex),
+                    None => Task::none(),
+                }
+            }
+
+            Message::SplitColorSet(id) => worker::perform(
+                move || db::split_color_set(id).map(|colors| (id, colors)),
+                Message::ColorSetSplit,
+            ),
+
+            Message::ColorSetSplit(result) => {
+                match result {
+                    Ok((id, colors)) => {
+                        if let Some(index) = self.snippets.iter().position(|s| s.id == id) {
+                            let mut set = self.snippets.remove(index);
+                            set.deleted_at = Some(unix_now());
+                            self.trash.insert(0, set);
+                        }
+                        if self.selected_snippet == Some(id) {
+                            self.selected_snippet = None;
+                        }
+                        self.status_message = Some(format!(
+                            "Split into {} colors (the set is in the trash)",
+                            colors.len()
+                        ));
+                        self.snippets.splice(0..0, colors);
+                    }
+                    Err(e) => self.status_message = Some(format!("Split failed: {}", e)),
+                }
+                Task::none()
+            }
 
             Message::CopyFinished(result) => {
                 match result {
@@ -164,16 +702,28 @@ impl Shard {
                 Task::none()
             }
 
-            Message::DeleteSnippet(id) => Task::perform(
-                async move { db::delete_snippet(id) },
-                Message::SnippetDeleted,
-            ),
+            Message::CopyRecorded(result) => {
+                if let Err(e) = result {
+                    self.status_message = Some(format!("Usage tracking failed: {}", e));
+                }
+                Task::none()
+            }
+
+            Message::DeleteSnippet(id) => {
+                worker::perform(move || db::trash_snippet(id), Message::SnippetDeleted)
+            }
 
             Message::SnippetDeleted(result) => {
                 match result {
                     Ok(id) => {
-                        self.snippets.retain(|s| s.id != id);
-                        self.status_message = Some("Snippet deleted".to_string());
+                        if let Some(index) = self.snippets.iter().position(|s| s.id == id) {
+                            let mut snippet = self.snippets.remove(index);
+                            snippet.deleted_at = Some(unix_now());
+                            self.trash.insert(0, snippet);
+                        }
+                        self.clamp_inbox_cursor();
+                        self.status_message = Some("Moved to trash".to_string());
+                        return self.sync_project_files();
                     }
                     Err(e) => {
                         self.status_message = Some(format!("Delete failed: {}", e));
@@ -182,674 +732,3591 @@ impl Shard {
                 Task::none()
             }
 
-            Message::SelectSnippet(id) => {
-                self.selected_snippet = id;
+            // === Favorites & Card Menu ===
+            Message::ToggleCardMenu(id) => {
+                self.card_menu_snippet = id;
                 Task::none()
             }
 
-            Message::OpenInExternalEditor(id, is_code) => {
-                // Find the snippet content
-                let Some(snippet) = self.snippets.iter().find(|s| s.id == id) else {
-                    self.status_message = Some("Snippet not found".to_string());
-                    return Task::none();
-                };
-
-                let content = match &snippet.content {
-                    SnippetContent::Code(code) => code.code.clone(),
-                    SnippetContent::Text(text) => text.text.clone(),
-                    SnippetContent::Color(_) => {
-                        self.status_message = Some("Cannot open colors in editor".to_string());
-                        return Task::none();
-                    }
-                };
+            Message::CardMenuSelected(action) => {
+                self.card_menu_snippet = None;
+                self.update(*action)
+            }
 
-                // Get file extension
-                let extension = if is_code {
-                    if let SnippetContent::Code(code) = &snippet.content {
-                        language_to_extension(&code.language)
-                    } else {
-                        "txt"
-                    }
-                } else {
-                    "txt"
-                };
+            Message::SnippetHovered(id) => {
+                self.hovered_snippet = Some(id);
+                Task::none()
+            }
 
-                let config = self.config.clone();
-                self.status_message = Some("Opening in external editor...".to_string());
+            // Entering the next card may arrive before leaving the previous one
+            Message::SnippetUnhovered(id) => {
+                if self.hovered_snippet == Some(id) {
+                    self.hovered_snippet = None;
+                }
+                Task::none()
+            }
 
-                Task::perform(
-                    async move {
-                        open_in_external_editor(id, &content, extension, is_code, &config).await
-                    },
-                    Message::ExternalEditorClosed,
+            Message::SetFavorite(id, favorite) => {
+                let favorites = self
+                    .snippets
+                    .iter()
+                    .filter(|s| s.favorite_at.is_some())
+                    .count();
+                if favorite && favorites >= MAX_FAVORITES {
+                    self.status_message = Some(format!(
+                        "The favorites bar holds up to {} snippets",
+                        MAX_FAVORITES
+                    ));
+                    return Task::none();
+                }
+                worker::perform(
+                    move || db::set_favorite(id, favorite),
+                    Message::FavoriteUpdated,
                 )
             }
 
-            Message::ExternalEditorClosed(result) => {
+            Message::FavoriteUpdated(result) => {
                 match result {
-                    Ok((id, new_content, is_code)) => {
-                        // Update the snippet in memory and database
-                        if is_code {
-                            let content = new_content.clone();
-                            return Task::perform(
-                                async move {
-                                    // Get existing snippet to preserve language and label
-                                    db::update_code_content(id, content)
-                                },
-                                Message::SnippetUpdated,
-                            );
+                    Ok(snippet) => {
+                        self.status_message = Some(if snippet.favorite_at.is_some() {
+                            "Added to favorites".to_string()
                         } else {
-                            let content = new_content.clone();
-                            return Task::perform(
-                                async move { db::update_text_content(id, content) },
-                                Message::SnippetUpdated,
-                            );
+                            "Removed from favorites".to_string()
+                        });
+                        if let Some(existing) =
+                            self.snippets.iter_mut().find(|s| s.id == snippet.id)
+                        {
+                            *existing = snippet;
                         }
                     }
-                    Err(e) => {
-                        self.status_message = Some(format!("Editor error: {}", e));
-                    }
+                    Err(e) => self.status_message = Some(format!("Favorite failed: {}", e)),
                 }
                 Task::none()
             }
 
-            Message::ToggleClipboard(enabled) => {
-                self.is_listening_clipboard = enabled;
-                if enabled {
-                    self.status_message = Some("Clipboard listening enabled".to_string());
-                } else {
-                    self.status_message = Some("Clipboard listening disabled".to_string());
+            // === Snippet Icons ===
+            Message::OpenIconPicker(id) => {
+                self.icon_picker = self
+                    .snippets
+                    .iter()
+                    .find(|s| s.id == id)
+                    .map(IconPickerState::new);
+                Task::none()
+            }
+
+            Message::CloseIconPicker => {
+                self.icon_picker = None;
+                Task::none()
+            }
+
+            Message::IconEmojiChanged(emoji) => {
+                if let Some(state) = &mut self.icon_picker {
+                    state.emoji = emoji;
                 }
                 Task::none()
             }
 
-            Message::ClipboardTick => Task::perform(
-                async {
-                    match arboard::Clipboard::new() {
-                        Ok(mut clipboard) => clipboard.get_text().ok(),
-                        Err(_) => None,
+            Message::SubmitIconEmoji => {
+                let Some(state) = &self.icon_picker else {
+                    return Task::none();
+                };
+                match normalize_icon(&state.emoji) {
+                    Some(icon) => {
+                        self.update(Message::SetSnippetIcon(state.snippet_id, Some(icon)))
                     }
-                },
-                Message::ClipboardContentReceived,
-            ),
+                    None => Task::none(),
+                }
+            }
 
-            Message::ClipboardContentReceived(content) => {
-                if let Some(text) = content {
-                    if !text.is_empty() && Some(&text) != self.last_clipboard_content.as_ref() {
-                        self.last_clipboard_content = Some(text.clone());
+            Message::SetSnippetIcon(id, icon) => {
+                worker::perform(move || db::set_icon(id, icon), Message::SnippetIconSet)
+            }
 
-                        // Detect snippet type and add accordingly
-                        if let Some(kind) = detect_snippet_type(&text) {
-                            match kind {
-                                SnippetKind::Color => {
-                                    let colors = extract_colors_from_text(&text);
-                                    if let Some(color) = colors.into_iter().next() {
-                                        let label = color.to_hex();
-                                        return Task::perform(
-                                            async move {
-                                                db::add_or_move_color(
-                                                    color.r, color.g, color.b, color.a, label,
-                                                )
-                                            },
-                                            Message::SnippetAdded,
-                                        );
-                                    }
-                                }
-                                SnippetKind::Code => {
-                                    let code = text.clone();
-                                    return Task::perform(
-                                        async move {
-                                            db::add_code_snippet(code, String::new(), String::new())
-                                        },
-                                        Message::SnippetAdded,
-                                    );
-                                }
-                                SnippetKind::Text => {
-                                    let text_content = text.clone();
-                                    return Task::perform(
-                                        async move { db::add_text_snippet(text_content, String::new()) },
-                                        Message::SnippetAdded,
-                                    );
-                                }
-                            }
+            Message::SnippetIconSet(result) => {
+                match result {
+                    Ok(snippet) => {
+                        self.status_message = Some(if snippet.icon.is_some() {
+                            "Icon set".to_string()
+                        } else {
+                            "Icon removed".to_string()
+                        });
+                        self.icon_picker = None;
+                        if let Some(existing) =
+                            self.snippets.iter_mut().find(|s| s.id == snippet.id)
+                        {
+                            *existing = snippet;
                         }
                     }
+                    Err(e) => self.status_message = Some(format!("Icon update failed: {}", e)),
                 }
                 Task::none()
             }
 
-            Message::FilterChanged(text) => {
-                self.filter_text = text;
+            // === Text Expander ===
+            Message::OpenAbbreviationEditor(id) => {
+                self.abbreviation_editor = self
+                    .snippets
+                    .iter()
+                    .find(|s| s.id == id)
+                    .map(AbbreviationState::new);
                 Task::none()
             }
 
-            Message::FilterKindChanged(kind) => {
-                self.filter_kind = kind;
+            Message::CloseAbbreviationEditor => {
+                self.abbreviation_editor = None;
                 Task::none()
             }
 
-            Message::PasteFromClipboard => Task::perform(
-                async {
-                    match arboard::Clipboard::new() {
-                        Ok(mut clipboard) => clipboard.get_text().ok(),
-                        Err(_) => None,
+            Message::AbbreviationChanged(abbreviation) => {
+                if let Some(state) = &mut self.abbreviation_editor {
+                    state.abbreviation = abbreviation;
+                    state.error = None;
+                }
+                Task::none()
+            }
+
+            Message::SubmitAbbreviation => {
+                let Some(state) = &mut self.abbreviation_editor else {
+                    return Task::none();
+                };
+                let abbreviation = match normalize_abbreviation(&state.abbreviation) {
+                    Ok(abbreviation) => abbreviation,
+                    Err(e) => {
+                        state.error = Some(e);
+                        return Task::none();
                     }
-                },
-                Message::PasteContentReceived,
+                };
+                // Each abbreviation expands into one snippet
+                let taken = self.snippets.iter().find(|s| {
+                    s.id != state.snippet_id
+                        && abbreviation.is_some()
+                        && s.abbreviation == abbreviation
+                });
+                if let Some(other) = taken {
+                    state.error = Some(format!("Already used by \"{}\"", other.label));
+                    return Task::none();
+                }
+                let id = state.snippet_id;
+                self.update(Message::SetSnippetAbbreviation(id, abbreviation))
+            }
+
+            Message::SetSnippetAbbreviation(id, abbreviation) => worker::perform(
+                move || db::set_abbreviation(id, abbreviation),
+                Message::SnippetAbbreviationSet,
             ),
 
-            Message::PasteContentReceived(content) => {
-                if let Some(text) = content {
-                    if !text.is_empty() {
-                        // Detect snippet type and add accordingly
-                        if let Some(kind) = detect_snippet_type(&text) {
-                            match kind {
-                                SnippetKind::Color => {
-                                    let colors = extract_colors_from_text(&text);
-                                    if let Some(color) = colors.into_iter().next() {
-                                        let label = color.to_hex();
-                                        return Task::perform(
-                                            async move {
-                                                db::add_or_move_color(
-                                                    color.r, color.g, color.b, color.a, label,
-                                                )
-                                            },
-                                            Message::SnippetAdded,
-                                        );
-                                    }
-                                }
-                                SnippetKind::Code => {
-                                    let code = text.clone();
-                                    return Task::perform(
-                                        async move {
-                                            db::add_code_snippet(code, String::new(), String::new())
-                                        },
-                                        Message::SnippetAdded,
-                                    );
-                                }
-                                SnippetKind::Text => {
-                                    let text_content = text.clone();
-                                    return Task::perform(
-                                        async move { db::add_text_snippet(text_content, String::new()) },
-                                        Message::SnippetAdded,
-                                    );
-                                }
-                            }
+            Message::SnippetAbbreviationSet(result) => {
+                match result {
+                    Ok(snippet) => {
+                        self.status_message = Some(match &snippet.abbreviation {
+                            Some(abbreviation) => format!("Abbreviation set to {}", abbreviation),
+                            None => "Abbreviation removed".to_string(),
+                        });
+                        self.abbreviation_editor = None;
+                        if let Some(existing) =
+                            self.snippets.iter_mut().find(|s| s.id == snippet.id)
+                        {
+                            *existing = snippet;
                         }
                     }
+                    Err(e) => {
+                        self.status_message = Some(format!("Abbreviation update failed: {}", e))
+                    }
                 }
                 Task::none()
             }
 
-            Message::EscapePressed => {
-                // Priority: close modals/menus > clear filter > deselect
-                if self.add_menu_open {
-                    self.add_menu_open = false;
-                } else if self.settings.is_some() {
-                    self.settings = None;
-                } else if self.color_picker.is_some() {
-                    self.color_picker = None;
-                } else if self.code_editor.is_some() {
-                    self.code_editor = None;
-                } else if self.text_editor.is_some() {
-                    self.text_editor = None;
-                } else if !self.filter_text.is_empty() {
-                    self.filter_text.clear();
+            Message::ExpanderKey(key) => {
+                // The listener also runs for board hotkeys
+                if !self.config.text_expansion {
+                    return Task::none();
+                }
+                self.typed_keys.handle(key);
+                let abbreviations = self
+                    .snippets
+                    .iter()
+                    .filter_map(|s| s.abbreviation.as_deref());
+                let Some(abbreviation) = self.typed_keys.ending(abbreviations) else {
+                    return Task::none();
+                };
+                let Some(snippet) = self
+                    .snippets
+                    .iter()
+                    .find(|s| s.abbreviation.as_deref() == Some(abbreviation))
+                else {
+                    return Task::none();
+                };
+                let erase = abbreviation.chars().count();
+                // Placeholders take their defaults, as there is nowhere to ask
+                let text =
+                    fill_placeholders(&snippet.content.to_copyable_string(), &HashMap::new());
+                self.typed_keys.clear();
+                Task::perform(
+                    async move { expander::expand(erase, &text) },
+                    Message::AbbreviationExpanded,
+                )
+            }
+
+            Message::ExpanderFailed(e) => {
+                let feature = if self.config.text_expansion {
+                    "Text expansion"
                 } else {
-                    self.selected_snippet = None;
+                    "Board hotkeys"
+                };
+                self.status_message = Some(format!("{} not working: {}", feature, e));
+                Task::none()
+            }
+
+            Message::AbbreviationExpanded(result) => {
+                if let Err(e) = result {
+                    self.status_message = Some(format!("Expansion failed: {}", e));
                 }
                 Task::none()
             }
 
-            Message::DeleteSelectedSnippet => {
-                if let Some(id) = self.selected_snippet {
-                    self.selected_snippet = None;
-                    Task::perform(
-                        async move { db::delete_snippet(id) },
-                        Message::SnippetDeleted,
-                    )
-                } else {
-                    Task::none()
+            // === Multi-select ===
+            Message::ToggleSelectionMode => {
+                self.selection_mode = !self.selection_mode;
+                self.selected_snippets.clear();
+                self.batch_language.clear();
+                Task::none()
+            }
+
+            Message::ToggleSnippetChecked(id) => {
+                match self
+                    .selected_snippets
+                    .iter()
+                    .position(|checked| *checked == id)
+                {
+                    Some(index) => {
+                        self.selected_snippets.remove(index);
+                    }
+                    None => self.selected_snippets.push(id),
                 }
+                Task::none()
             }
 
-            Message::CopySelectedSnippet => {
-                if let Some(id) = self.selected_snippet {
-                    if let Some(snippet) = self.snippets.iter().find(|s| s.id == id) {
-                        let text = snippet.content.to_copyable_string();
-                        Task::perform(
-                            async move { copy_to_clipboard(&text).await },
-                            Message::CopyFinished,
-                        )
-                    } else {
-                        Task::none()
+            Message::SelectAllVisible => {
+                let rules = palette_rules(&self.palettes, self.filter_palette);
+                let filter = self.snippet_filter(rules.as_deref());
+                let visible: Vec<i64> = self
+                    .snippets
+                    .iter()
+                    .filter(|s| filter.matches(s, &self.snippet_palettes))
+                    .filter(|s| !self.selected_snippets.contains(&s.id))
+                    .map(|s| s.id)
+                    .collect();
+                self.selected_snippets.extend(visible);
+                Task::none()
+            }
+
+            Message::ClearSelection => {
+                self.selected_snippets.clear();
+                Task::none()
+            }
+
+            Message::BatchDelete => {
+                let ids = std::mem::take(&mut self.selected_snippets);
+                if ids.is_empty() {
+                    return Task::none();
+                }
+                worker::perform(move || db::trash_snippets(ids), Message::SnippetsDeleted)
+            }
+
+            Message::BatchAddToPalette(palette_id) => {
+                let ids = self.selected_snippets.clone();
+                if ids.is_empty() {
+                    return Task::none();
+                }
+                worker::perform(
+                    move || db::add_snippets_to_palette(palette_id, ids),
+                    Message::BatchAddedToPalette,
+                )
+            }
+
+            Message::BatchAddedToPalette(result) => {
+                match result {
+                    Ok((palette_id, ids)) => {
+                        for id in &ids {
+                            let palette_ids = self.snippet_palettes.entry(*id).or_default();
+                            if !palette_ids.contains(&palette_id) {
+                                palette_ids.push(palette_id);
+                            }
+                        }
+                        let name = db::palette_path(&self.palettes, palette_id);
+                        self.status_message =
+                            Some(format!("Added {} snippets to {}", ids.len(), name));
                     }
-                } else {
-                    Task::none()
+                    Err(e) => self.status_message = Some(format!("Add to palette failed: {}", e)),
                 }
+                Task::none()
             }
 
-            // === Color Picker Messages ===
-            Message::OpenColorPicker(id) => {
-                self.add_menu_open = false;
-                let default_mode = self.config.default_picker_mode;
-                self.color_picker = Some(if let Some(snippet_id) = id {
-                    // Edit existing color
-                    if let Some(snippet) = self.snippets.iter().find(|s| s.id == snippet_id) {
-                        ColorPickerState::from_snippet(snippet, default_mode)
-                    } else {
-                        ColorPickerState::new_color(default_mode)
+            Message::BatchExport => {
+                let snippets: Vec<Snippet> = self
+                    .snippets
+                    .iter()
+                    .filter(|s| self.selected_snippets.contains(&s.id))
+                    .cloned()
+                    .collect();
+                if snippets.is_empty() {
+                    return Task::none();
+                }
+                Task::perform(export_snippets_json(snippets), Message::ExportFinished)
+            }
+
+            Message::BatchCopyCombined => {
+                let snippets: Vec<&Snippet> = self
+                    .selected_snippets
+                    .iter()
+                    .filter_map(|id| self.snippets.iter().find(|s| s.id == *id))
+                    .collect();
+                if snippets.is_empty() {
+                    return Task::none();
+                }
+                let count = snippets.len();
+                let text = combined_copy_text(&snippets, self.config.combine_separator.as_str());
+                Task::perform(
+                    async move {
+                        copy_to_clipboard(&text)
+                            .await
+                            .map(|_| format!("Copied {} snippets combined", count))
+                    },
+                    Message::CopyFinished,
+                )
+            }
+
+            Message::BatchCombineColors => {
+                // Keep the list order, top first
+                let ids: Vec<i64> = self
+                    .snippets
+                    .iter()
+                    .filter(|s| self.selected_snippets.contains(&s.id))
+                    .filter(|s| s.kind() == SnippetKind::Color)
+                    .map(|s| s.id)
+                    .collect();
+                if ids.len() < 2 {
+                    self.status_message = Some("Select at least two colors".to_string());
+                    return Task::none();
+                }
+                let label = format!("{} colors", ids.len());
+                worker::perform(
+                    move || db::combine_colors(ids, label),
+                    Message::SnippetAdded,
+                )
+            }
+
+            Message::BatchSyncToProject => {
+                let ids: Vec<i64> = self
+                    .snippets
+                    .iter()
+                    .filter(|s| self.selected_snippets.contains(&s.id))
+                    .filter(|s| s.kind() == SnippetKind::Code)
+                    .map(|s| s.id)
+                    .collect();
+                if ids.is_empty() {
+                    self.status_message = Some("Select code snippets to sync".to_string());
+                    return Task::none();
+                }
+                Task::perform(sync_to_project(ids), Message::ProjectSynced)
+            }
+
+            Message::ProjectSynced(result) => {
+                match result {
+                    Ok(msg) => self.status_message = Some(msg),
+                    Err(e) => self.status_message = Some(format!("Sync failed: {}", e)),
+                }
+                Task::none()
+            }
+
+            Message::ProjectFilesSynced(result) => {
+                if let Err(e) = result {
+                    self.status_message = Some(format!("Project sync failed: {}", e));
+                }
+                Task::none()
+            }
+
+            Message::BatchLanguageInput(language) => {
+                self.batch_language = language;
+                Task::none()
+            }
+
+            Message::BatchSetLanguage => {
+                let language = self.batch_language.trim().to_lowercase();
+                let ids = self.selected_snippets.clone();
+                if language.is_empty() || ids.is_empty() {
+                    return Task::none();
+                }
+                worker::perform(
+                    move || db::set_code_language(ids, language),
+                    Message::BatchLanguageSet,
+                )
+            }
+
+            Message::BatchLanguageSet(result) => {
+                match result {
+                    Ok(updated) => {
+                        self.status_message = Some(format!(
+                            "Changed the language of {} snippets",
+                            updated.len()
+                        ));
+                        for snippet in updated {
+                            if let Some(existing) =
+                                self.snippets.iter_mut().find(|s| s.id == snippet.id)
+                            {
+                                *existing = snippet;
+                            }
+                        }
+                        self.batch_language.clear();
+                        return self.sync_project_files();
                     }
-                } else {
-                    ColorPickerState::new_color(default_mode)
-                });
+                    Err(e) => self.status_message = Some(format!("Language change failed: {}", e)),
+                }
                 Task::none()
             }
 
-            Message::CloseColorPicker => {
-                self.color_picker = None;
+            // === Trash ===
+            Message::ShowTrash => {
+                self.trash_open = true;
                 Task::none()
             }
 
-            Message::PickerHueChanged(hue) => {
-                if let Some(picker) = &mut self.color_picker {
-                    picker.hue = hue;
+            Message::TrashLoaded(result) => {
+                match result {
+                    Ok(trash) => self.trash = trash,
+                    Err(e) => self.status_message = Some(format!("Trash load failed: {}", e)),
                 }
                 Task::none()
             }
 
-            Message::PickerSaturationChanged(saturation) => {
-                if let Some(picker) = &mut self.color_picker {
-                    picker.saturation = saturation;
+            Message::RestoreSnippet(id) => {
+                worker::perform(move || db::restore_snippet(id), Message::SnippetRestored)
+            }
+
+            Message::SnippetRestored(result) => {
+                match result {
+                    Ok(snippet) => {
+                        self.trash.retain(|s| s.id != snippet.id);
+                        // Keep the list ordered by position, highest first
+                        let index = self
+                            .snippets
+                            .iter()
+                            .position(|s| s.position < snippet.position)
+                            .unwrap_or(self.snippets.len());
+                        self.status_message = Some(format!("Restored '{}'", snippet.label));
+                        self.snippets.insert(index, snippet);
+                        return self.sync_project_files();
+                    }
+                    Err(e) => {
+                        self.status_message = Some(format!("Restore failed: {}", e));
+                    }
                 }
                 Task::none()
             }
 
-            Message::PickerLightnessChanged(lightness) => {
-                if let Some(picker) = &mut self.color_picker {
-                    picker.lightness = lightness;
+            Message::OpenBulkDelete => {
+                let rules = palette_rules(&self.palettes, self.filter_palette);
+                let filter = self.snippet_filter(rules.as_deref());
+                let snippet_ids: Vec<i64> = self
+                    .snippets
+                    .iter()
+                    .filter(|s| filter.matches(s, &self.snippet_palettes))
+                    .map(|s| s.id)
+                    .collect();
+                if snippet_ids.is_empty() {
+                    return Task::none();
                 }
+                self.bulk_delete = Some(BulkDeleteState {
+                    snippet_ids,
+                    description: self.filter_description(),
+                });
                 Task::none()
             }
 
-            Message::PickerSLChanged(saturation, lightness) => {
-                if let Some(picker) = &mut self.color_picker {
-                    picker.saturation = saturation;
-                    picker.lightness = lightness;
+            Message::CancelBulkDelete => {
+                self.bulk_delete = None;
+                Task::none()
+            }
+
+            Message::ConfirmBulkDelete => match self.bulk_delete.take() {
+                Some(state) => worker::perform(
+                    move || db::trash_snippets(state.snippet_ids),
+                    Message::SnippetsDeleted,
+                ),
+                None => Task::none(),
+            },
+
+            Message::SnippetsDeleted(result) => {
+                match result {
+                    Ok(ids) => {
+                        let ids: HashSet<i64> = ids.into_iter().collect();
+                        let now = unix_now();
+                        let (mut trashed, kept): (Vec<Snippet>, Vec<Snippet>) =
+                            std::mem::take(&mut self.snippets)
+                                .into_iter()
+                                .partition(|s| ids.contains(&s.id));
+                        self.snippets = kept;
+                        for snippet in &mut trashed {
+                            snippet.deleted_at = Some(now);
+                        }
+                        self.status_message =
+                            Some(format!("Moved {} snippets to trash", trashed.len()));
+                        self.trash.splice(0..0, trashed);
+                        if self.selected_snippet.is_some_and(|id| ids.contains(&id)) {
+                            self.selected_snippet = None;
+                        }
+                        self.clamp_inbox_cursor();
+                        return self.sync_project_files();
+                    }
+                    Err(e) => self.status_message = Some(format!("Delete failed: {}", e)),
                 }
                 Task::none()
             }
 
-            Message::PickerAlphaChanged(alpha) => {
-                if let Some(picker) = &mut self.color_picker {
-                    picker.alpha = alpha;
+            Message::DeleteSnippetForever(id) => worker::perform(
+                move || db::delete_snippet(id),
+                Message::SnippetDeletedForever,
+            ),
+
+            Message::SnippetDeletedForever(result) => {
+                match result {
+                    Ok(id) => {
+                        self.trash.retain(|s| s.id != id);
+                        self.status_message = Some("Snippet deleted forever".to_string());
+                    }
+                    Err(e) => {
+                        self.status_message = Some(format!("Delete failed: {}", e));
+                    }
                 }
                 Task::none()
             }
 
-            Message::PickerLabelChanged(label) => {
-                if let Some(picker) = &mut self.color_picker {
-                    picker.label = label;
+            Message::EmptyTrash => worker::perform(db::empty_trash, Message::TrashEmptied),
+
+            Message::TrashEmptied(result) => {
+                match result {
+                    Ok(count) => {
+                        self.trash.clear();
+                        self.status_message = Some(format!("Deleted {} snippets forever", count));
+                    }
+                    Err(e) => {
+                        self.status_message = Some(format!("Empty trash failed: {}", e));
+                    }
+                }
+                Task::none()
+            }
+
+            Message::SelectSnippet(id) => {
+                self.selected_snippet = id;
+                Task::none()
+            }
+
+            // === Inbox Triage ===
+            Message::OpenInbox => {
+                self.inbox_open = true;
+                self.clamp_inbox_cursor();
+                Task::none()
+            }
+
+            Message::CloseInbox => {
+                self.inbox_open = false;
+                Task::none()
+            }
+
+            Message::InboxNext => {
+                self.inbox_cursor += 1;
+                self.clamp_inbox_cursor();
+                Task::none()
+            }
+
+            Message::InboxPrevious => {
+                self.inbox_cursor = self.inbox_cursor.saturating_sub(1);
+                Task::none()
+            }
+
+            Message::KeepInboxCurrent => match self.inbox_snippets().get(self.inbox_cursor) {
+                Some(snippet) => self.update(Message::KeepInboxSnippet(snippet.id)),
+                None => Task::none(),
+            },
+
+            Message::DiscardInboxCurrent => match self.inbox_snippets().get(self.inbox_cursor) {
+                Some(snippet) => self.update(Message::DeleteSnippet(snippet.id)),
+                None => Task::none(),
+            },
+
+            Message::KeepInboxSnippet(id) => worker::perform(
+                move || db::keep_inbox_snippet(id),
+                Message::InboxSnippetKept,
+            ),
+
+            Message::InboxSnippetKept(result) => {
+                match result {
+                    Ok(id) => {
+                        if let Some(snippet) = self.snippets.iter_mut().find(|s| s.id == id) {
+                            snippet.in_inbox = false;
+                        }
+                        self.clamp_inbox_cursor();
+                        self.status_message = Some("Snippet kept".to_string());
+                    }
+                    Err(e) => self.status_message = Some(format!("Keep failed: {}", e)),
                 }
                 Task::none()
             }
 
-            Message::PickerModeChanged(mode) => {
-                if let Some(picker) = &mut self.color_picker {
-                    match mode {
-                        PickerMode::Hsl => picker.sync_hsl_from_rgb(),
-                        PickerMode::Oklch => picker.sync_oklch_from_rgb(),
-                    }
-                    picker.mode = mode;
+            Message::VerifyAgainstClipboard(id) => Task::perform(
+                async {
+                    match arboard::Clipboard::new() {
+                        Ok(mut clipboard) => clipboard.get_text().ok(),
+                        Err(_) => None,
+                    }
+                },
+                move |content| Message::ClipboardVerified(id, content),
+            ),
+
+            Message::ClipboardVerified(id, content) => {
+                let Some(snippet) = self.snippets.iter().find(|s| s.id == id) else {
+                    return Task::none();
+                };
+                let stored = snippet.content.to_copyable_string();
+                self.status_message = Some(match content {
+                    None => "Clipboard has no text to verify".to_string(),
+                    Some(clip) if clip == stored => format!(
+                        "Clipboard matches '{}' (#{})",
+                        snippet.label,
+                        short_hash(&stored)
+                    ),
+                    Some(clip) => format!(
+                        "Clipboard differs from '{}' (#{} vs stored #{})",
+                        snippet.label,
+                        short_hash(&clip),
+                        short_hash(&stored)
+                    ),
+                });
+                Task::none()
+            }
+
+            Message::ColorValueEdited(id, value) => {
+                self.color_value_drafts.insert(id, value);
+                Task::none()
+            }
+
+            Message::CommitColorValue(id) => {
+                let Some(draft) = self.color_value_drafts.get(&id) else {
+                    return Task::none();
+                };
+                let Some(snippet) = self.snippets.iter().find(|s| s.id == id) else {
+                    return Task::none();
+                };
+
+                match ColorData::parse(draft) {
+                    Ok(color) => {
+                        let label = snippet.label.clone();
+                        let description = snippet.description.clone();
+                        let revision = snippet.revision;
+                        self.color_value_drafts.remove(&id);
+                        worker::perform(
+                            move || db::update_color(id, color, label, description, revision),
+                            Message::SnippetUpdated,
+                        )
+                    }
+                    Err(e) => {
+                        self.status_message = Some(e.to_string());
+                        Task::none()
+                    }
+                }
+            }
+
+            Message::OpenInExternalEditor(id, is_code) => {
+                // Find the snippet content
+                let Some(snippet) = self.snippets.iter().find(|s| s.id == id) else {
+                    self.status_message = Some("Snippet not found".to_string());
+                    return Task::none();
+                };
+
+                let content = match &snippet.content {
+                    SnippetContent::Code(code) => code.code.clone(),
+                    SnippetContent::Json(json) => json.json.clone(),
+                    SnippetContent::Text(text) => text.text.clone(),
+                    SnippetContent::Color(_) | SnippetContent::ColorSet(_) => {
+                        self.status_message = Some("Cannot open colors in editor".to_string());
+                        return Task::none();
+                    }
+                    SnippetContent::Gradient(_) => {
+                        self.status_message = Some("Cannot open gradients in editor".to_string());
+                        return Task::none();
+                    }
+                    // Files open in the app registered for their type
+                    SnippetContent::File(file) => {
+                        if let Err(e) = open_in_default_app(std::path::Path::new(&file.path)) {
+                            self.status_message = Some(e);
+                        }
+                        return Task::none();
+                    }
+                };
+
+                // Get file extension
+                let extension = match &snippet.content {
+                    SnippetContent::Code(code) if is_code => language_to_extension(&code.language),
+                    SnippetContent::Json(_) if is_code => "json",
+                    _ => "txt",
+                };
+
+                let config = self.config.clone();
+                self.external_edits.insert(id, snippet.revision);
+                self.status_message = Some("Opening in external editor...".to_string());
+
+                Task::perform(
+                    async move {
+                        open_in_external_editor(id, &content, extension, is_code, &config).await
+                    },
+                    Message::ExternalEditorClosed,
+                )
+            }
+
+            Message::ExternalEditorClosed(result) => {
+                match result {
+                    Ok((id, new_content, is_code)) => {
+                        let stored = self
+                            .snippets
+                            .iter()
+                            .find(|s| s.id == id)
+                            .map(|s| (s.label.clone(), s.content.to_copyable_string()));
+                        match stored {
+                            Some((label, stored))
+                                if self.config.review_external_edits && stored != new_content =>
+                            {
+                                self.edit_review = Some(EditReviewState {
+                                    snippet_id: id,
+                                    label,
+                                    stored,
+                                    edited: new_content,
+                                    is_code,
+                                });
+                            }
+                            _ => return self.save_external_edit(id, new_content, is_code),
+                        }
+                    }
+                    Err(e) => {
+                        self.status_message = Some(format!("Editor error: {}", e));
+                    }
+                }
+                Task::none()
+            }
+
+            Message::SaveExternalEdit => {
+                let Some(review) = self.edit_review.take() else {
+                    return Task::none();
+                };
+                self.save_external_edit(review.snippet_id, review.edited, review.is_code)
+            }
+
+            Message::DiscardExternalEdit => {
+                if let Some(review) = self.edit_review.take() {
+                    self.external_edits.remove(&review.snippet_id);
+                    self.status_message = Some("Discarded the external edit".to_string());
+                }
+                Task::none()
+            }
+
+            Message::ToggleClipboard(enabled) => {
+                self.is_listening_clipboard = enabled;
+                if enabled {
+                    self.status_message = Some("Clipboard listening enabled".to_string());
+                } else {
+                    // Turning auto-capture off ends a running session too
+                    self.capture_session = None;
+                    self.status_message = Some("Clipboard listening disabled".to_string());
+                }
+                Task::none()
+            }
+
+            // === Capture Session Messages ===
+            Message::OpenCaptureSessionDialog => {
+                self.capture_session_draft = Some(CaptureSessionDraft::default());
+                widget::operation::focus(CAPTURE_SESSION_NAME_ID)
+            }
+
+            Message::CloseCaptureSessionDialog => {
+                self.capture_session_draft = None;
+                Task::none()
+            }
+
+            Message::CaptureSessionNameChanged(name) => {
+                if let Some(draft) = &mut self.capture_session_draft {
+                    draft.name = name;
+                }
+                Task::none()
+            }
+
+            Message::CaptureSessionLengthChanged(minutes) => {
+                if let Some(draft) = &mut self.capture_session_draft {
+                    draft.minutes = minutes;
+                }
+                Task::none()
+            }
+
+            Message::StartCaptureSession => {
+                let Some(draft) = &self.capture_session_draft else {
+                    return Task::none();
+                };
+                let name = draft.name.trim().to_string();
+                if name.is_empty() {
+                    return Task::none();
+                }
+                // A session named after an existing palette adds to it
+                let existing = self
+                    .palettes
+                    .iter()
+                    .find(|p| !p.is_smart() && p.name.eq_ignore_ascii_case(&name))
+                    .cloned();
+                match existing {
+                    Some(palette) => self.update(Message::CaptureSessionPaletteReady(Ok(palette))),
+                    None => worker::perform(
+                        move || db::create_palette(name),
+                        Message::CaptureSessionPaletteReady,
+                    ),
+                }
+            }
+
+            Message::CaptureSessionPaletteReady(result) => {
+                let Some(draft) = self.capture_session_draft.take() else {
+                    return Task::none();
+                };
+                match result {
+                    Ok(palette) => {
+                        if !self.palettes.iter().any(|p| p.id == palette.id) {
+                            self.palettes.push(palette.clone());
+                            self.palettes.sort_by(|a, b| a.name.cmp(&b.name));
+                        }
+                        self.capture_session = Some(CaptureSession {
+                            name: palette.name.clone(),
+                            palette_id: palette.id,
+                            ends_at: std::time::Instant::now()
+                                + std::time::Duration::from_secs(draft.minutes * 60),
+                            captured: 0,
+                            was_listening: self.is_listening_clipboard,
+                        });
+                        self.is_listening_clipboard = true;
+                        self.status_message = Some(format!(
+                            "Capturing into \"{}\" for {} minutes",
+                            palette.name, draft.minutes
+                        ));
+                    }
+                    Err(e) => self.status_message = Some(format!("Could not start session: {}", e)),
+                }
+                Task::none()
+            }
+
+            Message::CaptureSessionTick => {
+                if self
+                    .capture_session
+                    .as_ref()
+                    .is_some_and(CaptureSession::is_over)
+                {
+                    return self.update(Message::StopCaptureSession);
+                }
+                Task::none()
+            }
+
+            Message::StopCaptureSession => {
+                if let Some(session) = self.capture_session.take() {
+                    self.is_listening_clipboard = session.was_listening;
+                    let noun = if session.captured == 1 {
+                        "snippet"
+                    } else {
+                        "snippets"
+                    };
+                    self.status_message = Some(format!(
+                        "Capture session \"{}\" ended: {} {} captured",
+                        session.name, session.captured, noun
+                    ));
+                }
+                Task::none()
+            }
+
+            Message::ClipboardTick => {
+                let last = self.last_clipboard_content.clone();
+                Task::perform(
+                    async move {
+                        let mut content = read_clipboard(last.as_deref())?;
+                        // Only new text is captured, so only then is its source looked up
+                        if last.as_deref() != Some(content.text.as_str()) {
+                            content.source_app = crate::source_app::frontmost_app();
+                        }
+                        Some(content)
+                    },
+                    Message::ClipboardContentReceived,
+                )
+            }
+
+            Message::ClipboardContentReceived(content) => {
+                if let Some(ClipboardContent {
+                    text,
+                    html,
+                    source_app,
+                }) = content
+                {
+                    if !text.is_empty() && Some(&text) != self.last_clipboard_content.as_ref() {
+                        self.last_clipboard_content = Some(text.clone());
+
+                        // Apply the secret policy before anything is stored
+                        let secrets = find_secrets(&text);
+                        let (text, html) = if let Some(secret) = secrets.first() {
+                            match self.config.secret_policy {
+                                SecretPolicy::Warn => {
+                                    self.status_message = Some(format!(
+                                        "Warning: captured text contains a {}",
+                                        secret.display_name()
+                                    ));
+                                    (text, html)
+                                }
+                                // The rich text would still hold the secret
+                                SecretPolicy::Redact => (redact_secrets(&text), None),
+                                SecretPolicy::Refuse => {
+                                    self.status_message = Some(format!(
+                                        "Skipped capture containing a {}",
+                                        secret.display_name()
+                                    ));
+                                    return Task::none();
+                                }
+                            }
+                        } else {
+                            (text, html)
+                        };
+
+                        // Detect snippet type and add accordingly
+                        let dedupe = self.config.color_dedupe;
+                        if let Some(kind) = detect_snippet_type(&text) {
+                            match kind {
+                                SnippetKind::Color => {
+                                    let colors = extract_colors_from_text(&text);
+                                    if let Some(color) = colors.into_iter().next() {
+                                        let label = color.to_hex();
+                                        return worker::perform(
+                                            move || {
+                                                db::add_captured_snippet(
+                                                    Snippet::color(
+                                                        color.r, color.g, color.b, color.a, label,
+                                                    )
+                                                    .with_source_app(source_app),
+                                                    dedupe,
+                                                )
+                                            },
+                                            Message::SnippetCaptured,
+                                        );
+                                    }
+                                }
+                                SnippetKind::Code => {
+                                    let code = text.clone();
+                                    return worker::perform(
+                                        move || {
+                                            db::add_captured_snippet(
+                                                Snippet::code(code, String::new(), String::new())
+                                                    .with_source_app(source_app),
+                                                dedupe,
+                                            )
+                                        },
+                                        Message::SnippetCaptured,
+                                    );
+                                }
+                                SnippetKind::Json => {
+                                    let json = text.clone();
+                                    return worker::perform(
+                                        move || {
+                                            db::add_captured_snippet(
+                                                Snippet::json(json, String::new())
+                                                    .with_source_app(source_app),
+                                                dedupe,
+                                            )
+                                        },
+                                        Message::SnippetCaptured,
+                                    );
+                                }
+                                SnippetKind::Text => {
+                                    let text_content = text.clone();
+                                    return worker::perform(
+                                        move || {
+                                            db::add_captured_snippet(
+                                                Snippet::rich_text(
+                                                    text_content,
+                                                    html,
+                                                    String::new(),
+                                                )
+                                                .with_source_app(source_app),
+                                                dedupe,
+                                            )
+                                        },
+                                        Message::SnippetCaptured,
+                                    );
+                                }
+                                // Clipboard text is never detected as a set, gradient or file
+                                SnippetKind::ColorSet
+                                | SnippetKind::Gradient
+                                | SnippetKind::File => {}
+                            }
+                        }
+                    }
+                }
+                Task::none()
+            }
+
+            Message::FilterChanged(text) => {
+                self.filter_text = text;
+                Task::none()
+            }
+
+            Message::FilterKindChanged(kind) => {
+                // The language facet only applies to code snippets
+                if kind.as_ref().is_some_and(|k| *k != SnippetKind::Code) {
+                    self.filter_language = None;
+                }
+                self.filter_kind = kind;
+                self.trash_open = false;
+                Task::none()
+            }
+
+            Message::FilterLanguageChanged(language) => {
+                self.filter_language = language;
+                Task::none()
+            }
+
+            Message::FilterSourceAppChanged(source_app) => {
+                self.filter_source_app = source_app;
+                Task::none()
+            }
+
+            // === List Layout ===
+            Message::ToggleListLayout => {
+                self.config.list_layout = match self.config.list_layout {
+                    ListLayout::Cards => ListLayout::Table,
+                    ListLayout::Table => ListLayout::Cards,
+                };
+                let config = self.config.clone();
+                Task::perform(async move { config.save() }, Message::ConfigSaved)
+            }
+
+            Message::ToggleSplitPane => {
+                self.config.split_pane.enabled = !self.config.split_pane.enabled;
+                let config = self.config.clone();
+                Task::perform(async move { config.save() }, Message::ConfigSaved)
+            }
+
+            Message::SplitPaneFilterChanged(text) => {
+                self.split_pane_filter_text = text;
+                Task::none()
+            }
+
+            Message::SplitPaneKindChanged(kind) => {
+                self.config.split_pane.kind = kind;
+                let config = self.config.clone();
+                Task::perform(async move { config.save() }, Message::ConfigSaved)
+            }
+
+            Message::SplitPanePaletteChanged(palette_id) => {
+                self.config.split_pane.palette = palette_id;
+                let config = self.config.clone();
+                Task::perform(async move { config.save() }, Message::ConfigSaved)
+            }
+
+            Message::SortOrderChanged(order) => {
+                self.config.sort_order = order;
+                let config = self.config.clone();
+                Task::perform(async move { config.save() }, Message::ConfigSaved)
+            }
+
+            Message::SortTable(column) => {
+                self.table.toggle_sort(column);
+                Task::none()
+            }
+
+            Message::TableColumnResizeStarted(column) => {
+                self.table.start_resize(column);
+                Task::none()
+            }
+
+            Message::TableColumnResizeMoved(x) => {
+                self.table.drag_resize(x);
+                Task::none()
+            }
+
+            Message::TableColumnResizeEnded => {
+                self.table.end_resize();
+                Task::none()
+            }
+
+            Message::PasteFromClipboard => Task::perform(
+                async { read_clipboard(None) },
+                Message::PasteContentReceived,
+            ),
+
+            Message::PasteContentReceived(content) => {
+                if let Some(ClipboardContent { text, html, .. }) = content {
+                    if !text.is_empty() {
+                        // Detect snippet type and add accordingly
+                        if let Some(kind) = detect_snippet_type(&text) {
+                            match kind {
+                                SnippetKind::Color => {
+                                    let colors = extract_colors_from_text(&text);
+                                    if let Some(color) = colors.into_iter().next() {
+                                        let label = color.to_hex();
+                                        let dedupe = self.config.color_dedupe;
+                                        return worker::perform(
+                                            move || {
+                                                db::add_or_move_color(
+                                                    color.r, color.g, color.b, color.a, label,
+                                                    dedupe,
+                                                )
+                                            },
+                                            Message::SnippetCaptured,
+                                        );
+                                    }
+                                }
+                                SnippetKind::Code => {
+                                    let code = text.clone();
+                                    return worker::perform(
+                                        move || {
+                                            db::add_code_snippet(code, String::new(), String::new())
+                                        },
+                                        Message::SnippetCaptured,
+                                    );
+                                }
+                                SnippetKind::Json => {
+                                    let json = text.clone();
+                                    return worker::perform(
+                                        move || db::add_json_snippet(json, String::new()),
+                                        Message::SnippetCaptured,
+                                    );
+                                }
+                                SnippetKind::Text => {
+                                    let text_content = text.clone();
+                                    return worker::perform(
+                                        move || {
+                                            db::insert_snippet(Snippet::rich_text(
+                                                text_content,
+                                                html,
+                                                String::new(),
+                                            ))
+                                        },
+                                        Message::SnippetCaptured,
+                                    );
+                                }
+                                // Clipboard text is never detected as a set, gradient or file
+                                SnippetKind::ColorSet
+                                | SnippetKind::Gradient
+                                | SnippetKind::File => {}
+                            }
+                        }
+                    }
+                }
+                Task::none()
+            }
+
+            Message::FocusNext => widget::operation::focus_next(),
+
+            Message::FocusPrevious => widget::operation::focus_previous(),
+
+            Message::EscapePressed => {
+                // Priority: close modals/menus > clear filter > deselect
+                if self.unlock.is_some() {
+                    // The unlock prompt cannot be dismissed
+                } else if self.eyedropper.is_some() {
+                    return self.update(Message::CancelEyedropper);
+                } else if self.quick_popup_id().is_some() {
+                    return self.update(Message::CloseQuickPopup);
+                } else if self.add_menu_open {
+                    self.add_menu_open = false;
+                } else if self
+                    .encrypt_database
+                    .as_ref()
+                    .is_some_and(|state| !state.busy)
+                {
+                    self.encrypt_database = None;
+                } else if self.save_conflict.is_some() {
+                    return self.update(Message::KeepStoredVersion);
+                } else if self.edit_review.is_some() {
+                    return self.update(Message::DiscardExternalEdit);
+                } else if self.bulk_delete.is_some() {
+                    self.bulk_delete = None;
+                } else if self.release_notes_open {
+                    self.release_notes_open = false;
+                } else if self.section_draft.is_some() {
+                    self.section_draft = None;
+                } else if self.capture_session_draft.is_some() {
+                    self.capture_session_draft = None;
+                } else if self.selection_mode {
+                    return self.update(Message::ToggleSelectionMode);
+                } else if self.card_menu_snippet.is_some() {
+                    self.card_menu_snippet = None;
+                } else if self.palette_dropdown_snippet.is_some() {
+                    self.palette_dropdown_snippet = None;
+                } else if self.pinned_panel_open {
+                    self.pinned_panel_open = false;
+                } else if self.inbox_open {
+                    self.inbox_open = false;
+                } else if self.share_image.is_some() {
+                    self.share_image = None;
+                } else if self.contrast.is_some() {
+                    self.contrast = None;
+                } else if self.similar_colors.is_some() {
+                    self.similar_colors = None;
+                } else if self.template_fill.is_some() {
+                    self.template_fill = None;
+                } else if self.run_output.is_some() {
+                    self.run_output = None;
+                } else if self.abbreviation_editor.is_some() {
+                    self.abbreviation_editor = None;
+                } else if self.scale.is_some() {
+                    self.scale = None;
+                } else if self.code_export.is_some() {
+                    self.code_export = None;
+                } else if self.region_scan.is_some() {
+                    self.region_scan = None;
+                } else if self.icon_picker.is_some() {
+                    self.icon_picker = None;
+                } else if self.json_tree.is_some() {
+                    self.json_tree = None;
+                } else if self.scratchpad_open {
+                    return self.update(Message::CloseScratchpad);
+                } else if self.settings.is_some() {
+                    self.settings = None;
+                } else if let Some(picker) =
+                    self.color_picker.as_mut().filter(|p| p.tour_step.is_some())
+                {
+                    picker.tour_step = None;
+                } else if self.color_picker.is_some() {
+                    self.color_picker = None;
+                } else if self.gradient_editor.is_some() {
+                    self.gradient_editor = None;
+                } else if self.code_editor.is_some() {
+                    self.code_editor = None;
+                } else if self.text_editor.is_some() {
+                    self.text_editor = None;
+                } else if !self.filter_text.is_empty() {
+                    self.filter_text.clear();
+                } else {
+                    self.selected_snippet = None;
+                }
+                Task::none()
+            }
+
+            Message::DeleteSelectedSnippet => {
+                if let Some(id) = self.selected_snippet {
+                    self.selected_snippet = None;
+                    worker::perform(move || db::trash_snippet(id), Message::SnippetDeleted)
+                } else {
+                    Task::none()
+                }
+            }
+
+            Message::CopySelectedSnippet => {
+                if let Some(id) = self.selected_snippet {
+                    if let Some(snippet) = self.snippets.iter().find(|s| s.id == id) {
+                        let text = match self.config.copy_format {
+                            CopyFormat::Plain => snippet.content.to_copyable_string(),
+                            CopyFormat::Markdown => self.markdown_text(&snippet.content),
+                        };
+                        self.copy_snippet_text(id, text)
+                    } else {
+                        Task::none()
+                    }
+                } else {
+                    Task::none()
+                }
+            }
+
+            // === Color Picker Messages ===
+            Message::OpenColorPicker(id) => {
+                self.add_menu_open = false;
+                let default_mode = self.config.default_picker_mode;
+                self.color_picker = Some(if let Some(snippet_id) = id {
+                    // Edit existing color
+                    if let Some(snippet) = self.snippets.iter().find(|s| s.id == snippet_id) {
+                        ColorPickerState::from_snippet(snippet, default_mode)
+                    } else {
+                        ColorPickerState::new_color(default_mode)
+                    }
+                } else {
+                    ColorPickerState::new_color(default_mode)
+                });
+                Task::none()
+            }
+
+            Message::CloseColorPicker => {
+                self.color_picker = None;
+                Task::none()
+            }
+
+            Message::PickerHueChanged(hue) => {
+                if let Some(picker) = &mut self.color_picker {
+                    picker.begin_adjustment();
+                    picker.hue = hue;
+                }
+                Task::none()
+            }
+
+            Message::PickerSaturationChanged(saturation) => {
+                if let Some(picker) = &mut self.color_picker {
+                    picker.begin_adjustment();
+                    picker.saturation = saturation;
+                }
+                Task::none()
+            }
+
+            Message::PickerLightnessChanged(lightness) => {
+                if let Some(picker) = &mut self.color_picker {
+                    picker.begin_adjustment();
+                    picker.lightness = lightness;
+                }
+                Task::none()
+            }
+
+            Message::PickerSLChanged(saturation, lightness) => {
+                if let Some(picker) = &mut self.color_picker {
+                    picker.begin_adjustment();
+                    picker.saturation = saturation;
+                    picker.lightness = lightness;
+                }
+                Task::none()
+            }
+
+            Message::PickerSVChanged(saturation, value) => {
+                if let Some(picker) = &mut self.color_picker {
+                    picker.begin_adjustment();
+                    picker.hsv_s = saturation;
+                    picker.hsv_v = value;
+                }
+                Task::none()
+            }
+
+            Message::PickerHsvSaturationChanged(saturation) => {
+                if let Some(picker) = &mut self.color_picker {
+                    picker.begin_adjustment();
+                    picker.hsv_s = saturation;
+                }
+                Task::none()
+            }
+
+            Message::PickerHsvValueChanged(value) => {
+                if let Some(picker) = &mut self.color_picker {
+                    picker.begin_adjustment();
+                    picker.hsv_v = value;
+                }
+                Task::none()
+            }
+
+            Message::PickerAlphaChanged(alpha) => {
+                if let Some(picker) = &mut self.color_picker {
+                    picker.begin_adjustment();
+                    picker.alpha = alpha;
+                }
+                Task::none()
+            }
+
+            Message::PickerAdjustmentEnded => {
+                if let Some(picker) = &mut self.color_picker {
+                    picker.end_adjustment();
+                }
+                Task::none()
+            }
+
+            Message::PickerUndo => {
+                if let Some(picker) = &mut self.color_picker {
+                    if !picker.undo() {
+                        self.status_message = Some("Nothing to undo".to_string());
+                    }
+                }
+                Task::none()
+            }
+
+            // === Eyedropper Messages ===
+            Message::StartEyedropper => {
+                if self.eyedropper.is_some() {
+                    return Task::none();
+                }
+                self.add_menu_open = false;
+                // Shard gets out of the way, then the screen is captured once
+                // the window has had time to go
+                let hide = match self.main_window_id() {
+                    Some(id) => window::minimize(id, true),
+                    None => Task::none(),
+                };
+                let capture = Task::perform(
+                    async {
+                        tokio::time::sleep(std::time::Duration::from_millis(400)).await;
+                        tokio::task::spawn_blocking(crate::eyedropper::capture_screen)
+                            .await
+                            .map_err(|e| e.to_string())?
+                            .map(Arc::new)
+                    },
+                    Message::ScreenCaptured,
+                );
+                hide.chain(capture)
+            }
+
+            Message::ScreenCaptured(result) => match result {
+                Ok(screenshot) => {
+                    self.eyedropper = Some(screenshot);
+                    let (overlay_id, open_task) = window::open(window::Settings {
+                        fullscreen: true,
+                        decorations: false,
+                        transparent: true,
+                        resizable: false,
+                        level: window::Level::AlwaysOnTop,
+                        ..window::Settings::default()
+                    });
+                    self.windows.insert(overlay_id, WindowKind::Eyedropper);
+                    Task::batch([
+                        open_task.map(Message::WindowOpened),
+                        window::gain_focus(overlay_id),
+                    ])
+                }
+                Err(e) => {
+                    self.status_message = Some(format!("Screen capture failed: {}", e));
+                    self.restore_main_window()
+                }
+            },
+
+            Message::EyedropperPicked(color) => {
+                let close = self.close_eyedropper();
+                match &mut self.color_picker {
+                    Some(picker) => {
+                        picker.begin_adjustment();
+                        picker.set_color(&color);
+                        picker.end_adjustment();
+                    }
+                    None => {
+                        let mut picker =
+                            ColorPickerState::new_color(self.config.default_picker_mode);
+                        picker.set_color(&color);
+                        self.color_picker = Some(picker);
+                    }
+                }
+                self.status_message = Some(format!("Picked {}", color.to_hex()));
+                close
+            }
+
+            Message::CancelEyedropper => self.close_eyedropper(),
+
+            Message::PickerTourStep(step) => {
+                if let Some(picker) = &mut self.color_picker {
+                    picker.tour_step = step;
+                }
+                Task::none()
+            }
+
+            Message::CopyPickerTemplate(index) => {
+                let (Some(picker), Some(template)) =
+                    (&self.color_picker, self.config.copy_templates.get(index))
+                else {
+                    return Task::none();
+                };
+                let label = if picker.label.is_empty() {
+                    picker.suggested_label()
+                } else {
+                    picker.label.clone()
+                };
+                let text = template.render(&label, &picker.to_color_data());
+                let status = format!("Copied {}", template.name);
+                Task::perform(
+                    async move { copy_to_clipboard(&text).await.map(|_| status) },
+                    Message::CopyFinished,
+                )
+            }
+
+            Message::PickerColorInputChanged(input) => {
+                if let Some(picker) = &mut self.color_picker {
+                    picker.set_color_input(input);
+                }
+                Task::none()
+            }
+
+            Message::PickerRecentColorSelected(index) => {
+                if let (Some(picker), Some(color)) =
+                    (&mut self.color_picker, self.config.recent_colors.get(index))
+                {
+                    picker.begin_adjustment();
+                    picker.set_color(color);
+                    picker.end_adjustment();
+                }
+                Task::none()
+            }
+
+            Message::PickerLabelChanged(label) => {
+                if let Some(picker) = &mut self.color_picker {
+                    picker.label = label;
+                }
+                Task::none()
+            }
+
+            Message::PickerDescriptionChanged(action) => {
+                if let Some(picker) = &mut self.color_picker {
+                    picker.description.perform(action);
+                }
+                Task::none()
+            }
+
+            Message::PickerModeChanged(mode) => {
+                if let Some(picker) = &mut self.color_picker {
+                    match mode {
+                        PickerMode::Hsl => picker.sync_hsl_from_rgb(),
+                        PickerMode::Oklch => picker.sync_oklch_from_rgb(),
+                        PickerMode::Hsv => picker.sync_hsv_from_rgb(),
+                        PickerMode::Rgb => picker.sync_rgb(),
+                    }
+                    picker.mode = mode;
+                }
+                Task::none()
+            }
+
+            Message::PickerAdjustOklch(delta_l, delta_c) => {
+                if let Some(picker) = &mut self.color_picker {
+                    picker.begin_adjustment();
+                    picker.adjust_oklch(delta_l, delta_c);
+                    picker.end_adjustment();
+                }
+                Task::none()
+            }
+
+            Message::PickerAdjustColor(adjustment) => {
+                if let Some(picker) = &mut self.color_picker {
+                    let color = adjustment.apply(&picker.to_color_data());
+                    picker.begin_adjustment();
+                    picker.set_color(&color);
+                    picker.end_adjustment();
+                }
+                Task::none()
+            }
+
+            Message::PickerOklchLChanged(l) => {
+                if let Some(picker) = &mut self.color_picker {
+                    picker.begin_adjustment();
+                    picker.oklch_l = l;
+                }
+                Task::none()
+            }
+
+            Message::PickerOklchCChanged(c) => {
+                if let Some(picker) = &mut self.color_picker {
+                    picker.begin_adjustment();
+                    picker.oklch_c = c;
+                }
+                Task::none()
+            }
+
+            Message::PickerOklchHChanged(h) => {
+                if let Some(picker) = &mut self.color_picker {
+                    picker.begin_adjustment();
+                    picker.oklch_h = h;
+                }
+                Task::none()
+            }
+
+            Message::PickerCLChanged(chroma, lightness) => {
+                if let Some(picker) = &mut self.color_picker {
+                    picker.begin_adjustment();
+                    picker.oklch_c = chroma;
+                    picker.oklch_l = lightness;
+                }
+                Task::none()
+            }
+
+            Message::PickerRgbChanged(channel, value) => {
+                if let Some(picker) = &mut self.color_picker {
+                    picker.begin_adjustment();
+                    picker.rgb[channel] = value;
+                }
+                Task::none()
+            }
+
+            Message::PickerRgbInputChanged(channel, input) => {
+                // An emptied field is zero; anything else must be a channel value
+                let value = if input.is_empty() {
+                    Some(0)
+                } else {
+                    input.trim().parse::<u8>().ok()
+                };
+                if let (Some(picker), Some(value)) = (&mut self.color_picker, value) {
+                    picker.begin_adjustment();
+                    picker.rgb[channel] = value;
+                    picker.end_adjustment();
+                }
+                Task::none()
+            }
+
+            Message::ConfirmColorPicker => {
+                if let Some(picker) = self.color_picker.take() {
+                    let remember = self.remember_picker_color(picker.to_color_data());
+                    if let Some(index) = picker.gradient_stop {
+                        if let Some(stop) = self
+                            .gradient_editor
+                            .as_mut()
+                            .and_then(|state| state.gradient.stops.get_mut(index))
+                        {
+                            stop.color = picker.to_color_data();
+                        }
+                        return remember;
+                    }
+                    let (r, g, b) = picker.to_rgb();
+                    let alpha = picker.alpha;
+                    let label = if picker.label.is_empty() {
+                        picker.suggested_label()
+                    } else {
+                        picker.label.clone()
+                    };
+                    let description = normalize_description(&picker.description.text());
+
+                    let save = if let Some(editing_id) = picker.editing_id {
+                        // Update existing color
+                        let color = ColorData::new(r, g, b, alpha);
+                        let revision = picker.revision;
+                        worker::perform(
+                            move || {
+                                db::update_color(editing_id, color, label, description, revision)
+                            },
+                            Message::SnippetUpdated,
+                        )
+                    } else {
+                        // Add new color
+                        let dedupe = self.config.color_dedupe;
+                        worker::perform(
+                            move || {
+                                db::add_snippet(
+                                    Snippet::color(r, g, b, alpha, label)
+                                        .with_description(description),
+                                    dedupe,
+                                )
+                            },
+                            Message::SnippetAdded,
+                        )
+                    };
+                    Task::batch([save, remember])
+                } else {
+                    Task::none()
+                }
+            }
+
+            Message::SaveColorAsNew => {
+                // Save the current picker color as a new snippet (without modifying the original)
+                if let Some(picker) = self.color_picker.take() {
+                    let (r, g, b) = picker.to_rgb();
+                    let alpha = picker.alpha;
+                    let label = if picker.label.is_empty() {
+                        picker.suggested_label()
+                    } else {
+                        picker.label.clone()
+                    };
+                    let description = normalize_description(&picker.description.text());
+
+                    // Always add as new, regardless of editing_id
+                    let dedupe = self.config.color_dedupe;
+                    let remember = self.remember_picker_color(picker.to_color_data());
+                    Task::batch([
+                        worker::perform(
+                            move || {
+                                db::add_snippet(
+                                    Snippet::color(r, g, b, alpha, label)
+                                        .with_description(description),
+                                    dedupe,
+                                )
+                            },
+                            Message::SnippetAdded,
+                        ),
+                        remember,
+                    ])
+                } else {
+                    Task::none()
+                }
+            }
+
+            Message::SnippetUpdated(result) => {
+                match result {
+                    Ok(snippet) => {
+                        self.color_value_drafts.remove(&snippet.id);
+                        if let Some(existing) =
+                            self.snippets.iter_mut().find(|s| s.id == snippet.id)
+                        {
+                            *existing = snippet;
+                        }
+                        self.status_message = Some("Snippet updated".to_string());
+                        return self.sync_project_files();
+                    }
+                    Err(SaveError::Conflict(conflict)) => {
+                        // Show the newer stored version, and ask what to do with the edit
+                        if let Some(existing) = self
+                            .snippets
+                            .iter_mut()
+                            .find(|s| s.id == conflict.stored.id)
+                        {
+                            *existing = conflict.stored.clone();
+                        }
+                        self.status_message =
+                            Some("The snippet was changed elsewhere while editing".to_string());
+                        self.save_conflict = Some(*conflict);
+                    }
+                    Err(SaveError::Failed(e)) => {
+                        self.status_message = Some(format!("Update failed: {}", e));
+                    }
+                }
+                Task::none()
+            }
+
+            Message::KeepStoredVersion => {
+                self.save_conflict = None;
+                self.status_message = Some("Kept the stored version".to_string());
+                Task::none()
+            }
+
+            Message::OverwriteStoredVersion => {
+                let Some(conflict) = self.save_conflict.take() else {
+                    return Task::none();
+                };
+                let revision = conflict.stored.revision;
+                worker::perform(
+                    move || db::update_snippet(conflict.edited, revision),
+                    Message::SnippetUpdated,
+                )
+            }
+
+            Message::MergeConflictInEditor => {
+                let Some(conflict) = self.save_conflict.take() else {
+                    return Task::none();
+                };
+                // Continue editing the unsaved version on top of the stored one
+                let edited = Snippet {
+                    revision: conflict.stored.revision,
+                    ..conflict.edited
+                };
+                match &edited.content {
+                    SnippetContent::Code(_) | SnippetContent::Json(_) => {
+                        self.code_editor = Some(CodeEditorState::from_snippet(&edited));
+                    }
+                    SnippetContent::Text(_) => {
+                        self.text_editor = Some(TextEditorState::from_snippet(&edited));
+                    }
+                    SnippetContent::Color(_) => {
+                        self.color_picker = Some(ColorPickerState::from_snippet(
+                            &edited,
+                            self.config.default_picker_mode,
+                        ));
+                    }
+                    SnippetContent::Gradient(_) => {
+                        self.gradient_editor = GradientEditorState::from_snippet(&edited);
+                    }
+                    // Only the label of a color set or file snippet can be edited
+                    SnippetContent::ColorSet(_) | SnippetContent::File(_) => {}
+                }
+                Task::none()
+            }
+
+            // === Code Editor Messages ===
+            Message::OpenCodeEditor(id) => {
+                self.add_menu_open = false;
+                self.code_editor = Some(if let Some(snippet_id) = id {
+                    if let Some(snippet) = self.snippets.iter().find(|s| s.id == snippet_id) {
+                        CodeEditorState::from_snippet(snippet)
+                    } else {
+                        CodeEditorState::new_code()
+                    }
+                } else {
+                    CodeEditorState::new_code()
+                });
+                Task::none()
+            }
+
+            Message::CloseCodeEditor => {
+                self.code_editor = None;
+                Task::none()
+            }
+
+            Message::CodeEditorContentChanged(action) => {
+                let Some(editor) = &mut self.code_editor else {
+                    return Task::none();
+                };
+                // Clicks and drags happen where the code is in view already
+                let follows_cursor = !matches!(
+                    action,
+                    widget::text_editor::Action::Click(_)
+                        | widget::text_editor::Action::Drag(_)
+                        | widget::text_editor::Action::Scroll { .. }
+                );
+                editor.content.perform(action);
+                // Wrapped code scrolls inside the editor itself
+                let follows_cursor = follows_cursor && !self.config.editing.code_word_wrap;
+                match editor.scroll_to_cursor().filter(|_| follows_cursor) {
+                    Some(y) => widget::operation::scroll_to(
+                        CODE_EDITOR_SCROLL_ID,
+                        widget::scrollable::AbsoluteOffset {
+                            x: None,
+                            y: Some(y),
+                        },
+                    ),
+                    None => Task::none(),
+                }
+            }
+
+            Message::CodeEditorScrolled(viewport) => {
+                if let Some(editor) = &mut self.code_editor {
+                    editor.scroll_top = viewport.absolute_offset().y;
+                    editor.viewport_height = viewport.bounds().height;
+                }
+                Task::none()
+            }
+
+            Message::CodeEditorLanguageChanged(language) => {
+                if let Some(editor) = &mut self.code_editor {
+                    editor.language = language;
+                }
+                Task::none()
+            }
+
+            Message::CodeEditorLabelChanged(label) => {
+                if let Some(editor) = &mut self.code_editor {
+                    editor.label = label;
+                }
+                Task::none()
+            }
+
+            Message::CodeEditorDescriptionChanged(action) => {
+                if let Some(editor) = &mut self.code_editor {
+                    editor.description.perform(action);
+                }
+                Task::none()
+            }
+
+            Message::ConfirmCodeEditor => {
+                if let Some(editor) = self.code_editor.as_mut().filter(|e| e.is_json) {
+                    // Invalid JSON keeps the editor open with the parse error
+                    let json = editor.content.text();
+                    if let Err(e) = parse_json(&json) {
+                        editor.json_error = Some(e);
+                        return Task::none();
+                    }
+                    let label = editor.label.clone();
+                    let description = normalize_description(&editor.description.text());
+                    let dedupe = self.config.color_dedupe;
+                    let editing_id = editor.editing_id;
+                    let revision = editor.revision;
+                    self.code_editor = None;
+                    return match editing_id {
+                        Some(id) => worker::perform(
+                            move || db::update_json(id, json, label, description, revision),
+                            Message::SnippetUpdated,
+                        ),
+                        None => worker::perform(
+                            move || {
+                                db::add_snippet(
+                                    Snippet::json(json, label).with_description(description),
+                                    dedupe,
+                                )
+                            },
+                            Message::SnippetAdded,
+                        ),
+                    };
+                }
+                if let Some(editor) = self.code_editor.take() {
+                    let code = editor.content.text();
+                    let language = editor.language.clone();
+                    let label = editor.label.clone();
+                    let description = normalize_description(&editor.description.text());
+                    let dedupe = self.config.color_dedupe;
+
+                    if let Some(editing_id) = editor.editing_id {
+                        let revision = editor.revision;
+                        worker::perform(
+                            move || {
+                                db::update_code(
+                                    editing_id,
+                                    code,
+                                    language,
+                                    label,
+                                    description,
+                                    revision,
+                                )
+                            },
+                            Message::SnippetUpdated,
+                        )
+                    } else {
+                        worker::perform(
+                            move || {
+                                db::add_snippet(
+                                    Snippet::code(code, language, label)
+                                        .with_description(description),
+                                    dedupe,
+                                )
+                            },
+                            Message::SnippetAdded,
+                        )
+                    }
+                } else {
+                    Task::none()
+                }
+            }
+
+            // === JSON Messages ===
+            Message::OpenJsonEditor => {
+                self.add_menu_open = false;
+                self.code_editor = Some(CodeEditorState::new_json());
+                Task::none()
+            }
+
+            Message::CodeEditorFormatJson(format) => {
+                if let Some(editor) = &mut self.code_editor {
+                    let json = JsonData::new(editor.content.text());
+                    match format.apply(&json) {
+                        Ok(formatted) => {
+                            editor.content =
+                                iced::widget::text_editor::Content::with_text(&formatted);
+                            editor.json_error = None;
+                        }
+                        Err(e) => editor.json_error = Some(e),
+                    }
+                }
+                Task::none()
+            }
+
+            Message::CodeEditorTransform(transform) => {
+                let tab_width = usize::from(self.config.editing.tab_width);
+                if let Some(editor) = &mut self.code_editor {
+                    let code = transform.apply(&editor.content.text(), tab_width);
+                    editor.content = iced::widget::text_editor::Content::with_text(&code);
+                }
+                Task::none()
+            }
+
+            Message::TransformSnippet(id, transform) => {
+                let Some(snippet) = self.snippets.iter().find(|s| s.id == id) else {
+                    return Task::none();
+                };
+                let SnippetContent::Code(code) = &snippet.content else {
+                    return Task::none();
+                };
+                let transformed =
+                    transform.apply(&code.code, usize::from(self.config.editing.tab_width));
+                if transformed == code.code {
+                    self.status_message = Some(format!("{}: nothing to change", transform));
+                    return Task::none();
+                }
+                let revision = snippet.revision;
+                worker::perform(
+                    move || db::update_code_content(id, transformed, revision),
+                    Message::SnippetUpdated,
+                )
+            }
+
+            Message::ReformatSnippet(id, format) => {
+                let Some(snippet) = self.snippets.iter().find(|s| s.id == id) else {
+                    return Task::none();
+                };
+                let SnippetContent::Code(code) = &snippet.content else {
+                    return Task::none();
+                };
+                match reformat_code(&code.code, &code.language, format) {
+                    Ok(formatted) if formatted == code.code => {
+                        self.status_message = Some("Code is already formatted".to_string());
+                        Task::none()
+                    }
+                    Ok(formatted) => {
+                        self.reformat_undo = Some(ReformatUndo {
+                            snippet_id: id,
+                            previous: code.code.clone(),
+                            reformatted: formatted.clone(),
+                        });
+                        let revision = snippet.revision;
+                        worker::perform(
+                            move || db::update_code_content(id, formatted, revision),
+                            Message::SnippetUpdated,
+                        )
+                    }
+                    Err(e) => {
+                        self.status_message = Some(format!("Reformat failed: {}", e));
+                        Task::none()
+                    }
+                }
+            }
+
+            Message::UndoReformat => {
+                let Some(undo) = self.reformat_undo.take() else {
+                    return Task::none();
+                };
+                let Some(snippet) = self.snippets.iter().find(|s| s.id == undo.snippet_id) else {
+                    return Task::none();
+                };
+                // Only undo while the snippet still holds what the reformat wrote
+                let current = match &snippet.content {
+                    SnippetContent::Code(code) => &code.code,
+                    SnippetContent::Json(json) => &json.json,
+                    _ => return Task::none(),
+                };
+                if *current != undo.reformatted {
+                    self.status_message =
+                        Some("Can't undo: the snippet changed after the reformat".to_string());
+                    return Task::none();
+                }
+                let (id, previous, revision) = (undo.snippet_id, undo.previous, snippet.revision);
+                if matches!(snippet.content, SnippetContent::Json(_)) {
+                    worker::perform(
+                        move || db::update_json_content(id, previous, revision),
+                        Message::SnippetUpdated,
+                    )
+                } else {
+                    worker::perform(
+                        move || db::update_code_content(id, previous, revision),
+                        Message::SnippetUpdated,
+                    )
+                }
+            }
+
+            Message::FormatJson(id, format) => {
+                let Some(snippet) = self.snippets.iter().find(|s| s.id == id) else {
+                    return Task::none();
+                };
+                let SnippetContent::Json(json) = &snippet.content else {
+                    return Task::none();
+                };
+                match format.apply(json) {
+                    Ok(formatted) if formatted == json.json => {
+                        self.status_message = Some("JSON is already formatted".to_string());
+                        Task::none()
+                    }
+                    Ok(formatted) => {
+                        self.reformat_undo = Some(ReformatUndo {
+                            snippet_id: id,
+                            previous: json.json.clone(),
+                            reformatted: formatted.clone(),
+                        });
+                        let revision = snippet.revision;
+                        worker::perform(
+                            move || db::update_json_content(id, formatted, revision),
+                            Message::SnippetUpdated,
+                        )
+                    }
+                    Err(e) => {
+                        self.status_message = Some(format!("Invalid JSON: {}", e));
+                        Task::none()
+                    }
+                }
+            }
+
+            Message::OpenJsonTree(id) => {
+                self.json_tree = Some(JsonTreeState::new(id));
+                Task::none()
+            }
+
+            Message::CloseJsonTree => {
+                self.json_tree = None;
+                Task::none()
+            }
+
+            Message::ToggleJsonNode(path) => {
+                if let Some(tree) = &mut self.json_tree {
+                    tree.toggle(path);
+                }
+                Task::none()
+            }
+
+            // === Text Editor Messages ===
+            Message::OpenTextEditor(id) => {
+                self.add_menu_open = false;
+                self.text_editor = Some(if let Some(snippet_id) = id {
+                    if let Some(snippet) = self.snippets.iter().find(|s| s.id == snippet_id) {
+                        TextEditorState::from_snippet(snippet)
+                    } else {
+                        TextEditorState::new_text()
+                    }
+                } else {
+                    TextEditorState::new_text()
+                });
+                Task::none()
+            }
+
+            Message::CloseTextEditor => {
+                self.text_editor = None;
+                Task::none()
+            }
+
+            Message::TextEditorContentChanged(action) => {
+                if let Some(editor) = &mut self.text_editor {
+                    editor.content.perform(action);
+                }
+                Task::none()
+            }
+
+            Message::TextEditorLabelChanged(label) => {
+                if let Some(editor) = &mut self.text_editor {
+                    editor.label = label;
+                }
+                Task::none()
+            }
+
+            Message::TextEditorDescriptionChanged(action) => {
+                if let Some(editor) = &mut self.text_editor {
+                    editor.description.perform(action);
+                }
+                Task::none()
+            }
+
+            Message::ConfirmTextEditor => {
+                if let Some(editor) = self.text_editor.take() {
+                    let text = editor.content.text();
+                    let label = editor.label.clone();
+                    let description = normalize_description(&editor.description.text());
+                    let dedupe = self.config.color_dedupe;
+
+                    if let Some(editing_id) = editor.editing_id {
+                        let revision = editor.revision;
+                        worker::perform(
+                            move || db::update_text(editing_id, text, label, description, revision),
+                            Message::SnippetUpdated,
+                        )
+                    } else {
+                        worker::perform(
+                            move || {
+                                db::add_snippet(
+                                    Snippet::text(text, label).with_description(description),
+                                    dedupe,
+                                )
+                            },
+                            Message::SnippetAdded,
+                        )
+                    }
+                } else {
+                    Task::none()
+                }
+            }
+
+            // === Settings Messages ===
+            Message::OpenSettings => {
+                self.settings = Some(SettingsState::from_config(&self.config));
+                Task::none()
+            }
+
+            Message::CloseSettings => {
+                self.settings = None;
+                Task::none()
+            }
+
+            Message::SettingsEditorPresetChanged(preset) => {
+                if let Some(settings) = &mut self.settings {
+                    settings.editor_preset = preset;
+                }
+                Task::none()
+            }
+
+            Message::SettingsCustomCommandChanged(cmd) => {
+                if let Some(settings) = &mut self.settings {
+                    settings.custom_command = cmd;
+                }
+                Task::none()
+            }
+
+            Message::SettingsReviewExternalEditsChanged(review) => {
+                if let Some(settings) = &mut self.settings {
+                    settings.review_external_edits = review;
+                }
+                Task::none()
+            }
+
+            Message::SettingsWordWrapChanged(wrap) => {
+                if let Some(settings) = &mut self.settings {
+                    settings.editing.word_wrap = wrap;
+                }
+                Task::none()
+            }
+
+            Message::SettingsCodeWordWrapChanged(wrap) => {
+                if let Some(settings) = &mut self.settings {
+                    settings.editing.code_word_wrap = wrap;
+                }
+                Task::none()
+            }
+
+            Message::SettingsTabWidthChanged(width) => {
+                if let Some(settings) = &mut self.settings {
+                    settings.editing.tab_width = width;
+                }
+                Task::none()
+            }
+
+            Message::SettingsInsertSpacesChanged(spaces) => {
+                if let Some(settings) = &mut self.settings {
+                    settings.editing.insert_spaces = spaces;
+                }
+                Task::none()
+            }
+
+            Message::SettingsDefaultPickerModeChanged(mode) => {
+                if let Some(settings) = &mut self.settings {
+                    settings.default_picker_mode = mode;
+                }
+                Task::none()
+            }
+
+            Message::SettingsCapturePaletteChanged(palette_id) => {
+                if let Some(settings) = &mut self.settings {
+                    settings.capture_palette = palette_id;
+                }
+                Task::none()
+            }
+
+            Message::SettingsCardActionsChanged(visibility) => {
+                if let Some(settings) = &mut self.settings {
+                    settings.card_actions = visibility;
+                }
+                Task::none()
+            }
+
+            Message::SettingsSecretPolicyChanged(policy) => {
+                if let Some(settings) = &mut self.settings {
+                    settings.secret_policy = policy;
+                }
+                Task::none()
+            }
+
+            Message::SettingsCrashReportsChanged(enabled) => {
+                if let Some(settings) = &mut self.settings {
+                    settings.crash_reports = enabled;
+                }
+                Task::none()
+            }
+
+            Message::SettingsCheckForUpdatesChanged(enabled) => {
+                if let Some(settings) = &mut self.settings {
+                    settings.check_for_updates = enabled;
+                }
+                Task::none()
+            }
+
+            Message::SettingsShowFavoritesBarChanged(show) => {
+                if let Some(settings) = &mut self.settings {
+                    settings.show_favorites_bar = show;
+                }
+                Task::none()
+            }
+
+            Message::SettingsTextExpansionChanged(enabled) => {
+                if let Some(settings) = &mut self.settings {
+                    settings.text_expansion = enabled;
+                }
+                Task::none()
+            }
+
+            Message::SettingsShowSourceBadgeChanged(show) => {
+                if let Some(settings) = &mut self.settings {
+                    settings.show_source_badge = show;
+                }
+                Task::none()
+            }
+
+            Message::SettingsWindowAppearanceChanged(appearance) => {
+                if let Some(settings) = &mut self.settings {
+                    settings.window_appearance = appearance;
+                }
+                Task::none()
+            }
+
+            Message::SettingsTrashRetentionChanged(retention) => {
+                if let Some(settings) = &mut self.settings {
+                    settings.trash_retention = retention;
+                }
+                Task::none()
+            }
+
+            Message::SettingsColorDedupeChanged(dedupe) => {
+                if let Some(settings) = &mut self.settings {
+                    settings.color_dedupe = dedupe;
+                }
+                Task::none()
+            }
+
+            Message::SettingsCombineSeparatorChanged(separator) => {
+                if let Some(settings) = &mut self.settings {
+                    settings.combine_separator = separator;
+                }
+                Task::none()
+            }
+
+            Message::SettingsCopyFormatChanged(format) => {
+                if let Some(settings) = &mut self.settings {
+                    settings.copy_format = format;
+                }
+                Task::none()
+            }
+
+            Message::SettingsMarkdownTextChanged(style) => {
+                if let Some(settings) = &mut self.settings {
+                    settings.markdown_text = style;
+                }
+                Task::none()
+            }
+
+            Message::SettingsCodeThemeChanged(theme) => {
+                if let Some(settings) = &mut self.settings {
+                    settings.code_theme = theme;
+                }
+                Task::none()
+            }
+
+            Message::SettingsCopyTemplateAdded => {
+                if let Some(settings) = &mut self.settings {
+                    settings.copy_templates.push(CopyTemplate {
+                        name: String::new(),
+                        template: String::new(),
+                    });
+                }
+                Task::none()
+            }
+
+            Message::SettingsCopyTemplateNameChanged(index, name) => {
+                if let Some(template) = self
+                    .settings
+                    .as_mut()
+                    .and_then(|s| s.copy_templates.get_mut(index))
+                {
+                    template.name = name;
+                }
+                Task::none()
+            }
+
+            Message::SettingsCopyTemplateChanged(index, text) => {
+                if let Some(template) = self
+                    .settings
+                    .as_mut()
+                    .and_then(|s| s.copy_templates.get_mut(index))
+                {
+                    template.template = text;
+                }
+                Task::none()
+            }
+
+            Message::SettingsCopyTemplateRemoved(index) => {
+                if let Some(settings) = &mut self.settings {
+                    if index < settings.copy_templates.len() {
+                        settings.copy_templates.remove(index);
+                    }
+                }
+                Task::none()
+            }
+
+            Message::SettingsRunCommandAdded => {
+                if let Some(settings) = &mut self.settings {
+                    settings.run_commands.push(RunCommand {
+                        language: String::new(),
+                        command: String::new(),
+                    });
+                }
+                Task::none()
+            }
+
+            Message::SettingsRunCommandLanguageChanged(index, language) => {
+                if let Some(command) = self
+                    .settings
+                    .as_mut()
+                    .and_then(|s| s.run_commands.get_mut(index))
+                {
+                    command.language = language;
+                }
+                Task::none()
+            }
+
+            Message::SettingsRunCommandChanged(index, text) => {
+                if let Some(command) = self
+                    .settings
+                    .as_mut()
+                    .and_then(|s| s.run_commands.get_mut(index))
+                {
+                    command.command = text;
+                }
+                Task::none()
+            }
+
+            Message::SettingsRunCommandRemoved(index) => {
+                if let Some(settings) = &mut self.settings {
+                    if index < settings.run_commands.len() {
+                        settings.run_commands.remove(index);
+                    }
+                }
+                Task::none()
+            }
+
+            Message::CheckDatabaseIntegrity => self.run_maintenance(db::check_integrity),
+
+            Message::VacuumDatabase => self.run_maintenance(db::vacuum),
+
+            Message::ShowDatabaseStats => {
+                self.run_maintenance(|| db::database_stats().map(|stats| stats.to_string()))
+            }
+
+            Message::DatabaseMaintenanceFinished(result) => {
+                self.status_message = Some(match &result {
+                    Ok(report) => report.clone(),
+                    Err(e) => e.clone(),
+                });
+                if let Some(settings) = &mut self.settings {
+                    settings.maintenance_busy = false;
+                    settings.maintenance_report = Some(result);
+                }
+                Task::none()
+            }
+
+            Message::ConfirmSettings => {
+                if let Some(settings) = self.settings.take() {
+                    // Turning the update check on checks right away
+                    let check = settings.check_for_updates && !self.config.check_for_updates;
+                    settings.apply_to_config(&mut self.config);
+                    crate::crash::set_enabled(self.config.crash_reports);
+                    let config = self.config.clone();
+                    let save = Task::perform(async move { config.save() }, Message::ConfigSaved);
+                    if check {
+                        Task::batch([save, check_for_update()])
+                    } else {
+                        save
+                    }
+                } else {
+                    Task::none()
+                }
+            }
+
+            Message::ConfigSaved(result) => {
+                match result {
+                    Ok(()) => {
+                        self.status_message = Some("Settings saved".to_string());
+                    }
+                    Err(e) => {
+                        self.status_message = Some(format!("Failed to save settings: {}", e));
+                    }
+                }
+                Task::none()
+            }
+
+            // === Keyboard Shortcut Recording ===
+            Message::StartRecordingShortcut(action) => {
+                if let Some(settings) = &mut self.settings {
+                    settings.recording_action = Some(action);
+                    settings.recording_feedback = None;
+                }
+                Task::none()
+            }
+
+            Message::StopRecordingShortcut => {
+                if let Some(settings) = &mut self.settings {
+                    settings.recording_action = None;
+                }
+                Task::none()
+            }
+
+            Message::ShortcutRecorded(action, shortcut) => {
+                if let Some(settings) = &mut self.settings {
+                    settings.keyboard.set(action, shortcut);
+                    settings.recording_action = None;
+                    settings.recording_feedback = None;
+                }
+                Task::none()
+            }
+
+            Message::ShortcutRejected(reason) => {
+                // Keep recording until a usable combination is pressed
+                if let Some(settings) = &mut self.settings {
+                    settings.recording_feedback = Some(reason);
+                }
+                Task::none()
+            }
+
+            Message::ResetShortcutToDefault(action) => {
+                if let Some(settings) = &mut self.settings {
+                    let default_keyboard = KeyboardConfig::default();
+                    let default_shortcut = default_keyboard.get(action).clone();
+                    settings.keyboard.set(action, default_shortcut);
+                }
+                Task::none()
+            }
+
+            // === Add Menu Messages ===
+            Message::ToggleAddMenu => {
+                self.add_menu_open = !self.add_menu_open;
+                Task::none()
+            }
+
+            Message::CloseAddMenu => {
+                self.add_menu_open = false;
+                Task::none()
+            }
+
+            // === Export/Import Messages ===
+            Message::ExportSnippetsJson => {
+                let snippets = self
+                    .snippets
+                    .iter()
+                    .map(|s| Snippet {
+                        section: db::section_at(&self.sections, s.position)
+                            .map(|section| section.name.clone()),
+                        ..s.clone()
+                    })
+                    .collect();
+                Task::perform(
+                    async move { export_snippets_json(snippets).await },
+                    Message::ExportFinished,
+                )
+            }
+
+            Message::ExportCodeFiles(group_by_palette) => {
+                let rules = palette_rules(&self.palettes, self.filter_palette);
+                let filter = self.snippet_filter(rules.as_deref());
+                let files: Vec<(i64, Option<String>, String, String)> = self
+                    .snippets
+                    .iter()
+                    .filter(|s| filter.matches(s, &self.snippet_palettes))
+                    .filter_map(|s| {
+                        let (language, source) = match &s.content {
+                            SnippetContent::Code(code) => (code.language.as_str(), &code.code),
+                            SnippetContent::Json(json) => ("json", &json.json),
+                            _ => return None,
+                        };
+                        let folder = if group_by_palette {
+                            self.snippet_palettes
+                                .get(&s.id)
+                                .and_then(|ids| ids.first())
+                                .and_then(|id| self.palettes.iter().find(|p| p.id == *id))
+                                .map(|p| p.name.clone())
+                        } else {
+                            None
+                        };
+                        Some((
+                            s.id,
+                            folder,
+                            export_file_name(&s.label, language),
+                            source.clone(),
+                        ))
+                    })
+                    .collect();
+                if files.is_empty() {
+                    self.status_message = Some("No code snippets to export".to_string());
+                    return Task::none();
+                }
+                Task::perform(export_code_files(files), Message::CodeFilesExported)
+            }
+
+            Message::CodeFilesExported(result) => {
+                match result {
+                    Ok(msg) => self.status_message = Some(msg),
+                    Err(e) => self.status_message = Some(format!("Export failed: {}", e)),
+                }
+                // Pick up the files the snippets are now linked to
+                worker::perform(db::load_snippets, Message::SnippetsLoaded)
+            }
+
+            Message::ImportCodeFiles => Task::perform(import_code_files(), Message::ImportFinished),
+
+            Message::RevealSnippetFile(id) => {
+                let Some(path) = self
+                    .snippets
+                    .iter()
+                    .find(|s| s.id == id)
+                    .and_then(|s| s.file_on_disk())
+                else {
+                    return Task::none();
+                };
+                if let Err(e) = reveal_in_file_manager(std::path::Path::new(path)) {
+                    self.status_message = Some(e);
+                }
+                Task::none()
+            }
+
+            Message::LinkedFilesChecked(changed) => {
+                self.changed_files = changed;
+                Task::none()
+            }
+
+            Message::ExportFinished(result) => {
+                match result {
+                    Ok(msg) => self.status_message = Some(msg),
+                    Err(e) => self.status_message = Some(format!("Export failed: {}", e)),
+                }
+                Task::none()
+            }
+
+            Message::ImportSnippetsJson => Task::perform(
+                import_snippets_json(self.config.color_dedupe),
+                Message::ImportFinished,
+            ),
+
+            Message::ExportPalette(palette_id) => {
+                let Some(palette) = self.palettes.iter().find(|p| p.id == palette_id) else {
+                    return Task::none();
+                };
+                let rules = palette_rules(&self.palettes, Some(palette_id));
+                let members = SnippetFilter {
+                    palette: rules.as_deref(),
+                    ..Default::default()
+                };
+                let snippets: Vec<Snippet> = self
+                    .snippets
+                    .iter()
+                    .filter(|s| members.matches(s, &self.snippet_palettes))
+                    .cloned()
+                    .collect();
+                Task::perform(
+                    export_palette_file(PaletteFile::new(palette.name.clone(), snippets)),
+                    Message::ExportFinished,
+                )
+            }
+
+            Message::ExportEditorSnippets(palette_id) => {
+                let (name, snippets): (String, Vec<&Snippet>) = match palette_id {
+                    Some(id) => {
+                        let Some(palette) = self.palettes.iter().find(|p| p.id == id) else {
+                            return Task::none();
+                        };
+                        let rules = palette_rules(&self.palettes, Some(id));
+                        let members = SnippetFilter {
+                            palette: rules.as_deref(),
+                            ..Default::default()
+                        };
+                        (
+                            palette.name.clone(),
+                            self.snippets
+                                .iter()
+                                .filter(|s| members.matches(s, &self.snippet_palettes))
+                                .collect(),
+                        )
+                    }
+                    None => (
+                        "shard".to_string(),
+                        self.snippets
+                            .iter()
+                            .filter(|s| self.selected_snippets.contains(&s.id))
+                            .collect(),
+                    ),
+                };
+                let entries: Vec<(&str, &CodeData)> = snippets
+                    .iter()
+                    .filter_map(|s| match &s.content {
+                        SnippetContent::Code(code) => Some((s.label.as_str(), code)),
+                        _ => None,
+                    })
+                    .collect();
+                if entries.is_empty() {
+                    self.status_message = Some(match palette_id {
+                        Some(_) => format!("\"{}\" has no code snippets", name),
+                        None => "No code snippets are checked".to_string(),
+                    });
+                    return Task::none();
+                }
+                let count = entries.len();
+                let contents = code_snippets_file(&entries);
+                Task::perform(
+                    export_editor_snippets(name, count, contents),
+                    Message::ExportFinished,
+                )
+            }
+
+            Message::ExportPaletteAssets(palette_id) => {
+                let Some(palette) = self.palettes.iter().find(|p| p.id == palette_id) else {
+                    return Task::none();
+                };
+                let light = self.palette_asset_colors(palette_id);
+                if light.is_empty() {
+                    self.status_message = Some(format!("\"{}\" has no colors", palette.name));
+                    return Task::none();
+                }
+                let dark = palette
+                    .dark_palette_id
+                    .map(|id| self.palette_asset_colors(id))
+                    .unwrap_or_default();
+                let colors = pair_variants(light, &dark);
+                Task::perform(
+                    export_palette_assets(palette.name.clone(), colors),
+                    Message::ExportFinished,
+                )
+            }
+
+            Message::ExportColors(palette_id, format) => {
+                let (name, colors) = match palette_id {
+                    Some(id) => {
+                        let Some(palette) = self.palettes.iter().find(|p| p.id == id) else {
+                            return Task::none();
+                        };
+                        (palette.name.clone(), self.palette_asset_colors(id))
+                    }
+                    None => (
+                        "Shard Colors".to_string(),
+                        named_colors(self.snippets.iter().filter(|s| !s.in_inbox)),
+                    ),
+                };
+                if colors.is_empty() {
+                    self.status_message = Some(format!("\"{}\" has no colors", name));
+                    return Task::none();
+                }
+                let bytes = format.render(&name, &colors);
+                Task::perform(
+                    save_export_file(
+                        format.file_type(),
+                        format.extension(),
+                        name,
+                        colors.len(),
+                        bytes,
+                    ),
+                    Message::ExportFinished,
+                )
+            }
+
+            Message::OpenCodeExport(palette_id) => {
+                let Some(palette) = self.palettes.iter().find(|p| p.id == palette_id) else {
+                    return Task::none();
+                };
+                let colors = self.palette_asset_colors(palette_id);
+                if colors.is_empty() {
+                    self.status_message = Some(format!("\"{}\" has no colors", palette.name));
+                    return Task::none();
+                }
+                self.code_export = Some(CodeExportState::new(palette.name.clone(), colors));
+                self.palette_manager_open = false;
+                Task::none()
+            }
+
+            Message::CloseCodeExport => {
+                self.code_export = None;
+                Task::none()
+            }
+
+            Message::CodeExportFormatChanged(format) => {
+                if let Some(state) = &mut self.code_export {
+                    state.format = format;
+                }
+                Task::none()
+            }
+
+            Message::CodeExportValueChanged(value) => {
+                if let Some(state) = &mut self.code_export {
+                    state.options.value = value;
+                }
+                Task::none()
+            }
+
+            Message::CodeExportShadesToggled(shades) => {
+                if let Some(state) = &mut self.code_export {
+                    state.options.shades = shades;
+                }
+                Task::none()
+            }
+
+            Message::CodeExportMapToggled(map) => {
+                if let Some(state) = &mut self.code_export {
+                    state.options.map = map;
+                }
+                Task::none()
+            }
+
+            Message::CopyCodeExport => {
+                let Some(state) = &self.code_export else {
+                    return Task::none();
+                };
+                let text = state.output();
+                let status = format!("Copied {} as {}", state.name, state.format);
+                Task::perform(
+                    async move { copy_to_clipboard(&text).await.map(|_| status) },
+                    Message::CopyFinished,
+                )
+            }
+
+            Message::SaveCodeExport => {
+                let Some(state) = &self.code_export else {
+                    return Task::none();
+                };
+                let format = state.format;
+                Task::perform(
+                    save_export_file(
+                        format.file_type(),
+                        format.extension(),
+                        state.name.clone(),
+                        state.colors.len(),
+                        state.output().into_bytes(),
+                    ),
+                    Message::ExportFinished,
+                )
+            }
+
+            Message::ImportPaletteFile => Task::perform(
+                import_palette_file(None, self.config.color_dedupe),
+                Message::ImportFinished,
+            ),
+
+            Message::ImportTokensPalette => Task::perform(
+                import_tokens_palette(self.config.color_dedupe),
+                Message::ImportFinished,
+            ),
+
+            Message::ImportGplPalette => Task::perform(
+                import_gpl_palette(self.config.color_dedupe),
+                Message::ImportFinished,
+            ),
+
+            Message::ImportBookmarks(group_by_folder) => Task::perform(
+                import_bookmarks_html(group_by_folder),
+                Message::ImportFinished,
+            ),
+
+            Message::ImportFinished(result) => {
+                match result {
+                    Ok(msg) => self.status_message = Some(msg),
+                    Err((e, backup)) => {
+                        self.status_message = Some(format!("Import failed: {}", e));
+                        self.restore_backup = backup;
+                    }
+                }
+                // Reload snippets from database (including any imported before a failure)
+                Task::batch([
+                    worker::perform(db::load_snippets, Message::SnippetsLoaded),
+                    worker::perform(db::load_palettes, Message::PalettesLoaded),
+                    worker::perform(db::load_sections, Message::SectionsLoaded),
+                ])
+            }
+
+            // === Color Variant Messages ===
+            Message::DeriveColor(id, adjustment) => {
+                self.card_menu_snippet = None;
+                let Some(snippet) = self.snippets.iter().find(|s| s.id == id) else {
+                    return Task::none();
+                };
+                let SnippetContent::Color(color) = &snippet.content else {
+                    return Task::none();
+                };
+                let variant = adjustment.apply(color);
+                let label = format!(
+                    "{} {}",
+                    snippet.label,
+                    adjustment.display_name().to_lowercase()
+                );
+                let dedupe = self.config.color_dedupe;
+                worker::perform(
+                    move || {
+                        db::add_or_move_color(
+                            variant.r, variant.g, variant.b, variant.a, label, dedupe,
+                        )
+                    },
+                    Message::SnippetAdded,
+                )
+            }
+
+            // === Similar Colors Messages ===
+            Message::OpenSimilarColors(color_id) => {
+                self.card_menu_snippet = None;
+                self.similar_colors = Some(SimilarState { color_id });
+                Task::none()
+            }
+
+            Message::CloseSimilarColors => {
+                self.similar_colors = None;
+                Task::none()
+            }
+
+            Message::ShowSimilarColor(id) => {
+                self.similar_colors = None;
+                self.selected_snippet = Some(id);
+                Task::none()
+            }
+
+            // === Run Snippet Messages ===
+            Message::RunSnippet(id) => {
+                let Some(snippet) = self.snippets.iter().find(|s| s.id == id) else {
+                    return Task::none();
+                };
+                let SnippetContent::Code(code) = &snippet.content else {
+                    self.status_message = Some("Only code snippets can be run".to_string());
+                    return Task::none();
+                };
+                let Some(command) = self.config.run_command(&code.language).cloned() else {
+                    self.status_message = Some(format!(
+                        "No run command for {}; add one in Settings",
+                        code.language
+                    ));
+                    return Task::none();
+                };
+                let extension = language_to_extension(&code.language);
+                self.run_output = Some(RunState::new(id, snippet.label.clone()));
+                Task::perform(
+                    run_code_snippet(id, code.code.clone(), extension, command),
+                    move |result| Message::SnippetRunFinished(id, result),
+                )
+            }
+
+            Message::SnippetRunFinished(id, result) => {
+                // Results of a run whose pane was closed are dropped
+                if let Some(state) = self
+                    .run_output
+                    .as_mut()
+                    .filter(|state| state.snippet_id == id)
+                {
+                    state.result = Some(result);
+                }
+                Task::none()
+            }
+
+            Message::CopyRunOutput => {
+                let Some(output) = self.run_output.as_ref().and_then(RunState::printed) else {
+                    return Task::none();
+                };
+                Task::perform(
+                    async move {
+                        copy_to_clipboard(&output)
+                            .await
+                            .map(|_| "Copied the run output".to_string())
+                    },
+                    Message::CopyFinished,
+                )
+            }
+
+            Message::CloseRunOutput => {
+                self.run_output = None;
+                Task::none()
+            }
+
+            // === Template Placeholder Messages ===
+            Message::TemplateValueChanged(index, value) => {
+                if let Some(state) = &mut self.template_fill {
+                    if let Some(slot) = state.values.get_mut(index) {
+                        *slot = value;
+                    }
+                }
+                Task::none()
+            }
+
+            Message::CopyTemplateFill => match self.template_fill.take() {
+                Some(state) => self.copy_snippet_text(state.snippet_id, state.output()),
+                None => Task::none(),
+            },
+
+            Message::CloseTemplateFill => {
+                self.template_fill = None;
+                Task::none()
+            }
+
+            // === Contrast Checker Messages ===
+            Message::OpenContrastChecker(id) => {
+                self.contrast = Some(ContrastState::new(id));
+                Task::none()
+            }
+
+            Message::CloseContrastChecker => {
+                self.contrast = None;
+                Task::none()
+            }
+
+            Message::ContrastBackgroundSelected(id) => {
+                if let Some(state) = &mut self.contrast {
+                    state.background_id = Some(id);
+                }
+                Task::none()
+            }
+
+            Message::ContrastCustomBackgroundChanged(value) => {
+                if let Some(state) = &mut self.contrast {
+                    state.background_id = None;
+                    state.custom_background = value;
+                }
+                Task::none()
+            }
+
+            Message::SwapContrastColors => {
+                if let Some(state) = &mut self.contrast {
+                    if let Some(background_id) = state.background_id {
+                        state.background_id = Some(state.foreground_id);
+                        state.foreground_id = background_id;
+                    }
+                }
+                Task::none()
+            }
+
+            // === Color Scale Messages ===
+            Message::OpenScaleDialog(id) => {
+                let Some(snippet) = self.snippets.iter().find(|s| s.id == id) else {
+                    return Task::none();
+                };
+                if let SnippetContent::Color(color) = &snippet.content {
+                    self.scale = Some(ScaleState::new(snippet.label.clone(), color.clone()));
+                }
+                Task::none()
+            }
+
+            Message::CloseScaleDialog => {
+                self.scale = None;
+                Task::none()
+            }
+
+            Message::ScaleStepsChanged(steps) => {
+                if let Some(state) = &mut self.scale {
+                    state.steps = steps;
+                }
+                Task::none()
+            }
+
+            Message::SaveScaleColors => {
+                let Some(state) = self.scale.take() else {
+                    return Task::none();
+                };
+                let snippets = state
+                    .labeled_colors()
+                    .into_iter()
+                    .map(|(label, color)| Snippet::new(label, SnippetContent::Color(color)))
+                    .collect();
+                worker::perform(move || db::insert_snippets(snippets), Message::ScaleSaved)
+            }
+
+            Message::SaveScaleSet => {
+                let Some(state) = self.scale.take() else {
+                    return Task::none();
+                };
+                let colors = state
+                    .labeled_colors()
+                    .into_iter()
+                    .map(|(_, color)| color)
+                    .collect();
+                let snippet = Snippet::new(
+                    format!("{} scale", state.label),
+                    SnippetContent::ColorSet(ColorSetData::new(colors)),
+                );
+                worker::perform(move || db::insert_snippet(snippet), Message::SnippetAdded)
+            }
+
+            Message::ScaleSaved(result) => {
+                match result {
+                    Ok(colors) => {
+                        self.status_message = Some(format!("Added {} colors", colors.len()));
+                        self.snippets.splice(0..0, colors);
+                    }
+                    Err(e) => self.status_message = Some(format!("Save failed: {}", e)),
+                }
+                Task::none()
+            }
+
+            // === Share Image Messages ===
+            Message::OpenShareImage(id) => {
+                let mut state = ShareImageState::new(id);
+                state.options.theme = self.config.code_theme.highlighter();
+                self.share_image = Some(state);
+                Task::none()
+            }
+
+            Message::CloseShareImage => {
+                self.share_image = None;
+                Task::none()
+            }
+
+            Message::ShareImageThemeChanged(theme) => {
+                if let Some(state) = &mut self.share_image {
+                    state.options.theme = theme;
+                }
+                Task::none()
+            }
+
+            Message::ShareImageScaleChanged(scale) => {
+                if let Some(state) = &mut self.share_image {
+                    state.options.scale = scale;
+                }
+                Task::none()
+            }
+
+            Message::ShareImagePaddingChanged(padding) => {
+                if let Some(state) = &mut self.share_image {
+                    state.options.padding = padding;
+                }
+                Task::none()
+            }
+
+            Message::ShareImageChromeToggled(enabled) => {
+                if let Some(state) = &mut self.share_image {
+                    state.options.window_chrome = enabled;
+                }
+                Task::none()
+            }
+
+            Message::SaveShareImage => {
+                let Some(state) = self.share_image.take() else {
+                    return Task::none();
+                };
+                let Some(snippet) = self.snippets.iter().find(|s| s.id == state.snippet_id) else {
+                    return Task::none();
+                };
+                let SnippetContent::Code(code) = &snippet.content else {
+                    return Task::none();
+                };
+                let (code, language) = (code.code.clone(), code.language.clone());
+                let file_name = format!("{}.png", sanitize_file_stem(&snippet.label));
+                Task::perform(
+                    save_share_image(code, language, file_name, state.options),
+                    Message::ShareImageSaved,
+                )
+            }
+
+            Message::ShareImageSaved(result) => {
+                match result {
+                    Ok(msg) => self.status_message = Some(msg),
+                    Err(e) => self.status_message = Some(format!("Image export failed: {}", e)),
+                }
+                Task::none()
+            }
+
+            // === Gradient Editor Messages ===
+            Message::OpenGradientEditor(id) => {
+                self.add_menu_open = false;
+                let editing = id
+                    .and_then(|id| self.snippets.iter().find(|s| s.id == id))
+                    .and_then(GradientEditorState::from_snippet);
+                self.gradient_editor = Some(editing.unwrap_or_else(|| {
+                    GradientEditorState::new(
+                        ColorData::new(59, 130, 246, 1.0),
+                        ColorData::new(236, 72, 153, 1.0),
+                    )
+                }));
+                Task::none()
+            }
+
+            Message::CloseGradientEditor => {
+                self.gradient_editor = None;
+                Task::none()
+            }
+
+            Message::GradientStopSelected(index) => {
+                if let Some(state) = &mut self.gradient_editor {
+                    state.selected = index;
                 }
                 Task::none()
             }
 
-            Message::PickerOklchLChanged(l) => {
-                if let Some(picker) = &mut self.color_picker {
-                    picker.oklch_l = l;
+            Message::GradientStopAdded(position) => {
+                if let Some(state) = &mut self.gradient_editor {
+                    state.selected = state.gradient.insert_stop(position);
                 }
                 Task::none()
             }
 
-            Message::PickerOklchCChanged(c) => {
-                if let Some(picker) = &mut self.color_picker {
-                    picker.oklch_c = c;
+            Message::GradientStopMoved(position) => {
+                if let Some(state) = &mut self.gradient_editor {
+                    state.selected = state.gradient.move_stop(state.selected, position);
                 }
                 Task::none()
             }
 
-            Message::PickerOklchHChanged(h) => {
-                if let Some(picker) = &mut self.color_picker {
-                    picker.oklch_h = h;
+            Message::GradientStopRemoved => {
+                if let Some(state) = &mut self.gradient_editor {
+                    if state.gradient.remove_stop(state.selected) {
+                        state.selected = state.selected.saturating_sub(1);
+                    }
                 }
                 Task::none()
             }
 
-            Message::PickerCLChanged(chroma, lightness) => {
-                if let Some(picker) = &mut self.color_picker {
-                    picker.oklch_c = chroma;
-                    picker.oklch_l = lightness;
+            Message::GradientAngleChanged(angle) => {
+                if let Some(state) = &mut self.gradient_editor {
+                    state.gradient.angle = angle;
                 }
                 Task::none()
             }
 
-            Message::ConfirmColorPicker => {
-                if let Some(picker) = self.color_picker.take() {
-                    let (r, g, b) = picker.to_rgb();
-                    let alpha = picker.alpha;
-                    let label = if picker.label.is_empty() {
-                        ColorData::new(r, g, b, alpha).to_hex()
-                    } else {
-                        picker.label.clone()
-                    };
-
-                    if let Some(editing_id) = picker.editing_id {
-                        // Update existing color
-                        Task::perform(
-                            async move { db::update_color(editing_id, r, g, b, alpha, label) },
-                            Message::SnippetUpdated,
-                        )
-                    } else {
-                        // Add new color
-                        Task::perform(
-                            async move { db::add_or_move_color(r, g, b, alpha, label) },
-                            Message::SnippetAdded,
-                        )
-                    }
-                } else {
-                    Task::none()
+            Message::EditGradientStopColor => {
+                let Some(state) = &self.gradient_editor else {
+                    return Task::none();
+                };
+                if let Some(stop) = state.gradient.stops.get(state.selected) {
+                    self.color_picker = Some(ColorPickerState::for_gradient_stop(
+                        state.selected,
+                        &stop.color,
+                        self.config.default_picker_mode,
+                    ));
                 }
+                Task::none()
             }
 
-            Message::SaveColorAsNew => {
-                // Save the current picker color as a new snippet (without modifying the original)
-                if let Some(picker) = self.color_picker.take() {
-                    let (r, g, b) = picker.to_rgb();
-                    let alpha = picker.alpha;
-                    let label = if picker.label.is_empty() {
-                        ColorData::new(r, g, b, alpha).to_hex()
-                    } else {
-                        picker.label.clone()
-                    };
+            Message::CopyGradientCss => {
+                let Some(state) = &self.gradient_editor else {
+                    return Task::none();
+                };
+                let css = state.css();
+                Task::perform(
+                    async move {
+                        copy_to_clipboard(&css)
+                            .await
+                            .map(|_| "Copied gradient CSS".to_string())
+                    },
+                    Message::CopyFinished,
+                )
+            }
 
-                    // Always add as new, regardless of editing_id
-                    Task::perform(
-                        async move { db::add_or_move_color(r, g, b, alpha, label) },
-                        Message::SnippetAdded,
-                    )
-                } else {
-                    Task::none()
+            Message::GradientLabelChanged(label) => {
+                if let Some(state) = &mut self.gradient_editor {
+                    state.label = label;
                 }
+                Task::none()
             }
 
-            Message::SnippetUpdated(result) => {
-                match result {
-                    Ok(snippet) => {
-                        if let Some(existing) =
-                            self.snippets.iter_mut().find(|s| s.id == snippet.id)
-                        {
-                            *existing = snippet;
-                        }
-                        self.status_message = Some("Snippet updated".to_string());
-                    }
-                    Err(e) => {
-                        self.status_message = Some(format!("Update failed: {}", e));
-                    }
+            Message::SaveGradient => {
+                let Some(state) = self.gradient_editor.take() else {
+                    return Task::none();
+                };
+                let dedupe = self.config.color_dedupe;
+                match state.editing_id {
+                    Some(id) => worker::perform(
+                        move || {
+                            db::update_gradient(
+                                id,
+                                state.gradient,
+                                state.label,
+                                state.description,
+                                state.revision,
+                            )
+                        },
+                        Message::SnippetUpdated,
+                    ),
+                    None => worker::perform(
+                        move || {
+                            db::add_snippet(Snippet::gradient(state.gradient, state.label), dedupe)
+                        },
+                        Message::SnippetAdded,
+                    ),
                 }
-                Task::none()
             }
 
-            // === Code Editor Messages ===
-            Message::OpenCodeEditor(id) => {
+            // === Region Scan Messages ===
+            Message::ScanRegion => {
                 self.add_menu_open = false;
-                self.code_editor = Some(if let Some(snippet_id) = id {
-                    if let Some(snippet) = self.snippets.iter().find(|s| s.id == snippet_id) {
-                        CodeEditorState::from_snippet(snippet)
-                    } else {
-                        CodeEditorState::new_code()
-                    }
-                } else {
-                    CodeEditorState::new_code()
-                });
-                Task::none()
+                Task::perform(
+                    async {
+                        let mut clipboard = arboard::Clipboard::new()
+                            .map_err(|e| format!("Clipboard error: {}", e))?;
+                        let image = clipboard.get_image().map_err(|_| {
+                            "No image on the clipboard. Copy a screen region with your \
+                             system screenshot tool first"
+                                .to_string()
+                        })?;
+                        Ok(count_image_colors(&image.bytes))
+                    },
+                    Message::RegionScanned,
+                )
             }
 
-            Message::CloseCodeEditor => {
-                self.code_editor = None;
+            Message::RegionScanned(result) => {
+                match result {
+                    Ok(counts) if counts.is_empty() => {
+                        self.status_message = Some("No colors found in the region".to_string());
+                    }
+                    Ok(counts) => self.region_scan = Some(RegionScanState::new(counts)),
+                    Err(e) => self.status_message = Some(format!("Scan failed: {}", e)),
+                }
                 Task::none()
             }
 
-            Message::CodeEditorContentChanged(action) => {
-                if let Some(editor) = &mut self.code_editor {
-                    editor.content.perform(action);
+            Message::ToggleScannedColor(index) => {
+                if let Some(scanned) = self
+                    .region_scan
+                    .as_mut()
+                    .and_then(|state| state.colors.get_mut(index))
+                {
+                    scanned.selected = !scanned.selected;
                 }
                 Task::none()
             }
 
-            Message::CodeEditorLanguageChanged(language) => {
-                if let Some(editor) = &mut self.code_editor {
-                    editor.language = language;
+            Message::SelectAllScannedColors(selected) => {
+                if let Some(state) = &mut self.region_scan {
+                    for scanned in &mut state.colors {
+                        scanned.selected = selected;
+                    }
                 }
                 Task::none()
             }
 
-            Message::CodeEditorLabelChanged(label) => {
-                if let Some(editor) = &mut self.code_editor {
-                    editor.label = label;
+            Message::AddScannedColors => {
+                let Some(state) = self.region_scan.take() else {
+                    return Task::none();
+                };
+                // Add least common first so the most common color ends up on top
+                let dedupe = self.config.color_dedupe;
+                if let Some(name) = state.palette_name.clone() {
+                    let colors = state.selected_colors();
+                    return Task::perform(
+                        async move {
+                            worker::run(move || add_image_palette(name, colors, dedupe))
+                                .await
+                                .map_err(|e| (e, None))?
+                        },
+                        Message::ImportFinished,
+                    );
                 }
-                Task::none()
+                Task::batch(state.selected_colors().into_iter().rev().map(|color| {
+                    let label = color.to_hex();
+                    worker::perform(
+                        move || {
+                            db::add_or_move_color(color.r, color.g, color.b, color.a, label, dedupe)
+                        },
+                        Message::SnippetAdded,
+                    )
+                }))
             }
 
-            Message::ConfirmCodeEditor => {
-                if let Some(editor) = self.code_editor.take() {
-                    let code = editor.content.text();
-                    let language = editor.language.clone();
-                    let label = editor.label.clone();
+            Message::ExtractImagePalette => {
+                self.add_menu_open = false;
+                Task::perform(extract_image_palette(), Message::ImagePaletteExtracted)
+            }
 
-                    if let Some(editing_id) = editor.editing_id {
-                        Task::perform(
-                            async move { db::update_code(editing_id, code, language, label) },
-                            Message::SnippetUpdated,
-                        )
-                    } else {
-                        Task::perform(
-                            async move { db::add_code_snippet(code, language, label) },
-                            Message::SnippetAdded,
-                        )
+            Message::ImagePaletteExtracted(result) => {
+                match result {
+                    Ok((_, colors)) if colors.is_empty() => {
+                        self.status_message = Some("No colors found in the image".to_string());
                     }
-                } else {
-                    Task::none()
+                    Ok((name, colors)) => {
+                        self.region_scan = Some(RegionScanState::from_image(name, colors))
+                    }
+                    Err(e) => self.status_message = Some(format!("Extraction failed: {}", e)),
                 }
+                Task::none()
             }
 
-            // === Text Editor Messages ===
-            Message::OpenTextEditor(id) => {
-                self.add_menu_open = false;
-                self.text_editor = Some(if let Some(snippet_id) = id {
-                    if let Some(snippet) = self.snippets.iter().find(|s| s.id == snippet_id) {
-                        TextEditorState::from_snippet(snippet)
-                    } else {
-                        TextEditorState::new_text()
-                    }
-                } else {
-                    TextEditorState::new_text()
-                });
+            Message::ImagePaletteNameChanged(name) => {
+                if let Some(state) = &mut self.region_scan {
+                    state.palette_name = Some(name);
+                }
                 Task::none()
             }
 
-            Message::CloseTextEditor => {
-                self.text_editor = None;
+            Message::CloseRegionScan => {
+                self.region_scan = None;
                 Task::none()
             }
 
-            Message::TextEditorContentChanged(action) => {
-                if let Some(editor) = &mut self.text_editor {
-                    editor.content.perform(action);
+            // === Encryption Messages ===
+            Message::UnlockPassphraseChanged(passphrase) => {
+                if let Some(state) = &mut self.unlock {
+                    state.passphrase = passphrase;
+                    state.error = None;
                 }
                 Task::none()
             }
 
-            Message::TextEditorLabelChanged(label) => {
-                if let Some(editor) = &mut self.text_editor {
-                    editor.label = label;
+            Message::SubmitUnlock => {
+                let Some(state) = self.unlock.as_mut().filter(|s| !s.busy) else {
+                    return Task::none();
+                };
+                if state.passphrase.is_empty() {
+                    return Task::none();
                 }
-                Task::none()
+                state.busy = true;
+                let passphrase = state.passphrase.clone();
+                worker::perform(
+                    move || db::unlock_database(passphrase),
+                    Message::DatabaseUnlocked,
+                )
             }
 
-            Message::ConfirmTextEditor => {
-                if let Some(editor) = self.text_editor.take() {
-                    let text = editor.content.text();
-                    let label = editor.label.clone();
-
-                    if let Some(editing_id) = editor.editing_id {
-                        Task::perform(
-                            async move { db::update_text(editing_id, text, label) },
-                            Message::SnippetUpdated,
-                        )
-                    } else {
-                        Task::perform(
-                            async move { db::add_text_snippet(text, label) },
-                            Message::SnippetAdded,
-                        )
+            Message::DatabaseUnlocked(result) => match result {
+                Ok(()) => {
+                    self.unlock = None;
+                    self.load_library()
+                }
+                Err(e) => {
+                    if let Some(state) = &mut self.unlock {
+                        state.busy = false;
+                        state.passphrase.clear();
+                        state.error = Some(e);
                     }
-                } else {
                     Task::none()
                 }
-            }
-
-            // === Settings Messages ===
-            Message::OpenSettings => {
-                self.settings = Some(SettingsState::from_config(&self.config));
-                Task::none()
-            }
+            },
 
-            Message::CloseSettings => {
-                self.settings = None;
+            Message::OpenEncryptDatabase => {
+                self.encrypt_database = Some(EncryptState::default());
                 Task::none()
             }
 
-            Message::SettingsEditorPresetChanged(preset) => {
-                if let Some(settings) = &mut self.settings {
-                    settings.editor_preset = preset;
+            Message::EncryptPassphraseChanged(passphrase) => {
+                if let Some(state) = &mut self.encrypt_database {
+                    state.passphrase = passphrase;
+                    state.error = None;
                 }
                 Task::none()
             }
 
-            Message::SettingsCustomCommandChanged(cmd) => {
-                if let Some(settings) = &mut self.settings {
-                    settings.custom_command = cmd;
+            Message::EncryptConfirmChanged(confirm) => {
+                if let Some(state) = &mut self.encrypt_database {
+                    state.confirm = confirm;
+                    state.error = None;
                 }
                 Task::none()
             }
 
-            Message::SettingsDefaultPickerModeChanged(mode) => {
-                if let Some(settings) = &mut self.settings {
-                    settings.default_picker_mode = mode;
+            Message::SubmitEncrypt => {
+                let Some(state) = self.encrypt_database.as_mut().filter(|s| !s.busy) else {
+                    return Task::none();
+                };
+                if let Err(e) = state.validate() {
+                    state.error = Some(e);
+                    return Task::none();
                 }
-                Task::none()
+                state.busy = true;
+                let passphrase = state.passphrase.clone();
+                worker::perform(
+                    move || db::encrypt_database(passphrase),
+                    Message::DatabaseEncrypted,
+                )
             }
 
-            Message::ConfirmSettings => {
-                if let Some(settings) = self.settings.take() {
-                    settings.apply_to_config(&mut self.config);
-                    let config = self.config.clone();
-                    Task::perform(async move { config.save() }, Message::ConfigSaved)
-                } else {
-                    Task::none()
+            Message::CloseEncryptDatabase => {
+                if !self.encrypt_database.as_ref().is_some_and(|s| s.busy) {
+                    self.encrypt_database = None;
                 }
+                Task::none()
             }
 
-            Message::ConfigSaved(result) => {
+            Message::DatabaseEncrypted(result) => {
                 match result {
-                    Ok(()) => {
-                        self.status_message = Some("Settings saved".to_string());
+                    Ok(backups) => {
+                        self.encrypt_database = None;
+                        self.database_encrypted = true;
+                        let mut status = "Database encrypted".to_string();
+                        if backups.encrypted > 0 {
+                            status.push_str(&format!(
+                                "; {} backup{} encrypted with the same passphrase",
+                                backups.encrypted,
+                                if backups.encrypted == 1 { "" } else { "s" }
+                            ));
+                        }
+                        if backups.removed > 0 {
+                            status.push_str(&format!(
+                                "; {} plaintext backup{} deleted (could not be encrypted)",
+                                backups.removed,
+                                if backups.removed == 1 { "" } else { "s" }
+                            ));
+                        }
+                        self.status_message = Some(status);
                     }
                     Err(e) => {
-                        self.status_message = Some(format!("Failed to save settings: {}", e));
+                        if let Some(state) = &mut self.encrypt_database {
+                            state.busy = false;
+                            state.error = Some(e);
+                        }
                     }
                 }
                 Task::none()
             }
 
-            // === Keyboard Shortcut Recording ===
-            Message::StartRecordingShortcut(action) => {
-                if let Some(settings) = &mut self.settings {
-                    settings.recording_action = Some(action);
-                }
-                Task::none()
-            }
+            Message::PickDatabaseFolder => Task::perform(
+                async {
+                    rfd::AsyncFileDialog::new()
+                        .set_title("Choose Database Folder")
+                        .pick_folder()
+                        .await
+                        .map(|folder| folder.path().to_path_buf())
+                },
+                Message::DatabaseFolderPicked,
+            ),
 
-            Message::StopRecordingShortcut => {
-                if let Some(settings) = &mut self.settings {
-                    settings.recording_action = None;
-                }
-                Task::none()
-            }
+            Message::DatabaseFolderPicked(Some(dir)) => self.relocate_database(Some(dir)),
 
-            Message::ShortcutRecorded(action, shortcut) => {
-                if let Some(settings) = &mut self.settings {
-                    settings.keyboard.set(action, shortcut);
-                    settings.recording_action = None;
+            Message::DatabaseFolderPicked(None) => Task::none(),
+
+            Message::ResetDatabaseFolder => self.relocate_database(None),
+
+            Message::DatabaseRelocated(result) => match result {
+                Ok((dir, moved)) => {
+                    self.config.database_path = dir.clone();
+                    if let Some(settings) = &mut self.settings {
+                        settings.database_path = dir;
+                    }
+                    let location = db::get_database_path()
+                        .map(|path| path.display().to_string())
+                        .unwrap_or_default();
+                    self.status_message = Some(if moved {
+                        format!("Moved database to {}", location)
+                    } else {
+                        format!("Opened database at {}", location)
+                    });
+
+                    // The database there may be a different, encrypted one
+                    self.database_encrypted = db::is_encrypted();
+                    let load_library = if db::needs_unlock() {
+                        self.snippets.clear();
+                        self.unlock = Some(UnlockState::default());
+                        Task::none()
+                    } else {
+                        self.load_library()
+                    };
+                    let config = self.config.clone();
+                    Task::batch([
+                        load_library,
+                        Task::perform(async move { config.save() }, Message::ConfigSaved),
+                    ])
                 }
-                Task::none()
-            }
+                Err(e) => {
+                    self.status_message = Some(format!("Failed to move database: {}", e));
+                    Task::none()
+                }
+            },
 
-            Message::ResetShortcutToDefault(action) => {
-                if let Some(settings) = &mut self.settings {
-                    let default_keyboard = KeyboardConfig::default();
-                    let default_shortcut = default_keyboard.get(action).clone();
-                    settings.keyboard.set(action, default_shortcut);
+            Message::OpenCrashReport => {
+                if let Some(path) = self.crash_report.take() {
+                    let path = crate::crash::mark_seen(&path);
+                    if let Err(e) = open_in_default_app(&path) {
+                        self.status_message = Some(e);
+                    }
+                }
+                Task::none()
+            }
+
+            Message::DismissCrashReport => {
+                if let Some(path) = self.crash_report.take() {
+                    crate::crash::mark_seen(&path);
                 }
                 Task::none()
             }
 
-            // === Add Menu Messages ===
-            Message::ToggleAddMenu => {
-                self.add_menu_open = !self.add_menu_open;
+            Message::UpdateChecked(result) => {
+                // A failed check stays quiet; it runs again on the next start
+                if let Ok(release) = result {
+                    self.available_update = release;
+                }
                 Task::none()
             }
 
-            Message::CloseAddMenu => {
-                self.add_menu_open = false;
+            Message::OpenReleaseNotes => {
+                self.release_notes_open = self.available_update.is_some();
                 Task::none()
             }
 
-            // === Export/Import Messages ===
-            Message::ExportSnippetsJson => {
-                let snippets = self.snippets.clone();
-                Task::perform(
-                    async move { export_snippets_json(snippets).await },
-                    Message::ExportFinished,
-                )
+            Message::CloseReleaseNotes => {
+                self.release_notes_open = false;
+                Task::none()
             }
 
-            Message::ExportFinished(result) => {
-                match result {
-                    Ok(msg) => self.status_message = Some(msg),
-                    Err(e) => self.status_message = Some(format!("Export failed: {}", e)),
+            Message::OpenReleasePage => {
+                self.release_notes_open = false;
+                if let Some(release) = &self.available_update {
+                    if let Err(e) = open_url(&release.url) {
+                        self.status_message = Some(e);
+                    }
                 }
                 Task::none()
             }
 
-            Message::ImportSnippetsJson => {
-                Task::perform(import_snippets_json(), Message::ImportFinished)
+            Message::RestoreBackup => {
+                if let Some(path) = self.restore_backup.take() {
+                    worker::perform(move || db::restore_backup(path), Message::BackupRestored)
+                } else {
+                    Task::none()
+                }
             }
 
-            Message::ImportFinished(result) => {
-                match result {
-                    Ok(msg) => {
-                        self.status_message = Some(msg);
-                        // Reload snippets from database
-                        return Task::perform(
-                            async { db::load_snippets() },
-                            Message::SnippetsLoaded,
-                        );
-                    }
-                    Err(e) => self.status_message = Some(format!("Import failed: {}", e)),
+            Message::BackupRestored(result) => match result {
+                Ok(path) => {
+                    self.status_message = Some(format!("Restored backup {}", path.display()));
+                    Task::batch([
+                        worker::perform(db::load_snippets, Message::SnippetsLoaded),
+                        worker::perform(db::load_trash, Message::TrashLoaded),
+                        worker::perform(db::load_palettes, Message::PalettesLoaded),
+                    ])
                 }
-                Task::none()
-            }
+                Err(e) => {
+                    self.status_message = Some(format!("Restore failed: {}", e));
+                    Task::none()
+                }
+            },
 
             // === Palette Messages ===
             Message::PalettesLoaded(result) => {
@@ -862,6 +4329,27 @@ impl Shard {
 
             Message::FilterPaletteChanged(palette_id) => {
                 self.filter_palette = palette_id;
+                // Keep the branch holding the selected palette open
+                let mut parent = palette_id
+                    .and_then(|id| self.palettes.iter().find(|p| p.id == id))
+                    .and_then(|p| p.parent_id);
+                while let Some(id) = parent {
+                    if !self.expanded_palettes.insert(id) {
+                        break;
+                    }
+                    parent = self
+                        .palettes
+                        .iter()
+                        .find(|p| p.id == id)
+                        .and_then(|p| p.parent_id);
+                }
+                Task::none()
+            }
+
+            Message::TogglePaletteExpanded(palette_id) => {
+                if !self.expanded_palettes.remove(&palette_id) {
+                    self.expanded_palettes.insert(palette_id);
+                }
                 Task::none()
             }
 
@@ -873,6 +4361,7 @@ impl Shard {
             Message::ClosePaletteManager => {
                 self.palette_manager_open = false;
                 self.new_palette_name.clear();
+                self.new_palette_query.clear();
                 Task::none()
             }
 
@@ -881,14 +4370,34 @@ impl Shard {
                 Task::none()
             }
 
+            Message::NewPaletteQueryChanged(query) => {
+                self.new_palette_query = query;
+                Task::none()
+            }
+
             Message::CreatePalette(name) => {
                 let name = name.trim().to_string();
                 if name.is_empty() {
                     return Task::none();
                 }
+                let query = if self.new_palette_query.trim().is_empty() {
+                    None
+                } else {
+                    match PaletteQuery::parse(&self.new_palette_query) {
+                        Ok(query) => Some(query),
+                        Err(e) => {
+                            self.status_message = Some(format!("Invalid query: {}", e));
+                            return Task::none();
+                        }
+                    }
+                };
                 self.new_palette_name.clear();
-                Task::perform(
-                    async move { db::create_palette(name) },
+                self.new_palette_query.clear();
+                worker::perform(
+                    move || match query {
+                        Some(query) => db::create_smart_palette(name, query),
+                        None => db::create_palette(name),
+                    },
                     Message::PaletteCreated,
                 )
             }
@@ -910,8 +4419,8 @@ impl Shard {
                 if new_name.is_empty() {
                     return Task::none();
                 }
-                Task::perform(
-                    async move { db::rename_palette(id, new_name) },
+                worker::perform(
+                    move || db::rename_palette(id, new_name),
                     Message::PaletteRenamed,
                 )
             }
@@ -930,15 +4439,63 @@ impl Shard {
                 Task::none()
             }
 
-            Message::DeletePalette(id) => Task::perform(
-                async move { db::delete_palette(id) },
-                Message::PaletteDeleted,
+            Message::SetPaletteParent(id, parent_id) => worker::perform(
+                move || db::set_palette_parent(id, parent_id),
+                Message::PaletteParentChanged,
             ),
 
+            Message::PaletteParentChanged(result) => {
+                match result {
+                    Ok((id, parent_id)) => {
+                        if let Some(palette) = self.palettes.iter_mut().find(|p| p.id == id) {
+                            palette.parent_id = parent_id;
+                        }
+                        if let Some(parent_id) = parent_id {
+                            self.expanded_palettes.insert(parent_id);
+                        }
+                    }
+                    Err(e) => self.status_message = Some(format!("Move failed: {}", e)),
+                }
+                Task::none()
+            }
+
+            Message::SetPaletteDarkVariant(id, dark_id) => worker::perform(
+                move || db::set_dark_palette(id, dark_id),
+                Message::PaletteDarkVariantChanged,
+            ),
+
+            Message::PaletteDarkVariantChanged(result) => {
+                match result {
+                    Ok((id, dark_id)) => {
+                        if let Some(palette) = self.palettes.iter_mut().find(|p| p.id == id) {
+                            palette.dark_palette_id = dark_id;
+                        }
+                    }
+                    Err(e) => self.status_message = Some(format!("Link failed: {}", e)),
+                }
+                Task::none()
+            }
+
+            Message::DeletePalette(id) => {
+                worker::perform(move || db::delete_palette(id), Message::PaletteDeleted)
+            }
+
             Message::PaletteDeleted(result) => {
                 match result {
                     Ok(id) => {
+                        // Nested palettes move up to the deleted palette's parent
+                        let parent_id = self
+                            .palettes
+                            .iter()
+                            .find(|p| p.id == id)
+                            .and_then(|p| p.parent_id);
+                        for palette in &mut self.palettes {
+                            if palette.parent_id == Some(id) {
+                                palette.parent_id = parent_id;
+                            }
+                        }
                         self.palettes.retain(|p| p.id != id);
+                        self.expanded_palettes.remove(&id);
                         // Clear filter if deleted palette was selected
                         if self.filter_palette == Some(id) {
                             self.filter_palette = None;
@@ -948,6 +4505,9 @@ impl Shard {
                             palette_ids.retain(|&pid| pid != id);
                         }
                         self.status_message = Some("Palette deleted".to_string());
+                        if self.config.split_pane.palette == Some(id) {
+                            return self.update(Message::SplitPanePaletteChanged(None));
+                        }
                     }
                     Err(e) => self.status_message = Some(format!("Delete failed: {}", e)),
                 }
@@ -961,8 +4521,8 @@ impl Shard {
                     .or_default()
                     .push(palette_id);
                 self.palette_dropdown_snippet = None;
-                Task::perform(
-                    async move { db::add_snippet_to_palette(palette_id, snippet_id) },
+                worker::perform(
+                    move || db::add_snippet_to_palette(palette_id, snippet_id),
                     Message::SnippetPaletteUpdated,
                 )
             }
@@ -972,8 +4532,8 @@ impl Shard {
                 if let Some(palette_ids) = self.snippet_palettes.get_mut(&snippet_id) {
                     palette_ids.retain(|&id| id != palette_id);
                 }
-                Task::perform(
-                    async move { db::remove_snippet_from_palette(palette_id, snippet_id) },
+                worker::perform(
+                    move || db::remove_snippet_from_palette(palette_id, snippet_id),
                     Message::SnippetPaletteUpdated,
                 )
             }
@@ -991,9 +4551,66 @@ impl Shard {
                 } else {
                     snippet_id
                 };
+                self.palette_dropdown_query.clear();
+                self.palette_dropdown_cursor = 0;
+                if self.palette_dropdown_snippet.is_some() {
+                    widget::operation::focus(PALETTE_SEARCH_ID)
+                } else {
+                    Task::none()
+                }
+            }
+
+            Message::OpenPaletteAssignment => match self.selected_snippet {
+                Some(id) if self.palette_dropdown_snippet != Some(id) => {
+                    self.update(Message::TogglePaletteDropdown(Some(id)))
+                }
+                Some(_) => Task::none(),
+                None => {
+                    self.status_message = Some("Select a snippet first".to_string());
+                    Task::none()
+                }
+            },
+
+            Message::PaletteDropdownQueryChanged(query) => {
+                self.palette_dropdown_query = query;
+                self.palette_dropdown_cursor = 0;
+                Task::none()
+            }
+
+            Message::PaletteDropdownNext => {
+                let len = filter_palettes(&self.palettes, &self.palette_dropdown_query).len();
+                self.palette_dropdown_cursor =
+                    (self.palette_dropdown_cursor + 1).min(len.saturating_sub(1));
                 Task::none()
             }
 
+            Message::PaletteDropdownPrevious => {
+                self.palette_dropdown_cursor = self.palette_dropdown_cursor.saturating_sub(1);
+                Task::none()
+            }
+
+            Message::ToggleHighlightedPalette => {
+                let Some(snippet_id) = self.palette_dropdown_snippet else {
+                    return Task::none();
+                };
+                let Some(palette_id) =
+                    filter_palettes(&self.palettes, &self.palette_dropdown_query)
+                        .get(self.palette_dropdown_cursor)
+                        .map(|p| p.id)
+                else {
+                    return Task::none();
+                };
+                let is_member = self
+                    .snippet_palettes
+                    .get(&snippet_id)
+                    .is_some_and(|ids| ids.contains(&palette_id));
+                if is_member {
+                    self.update(Message::RemoveSnippetFromPalette(snippet_id, palette_id))
+                } else {
+                    self.update(Message::AddSnippetToPalette(snippet_id, palette_id))
+                }
+            }
+
             // === Window Management ===
             Message::WindowOpened(id) => {
                 // Window successfully opened
@@ -1004,11 +4621,19 @@ impl Shard {
             Message::WindowClosed(id) => {
                 // Check if this was the main window
                 let was_main = matches!(self.windows.get(&id), Some(WindowKind::Main));
+                if matches!(self.windows.get(&id), Some(WindowKind::Eyedropper)) {
+                    // Closed by the system rather than by picking or cancelling
+                    return self.close_eyedropper();
+                }
                 self.windows.remove(&id);
 
                 if was_main {
-                    // Main window closed - exit the application
-                    iced::exit()
+                    // Main window closed - keep the scratchpad, then exit the application
+                    let text = self.scratchpad.text();
+                    worker::perform(
+                        move || db::save_scratchpad(text),
+                        Message::ScratchpadSavedOnExit,
+                    )
                 } else {
                     // Pinned window closed - just remove from tracking
                     Task::none()
@@ -1019,17 +4644,21 @@ impl Shard {
                 // Check if snippet exists
                 if self.snippets.iter().any(|s| s.id == snippet_id) {
                     // Open a new always-on-top window for the snippet
+                    let appearance = self.config.window_appearance;
                     let (pinned_id, open_task) = window::open(window::Settings {
                         size: iced::Size::new(400.0, 300.0),
                         position: window::Position::Centered,
                         resizable: true,
-                        decorations: true, // Keep decorations for now (title bar with close button)
+                        decorations: !appearance.borderless,
+                        transparent: appearance.transparent(),
+                        blur: appearance.translucent && appearance.blur,
                         level: window::Level::AlwaysOnTop,
                         ..window::Settings::default()
                     });
 
                     // Track the pinned window
-                    self.windows.insert(pinned_id, WindowKind::Pinned(snippet_id));
+                    self.windows
+                        .insert(pinned_id, WindowKind::Pinned(snippet_id));
                     self.status_message = Some("Snippet pinned".to_string());
 
                     open_task.map(Message::WindowOpened)
@@ -1039,40 +4668,577 @@ impl Shard {
                 }
             }
 
-            Message::UnpinSnippet(id) => {
-                // Close pinned window
-                self.windows.remove(&id);
-                window::close(id)
-            }
+            Message::PinPaletteBoard(palette_id) => {
+                let appearance = self.config.window_appearance;
+                let (board_id, open_task) = window::open(window::Settings {
+                    size: iced::Size::new(300.0, 420.0),
+                    position: window::Position::Centered,
+                    resizable: true,
+                    decorations: !appearance.borderless,
+                    transparent: appearance.transparent(),
+                    blur: appearance.translucent && appearance.blur,
+                    level: window::Level::AlwaysOnTop,
+                    ..window::Settings::default()
+                });
+                self.windows.insert(board_id, WindowKind::Board(palette_id));
+                self.palette_manager_open = false;
+                self.status_message = Some("Palette board pinned".to_string());
+                open_task.map(Message::WindowOpened)
+            }
+
+            Message::BoardKeyPressed(window_id, index) => {
+                let Some(WindowKind::Board(palette_id)) = self.windows.get(&window_id) else {
+                    return Task::none();
+                };
+                self.copy_board_snippet(*palette_id, index)
+            }
+
+            Message::GlobalNumberKey(modifiers, index) => {
+                let board = self.windows.values().find_map(|kind| match kind {
+                    WindowKind::Board(palette_id)
+                        if self.config.board_hotkey(*palette_id).modifiers() == Some(modifiers) =>
+                    {
+                        Some(*palette_id)
+                    }
+                    _ => None,
+                });
+                match board {
+                    Some(palette_id) => self.copy_board_snippet(palette_id, index),
+                    None => Task::none(),
+                }
+            }
+
+            Message::BoardHotkeyChanged(palette_id, hotkey) => {
+                self.config.set_board_hotkey(palette_id, hotkey);
+                let config = self.config.clone();
+                Task::perform(async move { config.save() }, Message::ConfigSaved)
+            }
+
+            Message::OpenQuickPopup => {
+                if let Some(id) = self.quick_popup_id() {
+                    return window::gain_focus(id);
+                }
+                let appearance = self.config.window_appearance;
+                let (popup_id, open_task) = window::open(window::Settings {
+                    size: iced::Size::new(460.0, 340.0),
+                    position: window::Position::Centered,
+                    resizable: false,
+                    decorations: !appearance.borderless,
+                    transparent: appearance.transparent(),
+                    blur: appearance.translucent && appearance.blur,
+                    level: window::Level::AlwaysOnTop,
+                    ..window::Settings::default()
+                });
+                self.windows.insert(popup_id, WindowKind::QuickPopup);
+                self.quick_query.clear();
+                // Focus the search once the window is there
+                open_task.then(|id| {
+                    Task::batch([
+                        Task::done(Message::WindowOpened(id)),
+                        widget::operation::focus(QUICK_POPUP_INPUT_ID),
+                    ])
+                })
+            }
+
+            Message::QuickQueryChanged(query) => {
+                self.quick_query = query;
+                Task::none()
+            }
+
+            Message::SubmitQuickPopup => match self.quick_popup_matches().first() {
+                Some(snippet) => {
+                    let id = snippet.id;
+                    self.update(Message::QuickCopy(id))
+                }
+                None => self.update(Message::QuickCapture),
+            },
+
+            Message::QuickCopy(id) => {
+                let copy = self.update(Message::CopySnippet(id));
+                Task::batch([copy, self.update(Message::CloseQuickPopup)])
+            }
+
+            Message::QuickCapture => {
+                let text = std::mem::take(&mut self.quick_query);
+                if text.trim().is_empty() {
+                    return Task::none();
+                }
+                // Added like pasted text, so its kind is detected the same way
+                let capture = self.update(Message::PasteContentReceived(Some(ClipboardContent {
+                    text,
+                    html: None,
+                    source_app: None,
+                })));
+                Task::batch([capture, self.update(Message::CloseQuickPopup)])
+            }
+
+            Message::CloseQuickPopup => match self.quick_popup_id() {
+                Some(id) => {
+                    self.windows.remove(&id);
+                    window::close(id)
+                }
+                None => Task::none(),
+            },
+
+            Message::UnpinSnippet(id) => {
+                // Close pinned window
+                self.windows.remove(&id);
+                window::close(id)
+            }
+
+            Message::TogglePinSelectedSnippet => {
+                let Some(snippet_id) = self.selected_snippet else {
+                    return Task::none();
+                };
+
+                // Unpin if the snippet already has a pinned window, otherwise pin it
+                let pinned_window = self
+                    .pinned_windows()
+                    .into_iter()
+                    .find(|(_, id)| *id == snippet_id)
+                    .map(|(window_id, _)| window_id);
+
+                if let Some(window_id) = pinned_window {
+                    self.status_message = Some("Snippet unpinned".to_string());
+                    self.update(Message::UnpinSnippet(window_id))
+                } else {
+                    self.update(Message::PinSnippet(snippet_id))
+                }
+            }
+
+            Message::FocusPinnedWindow(id) => window::gain_focus(id),
+
+            Message::DragPinnedWindow(id) => window::drag(id),
+
+            Message::OpenPinnedPanel => {
+                self.pinned_panel_open = true;
+                Task::none()
+            }
+
+            Message::ClosePinnedPanel => {
+                self.pinned_panel_open = false;
+                Task::none()
+            }
+
+            // === Sections ===
+            Message::SectionsLoaded(result) => {
+                match result {
+                    Ok(sections) => self.sections = sections,
+                    Err(e) => self.status_message = Some(format!("Load error: {}", e)),
+                }
+                Task::none()
+            }
+
+            Message::NewSection(above_snippet) => {
+                self.add_menu_open = false;
+                self.card_menu_snippet = None;
+                self.section_draft = Some(SectionDraft {
+                    section_id: None,
+                    above_snippet,
+                    name: String::new(),
+                });
+                widget::operation::focus(SECTION_NAME_ID)
+            }
+
+            Message::EditSection(id) => {
+                let Some(section) = self.sections.iter().find(|s| s.id == id) else {
+                    return Task::none();
+                };
+                self.section_draft = Some(SectionDraft {
+                    section_id: Some(id),
+                    above_snippet: None,
+                    name: section.name.clone(),
+                });
+                widget::operation::focus(SECTION_NAME_ID)
+            }
+
+            Message::SectionNameChanged(name) => {
+                if let Some(draft) = &mut self.section_draft {
+                    draft.name = name;
+                }
+                Task::none()
+            }
+
+            Message::SaveSection => {
+                let Some(draft) = self.section_draft.take() else {
+                    return Task::none();
+                };
+                let name = draft.name.trim().to_string();
+                if name.is_empty() {
+                    self.section_draft = Some(draft);
+                    return Task::none();
+                }
+                match draft.section_id {
+                    Some(id) => {
+                        worker::perform(move || db::rename_section(id, name), Message::SectionSaved)
+                    }
+                    None => worker::perform(
+                        move || db::create_section(name, draft.above_snippet),
+                        Message::SectionSaved,
+                    ),
+                }
+            }
+
+            Message::CloseSectionDialog => {
+                self.section_draft = None;
+                Task::none()
+            }
+
+            Message::SectionSaved(result) => match result {
+                // Inserting a section shifts the positions of the snippets above it
+                Ok(()) => Task::batch([
+                    worker::perform(db::load_snippets, Message::SnippetsLoaded),
+                    worker::perform(db::load_sections, Message::SectionsLoaded),
+                ]),
+                Err(e) => {
+                    self.status_message = Some(format!("Section error: {}", e));
+                    Task::none()
+                }
+            },
+
+            Message::DeleteSection(id) => {
+                worker::perform(move || db::delete_section(id), Message::SectionDeleted)
+            }
+
+            Message::SectionDeleted(result) => {
+                match result {
+                    Ok(id) => self.sections.retain(|s| s.id != id),
+                    Err(e) => self.status_message = Some(format!("Section error: {}", e)),
+                }
+                Task::none()
+            }
+
+            // === Scratchpad ===
+            Message::ScratchpadLoaded(result) => {
+                match result {
+                    Ok(text) => {
+                        self.scratchpad = iced::widget::text_editor::Content::with_text(&text);
+                    }
+                    Err(e) => {
+                        self.status_message = Some(format!("Scratchpad load error: {}", e));
+                    }
+                }
+                Task::none()
+            }
+
+            Message::OpenScratchpad => {
+                self.scratchpad_open = true;
+                Task::none()
+            }
+
+            Message::CloseScratchpad => {
+                self.scratchpad_open = false;
+                let text = self.scratchpad.text();
+                worker::perform(move || db::save_scratchpad(text), Message::ScratchpadSaved)
+            }
+
+            Message::ScratchpadEdited(action) => {
+                self.scratchpad.perform(action);
+                Task::none()
+            }
+
+            Message::ScratchpadSaved(result) => {
+                if let Err(e) = result {
+                    self.status_message = Some(format!("Scratchpad save error: {}", e));
+                }
+                Task::none()
+            }
+
+            Message::ScratchpadSavedOnExit(result) => match result {
+                Ok(()) => iced::exit(),
+                Err(e) => {
+                    // Stay open with the notes rather than lose them
+                    self.status_message = Some(format!("Scratchpad save error: {}", e));
+                    self.scratchpad_open = true;
+                    self.open_main_window()
+                }
+            },
+
+            Message::PromoteScratchpad => {
+                let text = self.scratchpad.text();
+                if text.trim().is_empty() {
+                    return Task::none();
+                }
+                // Use the first line as the label
+                let label: String = text
+                    .lines()
+                    .map(str::trim)
+                    .find(|line| !line.is_empty())
+                    .unwrap_or_default()
+                    .chars()
+                    .take(40)
+                    .collect();
+                self.scratchpad = iced::widget::text_editor::Content::new();
+                self.status_message = Some("Scratchpad promoted to snippet".to_string());
+                Task::batch([
+                    worker::perform(
+                        move || db::add_text_snippet(text, label),
+                        Message::SnippetAdded,
+                    ),
+                    worker::perform(
+                        || db::save_scratchpad(String::new()),
+                        Message::ScratchpadSaved,
+                    ),
+                ])
+            }
+        }
+    }
+
+    /// Open the main window and track it.
+    fn open_main_window(&mut self) -> Task<Message> {
+        let (id, open) = window::open(window::Settings {
+            size: iced::Size::new(900.0, 700.0),
+            position: window::Position::Centered,
+            ..window::Settings::default()
+        });
+        self.windows.insert(id, WindowKind::Main);
+        open.map(Message::WindowOpened)
+    }
+
+    /// Get the ID of the main window, if it is open.
+    fn main_window_id(&self) -> Option<window::Id> {
+        self.windows
+            .iter()
+            .find(|(_, kind)| matches!(kind, WindowKind::Main))
+            .map(|(id, _)| *id)
+    }
+
+    /// Bring the main window back after the eyedropper.
+    fn restore_main_window(&self) -> Task<Message> {
+        match self.main_window_id() {
+            Some(id) => Task::batch([window::minimize(id, false), window::gain_focus(id)]),
+            None => Task::none(),
+        }
+    }
+
+    /// Close the eyedropper overlay and bring the main window back.
+    fn close_eyedropper(&mut self) -> Task<Message> {
+        self.eyedropper = None;
+        let overlays: Vec<window::Id> = self
+            .windows
+            .iter()
+            .filter(|(_, kind)| matches!(kind, WindowKind::Eyedropper))
+            .map(|(id, _)| *id)
+            .collect();
+        let mut tasks: Vec<Task<Message>> = overlays
+            .into_iter()
+            .map(|id| {
+                self.windows.remove(&id);
+                window::close(id)
+            })
+            .collect();
+        tasks.push(self.restore_main_window());
+        Task::batch(tasks)
+    }
+
+    /// List all open pinned windows with the snippet each one displays.
+    pub fn pinned_windows(&self) -> Vec<(window::Id, i64)> {
+        self.windows
+            .iter()
+            .filter_map(|(window_id, kind)| match kind {
+                WindowKind::Pinned(snippet_id) => Some((*window_id, *snippet_id)),
+                WindowKind::Main
+                | WindowKind::Board(_)
+                | WindowKind::Eyedropper
+                | WindowKind::QuickPopup => None,
+            })
+            .collect()
+    }
+
+    /// Get the ID of the quick capture/search popup, if it is open.
+    fn quick_popup_id(&self) -> Option<window::Id> {
+        self.windows
+            .iter()
+            .find(|(_, kind)| matches!(kind, WindowKind::QuickPopup))
+            .map(|(id, _)| *id)
+    }
+
+    /// Get the library snippets matching what is typed in the quick popup.
+    pub fn quick_popup_matches(&self) -> Vec<&Snippet> {
+        self.snippets
+            .iter()
+            .filter(|s| !s.in_inbox && s.matches_filter(self.quick_query.trim()))
+            .take(QUICK_POPUP_RESULTS)
+            .collect()
+    }
+
+    /// Copy the snippet at `index` of a palette board.
+    fn copy_board_snippet(&mut self, palette_id: i64, index: usize) -> Task<Message> {
+        match self.board_snippets(palette_id).get(index) {
+            Some(snippet) => {
+                let id = snippet.id;
+                self.update(Message::CopySnippet(id))
+            }
+            None => Task::none(),
+        }
+    }
+
+    /// Check if an open palette board has a global hotkey, which needs the
+    /// system-wide key listener.
+    pub fn board_hotkeys_active(&self) -> bool {
+        self.windows.values().any(|kind| {
+            matches!(kind, WindowKind::Board(palette_id)
+                if self.config.board_hotkey(*palette_id) != BoardHotkey::Off)
+        })
+    }
+
+    /// Get the library snippets shown on a palette board, in list order.
+    pub fn board_snippets(&self, palette_id: i64) -> Vec<&Snippet> {
+        let rules =
+            crate::view::palette_rules(&self.palettes, Some(palette_id)).unwrap_or_default();
+        self.snippets
+            .iter()
+            .filter(|s| !s.in_inbox)
+            .filter(|s| {
+                rules
+                    .iter()
+                    .any(|rule| rule.matches(s, &self.snippet_palettes))
+            })
+            .collect()
+    }
+
+    /// Get the named colors of a palette for export.
+    fn palette_asset_colors(&self, palette_id: i64) -> Vec<(String, ColorData)> {
+        named_colors(self.board_snippets(palette_id))
+    }
+
+    /// Move the database into `dir` (the default folder when `None`).
+    fn relocate_database(&mut self, dir: Option<std::path::PathBuf>) -> Task<Message> {
+        worker::perform(
+            move || db::relocate_database(dir.clone()).map(|moved| (dir, moved)),
+            Message::DatabaseRelocated,
+        )
+    }
+
+    /// Run a database maintenance task from the settings, reporting its outcome.
+    fn run_maintenance(
+        &mut self,
+        task: impl FnOnce() -> Result<String, String> + Send + 'static,
+    ) -> Task<Message> {
+        if let Some(settings) = &mut self.settings {
+            settings.maintenance_busy = true;
         }
+        worker::perform(task, Message::DatabaseMaintenanceFinished)
     }
 
     /// Load palette assignments for all snippets.
     fn load_all_snippet_palettes(&mut self) -> Task<Message> {
-        for snippet in &self.snippets {
-            if let Ok(palette_ids) = db::get_palettes_for_snippet(snippet.id) {
-                self.snippet_palettes.insert(snippet.id, palette_ids);
-            }
-        }
-        Task::none()
+        worker::perform(db::load_snippet_palettes, Message::SnippetPalettesLoaded)
+    }
+
+    /// Add a confirmed picker color to the recent colors and save them.
+    fn remember_picker_color(&mut self, color: ColorData) -> Task<Message> {
+        self.config.remember_color(color);
+        let config = self.config.clone();
+        Task::perform(async move { config.save() }, Message::ConfigSaved)
     }
 
     /// Helper to copy a color format to clipboard.
-    fn copy_color_format<F>(&self, id: i64, format_fn: F) -> Task<Message>
+    fn copy_color_format<F>(&mut self, id: i64, format_fn: F) -> Task<Message>
     where
         F: FnOnce(&ColorData) -> String + Send + 'static,
     {
         if let Some(snippet) = self.snippets.iter().find(|s| s.id == id) {
             if let SnippetContent::Color(color) = &snippet.content {
                 let text = format_fn(color);
-                return Task::perform(
-                    async move { copy_to_clipboard(&text).await },
-                    Message::CopyFinished,
-                );
+                return self.copy_snippet_text(id, text);
             }
         }
         Task::none()
     }
+
+    /// Save content returned from the external editor.
+    fn save_external_edit(&mut self, id: i64, content: String, is_code: bool) -> Task<Message> {
+        // Save against the revision the file was written from, so
+        // changes made in the app meanwhile are not overwritten
+        let revision = self.external_edits.remove(&id).unwrap_or_default();
+        let is_json = self
+            .snippets
+            .iter()
+            .any(|s| s.id == id && s.kind() == SnippetKind::Json);
+        if is_json {
+            // Rejected if the edited document no longer parses
+            worker::perform(
+                move || db::update_json_content(id, content, revision),
+                Message::SnippetUpdated,
+            )
+        } else if is_code {
+            // Keeps the language and label
+            worker::perform(
+                move || db::update_code_content(id, content, revision),
+                Message::SnippetUpdated,
+            )
+        } else {
+            worker::perform(
+                move || db::update_text_content(id, content, revision),
+                Message::SnippetUpdated,
+            )
+        }
+    }
+
+    /// Copy a snippet, as Markdown if `markdown` is set. Code and text with
+    /// placeholders open the fill dialog first.
+    fn copy_snippet(&mut self, id: i64, markdown: bool) -> Task<Message> {
+        let Some(snippet) = self.snippets.iter().find(|s| s.id == id) else {
+            return Task::none();
+        };
+        let text = if markdown {
+            self.markdown_text(&snippet.content)
+        } else {
+            snippet.content.to_copyable_string()
+        };
+        let placeholders = match snippet.content {
+            SnippetContent::Code(_) | SnippetContent::Text(_) => find_placeholders(&text),
+            _ => Vec::new(),
+        };
+        if placeholders.is_empty() {
+            self.copy_snippet_text(id, text)
+        } else {
+            // Ask for the values first
+            self.template_fill = Some(TemplateFillState::new(
+                id,
+                snippet.label.clone(),
+                text,
+                placeholders,
+            ));
+            Task::none()
+        }
+    }
+
+    /// Get a snippet's content as Markdown, text styled per the settings.
+    fn markdown_text(&self, content: &SnippetContent) -> String {
+        content.to_markdown(self.config.markdown_text == MarkdownText::Blockquote)
+    }
+
+    /// Copy a snippet's text to the clipboard and count the copy.
+    fn copy_snippet_text(&mut self, id: i64, text: String) -> Task<Message> {
+        self.copy_snippet_content(id, text, None)
+    }
+
+    /// Copy a snippet's text, with its rich text if given, and count the copy.
+    fn copy_snippet_content(
+        &mut self,
+        id: i64,
+        text: String,
+        html: Option<String>,
+    ) -> Task<Message> {
+        if let Some(snippet) = self.snippets.iter_mut().find(|s| s.id == id) {
+            snippet.copy_count += 1;
+            snippet.last_copied_at = Some(unix_now());
+        }
+        Task::batch([
+            Task::perform(
+                async move {
+                    match html {
+                        Some(html) => copy_html_to_clipboard(&html, &text).await,
+                        None => copy_to_clipboard(&text).await,
+                    }
+                },
+                Message::CopyFinished,
+            ),
+            worker::perform(move || db::record_copy(id), Message::CopyRecorded),
+        ])
+    }
 }
 
 /// Copy text to clipboard.
@@ -1089,6 +5255,76 @@ async fn copy_to_clipboard(text: &str) -> Result<String, String> {
     }
 }
 
+/// Copy rich text (HTML) to the clipboard, with the plain text for apps
+/// that only paste text.
+async fn copy_html_to_clipboard(html: &str, text: &str) -> Result<String, String> {
+    let mut clipboard = arboard::Clipboard::new().map_err(|e| e.to_string())?;
+    clipboard
+        .set_html(html, Some(text))
+        .map_err(|e| e.to_string())?;
+    Ok(format!(
+        "Copied as rich text: {}",
+        truncate_for_status(text, 40)
+    ))
+}
+
+/// Snippet content replaced by a one-click reformat.
+#[derive(Debug, Clone)]
+pub struct ReformatUndo {
+    pub snippet_id: i64,
+    pub previous: String,
+    pub reformatted: String,
+}
+
+/// Text read from the clipboard, with the rich text (HTML) copied alongside it.
+#[derive(Debug, Clone)]
+pub struct ClipboardContent {
+    pub text: String,
+    pub html: Option<String>,
+    /// Application in front when the text was read, for auto-captures.
+    pub source_app: Option<String>,
+}
+
+/// Read the clipboard's text. Its rich text is only read when the text differs
+/// from `known`, which was read before.
+fn read_clipboard(known: Option<&str>) -> Option<ClipboardContent> {
+    let mut clipboard = arboard::Clipboard::new().ok()?;
+    let text = clipboard.get_text().ok()?;
+    let html = if known == Some(text.as_str()) {
+        None
+    } else {
+        clipboard
+            .get()
+            .html()
+            .ok()
+            .filter(|html| !html.trim().is_empty())
+    };
+    Some(ClipboardContent {
+        text,
+        html,
+        source_app: None,
+    })
+}
+
+/// Get the named colors of snippets for export. The colors of a color set
+/// are numbered after its label.
+fn named_colors<'a>(snippets: impl IntoIterator<Item = &'a Snippet>) -> Vec<(String, ColorData)> {
+    let mut colors = Vec::new();
+    for snippet in snippets {
+        match &snippet.content {
+            SnippetContent::Color(color) => colors.push((snippet.label.clone(), color.clone())),
+            SnippetContent::ColorSet(set) => colors.extend(
+                set.colors
+                    .iter()
+                    .enumerate()
+                    .map(|(i, color)| (format!("{} {}", snippet.label, i + 1), color.clone())),
+            ),
+            _ => {}
+        }
+    }
+    colors
+}
+
 /// Truncate text for status bar display.
 fn truncate_for_status(text: &str, max_len: usize) -> String {
     // Take first line only
@@ -1115,13 +5351,7 @@ async fn open_in_external_editor(
     use std::fs;
     use std::process::Command;
 
-    // Create temp file
-    let temp_dir = std::env::temp_dir();
-    let file_name = format!("shard_snippet_{}.{}", id, extension);
-    let temp_path = temp_dir.join(&file_name);
-
-    // Write content to temp file
-    fs::write(&temp_path, content).map_err(|e| format!("Failed to create temp file: {}", e))?;
+    let temp_path = write_temp_file(&format!("shard_snippet_{}.{}", id, extension), content)?;
 
     // Get command from config
     let temp_path_str = temp_path.to_string_lossy().to_string();
@@ -1156,6 +5386,159 @@ async fn open_in_external_editor(
     Ok((id, new_content, is_code))
 }
 
+/// Write snippet content to a file in the temp folder.
+fn write_temp_file(file_name: &str, content: &str) -> Result<std::path::PathBuf, String> {
+    let temp_path = std::env::temp_dir().join(file_name);
+    std::fs::write(&temp_path, content)
+        .map_err(|e| format!("Failed to create temp file: {}", e))?;
+    Ok(temp_path)
+}
+
+/// Longest a code snippet may run before it is stopped.
+pub const RUN_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Output kept of each stream of a run, in bytes.
+pub const MAX_RUN_OUTPUT: usize = 64 * 1024;
+
+/// What a code snippet printed when it was run.
+#[derive(Debug, Clone)]
+pub struct RunOutput {
+    /// The command line that ran.
+    pub command: String,
+    pub stdout: String,
+    pub stderr: String,
+    /// None when the process was stopped by a signal, the timeout or the
+    /// output limit.
+    pub exit_code: Option<i32>,
+    pub timed_out: bool,
+    /// The process was stopped for printing more than `MAX_RUN_OUTPUT`.
+    pub output_limited: bool,
+}
+
+/// Keep the start of a run's output, cut at a character boundary.
+fn truncate_run_output(bytes: &[u8]) -> String {
+    let text = String::from_utf8_lossy(bytes);
+    if text.len() <= MAX_RUN_OUTPUT {
+        return text.into_owned();
+    }
+    let mut end = MAX_RUN_OUTPUT;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}\n… (output cut off)", &text[..end])
+}
+
+/// Read one output stream of a run into `bytes` in chunks, stopping once it
+/// holds more than `MAX_RUN_OUTPUT` bytes. Returns whether it stopped there.
+async fn read_run_output(
+    stream: Option<impl tokio::io::AsyncRead + Unpin>,
+    bytes: &mut Vec<u8>,
+) -> bool {
+    use tokio::io::AsyncReadExt;
+
+    let Some(mut stream) = stream else {
+        return false;
+    };
+    let mut chunk = [0; 8192];
+    while bytes.len() <= MAX_RUN_OUTPUT {
+        match stream.read(&mut chunk).await {
+            Ok(0) | Err(_) => return false,
+            Ok(read) => bytes.extend_from_slice(&chunk[..read]),
+        }
+    }
+    true
+}
+
+/// Wait for a run to finish while reading its output. A process printing
+/// more than `MAX_RUN_OUTPUT` is killed rather than buffered; the flag says
+/// if that happened.
+async fn capture_run(
+    child: &mut tokio::process::Child,
+    stdout: &mut Vec<u8>,
+    stderr: &mut Vec<u8>,
+) -> std::io::Result<(std::process::ExitStatus, bool)> {
+    let read_stdout = read_run_output(child.stdout.take(), stdout);
+    let read_stderr = read_run_output(child.stderr.take(), stderr);
+    tokio::pin!(read_stdout, read_stderr);
+    let (mut stdout_done, mut stderr_done) = (false, false);
+    let mut limited = false;
+    while !(limited || stdout_done && stderr_done) {
+        limited = tokio::select! {
+            limited = &mut read_stdout, if !stdout_done => {
+                stdout_done = true;
+                limited
+            }
+            limited = &mut read_stderr, if !stderr_done => {
+                stderr_done = true;
+                limited
+            }
+        };
+    }
+    if limited {
+        child.start_kill()?;
+    }
+    Ok((child.wait().await?, limited))
+}
+
+/// Run a code snippet from a temp file with its language's run command.
+async fn run_code_snippet(
+    id: i64,
+    code: String,
+    extension: &'static str,
+    command: RunCommand,
+) -> Result<RunOutput, String> {
+    use std::process::Stdio;
+
+    let temp_path = write_temp_file(&format!("shard_run_{}.{}", id, extension), &code)?;
+    let Some((program, args)) = command.build_command(&temp_path.to_string_lossy()) else {
+        let _ = std::fs::remove_file(&temp_path);
+        return Err(format!(
+            "No run command configured for {}",
+            command.language
+        ));
+    };
+    let command_line = std::iter::once(program.as_str())
+        .chain(args.iter().map(String::as_str))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let spawned = tokio::process::Command::new(&program)
+        .args(&args)
+        .current_dir(std::env::temp_dir())
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        // Dropping the child on timeout stops it
+        .kill_on_drop(true)
+        .spawn();
+    let (mut stdout, mut stderr) = (Vec::new(), Vec::new());
+    let result = match spawned {
+        Ok(mut child) => tokio::time::timeout(
+            RUN_TIMEOUT,
+            capture_run(&mut child, &mut stdout, &mut stderr),
+        )
+        .await
+        .ok(),
+        Err(e) => Some(Err(e)),
+    };
+    let _ = std::fs::remove_file(&temp_path);
+
+    // What was printed before a timeout is kept too
+    let (exit_code, timed_out, output_limited) = match result {
+        Some(Ok((status, limited))) => (status.code(), false, limited),
+        Some(Err(e)) => return Err(format!("Failed to run '{}': {}", program, e)),
+        None => (None, true, false),
+    };
+    Ok(RunOutput {
+        command: command_line,
+        stdout: truncate_run_output(&stdout),
+        stderr: truncate_run_output(&stderr),
+        exit_code,
+        timed_out,
+        output_limited,
+    })
+}
+
 /// Export all snippets to a JSON file.
 async fn export_snippets_json(snippets: Vec<crate::snippet::Snippet>) -> Result<String, String> {
     use std::fs;
@@ -1179,8 +5562,238 @@ async fn export_snippets_json(snippets: Vec<crate::snippet::Snippet>) -> Result<
     ))
 }
 
+/// Ask where to sync code snippets to, defaulting to a `.vscode` snippet file,
+/// and write it.
+async fn sync_to_project(ids: Vec<i64>) -> Result<String, String> {
+    let file = rfd::AsyncFileDialog::new()
+        .set_title("Sync to Project")
+        .set_file_name(format!("shard.{}", CODE_SNIPPETS_EXTENSION))
+        .add_filter("Editor snippets", &[CODE_SNIPPETS_EXTENSION])
+        .save_file()
+        .await
+        .ok_or_else(|| "Sync cancelled".to_string())?;
+    let target = file.path().display().to_string();
+
+    let count = worker::run({
+        let target = target.clone();
+        move || db::sync_to_project(target, ids)
+    })
+    .await??;
+    Ok(format!(
+        "Synced {} code snippets to {} (kept up to date)",
+        count, target
+    ))
+}
+
+/// Render a code snippet to a PNG and save it where the user chooses.
+async fn save_share_image(
+    code: String,
+    language: String,
+    file_name: String,
+    options: ShareImageOptions,
+) -> Result<String, String> {
+    let file = rfd::AsyncFileDialog::new()
+        .add_filter("PNG", &["png"])
+        .set_title("Save Share Image")
+        .set_file_name(&file_name)
+        .save_file()
+        .await
+        .ok_or_else(|| "Export cancelled".to_string())?;
+
+    let png = render_code_image(&code, &language, &options)?;
+    std::fs::write(file.path(), png).map_err(|e| format!("Write failed: {}", e))?;
+
+    Ok(format!("Saved image to {}", file.path().display()))
+}
+
+/// Write code snippets into a user-chosen directory and link each snippet to its file.
+/// Each entry is `(snippet ID, palette folder, file name, code)`; name collisions
+/// get a numeric suffix.
+async fn export_code_files(
+    files: Vec<(i64, Option<String>, String, String)>,
+) -> Result<String, String> {
+    use std::collections::HashSet;
+    use std::fs;
+
+    let dir = rfd::AsyncFileDialog::new()
+        .set_title("Export Code Snippets")
+        .pick_folder()
+        .await
+        .ok_or_else(|| "Export cancelled".to_string())?;
+    let root = dir.path().to_path_buf();
+
+    let mut used: HashSet<std::path::PathBuf> = HashSet::new();
+    let mut links = Vec::new();
+    for (id, folder, file_name, code) in &files {
+        let target_dir = match folder {
+            Some(name) => root.join(sanitize_file_stem(name)),
+            None => root.clone(),
+        };
+        fs::create_dir_all(&target_dir)
+            .map_err(|e| format!("Failed to create {}: {}", target_dir.display(), e))?;
+
+        let (stem, ext) = file_name.rsplit_once('.').unwrap_or((file_name, "txt"));
+        let mut path = target_dir.join(file_name);
+        let mut n = 2;
+        while used.contains(&path) || path.exists() {
+            path = target_dir.join(format!("{}-{}.{}", stem, n, ext));
+            n += 1;
+        }
+        fs::write(&path, code).map_err(|e| format!("Write failed: {}", e))?;
+        links.push((*id, path.display().to_string(), short_hash(code)));
+        used.insert(path);
+    }
+
+    worker::run(move || db::link_files(links)).await??;
+
+    Ok(format!(
+        "Exported {} code snippets to {}",
+        files.len(),
+        root.display()
+    ))
+}
+
+/// Read a file dropped on the code editor, with the language its extension
+/// names, if any.
+fn read_dropped_code(path: &std::path::Path) -> Result<(String, Option<String>), String> {
+    let code = std::fs::read_to_string(path)
+        .map_err(|e| format!("Can't paste {}: {}", path.display(), e))?;
+    let language = path
+        .extension()
+        .and_then(|ext| extension_to_language(&ext.to_string_lossy()))
+        .map(str::to_string);
+    Ok((code, language))
+}
+
+/// Import files chosen in a file picker as code snippets, each linked to its file.
+/// The language comes from the file extension, or is detected from the code.
+///
+/// On failure, returns the error and the backup taken before inserting, if any.
+async fn import_code_files() -> Result<String, (String, Option<std::path::PathBuf>)> {
+    let files = rfd::AsyncFileDialog::new()
+        .set_title("Import Code Files")
+        .pick_files()
+        .await
+        .ok_or_else(|| ("Import cancelled".to_string(), None))?;
+
+    let mut imports = Vec::new();
+    for file in &files {
+        let path = file.path();
+        let code = std::fs::read_to_string(path)
+            .map_err(|e| (format!("Failed to read {}: {}", path.display(), e), None))?;
+        let language = path
+            .extension()
+            .and_then(|ext| extension_to_language(&ext.to_string_lossy()))
+            .map(str::to_string)
+            .unwrap_or_else(|| detect_language(&code));
+        let label = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        imports.push((path.display().to_string(), code, language, label));
+    }
+
+    worker::run(move || {
+        let backup = db::backup_database("import").map_err(|e| (e, None))?;
+
+        let mut links = Vec::new();
+        for (path, code, language, label) in imports {
+            let hash = short_hash(&code);
+            match db::add_code_snippet(code, language, label) {
+                Ok(snippet) => links.push((snippet.id, path, hash)),
+                Err(e) => {
+                    return Err((
+                        format!("stopped after {} files: {}", links.len(), e),
+                        Some(backup),
+                    ))
+                }
+            }
+        }
+        let count = links.len();
+        db::link_files(links).map_err(|e| (e, Some(backup)))?;
+
+        Ok(format!("Imported {} code files", count))
+    })
+    .await
+    .map_err(|e| (e, None))?
+}
+
+/// Get the platform command that opens a file or folder in its default app.
+fn platform_opener() -> &'static str {
+    if cfg!(target_os = "macos") {
+        "open"
+    } else if cfg!(target_os = "windows") {
+        "explorer"
+    } else {
+        "xdg-open"
+    }
+}
+
+/// Open a file in its default app.
+fn open_in_default_app(path: &std::path::Path) -> Result<(), String> {
+    std::process::Command::new(platform_opener())
+        .arg(path)
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))
+}
+
+/// Open a web page in the default browser.
+fn open_url(url: &str) -> Result<(), String> {
+    std::process::Command::new(platform_opener())
+        .arg(url)
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| format!("Failed to open {}: {}", url, e))
+}
+
+/// Check for a newer release off the UI thread.
+fn check_for_update() -> Task<Message> {
+    Task::perform(
+        async {
+            tokio::task::spawn_blocking(crate::releases::check_for_update)
+                .await
+                .map_err(|e| e.to_string())?
+        },
+        Message::UpdateChecked,
+    )
+}
+
+/// Show a file in the platform file manager, selecting it where supported.
+/// A removed file still has its folder opened.
+fn reveal_in_file_manager(path: &std::path::Path) -> Result<(), String> {
+    use std::process::Command;
+
+    let mut command = if cfg!(target_os = "macos") && path.exists() {
+        let mut command = Command::new("open");
+        command.arg("-R").arg(path);
+        command
+    } else if cfg!(target_os = "windows") && path.exists() {
+        let mut command = Command::new("explorer");
+        command.arg(format!("/select,{}", path.display()));
+        command
+    } else {
+        let folder = path
+            .parent()
+            .filter(|folder| folder.exists())
+            .ok_or_else(|| format!("{} no longer exists", path.display()))?;
+        let mut command = Command::new(platform_opener());
+        command.arg(folder);
+        command
+    };
+
+    command
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| format!("Failed to open the file manager: {}", e))
+}
+
 /// Import snippets from a JSON file using a file picker dialog.
-async fn import_snippets_json() -> Result<String, String> {
+///
+/// On failure, returns the error and the backup taken before inserting, if any.
+async fn import_snippets_json(
+    dedupe: ColorDedupe,
+) -> Result<String, (String, Option<std::path::PathBuf>)> {
     use std::fs;
 
     // Open file picker dialog
@@ -1192,53 +5805,515 @@ async fn import_snippets_json() -> Result<String, String> {
 
     let file = match file {
         Some(f) => f,
-        None => return Err("Import cancelled".to_string()),
+        None => return Err(("Import cancelled".to_string(), None)),
     };
 
     // Read file contents
-    let contents =
-        fs::read_to_string(file.path()).map_err(|e| format!("Failed to read file: {}", e))?;
+    let contents = fs::read_to_string(file.path())
+        .map_err(|e| (format!("Failed to read file: {}", e), None))?;
 
     // Parse JSON
-    let snippets: Vec<Snippet> =
-        serde_json::from_str(&contents).map_err(|e| format!("Invalid JSON format: {}", e))?;
+    let snippets: Vec<Snippet> = serde_json::from_str(&contents)
+        .map_err(|e| (format!("Invalid JSON format: {}", e), None))?;
 
     if snippets.is_empty() {
         return Ok("No snippets to import".to_string());
     }
 
-    // Insert each snippet into the database
-    let mut imported_count = 0;
-    let mut skipped_count = 0;
+    // Insert on the database worker, so a large import doesn't stall the UI
+    worker::run(move || {
+        // Snapshot the database so a failed import can be rolled back
+        let backup = db::backup_database("import").map_err(|e| (e, None))?;
+
+        // Insert each snippet into the database, bottom first so every new one
+        // lands above the previous and the exported order is kept
+        let mut imported_count = 0;
+        let mut open_section: Option<String> = None;
+
+        for snippet in snippets.iter().rev() {
+            // Files are exported by path, so only those on this machine come back
+            if let SnippetContent::File(file) = &snippet.content {
+                if !std::path::Path::new(&file.path).exists() {
+                    continue;
+                }
+            }
+            // A section header goes above the last snippet of its group
+            if open_section.is_some() && snippet.section != open_section {
+                if let Some(name) = open_section.take() {
+                    db::create_section(name, None).map_err(|e| (e, Some(backup.clone())))?;
+                }
+            }
+            if let Err(e) = insert_imported_snippet(snippet, dedupe) {
+                return Err((
+                    format!("stopped after {} snippets: {}", imported_count, e),
+                    Some(backup),
+                ));
+            }
+            open_section = snippet.section.clone();
+            imported_count += 1;
+        }
+        if let Some(name) = open_section {
+            db::create_section(name, None).map_err(|e| (e, Some(backup)))?;
+        }
+
+        Ok(format!("Imported {} snippets", imported_count))
+    })
+    .await
+    .map_err(|e| (e, None))?
+}
 
-    for snippet in snippets {
-        // Create new snippet based on content type (ignoring original ID)
-        let result = match &snippet.content {
-            SnippetContent::Color(color) => {
-                db::add_or_move_color(color.r, color.g, color.b, color.a, snippet.label.clone())
-            }
-            SnippetContent::Code(code) => db::add_code_snippet(
-                code.code.clone(),
-                code.language.clone(),
-                snippet.label.clone(),
-            ),
-            SnippetContent::Text(text) => {
-                db::add_text_snippet(text.text.clone(), snippet.label.clone())
+/// Insert a copy of an imported snippet (ignoring its original ID), with
+/// its description.
+fn insert_imported_snippet(snippet: &Snippet, dedupe: ColorDedupe) -> Result<Snippet, String> {
+    let description = snippet.description.clone();
+    let copy = match &snippet.content {
+        SnippetContent::Color(color) => {
+            Snippet::color(color.r, color.g, color.b, color.a, snippet.label.clone())
+        }
+        SnippetContent::Code(code) => Snippet::code(
+            code.code.clone(),
+            code.language.clone(),
+            snippet.label.clone(),
+        ),
+        SnippetContent::Json(json) => Snippet::json(json.json.clone(), snippet.label.clone()),
+        SnippetContent::Text(text) => {
+            Snippet::rich_text(text.text.clone(), text.html.clone(), snippet.label.clone())
+        }
+        SnippetContent::ColorSet(_) | SnippetContent::Gradient(_) => snippet.clone(),
+        SnippetContent::File(file) => {
+            return db::attach_file(std::path::PathBuf::from(&file.path), description);
+        }
+    };
+    db::add_snippet(copy.with_description(description), dedupe)
+}
+
+/// Save a palette as a `.shardpalette` file where the user chooses.
+async fn export_palette_file(palette: PaletteFile) -> Result<String, String> {
+    let json = palette.to_json()?;
+
+    let file = rfd::AsyncFileDialog::new()
+        .add_filter("Shard Palette", &[PALETTE_FILE_EXTENSION])
+        .set_title("Export Palette")
+        .set_file_name(format!(
+            "{}.{}",
+            sanitize_file_stem(&palette.name),
+            PALETTE_FILE_EXTENSION
+        ))
+        .save_file()
+        .await
+        .ok_or_else(|| "Export cancelled".to_string())?;
+
+    std::fs::write(file.path(), json).map_err(|e| format!("Write failed: {}", e))?;
+
+    Ok(format!(
+        "Exported palette \"{}\" ({} snippets) to {}",
+        palette.name,
+        palette.snippets.len(),
+        file.path().display()
+    ))
+}
+
+/// Write a palette's colors into a user-chosen directory as an Xcode asset
+/// catalog (`<name>.xcassets`) and Android resources (`<name>-android/values`,
+/// plus `values-night` when some colors have a dark variant).
+async fn export_palette_assets(name: String, colors: Vec<AssetColor>) -> Result<String, String> {
+    use std::fs;
+
+    let dir = rfd::AsyncFileDialog::new()
+        .set_title("Export for Mobile")
+        .pick_folder()
+        .await
+        .ok_or_else(|| "Export cancelled".to_string())?;
+    let stem = sanitize_file_stem(&name);
+
+    let catalog = dir.path().join(format!("{}.xcassets", stem));
+    for (path, contents) in xcassets_files(&colors) {
+        let path = catalog.join(path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        fs::write(&path, contents).map_err(|e| format!("Write failed: {}", e))?;
+    }
+
+    let android = dir.path().join(format!("{}-android", stem));
+    let mut folders = vec![("values", false)];
+    if colors.iter().any(|color| color.dark.is_some()) {
+        folders.push(("values-night", true));
+    }
+    for (folder, night) in folders {
+        let folder = android.join(folder);
+        fs::create_dir_all(&folder)
+            .map_err(|e| format!("Failed to create {}: {}", folder.display(), e))?;
+        fs::write(
+            folder.join("colors.xml"),
+            android_colors_xml(&colors, night),
+        )
+        .map_err(|e| format!("Write failed: {}", e))?;
+    }
+
+    Ok(format!(
+        "Exported {} colors from \"{}\" to {}",
+        colors.len(),
+        name,
+        dir.path().display()
+    ))
+}
+
+/// Save a `.code-snippets` file where the user chooses.
+async fn export_editor_snippets(
+    name: String,
+    count: usize,
+    contents: String,
+) -> Result<String, String> {
+    let file = rfd::AsyncFileDialog::new()
+        .add_filter("Editor snippets", &[CODE_SNIPPETS_EXTENSION])
+        .set_title("Export Editor Snippets")
+        .set_file_name(format!(
+            "{}.{}",
+            sanitize_file_stem(&name),
+            CODE_SNIPPETS_EXTENSION
+        ))
+        .save_file()
+        .await
+        .ok_or_else(|| "Export cancelled".to_string())?;
+
+    std::fs::write(file.path(), contents).map_err(|e| format!("Write failed: {}", e))?;
+
+    Ok(format!(
+        "Exported {} code snippets to {}",
+        count,
+        file.path().display()
+    ))
+}
+
+/// Save an export for another tool where the user chooses.
+async fn save_export_file(
+    file_type: &'static str,
+    extension: &'static str,
+    name: String,
+    count: usize,
+    bytes: Vec<u8>,
+) -> Result<String, String> {
+    let file = rfd::AsyncFileDialog::new()
+        .add_filter(file_type, &[extension])
+        .set_title("Export Colors")
+        .set_file_name(format!("{}.{}", sanitize_file_stem(&name), extension))
+        .save_file()
+        .await
+        .ok_or_else(|| "Export cancelled".to_string())?;
+
+    std::fs::write(file.path(), bytes).map_err(|e| format!("Write failed: {}", e))?;
+
+    Ok(format!(
+        "Exported {} colors from \"{}\" to {}",
+        count,
+        name,
+        file.path().display()
+    ))
+}
+
+/// Pick a PNG or JPEG image and find its dominant colors, with the file name to
+/// name their palette after.
+async fn extract_image_palette() -> Result<(String, Vec<(ColorData, usize)>), String> {
+    let file = rfd::AsyncFileDialog::new()
+        .add_filter("Image", &["png", "jpg", "jpeg"])
+        .set_title("Extract Colors from Image")
+        .pick_file()
+        .await
+        .ok_or_else(|| "Extraction cancelled".to_string())?;
+    let path = file.path().to_path_buf();
+
+    // Decoding and quantizing a large image takes a while
+    tokio::task::spawn_blocking(move || {
+        let bytes = std::fs::read(&path).map_err(|e| format!("Failed to read file: {}", e))?;
+        let (_, _, rgba) = decode_image(&bytes)?;
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "Image".to_string());
+        Ok((name, median_cut(&rgba, IMAGE_PALETTE_SIZE)))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Add colors extracted from an image as color snippets in a new palette,
+/// the first color on top. Runs on the database worker.
+///
+/// On failure, returns the error and the backup taken before inserting, if any.
+fn add_image_palette(
+    name: String,
+    colors: Vec<ColorData>,
+    dedupe: ColorDedupe,
+) -> Result<String, (String, Option<std::path::PathBuf>)> {
+    // Snapshot the database so a failed import can be rolled back
+    let backup = db::backup_database("import").map_err(|e| (e, None))?;
+    let fail = |count: usize, e: String| {
+        (
+            format!("stopped after {} colors: {}", count, e),
+            Some(backup.clone()),
+        )
+    };
+
+    let palette = db::create_palette(name.trim().to_string()).map_err(|e| fail(0, e))?;
+    let mut ids = Vec::new();
+    for color in colors.into_iter().rev() {
+        let label = color.to_hex();
+        let snippet = db::add_or_move_color(color.r, color.g, color.b, color.a, label, dedupe)
+            .map_err(|e| fail(ids.len(), e))?;
+        ids.push(snippet.id);
+    }
+    let count = ids.len();
+    let (_, ids) = db::add_snippets_to_palette(palette.id, ids).map_err(|e| fail(count, e))?;
+    Ok(format!(
+        "Added {} colors to palette \"{}\"",
+        ids.len(),
+        palette.name
+    ))
+}
+
+/// Import a `.shardpalette` file into a palette of the same name, creating it
+/// if needed. Without a path, the user picks the file.
+///
+/// On failure, returns the error and the backup taken before inserting, if any.
+async fn import_palette_file(
+    path: Option<std::path::PathBuf>,
+    dedupe: ColorDedupe,
+) -> Result<String, (String, Option<std::path::PathBuf>)> {
+    let path = match path {
+        Some(path) => path,
+        None => rfd::AsyncFileDialog::new()
+            .add_filter("Shard Palette", &[PALETTE_FILE_EXTENSION])
+            .set_title("Import Palette")
+            .pick_file()
+            .await
+            .map(|file| file.path().to_path_buf())
+            .ok_or_else(|| ("Import cancelled".to_string(), None))?,
+    };
+
+    let contents = std::fs::read_to_string(&path)
+        .map_err(|e| (format!("Failed to read file: {}", e), None))?;
+    let palette = PaletteFile::parse(&contents).map_err(|e| (e, None))?;
+
+    // Insert on the database worker, so a large import doesn't stall the UI
+    worker::run(move || {
+        // Snapshot the database so a failed import can be rolled back
+        let backup = db::backup_database("import").map_err(|e| (e, None))?;
+        let fail = |count: usize, e: String| {
+            (
+                format!("stopped after {} snippets: {}", count, e),
+                Some(backup.clone()),
+            )
+        };
+
+        let existing = db::load_palettes()
+            .map_err(|e| fail(0, e))?
+            .into_iter()
+            .find(|p| p.name == palette.name);
+        let palette_id = match existing {
+            Some(existing) => existing.id,
+            None => {
+                db::create_palette(palette.name.clone())
+                    .map_err(|e| fail(0, e))?
+                    .id
             }
         };
 
-        match result {
-            Ok(_) => imported_count += 1,
-            Err(_) => skipped_count += 1,
+        let mut imported_count = 0;
+        for snippet in &palette.snippets {
+            let inserted =
+                insert_imported_snippet(snippet, dedupe).map_err(|e| fail(imported_count, e))?;
+            if let Some(icon) = snippet.icon.as_deref().and_then(normalize_icon) {
+                db::set_icon(inserted.id, Some(icon)).map_err(|e| fail(imported_count, e))?;
+            }
+            db::add_snippet_to_palette(palette_id, inserted.id)
+                .map_err(|e| fail(imported_count, e))?;
+            imported_count += 1;
         }
+
+        Ok(format!(
+            "Imported {} snippets into palette \"{}\"",
+            imported_count, palette.name
+        ))
+    })
+    .await
+    .map_err(|e| (e, None))?
+}
+
+/// Import a GIMP palette as color snippets in a palette of the same name,
+/// creating it if needed.
+///
+/// On failure, returns the error and the backup taken before inserting, if any.
+async fn import_gpl_palette(
+    dedupe: ColorDedupe,
+) -> Result<String, (String, Option<std::path::PathBuf>)> {
+    let file = rfd::AsyncFileDialog::new()
+        .add_filter("GIMP Palette", &["gpl"])
+        .set_title("Import GIMP Palette")
+        .pick_file()
+        .await
+        .ok_or_else(|| ("Import cancelled".to_string(), None))?;
+
+    let contents = std::fs::read_to_string(file.path())
+        .map_err(|e| (format!("Failed to read file: {}", e), None))?;
+    let GplPalette { name, colors } = parse_gpl(&contents).map_err(|e| (e, None))?;
+    // Palettes without a name take the file's
+    let name = name.unwrap_or_else(|| file_stem_or(file.path(), "GIMP Palette"));
+    import_named_palette(name, colors, dedupe).await
+}
+
+/// Import the colors of a design tokens JSON file into a palette named
+/// after the file, creating it if needed.
+///
+/// On failure, returns the error and the backup taken before inserting, if any.
+async fn import_tokens_palette(
+    dedupe: ColorDedupe,
+) -> Result<String, (String, Option<std::path::PathBuf>)> {
+    let file = rfd::AsyncFileDialog::new()
+        .add_filter("Design Tokens", &["json"])
+        .set_title("Import Design Tokens")
+        .pick_file()
+        .await
+        .ok_or_else(|| ("Import cancelled".to_string(), None))?;
+
+    let contents = std::fs::read_to_string(file.path())
+        .map_err(|e| (format!("Failed to read file: {}", e), None))?;
+    let colors = parse_tokens(&contents).map_err(|e| (e, None))?;
+    if colors.is_empty() {
+        return Ok("No color tokens to import".to_string());
     }
+    let name = file_stem_or(file.path(), "Design Tokens");
+    import_named_palette(name, colors, dedupe).await
+}
+
+/// Name of a file without its extension, or `fallback`.
+fn file_stem_or(path: &std::path::Path, fallback: &str) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| fallback.to_string())
+}
+
+/// Add colors to the palette called `name`, creating it if needed, keeping
+/// their order.
+///
+/// On failure, returns the error and the backup taken before inserting, if any.
+async fn import_named_palette(
+    name: String,
+    colors: Vec<(String, ColorData)>,
+    dedupe: ColorDedupe,
+) -> Result<String, (String, Option<std::path::PathBuf>)> {
+    // Insert on the database worker, so a large import doesn't stall the UI
+    worker::run(move || {
+        // Snapshot the database so a failed import can be rolled back
+        let backup = db::backup_database("import").map_err(|e| (e, None))?;
+        let fail = |count: usize, e: String| {
+            (
+                format!("stopped after {} colors: {}", count, e),
+                Some(backup.clone()),
+            )
+        };
+
+        let existing = db::load_palettes()
+            .map_err(|e| fail(0, e))?
+            .into_iter()
+            .find(|p| p.name == name);
+        let palette_id = match existing {
+            Some(existing) => existing.id,
+            None => db::create_palette(name.clone()).map_err(|e| fail(0, e))?.id,
+        };
+
+        // Last color first, so the palette's first color ends up on top
+        let mut imported_count = 0;
+        for (label, color) in colors.into_iter().rev() {
+            let inserted = db::add_or_move_color(color.r, color.g, color.b, color.a, label, dedupe)
+                .map_err(|e| fail(imported_count, e))?;
+            db::add_snippet_to_palette(palette_id, inserted.id)
+                .map_err(|e| fail(imported_count, e))?;
+            imported_count += 1;
+        }
 
-    if skipped_count > 0 {
         Ok(format!(
-            "Imported {} snippets ({} skipped)",
-            imported_count, skipped_count
+            "Imported {} colors into palette \"{}\"",
+            imported_count, name
         ))
-    } else {
-        Ok(format!("Imported {} snippets", imported_count))
+    })
+    .await
+    .map_err(|e| (e, None))?
+}
+
+/// Import links from an exported bookmarks HTML file as text snippets,
+/// optionally assigning them to a palette per bookmark folder.
+async fn import_bookmarks_html(
+    group_by_folder: bool,
+) -> Result<String, (String, Option<std::path::PathBuf>)> {
+    use std::collections::HashMap;
+    use std::fs;
+
+    let file = rfd::AsyncFileDialog::new()
+        .add_filter("Bookmarks HTML", &["html", "htm"])
+        .set_title("Import Bookmarks")
+        .pick_file()
+        .await;
+
+    let file = match file {
+        Some(f) => f,
+        None => return Err(("Import cancelled".to_string(), None)),
+    };
+
+    let contents = fs::read_to_string(file.path())
+        .map_err(|e| (format!("Failed to read file: {}", e), None))?;
+
+    let bookmarks = parse_bookmarks_html(&contents);
+    if bookmarks.is_empty() {
+        return Ok("No bookmarks to import".to_string());
     }
+
+    // Insert on the database worker, so a large import doesn't stall the UI
+    worker::run(move || {
+        // Snapshot the database so a failed import can be rolled back
+        let backup = db::backup_database("import").map_err(|e| (e, None))?;
+        let fail = |count: usize, e: String| {
+            (
+                format!("stopped after {} bookmarks: {}", count, e),
+                Some(backup.clone()),
+            )
+        };
+
+        // Reuse existing palettes whose name matches a folder
+        let mut palette_ids: HashMap<String, i64> = if group_by_folder {
+            db::load_palettes()
+                .map_err(|e| fail(0, e))?
+                .into_iter()
+                .map(|p| (p.name, p.id))
+                .collect()
+        } else {
+            HashMap::new()
+        };
+
+        let mut imported_count = 0;
+        for bookmark in bookmarks {
+            let snippet = db::add_text_snippet(bookmark.url, bookmark.title)
+                .map_err(|e| fail(imported_count, e))?;
+
+            if let Some(folder) = bookmark.folder.filter(|_| group_by_folder) {
+                let palette_id = match palette_ids.get(&folder) {
+                    Some(id) => *id,
+                    None => {
+                        let palette = db::create_palette(folder.clone())
+                            .map_err(|e| fail(imported_count, e))?;
+                        palette_ids.insert(folder, palette.id);
+                        palette.id
+                    }
+                };
+                db::add_snippet_to_palette(palette_id, snippet.id)
+                    .map_err(|e| fail(imported_count, e))?;
+            }
+            imported_count += 1;
+        }
+
+        Ok(format!("Imported {} bookmarks", imported_count))
+    })
+    .await
+    .map_err(|e| (e, None))?
 }
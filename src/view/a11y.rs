@@ -0,0 +1,377 @@
+//! Names for icon-only controls, and the Tab order behind dialogs.
+//!
+//! iced has no accessibility tree yet, so the name of a control that only
+//! shows an icon is given as a tooltip; the same names are the ones to hand
+//! to screen readers once the toolkit can expose them.
+
+use std::any::Any;
+use std::time::Duration;
+
+use iced::advanced::layout::{self, Layout};
+use iced::advanced::widget::operation::{Focusable, Operation, Scrollable, TextInput};
+use iced::advanced::widget::{tree, Id, Tree, Widget};
+use iced::advanced::{mouse, overlay, renderer, Clipboard, Shell};
+use iced::widget::text::IntoFragment;
+use iced::widget::{text, tooltip};
+use iced::{Element, Event, Length, Rectangle, Renderer, Size, Theme, Vector};
+
+use crate::message::Message;
+use crate::theme::{dropdown_menu_style, SPACE_XS, TEXT_PRIMARY};
+
+/// Hover time before a control's name is shown.
+const LABEL_DELAY: Duration = Duration::from_millis(500);
+
+/// Name an icon-only control, shown below it on hover.
+pub fn labeled<'a>(
+    control: impl Into<Element<'a, Message>>,
+    name: impl IntoFragment<'a>,
+) -> Element<'a, Message> {
+    tooltip(
+        control,
+        text(name).size(11).color(TEXT_PRIMARY),
+        tooltip::Position::Bottom,
+    )
+    .delay(LABEL_DELAY)
+    .gap(SPACE_XS)
+    .style(dropdown_menu_style)
+    .padding(SPACE_XS)
+    .into()
+}
+
+/// Keep the fields of content covered by a dialog or menu out of the Tab
+/// order. They also lose focus, so typing only reaches the dialog.
+pub fn inert<'a>(content: impl Into<Element<'a, Message>>) -> Element<'a, Message> {
+    Element::new(Inert {
+        content: content.into(),
+    })
+}
+
+/// Content passed through unchanged, except to focus operations.
+struct Inert<'a> {
+    content: Element<'a, Message>,
+}
+
+impl Widget<Message, Theme, Renderer> for Inert<'_> {
+    fn tag(&self) -> tree::Tag {
+        self.content.as_widget().tag()
+    }
+
+    fn state(&self) -> tree::State {
+        self.content.as_widget().state()
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        self.content.as_widget().children()
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        self.content.as_widget().diff(tree);
+    }
+
+    fn size(&self) -> Size<Length> {
+        self.content.as_widget().size()
+    }
+
+    fn size_hint(&self) -> Size<Length> {
+        self.content.as_widget().size_hint()
+    }
+
+    fn layout(
+        &mut self,
+        tree: &mut Tree,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        self.content.as_widget_mut().layout(tree, renderer, limits)
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        self.content
+            .as_widget()
+            .draw(tree, renderer, theme, style, layout, cursor, viewport);
+    }
+
+    fn operate(
+        &mut self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn Operation,
+    ) {
+        self.content.as_widget_mut().operate(
+            tree,
+            layout,
+            renderer,
+            &mut Unfocusable { operation },
+        );
+    }
+
+    fn update(
+        &mut self,
+        tree: &mut Tree,
+        event: &Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) {
+        self.content.as_widget_mut().update(
+            tree, event, layout, cursor, renderer, clipboard, shell, viewport,
+        );
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.content
+            .as_widget()
+            .mouse_interaction(tree, layout, cursor, viewport, renderer)
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'b>,
+        renderer: &Renderer,
+        viewport: &Rectangle,
+        translation: Vector,
+    ) -> Option<overlay::Element<'b, Message, Theme, Renderer>> {
+        self.content
+            .as_widget_mut()
+            .overlay(tree, layout, renderer, viewport, translation)
+    }
+}
+
+/// An operation that skips focusable widgets, unfocusing them instead.
+struct Unfocusable<'a> {
+    operation: &'a mut dyn Operation,
+}
+
+impl Operation for Unfocusable<'_> {
+    fn traverse(&mut self, operate: &mut dyn FnMut(&mut dyn Operation)) {
+        self.operation
+            .traverse(&mut |operation| operate(&mut Unfocusable { operation }));
+    }
+
+    fn container(&mut self, id: Option<&Id>, bounds: Rectangle) {
+        self.operation.container(id, bounds);
+    }
+
+    fn scrollable(
+        &mut self,
+        id: Option<&Id>,
+        bounds: Rectangle,
+        content_bounds: Rectangle,
+        translation: Vector,
+        state: &mut dyn Scrollable,
+    ) {
+        self.operation
+            .scrollable(id, bounds, content_bounds, translation, state);
+    }
+
+    fn focusable(&mut self, _id: Option<&Id>, _bounds: Rectangle, state: &mut dyn Focusable) {
+        state.unfocus();
+    }
+
+    fn text_input(&mut self, id: Option<&Id>, bounds: Rectangle, state: &mut dyn TextInput) {
+        self.operation.text_input(id, bounds, state);
+    }
+
+    fn text(&mut self, id: Option<&Id>, bounds: Rectangle, text: &str) {
+        self.operation.text(id, bounds, text);
+    }
+
+    fn custom(&mut self, id: Option<&Id>, bounds: Rectangle, state: &mut dyn Any) {
+        self.operation.custom(id, bounds, state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iced::advanced::layout::{Layout, Limits, Node};
+    use iced::advanced::widget::operation::{focusable, Focusable, Operation, Outcome};
+    use iced::advanced::widget::{Id, Tree};
+    use iced::{window, Element, Font, Pixels, Rectangle, Size};
+
+    use crate::message::Message;
+    use crate::update::{Shard, WindowKind};
+    use crate::view::{CodeEditorState, SettingsState, TextEditorState};
+
+    /// A laid out view, driven with the operations behind Tab / Shift+Tab.
+    struct Harness<'a> {
+        element: Element<'a, Message>,
+        tree: Tree,
+        node: Node,
+        renderer: iced::Renderer,
+    }
+
+    /// Records the focusable widgets in the order Tab visits them.
+    #[derive(Default)]
+    struct Focusables {
+        bounds: Vec<Rectangle>,
+        focused: Option<usize>,
+    }
+
+    impl Operation for Focusables {
+        fn traverse(&mut self, operate: &mut dyn FnMut(&mut dyn Operation)) {
+            operate(self);
+        }
+
+        fn focusable(&mut self, _id: Option<&Id>, bounds: Rectangle, state: &mut dyn Focusable) {
+            if state.is_focused() {
+                self.focused = Some(self.bounds.len());
+            }
+            self.bounds.push(bounds);
+        }
+    }
+
+    impl<'a> Harness<'a> {
+        fn new(mut element: Element<'a, Message>) -> Self {
+            // The software renderer lays text out without a window
+            let renderer = iced::Renderer::Secondary(iced_tiny_skia::Renderer::new(
+                Font::DEFAULT,
+                Pixels(14.0),
+            ));
+            let mut tree = Tree::new(&element);
+            // The main window's default size
+            let node = element.as_widget_mut().layout(
+                &mut tree,
+                &renderer,
+                &Limits::new(Size::ZERO, Size::new(900.0, 700.0)),
+            );
+            Self {
+                element,
+                tree,
+                node,
+                renderer,
+            }
+        }
+
+        /// Apply an operation and the ones it chains to.
+        fn run(&mut self, operation: Box<dyn Operation>) {
+            let mut next = Some(operation);
+            while let Some(mut operation) = next.take() {
+                self.element.as_widget_mut().operate(
+                    &mut self.tree,
+                    Layout::new(&self.node),
+                    &self.renderer,
+                    operation.as_mut(),
+                );
+                if let Outcome::Chain(chained) = operation.finish() {
+                    next = Some(chained);
+                }
+            }
+        }
+
+        fn focusables(&mut self) -> Focusables {
+            let mut focusables = Focusables::default();
+            self.element.as_widget_mut().operate(
+                &mut self.tree,
+                Layout::new(&self.node),
+                &self.renderer,
+                &mut focusables,
+            );
+            focusables
+        }
+
+        fn tab(&mut self) -> Option<usize> {
+            self.run(Box::new(focusable::focus_next()));
+            self.focusables().focused
+        }
+
+        fn shift_tab(&mut self) -> Option<usize> {
+            self.run(Box::new(focusable::focus_previous()));
+            self.focusables().focused
+        }
+    }
+
+    /// Whether `next` comes after `previous` when reading left to right, top
+    /// to bottom: below it, or beside it to the right.
+    fn reads_after(previous: &Rectangle, next: &Rectangle) -> bool {
+        let below = next.y >= previous.y + previous.height;
+        let beside = next.y < previous.y + previous.height
+            && previous.y < next.y + next.height
+            && next.x >= previous.x + previous.width;
+        below || beside
+    }
+
+    /// Check Tab visits every field of a view in reading order, and
+    /// Shift+Tab walks back, returning how many fields there are.
+    fn assert_tab_order(element: Element<'_, Message>) -> usize {
+        let mut harness = Harness::new(element);
+        let fields = harness.focusables().bounds;
+        for pair in fields.windows(2) {
+            assert!(
+                reads_after(&pair[0], &pair[1]),
+                "{:?} is visited before {:?}",
+                pair[0],
+                pair[1]
+            );
+        }
+
+        for index in 0..fields.len() {
+            assert_eq!(harness.tab(), Some(index));
+        }
+        for index in (0..fields.len().saturating_sub(1)).rev() {
+            assert_eq!(harness.shift_tab(), Some(index));
+        }
+        fields.len()
+    }
+
+    fn main_view(shard: &Shard) -> Element<'_, Message> {
+        shard.view(window::Id::unique())
+    }
+
+    #[test]
+    fn test_main_window_tab_order() {
+        let shard = Shard::default();
+        assert!(assert_tab_order(main_view(&shard)) > 0);
+    }
+
+    #[test]
+    fn test_editor_tab_order() {
+        let shard = Shard {
+            text_editor: Some(TextEditorState::new_text()),
+            ..Shard::default()
+        };
+        // Text, label and description; not the search field behind the dialog
+        assert_eq!(assert_tab_order(main_view(&shard)), 3);
+
+        let shard = Shard {
+            code_editor: Some(CodeEditorState::new_code()),
+            ..Shard::default()
+        };
+        assert_eq!(assert_tab_order(main_view(&shard)), 3);
+    }
+
+    #[test]
+    fn test_quick_popup_tab_order() {
+        let mut shard = Shard::default();
+        let id = window::Id::unique();
+        shard.windows.insert(id, WindowKind::QuickPopup);
+        assert_eq!(assert_tab_order(shard.view(id)), 1);
+    }
+
+    #[test]
+    fn test_settings_tab_order() {
+        let mut shard = Shard::default();
+        shard.settings = Some(SettingsState::from_config(&shard.config));
+        assert!(assert_tab_order(main_view(&shard)) > 0);
+    }
+}
@@ -0,0 +1,124 @@
+//! Dialog for setting a snippet's text-expander abbreviation.
+
+use iced::widget::{button, column, container, mouse_area, opaque, row, text, text_input};
+use iced::{Element, Length};
+
+use crate::icons;
+use crate::message::Message;
+use crate::snippet::Snippet;
+use crate::theme::{
+    input_style, modal_dialog_style, modal_overlay_style, primary_button_style,
+    secondary_button_style, subtle_button_style, DANGER, SPACE_MD, SPACE_SM, SPACE_XS, TEXT_MUTED,
+    TEXT_PRIMARY,
+};
+use crate::view::labeled;
+
+/// State for the abbreviation dialog.
+#[derive(Debug, Clone)]
+pub struct AbbreviationState {
+    pub snippet_id: i64,
+    pub label: String,
+    /// Abbreviation typed into the input.
+    pub abbreviation: String,
+    /// Why the typed abbreviation can't be used.
+    pub error: Option<String>,
+}
+
+impl AbbreviationState {
+    /// Open the dialog for a snippet, prefilled with its abbreviation.
+    pub fn new(snippet: &Snippet) -> Self {
+        Self {
+            snippet_id: snippet.id,
+            label: snippet.label.clone(),
+            abbreviation: snippet.abbreviation.clone().unwrap_or_default(),
+            error: None,
+        }
+    }
+}
+
+/// Render the abbreviation dialog.
+pub fn view_abbreviation_modal<'a>(
+    state: &'a AbbreviationState,
+    current: Option<&'a str>,
+    expansion_enabled: bool,
+) -> Element<'a, Message> {
+    let header_row = row![
+        text(format!("Abbreviation for \"{}\"", state.label))
+            .size(20)
+            .color(TEXT_PRIMARY),
+        iced::widget::Space::new().width(Length::Fill),
+        labeled(
+            button(icons::x().size(16))
+                .on_press(Message::CloseAbbreviationEditor)
+                .padding([SPACE_XS, SPACE_SM])
+                .style(subtle_button_style),
+            "Close"
+        ),
+    ]
+    .spacing(SPACE_XS)
+    .align_y(iced::Alignment::Center);
+
+    let has_error = state.error.is_some();
+    let input = text_input("e.g. ;sig", &state.abbreviation)
+        .on_input(Message::AbbreviationChanged)
+        .on_submit(Message::SubmitAbbreviation)
+        .padding(SPACE_SM)
+        .size(14)
+        .width(Length::Fill)
+        .style(move |theme, status| input_style(theme, status, has_error));
+
+    let hint = text(if expansion_enabled {
+        "Typed in any app, it is replaced with this snippet's content"
+    } else {
+        "Typed in any app, it is replaced with this snippet's content \
+         once text expansion is turned on in Settings"
+    })
+    .size(11)
+    .color(TEXT_MUTED);
+
+    let mut modal_content = column![header_row, input].spacing(SPACE_MD);
+    if let Some(error) = &state.error {
+        modal_content = modal_content.push(text(error).size(11).color(DANGER));
+    }
+
+    let action_buttons = row![
+        button(text("Remove").size(14))
+            .on_press_maybe(
+                current
+                    .is_some()
+                    .then_some(Message::SetSnippetAbbreviation(state.snippet_id, None))
+            )
+            .padding(SPACE_SM)
+            .style(secondary_button_style),
+        iced::widget::Space::new().width(Length::Fill),
+        button(text("Cancel").size(14))
+            .on_press(Message::CloseAbbreviationEditor)
+            .padding(SPACE_SM)
+            .style(secondary_button_style),
+        button(text("Save").size(14))
+            .on_press(Message::SubmitAbbreviation)
+            .padding(SPACE_SM)
+            .style(primary_button_style),
+    ]
+    .spacing(SPACE_SM);
+
+    let modal_content = modal_content
+        .push(hint)
+        .push(action_buttons)
+        .padding(SPACE_MD)
+        .width(Length::Fixed(400.0));
+
+    let modal_dialog = container(modal_content).style(modal_dialog_style);
+
+    // Semi-transparent overlay
+    mouse_area(
+        container(opaque(modal_dialog))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x(Length::Fill)
+            .center_y(Length::Fill)
+            .style(modal_overlay_style),
+    )
+    .on_press(Message::CloseAbbreviationEditor)
+    .into()
+}
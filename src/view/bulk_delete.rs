@@ -0,0 +1,69 @@
+//! Confirmation dialog for moving every snippet matching the filter to the trash.
+
+use iced::widget::{button, column, container, mouse_area, opaque, row, text};
+use iced::{Element, Length};
+
+use crate::message::Message;
+use crate::theme::{
+    danger_button_style, modal_dialog_style, modal_overlay_style, secondary_button_style, SPACE_MD,
+    SPACE_SM, SPACE_XS, TEXT_MUTED, TEXT_PRIMARY, TEXT_SECONDARY,
+};
+
+/// Snippets picked for a bulk delete, captured when the dialog opens.
+#[derive(Debug, Clone)]
+pub struct BulkDeleteState {
+    pub snippet_ids: Vec<i64>,
+    /// The filter that matched them, e.g. `"todo" · Text · in Work`.
+    pub description: String,
+}
+
+/// Render the bulk delete confirmation, stating how many snippets it affects.
+pub fn view_bulk_delete_modal(state: &BulkDeleteState) -> Element<'_, Message> {
+    let count = state.snippet_ids.len();
+    let noun = if count == 1 { "snippet" } else { "snippets" };
+
+    let header = column![
+        text(format!("Move {} {} to the trash?", count, noun))
+            .size(20)
+            .color(TEXT_PRIMARY),
+        text(format!("Every snippet matching {}", state.description))
+            .size(12)
+            .color(TEXT_SECONDARY),
+        text("They can be restored from the trash until it is emptied.")
+            .size(11)
+            .color(TEXT_MUTED),
+    ]
+    .spacing(SPACE_XS);
+
+    let action_buttons = row![
+        iced::widget::Space::new().width(Length::Fill),
+        button(text("Cancel").size(14))
+            .on_press(Message::CancelBulkDelete)
+            .padding(SPACE_SM)
+            .style(secondary_button_style),
+        button(text(format!("Delete {} {}", count, noun)).size(14))
+            .on_press(Message::ConfirmBulkDelete)
+            .padding(SPACE_SM)
+            .style(danger_button_style),
+    ]
+    .spacing(SPACE_SM);
+
+    let modal_content = column![header, action_buttons]
+        .spacing(SPACE_MD)
+        .padding(SPACE_MD)
+        .width(Length::Fixed(420.0));
+
+    let modal_dialog = container(modal_content).style(modal_dialog_style);
+
+    // Semi-transparent overlay
+    mouse_area(
+        container(opaque(modal_dialog))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x(Length::Fill)
+            .center_y(Length::Fill)
+            .style(modal_overlay_style),
+    )
+    .on_press(Message::CancelBulkDelete)
+    .into()
+}
@@ -0,0 +1,174 @@
+//! Capture sessions: auto-capture switched on for a fixed time, with every
+//! capture gathered in a palette named after the session.
+
+use std::time::{Duration, Instant};
+
+use iced::widget::{
+    button, column, container, mouse_area, opaque, pick_list, row, text, text_input,
+};
+use iced::{Element, Length};
+
+use crate::icons;
+use crate::message::Message;
+use crate::theme::{
+    input_style, modal_dialog_style, modal_overlay_style, pick_list_style, primary_button_style,
+    secondary_button_style, SPACE_MD, SPACE_SM, SPACE_XS, TEXT_MUTED, TEXT_PRIMARY, TEXT_SECONDARY,
+};
+
+/// Widget ID of the session name input.
+pub const CAPTURE_SESSION_NAME_ID: &str = "capture-session-name";
+
+/// Session lengths offered in the dialog, in minutes.
+pub const SESSION_LENGTHS: [u64; 5] = [15, 30, 60, 120, 240];
+
+/// A capture session being set up.
+#[derive(Debug, Clone)]
+pub struct CaptureSessionDraft {
+    pub name: String,
+    pub minutes: u64,
+}
+
+impl Default for CaptureSessionDraft {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            minutes: 30,
+        }
+    }
+}
+
+/// A running capture session.
+#[derive(Debug, Clone)]
+pub struct CaptureSession {
+    pub name: String,
+    /// Palette the session's captures are added to.
+    pub palette_id: i64,
+    pub ends_at: Instant,
+    /// Snippets captured so far.
+    pub captured: usize,
+    /// Whether auto-capture was on before the session, restored when it ends.
+    pub was_listening: bool,
+}
+
+impl CaptureSession {
+    /// Time left until the session ends.
+    pub fn remaining(&self) -> Duration {
+        self.ends_at.saturating_duration_since(Instant::now())
+    }
+
+    /// Check if the session has run its course.
+    pub fn is_over(&self) -> bool {
+        self.remaining().is_zero()
+    }
+}
+
+/// A session length in the dialog's dropdown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct SessionLength(u64);
+
+impl std::fmt::Display for SessionLength {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            minutes if minutes % 60 == 0 && minutes >= 120 => write!(f, "{} hours", minutes / 60),
+            60 => f.write_str("1 hour"),
+            minutes => write!(f, "{} minutes", minutes),
+        }
+    }
+}
+
+/// Describe the time left in a session, e.g. "12 min left".
+fn remaining_label(remaining: Duration) -> String {
+    let seconds = remaining.as_secs();
+    if seconds < 60 {
+        format!("{} s left", seconds)
+    } else {
+        format!("{} min left", seconds.div_ceil(60))
+    }
+}
+
+/// Render the header button of a running session; pressing it ends the session.
+pub fn view_session_button(session: &CaptureSession) -> Element<'static, Message> {
+    crate::view::labeled(
+        button(
+            row![
+                icons::timer().size(14),
+                text(session.name.clone()).size(12),
+                text(remaining_label(session.remaining()))
+                    .size(11)
+                    .color(TEXT_MUTED),
+            ]
+            .spacing(SPACE_XS)
+            .align_y(iced::Alignment::Center),
+        )
+        .on_press(Message::StopCaptureSession)
+        .padding([SPACE_SM, SPACE_MD])
+        .style(secondary_button_style),
+        "End capture session",
+    )
+}
+
+/// Render the dialog starting a capture session.
+pub fn view_capture_session_dialog(draft: &CaptureSessionDraft) -> Element<'_, Message> {
+    let can_start = !draft.name.trim().is_empty();
+
+    let header = column![
+        text("Start Capture Session").size(20).color(TEXT_PRIMARY),
+        text("Auto-capture stays on for the chosen time. Everything captured is added to a palette named after the session.")
+            .size(12)
+            .color(TEXT_SECONDARY),
+    ]
+    .spacing(SPACE_XS);
+
+    let name_input = text_input("Session name, e.g. Onboarding research", &draft.name)
+        .id(CAPTURE_SESSION_NAME_ID)
+        .on_input(Message::CaptureSessionNameChanged)
+        .on_submit_maybe(can_start.then_some(Message::StartCaptureSession))
+        .padding(SPACE_SM)
+        .style(|theme, status| input_style(theme, status, false));
+
+    let length_picker = row![
+        text("Duration").size(13).color(TEXT_SECONDARY),
+        pick_list(
+            SESSION_LENGTHS.map(SessionLength),
+            Some(SessionLength(draft.minutes)),
+            |length: SessionLength| Message::CaptureSessionLengthChanged(length.0),
+        )
+        .text_size(13)
+        .padding([SPACE_XS, SPACE_SM])
+        .style(pick_list_style),
+    ]
+    .spacing(SPACE_SM)
+    .align_y(iced::Alignment::Center);
+
+    let action_buttons = row![
+        iced::widget::Space::new().width(Length::Fill),
+        button(text("Cancel").size(14))
+            .on_press(Message::CloseCaptureSessionDialog)
+            .padding(SPACE_SM)
+            .style(secondary_button_style),
+        button(text("Start").size(14))
+            .on_press_maybe(can_start.then_some(Message::StartCaptureSession))
+            .padding(SPACE_SM)
+            .style(primary_button_style),
+    ]
+    .spacing(SPACE_SM);
+
+    let modal_content = column![header, name_input, length_picker, action_buttons]
+        .spacing(SPACE_MD)
+        .padding(SPACE_MD)
+        .width(Length::Fixed(400.0));
+
+    let modal_dialog = container(modal_content).style(modal_dialog_style);
+
+    // Semi-transparent overlay
+    mouse_area(
+        container(opaque(modal_dialog))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x(Length::Fill)
+            .center_y(Length::Fill)
+            .style(modal_overlay_style),
+    )
+    .on_press(Message::CloseCaptureSessionDialog)
+    .into()
+}
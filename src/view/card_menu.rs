@@ -0,0 +1,174 @@
+//! Context menu for a snippet card, also opened from table rows and
+//! favorite chips.
+
+use iced::widget::{button, column, container, mouse_area, row, scrollable, text};
+use iced::{Element, Length};
+
+use crate::icons;
+use crate::message::Message;
+use crate::snippet::{ColorAdjustment, Snippet, SnippetContent, WhitespaceTransform};
+use crate::theme::{
+    dropdown_item_style, dropdown_menu_style, scrollbar_style, SPACE_MD, SPACE_SM, SPACE_XS,
+    TEXT_MUTED,
+};
+
+/// Width of the menu.
+const MENU_WIDTH: f32 = 200.0;
+
+/// Render the context menu for a snippet card.
+pub fn view_card_menu(snippet: &Snippet) -> Element<'_, Message> {
+    let item = |icon: iced::widget::Text<'static>, label: &'static str, action: Message| {
+        button(
+            row![icon.size(14), text(label).size(13)]
+                .spacing(SPACE_SM)
+                .align_y(iced::Alignment::Center),
+        )
+        .on_press(Message::CardMenuSelected(Box::new(action)))
+        .padding([SPACE_SM, SPACE_MD])
+        .width(Length::Fill)
+        .style(dropdown_item_style)
+    };
+
+    let is_favorite = snippet.favorite_at.is_some();
+    let favorite_item = item(
+        icons::star(),
+        if is_favorite {
+            "Remove from Favorites"
+        } else {
+            "Add to Favorites"
+        },
+        Message::SetFavorite(snippet.id, !is_favorite),
+    );
+
+    let mut items = column![
+        container(text(&snippet.label).size(11).color(TEXT_MUTED)).padding([SPACE_XS, SPACE_MD]),
+        item(icons::copy(), "Copy", Message::CopySnippet(snippet.id)),
+        favorite_item,
+        item(
+            icons::tag(),
+            "Assign to Palette",
+            Message::TogglePaletteDropdown(Some(snippet.id))
+        ),
+    ]
+    .spacing(2);
+
+    // Colors copy as code for UI toolkits
+    if matches!(snippet.content, SnippetContent::Color(_)) {
+        items = items
+            .push(item(
+                icons::code(),
+                "Copy for SwiftUI",
+                Message::CopySwiftUi(snippet.id),
+            ))
+            .push(item(
+                icons::code(),
+                "Copy for Flutter",
+                Message::CopyFlutter(snippet.id),
+            ))
+            .push(item(
+                icons::code(),
+                "Copy for Jetpack Compose",
+                Message::CopyCompose(snippet.id),
+            ))
+            .push(item(
+                icons::code(),
+                "Copy for iced",
+                Message::CopyIced(snippet.id),
+            ))
+            .push(item(
+                icons::magnifying_glass(),
+                "Find Similar Colors",
+                Message::OpenSimilarColors(snippet.id),
+            ));
+        // Variants for hover and active states, saved next to the color
+        for adjustment in ColorAdjustment::ALL {
+            let (icon, label) = match adjustment {
+                ColorAdjustment::Lighter => (icons::sun(), "Save Lighter Variant"),
+                ColorAdjustment::Darker => (icons::moon(), "Save Darker Variant"),
+                ColorAdjustment::Warmer => (icons::fire(), "Save Warmer Variant"),
+                ColorAdjustment::Cooler => (icons::snowflake(), "Save Cooler Variant"),
+            };
+            items = items.push(item(
+                icon,
+                label,
+                Message::DeriveColor(snippet.id, adjustment),
+            ));
+        }
+    }
+
+    // Color cards show their swatch, so only code and text snippets get icons
+    if !matches!(snippet.content, SnippetContent::Color(_)) {
+        items = items.push(item(
+            icons::smiley(),
+            "Set Icon…",
+            Message::OpenIconPicker(snippet.id),
+        ));
+    }
+
+    if matches!(snippet.content, SnippetContent::Code(_)) {
+        items = items.push(item(icons::play(), "Run", Message::RunSnippet(snippet.id)));
+        for transform in WhitespaceTransform::ALL {
+            items = items.push(item(
+                icons::text_indent(),
+                transform.display_name(),
+                Message::TransformSnippet(snippet.id, transform),
+            ));
+        }
+    }
+
+    if matches!(
+        snippet.content,
+        SnippetContent::Code(_) | SnippetContent::Text(_) | SnippetContent::Json(_)
+    ) {
+        items = items.push(item(
+            icons::keyboard(),
+            "Set Abbreviation…",
+            Message::OpenAbbreviationEditor(snippet.id),
+        ));
+    }
+
+    items = items.push(item(
+        icons::push_pin(),
+        "Pin",
+        Message::PinSnippet(snippet.id),
+    ));
+
+    // File snippets and snippets imported from or exported to a file can show it
+    if snippet.file_on_disk().is_some() {
+        items = items.push(item(
+            icons::folder_open(),
+            "Reveal in File Manager",
+            Message::RevealSnippetFile(snippet.id),
+        ));
+    }
+
+    let items = items
+        .push(item(
+            icons::rows(),
+            "Insert Section Above",
+            Message::NewSection(Some(snippet.id)),
+        ))
+        .push(item(
+            icons::trash(),
+            "Delete",
+            Message::DeleteSnippet(snippet.id),
+        ));
+
+    // Color snippets have more items than a short window fits, so they scroll
+    let menu = container(scrollable(items).style(scrollbar_style))
+        .padding(SPACE_XS)
+        .width(Length::Fixed(MENU_WIDTH))
+        .style(dropdown_menu_style);
+
+    // Position menu (center of screen, like the palette dropdown)
+    let positioned = container(menu)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .padding(SPACE_MD)
+        .center_x(Length::Fill)
+        .center_y(Length::Fill);
+
+    mouse_area(positioned)
+        .on_press(Message::ToggleCardMenu(None))
+        .into()
+}
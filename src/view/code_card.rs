@@ -1,35 +1,62 @@
 //! Code snippet card view component.
 
-use iced::widget::{button, column, container, row, text};
+use iced::widget::{button, column, container, rich_text, row, text};
 use iced::{Element, Length};
 
+use crate::config::{CardActions, CodeTheme};
 use crate::icons;
 use crate::message::Message;
-use crate::snippet::CodeData;
+use crate::snippet::{
+    can_reformat, find_color_literals, language_to_extension, CodeData, JsonFormat, Snippet,
+};
 use crate::theme::{
     card_style, danger_button_style, subtle_button_style, BG_SURFACE, SPACE_MD, SPACE_SM, SPACE_XS,
     TEXT_MUTED, TEXT_SECONDARY,
 };
+use crate::view::highlight::highlighted_spans;
+use crate::view::{
+    card_with_actions, labeled, view_age, view_card_header, view_preview_lines, ActionSet,
+    CardStats,
+};
 
-/// Render a code snippet card.
+/// Render a code snippet card, showing actions per the visibility setting.
 pub fn view_code_card<'a>(
-    id: i64,
-    label: &'a str,
+    snippet: &'a Snippet,
+    code: &'a CodeData,
+    stats: CardStats<'a>,
+    is_selected: bool,
+    theme: CodeTheme,
+    visibility: CardActions,
+) -> Element<'a, Message> {
+    card_with_actions(visibility, |actions| {
+        build_code_card(snippet, code, stats, is_selected, theme, actions)
+    })
+}
+
+/// Build a code snippet card with the given set of action buttons.
+fn build_code_card<'a>(
+    snippet: &'a Snippet,
     code: &'a CodeData,
+    stats: CardStats<'a>,
     is_selected: bool,
+    theme: CodeTheme,
+    actions: ActionSet,
 ) -> Element<'a, Message> {
-    // Code icon (64x64 container)
-    let code_icon = container(
-        text(icons::CODE_ICON)
-            .size(28)
-            .font(icons::ICON_FONT)
-            .color(TEXT_SECONDARY),
-    )
-    .width(64)
-    .height(64)
-    .center_x(64)
-    .center_y(64)
-    .style(|_theme| iced::widget::container::Style::default().background(BG_SURFACE));
+    let id = snippet.id;
+    let label = snippet.label.as_str();
+    let icon = snippet.icon.as_deref();
+
+    // Code icon, or the snippet's custom icon (64x64 container)
+    let kind_icon = match icon {
+        Some(icon) => icons::snippet_icon(icon),
+        None => text(icons::CODE_ICON).font(icons::ICON_FONT),
+    };
+    let code_icon = container(kind_icon.size(28).color(TEXT_SECONDARY))
+        .width(64)
+        .height(64)
+        .center_x(64)
+        .center_y(64)
+        .style(|_theme| iced::widget::container::Style::default().background(BG_SURFACE));
 
     // Language badge
     let language_badge = container(text(&code.language).size(10).color(TEXT_MUTED))
@@ -37,13 +64,31 @@ pub fn view_code_card<'a>(
         .style(|_theme| iced::widget::container::Style::default().background(BG_SURFACE));
 
     // Header row: label + language badge
-    let header_row = row![text(label).size(14).color(TEXT_SECONDARY), language_badge]
-        .spacing(SPACE_SM)
-        .align_y(iced::Alignment::Center);
+    let header_row = row![
+        text(label).size(14).color(TEXT_SECONDARY),
+        language_badge,
+        view_age(stats)
+    ]
+    .spacing(SPACE_SM)
+    .align_y(iced::Alignment::Center);
 
-    // Code preview (first 2 lines)
+    // Code preview (first 2 lines), highlighted unless it has colors to mark
     let preview = code.preview(2);
-    let preview_text = text(preview).size(11).color(TEXT_MUTED);
+    let preview_text: Element<'_, Message> = if preview
+        .lines()
+        .any(|line| !find_color_literals(line).is_empty())
+    {
+        view_preview_lines(preview, 11.0)
+    } else {
+        rich_text(highlighted_spans(
+            &code.code,
+            language_to_extension(&code.language),
+            theme.highlighter(),
+            2,
+        ))
+        .size(11)
+        .into()
+    };
 
     // Line count
     let line_count = text(format!("{} lines", code.line_count()))
@@ -51,35 +96,95 @@ pub fn view_code_card<'a>(
         .color(TEXT_MUTED);
 
     // Info column with header, preview, and stats
-    let info_column = column![header_row, preview_text, line_count]
-        .spacing(SPACE_XS)
-        .width(Length::Fill);
-
-    // Action buttons
-    let action_row = row![
-        button(icons::copy().size(14))
-            .on_press(Message::CopySnippet(id))
-            .padding([SPACE_XS, SPACE_SM])
-            .style(subtle_button_style),
-        button(icons::push_pin().size(14))
-            .on_press(Message::PinSnippet(id))
-            .padding([SPACE_XS, SPACE_SM])
-            .style(subtle_button_style),
-        button(icons::arrow_square_out().size(14))
-            .on_press(Message::OpenInExternalEditor(id, true))
-            .padding([SPACE_XS, SPACE_SM])
-            .style(subtle_button_style),
-        button(icons::pencil().size(14))
-            .on_press(Message::OpenCodeEditor(Some(id)))
-            .padding([SPACE_XS, SPACE_SM])
-            .style(subtle_button_style),
-        button(icons::trash().size(14))
-            .on_press(Message::DeleteSnippet(id))
-            .padding([SPACE_XS, SPACE_SM])
-            .style(danger_button_style),
+    let info_column = column![
+        view_card_header(header_row, stats),
+        preview_text,
+        line_count
     ]
     .spacing(SPACE_XS)
-    .align_y(iced::Alignment::Center);
+    .width(Length::Fill);
+
+    // Action buttons
+    let mut action_row = row![].spacing(SPACE_XS).align_y(iced::Alignment::Center);
+    if actions.shows_copy() {
+        action_row = action_row.push(labeled(
+            button(icons::copy().size(14))
+                .on_press(Message::CopySnippet(id))
+                .padding([SPACE_XS, SPACE_SM])
+                .style(subtle_button_style),
+            "Copy",
+        ));
+        action_row = action_row.push(labeled(
+            button(icons::markdown_logo().size(14))
+                .on_press(Message::CopyMarkdown(id))
+                .padding([SPACE_XS, SPACE_SM])
+                .style(subtle_button_style),
+            "Copy as Markdown",
+        ));
+    }
+    if actions == ActionSet::All && can_reformat(&code.language) {
+        action_row = action_row.push(
+            row![
+                labeled(
+                    button(icons::text_indent().size(14))
+                        .on_press(Message::ReformatSnippet(id, JsonFormat::Pretty))
+                        .padding([SPACE_XS, SPACE_SM])
+                        .style(subtle_button_style),
+                    "Pretty-print"
+                ),
+                labeled(
+                    button(icons::arrows_in_line_horizontal().size(14))
+                        .on_press(Message::ReformatSnippet(id, JsonFormat::Minified))
+                        .padding([SPACE_XS, SPACE_SM])
+                        .style(subtle_button_style),
+                    "Minify"
+                ),
+            ]
+            .spacing(SPACE_XS),
+        );
+    }
+    if actions == ActionSet::All {
+        action_row = action_row.push(
+            row![
+                labeled(
+                    button(icons::push_pin().size(14))
+                        .on_press(Message::PinSnippet(id))
+                        .padding([SPACE_XS, SPACE_SM])
+                        .style(subtle_button_style),
+                    "Pin in a window"
+                ),
+                labeled(
+                    button(icons::arrow_square_out().size(14))
+                        .on_press(Message::OpenInExternalEditor(id, true))
+                        .padding([SPACE_XS, SPACE_SM])
+                        .style(subtle_button_style),
+                    "Open in external editor"
+                ),
+                labeled(
+                    button(icons::image().size(14))
+                        .on_press(Message::OpenShareImage(id))
+                        .padding([SPACE_XS, SPACE_SM])
+                        .style(subtle_button_style),
+                    "Share as image"
+                ),
+                labeled(
+                    button(icons::pencil().size(14))
+                        .on_press(Message::OpenCodeEditor(Some(id)))
+                        .padding([SPACE_XS, SPACE_SM])
+                        .style(subtle_button_style),
+                    "Edit"
+                ),
+                labeled(
+                    button(icons::trash().size(14))
+                        .on_press(Message::DeleteSnippet(id))
+                        .padding([SPACE_XS, SPACE_SM])
+                        .style(danger_button_style),
+                    "Move to trash"
+                ),
+            ]
+            .spacing(SPACE_XS),
+        );
+    }
 
     let card = row![code_icon, info_column, action_row]
         .spacing(SPACE_MD)
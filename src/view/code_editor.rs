@@ -1,31 +1,63 @@
-//! Code editor modal for editing code snippets.
+//! Code editor modal for editing code and JSON snippets.
 
 use iced::highlighter;
+use iced::widget::text::{LineHeight, Wrapping};
 use iced::widget::{
-    button, column, container, mouse_area, opaque, row, text, text_editor, text_input,
+    button, column, combo_box, container, mouse_area, opaque, pick_list, row, scrollable, text,
+    text_editor, text_input,
 };
 use iced::{Element, Length};
 
+use crate::config::EditingConfig;
 use crate::icons;
 use crate::message::Message;
-use crate::snippet::{detect_language, language_to_extension, Snippet, SnippetContent};
+use crate::snippet::{
+    detect_language, language_to_extension, JsonFormat, Snippet, SnippetContent,
+    WhitespaceTransform, LANGUAGES,
+};
 use crate::theme::{
-    input_style, modal_dialog_style, modal_overlay_style, primary_button_style,
-    secondary_button_style, subtle_button_style, SPACE_MD, SPACE_SM, SPACE_XS, TEXT_MUTED,
-    TEXT_PRIMARY, TEXT_SECONDARY,
+    input_style, modal_dialog_style, modal_overlay_style, pick_list_style, primary_button_style,
+    scrollbar_style, secondary_button_style, subtle_button_style, DANGER, SPACE_MD, SPACE_SM,
+    SPACE_XS, TEXT_MUTED, TEXT_PRIMARY, TEXT_SECONDARY,
 };
+use crate::view::text_editor::editor_key_binding;
+use crate::view::{description_content, labeled, view_content_hash, view_description_input};
+
+/// Id of the scrollable holding the line numbers and the code.
+pub const CODE_EDITOR_SCROLL_ID: &str = "code-editor-scroll";
+
+/// Text size of the code and its line numbers.
+const CODE_TEXT_SIZE: f32 = 14.0;
+/// Height of a line of code; the line numbers use the same, so they stay
+/// next to their lines.
+const CODE_LINE_HEIGHT: f32 = 20.0;
+/// Visible height of the code area.
+const CODE_AREA_HEIGHT: f32 = 300.0;
 
 /// State for the code editor modal.
 #[derive(Debug, Clone)]
 pub struct CodeEditorState {
     /// The snippet being edited (Some = editing existing, None = creating new)
     pub editing_id: Option<i64>,
+    /// Revision of the edited snippet when editing started.
+    pub revision: i64,
     /// The code content
     pub content: text_editor::Content,
     /// The detected/selected language
     pub language: String,
+    /// Known languages offered in the language box.
+    pub languages: combo_box::State<String>,
     /// Label for the snippet
     pub label: String,
+    /// Notes on the snippet
+    pub description: text_editor::Content,
+    /// Editing a JSON snippet, which is validated on save.
+    pub is_json: bool,
+    /// Why the JSON could not be saved or formatted.
+    pub json_error: Option<String>,
+    /// Scroll offset and visible height of the code area.
+    pub scroll_top: f32,
+    pub viewport_height: f32,
 }
 
 impl CodeEditorState {
@@ -33,23 +65,49 @@ impl CodeEditorState {
     pub fn new_code() -> Self {
         Self {
             editing_id: None,
+            revision: 0,
             content: text_editor::Content::new(),
             language: "plain".to_string(),
+            languages: combo_box::State::new(LANGUAGES.iter().map(|l| l.to_string()).collect()),
             label: String::new(),
+            description: text_editor::Content::new(),
+            is_json: false,
+            json_error: None,
+            scroll_top: 0.0,
+            viewport_height: CODE_AREA_HEIGHT,
+        }
+    }
+
+    /// Create a new editor state for a new JSON snippet.
+    pub fn new_json() -> Self {
+        Self {
+            language: "json".to_string(),
+            is_json: true,
+            ..Self::new_code()
         }
     }
 
     /// Create an editor state from an existing snippet.
     pub fn from_snippet(snippet: &Snippet) -> Self {
-        if let SnippetContent::Code(code) = &snippet.content {
-            Self {
+        match &snippet.content {
+            SnippetContent::Code(code) => Self {
                 editing_id: Some(snippet.id),
+                revision: snippet.revision,
                 content: text_editor::Content::with_text(&code.code),
                 language: code.language.clone(),
                 label: snippet.label.clone(),
-            }
-        } else {
-            Self::new_code()
+                description: description_content(snippet),
+                ..Self::new_code()
+            },
+            SnippetContent::Json(json) => Self {
+                editing_id: Some(snippet.id),
+                revision: snippet.revision,
+                content: text_editor::Content::with_text(&json.json),
+                label: snippet.label.clone(),
+                description: description_content(snippet),
+                ..Self::new_json()
+            },
+            _ => Self::new_code(),
         }
     }
 
@@ -63,70 +121,200 @@ impl CodeEditorState {
         let code = self.content.text();
         self.language = detect_language(&code);
     }
+
+    /// Find the scroll offset bringing the cursor's line into view, if it
+    /// is scrolled out of it.
+    pub fn scroll_to_cursor(&self) -> Option<f32> {
+        let top = SPACE_SM + self.content.cursor().position.line as f32 * CODE_LINE_HEIGHT;
+        let bottom = top + CODE_LINE_HEIGHT;
+        if top < self.scroll_top + SPACE_SM {
+            Some((top - SPACE_SM).max(0.0))
+        } else if bottom > self.scroll_top + self.viewport_height - SPACE_SM {
+            Some(bottom + SPACE_SM - self.viewport_height)
+        } else {
+            None
+        }
+    }
 }
 
 /// Render the code editor modal.
-pub fn view_code_editor_modal(editor: &CodeEditorState) -> Element<'_, Message> {
-    let title = if editor.editing_id.is_some() {
-        "Edit Code Snippet"
-    } else {
-        "New Code Snippet"
+pub fn view_code_editor_modal(
+    editor: &CodeEditorState,
+    theme: highlighter::Theme,
+    editing: EditingConfig,
+) -> Element<'_, Message> {
+    let title = match (editor.editing_id.is_some(), editor.is_json) {
+        (true, false) => "Edit Code Snippet",
+        (false, false) => "New Code Snippet",
+        (true, true) => "Edit JSON Snippet",
+        (false, true) => "New JSON Snippet",
     };
 
     // Header
-    let external_editor_button = if let Some(id) = editor.editing_id {
-        button(icons::arrow_square_out().size(14))
-            .on_press(Message::OpenInExternalEditor(id, true))
-            .padding([SPACE_XS, SPACE_SM])
-            .style(subtle_button_style)
-    } else {
+    let external_editor_button = labeled(
         button(icons::arrow_square_out().size(14))
+            .on_press_maybe(
+                editor
+                    .editing_id
+                    .map(|id| Message::OpenInExternalEditor(id, true)),
+            )
             .padding([SPACE_XS, SPACE_SM])
-            .style(subtle_button_style)
-    };
+            .style(subtle_button_style),
+        "Open in external editor",
+    );
 
     let header_row = row![
         text(title).size(20).color(TEXT_PRIMARY),
         iced::widget::Space::new().width(Length::Fill),
         external_editor_button,
-        button(icons::x().size(16))
-            .on_press(Message::CloseCodeEditor)
-            .padding([SPACE_XS, SPACE_SM])
-            .style(subtle_button_style),
+        labeled(
+            button(icons::x().size(16))
+                .on_press(Message::CloseCodeEditor)
+                .padding([SPACE_XS, SPACE_SM])
+                .style(subtle_button_style),
+            "Close"
+        ),
     ]
     .spacing(SPACE_XS)
     .align_y(iced::Alignment::Center);
 
     // Code editor with syntax highlighting
     let extension = language_to_extension(&editor.language);
+    let cursor = editor.content.cursor();
+    let line_count = editor.content.line_count();
     let code_text_editor = text_editor(&editor.content)
         .on_action(Message::CodeEditorContentChanged)
-        .height(Length::Fixed(300.0))
+        .key_binding(editor_key_binding(editing, cursor.position.column))
         .padding(SPACE_SM)
-        .highlight(extension, highlighter::Theme::Base16Mocha);
+        .size(CODE_TEXT_SIZE)
+        .line_height(LineHeight::Absolute(CODE_LINE_HEIGHT.into()))
+        .highlight(extension, theme)
+        .style(|theme, status| text_editor::Style {
+            border: iced::Border::default(),
+            ..text_editor::default(theme, status)
+        });
+
+    let code_area = if editing.code_word_wrap {
+        // Wrapped lines would drift from their numbers, so no gutter
+        container(
+            code_text_editor
+                .height(Length::Fixed(CODE_AREA_HEIGHT))
+                .wrapping(Wrapping::Word),
+        )
+    } else {
+        // Line number gutter, the cursor's line brighter
+        let gutter = column((1..=line_count).map(|number| {
+            text(number.to_string())
+                .size(CODE_TEXT_SIZE)
+                .line_height(LineHeight::Absolute(CODE_LINE_HEIGHT.into()))
+                .color(if number == cursor.position.line + 1 {
+                    TEXT_SECONDARY
+                } else {
+                    TEXT_MUTED
+                })
+                .into()
+        }))
+        .align_x(iced::Alignment::End)
+        .padding([SPACE_SM, 0.0]);
+
+        // The gutter scrolls with the code, as the editor grows to fit it
+        container(
+            scrollable(
+                row![
+                    gutter,
+                    code_text_editor
+                        .height(Length::Shrink)
+                        .min_height(CODE_AREA_HEIGHT)
+                        .wrapping(Wrapping::None)
+                ]
+                .spacing(SPACE_XS)
+                .padding(iced::Padding::ZERO.left(SPACE_SM)),
+            )
+            .id(CODE_EDITOR_SCROLL_ID)
+            .on_scroll(Message::CodeEditorScrolled)
+            .height(Length::Fixed(CODE_AREA_HEIGHT))
+            .style(scrollbar_style),
+        )
+    }
+    .style(|theme: &iced::Theme| {
+        let palette = theme.extended_palette();
+        container::Style::default()
+            .background(palette.background.base.color)
+            .border(
+                iced::Border::default()
+                    .rounded(2.0)
+                    .width(1.0)
+                    .color(palette.background.strong.color),
+            )
+    });
 
     // Editor status bar (line:column, line count)
-    let cursor = editor.content.cursor();
-    let line_count = editor.content.line_count();
     let status_text = format!(
         "Ln {}, Col {}  •  {} lines",
         cursor.position.line + 1,
         cursor.position.column + 1,
         line_count
     );
-    let editor_status = text(status_text).size(11).color(TEXT_MUTED);
+    let editor_status = row![
+        text(status_text).size(11).color(TEXT_MUTED),
+        iced::widget::Space::new().width(Length::Fill),
+        view_content_hash(&editor.content.text(), editor.editing_id),
+    ]
+    .align_y(iced::Alignment::Center);
 
-    // Language input
-    let language_input = row![
-        text("Language:").size(12).color(TEXT_SECONDARY),
-        text_input("plain", &editor.language)
+    // Language input, or the formatting actions and parse error of JSON
+    let language_input: Element<'_, Message> = if editor.is_json {
+        let mut json_row = row![
+            button(text("Pretty-print").size(12))
+                .on_press(Message::CodeEditorFormatJson(JsonFormat::Pretty))
+                .padding([SPACE_XS, SPACE_SM])
+                .style(secondary_button_style),
+            button(text("Minify").size(12))
+                .on_press(Message::CodeEditorFormatJson(JsonFormat::Minified))
+                .padding([SPACE_XS, SPACE_SM])
+                .style(secondary_button_style),
+        ]
+        .spacing(SPACE_SM)
+        .align_y(iced::Alignment::Center);
+        if let Some(error) = &editor.json_error {
+            json_row = json_row.push(text(error).size(12).color(DANGER));
+        }
+        json_row.into()
+    } else {
+        // Typed names that aren't in the list are kept as custom languages
+        let mut language_row = row![
+            text("Language:").size(12).color(TEXT_SECONDARY),
+            combo_box(
+                &editor.languages,
+                "plain",
+                Some(&editor.language),
+                Message::CodeEditorLanguageChanged
+            )
             .on_input(Message::CodeEditorLanguageChanged)
             .padding(SPACE_SM)
-            .width(Length::Fixed(120.0))
-            .style(|theme, status| input_style(theme, status, false)),
-    ]
-    .spacing(SPACE_SM)
-    .align_y(iced::Alignment::Center);
+            .width(Length::Fixed(140.0))
+            .input_style(|theme, status| input_style(theme, status, false)),
+        ]
+        .spacing(SPACE_SM)
+        .align_y(iced::Alignment::Center);
+        if extension == "txt" && !matches!(editor.language.as_str(), "" | "plain") {
+            language_row = language_row.push(text("Not highlighted").size(11).color(TEXT_MUTED));
+        }
+        language_row
+            .push(iced::widget::Space::new().width(Length::Fill))
+            .push(
+                pick_list(
+                    WhitespaceTransform::ALL,
+                    None::<WhitespaceTransform>,
+                    Message::CodeEditorTransform,
+                )
+                .placeholder("Whitespace…")
+                .text_size(12)
+                .padding([SPACE_XS, SPACE_SM])
+                .style(pick_list_style),
+            )
+            .into()
+    };
 
     // Label input
     let label_input = row![
@@ -163,10 +351,11 @@ pub fn view_code_editor_modal(editor: &CodeEditorState) -> Element<'_, Message>
     // Modal content
     let modal_content = column![
         header_row,
-        code_text_editor,
+        code_area,
         editor_status,
         language_input,
         label_input,
+        view_description_input(&editor.description, Message::CodeEditorDescriptionChanged),
         action_buttons,
     ]
     .spacing(SPACE_SM)
@@ -0,0 +1,188 @@
+//! Dialog exporting a palette's colors as code (CSS and Sass variables,
+//! Tailwind themes), with a preview to copy or save.
+
+use iced::widget::{
+    button, checkbox, column, container, mouse_area, opaque, row, scrollable, text,
+};
+use iced::{Element, Font, Length};
+
+use crate::export::{CodeFormat, CodeOptions, ColorValue};
+use crate::icons;
+use crate::message::Message;
+use crate::snippet::ColorData;
+use crate::theme::{
+    modal_dialog_style, modal_overlay_style, primary_button_style, scrollbar_style,
+    secondary_button_style, subtle_button_style, BG_BASE, BORDER_SUBTLE, SPACE_MD, SPACE_SM,
+    SPACE_XS, TEXT_PRIMARY, TEXT_SECONDARY,
+};
+use crate::view::labeled;
+
+/// State of the code export dialog.
+#[derive(Debug, Clone)]
+pub struct CodeExportState {
+    /// Name of the exported palette, used for the file name.
+    pub name: String,
+    /// The palette's named colors, taken when the dialog opened.
+    pub colors: Vec<(String, ColorData)>,
+    pub format: CodeFormat,
+    pub options: CodeOptions,
+}
+
+impl CodeExportState {
+    /// Open the dialog on a palette's colors as CSS variables.
+    pub fn new(name: String, colors: Vec<(String, ColorData)>) -> Self {
+        Self {
+            name,
+            colors,
+            format: CodeFormat::Css,
+            options: CodeOptions::default(),
+        }
+    }
+
+    /// The exported code.
+    pub fn output(&self) -> String {
+        self.format.render(&self.name, &self.colors, &self.options)
+    }
+}
+
+/// Render a row of toggle buttons, the selected one highlighted.
+fn choice_buttons<T: Copy + PartialEq + std::fmt::Display>(
+    choices: &[T],
+    selected: T,
+    on_press: fn(T) -> Message,
+) -> Element<'static, Message> {
+    row(choices.iter().map(|choice| {
+        button(text(choice.to_string()).size(12))
+            .on_press(on_press(*choice))
+            .padding([SPACE_XS, SPACE_SM])
+            .style(if *choice == selected {
+                primary_button_style
+            } else {
+                secondary_button_style
+            })
+            .into()
+    }))
+    .spacing(2)
+    .into()
+}
+
+/// Render the code export dialog.
+pub fn view_code_export_modal(state: &CodeExportState) -> Element<'_, Message> {
+    let header_row = row![
+        text(format!("Export \"{}\" as Code", state.name))
+            .size(20)
+            .color(TEXT_PRIMARY),
+        iced::widget::Space::new().width(Length::Fill),
+        labeled(
+            button(icons::x().size(16))
+                .on_press(Message::CloseCodeExport)
+                .padding([SPACE_XS, SPACE_SM])
+                .style(subtle_button_style),
+            "Close"
+        ),
+    ]
+    .spacing(SPACE_XS)
+    .align_y(iced::Alignment::Center);
+
+    let option_row = |label, control| {
+        row![
+            text(label).size(12).color(TEXT_SECONDARY).width(80),
+            control
+        ]
+        .spacing(SPACE_SM)
+        .align_y(iced::Alignment::Center)
+    };
+    let mut options = column![
+        option_row(
+            "Format",
+            choice_buttons(
+                &CodeFormat::ALL,
+                state.format,
+                Message::CodeExportFormatChanged
+            )
+        ),
+        option_row(
+            "Values",
+            choice_buttons(
+                &ColorValue::ALL,
+                state.options.value,
+                Message::CodeExportValueChanged
+            )
+        ),
+    ]
+    .spacing(SPACE_SM);
+    if state.format.has_shades() {
+        options = options.push(option_row(
+            "Shades",
+            checkbox(state.options.shades)
+                .label("50–950 scale for each color")
+                .on_toggle(Message::CodeExportShadesToggled)
+                .text_size(12)
+                .into(),
+        ));
+    }
+    if state.format.has_map() {
+        options = options.push(option_row(
+            "Map",
+            checkbox(state.options.map)
+                .label("Also a map of every color")
+                .on_toggle(Message::CodeExportMapToggled)
+                .text_size(12)
+                .into(),
+        ));
+    }
+
+    let preview = container(
+        scrollable(
+            text(state.output())
+                .size(12)
+                .font(Font::MONOSPACE)
+                .color(TEXT_PRIMARY),
+        )
+        .width(Length::Fill)
+        .style(scrollbar_style),
+    )
+    .padding(SPACE_SM)
+    .width(Length::Fill)
+    .height(Length::Fixed(260.0))
+    .style(|_theme| {
+        container::Style::default().background(BG_BASE).border(
+            iced::Border::default()
+                .rounded(4.0)
+                .width(1.0)
+                .color(BORDER_SUBTLE),
+        )
+    });
+
+    let action_buttons = row![
+        iced::widget::Space::new().width(Length::Fill),
+        button(text("Save…").size(14))
+            .on_press(Message::SaveCodeExport)
+            .padding(SPACE_SM)
+            .style(secondary_button_style),
+        button(text("Copy").size(14))
+            .on_press(Message::CopyCodeExport)
+            .padding(SPACE_SM)
+            .style(primary_button_style),
+    ]
+    .spacing(SPACE_SM);
+
+    let modal_content = column![header_row, options, preview, action_buttons]
+        .spacing(SPACE_MD)
+        .padding(SPACE_MD)
+        .width(Length::Fixed(520.0));
+
+    let modal_dialog = container(modal_content).style(modal_dialog_style);
+
+    // Semi-transparent overlay
+    mouse_area(
+        container(opaque(modal_dialog))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x(Length::Fill)
+            .center_y(Length::Fill)
+            .style(modal_overlay_style),
+    )
+    .on_press(Message::CloseCodeExport)
+    .into()
+}
@@ -1,24 +1,58 @@
 //! Color card view component.
 
-use iced::widget::{button, column, container, row, text, Canvas};
+use iced::widget::{button, column, container, row, text, text_input, Canvas};
 use iced::{Element, Length};
 
+use crate::config::{CardActions, CopyTemplate};
 use crate::icons;
 use crate::message::Message;
 use crate::snippet::ColorData;
 use crate::theme::{
-    card_style, danger_button_style, subtle_button_style, BG_SURFACE, SPACE_MD, SPACE_SM, SPACE_XS,
-    TEXT_MUTED, TEXT_SECONDARY,
+    card_style, danger_button_style, input_style, subtle_button_style, BG_SURFACE, SPACE_MD,
+    SPACE_SM, SPACE_XS, TEXT_SECONDARY,
 };
+use crate::view::{card_with_actions, labeled, view_age, view_card_header, ActionSet, CardStats};
 use crate::widgets::ColorSwatch;
 
+/// What a color card shows besides its snippet.
+#[derive(Debug, Clone, Copy)]
+pub struct ColorCardInputs<'a> {
+    /// Uncommitted edit of the inline value field, if any.
+    pub value_draft: Option<&'a str>,
+    /// The user's copy templates, shown as extra copy buttons.
+    pub copy_templates: &'a [CopyTemplate],
+}
+
 /// Render a color card for the palette list.
 pub fn view_color_card<'a>(
     id: i64,
     label: &'a str,
     color: &'a ColorData,
+    stats: CardStats<'a>,
+    is_selected: bool,
+    inputs: ColorCardInputs<'a>,
+    visibility: CardActions,
+) -> Element<'a, Message> {
+    card_with_actions(visibility, |actions| {
+        build_color_card(id, label, color, stats, is_selected, inputs, actions)
+    })
+}
+
+/// Build a color card with the given set of action buttons.
+/// The format copy buttons are part of the card body and always shown.
+fn build_color_card<'a>(
+    id: i64,
+    label: &'a str,
+    color: &'a ColorData,
+    stats: CardStats<'a>,
     is_selected: bool,
+    inputs: ColorCardInputs<'a>,
+    actions: ActionSet,
 ) -> Element<'a, Message> {
+    let ColorCardInputs {
+        value_draft,
+        copy_templates,
+    } = inputs;
     // Color swatch (64x64)
     let swatch = container(
         Canvas::new(ColorSwatch {
@@ -33,8 +67,18 @@ pub fn view_color_card<'a>(
     .center_y(64)
     .style(|_theme| iced::widget::container::Style::default().background(BG_SURFACE));
 
-    // Hex display
-    let hex_display = text(color.to_hex()).size(11).color(TEXT_MUTED);
+    // Editable hex value (accepts any format ColorData::parse understands)
+    let hex_value = value_draft
+        .map(str::to_string)
+        .unwrap_or_else(|| color.to_hex());
+    let has_error = value_draft.is_some_and(|draft| ColorData::parse(draft).is_err());
+    let hex_display = text_input("#RRGGBB", &hex_value)
+        .on_input(move |value| Message::ColorValueEdited(id, value))
+        .on_submit(Message::CommitColorValue(id))
+        .size(11)
+        .padding([2.0, SPACE_XS])
+        .width(Length::Fixed(160.0))
+        .style(move |theme, status| input_style(theme, status, has_error));
 
     // Copy buttons row
     let copy_buttons = row![
@@ -50,39 +94,110 @@ pub fn view_color_card<'a>(
             .on_press(Message::CopyHsl(id))
             .padding([SPACE_XS, SPACE_SM])
             .style(subtle_button_style),
+        button(row![icons::copy().size(11), text("HSV").size(11)].spacing(4))
+            .on_press(Message::CopyHsv(id))
+            .padding([SPACE_XS, SPACE_SM])
+            .style(subtle_button_style),
         button(row![icons::copy().size(11), text("OKLCH").size(11)].spacing(4))
             .on_press(Message::CopyOklch(id))
             .padding([SPACE_XS, SPACE_SM])
             .style(subtle_button_style),
+        button(row![icons::copy().size(11), text("LAB").size(11)].spacing(4))
+            .on_press(Message::CopyLab(id))
+            .padding([SPACE_XS, SPACE_SM])
+            .style(subtle_button_style),
+        button(row![icons::copy().size(11), text("LCH").size(11)].spacing(4))
+            .on_press(Message::CopyLch(id))
+            .padding([SPACE_XS, SPACE_SM])
+            .style(subtle_button_style),
+        button(row![icons::copy().size(11), text("P3").size(11)].spacing(4))
+            .on_press(Message::CopyDisplayP3(id))
+            .padding([SPACE_XS, SPACE_SM])
+            .style(subtle_button_style),
+        button(row![icons::copy().size(11), text("CMYK").size(11)].spacing(4))
+            .on_press(Message::CopyCmyk(id))
+            .padding([SPACE_XS, SPACE_SM])
+            .style(subtle_button_style),
     ]
     .spacing(SPACE_XS);
 
     // Info column with label, hex, and copy buttons
-    let info_column = column![
-        text(label).size(14).color(TEXT_SECONDARY),
+    let header_row = row![text(label).size(14).color(TEXT_SECONDARY), view_age(stats)]
+        .spacing(SPACE_SM)
+        .align_y(iced::Alignment::Center);
+    let mut info_column = column![
+        view_card_header(header_row, stats),
         hex_display,
-        copy_buttons,
+        copy_buttons
     ]
     .spacing(SPACE_XS)
     .width(Length::Fill);
 
+    // The user's copy templates, on a row of their own
+    if !copy_templates.is_empty() {
+        info_column = info_column.push(
+            row(copy_templates.iter().enumerate().map(|(index, template)| {
+                button(row![icons::copy().size(11), text(&template.name).size(11)].spacing(4))
+                    .on_press(Message::CopyColorTemplate(id, index))
+                    .padding([SPACE_XS, SPACE_SM])
+                    .style(subtle_button_style)
+                    .into()
+            }))
+            .spacing(SPACE_XS),
+        );
+    }
+
     // Action buttons (pin, edit, delete)
-    let action_row = row![
-        button(icons::push_pin().size(14))
-            .on_press(Message::PinSnippet(id))
-            .padding([SPACE_XS, SPACE_SM])
-            .style(subtle_button_style),
-        button(icons::pencil().size(14))
-            .on_press(Message::OpenColorPicker(Some(id)))
-            .padding([SPACE_XS, SPACE_SM])
-            .style(subtle_button_style),
-        button(icons::trash().size(14))
-            .on_press(Message::DeleteSnippet(id))
-            .padding([SPACE_XS, SPACE_SM])
-            .style(danger_button_style),
-    ]
-    .spacing(SPACE_XS)
-    .align_y(iced::Alignment::Center);
+    let mut action_row = row![].spacing(SPACE_XS).align_y(iced::Alignment::Center);
+    if actions == ActionSet::All {
+        action_row = action_row.push(
+            row![
+                labeled(
+                    button(icons::push_pin().size(14))
+                        .on_press(Message::PinSnippet(id))
+                        .padding([SPACE_XS, SPACE_SM])
+                        .style(subtle_button_style),
+                    "Pin in a window"
+                ),
+                labeled(
+                    button(icons::stack().size(14))
+                        .on_press(Message::OpenScaleDialog(id))
+                        .padding([SPACE_XS, SPACE_SM])
+                        .style(subtle_button_style),
+                    "Generate scale"
+                ),
+                labeled(
+                    button(icons::circle_half().size(14))
+                        .on_press(Message::OpenContrastChecker(id))
+                        .padding([SPACE_XS, SPACE_SM])
+                        .style(subtle_button_style),
+                    "Check contrast"
+                ),
+                labeled(
+                    button(icons::pencil().size(14))
+                        .on_press(Message::OpenColorPicker(Some(id)))
+                        .padding([SPACE_XS, SPACE_SM])
+                        .style(subtle_button_style),
+                    "Edit"
+                ),
+                labeled(
+                    button(icons::trash().size(14))
+                        .on_press(Message::DeleteSnippet(id))
+                        .padding([SPACE_XS, SPACE_SM])
+                        .style(danger_button_style),
+                    "Move to trash"
+                ),
+                labeled(
+                    button(icons::dots_three().size(14))
+                        .on_press(Message::ToggleCardMenu(Some(id)))
+                        .padding([SPACE_XS, SPACE_SM])
+                        .style(subtle_button_style),
+                    "More actions"
+                ),
+            ]
+            .spacing(SPACE_XS),
+        );
+    }
 
     let card = row![swatch, info_column, action_row]
         .spacing(SPACE_MD)
@@ -1,29 +1,87 @@
 //! Color picker modal state and view.
 
 use iced::widget::{
-    button, column, container, mouse_area, opaque, row, slider, text, text_input, Canvas,
+    button, column, container, mouse_area, opaque, row, slider, stack, text, text_editor,
+    text_input, Canvas,
 };
 use iced::{Element, Length};
 
+use crate::config::CopyTemplate;
 use crate::icons;
 use crate::message::Message;
 use crate::snippet::{
-    hsl_to_rgb, oklch_to_rgb, rgb_to_hsl, rgb_to_oklch, ColorData, Snippet, SnippetContent,
+    hsl_to_rgb, hsv_to_rgb, nearest_named_color, oklch_gamut, oklch_to_rgb, rgb_to_hsl, rgb_to_hsv,
+    rgb_to_oklch, ColorAdjustment, ColorData, Gamut, Snippet, SnippetContent,
 };
 use crate::theme::{
     input_style, modal_dialog_style, modal_overlay_style, primary_button_style,
     secondary_button_style, subtle_button_style, SPACE_MD, SPACE_SM, SPACE_XS, TEXT_MUTED,
     TEXT_PRIMARY, TEXT_SECONDARY,
 };
-use crate::widgets::{AlphaBar, ChromaLightnessBox, ColorSwatch, HueBar, SaturationLightnessBox};
+use crate::view::{description_content, labeled, view_description_input};
+use crate::widgets::{
+    AlphaBar, ChromaLightnessBox, ColorSwatch, HueBar, SaturationLightnessBox, SaturationValueBox,
+};
 
-/// Color picker mode: HSL or OKLCH color space.
+/// OKLCH lightness step for the quick tone buttons (5% of the 0-1 range).
+const OKLCH_LIGHTNESS_STEP: f32 = 0.05;
+/// OKLCH chroma step for the quick tone buttons (5% of the 0-0.4 range).
+const OKLCH_CHROMA_STEP: f32 = 0.02;
+
+/// Maximum number of undo steps kept per picker session.
+const MAX_UNDO_STEPS: usize = 50;
+
+/// Steps of the picker tour, as (title, explanation).
+const TOUR_STEPS: [(&str, &str); 3] = [
+    (
+        "HSL or OKLCH",
+        "HSL is quick to reason about, but its lightness is not how bright a color looks: \
+         yellow and blue at the same L are far apart. OKLCH is perceptual, so equal L looks \
+         equally light across hues and steps in C change colorfulness evenly. Use it for \
+         shades and palettes that need to stay balanced.",
+    ),
+    (
+        "The CL box",
+        "In OKLCH mode the box plots chroma (left to right) against lightness (bottom to top) \
+         for the current hue. Screens can't show every combination: past the edge of the sRGB \
+         gamut a color is clipped to the nearest displayable one, so the swatch stops getting \
+         more colorful and the far side of the box flattens into bands.",
+    ),
+    (
+        "Alpha",
+        "The bar under the tone buttons sets transparency, shown over a checkerboard. Alpha is \
+         kept apart from the color, so switching modes never changes it. Colors below 100% \
+         are copied with their alpha, e.g. as 8-digit hex or rgba().",
+    ),
+];
+
+/// Color picker mode: HSL, HSV or OKLCH color space, or raw RGB channels.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum PickerMode {
     #[default]
     Hsl,
+    Hsv,
     Oklch,
+    Rgb,
+}
+
+impl PickerMode {
+    pub const ALL: [PickerMode; 4] = [
+        PickerMode::Hsl,
+        PickerMode::Hsv,
+        PickerMode::Oklch,
+        PickerMode::Rgb,
+    ];
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            PickerMode::Hsl => "HSL",
+            PickerMode::Hsv => "HSV",
+            PickerMode::Oklch => "OKLCH",
+            PickerMode::Rgb => "RGB",
+        }
+    }
 }
 
 /// State for the color picker modal.
@@ -31,26 +89,62 @@ pub enum PickerMode {
 pub struct ColorPickerState {
     /// The snippet being edited (Some = editing existing, None = creating new)
     pub editing_id: Option<i64>,
-    /// Current picker mode (HSL or OKLCH)
+    /// Revision of the edited snippet when editing started.
+    pub revision: i64,
+    /// Current picker mode (HSL, HSV, OKLCH or RGB)
     pub mode: PickerMode,
-    /// Current hue (0-360) - shared between HSL and OKLCH
+    /// Current hue (0-360) - shared between HSL and HSV
     pub hue: f32,
     /// Current saturation (0-1) - HSL mode
     pub saturation: f32,
     /// Current lightness (0-1) - HSL mode
     pub lightness: f32,
+    /// Current saturation (0-1) - HSV mode
+    pub hsv_s: f32,
+    /// Current value (0-1) - HSV mode
+    pub hsv_v: f32,
     /// Current OKLCH lightness (0-1)
     pub oklch_l: f32,
     /// Current OKLCH chroma (0-0.4+)
     pub oklch_c: f32,
     /// Current OKLCH hue (0-360)
     pub oklch_h: f32,
+    /// Current red, green and blue channels (0-255) - RGB mode
+    pub rgb: [u8; 3],
     /// Current alpha (0-1)
     pub alpha: f32,
     /// Label for the color
     pub label: String,
+    /// Notes on the color
+    pub description: text_editor::Content,
     /// Original color when editing (r, g, b, a) - used to detect changes
     original_color: Option<(u8, u8, u8, f32)>,
+    /// Picker values before each adjustment, newest last.
+    history: Vec<PickerSnapshot>,
+    /// Whether a drag is in progress (its changes form a single undo step).
+    adjusting: bool,
+    /// Step of the guided tour being shown, if the tour is open.
+    pub tour_step: Option<usize>,
+    /// Gradient stop whose color is edited, instead of a snippet.
+    pub gradient_stop: Option<usize>,
+    /// Text typed into the color field, until another control changes the color.
+    pub color_input: Option<String>,
+}
+
+/// The adjustable values of a picker, saved for undo.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct PickerSnapshot {
+    mode: PickerMode,
+    hue: f32,
+    saturation: f32,
+    lightness: f32,
+    hsv_s: f32,
+    hsv_v: f32,
+    oklch_l: f32,
+    oklch_c: f32,
+    oklch_h: f32,
+    rgb: [u8; 3],
+    alpha: f32,
 }
 
 impl ColorPickerState {
@@ -58,36 +152,65 @@ impl ColorPickerState {
     pub fn new_color(default_mode: PickerMode) -> Self {
         Self {
             editing_id: None,
+            revision: 0,
             mode: default_mode,
             hue: 0.0,
             saturation: 0.5,
             lightness: 0.5,
+            hsv_s: 2.0 / 3.0,
+            hsv_v: 0.75,
             oklch_l: 0.5,
             oklch_c: 0.15,
             oklch_h: 0.0,
+            rgb: [191, 64, 64],
             alpha: 1.0,
             label: String::new(),
+            description: text_editor::Content::new(),
             original_color: None,
+            history: Vec::new(),
+            adjusting: false,
+            tour_step: None,
+            gradient_stop: None,
+            color_input: None,
         }
     }
 
+    /// Create a picker state editing the color of a gradient stop.
+    pub fn for_gradient_stop(index: usize, color: &ColorData, default_mode: PickerMode) -> Self {
+        let mut picker = Self::new_color(default_mode);
+        picker.set_color(color);
+        picker.gradient_stop = Some(index);
+        picker
+    }
+
     /// Create a picker state from an existing snippet.
     pub fn from_snippet(snippet: &Snippet, default_mode: PickerMode) -> Self {
         if let SnippetContent::Color(color) = &snippet.content {
             let (h, s, l) = rgb_to_hsl(color.r, color.g, color.b);
+            let (_, hsv_s, hsv_v) = rgb_to_hsv(color.r, color.g, color.b);
             let (ok_l, ok_c, ok_h) = rgb_to_oklch(color.r, color.g, color.b);
             Self {
                 editing_id: Some(snippet.id),
+                revision: snippet.revision,
                 mode: default_mode,
                 hue: h,
                 saturation: s,
                 lightness: l,
+                hsv_s,
+                hsv_v,
                 oklch_l: ok_l,
                 oklch_c: ok_c,
                 oklch_h: ok_h,
+                rgb: [color.r, color.g, color.b],
                 alpha: color.a,
                 label: snippet.label.clone(),
+                description: description_content(snippet),
                 original_color: Some((color.r, color.g, color.b, color.a)),
+                history: Vec::new(),
+                adjusting: false,
+                tour_step: None,
+                gradient_stop: None,
+                color_input: None,
             }
         } else {
             Self::new_color(default_mode)
@@ -98,7 +221,9 @@ impl ColorPickerState {
     pub fn to_rgb(&self) -> (u8, u8, u8) {
         match self.mode {
             PickerMode::Hsl => hsl_to_rgb(self.hue, self.saturation, self.lightness),
+            PickerMode::Hsv => hsv_to_rgb(self.hue, self.hsv_s, self.hsv_v),
             PickerMode::Oklch => oklch_to_rgb(self.oklch_l, self.oklch_c, self.oklch_h),
+            PickerMode::Rgb => (self.rgb[0], self.rgb[1], self.rgb[2]),
         }
     }
 
@@ -114,6 +239,11 @@ impl ColorPickerState {
         ColorData::new(r, g, b, self.alpha)
     }
 
+    /// Label for a color saved without one: the nearest CSS named color.
+    pub fn suggested_label(&self) -> String {
+        nearest_named_color(&self.to_color_data()).0.to_string()
+    }
+
     /// Sync HSL values from the current RGB (used when switching modes).
     pub fn sync_hsl_from_rgb(&mut self) {
         let (r, g, b) = self.to_rgb();
@@ -123,6 +253,15 @@ impl ColorPickerState {
         self.lightness = l;
     }
 
+    /// Sync HSV values from the current RGB (used when switching modes).
+    pub fn sync_hsv_from_rgb(&mut self) {
+        let (r, g, b) = self.to_rgb();
+        let (h, s, v) = rgb_to_hsv(r, g, b);
+        self.hue = h;
+        self.hsv_s = s;
+        self.hsv_v = v;
+    }
+
     /// Sync OKLCH values from the current RGB (used when switching modes).
     pub fn sync_oklch_from_rgb(&mut self) {
         let (r, g, b) = self.to_rgb();
@@ -132,6 +271,133 @@ impl ColorPickerState {
         self.oklch_h = ok_h;
     }
 
+    /// Sync the RGB channels from the current color (used when switching modes).
+    pub fn sync_rgb(&mut self) {
+        let (r, g, b) = self.to_rgb();
+        self.rgb = [r, g, b];
+    }
+
+    fn snapshot(&self) -> PickerSnapshot {
+        PickerSnapshot {
+            mode: self.mode,
+            hue: self.hue,
+            saturation: self.saturation,
+            lightness: self.lightness,
+            hsv_s: self.hsv_s,
+            hsv_v: self.hsv_v,
+            oklch_l: self.oklch_l,
+            oklch_c: self.oklch_c,
+            oklch_h: self.oklch_h,
+            rgb: self.rgb,
+            alpha: self.alpha,
+        }
+    }
+
+    /// Record an undo step before a change, unless a drag is already in progress.
+    pub fn begin_adjustment(&mut self) {
+        self.color_input = None;
+        if self.adjusting {
+            return;
+        }
+        self.adjusting = true;
+        let snapshot = self.snapshot();
+        if self.history.last() != Some(&snapshot) {
+            self.history.push(snapshot);
+            if self.history.len() > MAX_UNDO_STEPS {
+                self.history.remove(0);
+            }
+        }
+    }
+
+    /// Finish the current adjustment so the next change starts a new undo step.
+    pub fn end_adjustment(&mut self) {
+        self.adjusting = false;
+    }
+
+    /// Restore the values from before the last adjustment.
+    /// Returns false if there is nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        self.adjusting = false;
+        self.color_input = None;
+        let Some(snapshot) = self.history.pop() else {
+            return false;
+        };
+        self.mode = snapshot.mode;
+        self.hue = snapshot.hue;
+        self.saturation = snapshot.saturation;
+        self.lightness = snapshot.lightness;
+        self.hsv_s = snapshot.hsv_s;
+        self.hsv_v = snapshot.hsv_v;
+        self.oklch_l = snapshot.oklch_l;
+        self.oklch_c = snapshot.oklch_c;
+        self.oklch_h = snapshot.oklch_h;
+        self.rgb = snapshot.rgb;
+        self.alpha = snapshot.alpha;
+        true
+    }
+
+    /// Check if there are adjustments to undo.
+    pub fn can_undo(&self) -> bool {
+        !self.history.is_empty()
+    }
+
+    /// Step OKLCH lightness and chroma while keeping the OKLCH hue constant.
+    /// In the other modes the result is converted back so their controls follow.
+    pub fn adjust_oklch(&mut self, delta_l: f32, delta_c: f32) {
+        if self.mode != PickerMode::Oklch {
+            self.sync_oklch_from_rgb();
+        }
+
+        self.oklch_l = (self.oklch_l + delta_l).clamp(0.0, 1.0);
+        self.oklch_c = (self.oklch_c + delta_c).clamp(0.0, 0.4);
+
+        if self.mode != PickerMode::Oklch {
+            let (r, g, b) = oklch_to_rgb(self.oklch_l, self.oklch_c, self.oklch_h);
+            let (h, s, l) = rgb_to_hsl(r, g, b);
+            let (_, hsv_s, hsv_v) = rgb_to_hsv(r, g, b);
+            self.hue = h;
+            self.saturation = s;
+            self.lightness = l;
+            self.hsv_s = hsv_s;
+            self.hsv_v = hsv_v;
+            self.rgb = [r, g, b];
+        }
+    }
+
+    /// Set the color in both color spaces, e.g. to a sampled screen pixel.
+    pub fn set_color(&mut self, color: &ColorData) {
+        let (h, s, l) = rgb_to_hsl(color.r, color.g, color.b);
+        let (_, hsv_s, hsv_v) = rgb_to_hsv(color.r, color.g, color.b);
+        let (ok_l, ok_c, ok_h) = rgb_to_oklch(color.r, color.g, color.b);
+        self.hue = h;
+        self.saturation = s;
+        self.lightness = l;
+        self.hsv_s = hsv_s;
+        self.hsv_v = hsv_v;
+        self.oklch_l = ok_l;
+        self.oklch_c = ok_c;
+        self.oklch_h = ok_h;
+        self.rgb = [color.r, color.g, color.b];
+        self.alpha = color.a;
+    }
+
+    /// Take typed color text, applying it as soon as it parses.
+    pub fn set_color_input(&mut self, input: String) {
+        if let Ok(color) = ColorData::parse(&input) {
+            self.begin_adjustment();
+            self.set_color(&color);
+            self.end_adjustment();
+        }
+        self.color_input = Some(input);
+    }
+
+    /// Check if the typed color text is not a color.
+    pub fn color_input_invalid(&self) -> bool {
+        self.color_input
+            .as_deref()
+            .is_some_and(|input| !input.trim().is_empty() && ColorData::parse(input).is_err())
+    }
+
     /// Check if the color has changed from the original (when editing).
     /// Returns true if editing and color differs from original.
     pub fn has_color_changed(&self) -> bool {
@@ -145,43 +411,65 @@ impl ColorPickerState {
 }
 
 /// Render the color picker modal.
-pub fn view_color_picker_modal(picker: &ColorPickerState) -> Element<'_, Message> {
-    let title = if picker.editing_id.is_some() {
+pub fn view_color_picker_modal<'a>(
+    picker: &'a ColorPickerState,
+    copy_templates: &'a [CopyTemplate],
+    recent_colors: &'a [ColorData],
+) -> Element<'a, Message> {
+    let title = if picker.gradient_stop.is_some() {
+        "Stop Color"
+    } else if picker.editing_id.is_some() {
         "Edit Color"
     } else {
         "New Color"
     };
 
     // Header row with title, mode toggle, and close button
-    let mode_toggle = row![
-        button(text("HSL").size(12))
-            .on_press(Message::PickerModeChanged(PickerMode::Hsl))
+    let mode_toggle = row(PickerMode::ALL.iter().map(|mode| {
+        button(text(mode.display_name()).size(12))
+            .on_press(Message::PickerModeChanged(*mode))
             .padding([SPACE_XS, SPACE_SM])
-            .style(if picker.mode == PickerMode::Hsl {
+            .style(if picker.mode == *mode {
                 primary_button_style
             } else {
                 secondary_button_style
-            }),
-        button(text("OKLCH").size(12))
-            .on_press(Message::PickerModeChanged(PickerMode::Oklch))
-            .padding([SPACE_XS, SPACE_SM])
-            .style(if picker.mode == PickerMode::Oklch {
-                primary_button_style
-            } else {
-                secondary_button_style
-            }),
-    ]
+            })
+            .into()
+    }))
     .spacing(2);
 
     let header_row = row![
         text(title).size(20).color(TEXT_PRIMARY),
         iced::widget::Space::new().width(Length::Fill),
+        labeled(
+            button(icons::eyedropper().size(14))
+                .on_press(Message::StartEyedropper)
+                .padding([SPACE_XS, SPACE_SM])
+                .style(subtle_button_style),
+            "Pick from screen"
+        ),
+        labeled(
+            button(icons::arrow_counter_clockwise().size(14))
+                .on_press_maybe(picker.can_undo().then_some(Message::PickerUndo))
+                .padding([SPACE_XS, SPACE_SM])
+                .style(subtle_button_style),
+            "Undo"
+        ),
         mode_toggle,
-        iced::widget::Space::new().width(Length::Fixed(SPACE_SM)),
-        button(icons::x().size(16))
-            .on_press(Message::CloseColorPicker)
-            .padding([SPACE_XS, SPACE_SM])
-            .style(subtle_button_style),
+        labeled(
+            button(icons::question().size(14))
+                .on_press(Message::PickerTourStep(Some(0)))
+                .padding([SPACE_XS, SPACE_SM])
+                .style(subtle_button_style),
+            "Show tour"
+        ),
+        labeled(
+            button(icons::x().size(16))
+                .on_press(Message::CloseColorPicker)
+                .padding([SPACE_XS, SPACE_SM])
+                .style(subtle_button_style),
+            "Close"
+        ),
     ]
     .align_y(iced::Alignment::Center);
 
@@ -221,12 +509,20 @@ pub fn view_color_picker_modal(picker: &ColorPickerState) -> Element<'_, Message
         ))
         .size(12)
         .color(TEXT_SECONDARY),
-        text(picker.to_color_data().to_hex())
-            .size(12)
-            .color(TEXT_MUTED),
     ]
     .spacing(SPACE_XS);
 
+    // Any color the parser understands, shown as hex while not being typed in
+    let hex = picker.to_color_data().to_hex();
+    let has_error = picker.color_input_invalid();
+    let color_input = text_input(&hex, picker.color_input.as_deref().unwrap_or(&hex))
+        .on_input(Message::PickerColorInputChanged)
+        .size(12)
+        .padding(SPACE_XS)
+        .width(Length::Fill)
+        .style(move |theme, status| input_style(theme, status, has_error));
+    let color_values = color_values.push(color_input).width(Length::Fill);
+
     let preview_row = row![preview_swatch, color_values]
         .spacing(SPACE_MD)
         .align_y(iced::Alignment::Center);
@@ -234,13 +530,16 @@ pub fn view_color_picker_modal(picker: &ColorPickerState) -> Element<'_, Message
     // Build mode-specific controls
     let controls: Element<'_, Message> = match picker.mode {
         PickerMode::Hsl => {
-            let sl_box = Canvas::new(SaturationLightnessBox {
-                hue: picker.hue,
-                saturation: picker.saturation,
-                lightness: picker.lightness,
-            })
-            .width(280)
-            .height(200);
+            let sl_box = labeled(
+                Canvas::new(SaturationLightnessBox {
+                    hue: picker.hue,
+                    saturation: picker.saturation,
+                    lightness: picker.lightness,
+                })
+                .width(280)
+                .height(200),
+                "Saturation and lightness",
+            );
 
             let saturation_slider = row![
                 text("S")
@@ -253,6 +552,7 @@ pub fn view_color_picker_modal(picker: &ColorPickerState) -> Element<'_, Message
                     Message::PickerSaturationChanged
                 )
                 .step(0.01)
+                .on_release(Message::PickerAdjustmentEnded)
                 .width(Length::Fill),
             ]
             .spacing(SPACE_SM)
@@ -265,30 +565,88 @@ pub fn view_color_picker_modal(picker: &ColorPickerState) -> Element<'_, Message
                     .width(Length::Fixed(20.0)),
                 slider(0.0..=1.0, picker.lightness, Message::PickerLightnessChanged)
                     .step(0.01)
+                    .on_release(Message::PickerAdjustmentEnded)
                     .width(Length::Fill),
             ]
             .spacing(SPACE_SM)
             .align_y(iced::Alignment::Center);
 
-            let hue_bar = Canvas::new(HueBar {
-                current_hue: picker.hue,
-            })
-            .width(280)
-            .height(25);
+            let hue_bar = labeled(
+                Canvas::new(HueBar {
+                    current_hue: picker.hue,
+                })
+                .width(280)
+                .height(25),
+                "Hue",
+            );
 
             column![sl_box, saturation_slider, lightness_slider, hue_bar,]
                 .spacing(SPACE_MD)
                 .into()
         }
+        PickerMode::Hsv => {
+            let sv_box = labeled(
+                Canvas::new(SaturationValueBox {
+                    hue: picker.hue,
+                    saturation: picker.hsv_s,
+                    value: picker.hsv_v,
+                })
+                .width(280)
+                .height(200),
+                "Saturation and value",
+            );
+
+            let saturation_slider = row![
+                text("S")
+                    .size(12)
+                    .color(TEXT_SECONDARY)
+                    .width(Length::Fixed(20.0)),
+                slider(0.0..=1.0, picker.hsv_s, Message::PickerHsvSaturationChanged)
+                    .step(0.01)
+                    .on_release(Message::PickerAdjustmentEnded)
+                    .width(Length::Fill),
+            ]
+            .spacing(SPACE_SM)
+            .align_y(iced::Alignment::Center);
+
+            let value_slider = row![
+                text("V")
+                    .size(12)
+                    .color(TEXT_SECONDARY)
+                    .width(Length::Fixed(20.0)),
+                slider(0.0..=1.0, picker.hsv_v, Message::PickerHsvValueChanged)
+                    .step(0.01)
+                    .on_release(Message::PickerAdjustmentEnded)
+                    .width(Length::Fill),
+            ]
+            .spacing(SPACE_SM)
+            .align_y(iced::Alignment::Center);
+
+            let hue_bar = labeled(
+                Canvas::new(HueBar {
+                    current_hue: picker.hue,
+                })
+                .width(280)
+                .height(25),
+                "Hue",
+            );
+
+            column![sv_box, saturation_slider, value_slider, hue_bar]
+                .spacing(SPACE_MD)
+                .into()
+        }
         PickerMode::Oklch => {
-            let cl_box = Canvas::new(ChromaLightnessBox {
-                hue: picker.oklch_h,
-                chroma: picker.oklch_c,
-                lightness: picker.oklch_l,
-                max_chroma: 0.4,
-            })
-            .width(280)
-            .height(200);
+            let cl_box = labeled(
+                Canvas::new(ChromaLightnessBox {
+                    hue: picker.oklch_h,
+                    chroma: picker.oklch_c,
+                    lightness: picker.oklch_l,
+                    max_chroma: 0.4,
+                })
+                .width(280)
+                .height(200),
+                "Chroma and lightness",
+            );
 
             let lightness_slider = row![
                 text("L")
@@ -297,6 +655,7 @@ pub fn view_color_picker_modal(picker: &ColorPickerState) -> Element<'_, Message
                     .width(Length::Fixed(20.0)),
                 slider(0.0..=1.0, picker.oklch_l, Message::PickerOklchLChanged)
                     .step(0.01)
+                    .on_release(Message::PickerAdjustmentEnded)
                     .width(Length::Fill),
             ]
             .spacing(SPACE_SM)
@@ -309,6 +668,7 @@ pub fn view_color_picker_modal(picker: &ColorPickerState) -> Element<'_, Message
                     .width(Length::Fixed(20.0)),
                 slider(0.0..=0.4, picker.oklch_c, Message::PickerOklchCChanged)
                     .step(0.005)
+                    .on_release(Message::PickerAdjustmentEnded)
                     .width(Length::Fill),
             ]
             .spacing(SPACE_SM)
@@ -321,32 +681,149 @@ pub fn view_color_picker_modal(picker: &ColorPickerState) -> Element<'_, Message
                     .width(Length::Fixed(20.0)),
                 slider(0.0..=360.0, picker.oklch_h, Message::PickerOklchHChanged)
                     .step(1.0)
+                    .on_release(Message::PickerAdjustmentEnded)
                     .width(Length::Fill),
             ]
             .spacing(SPACE_SM)
             .align_y(iced::Alignment::Center);
 
-            column![cl_box, lightness_slider, chroma_slider, hue_slider,]
-                .spacing(SPACE_MD)
-                .into()
+            // Colors outside sRGB are clamped when saved; say which gamut they need
+            let gamut_note: Element<'_, Message> =
+                match oklch_gamut(picker.oklch_l, picker.oklch_c, picker.oklch_h) {
+                    Gamut::Srgb => column![].into(),
+                    Gamut::DisplayP3 => row![
+                        icons::warning().size(12).color(TEXT_SECONDARY),
+                        text("Outside sRGB · inside Display P3")
+                            .size(11)
+                            .color(TEXT_SECONDARY),
+                    ]
+                    .spacing(SPACE_XS)
+                    .align_y(iced::Alignment::Center)
+                    .into(),
+                    Gamut::Wider => row![
+                        icons::warning().size(12).color(TEXT_MUTED),
+                        text("Outside Display P3").size(11).color(TEXT_MUTED),
+                    ]
+                    .spacing(SPACE_XS)
+                    .align_y(iced::Alignment::Center)
+                    .into(),
+                };
+
+            column![
+                cl_box,
+                gamut_note,
+                lightness_slider,
+                chroma_slider,
+                hue_slider,
+            ]
+            .spacing(SPACE_MD)
+            .into()
+        }
+        PickerMode::Rgb => {
+            // A slider and a number field per channel
+            let channel_row = |channel: usize, name: &'static str| {
+                let value = picker.rgb[channel];
+                row![
+                    text(name)
+                        .size(12)
+                        .color(TEXT_SECONDARY)
+                        .width(Length::Fixed(20.0)),
+                    slider(0..=255, value, move |value| Message::PickerRgbChanged(
+                        channel, value
+                    ))
+                    .on_release(Message::PickerAdjustmentEnded)
+                    .width(Length::Fill),
+                    text_input("0", &value.to_string())
+                        .on_input(move |input| Message::PickerRgbInputChanged(channel, input))
+                        .size(12)
+                        .padding(SPACE_XS)
+                        .width(Length::Fixed(48.0))
+                        .style(|theme, status| input_style(theme, status, false)),
+                ]
+                .spacing(SPACE_SM)
+                .align_y(iced::Alignment::Center)
+            };
+
+            column![
+                channel_row(0, "R"),
+                channel_row(1, "G"),
+                channel_row(2, "B"),
+            ]
+            .spacing(SPACE_MD)
+            .into()
         }
     };
 
-    // Alpha bar
-    let alpha_bar = Canvas::new(AlphaBar {
-        color: {
-            let (r, g, b) = picker.to_rgb();
-            iced::Color::from_rgb8(r, g, b)
-        },
-        alpha: picker.alpha,
-    })
-    .width(280)
-    .height(25);
+    // Quick tonal variants (hue-preserving in OKLCH)
+    let tone_button = |label: &'static str, delta_l: f32, delta_c: f32| {
+        button(text(label).size(11))
+            .on_press(Message::PickerAdjustOklch(delta_l, delta_c))
+            .padding([SPACE_XS, SPACE_SM])
+            .style(secondary_button_style)
+    };
+    let tone_row = row![
+        text("Tone").size(12).color(TEXT_SECONDARY),
+        iced::widget::Space::new().width(Length::Fill),
+        tone_button("L −5%", -OKLCH_LIGHTNESS_STEP, 0.0),
+        tone_button("L +5%", OKLCH_LIGHTNESS_STEP, 0.0),
+        tone_button("C −5%", 0.0, -OKLCH_CHROMA_STEP),
+        tone_button("C +5%", 0.0, OKLCH_CHROMA_STEP),
+    ]
+    .spacing(SPACE_XS)
+    .align_y(iced::Alignment::Center);
 
-    // Label input
+    // Temperature: the OKLCH hue turned toward orange or blue
+    let temperature_button = |adjustment: ColorAdjustment| {
+        button(text(adjustment.display_name()).size(11))
+            .on_press(Message::PickerAdjustColor(adjustment))
+            .padding([SPACE_XS, SPACE_SM])
+            .style(secondary_button_style)
+    };
+    let temperature_row = row![
+        text("Temperature").size(12).color(TEXT_SECONDARY),
+        iced::widget::Space::new().width(Length::Fill),
+        temperature_button(ColorAdjustment::Cooler),
+        temperature_button(ColorAdjustment::Warmer),
+    ]
+    .spacing(SPACE_XS)
+    .align_y(iced::Alignment::Center);
+
+    // Alpha bar
+    let alpha_bar = labeled(
+        Canvas::new(AlphaBar {
+            color: {
+                let (r, g, b) = picker.to_rgb();
+                iced::Color::from_rgb8(r, g, b)
+            },
+            alpha: picker.alpha,
+        })
+        .width(280)
+        .height(25),
+        "Opacity",
+    );
+
+    // Label input, suggesting the nearest named color
+    let (nearest, distance) = nearest_named_color(&picker.to_color_data());
+    let suggestion = row![
+        text(if distance < 0.5 {
+            format!("Named color: {}", nearest)
+        } else {
+            format!("Nearest named color: {} (ΔE {:.1})", nearest, distance)
+        })
+        .size(11)
+        .color(TEXT_MUTED),
+        iced::widget::Space::new().width(Length::Fill),
+        button(text("Use").size(11))
+            .on_press_maybe(
+                (picker.label != nearest).then(|| Message::PickerLabelChanged(nearest.to_string()))
+            )
+            .padding([2.0, SPACE_SM])
+            .style(subtle_button_style),
+    ]
+    .align_y(iced::Alignment::Center);
     let label_input = row![
         text("Label:").size(12).color(TEXT_SECONDARY),
-        text_input("Color label...", &picker.label)
+        text_input(nearest, &picker.label)
             .on_input(Message::PickerLabelChanged)
             .padding(SPACE_SM)
             .width(Length::Fill)
@@ -362,7 +839,9 @@ pub fn view_color_picker_modal(picker: &ColorPickerState) -> Element<'_, Message
         .style(secondary_button_style);
 
     let confirm_btn = button(
-        text(if picker.editing_id.is_some() {
+        text(if picker.gradient_stop.is_some() {
+            "Apply"
+        } else if picker.editing_id.is_some() {
             "Save"
         } else {
             "Add"
@@ -389,17 +868,70 @@ pub fn view_color_picker_modal(picker: &ColorPickerState) -> Element<'_, Message
         };
 
     // Modal content
-    let modal_content = column![
+    let mut modal_content = column![
         header_row,
         preview_row,
         controls,
-        alpha_bar,
-        label_input,
-        action_buttons,
-    ]
-    .spacing(SPACE_MD)
-    .padding(SPACE_MD)
-    .width(Length::Fixed(320.0));
+        column![tone_row, temperature_row].spacing(SPACE_XS),
+        alpha_bar
+    ];
+    // Gradient stops have no label
+    if picker.gradient_stop.is_none() {
+        modal_content = modal_content.push(column![label_input, suggestion].spacing(SPACE_XS));
+        modal_content = modal_content.push(view_description_input(
+            &picker.description,
+            Message::PickerDescriptionChanged,
+        ));
+    }
+    if !copy_templates.is_empty() {
+        modal_content = modal_content.push(
+            row(copy_templates.iter().enumerate().map(|(index, template)| {
+                button(row![icons::copy().size(11), text(&template.name).size(11)].spacing(4))
+                    .on_press(Message::CopyPickerTemplate(index))
+                    .padding([SPACE_XS, SPACE_SM])
+                    .style(subtle_button_style)
+                    .into()
+            }))
+            .spacing(SPACE_XS)
+            .wrap(),
+        );
+    }
+    if !recent_colors.is_empty() {
+        let swatches = recent_colors.iter().enumerate().map(|(index, color)| {
+            labeled(
+                button(
+                    Canvas::new(ColorSwatch {
+                        color: color.to_iced_color(),
+                    })
+                    .width(20)
+                    .height(20),
+                )
+                .on_press(Message::PickerRecentColorSelected(index))
+                .padding(2)
+                .style(subtle_button_style),
+                color.to_hex(),
+            )
+        });
+        modal_content = modal_content.push(
+            row![
+                text("Recent").size(12).color(TEXT_SECONDARY),
+                row(swatches).spacing(2).wrap()
+            ]
+            .spacing(SPACE_SM)
+            .align_y(iced::Alignment::Center),
+        );
+    }
+    let modal_content = modal_content
+        .push(action_buttons)
+        .spacing(SPACE_MD)
+        .padding(SPACE_MD)
+        .width(Length::Fixed(320.0));
+
+    // The tour covers the controls it explains
+    let modal_content: Element<'_, Message> = match picker.tour_step {
+        Some(step) => stack![modal_content, view_tour_step(step)].into(),
+        None => modal_content.into(),
+    };
 
     let modal_dialog = container(modal_content).style(modal_dialog_style);
 
@@ -416,3 +948,61 @@ pub fn view_color_picker_modal(picker: &ColorPickerState) -> Element<'_, Message
 
     overlay.into()
 }
+
+/// Render one step of the picker tour over the dimmed picker.
+fn view_tour_step<'a>(step: usize) -> Element<'a, Message> {
+    let Some((title, body)) = TOUR_STEPS.get(step) else {
+        return iced::widget::Space::new().into();
+    };
+    let is_last = step + 1 == TOUR_STEPS.len();
+
+    let header = row![
+        text(*title).size(16).color(TEXT_PRIMARY),
+        iced::widget::Space::new().width(Length::Fill),
+        text(format!("{} / {}", step + 1, TOUR_STEPS.len()))
+            .size(11)
+            .color(TEXT_MUTED),
+    ]
+    .align_y(iced::Alignment::Center);
+
+    let navigation = row![
+        button(text("Skip").size(12))
+            .on_press(Message::PickerTourStep(None))
+            .padding([SPACE_XS, SPACE_SM])
+            .style(subtle_button_style),
+        iced::widget::Space::new().width(Length::Fill),
+        button(text("Back").size(12))
+            .on_press_maybe(
+                step.checked_sub(1)
+                    .map(|prev| Message::PickerTourStep(Some(prev)))
+            )
+            .padding([SPACE_XS, SPACE_SM])
+            .style(secondary_button_style),
+        button(text(if is_last { "Done" } else { "Next" }).size(12))
+            .on_press(Message::PickerTourStep((!is_last).then_some(step + 1)))
+            .padding([SPACE_XS, SPACE_SM])
+            .style(primary_button_style),
+    ]
+    .spacing(SPACE_SM);
+
+    let card = container(
+        column![
+            header,
+            text(*body).size(12).color(TEXT_SECONDARY),
+            navigation
+        ]
+        .spacing(SPACE_SM),
+    )
+    .padding(SPACE_MD)
+    .style(modal_dialog_style);
+
+    // Dim the picker and keep it from taking clicks while the tour is open
+    opaque(
+        container(card)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .padding(SPACE_MD)
+            .center_y(Length::Fill)
+            .style(modal_overlay_style),
+    )
+}
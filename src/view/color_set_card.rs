@@ -0,0 +1,148 @@
+//! Color set card view component.
+
+use iced::widget::{button, column, container, row, text, Canvas};
+use iced::{Element, Length};
+
+use crate::config::CardActions;
+use crate::icons;
+use crate::message::Message;
+use crate::snippet::ColorSetData;
+use crate::theme::{
+    card_style, danger_button_style, subtle_button_style, BG_SURFACE, SPACE_MD, SPACE_SM, SPACE_XS,
+    TEXT_MUTED, TEXT_SECONDARY,
+};
+use crate::view::{card_with_actions, labeled, view_age, view_card_header, ActionSet, CardStats};
+use crate::widgets::ColorSwatch;
+
+/// Side of a swatch in the card's strip.
+const STRIP_SWATCH_SIZE: f32 = 36.0;
+
+/// Render a color set card; each swatch in the strip copies its color.
+pub fn view_color_set_card<'a>(
+    id: i64,
+    label: &'a str,
+    set: &'a ColorSetData,
+    stats: CardStats<'a>,
+    is_selected: bool,
+    visibility: CardActions,
+) -> Element<'a, Message> {
+    card_with_actions(visibility, |actions| {
+        build_color_set_card(id, label, set, stats, is_selected, actions)
+    })
+}
+
+/// Render the colors of a set side by side, without interaction.
+pub fn view_swatch_strip(set: &ColorSetData, size: f32) -> Element<'_, Message> {
+    row(set.colors.iter().map(|color| {
+        Canvas::new(ColorSwatch {
+            color: color.to_iced_color(),
+        })
+        .width(size)
+        .height(size)
+        .into()
+    }))
+    .spacing(2)
+    .into()
+}
+
+/// Build a color set card with the given set of action buttons.
+fn build_color_set_card<'a>(
+    id: i64,
+    label: &'a str,
+    set: &'a ColorSetData,
+    stats: CardStats<'a>,
+    is_selected: bool,
+    actions: ActionSet,
+) -> Element<'a, Message> {
+    // Swatch strip, one copy button per color
+    let strip = row(set.colors.iter().enumerate().map(|(index, color)| {
+        labeled(
+            button(
+                Canvas::new(ColorSwatch {
+                    color: color.to_iced_color(),
+                })
+                .width(STRIP_SWATCH_SIZE)
+                .height(STRIP_SWATCH_SIZE),
+            )
+            .on_press(Message::CopySetColor(id, index))
+            .padding(0)
+            .style(subtle_button_style),
+            color.to_hex(),
+        )
+    }))
+    .spacing(2);
+    let strip = container(strip)
+        .padding(SPACE_XS)
+        .style(|_theme| iced::widget::container::Style::default().background(BG_SURFACE));
+
+    // Header row: label + color count
+    let header_row = row![
+        text(label).size(14).color(TEXT_SECONDARY),
+        text(format!("{} colors", set.colors.len()))
+            .size(10)
+            .color(TEXT_MUTED),
+        view_age(stats)
+    ]
+    .spacing(SPACE_SM)
+    .align_y(iced::Alignment::Center);
+
+    let info_column = column![view_card_header(header_row, stats), strip]
+        .spacing(SPACE_XS)
+        .width(Length::Fill);
+
+    // Action buttons
+    let mut action_row = row![].spacing(SPACE_XS).align_y(iced::Alignment::Center);
+    if actions.shows_copy() {
+        // Copies all colors as a hex list
+        action_row = action_row.push(labeled(
+            button(icons::copy().size(14))
+                .on_press(Message::CopySnippet(id))
+                .padding([SPACE_XS, SPACE_SM])
+                .style(subtle_button_style),
+            "Copy all",
+        ));
+    }
+    if actions == ActionSet::All {
+        action_row = action_row.push(
+            row![
+                labeled(
+                    button(icons::push_pin().size(14))
+                        .on_press(Message::PinSnippet(id))
+                        .padding([SPACE_XS, SPACE_SM])
+                        .style(subtle_button_style),
+                    "Pin in a window",
+                ),
+                labeled(
+                    button(icons::arrows_split().size(14))
+                        .on_press(Message::SplitColorSet(id))
+                        .padding([SPACE_XS, SPACE_SM])
+                        .style(subtle_button_style),
+                    "Split into colors",
+                ),
+                labeled(
+                    button(icons::trash().size(14))
+                        .on_press(Message::DeleteSnippet(id))
+                        .padding([SPACE_XS, SPACE_SM])
+                        .style(danger_button_style),
+                    "Move to trash",
+                ),
+            ]
+            .spacing(SPACE_XS),
+        );
+    }
+
+    let card = row![info_column, action_row]
+        .spacing(SPACE_MD)
+        .padding(SPACE_MD)
+        .align_y(iced::Alignment::Center);
+
+    let card_container = container(card)
+        .style(move |theme| card_style(theme, is_selected))
+        .width(Length::Fill);
+
+    button(card_container)
+        .on_press(Message::SelectSnippet(Some(id)))
+        .style(|_theme, _status| button::Style::default())
+        .padding(0)
+        .into()
+}
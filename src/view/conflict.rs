@@ -0,0 +1,104 @@
+//! Dialog for resolving an edit that collided with a newer stored version.
+
+use iced::widget::{button, column, container, mouse_area, opaque, row, scrollable, text};
+use iced::{Color, Element, Font, Length};
+
+use crate::db::SaveConflict;
+use crate::message::Message;
+use crate::snippet::{diff_lines, DiffLine};
+use crate::theme::{
+    modal_dialog_style, modal_overlay_style, primary_button_style, scrollbar_style,
+    secondary_button_style, BG_SURFACE, DANGER, SPACE_MD, SPACE_SM, SPACE_XS, SUCCESS, TEXT_MUTED,
+    TEXT_PRIMARY, TEXT_SECONDARY,
+};
+
+/// Render the save conflict dialog, diffing the stored version against the edit.
+pub fn view_conflict_modal(conflict: &SaveConflict) -> Element<'_, Message> {
+    let stored = &conflict.stored;
+    let edited = &conflict.edited;
+
+    let header = column![
+        text("Edit Conflict").size(20).color(TEXT_PRIMARY),
+        text(format!(
+            "\"{}\" was changed elsewhere while you were editing it.",
+            stored.label
+        ))
+        .size(12)
+        .color(TEXT_SECONDARY),
+        text("− stored version   + your edit")
+            .size(11)
+            .color(TEXT_MUTED),
+    ]
+    .spacing(SPACE_XS);
+
+    let mut lines: Vec<Element<'_, Message>> = Vec::new();
+    if stored.label != edited.label {
+        lines.push(diff_row(DiffLine::Removed(&stored.label), "label "));
+        lines.push(diff_row(DiffLine::Added(&edited.label), "label "));
+    }
+    let stored_text = stored.content.to_copyable_string();
+    let edited_text = edited.content.to_copyable_string();
+    lines.extend(
+        diff_lines(&stored_text, &edited_text)
+            .into_iter()
+            .map(|line| diff_row(line, "")),
+    );
+
+    let diff_view = container(
+        scrollable(column(lines).padding(SPACE_SM))
+            .height(Length::Fixed(280.0))
+            .style(scrollbar_style),
+    )
+    .width(Length::Fill)
+    .style(|_theme| iced::widget::container::Style::default().background(BG_SURFACE));
+
+    let action_buttons = row![
+        button(text("Keep Stored").size(14))
+            .on_press(Message::KeepStoredVersion)
+            .padding(SPACE_SM)
+            .style(secondary_button_style),
+        button(text("Merge in Editor").size(14))
+            .on_press(Message::MergeConflictInEditor)
+            .padding(SPACE_SM)
+            .style(secondary_button_style),
+        iced::widget::Space::new().width(Length::Fill),
+        button(text("Overwrite with Mine").size(14))
+            .on_press(Message::OverwriteStoredVersion)
+            .padding(SPACE_SM)
+            .style(primary_button_style),
+    ]
+    .spacing(SPACE_SM);
+
+    let modal_content = column![header, diff_view, action_buttons]
+        .spacing(SPACE_MD)
+        .padding(SPACE_MD)
+        .width(Length::Fixed(560.0));
+
+    let modal_dialog = container(modal_content).style(modal_dialog_style);
+
+    // Semi-transparent overlay
+    mouse_area(
+        container(opaque(modal_dialog))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x(Length::Fill)
+            .center_y(Length::Fill)
+            .style(modal_overlay_style),
+    )
+    .on_press(Message::KeepStoredVersion)
+    .into()
+}
+
+/// Render one diff line with its marker, colored by side.
+fn diff_row<'a>(line: DiffLine<'_>, prefix: &str) -> Element<'a, Message> {
+    let (marker, content, color): (&str, &str, Color) = match line {
+        DiffLine::Same(content) => (" ", content, TEXT_MUTED),
+        DiffLine::Removed(content) => ("−", content, DANGER),
+        DiffLine::Added(content) => ("+", content, SUCCESS),
+    };
+    text(format!("{} {}{}", marker, prefix, content))
+        .size(12)
+        .font(Font::MONOSPACE)
+        .color(color)
+        .into()
+}
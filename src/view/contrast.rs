@@ -0,0 +1,251 @@
+//! Contrast checker: the WCAG contrast ratio of a color snippet on another
+//! color snippet or on a custom background.
+
+use iced::widget::{
+    button, column, container, mouse_area, opaque, pick_list, row, text, text_input,
+};
+use iced::{Element, Length};
+
+use crate::icons;
+use crate::message::Message;
+use crate::snippet::{ColorData, Snippet, SnippetContent};
+use crate::theme::{
+    input_style, modal_dialog_style, modal_overlay_style, pick_list_style, secondary_button_style,
+    subtle_button_style, BORDER_SUBTLE, DANGER, SPACE_MD, SPACE_SM, SPACE_XS, SUCCESS, TEXT_MUTED,
+    TEXT_PRIMARY, TEXT_SECONDARY,
+};
+use crate::view::labeled;
+
+/// WCAG 2 success criteria, as (name, minimum contrast ratio).
+const CRITERIA: [(&str, f32); 4] = [
+    ("AA", 4.5),
+    ("AA Large", 3.0),
+    ("AAA", 7.0),
+    ("AAA Large", 4.5),
+];
+
+/// State of the contrast checker.
+#[derive(Debug, Clone)]
+pub struct ContrastState {
+    /// Color snippet checked as the text color.
+    pub foreground_id: i64,
+    /// Color snippet used as the background; `None` uses `custom_background`.
+    pub background_id: Option<i64>,
+    /// Background typed in, in any format `ColorData::parse` understands.
+    pub custom_background: String,
+}
+
+impl ContrastState {
+    /// Check a color snippet against a white background.
+    pub fn new(foreground_id: i64) -> Self {
+        Self {
+            foreground_id,
+            background_id: None,
+            custom_background: "#FFFFFF".to_string(),
+        }
+    }
+}
+
+/// A color snippet in the background dropdown.
+#[derive(Debug, Clone, PartialEq)]
+struct BackgroundChoice {
+    id: i64,
+    label: String,
+}
+
+impl std::fmt::Display for BackgroundChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.label)
+    }
+}
+
+/// Get the color of a color snippet.
+fn snippet_color(snippets: &[Snippet], id: i64) -> Option<&ColorData> {
+    snippets
+        .iter()
+        .find(|s| s.id == id)
+        .and_then(|s| match &s.content {
+            SnippetContent::Color(color) => Some(color),
+            _ => None,
+        })
+}
+
+/// Render the contrast checker.
+pub fn view_contrast_modal<'a>(
+    state: &'a ContrastState,
+    snippets: &'a [Snippet],
+) -> Element<'a, Message> {
+    let foreground = snippet_color(snippets, state.foreground_id);
+    let background = match state.background_id {
+        Some(id) => snippet_color(snippets, id).cloned(),
+        None => ColorData::parse(&state.custom_background).ok(),
+    };
+
+    let header_row = row![
+        text("Check Contrast").size(20).color(TEXT_PRIMARY),
+        iced::widget::Space::new().width(Length::Fill),
+        labeled(
+            button(icons::x().size(16))
+                .on_press(Message::CloseContrastChecker)
+                .padding([SPACE_XS, SPACE_SM])
+                .style(subtle_button_style),
+            "Close"
+        ),
+    ]
+    .spacing(SPACE_XS)
+    .align_y(iced::Alignment::Center);
+
+    let foreground_label = snippets
+        .iter()
+        .find(|s| s.id == state.foreground_id)
+        .map_or("", |s| s.label.as_str());
+    let foreground_row = row![
+        text("Text").size(12).color(TEXT_SECONDARY).width(80),
+        text(foreground_label).size(13).color(TEXT_PRIMARY),
+        text(foreground.map(ColorData::to_hex).unwrap_or_default())
+            .size(12)
+            .color(TEXT_MUTED),
+    ]
+    .spacing(SPACE_SM)
+    .align_y(iced::Alignment::Center);
+
+    let choices: Vec<BackgroundChoice> = snippets
+        .iter()
+        .filter(|s| s.id != state.foreground_id && matches!(s.content, SnippetContent::Color(_)))
+        .map(|s| BackgroundChoice {
+            id: s.id,
+            label: s.label.clone(),
+        })
+        .collect();
+    let selected = state
+        .background_id
+        .and_then(|id| choices.iter().find(|choice| choice.id == id).cloned());
+    let custom_invalid =
+        state.background_id.is_none() && ColorData::parse(&state.custom_background).is_err();
+    let background_row = row![
+        text("Background").size(12).color(TEXT_SECONDARY).width(80),
+        pick_list(choices, selected, |choice: BackgroundChoice| {
+            Message::ContrastBackgroundSelected(choice.id)
+        })
+        .placeholder("Color snippet")
+        .text_size(12)
+        .padding([SPACE_XS, SPACE_SM])
+        .style(pick_list_style),
+        text_input("#FFFFFF", &state.custom_background)
+            .on_input(Message::ContrastCustomBackgroundChanged)
+            .size(12)
+            .padding([SPACE_XS, SPACE_SM])
+            .width(Length::Fixed(120.0))
+            .style(move |theme, status| input_style(theme, status, custom_invalid)),
+        labeled(
+            button(icons::arrows_down_up().size(14))
+                .on_press_maybe(state.background_id.map(|_| Message::SwapContrastColors))
+                .padding([SPACE_XS, SPACE_SM])
+                .style(secondary_button_style),
+            "Swap text and background"
+        ),
+    ]
+    .spacing(SPACE_SM)
+    .align_y(iced::Alignment::Center);
+
+    let result: Element<'a, Message> = match (foreground, background) {
+        (Some(foreground), Some(background)) => view_result(foreground, &background),
+        (None, _) => text("The color snippet no longer exists")
+            .size(12)
+            .color(TEXT_MUTED)
+            .into(),
+        (_, None) => text("Enter a background color, e.g. #1A1A1A or rgb(26, 26, 26)")
+            .size(12)
+            .color(TEXT_MUTED)
+            .into(),
+    };
+
+    let modal_content = column![header_row, foreground_row, background_row, result]
+        .spacing(SPACE_MD)
+        .padding(SPACE_MD)
+        .width(Length::Fixed(460.0));
+
+    let modal_dialog = container(modal_content).style(modal_dialog_style);
+
+    // Semi-transparent overlay
+    mouse_area(
+        container(opaque(modal_dialog))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x(Length::Fill)
+            .center_y(Length::Fill)
+            .style(modal_overlay_style),
+    )
+    .on_press(Message::CloseContrastChecker)
+    .into()
+}
+
+/// Render a text sample on the background, the ratio, and a pass or fail
+/// badge per criterion.
+fn view_result(foreground: &ColorData, background: &ColorData) -> Element<'static, Message> {
+    // Truncated rather than rounded: a ratio of 4.499 must not show as 4.50
+    // and pass a 4.5 criterion
+    let ratio = (foreground.contrast_ratio(background) * 100.0).floor() / 100.0;
+    let text_color = foreground.over(background).to_iced_color();
+    let background_color = background.to_iced_color();
+
+    let sample = container(
+        column![
+            text("Large text sample").size(24).color(text_color),
+            text("Body text sample, the quick brown fox jumps over the lazy dog.")
+                .size(14)
+                .color(text_color),
+        ]
+        .spacing(SPACE_XS),
+    )
+    .padding(SPACE_MD)
+    .width(Length::Fill)
+    .style(move |_theme| {
+        container::Style::default()
+            .background(background_color)
+            .border(
+                iced::Border::default()
+                    .rounded(6.0)
+                    .width(1.0)
+                    .color(BORDER_SUBTLE),
+            )
+    });
+
+    let badges = row(CRITERIA.iter().map(|(name, minimum)| {
+        let (icon, color) = if ratio >= *minimum {
+            (icons::check(), SUCCESS)
+        } else {
+            (icons::x(), DANGER)
+        };
+        container(
+            row![
+                icon.size(12).color(color),
+                text(*name).size(12).color(color)
+            ]
+            .spacing(SPACE_XS)
+            .align_y(iced::Alignment::Center),
+        )
+        .padding([SPACE_XS, SPACE_SM])
+        .style(move |_theme| {
+            container::Style::default()
+                .border(iced::Border::default().rounded(4.0).width(1.0).color(color))
+        })
+        .into()
+    }))
+    .spacing(SPACE_SM);
+
+    column![
+        sample,
+        row![
+            text(format!("{:.2}:1", ratio)).size(20).color(TEXT_PRIMARY),
+            text("Large text is 24px, or 18.66px bold")
+                .size(11)
+                .color(TEXT_MUTED),
+        ]
+        .spacing(SPACE_MD)
+        .align_y(iced::Alignment::Center),
+        badges,
+    ]
+    .spacing(SPACE_SM)
+    .into()
+}
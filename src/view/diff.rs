@@ -0,0 +1,85 @@
+//! Side-by-side diff of two versions of a snippet's text.
+
+use iced::widget::{column, container, row, scrollable, text};
+use iced::{Color, Element, Font, Length};
+
+use crate::message::Message;
+use crate::snippet::{diff_lines, side_by_side, DiffLine};
+use crate::theme::{
+    scrollbar_style, BG_SURFACE, DANGER, SPACE_SM, SPACE_XS, SUCCESS, TEXT_MUTED, TEXT_PRIMARY,
+};
+
+/// Render the old text on the left and the new on the right, changed lines
+/// tinted and side by side with what replaced them.
+pub fn view_side_by_side_diff<'a>(old: &str, new: &str, height: f32) -> Element<'a, Message> {
+    let diff = diff_lines(old, new);
+    let (mut old_line, mut new_line) = (0, 0);
+    let rows = side_by_side(&diff).into_iter().map(|(left, right)| {
+        let left = left.map(|line| {
+            old_line += 1;
+            (old_line, line)
+        });
+        let right = right.map(|line| {
+            new_line += 1;
+            (new_line, line)
+        });
+        row![diff_cell(left), diff_cell(right)]
+            .spacing(SPACE_XS)
+            .into()
+    });
+
+    container(
+        scrollable(column(rows).padding(SPACE_SM))
+            .height(Length::Fixed(height))
+            .style(scrollbar_style),
+    )
+    .width(Length::Fill)
+    .style(|_theme| container::Style::default().background(BG_SURFACE))
+    .into()
+}
+
+/// Count the added and removed lines between two texts.
+pub fn diff_stats(old: &str, new: &str) -> (usize, usize) {
+    diff_lines(old, new)
+        .iter()
+        .fold((0, 0), |(added, removed), line| match line {
+            DiffLine::Added(_) => (added + 1, removed),
+            DiffLine::Removed(_) => (added, removed + 1),
+            DiffLine::Same(_) => (added, removed),
+        })
+}
+
+/// Render one side of a diff row with its line number; empty when the
+/// other side has more lines in this change.
+fn diff_cell<'a>(line: Option<(usize, DiffLine<'_>)>) -> Element<'a, Message> {
+    let Some((number, line)) = line else {
+        return container(text("")).width(Length::FillPortion(1)).into();
+    };
+    let (content, color, tint) = match line {
+        DiffLine::Same(content) => (content, TEXT_PRIMARY, None),
+        DiffLine::Removed(content) => (content, DANGER, Some(DANGER)),
+        DiffLine::Added(content) => (content, SUCCESS, Some(SUCCESS)),
+    };
+
+    container(
+        row![
+            text(number.to_string())
+                .size(12)
+                .font(Font::MONOSPACE)
+                .color(TEXT_MUTED)
+                .width(Length::Fixed(32.0))
+                .align_x(iced::alignment::Horizontal::Right),
+            text(content.to_string())
+                .size(12)
+                .font(Font::MONOSPACE)
+                .color(color),
+        ]
+        .spacing(SPACE_SM),
+    )
+    .width(Length::FillPortion(1))
+    .style(move |_theme| match tint {
+        Some(tint) => container::Style::default().background(Color { a: 0.12, ..tint }),
+        None => container::Style::default(),
+    })
+    .into()
+}
@@ -0,0 +1,87 @@
+//! Review of changes made in the external editor before they are saved.
+
+use iced::widget::{button, column, container, mouse_area, opaque, row, text};
+use iced::{Element, Length};
+
+use crate::message::Message;
+use crate::theme::{
+    modal_dialog_style, modal_overlay_style, primary_button_style, secondary_button_style, DANGER,
+    SPACE_MD, SPACE_SM, SPACE_XS, SUCCESS, TEXT_MUTED, TEXT_PRIMARY, TEXT_SECONDARY,
+};
+use crate::view::diff::{diff_stats, view_side_by_side_diff};
+
+/// An edit returned from the external editor, waiting to be saved or dropped.
+#[derive(Debug, Clone)]
+pub struct EditReviewState {
+    pub snippet_id: i64,
+    pub label: String,
+    /// Content as stored when the editor was opened.
+    pub stored: String,
+    pub edited: String,
+    pub is_code: bool,
+}
+
+/// Render the review dialog, the stored version next to the edit.
+pub fn view_edit_review_modal(state: &EditReviewState) -> Element<'_, Message> {
+    let (added, removed) = diff_stats(&state.stored, &state.edited);
+
+    let header = column![
+        text("Review Changes").size(20).color(TEXT_PRIMARY),
+        text(format!(
+            "\"{}\" was edited in the external editor.",
+            state.label
+        ))
+        .size(12)
+        .color(TEXT_SECONDARY),
+        row![
+            text("Stored")
+                .size(11)
+                .color(TEXT_MUTED)
+                .width(Length::Fill),
+            text("Edited")
+                .size(11)
+                .color(TEXT_MUTED)
+                .width(Length::Fill),
+            text(format!("+{}", added)).size(11).color(SUCCESS),
+            text(format!("−{}", removed)).size(11).color(DANGER),
+        ]
+        .spacing(SPACE_SM),
+    ]
+    .spacing(SPACE_XS);
+
+    let action_buttons = row![
+        button(text("Discard").size(14))
+            .on_press(Message::DiscardExternalEdit)
+            .padding(SPACE_SM)
+            .style(secondary_button_style),
+        iced::widget::Space::new().width(Length::Fill),
+        button(text("Save Changes").size(14))
+            .on_press(Message::SaveExternalEdit)
+            .padding(SPACE_SM)
+            .style(primary_button_style),
+    ]
+    .spacing(SPACE_SM);
+
+    let modal_content = column![
+        header,
+        view_side_by_side_diff(&state.stored, &state.edited, 320.0),
+        action_buttons
+    ]
+    .spacing(SPACE_MD)
+    .padding(SPACE_MD)
+    .width(Length::Fixed(720.0));
+
+    let modal_dialog = container(modal_content).style(modal_dialog_style);
+
+    // Semi-transparent overlay
+    mouse_area(
+        container(opaque(modal_dialog))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x(Length::Fill)
+            .center_y(Length::Fill)
+            .style(modal_overlay_style),
+    )
+    .on_press(Message::DiscardExternalEdit)
+    .into()
+}
@@ -0,0 +1,174 @@
+//! Passphrase dialogs for the encrypted database.
+
+use iced::widget::{button, column, container, mouse_area, opaque, row, text, text_input};
+use iced::{Element, Length};
+
+use crate::icons;
+use crate::message::Message;
+use crate::theme::{
+    input_style, modal_dialog_style, modal_overlay_style, primary_button_style,
+    secondary_button_style, subtle_button_style, DANGER, SPACE_MD, SPACE_SM, SPACE_XS, TEXT_MUTED,
+    TEXT_PRIMARY,
+};
+use crate::view::labeled;
+
+/// Shortest passphrase accepted when encrypting.
+pub const MIN_PASSPHRASE_LEN: usize = 8;
+
+/// State for the startup unlock prompt.
+#[derive(Debug, Clone, Default)]
+pub struct UnlockState {
+    pub passphrase: String,
+    pub error: Option<String>,
+    /// An unlock attempt is running.
+    pub busy: bool,
+}
+
+/// State for the encrypt database dialog.
+#[derive(Debug, Clone, Default)]
+pub struct EncryptState {
+    pub passphrase: String,
+    pub confirm: String,
+    pub error: Option<String>,
+    /// Encryption is running.
+    pub busy: bool,
+}
+
+impl EncryptState {
+    /// Check the passphrase fields, returning the problem if any.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.passphrase.chars().count() < MIN_PASSPHRASE_LEN {
+            Err(format!("Use at least {} characters", MIN_PASSPHRASE_LEN))
+        } else if self.passphrase != self.confirm {
+            Err("Passphrases don't match".to_string())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Render the passphrase prompt shown before an encrypted database is loaded.
+/// It cannot be dismissed; the library stays hidden until unlocked.
+pub fn view_unlock_modal(state: &UnlockState) -> Element<'_, Message> {
+    let passphrase_input = text_input("Passphrase", &state.passphrase)
+        .secure(true)
+        .on_input(Message::UnlockPassphraseChanged)
+        .on_submit(Message::SubmitUnlock)
+        .padding(SPACE_SM)
+        .size(14)
+        .style(|theme, status| input_style(theme, status, state.error.is_some()));
+
+    let mut content = column![
+        text("Unlock Shard").size(20).color(TEXT_PRIMARY),
+        text("Your snippet database is encrypted. Enter its passphrase to continue.")
+            .size(12)
+            .color(TEXT_MUTED),
+        passphrase_input,
+    ]
+    .spacing(SPACE_MD)
+    .padding(SPACE_MD)
+    .width(Length::Fixed(360.0));
+
+    if let Some(error) = &state.error {
+        content = content.push(text(error).size(12).color(DANGER));
+    }
+
+    content = content.push(
+        button(text(if state.busy { "Unlocking..." } else { "Unlock" }).size(14))
+            .on_press_maybe(
+                (!state.busy && !state.passphrase.is_empty()).then_some(Message::SubmitUnlock),
+            )
+            .padding(SPACE_SM)
+            .style(primary_button_style),
+    );
+
+    container(opaque(container(content).style(modal_dialog_style)))
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .center_x(Length::Fill)
+        .center_y(Length::Fill)
+        .style(modal_overlay_style)
+        .into()
+}
+
+/// Render the dialog for encrypting the plaintext database.
+pub fn view_encrypt_modal(state: &EncryptState) -> Element<'_, Message> {
+    let header_row = row![
+        text("Encrypt Database").size(20).color(TEXT_PRIMARY),
+        iced::widget::Space::new().width(Length::Fill),
+        labeled(
+            button(icons::x().size(16))
+                .on_press(Message::CloseEncryptDatabase)
+                .padding([SPACE_XS, SPACE_SM])
+                .style(subtle_button_style),
+            "Close"
+        ),
+    ]
+    .spacing(SPACE_XS)
+    .align_y(iced::Alignment::Center);
+
+    let hint = text(
+        "Shard will ask for this passphrase on every start. \
+         It cannot be recovered if you forget it. \
+         Existing backups are encrypted with it too.",
+    )
+    .size(12)
+    .color(TEXT_MUTED);
+
+    let passphrase_input = text_input("Passphrase", &state.passphrase)
+        .secure(true)
+        .on_input(Message::EncryptPassphraseChanged)
+        .padding(SPACE_SM)
+        .size(14)
+        .style(|theme, status| input_style(theme, status, false));
+
+    let confirm_input = text_input("Confirm passphrase", &state.confirm)
+        .secure(true)
+        .on_input(Message::EncryptConfirmChanged)
+        .on_submit(Message::SubmitEncrypt)
+        .padding(SPACE_SM)
+        .size(14)
+        .style(|theme, status| input_style(theme, status, state.error.is_some()));
+
+    let mut content = column![header_row, hint, passphrase_input, confirm_input]
+        .spacing(SPACE_MD)
+        .padding(SPACE_MD)
+        .width(Length::Fixed(400.0));
+
+    if let Some(error) = &state.error {
+        content = content.push(text(error).size(12).color(DANGER));
+    }
+
+    content = content.push(
+        row![
+            button(text("Cancel").size(14))
+                .on_press(Message::CloseEncryptDatabase)
+                .padding(SPACE_SM)
+                .style(secondary_button_style),
+            button(
+                text(if state.busy {
+                    "Encrypting..."
+                } else {
+                    "Encrypt"
+                })
+                .size(14)
+            )
+            .on_press_maybe((!state.busy).then_some(Message::SubmitEncrypt))
+            .padding(SPACE_SM)
+            .style(primary_button_style),
+        ]
+        .spacing(SPACE_SM),
+    );
+
+    // Semi-transparent overlay
+    mouse_area(
+        container(opaque(container(content).style(modal_dialog_style)))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x(Length::Fill)
+            .center_y(Length::Fill)
+            .style(modal_overlay_style),
+    )
+    .on_press(Message::CloseEncryptDatabase)
+    .into()
+}
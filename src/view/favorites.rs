@@ -0,0 +1,105 @@
+//! Favorites bar of starred snippets.
+
+use iced::widget::{button, container, mouse_area, row, text};
+use iced::{Element, Length};
+
+use crate::icons;
+use crate::message::Message;
+use crate::snippet::{Snippet, SnippetContent};
+use crate::theme::{
+    secondary_button_style, BORDER_SUBTLE, SPACE_MD, SPACE_SM, SPACE_XS, TEXT_MUTED,
+};
+
+/// Most snippets the favorites bar holds.
+pub const MAX_FAVORITES: usize = 10;
+
+/// Longest chip label before it is truncated.
+const CHIP_LABEL_LEN: usize = 16;
+
+/// Get the favorite snippets in the order they were added.
+pub fn favorite_snippets(snippets: &[Snippet]) -> Vec<&Snippet> {
+    let mut favorites: Vec<&Snippet> = snippets
+        .iter()
+        .filter(|s| s.favorite_at.is_some())
+        .collect();
+    favorites.sort_by_key(|s| (s.favorite_at, s.id));
+    favorites.truncate(MAX_FAVORITES);
+    favorites
+}
+
+/// Render the favorites bar, or nothing when there are no favorites.
+pub fn view_favorites_bar(snippets: &[Snippet]) -> Option<Element<'_, Message>> {
+    let favorites = favorite_snippets(snippets);
+    if favorites.is_empty() {
+        return None;
+    }
+
+    let chips: Vec<Element<'_, Message>> = favorites
+        .into_iter()
+        .map(|snippet| {
+            let marker: Element<'_, Message> = match (&snippet.content, &snippet.icon) {
+                (SnippetContent::Color(color), _) => {
+                    let color = color.to_iced_color();
+                    container(text(""))
+                        .width(Length::Fixed(12.0))
+                        .height(Length::Fixed(12.0))
+                        .style(move |_theme| {
+                            iced::widget::container::Style::default()
+                                .background(color)
+                                .border(
+                                    iced::Border::default()
+                                        .rounded(3.0)
+                                        .width(1.0)
+                                        .color(BORDER_SUBTLE),
+                                )
+                        })
+                        .into()
+                }
+                (_, Some(icon)) => icons::snippet_icon(icon).size(12).into(),
+                (SnippetContent::ColorSet(_), None) => icons::swatches().size(12).into(),
+                (SnippetContent::Gradient(_), None) => icons::gradient().size(12).into(),
+                (SnippetContent::Code(_), None) => icons::code().size(12).into(),
+                (SnippetContent::Json(_), None) => icons::brackets_curly().size(12).into(),
+                (SnippetContent::Text(_), None) => icons::text_icon().size(12).into(),
+                (SnippetContent::File(file), None) => icons::file_icon(file.extension().as_deref())
+                    .size(12)
+                    .into(),
+            };
+
+            let label: String = if snippet.label.chars().count() > CHIP_LABEL_LEN {
+                let truncated: String = snippet.label.chars().take(CHIP_LABEL_LEN - 1).collect();
+                format!("{}…", truncated)
+            } else {
+                snippet.label.clone()
+            };
+
+            let chip = button(
+                row![marker, text(label).size(11)]
+                    .spacing(SPACE_XS)
+                    .align_y(iced::Alignment::Center),
+            )
+            .on_press(Message::CopySnippet(snippet.id))
+            .padding([2.0, SPACE_SM])
+            .style(secondary_button_style);
+
+            // Right-click a chip for the same menu as its card
+            mouse_area(chip)
+                .on_right_press(Message::ToggleCardMenu(Some(snippet.id)))
+                .into()
+        })
+        .collect();
+
+    let bar = row![
+        icons::star().size(12).color(TEXT_MUTED),
+        row(chips).spacing(SPACE_XS)
+    ]
+    .spacing(SPACE_SM)
+    .align_y(iced::Alignment::Center);
+
+    Some(
+        container(bar)
+            .width(Length::Fill)
+            .padding([SPACE_XS, SPACE_MD])
+            .into(),
+    )
+}
@@ -0,0 +1,131 @@
+//! File snippet card view component.
+
+use iced::widget::{button, column, container, row, text};
+use iced::{Element, Length};
+
+use crate::config::CardActions;
+use crate::db::format_size;
+use crate::icons;
+use crate::message::Message;
+use crate::snippet::FileData;
+use crate::theme::{
+    card_style, danger_button_style, subtle_button_style, BG_SURFACE, SPACE_MD, SPACE_SM, SPACE_XS,
+    TEXT_MUTED, TEXT_SECONDARY,
+};
+use crate::view::{card_with_actions, labeled, view_age, view_card_header, ActionSet, CardStats};
+
+/// Render a file snippet card, showing actions per the visibility setting.
+pub fn view_file_card<'a>(
+    id: i64,
+    label: &'a str,
+    icon: Option<&'a str>,
+    file: &'a FileData,
+    stats: CardStats<'a>,
+    is_selected: bool,
+    visibility: CardActions,
+) -> Element<'a, Message> {
+    card_with_actions(visibility, |actions| {
+        build_file_card(id, label, icon, file, stats, is_selected, actions)
+    })
+}
+
+/// Build a file snippet card with the given set of action buttons.
+fn build_file_card<'a>(
+    id: i64,
+    label: &'a str,
+    icon: Option<&'a str>,
+    file: &'a FileData,
+    stats: CardStats<'a>,
+    is_selected: bool,
+    actions: ActionSet,
+) -> Element<'a, Message> {
+    // File type icon, or the snippet's custom icon (64x64 container)
+    let kind_icon = match icon {
+        Some(icon) => icons::snippet_icon(icon),
+        None => icons::file_icon(file.extension().as_deref()),
+    };
+    let file_icon = container(kind_icon.size(28).color(TEXT_SECONDARY))
+        .width(64)
+        .height(64)
+        .center_x(64)
+        .center_y(64)
+        .style(|_theme| iced::widget::container::Style::default().background(BG_SURFACE));
+
+    // Size badge
+    let size_badge = container(text(format_size(file.size)).size(10).color(TEXT_MUTED))
+        .padding([2, 6])
+        .style(|_theme| iced::widget::container::Style::default().background(BG_SURFACE));
+
+    // Header row: label + size badge
+    let header_row = row![
+        text(label).size(14).color(TEXT_SECONDARY),
+        size_badge,
+        view_age(stats)
+    ]
+    .spacing(SPACE_SM)
+    .align_y(iced::Alignment::Center);
+
+    // Original file name
+    let name_text = text(&file.name).size(11).color(TEXT_MUTED);
+
+    // Info column with header and file name
+    let info_column = column![view_card_header(header_row, stats), name_text]
+        .spacing(SPACE_XS)
+        .width(Length::Fill);
+
+    // Action buttons
+    let mut action_row = row![].spacing(SPACE_XS).align_y(iced::Alignment::Center);
+    if actions.shows_copy() {
+        // Copies the path of the stored file
+        action_row = action_row.push(labeled(
+            button(icons::copy().size(14))
+                .on_press(Message::CopySnippet(id))
+                .padding([SPACE_XS, SPACE_SM])
+                .style(subtle_button_style),
+            "Copy",
+        ));
+    }
+    if actions == ActionSet::All {
+        action_row = action_row.push(
+            row![
+                labeled(
+                    button(icons::folder_open().size(14))
+                        .on_press(Message::RevealSnippetFile(id))
+                        .padding([SPACE_XS, SPACE_SM])
+                        .style(subtle_button_style),
+                    "Show in file manager"
+                ),
+                labeled(
+                    button(icons::arrow_square_out().size(14))
+                        .on_press(Message::OpenInExternalEditor(id, false))
+                        .padding([SPACE_XS, SPACE_SM])
+                        .style(subtle_button_style),
+                    "Open"
+                ),
+                labeled(
+                    button(icons::trash().size(14))
+                        .on_press(Message::DeleteSnippet(id))
+                        .padding([SPACE_XS, SPACE_SM])
+                        .style(danger_button_style),
+                    "Move to trash"
+                ),
+            ]
+            .spacing(SPACE_XS),
+        );
+    }
+
+    let card = row![file_icon, info_column, action_row]
+        .spacing(SPACE_MD)
+        .padding(SPACE_MD)
+        .align_y(iced::Alignment::Center);
+
+    let card_container = container(card)
+        .style(move |theme| card_style(theme, is_selected))
+        .width(Length::Fill);
+
+    button(card_container)
+        .on_press(Message::SelectSnippet(Some(id)))
+        .style(|_theme, _status| button::Style::default())
+        .padding(0)
+        .into()
+}
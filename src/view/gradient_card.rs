@@ -0,0 +1,121 @@
+//! Gradient card view component.
+
+use iced::widget::{button, column, container, row, text, Canvas};
+use iced::{Element, Font, Length};
+
+use crate::config::CardActions;
+use crate::icons;
+use crate::message::Message;
+use crate::snippet::GradientData;
+use crate::theme::{
+    card_style, danger_button_style, subtle_button_style, SPACE_MD, SPACE_SM, SPACE_XS, TEXT_MUTED,
+    TEXT_SECONDARY,
+};
+use crate::view::{card_with_actions, labeled, view_age, view_card_header, ActionSet, CardStats};
+use crate::widgets::GradientPreview;
+
+/// Height of the gradient strip on the card.
+const STRIP_HEIGHT: f32 = 36.0;
+
+/// Render a gradient card.
+pub fn view_gradient_card<'a>(
+    id: i64,
+    label: &'a str,
+    gradient: &'a GradientData,
+    stats: CardStats<'a>,
+    is_selected: bool,
+    visibility: CardActions,
+) -> Element<'a, Message> {
+    card_with_actions(visibility, |actions| {
+        build_gradient_card(id, label, gradient, stats, is_selected, actions)
+    })
+}
+
+/// Build a gradient card with the given set of action buttons.
+fn build_gradient_card<'a>(
+    id: i64,
+    label: &'a str,
+    gradient: &'a GradientData,
+    stats: CardStats<'a>,
+    is_selected: bool,
+    actions: ActionSet,
+) -> Element<'a, Message> {
+    let strip = Canvas::new(GradientPreview { gradient })
+        .width(Length::Fill)
+        .height(STRIP_HEIGHT);
+
+    // Header row: label + stop count
+    let header_row = row![
+        text(label).size(14).color(TEXT_SECONDARY),
+        text(format!("{} stops", gradient.stops.len()))
+            .size(10)
+            .color(TEXT_MUTED),
+        view_age(stats)
+    ]
+    .spacing(SPACE_SM)
+    .align_y(iced::Alignment::Center);
+
+    let css_text = text(gradient.to_css())
+        .size(11)
+        .font(Font::MONOSPACE)
+        .color(TEXT_MUTED);
+
+    let info_column = column![view_card_header(header_row, stats), strip, css_text]
+        .spacing(SPACE_XS)
+        .width(Length::Fill);
+
+    // Action buttons
+    let mut action_row = row![].spacing(SPACE_XS).align_y(iced::Alignment::Center);
+    if actions.shows_copy() {
+        action_row = action_row.push(labeled(
+            button(icons::copy().size(14))
+                .on_press(Message::CopySnippet(id))
+                .padding([SPACE_XS, SPACE_SM])
+                .style(subtle_button_style),
+            "Copy CSS",
+        ));
+    }
+    if actions == ActionSet::All {
+        action_row = action_row.push(
+            row![
+                labeled(
+                    button(icons::pencil().size(14))
+                        .on_press(Message::OpenGradientEditor(Some(id)))
+                        .padding([SPACE_XS, SPACE_SM])
+                        .style(subtle_button_style),
+                    "Edit",
+                ),
+                labeled(
+                    button(icons::push_pin().size(14))
+                        .on_press(Message::PinSnippet(id))
+                        .padding([SPACE_XS, SPACE_SM])
+                        .style(subtle_button_style),
+                    "Pin in a window",
+                ),
+                labeled(
+                    button(icons::trash().size(14))
+                        .on_press(Message::DeleteSnippet(id))
+                        .padding([SPACE_XS, SPACE_SM])
+                        .style(danger_button_style),
+                    "Move to trash",
+                ),
+            ]
+            .spacing(SPACE_XS),
+        );
+    }
+
+    let card = row![info_column, action_row]
+        .spacing(SPACE_MD)
+        .padding(SPACE_MD)
+        .align_y(iced::Alignment::Center);
+
+    let card_container = container(card)
+        .style(move |theme| card_style(theme, is_selected))
+        .width(Length::Fill);
+
+    button(card_container)
+        .on_press(Message::SelectSnippet(Some(id)))
+        .style(|_theme, _status| button::Style::default())
+        .padding(0)
+        .into()
+}
@@ -0,0 +1,232 @@
+//! Dialog editing a linear gradient: stops on a bar, their colors in the
+//! color picker, and the angle, with the CSS to copy.
+
+use iced::widget::{
+    button, column, container, mouse_area, opaque, row, slider, text, text_input, Canvas,
+};
+use iced::{Element, Font, Length};
+
+use crate::icons;
+use crate::message::Message;
+use crate::snippet::{ColorData, GradientData, Snippet, SnippetContent};
+use crate::theme::{
+    input_style, modal_dialog_style, modal_overlay_style, primary_button_style,
+    secondary_button_style, subtle_button_style, BG_BASE, BORDER_SUBTLE, SPACE_MD, SPACE_SM,
+    SPACE_XS, TEXT_MUTED, TEXT_PRIMARY, TEXT_SECONDARY,
+};
+use crate::view::labeled;
+use crate::widgets::{ColorSwatch, GradientBar, GradientPreview};
+
+/// Width of the dialog's gradient bar and preview.
+const BAR_WIDTH: f32 = 440.0;
+
+/// State of the gradient editor.
+#[derive(Debug, Clone)]
+pub struct GradientEditorState {
+    pub gradient: GradientData,
+    /// Index of the selected stop.
+    pub selected: usize,
+    pub label: String,
+    /// The gradient snippet being edited; `None` saves a new one.
+    pub editing_id: Option<i64>,
+    /// Revision of the edited snippet when the editor opened.
+    pub revision: i64,
+    /// Description of the edited snippet, saved back unchanged.
+    pub description: Option<String>,
+}
+
+impl GradientEditorState {
+    /// Open the editor on a gradient between two colors.
+    pub fn new(from: ColorData, to: ColorData) -> Self {
+        Self {
+            gradient: GradientData::new(from, to),
+            selected: 0,
+            label: String::new(),
+            editing_id: None,
+            revision: 0,
+            description: None,
+        }
+    }
+
+    /// Open the editor on a gradient snippet.
+    pub fn from_snippet(snippet: &Snippet) -> Option<Self> {
+        let SnippetContent::Gradient(gradient) = &snippet.content else {
+            return None;
+        };
+        Some(Self {
+            gradient: gradient.clone(),
+            selected: 0,
+            label: snippet.label.clone(),
+            editing_id: Some(snippet.id),
+            revision: snippet.revision,
+            description: snippet.description.clone(),
+        })
+    }
+
+    /// The CSS declaration to paste into a stylesheet.
+    pub fn css(&self) -> String {
+        format!("background: {};", self.gradient.to_css())
+    }
+}
+
+/// Render the gradient editor.
+pub fn view_gradient_editor_modal(state: &GradientEditorState) -> Element<'_, Message> {
+    let header_row = row![
+        text("Gradient").size(20).color(TEXT_PRIMARY),
+        iced::widget::Space::new().width(Length::Fill),
+        labeled(
+            button(icons::x().size(16))
+                .on_press(Message::CloseGradientEditor)
+                .padding([SPACE_XS, SPACE_SM])
+                .style(subtle_button_style),
+            "Close"
+        ),
+    ]
+    .spacing(SPACE_XS)
+    .align_y(iced::Alignment::Center);
+
+    let preview = Canvas::new(GradientPreview {
+        gradient: &state.gradient,
+    })
+    .width(BAR_WIDTH)
+    .height(120);
+
+    let bar = labeled(
+        Canvas::new(GradientBar {
+            gradient: &state.gradient,
+            selected: state.selected,
+        })
+        .width(BAR_WIDTH)
+        .height(40),
+        "Gradient stops",
+    );
+    let hint = text("Click the bar to add a stop; drag a stop to move it")
+        .size(11)
+        .color(TEXT_MUTED);
+
+    // The selected stop: its color, position, and actions
+    let stop_row: Element<'_, Message> = match state.gradient.stops.get(state.selected) {
+        Some(stop) => row![
+            Canvas::new(ColorSwatch {
+                color: stop.color.to_iced_color(),
+            })
+            .width(24)
+            .height(24),
+            text(stop.color.to_hex()).size(13).color(TEXT_PRIMARY),
+            text(format!("at {}%", (stop.position * 100.0).round()))
+                .size(12)
+                .color(TEXT_SECONDARY),
+            iced::widget::Space::new().width(Length::Fill),
+            button(text("Edit Color…").size(12))
+                .on_press(Message::EditGradientStopColor)
+                .padding([SPACE_XS, SPACE_SM])
+                .style(secondary_button_style),
+            labeled(
+                button(icons::trash().size(14))
+                    .on_press_maybe(
+                        (state.gradient.stops.len() > 2).then_some(Message::GradientStopRemoved)
+                    )
+                    .padding([SPACE_XS, SPACE_SM])
+                    .style(subtle_button_style),
+                "Remove stop"
+            ),
+        ]
+        .spacing(SPACE_SM)
+        .align_y(iced::Alignment::Center)
+        .into(),
+        None => iced::widget::Space::new().into(),
+    };
+
+    let angle_row = row![
+        text("Angle").size(12).color(TEXT_SECONDARY).width(48),
+        slider(
+            0.0..=359.0,
+            state.gradient.angle,
+            Message::GradientAngleChanged
+        )
+        .step(1.0)
+        .width(Length::Fill),
+        text(format!("{}°", state.gradient.angle.round()))
+            .size(12)
+            .color(TEXT_PRIMARY)
+            .width(36),
+    ]
+    .spacing(SPACE_SM)
+    .align_y(iced::Alignment::Center);
+
+    let css = container(
+        text(state.css())
+            .size(12)
+            .font(Font::MONOSPACE)
+            .color(TEXT_PRIMARY),
+    )
+    .padding(SPACE_SM)
+    .width(Length::Fill)
+    .style(|_theme| {
+        container::Style::default().background(BG_BASE).border(
+            iced::Border::default()
+                .rounded(4.0)
+                .width(1.0)
+                .color(BORDER_SUBTLE),
+        )
+    });
+
+    let label_input = row![
+        text("Label:").size(12).color(TEXT_SECONDARY),
+        text_input("Gradient label...", &state.label)
+            .on_input(Message::GradientLabelChanged)
+            .padding(SPACE_SM)
+            .width(Length::Fill)
+            .style(|theme, status| input_style(theme, status, false)),
+    ]
+    .spacing(SPACE_SM)
+    .align_y(iced::Alignment::Center);
+
+    let action_buttons = row![
+        iced::widget::Space::new().width(Length::Fill),
+        button(text("Copy CSS").size(14))
+            .on_press(Message::CopyGradientCss)
+            .padding(SPACE_SM)
+            .style(secondary_button_style),
+        button(
+            text(if state.editing_id.is_some() {
+                "Save"
+            } else {
+                "Add"
+            })
+            .size(14)
+        )
+        .on_press(Message::SaveGradient)
+        .padding(SPACE_SM)
+        .style(primary_button_style),
+    ]
+    .spacing(SPACE_SM);
+
+    let modal_content = column![
+        header_row,
+        preview,
+        column![bar, hint].spacing(SPACE_XS),
+        stop_row,
+        angle_row,
+        css,
+        label_input,
+        action_buttons
+    ]
+    .spacing(SPACE_MD)
+    .padding(SPACE_MD)
+    .width(Length::Fixed(BAR_WIDTH + 2.0 * SPACE_MD));
+
+    let modal_dialog = container(modal_content).style(modal_dialog_style);
+
+    // Semi-transparent overlay
+    mouse_area(
+        container(opaque(modal_dialog))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x(Length::Fill)
+            .center_y(Length::Fill)
+            .style(modal_overlay_style),
+    )
+    .on_press(Message::CloseGradientEditor)
+    .into()
+}
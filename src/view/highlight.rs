@@ -0,0 +1,44 @@
+//! Syntax highlighting of code shown as text (card and hover previews).
+
+use iced::advanced::text::Highlighter as _;
+use iced::highlighter::{self, Highlighter};
+use iced::widget::span;
+use iced::widget::text::Span;
+use iced::Font;
+
+use crate::theme::TEXT_PRIMARY;
+
+/// Highlight the first lines of code as rich text spans.
+pub fn highlighted_spans<'a>(
+    code: &'a str,
+    extension: &str,
+    theme: highlighter::Theme,
+    max_lines: usize,
+) -> Vec<Span<'a, (), Font>> {
+    let mut highlighter = Highlighter::new(&highlighter::Settings {
+        theme,
+        token: extension.to_string(),
+    });
+
+    let mut spans: Vec<Span<'_, (), Font>> = Vec::new();
+    for (index, line) in code.lines().take(max_lines).enumerate() {
+        if index > 0 {
+            spans.push(span("\n"));
+        }
+        let mut end = 0;
+        for (range, highlight) in highlighter.highlight_line(line) {
+            if range.start < end || range.end > line.len() {
+                continue;
+            }
+            if range.start > end {
+                spans.push(span(&line[end..range.start]).color(TEXT_PRIMARY));
+            }
+            spans.push(span(&line[range.clone()]).color(highlight.color().unwrap_or(TEXT_PRIMARY)));
+            end = range.end;
+        }
+        if end < line.len() {
+            spans.push(span(&line[end..]).color(TEXT_PRIMARY));
+        }
+    }
+    spans
+}
@@ -0,0 +1,146 @@
+//! Dialog for choosing a snippet's custom icon.
+
+use iced::widget::{button, column, container, mouse_area, opaque, row, text, text_input};
+use iced::{Element, Length};
+
+use crate::icons;
+use crate::message::Message;
+use crate::snippet::{Snippet, PHOSPHOR_ICON_PREFIX};
+use crate::theme::{
+    input_style, modal_dialog_style, modal_overlay_style, primary_button_style,
+    secondary_button_style, subtle_button_style, SPACE_MD, SPACE_SM, SPACE_XS, TEXT_MUTED,
+    TEXT_PRIMARY, TEXT_SECONDARY,
+};
+use crate::view::labeled;
+
+/// Phosphor icons per row in the icon grid.
+const ICONS_PER_ROW: usize = 10;
+
+/// State for the icon picker dialog.
+#[derive(Debug, Clone)]
+pub struct IconPickerState {
+    pub snippet_id: i64,
+    /// Emoji typed into the input.
+    pub emoji: String,
+}
+
+impl IconPickerState {
+    /// Open the picker for a snippet, prefilled with its emoji icon.
+    pub fn new(snippet: &Snippet) -> Self {
+        let emoji = snippet
+            .icon
+            .as_deref()
+            .filter(|icon| !icon.starts_with(PHOSPHOR_ICON_PREFIX))
+            .unwrap_or_default()
+            .to_string();
+        Self {
+            snippet_id: snippet.id,
+            emoji,
+        }
+    }
+}
+
+/// Render the icon picker dialog.
+pub fn view_icon_picker_modal<'a>(
+    state: &'a IconPickerState,
+    current: Option<&'a str>,
+) -> Element<'a, Message> {
+    let snippet_id = state.snippet_id;
+
+    let header_row = row![
+        text("Snippet Icon").size(20).color(TEXT_PRIMARY),
+        iced::widget::Space::new().width(Length::Fill),
+        labeled(
+            button(icons::x().size(16))
+                .on_press(Message::CloseIconPicker)
+                .padding([SPACE_XS, SPACE_SM])
+                .style(subtle_button_style),
+            "Close"
+        ),
+    ]
+    .spacing(SPACE_XS)
+    .align_y(iced::Alignment::Center);
+
+    let icon_rows: Vec<Element<'a, Message>> = icons::SNIPPET_ICONS
+        .chunks(ICONS_PER_ROW)
+        .map(|chunk| {
+            row(chunk.iter().map(|(name, _)| {
+                let value = format!("{}{}", PHOSPHOR_ICON_PREFIX, name);
+                let is_current = current == Some(value.as_str());
+                button(icons::snippet_icon(&value).size(18))
+                    .on_press(Message::SetSnippetIcon(snippet_id, Some(value.clone())))
+                    .padding(SPACE_XS)
+                    .style(if is_current {
+                        primary_button_style
+                    } else {
+                        subtle_button_style
+                    })
+                    .into()
+            }))
+            .spacing(SPACE_XS)
+            .into()
+        })
+        .collect();
+
+    let emoji_row = row![
+        text_input("Emoji, e.g. 🚀", &state.emoji)
+            .on_input(Message::IconEmojiChanged)
+            .on_submit(Message::SubmitIconEmoji)
+            .padding(SPACE_SM)
+            .size(14)
+            .width(Length::Fill)
+            .style(|theme, status| input_style(theme, status, false)),
+        button(text("Use Emoji").size(12))
+            .on_press_maybe((!state.emoji.trim().is_empty()).then_some(Message::SubmitIconEmoji))
+            .padding(SPACE_SM)
+            .style(secondary_button_style),
+    ]
+    .spacing(SPACE_SM)
+    .align_y(iced::Alignment::Center);
+
+    let action_buttons = row![
+        button(text("Remove Icon").size(14))
+            .on_press_maybe(
+                current
+                    .is_some()
+                    .then_some(Message::SetSnippetIcon(snippet_id, None))
+            )
+            .padding(SPACE_SM)
+            .style(secondary_button_style),
+        iced::widget::Space::new().width(Length::Fill),
+        button(text("Done").size(14))
+            .on_press(Message::CloseIconPicker)
+            .padding(SPACE_SM)
+            .style(primary_button_style),
+    ]
+    .spacing(SPACE_SM);
+
+    let modal_content = column![
+        header_row,
+        text("Shown in place of the kind icon on the card and pinned window")
+            .size(11)
+            .color(TEXT_MUTED),
+        text("Icons").size(12).color(TEXT_SECONDARY),
+        column(icon_rows).spacing(SPACE_XS),
+        text("Emoji").size(12).color(TEXT_SECONDARY),
+        emoji_row,
+        action_buttons,
+    ]
+    .spacing(SPACE_MD)
+    .padding(SPACE_MD)
+    .width(Length::Fixed(400.0));
+
+    let modal_dialog = container(modal_content).style(modal_dialog_style);
+
+    // Semi-transparent overlay
+    mouse_area(
+        container(opaque(modal_dialog))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x(Length::Fill)
+            .center_y(Length::Fill)
+            .style(modal_overlay_style),
+    )
+    .on_press(Message::CloseIconPicker)
+    .into()
+}
@@ -0,0 +1,63 @@
+//! Card previews with a small swatch before each color literal, as editors
+//! show them. Clicking a swatch adds the color as a color snippet.
+
+use iced::widget::{button, column, container, row, text};
+use iced::{Element, Length};
+
+use crate::message::Message;
+use crate::snippet::find_color_literals;
+use crate::theme::{BORDER_SUBTLE, TEXT_MUTED};
+use crate::view::labeled;
+
+/// Render preview lines, marking the colors found in them.
+pub fn view_preview_lines<'a>(preview: String, size: f32) -> Element<'a, Message> {
+    let literals: Vec<_> = preview
+        .lines()
+        .map(|line| (line, find_color_literals(line)))
+        .collect();
+    if literals.iter().all(|(_, found)| found.is_empty()) {
+        return text(preview).size(size).color(TEXT_MUTED).into();
+    }
+
+    column(literals.into_iter().map(|(line, found)| {
+        let mut parts = row![].align_y(iced::Alignment::Center);
+        let mut end = 0;
+        for (range, color) in found {
+            let swatch_color = color.to_iced_color();
+            let swatch = container(text(""))
+                .width(Length::Fixed(size - 1.0))
+                .height(Length::Fixed(size - 1.0))
+                .style(move |_theme| {
+                    container::Style::default().background(swatch_color).border(
+                        iced::Border::default()
+                            .rounded(2.0)
+                            .width(1.0)
+                            .color(BORDER_SUBTLE),
+                    )
+                });
+            parts = parts
+                .push(
+                    text(line[end..range.start].to_string())
+                        .size(size)
+                        .color(TEXT_MUTED),
+                )
+                .push(labeled(
+                    button(swatch)
+                        .on_press(Message::AddInlineColor(color))
+                        .padding([0.0, 2.0])
+                        .style(|_theme, _status| button::Style::default()),
+                    "Add as a color snippet",
+                ))
+                .push(
+                    text(line[range.clone()].to_string())
+                        .size(size)
+                        .color(TEXT_MUTED),
+                );
+            end = range.end;
+        }
+        parts
+            .push(text(line[end..].to_string()).size(size).color(TEXT_MUTED))
+            .into()
+    }))
+    .into()
+}
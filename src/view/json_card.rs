@@ -0,0 +1,144 @@
+//! JSON snippet card view component.
+
+use iced::widget::{button, column, container, row, text};
+use iced::{Element, Length};
+
+use crate::config::CardActions;
+use crate::icons;
+use crate::message::Message;
+use crate::snippet::{JsonData, JsonFormat};
+use crate::theme::{
+    card_style, danger_button_style, subtle_button_style, BG_SURFACE, SPACE_MD, SPACE_SM, SPACE_XS,
+    TEXT_MUTED, TEXT_SECONDARY,
+};
+use crate::view::{
+    card_with_actions, labeled, view_age, view_card_header, view_preview_lines, ActionSet,
+    CardStats,
+};
+
+/// Render a JSON snippet card, showing actions per the visibility setting.
+pub fn view_json_card<'a>(
+    id: i64,
+    label: &'a str,
+    icon: Option<&'a str>,
+    json: &'a JsonData,
+    stats: CardStats<'a>,
+    is_selected: bool,
+    visibility: CardActions,
+) -> Element<'a, Message> {
+    card_with_actions(visibility, |actions| {
+        build_json_card(id, label, icon, json, stats, is_selected, actions)
+    })
+}
+
+/// Build a JSON snippet card with the given set of action buttons.
+fn build_json_card<'a>(
+    id: i64,
+    label: &'a str,
+    icon: Option<&'a str>,
+    json: &'a JsonData,
+    stats: CardStats<'a>,
+    is_selected: bool,
+    actions: ActionSet,
+) -> Element<'a, Message> {
+    // Brackets icon, or the snippet's custom icon (64x64 container)
+    let kind_icon = match icon {
+        Some(icon) => icons::snippet_icon(icon),
+        None => text(icons::JSON_ICON).font(icons::ICON_FONT),
+    };
+    let json_icon = container(kind_icon.size(28).color(TEXT_SECONDARY))
+        .width(64)
+        .height(64)
+        .center_x(64)
+        .center_y(64)
+        .style(|_theme| iced::widget::container::Style::default().background(BG_SURFACE));
+
+    // Top-level value badge, e.g. "Object · 3 keys"
+    let summary_badge = container(text(json.summary()).size(10).color(TEXT_MUTED))
+        .padding([2, 6])
+        .style(|_theme| iced::widget::container::Style::default().background(BG_SURFACE));
+
+    let header_row = row![
+        text(label).size(14).color(TEXT_SECONDARY),
+        summary_badge,
+        view_age(stats)
+    ]
+    .spacing(SPACE_SM)
+    .align_y(iced::Alignment::Center);
+
+    // Document preview (first 2 lines)
+    let preview_text = view_preview_lines(json.preview(2), 11.0);
+
+    let info_column = column![view_card_header(header_row, stats), preview_text]
+        .spacing(SPACE_XS)
+        .width(Length::Fill);
+
+    // Action buttons
+    let mut action_row = row![].spacing(SPACE_XS).align_y(iced::Alignment::Center);
+    if actions.shows_copy() {
+        action_row = action_row.push(labeled(
+            button(icons::copy().size(14))
+                .on_press(Message::CopySnippet(id))
+                .padding([SPACE_XS, SPACE_SM])
+                .style(subtle_button_style),
+            "Copy",
+        ));
+    }
+    if actions == ActionSet::All {
+        action_row = action_row.push(
+            row![
+                labeled(
+                    button(icons::tree_structure().size(14))
+                        .on_press(Message::OpenJsonTree(id))
+                        .padding([SPACE_XS, SPACE_SM])
+                        .style(subtle_button_style),
+                    "Show as tree"
+                ),
+                labeled(
+                    button(icons::text_indent().size(14))
+                        .on_press(Message::FormatJson(id, JsonFormat::Pretty))
+                        .padding([SPACE_XS, SPACE_SM])
+                        .style(subtle_button_style),
+                    "Pretty-print"
+                ),
+                labeled(
+                    button(icons::arrows_in_line_horizontal().size(14))
+                        .on_press(Message::FormatJson(id, JsonFormat::Minified))
+                        .padding([SPACE_XS, SPACE_SM])
+                        .style(subtle_button_style),
+                    "Minify"
+                ),
+                labeled(
+                    button(icons::pencil().size(14))
+                        .on_press(Message::OpenCodeEditor(Some(id)))
+                        .padding([SPACE_XS, SPACE_SM])
+                        .style(subtle_button_style),
+                    "Edit"
+                ),
+                labeled(
+                    button(icons::trash().size(14))
+                        .on_press(Message::DeleteSnippet(id))
+                        .padding([SPACE_XS, SPACE_SM])
+                        .style(danger_button_style),
+                    "Move to trash"
+                ),
+            ]
+            .spacing(SPACE_XS),
+        );
+    }
+
+    let card = row![json_icon, info_column, action_row]
+        .spacing(SPACE_MD)
+        .padding(SPACE_MD)
+        .align_y(iced::Alignment::Center);
+
+    let card_container = container(card)
+        .style(move |theme| card_style(theme, is_selected))
+        .width(Length::Fill);
+
+    button(card_container)
+        .on_press(Message::SelectSnippet(Some(id)))
+        .style(|_theme, _status| button::Style::default())
+        .padding(0)
+        .into()
+}
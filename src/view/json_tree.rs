@@ -0,0 +1,140 @@
+//! Tree view of a JSON snippet with collapsible objects and arrays.
+
+use std::collections::HashSet;
+
+use iced::widget::{button, column, container, mouse_area, opaque, row, scrollable, text};
+use iced::{Element, Font, Length};
+
+use crate::icons;
+use crate::message::Message;
+use crate::snippet::{json_tree_rows, JsonData};
+use crate::theme::{
+    modal_dialog_style, modal_overlay_style, scrollbar_style, subtle_button_style, SPACE_MD,
+    SPACE_SM, SPACE_XS, TEXT_MUTED, TEXT_PRIMARY, TEXT_SECONDARY,
+};
+use crate::view::labeled;
+
+/// Indentation per nesting level.
+const INDENT: f32 = 16.0;
+
+/// Width of the expand/collapse toggle column.
+const TOGGLE_WIDTH: f32 = 18.0;
+
+/// State for the JSON tree view.
+#[derive(Debug, Clone)]
+pub struct JsonTreeState {
+    pub snippet_id: i64,
+    /// Paths of the collapsed objects and arrays; everything starts expanded.
+    pub collapsed: HashSet<String>,
+}
+
+impl JsonTreeState {
+    /// Create a tree view of a JSON snippet.
+    pub fn new(snippet_id: i64) -> Self {
+        Self {
+            snippet_id,
+            collapsed: HashSet::new(),
+        }
+    }
+
+    /// Collapse an expanded node, or expand a collapsed one.
+    pub fn toggle(&mut self, path: String) {
+        if !self.collapsed.remove(&path) {
+            self.collapsed.insert(path);
+        }
+    }
+}
+
+/// Render the tree view of a JSON snippet.
+pub fn view_json_tree_modal<'a>(
+    state: &'a JsonTreeState,
+    label: &'a str,
+    json: &'a JsonData,
+) -> Element<'a, Message> {
+    let header_row = row![
+        text(label).size(20).color(TEXT_PRIMARY),
+        text(json.summary()).size(12).color(TEXT_MUTED),
+        iced::widget::Space::new().width(Length::Fill),
+        labeled(
+            button(icons::x().size(16))
+                .on_press(Message::CloseJsonTree)
+                .padding([SPACE_XS, SPACE_SM])
+                .style(subtle_button_style),
+            "Close"
+        ),
+    ]
+    .spacing(SPACE_SM)
+    .align_y(iced::Alignment::Center);
+
+    let body: Element<'a, Message> = match json.parse() {
+        Ok(value) => {
+            let rows = json_tree_rows(&value, &state.collapsed)
+                .into_iter()
+                .map(|node| {
+                    let toggle: Element<'a, Message> = if node.expandable {
+                        let caret = if node.expanded {
+                            icons::caret_down()
+                        } else {
+                            icons::caret_right()
+                        };
+                        button(caret.size(12))
+                            .on_press(Message::ToggleJsonNode(node.path))
+                            .padding(0)
+                            .width(Length::Fixed(TOGGLE_WIDTH))
+                            .style(subtle_button_style)
+                            .into()
+                    } else {
+                        iced::widget::Space::new()
+                            .width(Length::Fixed(TOGGLE_WIDTH))
+                            .into()
+                    };
+                    let mut line = row![
+                        iced::widget::Space::new().width(Length::Fixed(node.depth as f32 * INDENT)),
+                        toggle,
+                    ]
+                    .spacing(SPACE_XS)
+                    .align_y(iced::Alignment::Center);
+                    if let Some(key) = node.key {
+                        line = line.push(
+                            text(format!("{}:", key))
+                                .size(12)
+                                .font(Font::MONOSPACE)
+                                .color(TEXT_SECONDARY),
+                        );
+                    }
+                    line.push(text(node.value).size(12).font(Font::MONOSPACE).color(
+                        if node.expandable {
+                            TEXT_MUTED
+                        } else {
+                            TEXT_PRIMARY
+                        },
+                    ))
+                    .into()
+                });
+            scrollable(container(column(rows).spacing(2)).padding(SPACE_SM))
+                .height(Length::Fixed(360.0))
+                .style(scrollbar_style)
+                .into()
+        }
+        Err(e) => text(e).size(12).color(TEXT_MUTED).into(),
+    };
+
+    let modal_content = column![header_row, body]
+        .spacing(SPACE_MD)
+        .padding(SPACE_MD)
+        .width(Length::Fixed(550.0));
+
+    let modal_dialog = container(modal_content).style(modal_dialog_style);
+
+    // Semi-transparent overlay
+    mouse_area(
+        container(opaque(modal_dialog))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x(Length::Fill)
+            .center_y(Length::Fill)
+            .style(modal_overlay_style),
+    )
+    .on_press(Message::CloseJsonTree)
+    .into()
+}
@@ -1,38 +1,114 @@
 //! View module containing UI components.
 
+pub mod a11y;
+pub mod abbreviation;
+pub mod bulk_delete;
+pub mod capture_session;
+pub mod card_menu;
 pub mod code_card;
 pub mod code_editor;
+pub mod code_export;
 pub mod color_card;
 pub mod color_picker;
+pub mod color_set_card;
+pub mod conflict;
+pub mod contrast;
+pub mod diff;
+pub mod edit_review;
+pub mod encryption;
+pub mod favorites;
+pub mod file_card;
+pub mod gradient_card;
+pub mod gradient_editor;
+pub mod highlight;
+pub mod icon_picker;
+pub mod inline_swatches;
+pub mod json_card;
+pub mod json_tree;
+pub mod palette_board;
+pub mod preview;
+pub mod quick_popup;
+pub mod release_notes;
+pub mod run_output;
+pub mod scale_dialog;
+pub mod scan_dialog;
+pub mod scratchpad;
+pub mod sections;
+pub mod selection;
 pub mod settings;
+pub mod share_dialog;
+pub mod similar;
+pub mod split_pane;
+pub mod table_view;
+pub mod template_fill;
 pub mod text_card;
 pub mod text_editor;
+pub mod trash;
 
+pub use a11y::{inert, labeled};
+pub use abbreviation::AbbreviationState;
+pub use bulk_delete::BulkDeleteState;
+pub use capture_session::{CaptureSession, CaptureSessionDraft, CAPTURE_SESSION_NAME_ID};
 pub use code_card::view_code_card;
-pub use code_editor::CodeEditorState;
-pub use color_card::view_color_card;
+pub use code_editor::{CodeEditorState, CODE_EDITOR_SCROLL_ID};
+pub use code_export::CodeExportState;
+pub use color_card::{view_color_card, ColorCardInputs};
 pub use color_picker::{view_color_picker_modal, ColorPickerState, PickerMode};
+pub use color_set_card::{view_color_set_card, view_swatch_strip};
+pub use contrast::ContrastState;
+pub use edit_review::EditReviewState;
+pub use encryption::{EncryptState, UnlockState};
+pub use favorites::MAX_FAVORITES;
+pub use file_card::view_file_card;
+pub use gradient_card::view_gradient_card;
+pub use gradient_editor::GradientEditorState;
+pub use icon_picker::IconPickerState;
+pub use inline_swatches::view_preview_lines;
+pub use json_card::view_json_card;
+pub use json_tree::JsonTreeState;
+pub use run_output::RunState;
+pub use scale_dialog::ScaleState;
+pub use scan_dialog::RegionScanState;
+pub use sections::{SectionDraft, SECTION_NAME_ID};
 pub use settings::SettingsState;
+pub use share_dialog::ShareImageState;
+pub use similar::SimilarState;
+pub use table_view::{TableColumn, TableState};
+pub use template_fill::TemplateFillState;
 pub use text_card::view_text_card;
 pub use text_editor::TextEditorState;
 
 use iced::widget::{
-    button, checkbox, column, container, mouse_area, row, scrollable, stack, text, text_input,
+    button, checkbox, column, container, hover, mouse_area, pick_list, row, scrollable, stack,
+    text, text_input,
 };
-use iced::{Element, Length};
+use iced::{window, Element, Length};
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
-use crate::db::Palette;
+use crate::config::{
+    CardActions, CodeTheme, CopyTemplate, EditingConfig, ListLayout, SnippetSort, SplitPane,
+    TrashRetention,
+};
+use crate::db::{self, Palette, SaveConflict, Section};
+use crate::export::ExportFormat;
 use crate::icons;
 use crate::message::Message;
-use crate::snippet::{Snippet, SnippetContent, SnippetKind};
+use crate::releases::Release;
+use crate::snippet::{
+    language_counts, relative_time, short_hash, source_app_counts, unix_now, ColorData,
+    PaletteQuery, PaletteRule, Snippet, SnippetContent, SnippetFilter, SnippetKind,
+};
 use crate::theme::{
-    dropdown_item_style, dropdown_menu_style, header_style, input_style, primary_button_style,
-    scrollbar_style, secondary_button_style, status_bar_style, subtle_button_style, BG_BASE,
-    SPACE_LG, SPACE_MD, SPACE_SM, SPACE_XS, TEXT_MUTED, TEXT_SECONDARY,
+    button_group_style, dropdown_item_style, dropdown_menu_style, header_style, input_style,
+    pick_list_style, primary_button_style, scrollbar_style, secondary_button_style,
+    status_bar_style, subtle_button_style, BG_BASE, SPACE_LG, SPACE_MD, SPACE_SM, SPACE_XS,
+    TEXT_MUTED, TEXT_SECONDARY,
 };
 
+/// Number of member colors shown under each palette in the filter bar.
+const PALETTE_STRIP_SWATCHES: usize = 6;
+
 /// Context for rendering the main view.
 pub struct ViewContext<'a> {
     pub snippets: &'a [Snippet],
@@ -40,6 +116,8 @@ pub struct ViewContext<'a> {
     pub status_message: Option<&'a str>,
     pub filter_text: &'a str,
     pub filter_kind: Option<&'a SnippetKind>,
+    pub filter_language: Option<&'a str>,
+    pub filter_source_app: Option<&'a str>,
     pub selected_snippet: Option<i64>,
     pub color_picker: Option<&'a ColorPickerState>,
     pub code_editor: Option<&'a CodeEditorState>,
@@ -51,18 +129,97 @@ pub struct ViewContext<'a> {
     pub filter_palette: Option<i64>,
     pub palette_manager_open: bool,
     pub palette_dropdown_snippet: Option<i64>,
+    pub palette_dropdown_query: &'a str,
+    pub palette_dropdown_cursor: usize,
     pub snippet_palettes: &'a HashMap<i64, Vec<i64>>,
     pub new_palette_name: &'a str,
+    /// Query of the palette being created; non-empty makes it a smart palette.
+    pub new_palette_query: &'a str,
+    // Pinned windows
+    pub pinned_panel_open: bool,
+    pub pinned_windows: Vec<(window::Id, i64)>,
+    pub color_value_drafts: &'a HashMap<i64, String>,
+    // Inbox triage
+    pub inbox_open: bool,
+    pub inbox_cursor: usize,
+    pub card_actions: CardActions,
+    pub code_theme: CodeTheme,
+    pub editing: EditingConfig,
+    pub list_layout: ListLayout,
+    pub sort_order: SnippetSort,
+    pub table: &'a TableState,
+    /// Configured palette for new captures, and whether capturing into it is on.
+    pub capture_palette: Option<i64>,
+    pub capture_to_palette: bool,
+    pub capture_session: Option<&'a CaptureSession>,
+    pub capture_session_draft: Option<&'a CaptureSessionDraft>,
+    pub scratchpad_open: bool,
+    pub scratchpad: &'a iced::widget::text_editor::Content,
+    pub restore_available: bool,
+    /// A reformatted snippet can be put back as it was.
+    pub reformat_undo_available: bool,
+    pub crash_report_available: bool,
+    /// Newer release found by the update check, and whether its notes are shown.
+    pub available_update: Option<&'a Release>,
+    pub release_notes_open: bool,
+    pub share_image: Option<&'a ShareImageState>,
+    pub contrast: Option<&'a ContrastState>,
+    pub similar_colors: Option<&'a SimilarState>,
+    pub template_fill: Option<&'a TemplateFillState>,
+    pub run_output: Option<&'a RunState>,
+    pub scale: Option<&'a ScaleState>,
+    pub code_export: Option<&'a CodeExportState>,
+    pub gradient_editor: Option<&'a GradientEditorState>,
+    pub region_scan: Option<&'a RegionScanState>,
+    // Favorites
+    pub show_favorites_bar: bool,
+    pub show_source_badge: bool,
+    pub copy_templates: &'a [CopyTemplate],
+    /// Colors last confirmed in the color picker, newest first.
+    pub recent_colors: &'a [ColorData],
+    pub card_menu_snippet: Option<i64>,
+    pub hovered_snippet: Option<i64>,
+    /// Snippets whose linked file changed since it was linked.
+    pub changed_files: &'a HashSet<i64>,
+    pub expanded_palettes: &'a HashSet<i64>,
+    // Multi-select
+    pub selection_mode: bool,
+    pub selected_snippets: &'a [i64],
+    pub batch_language: &'a str,
+    // Split pane
+    pub split_pane: &'a SplitPane,
+    pub split_pane_filter_text: &'a str,
+    pub icon_picker: Option<&'a IconPickerState>,
+    pub abbreviation_editor: Option<&'a AbbreviationState>,
+    /// Abbreviations typed in other apps are expanded.
+    pub text_expansion: bool,
+    pub json_tree: Option<&'a JsonTreeState>,
+    pub save_conflict: Option<&'a SaveConflict>,
+    pub edit_review: Option<&'a EditReviewState>,
+    pub bulk_delete: Option<&'a BulkDeleteState>,
+    // Sections
+    pub sections: &'a [Section],
+    pub section_draft: Option<&'a SectionDraft>,
+    // Encryption
+    pub unlock: Option<&'a UnlockState>,
+    pub encrypt_database: Option<&'a EncryptState>,
+    pub database_encrypted: bool,
+    // Trash
+    pub trash: &'a [Snippet],
+    pub trash_open: bool,
+    pub trash_retention: TrashRetention,
 }
 
 /// Render the main application view.
-pub fn view(ctx: ViewContext<'_>) -> Element<'_, Message> {
+pub fn view<'a>(ctx: ViewContext<'a>) -> Element<'a, Message> {
     let ViewContext {
         snippets,
         is_listening_clipboard,
         status_message,
         filter_text,
         filter_kind,
+        filter_language,
+        filter_source_app,
         selected_snippet,
         color_picker,
         code_editor,
@@ -73,8 +230,70 @@ pub fn view(ctx: ViewContext<'_>) -> Element<'_, Message> {
         filter_palette,
         palette_manager_open,
         palette_dropdown_snippet,
+        palette_dropdown_query,
+        palette_dropdown_cursor,
         snippet_palettes,
         new_palette_name,
+        new_palette_query,
+        pinned_panel_open,
+        pinned_windows,
+        color_value_drafts,
+        inbox_open,
+        inbox_cursor,
+        card_actions,
+        code_theme,
+        editing,
+        list_layout,
+        sort_order,
+        table,
+        capture_palette,
+        capture_to_palette,
+        capture_session,
+        capture_session_draft,
+        scratchpad_open,
+        scratchpad,
+        restore_available,
+        reformat_undo_available,
+        crash_report_available,
+        available_update,
+        release_notes_open,
+        share_image,
+        contrast,
+        similar_colors,
+        template_fill,
+        run_output,
+        scale,
+        code_export,
+        gradient_editor,
+        region_scan,
+        show_favorites_bar,
+        show_source_badge,
+        copy_templates,
+        recent_colors,
+        card_menu_snippet,
+        hovered_snippet,
+        changed_files,
+        expanded_palettes,
+        selection_mode,
+        selected_snippets,
+        batch_language,
+        split_pane,
+        split_pane_filter_text,
+        icon_picker,
+        abbreviation_editor,
+        text_expansion,
+        json_tree,
+        save_conflict,
+        edit_review,
+        bulk_delete,
+        sections,
+        section_draft,
+        unlock,
+        encrypt_database,
+        database_encrypted,
+        trash,
+        trash_open,
+        trash_retention,
     } = ctx;
 
     // === HEADER: Primary Actions + Filters + Tools ===
@@ -108,44 +327,93 @@ pub fn view(ctx: ViewContext<'_>) -> Element<'_, Message> {
     let tab_row = row![
         tab_button(
             "All",
-            filter_kind.is_none(),
+            !trash_open && filter_kind.is_none(),
             Message::FilterKindChanged(None)
         ),
         tab_button(
             "Colors",
-            filter_kind == Some(&SnippetKind::Color),
+            !trash_open && filter_kind == Some(&SnippetKind::Color),
             Message::FilterKindChanged(Some(SnippetKind::Color))
         ),
+        tab_button(
+            "Sets",
+            !trash_open && filter_kind == Some(&SnippetKind::ColorSet),
+            Message::FilterKindChanged(Some(SnippetKind::ColorSet))
+        ),
+        tab_button(
+            "Gradients",
+            !trash_open && filter_kind == Some(&SnippetKind::Gradient),
+            Message::FilterKindChanged(Some(SnippetKind::Gradient))
+        ),
         tab_button(
             "Code",
-            filter_kind == Some(&SnippetKind::Code),
+            !trash_open && filter_kind == Some(&SnippetKind::Code),
             Message::FilterKindChanged(Some(SnippetKind::Code))
         ),
+        tab_button(
+            "JSON",
+            !trash_open && filter_kind == Some(&SnippetKind::Json),
+            Message::FilterKindChanged(Some(SnippetKind::Json))
+        ),
         tab_button(
             "Text",
-            filter_kind == Some(&SnippetKind::Text),
+            !trash_open && filter_kind == Some(&SnippetKind::Text),
             Message::FilterKindChanged(Some(SnippetKind::Text))
         ),
+        tab_button(
+            "Files",
+            !trash_open && filter_kind == Some(&SnippetKind::File),
+            Message::FilterKindChanged(Some(SnippetKind::File))
+        ),
+        tab_button(
+            format!("Trash ({})", trash.len()),
+            trash_open,
+            Message::ShowTrash
+        ),
     ]
     .spacing(SPACE_XS);
 
     // Palette filter dropdown
-    let palette_filter = view_palette_filter(palettes, filter_palette);
+    let palette_filter = view_palette_filter(
+        palettes,
+        filter_palette,
+        expanded_palettes,
+        snippets,
+        snippet_palettes,
+    );
 
     // Vertical Divider
     let divider = || {
-        container(text(" "))
-            .width(1.0)
-            .height(16.0)
-            .style(|_t| {
-                iced::widget::container::Style::default().background(crate::theme::BORDER_SUBTLE)
-            })
+        container(text(" ")).width(1.0).height(16.0).style(|_t| {
+            iced::widget::container::Style::default().background(crate::theme::BORDER_SUBTLE)
+        })
     };
 
-    let filter_group = row![tab_row, divider(), palette_filter]
+    let sort_picker = pick_list(
+        SnippetSort::ALL,
+        Some(sort_order),
+        Message::SortOrderChanged,
+    )
+    .text_size(12)
+    .padding([SPACE_XS, SPACE_SM])
+    .style(pick_list_style);
+
+    let mut filter_group = row![tab_row, divider(), palette_filter, sort_picker]
         .spacing(SPACE_MD)
         .align_y(iced::Alignment::Center);
 
+    // Language facet dropdown (only meaningful when code snippets are visible)
+    if matches!(filter_kind, None | Some(SnippetKind::Code)) {
+        if let Some(language_filter) = view_language_filter(snippets, filter_language) {
+            filter_group = filter_group.push(language_filter);
+        }
+    }
+
+    // Source application facet dropdown (once captures recorded a source)
+    if let Some(source_filter) = view_source_app_filter(snippets, filter_source_app) {
+        filter_group = filter_group.push(source_filter);
+    }
+
     // 3. Settings & Tools (Right)
     // Clipboard toggle - Button style for cleaner look
     let clipboard_toggle = button(
@@ -169,15 +437,157 @@ pub fn view(ctx: ViewContext<'_>) -> Element<'_, Message> {
     });
 
     // Settings button
-    let settings_button = button(icons::gear().size(16))
-        .on_press(Message::OpenSettings)
+    let settings_button = labeled(
+        button(icons::gear().size(16))
+            .on_press(Message::OpenSettings)
+            .padding([SPACE_SM, SPACE_MD])
+            .style(subtle_button_style),
+        "Settings",
+    );
+
+    // Pinned windows panel button (shows count of open pinned windows)
+    let pinned_button = button(
+        row![
+            icons::push_pin().size(14),
+            text(pinned_windows.len().to_string()).size(12)
+        ]
+        .spacing(SPACE_XS)
+        .align_y(iced::Alignment::Center),
+    )
+    .on_press(Message::OpenPinnedPanel)
+    .padding([SPACE_SM, SPACE_MD])
+    .style(subtle_button_style);
+
+    // Inbox button (shows count of auto-captures awaiting triage)
+    let inbox_count = snippets.iter().filter(|s| s.in_inbox).count();
+    let inbox_button = button(
+        row![
+            icons::tray().size(14),
+            text(inbox_count.to_string()).size(12)
+        ]
+        .spacing(SPACE_XS)
+        .align_y(iced::Alignment::Center),
+    )
+    .on_press(Message::OpenInbox)
+    .padding([SPACE_SM, SPACE_MD])
+    .style(if inbox_count > 0 {
+        secondary_button_style
+    } else {
+        subtle_button_style
+    });
+
+    // Scratchpad button
+    let scratchpad_button = labeled(
+        button(icons::notepad().size(16))
+            .on_press(Message::OpenScratchpad)
+            .padding([SPACE_SM, SPACE_MD])
+            .style(subtle_button_style),
+        "Scratchpad",
+    );
+
+    // List layout toggle (shows the layout it switches to)
+    let layout_icon = match list_layout {
+        ListLayout::Cards => icons::table(),
+        ListLayout::Table => icons::cards(),
+    };
+    let layout_button = button(layout_icon.size(16))
+        .on_press(Message::ToggleListLayout)
         .padding([SPACE_SM, SPACE_MD])
         .style(subtle_button_style);
 
-    let tools_group = row![clipboard_toggle, settings_button]
+    // Capture session: time left while one runs, otherwise a button to start one
+    let session_control = match capture_session {
+        Some(session) => capture_session::view_session_button(session),
+        None => labeled(
+            button(icons::timer().size(16))
+                .on_press(Message::OpenCaptureSessionDialog)
+                .padding([SPACE_SM, SPACE_MD])
+                .style(subtle_button_style),
+            "Start capture session",
+        ),
+    };
+
+    // Eyedropper: samples a screen pixel into the color picker
+    let eyedropper_button = labeled(
+        button(icons::eyedropper().size(16))
+            .on_press(Message::StartEyedropper)
+            .padding([SPACE_SM, SPACE_MD])
+            .style(subtle_button_style),
+        "Pick color from screen",
+    );
+
+    let mut tools_group = row![clipboard_toggle, session_control, eyedropper_button]
         .spacing(SPACE_SM)
         .align_y(iced::Alignment::Center);
 
+    // Capture palette toggle (only once a capture palette is configured)
+    if let Some(palette) = capture_palette.and_then(|id| palettes.iter().find(|p| p.id == id)) {
+        let capture_palette_button = button(
+            row![icons::tag().size(14), text(palette.name.clone()).size(12)]
+                .spacing(SPACE_XS)
+                .align_y(iced::Alignment::Center),
+        )
+        .on_press(Message::ToggleCapturePalette)
+        .padding([SPACE_SM, SPACE_MD])
+        .style(if capture_to_palette {
+            secondary_button_style
+        } else {
+            subtle_button_style
+        });
+        tools_group = tools_group.push(capture_palette_button);
+    }
+
+    // Multi-select toggle
+    let selection_button = labeled(
+        button(icons::check_square().size(16))
+            .on_press(Message::ToggleSelectionMode)
+            .padding([SPACE_SM, SPACE_MD])
+            .style(if selection_mode {
+                secondary_button_style
+            } else {
+                subtle_button_style
+            }),
+        "Select several",
+    );
+
+    // Split pane toggle
+    let split_button = labeled(
+        button(icons::columns().size(16))
+            .on_press(Message::ToggleSplitPane)
+            .padding([SPACE_SM, SPACE_MD])
+            .style(if split_pane.enabled {
+                secondary_button_style
+            } else {
+                subtle_button_style
+            }),
+        "Split view",
+    );
+
+    // Update badge (only once the update check found a newer release)
+    if let Some(release) = available_update {
+        let update_button = button(
+            row![
+                icons::arrow_circle_up().size(14),
+                text(format!("v{}", release.version())).size(12)
+            ]
+            .spacing(SPACE_XS)
+            .align_y(iced::Alignment::Center),
+        )
+        .on_press(Message::OpenReleaseNotes)
+        .padding([SPACE_SM, SPACE_MD])
+        .style(secondary_button_style);
+        tools_group = tools_group.push(update_button);
+    }
+
+    let tools_group = tools_group
+        .push(layout_button)
+        .push(selection_button)
+        .push(split_button)
+        .push(scratchpad_button)
+        .push(inbox_button)
+        .push(pinned_button)
+        .push(settings_button);
+
     // Spacers for layout distribution
     let left_spacer = container(text("")).width(Length::Fill);
     let right_spacer = container(text("")).width(Length::Shrink);
@@ -205,35 +615,81 @@ pub fn view(ctx: ViewContext<'_>) -> Element<'_, Message> {
         });
 
     // Filter snippets
-    let filtered_snippets: Vec<&Snippet> = snippets
-        .iter()
-        .filter(|s| {
-            // Filter by kind
-            if let Some(kind) = filter_kind {
-                if &s.kind() != kind {
-                    return false;
-                }
-            }
-            // Filter by palette
-            if let Some(palette_id) = filter_palette {
-                if let Some(snippet_palette_ids) = snippet_palettes.get(&s.id) {
-                    if !snippet_palette_ids.contains(&palette_id) {
-                        return false;
-                    }
-                } else {
-                    return false; // Snippet not in any palette
-                }
-            }
-            // Filter by text
-            if !filter_text.trim().is_empty() {
-                return s.matches_filter(filter_text);
+    let filter_rules = palette_rules(palettes, filter_palette);
+    let filter = SnippetFilter {
+        text: filter_text,
+        kind: filter_kind,
+        language: filter_language,
+        source_app: filter_source_app,
+        palette: filter_rules.as_deref(),
+        inbox: false,
+    };
+    let filtered_snippets = filter_and_sort(snippets, &filter, snippet_palettes, sort_order);
+
+    // Render filtered snippets in the current layout; with `unfiltered`,
+    // empty sections keep their headers
+    let view_list = |filtered: Vec<&'a Snippet>, unfiltered: bool| -> Element<'a, Message> {
+        if list_layout == ListLayout::Table {
+            return table_view::view_snippet_table(
+                filtered,
+                table,
+                selected_snippet,
+                selection_mode.then_some(selected_snippets),
+                palettes,
+                snippet_palettes,
+            );
+        }
+        let card = |snippet: &'a Snippet| -> Element<'a, Message> {
+            let is_selected = selected_snippet == Some(snippet.id);
+            let color_inputs = ColorCardInputs {
+                value_draft: color_value_drafts.get(&snippet.id).map(String::as_str),
+                copy_templates,
+            };
+            let show_preview = hovered_snippet == Some(snippet.id);
+            let stats = CardStats {
+                file_changed: changed_files.contains(&snippet.id),
+                source_app: snippet.source_app.as_deref().filter(|_| show_source_badge),
+                ..CardStats::of(snippet)
+            };
+            let card = view_snippet_card(
+                snippet,
+                is_selected,
+                color_inputs,
+                card_actions,
+                code_theme,
+                show_preview,
+                stats,
+            );
+            if !selection_mode {
+                return card;
             }
-            true
-        })
-        .collect();
+            let id = snippet.id;
+            row![
+                checkbox(selected_snippets.contains(&id))
+                    .on_toggle(move |_| Message::ToggleSnippetChecked(id)),
+                card
+            ]
+            .spacing(SPACE_SM)
+            .align_y(iced::Alignment::Center)
+            .into()
+        };
+        // Sections only make sense in the manual order
+        let items: Vec<Element<'a, Message>> = if sort_order == SnippetSort::Manual {
+            sections::with_section_headers(&filtered, sections, unfiltered, card)
+        } else {
+            filtered.into_iter().map(card).collect()
+        };
+
+        scrollable(column(items).spacing(SPACE_SM).padding(SPACE_MD))
+            .height(Length::Fill)
+            .style(scrollbar_style)
+            .into()
+    };
 
     // Snippet list
-    let snippets_list: Element<'_, Message> = if snippets.is_empty() {
+    let snippets_list: Element<'_, Message> = if trash_open {
+        trash::view_trash(trash, trash_retention)
+    } else if snippets.is_empty() {
         container(
             text("No snippets yet. Add a color, code, or text snippet above.")
                 .size(14)
@@ -252,76 +708,274 @@ pub fn view(ctx: ViewContext<'_>) -> Element<'_, Message> {
         .center_x(Length::Fill)
         .into()
     } else {
-        let items: Vec<Element<'_, Message>> = filtered_snippets
-            .iter()
-            .map(|snippet| {
-                let is_selected = selected_snippet == Some(snippet.id);
-                view_snippet_card(snippet, is_selected)
-            })
-            .collect();
+        view_list(filtered_snippets.clone(), !filter.is_active())
+    };
 
-        scrollable(column(items).spacing(SPACE_SM).padding(SPACE_MD))
-            .height(Length::Fill)
-            .style(scrollbar_style)
+    // Optional second pane with its own filters, beside the main list
+    let snippets_list: Element<'_, Message> = if split_pane.enabled {
+        let pane_rules = palette_rules(palettes, split_pane.palette);
+        let pane_filter = SnippetFilter {
+            text: split_pane_filter_text,
+            kind: split_pane.kind.as_ref(),
+            language: None,
+            source_app: None,
+            palette: pane_rules.as_deref(),
+            inbox: false,
+        };
+        let pane_snippets = filter_and_sort(snippets, &pane_filter, snippet_palettes, sort_order);
+        let pane_list = if pane_snippets.is_empty() {
+            container(
+                text("No snippets match this pane's filters")
+                    .size(14)
+                    .color(TEXT_SECONDARY),
+            )
+            .padding(SPACE_MD)
+            .center_x(Length::Fill)
             .into()
+        } else {
+            view_list(pane_snippets, !pane_filter.is_active())
+        };
+
+        let pane_divider = container(text(" "))
+            .width(1.0)
+            .height(Length::Fill)
+            .style(|_t| {
+                iced::widget::container::Style::default().background(crate::theme::BORDER_SUBTLE)
+            });
+
+        row![
+            container(snippets_list).width(Length::Fill),
+            pane_divider,
+            column![
+                split_pane::view_split_pane_bar(split_pane, split_pane_filter_text, palettes),
+                pane_list
+            ]
+            .width(Length::Fill),
+        ]
+        .height(Length::Fill)
+        .into()
+    } else {
+        snippets_list
     };
 
     // Status bar
     let status_text = status_message.unwrap_or("Ready");
-    let count_text =
-        if filter_text.trim().is_empty() && filter_kind.is_none() && filter_palette.is_none() {
-            format!("{} snippets", snippets.len())
-        } else {
-            format!("{} / {} snippets", filtered_snippets.len(), snippets.len())
-        };
-    let status_bar_content = row![
+    let count_text = if !filter.is_active() {
+        format!("{} snippets", snippets.len())
+    } else {
+        format!("{} / {} snippets", filtered_snippets.len(), snippets.len())
+    };
+    let mut status_bar_content = row![
         text(count_text).size(12).color(TEXT_SECONDARY),
         text("|").size(12).color(TEXT_SECONDARY),
         text(status_text).size(12).color(TEXT_SECONDARY),
     ]
     .spacing(SPACE_SM)
-    .padding(SPACE_SM);
+    .padding(SPACE_SM)
+    .align_y(iced::Alignment::Center);
+
+    // Offer to trash everything the filter matches
+    if filter.is_active() && !filtered_snippets.is_empty() && !trash_open {
+        status_bar_content = status_bar_content.push(
+            button(
+                row![
+                    icons::trash().size(12),
+                    text(format!("Delete {} matching…", filtered_snippets.len())).size(12)
+                ]
+                .spacing(SPACE_XS)
+                .align_y(iced::Alignment::Center),
+            )
+            .on_press(Message::OpenBulkDelete)
+            .padding([2.0, SPACE_SM])
+            .style(secondary_button_style),
+        );
+    }
+
+    // Offer to roll back a failed migration or import
+    if restore_available {
+        status_bar_content = status_bar_content.push(
+            button(
+                row![
+                    icons::arrow_counter_clockwise().size(12),
+                    text("Restore backup").size(12)
+                ]
+                .spacing(SPACE_XS)
+                .align_y(iced::Alignment::Center),
+            )
+            .on_press(Message::RestoreBackup)
+            .padding([2.0, SPACE_SM])
+            .style(secondary_button_style),
+        );
+    }
+
+    // Offer to put back a reformatted snippet
+    if reformat_undo_available {
+        status_bar_content = status_bar_content.push(
+            button(
+                row![
+                    icons::arrow_counter_clockwise().size(12),
+                    text("Undo reformat").size(12)
+                ]
+                .spacing(SPACE_XS)
+                .align_y(iced::Alignment::Center),
+            )
+            .on_press(Message::UndoReformat)
+            .padding([2.0, SPACE_SM])
+            .style(secondary_button_style),
+        );
+    }
+
+    // Offer the crash report left by the last session
+    if crash_report_available {
+        status_bar_content = status_bar_content.push(
+            row![
+                button(
+                    row![
+                        icons::warning().size(12),
+                        text("Shard crashed last time · Open report").size(12)
+                    ]
+                    .spacing(SPACE_XS)
+                    .align_y(iced::Alignment::Center),
+                )
+                .on_press(Message::OpenCrashReport)
+                .padding([2.0, SPACE_SM])
+                .style(secondary_button_style),
+                labeled(
+                    button(icons::x().size(12))
+                        .on_press(Message::DismissCrashReport)
+                        .padding([2.0, SPACE_XS])
+                        .style(subtle_button_style),
+                    "Dismiss"
+                ),
+            ]
+            .spacing(SPACE_XS),
+        );
+    }
 
     let status_bar = container(status_bar_content)
         .width(Length::Fill)
         .style(status_bar_style);
 
     // Main layout
-    let main_content = container(column![header, snippets_list, status_bar])
+    let mut main_column = column![header];
+    if selection_mode && !trash_open {
+        main_column = main_column.push(selection::view_selection_bar(
+            selected_snippets.len(),
+            palettes,
+            batch_language,
+        ));
+    }
+    if show_favorites_bar {
+        if let Some(favorites_bar) = favorites::view_favorites_bar(snippets) {
+            main_column = main_column.push(favorites_bar);
+        }
+    }
+    let main_content = container(main_column.push(snippets_list).push(status_bar))
         .width(Length::Fill)
         .height(Length::Fill)
         .style(|_theme| iced::widget::container::Style::default().background(BG_BASE));
 
     // Build overlay layer (always present to maintain consistent widget tree)
-    let overlay: Element<'_, Message> = if let Some(s) = settings {
-        settings::view_settings_modal(s)
+    let overlay: Element<'_, Message> = if let Some(state) = unlock {
+        encryption::view_unlock_modal(state)
+    } else if let Some(state) = encrypt_database {
+        encryption::view_encrypt_modal(state)
+    } else if let Some(conflict) = save_conflict {
+        conflict::view_conflict_modal(conflict)
+    } else if let Some(state) = edit_review {
+        edit_review::view_edit_review_modal(state)
+    } else if let Some(state) = bulk_delete {
+        bulk_delete::view_bulk_delete_modal(state)
+    } else if let Some(release) = available_update.filter(|_| release_notes_open) {
+        release_notes::view_release_notes_modal(release)
+    } else if let Some(draft) = section_draft {
+        sections::view_section_dialog(draft)
+    } else if let Some(draft) = capture_session_draft {
+        capture_session::view_capture_session_dialog(draft)
+    } else if let Some(s) = settings {
+        settings::view_settings_modal(s, palettes, database_encrypted)
+    } else if pinned_panel_open {
+        view_pinned_panel_modal(snippets, pinned_windows)
+    } else if inbox_open {
+        view_inbox_modal(snippets, inbox_cursor)
+    } else if scratchpad_open {
+        scratchpad::view_scratchpad_modal(scratchpad)
     } else if palette_manager_open {
-        view_palette_manager_modal(palettes, new_palette_name)
+        view_palette_manager_modal(palettes, new_palette_name, new_palette_query)
     } else if let Some(picker) = color_picker {
-        view_color_picker_modal(picker)
+        view_color_picker_modal(picker, copy_templates, recent_colors)
+    } else if let Some(state) = share_image {
+        share_dialog::view_share_image_modal(state)
+    } else if let Some(state) = contrast {
+        contrast::view_contrast_modal(state, snippets)
+    } else if let Some(state) = similar_colors {
+        similar::view_similar_modal(state, snippets)
+    } else if let Some(state) = template_fill {
+        template_fill::view_template_fill_modal(state)
+    } else if let Some(state) = run_output {
+        run_output::view_run_output_modal(state)
+    } else if let Some(state) = scale {
+        scale_dialog::view_scale_modal(state)
+    } else if let Some(state) = code_export {
+        code_export::view_code_export_modal(state)
+    } else if let Some(state) = gradient_editor {
+        gradient_editor::view_gradient_editor_modal(state)
+    } else if let Some(state) = region_scan {
+        scan_dialog::view_region_scan_modal(state)
+    } else if let Some(state) = icon_picker {
+        let current = snippets
+            .iter()
+            .find(|s| s.id == state.snippet_id)
+            .and_then(|s| s.icon.as_deref());
+        icon_picker::view_icon_picker_modal(state, current)
+    } else if let Some(state) = abbreviation_editor {
+        let current = snippets
+            .iter()
+            .find(|s| s.id == state.snippet_id)
+            .and_then(|s| s.abbreviation.as_deref());
+        abbreviation::view_abbreviation_modal(state, current, text_expansion)
+    } else if let Some((state, snippet, data)) = json_tree.and_then(|state| {
+        let snippet = snippets.iter().find(|s| s.id == state.snippet_id)?;
+        match &snippet.content {
+            SnippetContent::Json(data) => Some((state, snippet, data)),
+            _ => None,
+        }
+    }) {
+        json_tree::view_json_tree_modal(state, &snippet.label, data)
     } else if let Some(editor) = code_editor {
-        code_editor::view_code_editor_modal(editor)
+        code_editor::view_code_editor_modal(editor, code_theme.highlighter(), editing)
     } else if let Some(editor) = text_editor {
-        text_editor::view_text_editor_modal(editor)
+        text_editor::view_text_editor_modal(editor, editing)
     } else if add_menu_open {
         view_add_menu_dropdown()
-    } else if palette_dropdown_snippet.is_some() {
+    } else if let Some(snippet) =
+        card_menu_snippet.and_then(|id| snippets.iter().find(|s| s.id == id))
+    {
+        card_menu::view_card_menu(snippet)
+    } else if let Some(snippet_id) = palette_dropdown_snippet {
         // Palette assignment dropdown (shown over snippet card)
         view_palette_assignment_dropdown(
             palettes,
-            palette_dropdown_snippet.unwrap(),
+            snippet_id,
             snippet_palettes,
+            palette_dropdown_query,
+            palette_dropdown_cursor,
         )
     } else {
         // Empty overlay - preserves widget tree structure
-        container(text("")).width(0).height(0).into()
+        return stack![main_content, container(text("")).width(0).height(0)].into();
     };
 
-    stack![main_content, overlay].into()
+    // Tab stays within the dialog or menu on top
+    stack![inert(main_content), overlay].into()
 }
 
 /// Render a tab filter button.
-fn tab_button(label: &str, is_active: bool, on_press: Message) -> Element<'_, Message> {
+pub(crate) fn tab_button<'a>(
+    label: impl text::IntoFragment<'a>,
+    is_active: bool,
+    on_press: Message,
+) -> Element<'a, Message> {
     button(text(label).size(12))
         .on_press(on_press)
         .padding([SPACE_XS, SPACE_SM])
@@ -333,19 +987,281 @@ fn tab_button(label: &str, is_active: bool, on_press: Message) -> Element<'_, Me
         .into()
 }
 
+/// Get the snippets passing a filter, in the chosen order.
+fn filter_and_sort<'a>(
+    snippets: &'a [Snippet],
+    filter: &SnippetFilter<'_>,
+    snippet_palettes: &HashMap<i64, Vec<i64>>,
+    sort_order: SnippetSort,
+) -> Vec<&'a Snippet> {
+    let mut filtered: Vec<&Snippet> = snippets
+        .iter()
+        .filter(|s| filter.matches(s, snippet_palettes))
+        .collect();
+    match sort_order {
+        SnippetSort::Manual => {}
+        SnippetSort::Created => filtered.sort_by_key(|s| std::cmp::Reverse(s.created_at)),
+        SnippetSort::Updated => filtered.sort_by_key(|s| std::cmp::Reverse(s.updated_at)),
+        SnippetSort::MostUsed => {
+            filtered.sort_by_key(|s| std::cmp::Reverse((s.copy_count, s.last_copied_at)))
+        }
+    }
+    filtered
+}
+
 /// Render a snippet card based on its type.
-fn view_snippet_card(snippet: &Snippet, is_selected: bool) -> Element<'_, Message> {
-    match &snippet.content {
-        SnippetContent::Color(color) => {
-            view_color_card(snippet.id, &snippet.label, color, is_selected)
+fn view_snippet_card<'a>(
+    snippet: &'a Snippet,
+    is_selected: bool,
+    color_inputs: ColorCardInputs<'a>,
+    card_actions: CardActions,
+    code_theme: CodeTheme,
+    show_preview: bool,
+    stats: CardStats<'a>,
+) -> Element<'a, Message> {
+    let card = match &snippet.content {
+        SnippetContent::Color(color) => view_color_card(
+            snippet.id,
+            &snippet.label,
+            color,
+            stats,
+            is_selected,
+            color_inputs,
+            card_actions,
+        ),
+        SnippetContent::ColorSet(set) => view_color_set_card(
+            snippet.id,
+            &snippet.label,
+            set,
+            stats,
+            is_selected,
+            card_actions,
+        ),
+        SnippetContent::Gradient(gradient) => view_gradient_card(
+            snippet.id,
+            &snippet.label,
+            gradient,
+            stats,
+            is_selected,
+            card_actions,
+        ),
+        SnippetContent::Code(code) => {
+            view_code_card(snippet, code, stats, is_selected, code_theme, card_actions)
         }
-        SnippetContent::Code(code) => view_code_card(snippet.id, &snippet.label, code, is_selected),
-        SnippetContent::Text(text_data) => {
-            view_text_card(snippet.id, &snippet.label, text_data, is_selected)
+        SnippetContent::Json(json) => view_json_card(
+            snippet.id,
+            &snippet.label,
+            snippet.icon.as_deref(),
+            json,
+            stats,
+            is_selected,
+            card_actions,
+        ),
+        SnippetContent::Text(text_data) => view_text_card(
+            snippet.id,
+            &snippet.label,
+            snippet.icon.as_deref(),
+            text_data,
+            stats,
+            is_selected,
+            card_actions,
+        ),
+        SnippetContent::File(file) => view_file_card(
+            snippet.id,
+            &snippet.label,
+            snippet.icon.as_deref(),
+            file,
+            stats,
+            is_selected,
+            card_actions,
+        ),
+    };
+
+    // Only the hovered card builds its (syntax-highlighted) preview
+    let card = if show_preview {
+        preview::with_preview(card, snippet, code_theme.highlighter())
+    } else {
+        card
+    };
+
+    // Right-click opens the card context menu
+    mouse_area(card)
+        .on_right_press(Message::ToggleCardMenu(Some(snippet.id)))
+        .on_enter(Message::SnippetHovered(snippet.id))
+        .on_exit(Message::SnippetUnhovered(snippet.id))
+        .into()
+}
+
+/// Change and usage details shown on a snippet card.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct CardStats<'a> {
+    /// Last content or label change in Unix seconds.
+    pub updated_at: i64,
+    /// Number of times the snippet was copied.
+    pub copy_count: i64,
+    /// The linked file was edited or removed since it was linked.
+    pub file_changed: bool,
+    /// Application the snippet was captured from, when its badge is shown.
+    pub source_app: Option<&'a str>,
+    /// Notes shown under the label.
+    pub description: Option<&'a str>,
+}
+
+impl<'a> CardStats<'a> {
+    /// Get the card stats of a snippet.
+    pub fn of(snippet: &'a Snippet) -> Self {
+        Self {
+            updated_at: snippet.updated_at,
+            copy_count: snippet.copy_count,
+            file_changed: false,
+            source_app: None,
+            description: snippet.description.as_deref(),
         }
     }
 }
 
+/// Render how long ago a snippet was last changed and how often it was
+/// copied, with the app it was captured from (click to filter by it).
+pub(crate) fn view_age<'a>(stats: CardStats<'a>) -> Element<'a, Message> {
+    let age = relative_time(stats.updated_at, unix_now());
+    let label = match stats.copy_count {
+        0 => age,
+        1 => format!("{} · copied once", age),
+        n => format!("{} · copied {}×", age, n),
+    };
+    let label = text(label).size(10).color(TEXT_MUTED);
+    if !stats.file_changed && stats.source_app.is_none() {
+        return label.into();
+    }
+    let mut age_row = row![label]
+        .spacing(SPACE_XS)
+        .align_y(iced::Alignment::Center);
+    if let Some(app) = stats.source_app {
+        age_row = age_row.push(labeled(
+            button(text(app).size(10).color(TEXT_SECONDARY))
+                .on_press(Message::FilterSourceAppChanged(Some(app.to_string())))
+                .padding([1.0, SPACE_XS])
+                .style(subtle_button_style),
+            "Show snippets copied from this app",
+        ));
+    }
+    if stats.file_changed {
+        age_row = age_row.push(
+            row![
+                icons::warning().size(10).color(TEXT_SECONDARY),
+                text("file changed").size(10).color(TEXT_SECONDARY),
+            ]
+            .spacing(SPACE_XS)
+            .align_y(iced::Alignment::Center),
+        );
+    }
+    age_row.into()
+}
+
+/// Lines of a description shown on a card.
+const CARD_DESCRIPTION_LINES: usize = 2;
+
+/// Render a card's header row with the snippet's description under it.
+pub(crate) fn view_card_header<'a>(
+    header: impl Into<Element<'a, Message>>,
+    stats: CardStats<'a>,
+) -> Element<'a, Message> {
+    let Some(description) = stats.description else {
+        return header.into();
+    };
+    let mut lines = description.lines();
+    let mut shown = lines
+        .by_ref()
+        .take(CARD_DESCRIPTION_LINES)
+        .collect::<Vec<_>>()
+        .join("\n");
+    if lines.next().is_some() {
+        shown.push('…');
+    }
+    column![header.into(), text(shown).size(11).color(TEXT_MUTED)]
+        .spacing(2)
+        .into()
+}
+
+/// Get a snippet's description as the content of an editor's input.
+pub(crate) fn description_content(snippet: &Snippet) -> iced::widget::text_editor::Content {
+    iced::widget::text_editor::Content::with_text(snippet.description.as_deref().unwrap_or(""))
+}
+
+/// Render the description input of an editor modal.
+pub(crate) fn view_description_input<'a>(
+    content: &'a iced::widget::text_editor::Content,
+    on_action: fn(iced::widget::text_editor::Action) -> Message,
+) -> Element<'a, Message> {
+    row![
+        text("Notes:").size(12).color(TEXT_SECONDARY),
+        iced::widget::text_editor(content)
+            .placeholder("Why this snippet exists, caveats, links...")
+            .on_action(on_action)
+            .height(Length::Fixed(54.0))
+            .size(12)
+            .padding(SPACE_SM),
+    ]
+    .spacing(SPACE_SM)
+    .into()
+}
+
+/// Render the content fingerprint row of an editor, with a clipboard
+/// verification button when editing a stored snippet.
+pub(crate) fn view_content_hash<'a>(
+    content: &str,
+    editing_id: Option<i64>,
+) -> Element<'a, Message> {
+    let hash = text(format!("#{}", short_hash(content)))
+        .size(11)
+        .color(TEXT_MUTED);
+
+    let mut hash_row = row![hash]
+        .spacing(SPACE_SM)
+        .align_y(iced::Alignment::Center);
+    if let Some(id) = editing_id {
+        hash_row = hash_row.push(
+            button(text("Verify against clipboard").size(11))
+                .on_press(Message::VerifyAgainstClipboard(id))
+                .padding([2.0, SPACE_SM])
+                .style(subtle_button_style),
+        );
+    }
+    hash_row.into()
+}
+
+/// Which action buttons a snippet card renders.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ActionSet {
+    /// No action buttons.
+    None,
+    /// Only the copy button.
+    CopyOnly,
+    /// Every action button.
+    All,
+}
+
+impl ActionSet {
+    /// Check if the copy button is included.
+    pub(crate) fn shows_copy(self) -> bool {
+        self != ActionSet::None
+    }
+}
+
+/// Render a snippet card according to the card action visibility setting.
+/// `build` renders the card with a given set of actions; hidden actions are
+/// revealed by showing the full card while the cursor is over it.
+pub(crate) fn card_with_actions<'a>(
+    visibility: CardActions,
+    build: impl Fn(ActionSet) -> Element<'a, Message>,
+) -> Element<'a, Message> {
+    match visibility {
+        CardActions::Always => build(ActionSet::All),
+        CardActions::OnHover => hover(build(ActionSet::None), build(ActionSet::All)),
+        CardActions::Minimal => hover(build(ActionSet::CopyOnly), build(ActionSet::All)),
+    }
+}
+
 /// Render the add menu dropdown overlay.
 fn view_add_menu_dropdown() -> Element<'static, Message> {
     // Dropdown menu items
@@ -369,6 +1285,16 @@ fn view_add_menu_dropdown() -> Element<'static, Message> {
     .width(Length::Fill)
     .style(dropdown_item_style);
 
+    let json_item = button(
+        row![icons::brackets_curly().size(14), text("JSON").size(13)]
+            .spacing(SPACE_SM)
+            .align_y(iced::Alignment::Center),
+    )
+    .on_press(Message::OpenJsonEditor)
+    .padding([SPACE_SM, SPACE_MD])
+    .width(Length::Fill)
+    .style(dropdown_item_style);
+
     let text_item = button(
         row![icons::text_icon().size(14), text("Text").size(13)]
             .spacing(SPACE_SM)
@@ -379,28 +1305,204 @@ fn view_add_menu_dropdown() -> Element<'static, Message> {
     .width(Length::Fill)
     .style(dropdown_item_style);
 
-    // Menu container
-    let menu = container(column![color_item, code_item, text_item].spacing(2))
-        .padding(SPACE_XS)
-        .width(Length::Fixed(140.0))
-        .style(dropdown_menu_style);
+    let file_item = button(
+        row![icons::paperclip().size(14), text("File").size(13)]
+            .spacing(SPACE_SM)
+            .align_y(iced::Alignment::Center),
+    )
+    .on_press(Message::PickFilesToAttach)
+    .padding([SPACE_SM, SPACE_MD])
+    .width(Length::Fill)
+    .style(dropdown_item_style);
 
-    // Position menu at top-right using container alignment
-    let positioned_menu = container(menu)
-        .width(Length::Fill)
-        .padding(iced::Padding::new(0.0).top(52.0).left(SPACE_MD))
-        .align_x(iced::alignment::Horizontal::Left);
+    let scan_item = button(
+        row![icons::swatches().size(14), text("Scan Region").size(13)]
+            .spacing(SPACE_SM)
+            .align_y(iced::Alignment::Center),
+    )
+    .on_press(Message::ScanRegion)
+    .padding([SPACE_SM, SPACE_MD])
+    .width(Length::Fill)
+    .style(dropdown_item_style);
 
-    // Click-outside-to-close overlay (transparent, minimal overhead)
-    mouse_area(positioned_menu)
-        .on_press(Message::CloseAddMenu)
-        .into()
+    let image_item = button(
+        row![icons::image().size(14), text("From Image").size(13)]
+            .spacing(SPACE_SM)
+            .align_y(iced::Alignment::Center),
+    )
+    .on_press(Message::ExtractImagePalette)
+    .padding([SPACE_SM, SPACE_MD])
+    .width(Length::Fill)
+    .style(dropdown_item_style);
+
+    let gradient_item = button(
+        row![icons::gradient().size(14), text("Gradient").size(13)]
+            .spacing(SPACE_SM)
+            .align_y(iced::Alignment::Center),
+    )
+    .on_press(Message::OpenGradientEditor(None))
+    .padding([SPACE_SM, SPACE_MD])
+    .width(Length::Fill)
+    .style(dropdown_item_style);
+
+    let section_item = button(
+        row![icons::rows().size(14), text("Section").size(13)]
+            .spacing(SPACE_SM)
+            .align_y(iced::Alignment::Center),
+    )
+    .on_press(Message::NewSection(None))
+    .padding([SPACE_SM, SPACE_MD])
+    .width(Length::Fill)
+    .style(dropdown_item_style);
+
+    // Menu container
+    let menu = container(
+        column![
+            color_item,
+            code_item,
+            json_item,
+            text_item,
+            file_item,
+            scan_item,
+            image_item,
+            gradient_item,
+            section_item
+        ]
+        .spacing(2),
+    )
+    .padding(SPACE_XS)
+    .width(Length::Fixed(140.0))
+    .style(dropdown_menu_style);
+
+    // Position menu at top-right using container alignment
+    let positioned_menu = container(menu)
+        .width(Length::Fill)
+        .padding(iced::Padding::new(0.0).top(52.0).left(SPACE_MD))
+        .align_x(iced::alignment::Horizontal::Left);
+
+    // Click-outside-to-close overlay (transparent, minimal overhead)
+    mouse_area(positioned_menu)
+        .on_press(Message::CloseAddMenu)
+        .into()
+}
+
+/// A language facet entry shown in the language filter dropdown.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LanguageFacet {
+    /// The language to filter by (None = all languages).
+    pub language: Option<String>,
+    /// Number of code snippets with this language.
+    pub count: usize,
+}
+
+impl std::fmt::Display for LanguageFacet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.language {
+            Some(language) => write!(f, "{} ({})", language, self.count),
+            None => write!(f, "All languages ({})", self.count),
+        }
+    }
+}
+
+/// Render the language facet dropdown in header.
+/// Returns None when there are no code snippets to filter.
+fn view_language_filter<'a>(
+    snippets: &'a [Snippet],
+    filter_language: Option<&'a str>,
+) -> Option<Element<'a, Message>> {
+    let counts = language_counts(snippets);
+    if counts.is_empty() {
+        return None;
+    }
+
+    let total = counts.iter().map(|(_, count)| count).sum();
+    let mut facets = vec![LanguageFacet {
+        language: None,
+        count: total,
+    }];
+    facets.extend(counts.into_iter().map(|(language, count)| LanguageFacet {
+        language: Some(language),
+        count,
+    }));
+
+    let selected = facets
+        .iter()
+        .find(|f| f.language.as_deref() == filter_language)
+        .cloned();
+
+    Some(
+        pick_list(facets, selected, |facet: LanguageFacet| {
+            Message::FilterLanguageChanged(facet.language)
+        })
+        .text_size(12)
+        .padding([SPACE_XS, SPACE_SM])
+        .style(pick_list_style)
+        .into(),
+    )
+}
+
+/// A source application entry shown in the source filter dropdown.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceAppFacet {
+    /// The application to filter by (None = all sources).
+    pub source_app: Option<String>,
+    /// Number of snippets captured from it.
+    pub count: usize,
+}
+
+impl std::fmt::Display for SourceAppFacet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.source_app {
+            Some(app) => write!(f, "{} ({})", app, self.count),
+            None => write!(f, "All sources ({})", self.count),
+        }
+    }
 }
 
-/// Render the palette filter dropdown in header.
+/// Render the source application facet dropdown in header.
+/// Returns None when no snippet has a recorded source.
+fn view_source_app_filter<'a>(
+    snippets: &'a [Snippet],
+    filter_source_app: Option<&'a str>,
+) -> Option<Element<'a, Message>> {
+    let counts = source_app_counts(snippets);
+    if counts.is_empty() {
+        return None;
+    }
+
+    let total = counts.iter().map(|(_, count)| count).sum();
+    let mut facets = vec![SourceAppFacet {
+        source_app: None,
+        count: total,
+    }];
+    facets.extend(counts.into_iter().map(|(app, count)| SourceAppFacet {
+        source_app: Some(app),
+        count,
+    }));
+
+    let selected = facets
+        .iter()
+        .find(|f| f.source_app.as_deref() == filter_source_app)
+        .cloned();
+
+    Some(
+        pick_list(facets, selected, |facet: SourceAppFacet| {
+            Message::FilterSourceAppChanged(facet.source_app)
+        })
+        .text_size(12)
+        .padding([SPACE_XS, SPACE_SM])
+        .style(pick_list_style)
+        .into(),
+    )
+}
+
+/// Render the palette filter in the header, with nested palettes as expandable branches.
 fn view_palette_filter<'a>(
     palettes: &'a [Palette],
     filter_palette: Option<i64>,
+    expanded_palettes: &'a HashSet<i64>,
+    snippets: &'a [Snippet],
+    snippet_palettes: &'a HashMap<i64, Vec<i64>>,
 ) -> Element<'a, Message> {
     // Create palette selection buttons
     let all_btn = button(text("All Palettes").size(12))
@@ -412,41 +1514,229 @@ fn view_palette_filter<'a>(
             secondary_button_style
         });
 
-    let palette_buttons: Vec<Element<'a, Message>> = palettes
+    let palette_buttons = view_palette_branch(
+        palettes,
+        None,
+        filter_palette,
+        expanded_palettes,
+        snippets,
+        snippet_palettes,
+    );
+
+    // Manage palettes button
+    let manage_btn = labeled(
+        button(icons::tag().size(12))
+            .on_press(Message::OpenPaletteManager)
+            .padding([SPACE_XS, SPACE_SM])
+            .style(subtle_button_style),
+        "Manage palettes",
+    );
+
+    let mut items: Vec<Element<'a, Message>> = vec![all_btn.into()];
+    items.extend(palette_buttons);
+    items.push(manage_btn);
+
+    row(items).spacing(SPACE_XS).into()
+}
+
+/// Render the palettes directly inside `parent` (the top level for `None`).
+/// Expanded palettes show their nested palettes in a group after their button.
+fn view_palette_branch<'a>(
+    palettes: &'a [Palette],
+    parent: Option<i64>,
+    filter_palette: Option<i64>,
+    expanded_palettes: &'a HashSet<i64>,
+    snippets: &'a [Snippet],
+    snippet_palettes: &'a HashMap<i64, Vec<i64>>,
+) -> Vec<Element<'a, Message>> {
+    palettes
         .iter()
+        .filter(|p| p.parent_id == parent)
         .map(|p| {
             let is_selected = filter_palette == Some(p.id);
-            button(text(&p.name).size(12))
+            // Smart palettes are marked with a funnel
+            let name: Element<'a, Message> = if p.is_smart() {
+                row![icons::funnel().size(11), text(&p.name).size(12)]
+                    .spacing(2)
+                    .align_y(iced::Alignment::Center)
+                    .into()
+            } else {
+                text(&p.name).size(12).into()
+            };
+            let label = column![
+                name,
+                view_palette_swatch_strip(p, palettes, snippets, snippet_palettes)
+            ]
+            .spacing(2)
+            .align_x(iced::Alignment::Center);
+            let palette_btn = button(label)
                 .on_press(Message::FilterPaletteChanged(Some(p.id)))
                 .padding([SPACE_XS, SPACE_SM])
                 .style(if is_selected {
                     primary_button_style
                 } else {
                     secondary_button_style
+                });
+
+            if !palettes.iter().any(|child| child.parent_id == Some(p.id)) {
+                return palette_btn.into();
+            }
+
+            let expanded = expanded_palettes.contains(&p.id);
+            let toggle_btn = button(
+                if expanded {
+                    icons::caret_left()
+                } else {
+                    icons::caret_right()
+                }
+                .size(10),
+            )
+            .on_press(Message::TogglePaletteExpanded(p.id))
+            .padding(SPACE_XS)
+            .style(subtle_button_style);
+
+            let mut branch = row![palette_btn, toggle_btn]
+                .spacing(2)
+                .align_y(iced::Alignment::Center);
+            if expanded {
+                branch = branch.push(
+                    row(view_palette_branch(
+                        palettes,
+                        Some(p.id),
+                        filter_palette,
+                        expanded_palettes,
+                        snippets,
+                        snippet_palettes,
+                    ))
+                    .spacing(SPACE_XS),
+                );
+            }
+            container(branch)
+                .padding(2)
+                .style(button_group_style)
+                .into()
+        })
+        .collect()
+}
+
+/// Render a strip of the first few member colors of a palette.
+fn view_palette_swatch_strip<'a>(
+    palette: &'a Palette,
+    palettes: &'a [Palette],
+    snippets: &'a [Snippet],
+    snippet_palettes: &'a HashMap<i64, Vec<i64>>,
+) -> Element<'a, Message> {
+    let rules = palette_rules(palettes, Some(palette.id));
+    let members = SnippetFilter {
+        palette: rules.as_deref(),
+        ..Default::default()
+    };
+    let swatches: Vec<Element<'a, Message>> = snippets
+        .iter()
+        .filter(|s| members.matches(s, snippet_palettes))
+        .filter_map(|s| match &s.content {
+            SnippetContent::Color(color) => Some(color.to_iced_color()),
+            _ => None,
+        })
+        .take(PALETTE_STRIP_SWATCHES)
+        .map(|color| {
+            container(text(""))
+                .width(Length::Fixed(8.0))
+                .height(Length::Fixed(4.0))
+                .style(move |_theme| {
+                    iced::widget::container::Style::default()
+                        .background(color)
+                        .border(iced::Border::default().rounded(1.0))
                 })
                 .into()
         })
         .collect();
 
-    // Manage palettes button
-    let manage_btn = button(icons::tag().size(12))
-        .on_press(Message::OpenPaletteManager)
-        .padding([SPACE_XS, SPACE_SM])
-        .style(subtle_button_style);
+    row(swatches).spacing(1).into()
+}
 
-    let mut items: Vec<Element<'a, Message>> = vec![all_btn.into()];
-    items.extend(palette_buttons);
-    items.push(manage_btn.into());
+/// Widget ID of the palette dropdown's filter input.
+pub const PALETTE_SEARCH_ID: &str = "palette-assignment-search";
 
-    row(items).spacing(SPACE_XS).into()
+/// Get the palettes whose name contains the query (case-insensitive).
+pub fn filter_palettes<'a>(palettes: &'a [Palette], query: &str) -> Vec<&'a Palette> {
+    let query = query.to_lowercase();
+    palettes
+        .iter()
+        // Smart palette members come from their query, not assignment
+        .filter(|p| !p.is_smart() && p.name.to_lowercase().contains(&query))
+        .collect()
+}
+
+/// Get the membership rules of a palette and its nested palettes.
+pub fn palette_rules(
+    palettes: &[Palette],
+    palette_id: Option<i64>,
+) -> Option<Vec<PaletteRule<'_>>> {
+    let palette_id = palette_id?;
+    Some(
+        db::palette_with_descendants(palettes, palette_id)
+            .into_iter()
+            .map(|p| match &p.query {
+                Some(query) => PaletteRule::Query(query),
+                None => PaletteRule::Members(p.id),
+            })
+            .collect(),
+    )
+}
+
+/// A parent choice in the palette manager's "move to" dropdown.
+#[derive(Debug, Clone, PartialEq)]
+struct ParentChoice {
+    parent_id: Option<i64>,
+    name: String,
+}
+
+impl std::fmt::Display for ParentChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+/// A choice in the palette manager's dark variant dropdown.
+#[derive(Debug, Clone, PartialEq)]
+struct DarkVariantChoice {
+    palette_id: Option<i64>,
+    name: String,
+}
+
+impl std::fmt::Display for DarkVariantChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+/// Order palettes depth-first, each followed by its nested palettes, with their depth.
+fn palette_tree(palettes: &[Palette]) -> Vec<(&Palette, usize)> {
+    fn visit<'a>(
+        palettes: &'a [Palette],
+        parent: Option<i64>,
+        depth: usize,
+        out: &mut Vec<(&'a Palette, usize)>,
+    ) {
+        for palette in palettes.iter().filter(|p| p.parent_id == parent) {
+            out.push((palette, depth));
+            visit(palettes, Some(palette.id), depth + 1, out);
+        }
+    }
+
+    let mut out = Vec::new();
+    visit(palettes, None, 0, &mut out);
+    out
 }
 
 /// Render the palette manager modal.
 fn view_palette_manager_modal<'a>(
     palettes: &'a [Palette],
     new_palette_name: &'a str,
+    new_palette_query: &'a str,
 ) -> Element<'a, Message> {
-    use crate::theme::{modal_dialog_style, modal_overlay_style};
+    use crate::theme::{modal_dialog_style, modal_overlay_style, DANGER};
 
     // Title
     let title = text("Manage Palettes").size(18);
@@ -468,19 +1758,189 @@ fn view_palette_manager_modal<'a>(
         .spacing(SPACE_SM)
         .align_y(iced::Alignment::Center);
 
-    // Palette list
-    let palette_items: Vec<Element<'a, Message>> = palettes
-        .iter()
-        .map(|p| {
-            let delete_btn = button(icons::trash().size(14))
-                .on_press(Message::DeletePalette(p.id))
-                .padding(SPACE_XS)
-                .style(subtle_button_style);
+    // Optional query turning the new palette into a smart palette
+    let query_error = if new_palette_query.trim().is_empty() {
+        None
+    } else {
+        PaletteQuery::parse(new_palette_query).err()
+    };
+    let has_query_error = query_error.is_some();
+    let query_input = text_input(
+        "Smart palette query (optional), e.g. kind=code AND language=rust",
+        new_palette_query,
+    )
+    .on_input(Message::NewPaletteQueryChanged)
+    .on_submit(Message::CreatePalette(new_palette_name.to_string()))
+    .padding(SPACE_SM)
+    .size(12)
+    .width(Length::Fill)
+    .style(move |theme, status| input_style(theme, status, has_query_error));
+    let query_hint = match query_error {
+        Some(e) => text(e).size(11).color(DANGER),
+        None => text("Fields: kind, language, label, content · =, !=, contains · join with AND")
+            .size(11)
+            .color(TEXT_MUTED),
+    };
+    let new_section = column![new_row, query_input, query_hint].spacing(SPACE_XS);
 
-            container(
-                row![text(&p.name).size(14).width(Length::Fill), delete_btn,]
-                    .spacing(SPACE_SM)
+    // Palette list, nested palettes indented below their parent
+    let palette_items: Vec<Element<'a, Message>> = palette_tree(palettes)
+        .into_iter()
+        .map(|(p, depth)| {
+            // A palette can move anywhere except inside itself
+            let subtree = db::palette_with_descendants(palettes, p.id);
+            let parent_choices: Vec<ParentChoice> = std::iter::once(ParentChoice {
+                parent_id: None,
+                name: "Top level".to_string(),
+            })
+            .chain(
+                palettes
+                    .iter()
+                    .filter(|other| !subtree.iter().any(|s| s.id == other.id))
+                    .map(|other| ParentChoice {
+                        parent_id: Some(other.id),
+                        name: db::palette_path(palettes, other.id),
+                    }),
+            )
+            .collect();
+            let selected_parent = parent_choices
+                .iter()
+                .find(|choice| choice.parent_id == p.parent_id)
+                .cloned();
+            let palette_id = p.id;
+            let parent_picker = pick_list(
+                parent_choices,
+                selected_parent,
+                move |choice: ParentChoice| Message::SetPaletteParent(palette_id, choice.parent_id),
+            )
+            .text_size(11)
+            .padding([SPACE_XS, SPACE_SM])
+            .width(Length::Fixed(110.0))
+            .style(pick_list_style);
+
+            // Mobile asset export pairs colors with those of the dark variant
+            let dark_choices: Vec<DarkVariantChoice> = std::iter::once(DarkVariantChoice {
+                palette_id: None,
+                name: "No dark variant".to_string(),
+            })
+            .chain(
+                palettes
+                    .iter()
+                    .filter(|other| other.id != p.id)
+                    .map(|other| DarkVariantChoice {
+                        palette_id: Some(other.id),
+                        name: db::palette_path(palettes, other.id),
+                    }),
+            )
+            .collect();
+            let selected_dark = dark_choices
+                .iter()
+                .find(|choice| choice.palette_id == p.dark_palette_id)
+                .cloned();
+            let dark_picker = labeled(
+                pick_list(
+                    dark_choices,
+                    selected_dark,
+                    move |choice: DarkVariantChoice| {
+                        Message::SetPaletteDarkVariant(palette_id, choice.palette_id)
+                    },
+                )
+                .text_size(11)
+                .padding([SPACE_XS, SPACE_SM])
+                .width(Length::Fixed(110.0))
+                .style(pick_list_style),
+                "Dark variant",
+            );
+
+            let board_btn = labeled(
+                button(icons::push_pin().size(14))
+                    .on_press(Message::PinPaletteBoard(p.id))
+                    .padding(SPACE_XS)
+                    .style(subtle_button_style),
+                "Pin as board",
+            );
+
+            let export_btn = labeled(
+                button(icons::export().size(14))
+                    .on_press(Message::ExportPalette(p.id))
+                    .padding(SPACE_XS)
+                    .style(subtle_button_style),
+                "Export palette",
+            );
+
+            let editor_snippets_btn = labeled(
+                button(icons::brackets_curly().size(14))
+                    .on_press(Message::ExportEditorSnippets(Some(p.id)))
+                    .padding(SPACE_XS)
+                    .style(subtle_button_style),
+                "Export as editor snippets",
+            );
+
+            let mobile_btn = labeled(
+                button(icons::device_mobile().size(14))
+                    .on_press(Message::ExportPaletteAssets(p.id))
+                    .padding(SPACE_XS)
+                    .style(subtle_button_style),
+                "Export for mobile",
+            );
+
+            let code_btn = labeled(
+                button(icons::code().size(14))
+                    .on_press(Message::OpenCodeExport(p.id))
+                    .padding(SPACE_XS)
+                    .style(subtle_button_style),
+                "Export as code",
+            );
+
+            let format_picker = labeled(
+                pick_list(ExportFormat::ALL, None::<ExportFormat>, move |format| {
+                    Message::ExportColors(Some(palette_id), format)
+                })
+                .placeholder("Export as")
+                .text_size(11)
+                .padding([SPACE_XS, SPACE_SM])
+                .width(Length::Fixed(90.0))
+                .style(pick_list_style),
+                "Export for another tool",
+            );
+
+            let delete_btn = labeled(
+                button(icons::trash().size(14))
+                    .on_press(Message::DeletePalette(p.id))
+                    .padding(SPACE_XS)
+                    .style(subtle_button_style),
+                "Delete palette",
+            );
+
+            let mut name = column![text(&p.name).size(14)]
+                .width(Length::Fill)
+                .padding(iced::Padding::ZERO.left(depth as f32 * SPACE_MD));
+            if let Some(query) = &p.query {
+                name = name.push(
+                    row![
+                        icons::funnel().size(11).color(TEXT_MUTED),
+                        text(query.to_string()).size(11).color(TEXT_MUTED)
+                    ]
+                    .spacing(SPACE_XS)
                     .align_y(iced::Alignment::Center),
+                );
+            }
+
+            container(
+                row![
+                    name,
+                    parent_picker,
+                    dark_picker,
+                    board_btn,
+                    export_btn,
+                    editor_snippets_btn,
+                    mobile_btn,
+                    code_btn,
+                    format_picker,
+                    delete_btn
+                ]
+                .spacing(SPACE_SM)
+                .align_y(iced::Alignment::Center),
             )
             .padding(SPACE_SM)
             .style(|_theme| {
@@ -508,20 +1968,42 @@ fn view_palette_manager_modal<'a>(
             .into()
     };
 
-    // Close button
+    // Footer buttons
+    let import_btn = button(text("Import .shardpalette…").size(13))
+        .on_press(Message::ImportPaletteFile)
+        .padding([SPACE_SM, SPACE_MD])
+        .style(secondary_button_style);
+
     let close_btn = button(text("Close").size(13))
         .on_press(Message::ClosePaletteManager)
         .padding([SPACE_SM, SPACE_MD])
         .style(secondary_button_style);
 
+    let export_all = pick_list(ExportFormat::ALL, None::<ExportFormat>, |format| {
+        Message::ExportColors(None, format)
+    })
+    .placeholder("Export all colors as")
+    .text_size(13)
+    .padding([SPACE_SM, SPACE_MD])
+    .style(pick_list_style);
+
+    let footer = row![
+        import_btn,
+        export_all,
+        iced::widget::Space::new().width(Length::Fill),
+        close_btn
+    ]
+    .spacing(SPACE_SM)
+    .align_y(iced::Alignment::Center);
+
     // Modal content
     let modal_content = container(
-        column![title, new_row, palette_list, close_btn]
+        column![title, new_section, palette_list, footer]
             .spacing(SPACE_MD)
             .align_x(iced::Alignment::End),
     )
     .padding(SPACE_LG)
-    .width(Length::Fixed(400.0))
+    .width(Length::Fixed(580.0))
     .style(modal_dialog_style);
 
     // Center modal
@@ -542,20 +2024,242 @@ fn view_palette_manager_modal<'a>(
     .into()
 }
 
+/// Render the pinned windows management panel.
+fn view_pinned_panel_modal(
+    snippets: &[Snippet],
+    pinned_windows: Vec<(window::Id, i64)>,
+) -> Element<'_, Message> {
+    use crate::theme::{modal_dialog_style, modal_overlay_style};
+
+    let title = text("Pinned Windows").size(18);
+
+    let items: Vec<Element<'_, Message>> = pinned_windows
+        .into_iter()
+        .map(|(window_id, snippet_id)| {
+            let label = snippets
+                .iter()
+                .find(|s| s.id == snippet_id)
+                .map(|s| s.label.as_str())
+                .unwrap_or("(deleted snippet)");
+
+            let focus_btn = labeled(
+                button(icons::eye().size(14))
+                    .on_press(Message::FocusPinnedWindow(window_id))
+                    .padding(SPACE_XS)
+                    .style(subtle_button_style),
+                "Show window",
+            );
+
+            let close_btn = labeled(
+                button(icons::x().size(14))
+                    .on_press(Message::UnpinSnippet(window_id))
+                    .padding(SPACE_XS)
+                    .style(subtle_button_style),
+                "Unpin",
+            );
+
+            container(
+                row![
+                    text(label).size(14).width(Length::Fill),
+                    focus_btn,
+                    close_btn
+                ]
+                .spacing(SPACE_SM)
+                .align_y(iced::Alignment::Center),
+            )
+            .padding(SPACE_SM)
+            .style(|_theme| {
+                iced::widget::container::Style::default()
+                    .background(crate::theme::BG_ELEVATED)
+                    .border(iced::Border::default().rounded(4.0))
+            })
+            .into()
+        })
+        .collect();
+
+    let list: Element<'_, Message> = if items.is_empty() {
+        container(
+            text("No pinned windows. Pin a snippet from its card.")
+                .size(13)
+                .color(TEXT_MUTED),
+        )
+        .padding(SPACE_MD)
+        .center_x(Length::Fill)
+        .into()
+    } else {
+        scrollable(column(items).spacing(SPACE_XS))
+            .height(Length::Fixed(200.0))
+            .style(scrollbar_style)
+            .into()
+    };
+
+    let close_btn = button(text("Close").size(13))
+        .on_press(Message::ClosePinnedPanel)
+        .padding([SPACE_SM, SPACE_MD])
+        .style(secondary_button_style);
+
+    let modal_content = container(
+        column![title, list, close_btn]
+            .spacing(SPACE_MD)
+            .align_x(iced::Alignment::End),
+    )
+    .padding(SPACE_LG)
+    .width(Length::Fixed(400.0))
+    .style(modal_dialog_style);
+
+    let centered = container(modal_content)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .center_x(Length::Fill)
+        .center_y(Length::Fill);
+
+    mouse_area(
+        container(centered)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .style(modal_overlay_style),
+    )
+    .on_press(Message::ClosePinnedPanel)
+    .into()
+}
+
+/// Render the inbox triage modal for auto-captured snippets.
+fn view_inbox_modal(snippets: &[Snippet], cursor: usize) -> Element<'_, Message> {
+    use crate::theme::{modal_dialog_style, modal_overlay_style, BORDER_ACCENT};
+
+    let title = text("Inbox").size(18);
+    let hint = text("J/K to move, Enter to keep, X to discard")
+        .size(11)
+        .color(TEXT_MUTED);
+
+    let items: Vec<Element<'_, Message>> = snippets
+        .iter()
+        .filter(|s| s.in_inbox)
+        .enumerate()
+        .map(|(index, snippet)| {
+            let is_current = index == cursor;
+
+            let keep_btn = labeled(
+                button(icons::check().size(14))
+                    .on_press(Message::KeepInboxSnippet(snippet.id))
+                    .padding(SPACE_XS)
+                    .style(subtle_button_style),
+                "Keep",
+            );
+
+            let discard_btn = labeled(
+                button(icons::trash().size(14))
+                    .on_press(Message::DeleteSnippet(snippet.id))
+                    .padding(SPACE_XS)
+                    .style(subtle_button_style),
+                "Discard",
+            );
+
+            let summary = column![
+                text(&snippet.label).size(13),
+                text(snippet.content.preview(60)).size(11).color(TEXT_MUTED),
+            ]
+            .spacing(2)
+            .width(Length::Fill);
+
+            container(
+                row![summary, keep_btn, discard_btn]
+                    .spacing(SPACE_SM)
+                    .align_y(iced::Alignment::Center),
+            )
+            .padding(SPACE_SM)
+            .style(move |_theme| {
+                let border = if is_current {
+                    iced::Border::default()
+                        .rounded(4.0)
+                        .width(1.0)
+                        .color(BORDER_ACCENT)
+                } else {
+                    iced::Border::default().rounded(4.0)
+                };
+                iced::widget::container::Style::default()
+                    .background(crate::theme::BG_ELEVATED)
+                    .border(border)
+            })
+            .into()
+        })
+        .collect();
+
+    let list: Element<'_, Message> = if items.is_empty() {
+        container(
+            text("Inbox is empty. Clipboard captures land here for review.")
+                .size(13)
+                .color(TEXT_MUTED),
+        )
+        .padding(SPACE_MD)
+        .center_x(Length::Fill)
+        .into()
+    } else {
+        scrollable(column(items).spacing(SPACE_XS))
+            .height(Length::Fixed(300.0))
+            .style(scrollbar_style)
+            .into()
+    };
+
+    let close_btn = button(text("Close").size(13))
+        .on_press(Message::CloseInbox)
+        .padding([SPACE_SM, SPACE_MD])
+        .style(secondary_button_style);
+
+    let modal_content = container(
+        column![column![title, hint].spacing(SPACE_XS), list, close_btn]
+            .spacing(SPACE_MD)
+            .align_x(iced::Alignment::End),
+    )
+    .padding(SPACE_LG)
+    .width(Length::Fixed(440.0))
+    .style(modal_dialog_style);
+
+    let centered = container(modal_content)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .center_x(Length::Fill)
+        .center_y(Length::Fill);
+
+    mouse_area(
+        container(centered)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .style(modal_overlay_style),
+    )
+    .on_press(Message::CloseInbox)
+    .into()
+}
+
 /// Render palette assignment dropdown overlay.
 fn view_palette_assignment_dropdown<'a>(
     palettes: &'a [Palette],
     snippet_id: i64,
     snippet_palettes: &'a HashMap<i64, Vec<i64>>,
+    query: &'a str,
+    cursor: usize,
 ) -> Element<'a, Message> {
+    use crate::theme::BORDER_ACCENT;
+
     let snippet_palette_ids = snippet_palettes
         .get(&snippet_id)
         .cloned()
         .unwrap_or_default();
 
-    let items: Vec<Element<'a, Message>> = palettes
-        .iter()
-        .map(|p| {
+    // Type to filter, Up/Down to move, Enter to toggle the highlighted palette
+    let search = text_input("Filter palettes...", query)
+        .id(PALETTE_SEARCH_ID)
+        .on_input(Message::PaletteDropdownQueryChanged)
+        .on_submit(Message::ToggleHighlightedPalette)
+        .padding([SPACE_XS, SPACE_SM])
+        .size(12)
+        .style(|theme, status| input_style(theme, status, false));
+
+    let items: Vec<Element<'a, Message>> = filter_palettes(palettes, query)
+        .into_iter()
+        .enumerate()
+        .map(|(index, p)| {
+            let is_highlighted = index == cursor;
             let is_in_palette = snippet_palette_ids.contains(&p.id);
             let msg = if is_in_palette {
                 Message::RemoveSnippetFromPalette(snippet_id, p.id)
@@ -575,12 +2279,25 @@ fn view_palette_assignment_dropdown<'a>(
             .on_press(msg)
             .padding([SPACE_SM, SPACE_MD])
             .width(Length::Fill)
-            .style(dropdown_item_style)
+            .style(move |theme, status| {
+                let style = dropdown_item_style(theme, status);
+                if is_highlighted {
+                    button::Style {
+                        border: iced::Border::default()
+                            .rounded(4.0)
+                            .width(1.0)
+                            .color(BORDER_ACCENT),
+                        ..style
+                    }
+                } else {
+                    style
+                }
+            })
             .into()
         })
         .collect();
 
-    let menu_content: Element<'a, Message> = if items.is_empty() {
+    let list: Element<'a, Message> = if palettes.is_empty() {
         container(
             text("No palettes. Create one in settings.")
                 .size(12)
@@ -588,9 +2305,14 @@ fn view_palette_assignment_dropdown<'a>(
         )
         .padding(SPACE_SM)
         .into()
+    } else if items.is_empty() {
+        container(text("No matching palettes").size(12).color(TEXT_MUTED))
+            .padding(SPACE_SM)
+            .into()
     } else {
         column(items).spacing(2).into()
     };
+    let menu_content = column![search, list].spacing(SPACE_XS);
 
     let menu = container(menu_content)
         .padding(SPACE_XS)
@@ -0,0 +1,138 @@
+//! Pinned palette board: a palette's snippets in an always-on-top window,
+//! the first nine copied with the number keys, or from any application with
+//! the board's global hotkey.
+
+use iced::widget::{button, column, container, pick_list, row, scrollable, text};
+use iced::{Element, Length};
+
+use crate::config::BoardHotkey;
+use crate::icons;
+use crate::message::Message;
+use crate::snippet::{Snippet, SnippetContent};
+use crate::theme::{
+    pick_list_style, scrollbar_style, subtle_button_style, BG_ELEVATED, BORDER_SUBTLE, SPACE_SM,
+    SPACE_XS, TEXT_MUTED, TEXT_PRIMARY, TEXT_SECONDARY,
+};
+
+/// Number of snippets reachable with the number keys 1–9.
+pub const BOARD_KEYS: usize = 9;
+
+/// Render the snippets of a palette board, numbered for the number keys.
+pub fn view_board_snippets<'a>(
+    snippets: &[&'a Snippet],
+    hotkey: BoardHotkey,
+) -> Element<'a, Message> {
+    if snippets.is_empty() {
+        return text("This palette has no snippets")
+            .size(12)
+            .color(TEXT_MUTED)
+            .into();
+    }
+
+    let rows: Vec<Element<'a, Message>> = snippets
+        .iter()
+        .enumerate()
+        .map(|(index, snippet)| view_board_row(index, snippet))
+        .collect();
+
+    column![
+        scrollable(column(rows).spacing(SPACE_XS))
+            .height(Length::Fill)
+            .style(scrollbar_style),
+        text(match hotkey.modifiers() {
+            Some(modifiers) => format!(
+                "Press 1–{0} to copy, or {1}+1–{0} anywhere",
+                snippets.len().min(BOARD_KEYS),
+                modifiers
+            ),
+            None => format!("Press 1–{} to copy", snippets.len().min(BOARD_KEYS)),
+        })
+        .size(10)
+        .color(TEXT_MUTED),
+    ]
+    .spacing(SPACE_SM)
+    .into()
+}
+
+/// Render the choice of the board's global hotkey.
+pub fn view_board_hotkey<'a>(palette_id: i64, hotkey: BoardHotkey) -> Element<'a, Message> {
+    row![
+        text("Global keys:").size(11).color(TEXT_MUTED),
+        pick_list(BoardHotkey::ALL, Some(hotkey), move |hotkey| {
+            Message::BoardHotkeyChanged(palette_id, hotkey)
+        })
+        .text_size(11)
+        .padding([2.0, SPACE_SM])
+        .style(pick_list_style),
+    ]
+    .spacing(SPACE_XS)
+    .align_y(iced::Alignment::Center)
+    .into()
+}
+
+/// Render one board entry; clicking it copies the snippet.
+fn view_board_row<'a>(index: usize, snippet: &'a Snippet) -> Element<'a, Message> {
+    let number = if index < BOARD_KEYS {
+        (index + 1).to_string()
+    } else {
+        String::new()
+    };
+    let key_badge = container(text(number).size(11).color(TEXT_SECONDARY))
+        .width(Length::Fixed(20.0))
+        .center_x(Length::Fixed(20.0))
+        .style(|_theme| {
+            container::Style::default()
+                .background(BG_ELEVATED)
+                .border(iced::Border::default().rounded(3.0))
+        });
+
+    let marker = view_snippet_marker(snippet);
+
+    button(
+        row![
+            key_badge,
+            marker,
+            text(&snippet.label)
+                .size(12)
+                .color(TEXT_PRIMARY)
+                .width(Length::Fill)
+        ]
+        .spacing(SPACE_SM)
+        .align_y(iced::Alignment::Center),
+    )
+    .on_press(Message::CopySnippet(snippet.id))
+    .padding([SPACE_XS, SPACE_SM])
+    .width(Length::Fill)
+    .style(subtle_button_style)
+    .into()
+}
+
+/// Render a small marker of a snippet's kind: its swatch for a color, an
+/// icon otherwise.
+pub(crate) fn view_snippet_marker<'a>(snippet: &'a Snippet) -> Element<'a, Message> {
+    match &snippet.content {
+        SnippetContent::Color(color) => {
+            let color = color.to_iced_color();
+            container(text(""))
+                .width(Length::Fixed(14.0))
+                .height(Length::Fixed(14.0))
+                .style(move |_theme| {
+                    container::Style::default().background(color).border(
+                        iced::Border::default()
+                            .rounded(3.0)
+                            .width(1.0)
+                            .color(BORDER_SUBTLE),
+                    )
+                })
+                .into()
+        }
+        SnippetContent::ColorSet(_) => icons::swatches().size(14).into(),
+        SnippetContent::Gradient(_) => icons::gradient().size(14).into(),
+        SnippetContent::Code(_) => icons::code().size(14).into(),
+        SnippetContent::Json(_) => icons::brackets_curly().size(14).into(),
+        SnippetContent::Text(_) => icons::text_icon().size(14).into(),
+        SnippetContent::File(file) => icons::file_icon(file.extension().as_deref())
+            .size(14)
+            .into(),
+    }
+}
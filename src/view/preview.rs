@@ -0,0 +1,173 @@
+//! Larger snippet previews shown when hovering a card.
+
+use std::time::Duration;
+
+use iced::highlighter;
+use iced::widget::{column, container, rich_text, row, text, tooltip, Canvas};
+use iced::{Element, Font, Length};
+
+use crate::db::format_size;
+use crate::message::Message;
+use crate::snippet::{
+    language_to_extension, nearest_named_color, ColorData, Snippet, SnippetContent,
+};
+use crate::theme::{
+    dropdown_menu_style, SPACE_SM, SPACE_XS, TEXT_MUTED, TEXT_PRIMARY, TEXT_SECONDARY,
+};
+use crate::view::highlight::highlighted_spans;
+use crate::view::view_swatch_strip;
+use crate::widgets::GradientPreview;
+
+/// How long the cursor rests on a card before its preview appears.
+const PREVIEW_DELAY: Duration = Duration::from_millis(600);
+
+/// Code lines shown in a preview.
+const PREVIEW_CODE_LINES: usize = 20;
+
+/// Characters of text shown in a preview.
+const PREVIEW_TEXT_CHARS: usize = 600;
+
+/// Width of the preview popover.
+const PREVIEW_WIDTH: f32 = 420.0;
+
+/// Show a snippet's preview over its card once the cursor rests on it.
+pub fn with_preview<'a>(
+    card: impl Into<Element<'a, Message>>,
+    snippet: &'a Snippet,
+    code_theme: highlighter::Theme,
+) -> Element<'a, Message> {
+    tooltip(
+        card,
+        view_preview(snippet, code_theme),
+        tooltip::Position::Bottom,
+    )
+    .delay(PREVIEW_DELAY)
+    .style(dropdown_menu_style)
+    .padding(SPACE_SM)
+    .into()
+}
+
+/// Render the preview content of a snippet.
+fn view_preview(snippet: &Snippet, code_theme: highlighter::Theme) -> Element<'_, Message> {
+    let body: Element<'_, Message> = match &snippet.content {
+        SnippetContent::Color(color) => view_color_preview(color),
+        SnippetContent::ColorSet(set) => column![
+            view_swatch_strip(set, 32.0),
+            text(set.to_hex_list())
+                .size(12)
+                .font(Font::MONOSPACE)
+                .color(TEXT_PRIMARY),
+        ]
+        .spacing(SPACE_XS)
+        .into(),
+        SnippetContent::Gradient(gradient) => column![
+            Canvas::new(GradientPreview { gradient })
+                .width(Length::Fill)
+                .height(48),
+            text(gradient.to_css())
+                .size(12)
+                .font(Font::MONOSPACE)
+                .color(TEXT_PRIMARY),
+        ]
+        .spacing(SPACE_XS)
+        .into(),
+        SnippetContent::Code(code) => view_code_preview(
+            &code.code,
+            language_to_extension(&code.language),
+            code_theme,
+        ),
+        SnippetContent::Json(json) => view_code_preview(&json.json, "json", code_theme),
+        SnippetContent::Text(text_data) => text(text_data.first_paragraph(PREVIEW_TEXT_CHARS))
+            .size(13)
+            .color(TEXT_PRIMARY)
+            .into(),
+        SnippetContent::File(file) => column![
+            text(&file.name).size(13).color(TEXT_PRIMARY),
+            text(format!("{} · {}", format_size(file.size), file.path))
+                .size(11)
+                .color(TEXT_MUTED),
+        ]
+        .spacing(SPACE_XS)
+        .into(),
+    };
+
+    column![text(&snippet.label).size(12).color(TEXT_SECONDARY), body]
+        .spacing(SPACE_XS)
+        .max_width(PREVIEW_WIDTH)
+        .into()
+}
+
+/// Render a large swatch with every color format.
+fn view_color_preview(color: &ColorData) -> Element<'_, Message> {
+    let swatch_color = color.to_iced_color();
+    let swatch = container(text(""))
+        .width(Length::Fixed(96.0))
+        .height(Length::Fixed(96.0))
+        .style(move |_theme| {
+            container::Style::default()
+                .background(swatch_color)
+                .border(iced::Border::default().rounded(6.0))
+        });
+
+    let formats = column(
+        [
+            ("HEX", color.to_hex()),
+            ("RGB", color.to_rgb()),
+            ("HSL", color.to_hsl()),
+            ("HSV", color.to_hsv()),
+            ("OKLCH", color.to_oklch()),
+            ("LAB", color.to_lab()),
+            ("LCH", color.to_lch()),
+            ("P3", color.to_display_p3()),
+            ("CMYK", color.to_cmyk()),
+            ("NAME", {
+                let (name, distance) = nearest_named_color(color);
+                if distance < 0.5 {
+                    name.to_string()
+                } else {
+                    format!("≈ {} (ΔE {:.1})", name, distance)
+                }
+            }),
+        ]
+        .into_iter()
+        .map(|(name, value)| {
+            row![
+                text(name)
+                    .size(11)
+                    .color(TEXT_MUTED)
+                    .width(Length::Fixed(44.0)),
+                text(value)
+                    .size(12)
+                    .font(Font::MONOSPACE)
+                    .color(TEXT_PRIMARY),
+            ]
+            .spacing(SPACE_XS)
+            .into()
+        }),
+    )
+    .spacing(SPACE_XS);
+
+    row![swatch, formats]
+        .spacing(SPACE_SM)
+        .align_y(iced::Alignment::Center)
+        .into()
+}
+
+/// Render the first lines of code, syntax highlighted.
+fn view_code_preview<'a>(
+    code: &'a str,
+    extension: &str,
+    theme: highlighter::Theme,
+) -> Element<'a, Message> {
+    let spans = highlighted_spans(code, extension, theme, PREVIEW_CODE_LINES);
+    let mut preview = column![rich_text(spans).size(12).font(Font::MONOSPACE)].spacing(SPACE_XS);
+    let hidden_lines = code.lines().count().saturating_sub(PREVIEW_CODE_LINES);
+    if hidden_lines > 0 {
+        preview = preview.push(
+            text(format!("… {} more lines", hidden_lines))
+                .size(11)
+                .color(TEXT_MUTED),
+        );
+    }
+    preview.into()
+}
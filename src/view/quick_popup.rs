@@ -0,0 +1,95 @@
+//! Quick capture/search popup: a small always-on-top window to find a
+//! snippet and copy it, or to add what was typed as a new snippet.
+
+use iced::widget::{button, column, row, scrollable, text, text_input};
+use iced::{Element, Length};
+
+use crate::icons;
+use crate::message::Message;
+use crate::snippet::Snippet;
+use crate::theme::{
+    input_style, primary_button_style, scrollbar_style, subtle_button_style, SPACE_SM, SPACE_XS,
+    TEXT_MUTED, TEXT_PRIMARY,
+};
+use crate::view::labeled;
+use crate::view::palette_board::view_snippet_marker;
+
+/// ID of the popup's search input, focused when it opens.
+pub const QUICK_POPUP_INPUT_ID: &str = "quick-popup-input";
+
+/// Matches listed below the search.
+pub const QUICK_POPUP_RESULTS: usize = 8;
+
+/// Render the popup's search field and the snippets matching it.
+pub fn view_quick_popup<'a>(query: &'a str, matches: &[&'a Snippet]) -> Element<'a, Message> {
+    let input = text_input("Search snippets, or type one to add…", query)
+        .id(QUICK_POPUP_INPUT_ID)
+        .on_input(Message::QuickQueryChanged)
+        .on_submit(Message::SubmitQuickPopup)
+        .padding(SPACE_SM)
+        .size(14)
+        .style(|theme, status| input_style(theme, status, false));
+
+    let close_btn = labeled(
+        button(icons::x().size(12))
+            .on_press(Message::CloseQuickPopup)
+            .padding(SPACE_SM)
+            .style(subtle_button_style),
+        "Close",
+    );
+
+    let rows: Vec<Element<'a, Message>> = matches
+        .iter()
+        .map(|snippet| {
+            button(
+                row![
+                    view_snippet_marker(snippet),
+                    text(&snippet.label)
+                        .size(13)
+                        .color(TEXT_PRIMARY)
+                        .width(Length::Fill),
+                    text(snippet.content.kind().display_name())
+                        .size(11)
+                        .color(TEXT_MUTED),
+                ]
+                .spacing(SPACE_SM)
+                .align_y(iced::Alignment::Center),
+            )
+            .on_press(Message::QuickCopy(snippet.id))
+            .padding([SPACE_XS, SPACE_SM])
+            .width(Length::Fill)
+            .style(subtle_button_style)
+            .into()
+        })
+        .collect();
+
+    let has_query = !query.trim().is_empty();
+    let hint = if !matches.is_empty() {
+        "Enter copies the first match"
+    } else if has_query {
+        "Nothing matches; Enter adds this as a new snippet"
+    } else {
+        "The library is empty"
+    };
+    let footer = row![
+        text(hint).size(11).color(TEXT_MUTED).width(Length::Fill),
+        button(text("Add as Snippet").size(12))
+            .on_press_maybe(has_query.then_some(Message::QuickCapture))
+            .padding([SPACE_XS, SPACE_SM])
+            .style(primary_button_style),
+    ]
+    .spacing(SPACE_SM)
+    .align_y(iced::Alignment::Center);
+
+    column![
+        row![input, close_btn]
+            .spacing(SPACE_XS)
+            .align_y(iced::Alignment::Center),
+        scrollable(column(rows).spacing(2.0))
+            .height(Length::Fill)
+            .style(scrollbar_style),
+        footer,
+    ]
+    .spacing(SPACE_SM)
+    .into()
+}
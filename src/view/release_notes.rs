@@ -0,0 +1,78 @@
+//! Dialog announcing a new Shard release with its notes.
+
+use iced::widget::{button, column, container, mouse_area, opaque, row, scrollable, text};
+use iced::{Element, Length};
+
+use crate::icons;
+use crate::message::Message;
+use crate::releases::{current_version, Release};
+use crate::theme::{
+    modal_dialog_style, modal_overlay_style, primary_button_style, scrollbar_style,
+    secondary_button_style, SPACE_MD, SPACE_SM, SPACE_XS, TEXT_MUTED, TEXT_PRIMARY, TEXT_SECONDARY,
+};
+
+/// Render the release notes of an available update and a download link.
+pub fn view_release_notes_modal(release: &Release) -> Element<'_, Message> {
+    let header = column![
+        text(format!("Shard {} is available", release.version()))
+            .size(20)
+            .color(TEXT_PRIMARY),
+        text(format!("You have version {}", current_version()))
+            .size(12)
+            .color(TEXT_SECONDARY),
+    ]
+    .spacing(SPACE_XS);
+
+    let notes = release
+        .notes
+        .as_deref()
+        .filter(|notes| !notes.trim().is_empty())
+        .unwrap_or("No release notes.");
+    let notes_view = scrollable(
+        container(text(notes).size(12).color(TEXT_SECONDARY))
+            .padding(SPACE_SM)
+            .width(Length::Fill),
+    )
+    .height(Length::Fixed(260.0))
+    .style(scrollbar_style);
+
+    let action_buttons = row![
+        text("Opens the release page in your browser")
+            .size(11)
+            .color(TEXT_MUTED),
+        iced::widget::Space::new().width(Length::Fill),
+        button(text("Later").size(14))
+            .on_press(Message::CloseReleaseNotes)
+            .padding(SPACE_SM)
+            .style(secondary_button_style),
+        button(
+            row![icons::download().size(14), text("Download").size(14)]
+                .spacing(SPACE_XS)
+                .align_y(iced::Alignment::Center),
+        )
+        .on_press(Message::OpenReleasePage)
+        .padding(SPACE_SM)
+        .style(primary_button_style),
+    ]
+    .spacing(SPACE_SM)
+    .align_y(iced::Alignment::Center);
+
+    let modal_content = column![header, notes_view, action_buttons]
+        .spacing(SPACE_MD)
+        .padding(SPACE_MD)
+        .width(Length::Fixed(520.0));
+
+    let modal_dialog = container(modal_content).style(modal_dialog_style);
+
+    // Semi-transparent overlay
+    mouse_area(
+        container(opaque(modal_dialog))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x(Length::Fill)
+            .center_y(Length::Fill)
+            .style(modal_overlay_style),
+    )
+    .on_press(Message::CloseReleaseNotes)
+    .into()
+}
@@ -0,0 +1,171 @@
+//! Results of running a code snippet: exit code, stdout and stderr.
+
+use iced::widget::{button, column, container, mouse_area, opaque, row, scrollable, text};
+use iced::{Element, Font, Length};
+
+use crate::icons;
+use crate::message::Message;
+use crate::theme::{
+    modal_dialog_style, modal_overlay_style, primary_button_style, scrollbar_style,
+    secondary_button_style, subtle_button_style, BG_BASE, BORDER_SUBTLE, DANGER, SPACE_MD,
+    SPACE_SM, SPACE_XS, SUCCESS, TEXT_MUTED, TEXT_PRIMARY, TEXT_SECONDARY,
+};
+use crate::update::{RunOutput, MAX_RUN_OUTPUT, RUN_TIMEOUT};
+use crate::view::labeled;
+
+/// State of the run results pane.
+#[derive(Debug, Clone)]
+pub struct RunState {
+    pub snippet_id: i64,
+    pub label: String,
+    /// None while the snippet is still running.
+    pub result: Option<Result<RunOutput, String>>,
+}
+
+impl RunState {
+    /// Open the pane for a snippet that just started running.
+    pub fn new(snippet_id: i64, label: String) -> Self {
+        Self {
+            snippet_id,
+            label,
+            result: None,
+        }
+    }
+
+    /// Everything the snippet printed, stdout first.
+    pub fn printed(&self) -> Option<String> {
+        let Some(Ok(output)) = &self.result else {
+            return None;
+        };
+        Some(
+            [output.stdout.as_str(), output.stderr.as_str()]
+                .into_iter()
+                .filter(|stream| !stream.is_empty())
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )
+    }
+}
+
+/// A titled, scrollable block of monospace output.
+fn output_block<'a>(title: &'a str, output: &'a str, color: iced::Color) -> Element<'a, Message> {
+    column![
+        text(title).size(12).color(TEXT_SECONDARY),
+        container(
+            scrollable(text(output).size(12).font(Font::MONOSPACE).color(color))
+                .width(Length::Fill)
+                .style(scrollbar_style),
+        )
+        .padding(SPACE_SM)
+        .width(Length::Fill)
+        .max_height(200.0)
+        .style(|_theme| {
+            container::Style::default().background(BG_BASE).border(
+                iced::Border::default()
+                    .rounded(4.0)
+                    .width(1.0)
+                    .color(BORDER_SUBTLE),
+            )
+        }),
+    ]
+    .spacing(SPACE_XS)
+    .into()
+}
+
+/// Render the run results pane.
+pub fn view_run_output_modal(state: &RunState) -> Element<'_, Message> {
+    let header_row = row![
+        text(format!("Run \"{}\"", state.label))
+            .size(20)
+            .color(TEXT_PRIMARY),
+        iced::widget::Space::new().width(Length::Fill),
+        labeled(
+            button(icons::x().size(16))
+                .on_press(Message::CloseRunOutput)
+                .padding([SPACE_XS, SPACE_SM])
+                .style(subtle_button_style),
+            "Close"
+        ),
+    ]
+    .spacing(SPACE_XS)
+    .align_y(iced::Alignment::Center);
+
+    let mut body = column![].spacing(SPACE_MD);
+    match &state.result {
+        None => {
+            body = body.push(text("Running…").size(13).color(TEXT_MUTED));
+        }
+        Some(Err(e)) => {
+            body = body.push(text(e).size(13).color(DANGER));
+        }
+        Some(Ok(output)) => {
+            let (status, color) = match output.exit_code {
+                _ if output.timed_out => (
+                    format!("Stopped after {} seconds", RUN_TIMEOUT.as_secs()),
+                    DANGER,
+                ),
+                _ if output.output_limited => (
+                    format!("Stopped after printing {} KB", MAX_RUN_OUTPUT / 1024),
+                    DANGER,
+                ),
+                Some(0) => ("Exit code 0".to_string(), SUCCESS),
+                Some(code) => (format!("Exit code {}", code), DANGER),
+                None => ("Stopped by a signal".to_string(), DANGER),
+            };
+            body = body.push(
+                row![
+                    text(status).size(12).color(color),
+                    text(&output.command)
+                        .size(11)
+                        .font(Font::MONOSPACE)
+                        .color(TEXT_MUTED),
+                ]
+                .spacing(SPACE_SM)
+                .align_y(iced::Alignment::Center),
+            );
+            if !output.stdout.is_empty() {
+                body = body.push(output_block("Output", &output.stdout, TEXT_PRIMARY));
+            }
+            if !output.stderr.is_empty() {
+                body = body.push(output_block("Errors", &output.stderr, DANGER));
+            }
+            if output.stdout.is_empty() && output.stderr.is_empty() && !output.timed_out {
+                body = body.push(text("Nothing was printed").size(12).color(TEXT_MUTED));
+            }
+        }
+    }
+
+    let is_running = state.result.is_none();
+    let has_output = state.printed().is_some_and(|printed| !printed.is_empty());
+    let action_buttons = row![
+        iced::widget::Space::new().width(Length::Fill),
+        button(text("Copy Output").size(14))
+            .on_press_maybe(has_output.then_some(Message::CopyRunOutput))
+            .padding(SPACE_SM)
+            .style(secondary_button_style),
+        button(text("Run Again").size(14))
+            .on_press_maybe((!is_running).then_some(Message::RunSnippet(state.snippet_id)))
+            .padding(SPACE_SM)
+            .style(primary_button_style),
+    ]
+    .spacing(SPACE_SM);
+
+    let modal_content = column![header_row, body, action_buttons]
+        .spacing(SPACE_MD)
+        .padding(SPACE_MD)
+        .width(Length::Fixed(560.0));
+
+    let modal_dialog = container(modal_content).style(modal_dialog_style);
+
+    // Semi-transparent overlay
+    mouse_area(
+        container(opaque(modal_dialog))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x(Length::Fill)
+            .center_y(Length::Fill)
+            .style(modal_overlay_style),
+    )
+    .on_press(Message::CloseRunOutput)
+    .into()
+}
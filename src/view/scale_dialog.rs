@@ -0,0 +1,149 @@
+//! Dialog generating a 50–900 style scale from a color snippet.
+
+use iced::widget::{button, column, container, mouse_area, opaque, row, text, Canvas};
+use iced::{Element, Length};
+
+use crate::icons;
+use crate::message::Message;
+use crate::snippet::{color_scale, scale_step_names, ColorData, SCALE_SIZES};
+use crate::theme::{
+    modal_dialog_style, modal_overlay_style, primary_button_style, secondary_button_style,
+    subtle_button_style, SPACE_MD, SPACE_SM, SPACE_XS, TEXT_MUTED, TEXT_PRIMARY, TEXT_SECONDARY,
+};
+use crate::view::labeled;
+use crate::widgets::ColorSwatch;
+
+/// Width of the dialog's swatch strip.
+const STRIP_WIDTH: f32 = 440.0;
+
+/// State of the scale dialog.
+#[derive(Debug, Clone)]
+pub struct ScaleState {
+    /// Label and color of the snippet the scale is built from.
+    pub label: String,
+    pub base: ColorData,
+    pub steps: usize,
+}
+
+impl ScaleState {
+    /// Open the dialog for a color snippet with a 10-step scale.
+    pub fn new(label: String, base: ColorData) -> Self {
+        Self {
+            label,
+            base,
+            steps: 10,
+        }
+    }
+
+    /// The scale's colors with their labels, e.g. "Brand 500", light to dark.
+    pub fn labeled_colors(&self) -> Vec<(String, ColorData)> {
+        scale_step_names(self.steps)
+            .into_iter()
+            .map(|step| format!("{} {}", self.label, step))
+            .zip(color_scale(&self.base, self.steps))
+            .collect()
+    }
+}
+
+/// Render the scale dialog: step count, a preview strip, and the save actions.
+pub fn view_scale_modal(state: &ScaleState) -> Element<'_, Message> {
+    let header_row = row![
+        text("Generate Scale").size(20).color(TEXT_PRIMARY),
+        iced::widget::Space::new().width(Length::Fill),
+        labeled(
+            button(icons::x().size(16))
+                .on_press(Message::CloseScaleDialog)
+                .padding([SPACE_XS, SPACE_SM])
+                .style(subtle_button_style),
+            "Close"
+        ),
+    ]
+    .spacing(SPACE_XS)
+    .align_y(iced::Alignment::Center);
+
+    let step_buttons = row(SCALE_SIZES.iter().map(|steps| {
+        button(text(steps.to_string()).size(12))
+            .on_press(Message::ScaleStepsChanged(*steps))
+            .padding([SPACE_XS, SPACE_SM])
+            .style(if state.steps == *steps {
+                primary_button_style
+            } else {
+                secondary_button_style
+            })
+            .into()
+    }))
+    .spacing(SPACE_XS);
+    let steps_row = row![text("Steps:").size(12).color(TEXT_SECONDARY), step_buttons]
+        .spacing(SPACE_SM)
+        .align_y(iced::Alignment::Center);
+
+    // One column per step: swatch, step name, hex value
+    let swatch_width = (STRIP_WIDTH - 2.0 * (state.steps as f32 - 1.0)) / state.steps as f32;
+    let strip = row(scale_step_names(state.steps)
+        .into_iter()
+        .zip(color_scale(&state.base, state.steps))
+        .map(|(step, color)| {
+            let is_base = color == state.base;
+            column![
+                Canvas::new(ColorSwatch {
+                    color: color.to_iced_color(),
+                })
+                .width(swatch_width)
+                .height(48),
+                text(step.to_string()).size(11).color(if is_base {
+                    TEXT_PRIMARY
+                } else {
+                    TEXT_SECONDARY
+                }),
+                text(color.to_hex()).size(9).color(TEXT_MUTED),
+            ]
+            .spacing(2)
+            .align_x(iced::Alignment::Center)
+            .width(swatch_width)
+            .into()
+        }))
+    .spacing(2);
+
+    let hint = text(format!(
+        "Built around {} in OKLCH; it keeps its place in the scale",
+        state.base.to_hex()
+    ))
+    .size(11)
+    .color(TEXT_MUTED);
+
+    let action_buttons = row![
+        iced::widget::Space::new().width(Length::Fill),
+        button(text("Cancel").size(14))
+            .on_press(Message::CloseScaleDialog)
+            .padding(SPACE_SM)
+            .style(secondary_button_style),
+        button(text("Save as Color Set").size(14))
+            .on_press(Message::SaveScaleSet)
+            .padding(SPACE_SM)
+            .style(secondary_button_style),
+        button(text(format!("Save {} Colors", state.steps)).size(14))
+            .on_press(Message::SaveScaleColors)
+            .padding(SPACE_SM)
+            .style(primary_button_style),
+    ]
+    .spacing(SPACE_SM);
+
+    let modal_content = column![header_row, steps_row, strip, hint, action_buttons]
+        .spacing(SPACE_MD)
+        .padding(SPACE_MD)
+        .width(Length::Fixed(STRIP_WIDTH + 2.0 * SPACE_MD));
+
+    let modal_dialog = container(modal_content).style(modal_dialog_style);
+
+    // Semi-transparent overlay
+    mouse_area(
+        container(opaque(modal_dialog))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x(Length::Fill)
+            .center_y(Length::Fill)
+            .style(modal_overlay_style),
+    )
+    .on_press(Message::CloseScaleDialog)
+    .into()
+}
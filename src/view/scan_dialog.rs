@@ -0,0 +1,243 @@
+//! Dialog listing the distinct colors found in a scanned screen region, or
+//! the dominant colors of an image file.
+
+use iced::widget::{
+    button, checkbox, column, container, mouse_area, opaque, row, scrollable, text, text_input,
+};
+use iced::{Element, Length};
+
+use crate::icons;
+use crate::message::Message;
+use crate::snippet::ColorData;
+use crate::theme::{
+    input_style, modal_dialog_style, modal_overlay_style, primary_button_style, scrollbar_style,
+    secondary_button_style, subtle_button_style, BORDER_SUBTLE, SPACE_MD, SPACE_SM, SPACE_XS,
+    TEXT_MUTED, TEXT_PRIMARY, TEXT_SECONDARY,
+};
+use crate::view::labeled;
+
+/// Most frequent colors kept from a scan; screenshots of anti-aliased
+/// text and gradients easily contain thousands of one-off shades.
+const MAX_SCANNED_COLORS: usize = 64;
+
+/// A distinct color found in the scanned region.
+#[derive(Debug, Clone)]
+pub struct ScannedColor {
+    pub color: ColorData,
+    /// Number of pixels with this color.
+    pub count: usize,
+    /// Selected for bulk adding.
+    pub selected: bool,
+}
+
+/// State for the region scan dialog.
+#[derive(Debug, Clone)]
+pub struct RegionScanState {
+    /// Most frequent colors first.
+    pub colors: Vec<ScannedColor>,
+    /// Number of distinct colors in the region, before truncation.
+    pub distinct: usize,
+    /// Number of scanned (non-transparent) pixels.
+    pub pixels: usize,
+    /// Name of the palette the colors go to, when they come from an image
+    /// file rather than a screen region.
+    pub palette_name: Option<String>,
+}
+
+impl RegionScanState {
+    /// Build dialog state from counted colors, most frequent first.
+    pub fn new(counts: Vec<(ColorData, usize)>) -> Self {
+        let distinct = counts.len();
+        let pixels = counts.iter().map(|(_, count)| count).sum();
+        let colors = counts
+            .into_iter()
+            .take(MAX_SCANNED_COLORS)
+            .map(|(color, count)| ScannedColor {
+                color,
+                count,
+                selected: false,
+            })
+            .collect();
+        Self {
+            colors,
+            distinct,
+            pixels,
+            palette_name: None,
+        }
+    }
+
+    /// Build dialog state from the dominant colors of an image, all selected,
+    /// to be added to a new palette named after the image.
+    pub fn from_image(name: String, counts: Vec<(ColorData, usize)>) -> Self {
+        let mut state = Self::new(counts);
+        for scanned in &mut state.colors {
+            scanned.selected = true;
+        }
+        state.palette_name = Some(name);
+        state
+    }
+
+    /// Get the colors selected for adding.
+    pub fn selected_colors(&self) -> Vec<ColorData> {
+        self.colors
+            .iter()
+            .filter(|c| c.selected)
+            .map(|c| c.color.clone())
+            .collect()
+    }
+}
+
+/// Render the region scan dialog.
+pub fn view_region_scan_modal(state: &RegionScanState) -> Element<'_, Message> {
+    let title = if state.palette_name.is_some() {
+        "Image Colors"
+    } else {
+        "Scanned Colors"
+    };
+    let header_row = row![
+        text(title).size(20).color(TEXT_PRIMARY),
+        iced::widget::Space::new().width(Length::Fill),
+        labeled(
+            button(icons::x().size(16))
+                .on_press(Message::CloseRegionScan)
+                .padding([SPACE_XS, SPACE_SM])
+                .style(subtle_button_style),
+            "Close"
+        ),
+    ]
+    .spacing(SPACE_XS)
+    .align_y(iced::Alignment::Center);
+
+    let summary = if state.palette_name.is_some() {
+        format!(
+            "{} dominant colors in {} pixels",
+            state.colors.len(),
+            state.pixels
+        )
+    } else if state.distinct > state.colors.len() {
+        format!(
+            "{} distinct colors in {} pixels, showing the {} most common",
+            state.distinct,
+            state.pixels,
+            state.colors.len()
+        )
+    } else {
+        format!(
+            "{} distinct colors in {} pixels",
+            state.distinct, state.pixels
+        )
+    };
+    let summary = text(summary).size(11).color(TEXT_MUTED);
+
+    let all_selected = state.colors.iter().all(|c| c.selected);
+    let select_all = checkbox(all_selected)
+        .label("Select all")
+        .on_toggle(Message::SelectAllScannedColors)
+        .text_size(12);
+
+    let pixels = state.pixels.max(1) as f32;
+    let items: Vec<Element<'_, Message>> = state
+        .colors
+        .iter()
+        .enumerate()
+        .map(|(index, scanned)| {
+            let color = scanned.color.to_iced_color();
+            let swatch = container(text(""))
+                .width(Length::Fixed(24.0))
+                .height(Length::Fixed(24.0))
+                .style(move |_theme| {
+                    iced::widget::container::Style::default()
+                        .background(color)
+                        .border(
+                            iced::Border::default()
+                                .rounded(4.0)
+                                .width(1.0)
+                                .color(BORDER_SUBTLE),
+                        )
+                });
+
+            row![
+                checkbox(scanned.selected).on_toggle(move |_| Message::ToggleScannedColor(index)),
+                swatch,
+                text(scanned.color.to_hex())
+                    .size(13)
+                    .width(Length::Fill)
+                    .color(TEXT_PRIMARY),
+                text(format!(
+                    "{} px ({:.1}%)",
+                    scanned.count,
+                    scanned.count as f32 / pixels * 100.0
+                ))
+                .size(11)
+                .color(TEXT_SECONDARY),
+            ]
+            .spacing(SPACE_SM)
+            .align_y(iced::Alignment::Center)
+            .into()
+        })
+        .collect();
+
+    let list = scrollable(column(items).spacing(SPACE_XS).padding([0.0, SPACE_SM]))
+        .height(Length::Fixed(320.0))
+        .style(scrollbar_style);
+
+    let selected = state.colors.iter().filter(|c| c.selected).count();
+    let can_add = selected > 0
+        && state
+            .palette_name
+            .as_ref()
+            .is_none_or(|name| !name.trim().is_empty());
+    let add_label = if state.palette_name.is_some() {
+        format!("Add {} Colors to Palette", selected)
+    } else {
+        format!("Add {} Colors", selected)
+    };
+    let action_buttons = row![
+        button(text("Cancel").size(14))
+            .on_press(Message::CloseRegionScan)
+            .padding(SPACE_SM)
+            .style(secondary_button_style),
+        button(text(add_label).size(14))
+            .on_press_maybe(can_add.then_some(Message::AddScannedColors))
+            .padding(SPACE_SM)
+            .style(primary_button_style),
+    ]
+    .spacing(SPACE_SM);
+
+    let mut modal_content = column![header_row, summary];
+    if let Some(name) = &state.palette_name {
+        modal_content = modal_content.push(
+            row![
+                text("Palette").size(12).color(TEXT_SECONDARY),
+                text_input("Palette name", name)
+                    .on_input(Message::ImagePaletteNameChanged)
+                    .size(13)
+                    .padding([SPACE_XS, SPACE_SM])
+                    .style(|theme, status| input_style(theme, status, false)),
+            ]
+            .spacing(SPACE_SM)
+            .align_y(iced::Alignment::Center),
+        );
+    }
+    let modal_content = modal_content
+        .push(select_all)
+        .push(list)
+        .push(action_buttons)
+        .spacing(SPACE_MD)
+        .padding(SPACE_MD)
+        .width(Length::Fixed(400.0));
+
+    let modal_dialog = container(modal_content).style(modal_dialog_style);
+
+    // Semi-transparent overlay
+    mouse_area(
+        container(opaque(modal_dialog))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x(Length::Fill)
+            .center_y(Length::Fill)
+            .style(modal_overlay_style),
+    )
+    .on_press(Message::CloseRegionScan)
+    .into()
+}
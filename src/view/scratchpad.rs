@@ -0,0 +1,75 @@
+//! Scratchpad panel for jotting temporary text.
+
+use iced::widget::{button, column, container, mouse_area, opaque, row, text, text_editor};
+use iced::{Element, Length};
+
+use crate::icons;
+use crate::message::Message;
+use crate::theme::{
+    modal_dialog_style, modal_overlay_style, primary_button_style, secondary_button_style,
+    subtle_button_style, SPACE_MD, SPACE_SM, SPACE_XS, TEXT_MUTED, TEXT_PRIMARY,
+};
+use crate::view::labeled;
+
+/// Render the scratchpad modal.
+pub fn view_scratchpad_modal(content: &text_editor::Content) -> Element<'_, Message> {
+    // Header
+    let header_row = row![
+        text("Scratchpad").size(20).color(TEXT_PRIMARY),
+        iced::widget::Space::new().width(Length::Fill),
+        labeled(
+            button(icons::x().size(16))
+                .on_press(Message::CloseScratchpad)
+                .padding([SPACE_XS, SPACE_SM])
+                .style(subtle_button_style),
+            "Close"
+        ),
+    ]
+    .spacing(SPACE_XS)
+    .align_y(iced::Alignment::Center);
+
+    let hint = text("Notes here are kept between sessions until you promote them to a snippet")
+        .size(11)
+        .color(TEXT_MUTED);
+
+    let editor = text_editor(content)
+        .placeholder("Jot something down...")
+        .on_action(Message::ScratchpadEdited)
+        .height(Length::Fixed(300.0))
+        .padding(SPACE_SM);
+
+    // Action buttons
+    let has_text = !content.text().trim().is_empty();
+    let action_buttons = row![
+        button(text("Close").size(14))
+            .on_press(Message::CloseScratchpad)
+            .padding(SPACE_SM)
+            .style(secondary_button_style),
+        button(text("Promote to Snippet").size(14))
+            .on_press_maybe(has_text.then_some(Message::PromoteScratchpad))
+            .padding(SPACE_SM)
+            .style(primary_button_style),
+    ]
+    .spacing(SPACE_SM);
+
+    // Modal content
+    let modal_content = column![header_row, hint, editor, action_buttons]
+        .spacing(SPACE_MD)
+        .padding(SPACE_MD)
+        .width(Length::Fixed(500.0));
+
+    let modal_dialog = container(modal_content).style(modal_dialog_style);
+
+    // Semi-transparent overlay
+    let overlay = mouse_area(
+        container(opaque(modal_dialog))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x(Length::Fill)
+            .center_y(Length::Fill)
+            .style(modal_overlay_style),
+    )
+    .on_press(Message::CloseScratchpad);
+
+    overlay.into()
+}
@@ -0,0 +1,141 @@
+//! Section headers in the manual snippet order, and the dialog naming them.
+
+use iced::widget::{button, column, container, mouse_area, opaque, row, text, text_input};
+use iced::{Element, Length};
+
+use crate::db::Section;
+use crate::icons;
+use crate::message::Message;
+use crate::snippet::Snippet;
+use crate::theme::{
+    input_style, modal_dialog_style, modal_overlay_style, primary_button_style,
+    secondary_button_style, subtle_button_style, BORDER_SUBTLE, SPACE_MD, SPACE_SM, SPACE_XS,
+    TEXT_PRIMARY, TEXT_SECONDARY,
+};
+use crate::view::labeled;
+
+/// Widget ID of the section name input.
+pub const SECTION_NAME_ID: &str = "section-name";
+
+/// A section being created or renamed.
+#[derive(Debug, Clone)]
+pub struct SectionDraft {
+    /// Section being renamed; `None` creates a new one.
+    pub section_id: Option<i64>,
+    /// Snippet a new section is inserted above; `None` inserts at the top.
+    pub above_snippet: Option<i64>,
+    pub name: String,
+}
+
+/// Interleave section headers with snippets in manual order.
+/// With `show_empty`, sections without visible snippets keep their header.
+pub fn with_section_headers<'a>(
+    snippets: &[&'a Snippet],
+    sections: &'a [Section],
+    show_empty: bool,
+    mut card: impl FnMut(&'a Snippet) -> Element<'a, Message>,
+) -> Vec<Element<'a, Message>> {
+    let mut items = Vec::new();
+    let mut pending = sections.iter().peekable();
+    for snippet in snippets {
+        // Sections are ordered like snippets, highest position first
+        let mut header = None;
+        while let Some(section) = pending.next_if(|s| s.position > snippet.position) {
+            if let Some(skipped) = header.replace(section).filter(|_| show_empty) {
+                items.push(view_section_header(skipped));
+            }
+        }
+        if let Some(section) = header {
+            items.push(view_section_header(section));
+        }
+        items.push(card(snippet));
+    }
+    if show_empty {
+        items.extend(pending.map(view_section_header));
+    }
+    items
+}
+
+/// Render a section header with its rename and delete buttons.
+fn view_section_header(section: &Section) -> Element<'_, Message> {
+    let rule = container(text(""))
+        .width(Length::Fill)
+        .height(1.0)
+        .style(|_theme| container::Style::default().background(BORDER_SUBTLE));
+
+    row![
+        icons::rows().size(12).color(TEXT_SECONDARY),
+        text(&section.name).size(13).color(TEXT_PRIMARY),
+        rule,
+        labeled(
+            button(icons::pencil().size(12))
+                .on_press(Message::EditSection(section.id))
+                .padding(SPACE_XS)
+                .style(subtle_button_style),
+            "Rename section"
+        ),
+        labeled(
+            button(icons::x().size(12))
+                .on_press(Message::DeleteSection(section.id))
+                .padding(SPACE_XS)
+                .style(subtle_button_style),
+            "Delete section"
+        ),
+    ]
+    .spacing(SPACE_SM)
+    .padding([SPACE_XS, 0.0])
+    .align_y(iced::Alignment::Center)
+    .into()
+}
+
+/// Render the dialog naming a new or renamed section.
+pub fn view_section_dialog(draft: &SectionDraft) -> Element<'_, Message> {
+    let title = if draft.section_id.is_some() {
+        "Rename Section"
+    } else {
+        "New Section"
+    };
+
+    let name_input = text_input("Section name, e.g. Brand", &draft.name)
+        .id(SECTION_NAME_ID)
+        .on_input(Message::SectionNameChanged)
+        .on_submit(Message::SaveSection)
+        .padding(SPACE_SM)
+        .style(|theme, status| input_style(theme, status, false));
+
+    let action_buttons = row![
+        iced::widget::Space::new().width(Length::Fill),
+        button(text("Cancel").size(14))
+            .on_press(Message::CloseSectionDialog)
+            .padding(SPACE_SM)
+            .style(secondary_button_style),
+        button(text("Save").size(14))
+            .on_press_maybe((!draft.name.trim().is_empty()).then_some(Message::SaveSection))
+            .padding(SPACE_SM)
+            .style(primary_button_style),
+    ]
+    .spacing(SPACE_SM);
+
+    let modal_content = column![
+        text(title).size(20).color(TEXT_PRIMARY),
+        name_input,
+        action_buttons
+    ]
+    .spacing(SPACE_MD)
+    .padding(SPACE_MD)
+    .width(Length::Fixed(360.0));
+
+    let modal_dialog = container(modal_content).style(modal_dialog_style);
+
+    // Semi-transparent overlay
+    mouse_area(
+        container(opaque(modal_dialog))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x(Length::Fill)
+            .center_y(Length::Fill)
+            .style(modal_overlay_style),
+    )
+    .on_press(Message::CloseSectionDialog)
+    .into()
+}
@@ -0,0 +1,133 @@
+//! Action bar shown while picking several snippets for batch operations.
+
+use iced::widget::{button, container, pick_list, row, text, text_input};
+use iced::{Element, Length};
+
+use crate::db::{self, Palette};
+use crate::message::Message;
+use crate::theme::{
+    danger_button_style, header_style, input_style, pick_list_style, primary_button_style,
+    secondary_button_style, SPACE_MD, SPACE_SM, SPACE_XS, TEXT_SECONDARY,
+};
+
+/// A palette choice in the "Add to palette" dropdown.
+#[derive(Debug, Clone, PartialEq)]
+struct PaletteChoice {
+    palette_id: i64,
+    name: String,
+}
+
+impl std::fmt::Display for PaletteChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+/// Render the batch actions for the checked snippets.
+pub fn view_selection_bar<'a>(
+    selected_count: usize,
+    palettes: &'a [Palette],
+    language: &'a str,
+) -> Element<'a, Message> {
+    let has_selection = selected_count > 0;
+
+    let count = text(format!("{} selected", selected_count))
+        .size(12)
+        .color(TEXT_SECONDARY);
+
+    let select_all = button(text("Select all").size(12))
+        .on_press(Message::SelectAllVisible)
+        .padding([SPACE_XS, SPACE_SM])
+        .style(secondary_button_style);
+
+    let clear = button(text("Clear").size(12))
+        .on_press_maybe(has_selection.then_some(Message::ClearSelection))
+        .padding([SPACE_XS, SPACE_SM])
+        .style(secondary_button_style);
+
+    // Smart palettes pick their members by query, so they can't be assigned
+    let choices: Vec<PaletteChoice> = palettes
+        .iter()
+        .filter(|p| !p.is_smart())
+        .map(|p| PaletteChoice {
+            palette_id: p.id,
+            name: db::palette_path(palettes, p.id),
+        })
+        .collect();
+    let palette_picker = pick_list(choices, None::<PaletteChoice>, |choice: PaletteChoice| {
+        Message::BatchAddToPalette(choice.palette_id)
+    })
+    .placeholder("Add to palette…")
+    .text_size(12)
+    .padding([SPACE_XS, SPACE_SM])
+    .style(pick_list_style);
+
+    let language_input = text_input("Set language…", language)
+        .on_input(Message::BatchLanguageInput)
+        .on_submit_maybe(
+            (has_selection && !language.trim().is_empty()).then_some(Message::BatchSetLanguage),
+        )
+        .width(Length::Fixed(120.0))
+        .padding([SPACE_XS, SPACE_SM])
+        .size(12)
+        .style(|theme, status| input_style(theme, status, false));
+
+    let export = button(text("Export").size(12))
+        .on_press_maybe(has_selection.then_some(Message::BatchExport))
+        .padding([SPACE_XS, SPACE_SM])
+        .style(secondary_button_style);
+
+    let copy_combined = button(text("Copy combined").size(12))
+        .on_press_maybe(has_selection.then_some(Message::BatchCopyCombined))
+        .padding([SPACE_XS, SPACE_SM])
+        .style(secondary_button_style);
+
+    let combine = button(text("Combine colors").size(12))
+        .on_press_maybe((selected_count > 1).then_some(Message::BatchCombineColors))
+        .padding([SPACE_XS, SPACE_SM])
+        .style(secondary_button_style);
+
+    let editor_snippets = button(text("Editor snippets").size(12))
+        .on_press_maybe(has_selection.then_some(Message::ExportEditorSnippets(None)))
+        .padding([SPACE_XS, SPACE_SM])
+        .style(secondary_button_style);
+
+    let sync = button(text("Sync to project").size(12))
+        .on_press_maybe(has_selection.then_some(Message::BatchSyncToProject))
+        .padding([SPACE_XS, SPACE_SM])
+        .style(secondary_button_style);
+
+    let delete = button(text("Delete").size(12))
+        .on_press_maybe(has_selection.then_some(Message::BatchDelete))
+        .padding([SPACE_XS, SPACE_SM])
+        .style(danger_button_style);
+
+    let done = button(text("Done").size(12))
+        .on_press(Message::ToggleSelectionMode)
+        .padding([SPACE_XS, SPACE_SM])
+        .style(primary_button_style);
+
+    container(
+        row![
+            count,
+            select_all,
+            clear,
+            iced::widget::Space::new().width(Length::Fill),
+            palette_picker,
+            language_input,
+            copy_combined,
+            combine,
+            export,
+            editor_snippets,
+            sync,
+            delete,
+            done,
+        ]
+        .spacing(SPACE_SM)
+        .align_y(iced::Alignment::Center),
+    )
+    .width(Length::Fill)
+    .padding([SPACE_SM, SPACE_MD])
+    .style(header_style)
+    .into()
+}
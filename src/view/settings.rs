@@ -1,19 +1,26 @@
 //! Settings modal for application configuration.
 
 use iced::widget::{
-    button, column, container, mouse_area, opaque, row, scrollable, text, text_input,
+    button, checkbox, column, container, mouse_area, opaque, pick_list, row, scrollable, slider,
+    text, text_input,
 };
 use iced::{Element, Length};
+use std::path::PathBuf;
 
-use crate::config::{Config, EditorPreset, KeyboardConfig, ShortcutAction};
+use crate::config::{
+    CardActions, CodeTheme, ColorDedupe, CombineSeparator, Config, CopyFormat, CopyTemplate,
+    EditingConfig, EditorPreset, KeyboardConfig, MarkdownText, RunCommand, SecretPolicy,
+    ShortcutAction, TrashRetention, WindowAppearance, TAB_WIDTHS,
+};
+use crate::db::{self, Palette};
 use crate::icons;
 use crate::message::Message;
 use crate::theme::{
-    input_style, modal_dialog_style, modal_overlay_style, primary_button_style, scrollbar_style,
-    secondary_button_style, subtle_button_style, SPACE_MD, SPACE_SM, SPACE_XS, TEXT_MUTED,
-    TEXT_PRIMARY, TEXT_SECONDARY,
+    input_style, modal_dialog_style, modal_overlay_style, pick_list_style, primary_button_style,
+    scrollbar_style, secondary_button_style, subtle_button_style, DANGER, SPACE_MD, SPACE_SM,
+    SPACE_XS, TEXT_MUTED, TEXT_PRIMARY, TEXT_SECONDARY,
 };
-use crate::view::PickerMode;
+use crate::view::{labeled, PickerMode};
 
 /// State for the settings modal.
 #[derive(Debug, Clone)]
@@ -22,12 +29,53 @@ pub struct SettingsState {
     pub editor_preset: EditorPreset,
     /// Custom command (used when preset is Custom).
     pub custom_command: String,
+    /// Show a diff of external edits before saving them.
+    pub review_external_edits: bool,
+    /// Wrapping and Tab behavior of the editor modals.
+    pub editing: EditingConfig,
     /// Keyboard shortcuts configuration.
     pub keyboard: KeyboardConfig,
     /// Which shortcut action is currently being recorded (if any).
     pub recording_action: Option<ShortcutAction>,
+    /// Held modifiers, or why the last key press was not recorded.
+    pub recording_feedback: Option<String>,
     /// Default color picker mode.
     pub default_picker_mode: PickerMode,
+    /// When a new color duplicates a saved one.
+    pub color_dedupe: ColorDedupe,
+    /// Snippet card action visibility.
+    pub card_actions: CardActions,
+    /// Syntax highlighting theme.
+    pub code_theme: CodeTheme,
+    /// Palette that captured snippets are assigned to.
+    pub capture_palette: Option<i64>,
+    /// Handling of captured text that contains secrets.
+    pub secret_policy: SecretPolicy,
+    /// How long deleted snippets stay in the trash.
+    pub trash_retention: TrashRetention,
+    /// Show the favorites bar under the header.
+    pub show_favorites_bar: bool,
+    /// Show the source application of captured snippets on their cards.
+    pub show_source_badge: bool,
+    pub combine_separator: CombineSeparator,
+    pub copy_format: CopyFormat,
+    pub markdown_text: MarkdownText,
+    /// Appearance of pinned snippet windows.
+    pub window_appearance: WindowAppearance,
+    /// Custom database folder. Changed right away, not on save.
+    pub database_path: Option<PathBuf>,
+    /// A maintenance task is running on the database.
+    pub maintenance_busy: bool,
+    /// Outcome of the last maintenance task.
+    pub maintenance_report: Option<Result<String, String>>,
+    /// Write a crash report when Shard panics.
+    pub crash_reports: bool,
+    /// Check for new releases on startup.
+    pub check_for_updates: bool,
+    /// Expand abbreviations typed in other applications.
+    pub text_expansion: bool,
+    pub copy_templates: Vec<CopyTemplate>,
+    pub run_commands: Vec<RunCommand>,
 }
 
 impl SettingsState {
@@ -36,9 +84,32 @@ impl SettingsState {
         Self {
             editor_preset: config.editor.preset,
             custom_command: config.editor.custom_command.clone(),
+            review_external_edits: config.review_external_edits,
+            editing: config.editing,
             keyboard: config.keyboard.clone(),
             recording_action: None,
+            recording_feedback: None,
             default_picker_mode: config.default_picker_mode,
+            color_dedupe: config.color_dedupe,
+            card_actions: config.card_actions,
+            code_theme: config.code_theme,
+            capture_palette: config.capture_palette,
+            secret_policy: config.secret_policy,
+            trash_retention: config.trash_retention,
+            show_favorites_bar: config.show_favorites_bar,
+            show_source_badge: config.show_source_badge,
+            combine_separator: config.combine_separator,
+            copy_format: config.copy_format,
+            markdown_text: config.markdown_text,
+            window_appearance: config.window_appearance,
+            database_path: config.database_path.clone(),
+            maintenance_busy: false,
+            maintenance_report: None,
+            crash_reports: config.crash_reports,
+            check_for_updates: config.check_for_updates,
+            text_expansion: config.text_expansion,
+            copy_templates: config.copy_templates.clone(),
+            run_commands: config.run_commands.clone(),
         }
     }
 
@@ -46,8 +117,53 @@ impl SettingsState {
     pub fn apply_to_config(&self, config: &mut Config) {
         config.editor.preset = self.editor_preset;
         config.editor.custom_command = self.custom_command.clone();
+        config.review_external_edits = self.review_external_edits;
+        config.editing = self.editing;
         config.keyboard = self.keyboard.clone();
         config.default_picker_mode = self.default_picker_mode;
+        config.color_dedupe = self.color_dedupe;
+        config.card_actions = self.card_actions;
+        config.code_theme = self.code_theme;
+        // Choosing a new capture palette turns capturing into it on
+        if self.capture_palette != config.capture_palette {
+            config.capture_to_palette = self.capture_palette.is_some();
+        }
+        config.capture_palette = self.capture_palette;
+        config.secret_policy = self.secret_policy;
+        config.trash_retention = self.trash_retention;
+        config.show_favorites_bar = self.show_favorites_bar;
+        config.show_source_badge = self.show_source_badge;
+        config.combine_separator = self.combine_separator;
+        config.copy_format = self.copy_format;
+        config.markdown_text = self.markdown_text;
+        config.crash_reports = self.crash_reports;
+        config.check_for_updates = self.check_for_updates;
+        config.text_expansion = self.text_expansion;
+        config.window_appearance = self.window_appearance;
+        // Templates left empty are dropped; unnamed ones are named after their text
+        config.copy_templates = self
+            .copy_templates
+            .iter()
+            .filter(|t| !t.template.trim().is_empty())
+            .map(|t| CopyTemplate {
+                name: if t.name.trim().is_empty() {
+                    t.template.clone()
+                } else {
+                    t.name.clone()
+                },
+                template: t.template.clone(),
+            })
+            .collect();
+        // Rows missing a language or a command are dropped
+        config.run_commands = self
+            .run_commands
+            .iter()
+            .filter(|c| !c.language.trim().is_empty() && !c.command.trim().is_empty())
+            .map(|c| RunCommand {
+                language: c.language.trim().to_string(),
+                command: c.command.trim().to_string(),
+            })
+            .collect();
     }
 }
 
@@ -108,20 +224,45 @@ fn view_shortcut_row<'a>(
         .into()
 }
 
+/// A capture palette choice in the settings dropdown.
+#[derive(Debug, Clone, PartialEq)]
+struct CapturePaletteChoice {
+    palette_id: Option<i64>,
+    name: String,
+}
+
+impl std::fmt::Display for CapturePaletteChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
 /// Render the settings modal.
-pub fn view_settings_modal(settings: &SettingsState) -> Element<'_, Message> {
+pub fn view_settings_modal<'a>(
+    settings: &'a SettingsState,
+    palettes: &'a [Palette],
+    database_encrypted: bool,
+) -> Element<'a, Message> {
     // Header
     let header_row = row![
         icons::gear().size(20).color(TEXT_PRIMARY),
         iced::widget::Space::new().width(SPACE_SM),
         text("Settings").size(20).color(TEXT_PRIMARY),
         iced::widget::Space::new().width(Length::Fill),
-        button(icons::x().size(16))
-            .on_press(Message::CloseSettings)
-            .padding([SPACE_XS, SPACE_SM])
-            .style(subtle_button_style),
+        labeled(
+            button(icons::x().size(16))
+                .on_press(Message::CloseSettings)
+                .padding([SPACE_XS, SPACE_SM])
+                .style(subtle_button_style),
+            "Close"
+        ),
     ]
-    .padding(iced::Padding::new(SPACE_XS).top(SPACE_SM).bottom(0.0).left(SPACE_MD))
+    .padding(
+        iced::Padding::new(SPACE_XS)
+            .top(SPACE_SM)
+            .bottom(0.0)
+            .left(SPACE_MD),
+    )
     .align_y(iced::Alignment::Center);
 
     // Editor section title
@@ -198,6 +339,98 @@ pub fn view_settings_modal(settings: &SettingsState) -> Element<'_, Message> {
             container(text("")).into()
         };
 
+    let review_edits_toggle = checkbox(settings.review_external_edits)
+        .label("Review changes before saving them")
+        .on_toggle(Message::SettingsReviewExternalEditsChanged)
+        .text_size(12);
+
+    // Editing section
+    let editing_section_title = row![
+        icons::text_indent().size(14).color(TEXT_SECONDARY),
+        text("Editor").size(14).color(TEXT_SECONDARY),
+    ]
+    .spacing(SPACE_SM)
+    .align_y(iced::Alignment::Center);
+
+    let word_wrap_toggle = checkbox(settings.editing.word_wrap)
+        .label("Wrap long lines in text")
+        .on_toggle(Message::SettingsWordWrapChanged)
+        .text_size(12);
+
+    let code_word_wrap_toggle = checkbox(settings.editing.code_word_wrap)
+        .label("Wrap long lines in code (line numbers show without wrapping)")
+        .on_toggle(Message::SettingsCodeWordWrapChanged)
+        .text_size(12);
+
+    let tab_width_buttons = row![text("Tab width:").size(12).color(TEXT_SECONDARY)]
+        .extend(TAB_WIDTHS.iter().map(|width| {
+            button(text(width.to_string()).size(12))
+                .on_press(Message::SettingsTabWidthChanged(*width))
+                .padding([SPACE_XS, SPACE_SM])
+                .style(if settings.editing.tab_width == *width {
+                    primary_button_style
+                } else {
+                    secondary_button_style
+                })
+                .into()
+        }))
+        .spacing(SPACE_XS)
+        .align_y(iced::Alignment::Center);
+
+    let insert_spaces_toggle = checkbox(settings.editing.insert_spaces)
+        .label("Insert spaces when pressing Tab")
+        .on_toggle(Message::SettingsInsertSpacesChanged)
+        .text_size(12);
+
+    // Run commands section
+    let run_section_title = row![
+        icons::play().size(14).color(TEXT_SECONDARY),
+        text("Run Commands").size(14).color(TEXT_SECONDARY),
+    ]
+    .spacing(SPACE_SM)
+    .align_y(iced::Alignment::Center);
+
+    let run_command_rows = column(settings.run_commands.iter().enumerate().map(|(index, c)| {
+        row![
+            text_input("Language", &c.language)
+                .on_input(move |language| {
+                    Message::SettingsRunCommandLanguageChanged(index, language)
+                })
+                .size(12)
+                .padding(SPACE_XS)
+                .width(Length::Fixed(100.0))
+                .style(|theme, status| input_style(theme, status, false)),
+            text_input("python3 {file}", &c.command)
+                .on_input(move |text| Message::SettingsRunCommandChanged(index, text))
+                .size(12)
+                .padding(SPACE_XS)
+                .width(Length::Fill)
+                .font(iced::Font::MONOSPACE)
+                .style(|theme, status| input_style(theme, status, false)),
+            labeled(
+                button(icons::x().size(12))
+                    .on_press(Message::SettingsRunCommandRemoved(index))
+                    .padding([SPACE_XS, SPACE_SM])
+                    .style(subtle_button_style),
+                "Remove run command"
+            ),
+        ]
+        .spacing(SPACE_XS)
+        .align_y(iced::Alignment::Center)
+        .into()
+    }))
+    .spacing(SPACE_XS);
+    let add_run_command_button = button(text("Add run command").size(12))
+        .on_press(Message::SettingsRunCommandAdded)
+        .padding([SPACE_XS, SPACE_SM])
+        .style(secondary_button_style);
+    let run_command_hint = text(
+        "Code snippets in these languages run from their card menu. \
+         {file} is the snippet saved to a temporary file.",
+    )
+    .size(11)
+    .color(TEXT_MUTED);
+
     // Color Picker section
     let picker_section_title = row![
         icons::swatches().size(14).color(TEXT_SECONDARY),
@@ -206,30 +439,340 @@ pub fn view_settings_modal(settings: &SettingsState) -> Element<'_, Message> {
     .spacing(SPACE_SM)
     .align_y(iced::Alignment::Center);
 
-    let picker_mode_buttons = row![
-        button(text("HSL").size(12))
-            .on_press(Message::SettingsDefaultPickerModeChanged(PickerMode::Hsl))
+    let picker_mode_buttons = row(PickerMode::ALL.iter().map(|mode| {
+        button(text(mode.display_name()).size(12))
+            .on_press(Message::SettingsDefaultPickerModeChanged(*mode))
             .padding([SPACE_XS, SPACE_SM])
-            .style(if settings.default_picker_mode == PickerMode::Hsl {
+            .style(if settings.default_picker_mode == *mode {
                 primary_button_style
             } else {
                 secondary_button_style
-            }),
-        button(text("OKLCH").size(12))
-            .on_press(Message::SettingsDefaultPickerModeChanged(PickerMode::Oklch))
+            })
+            .into()
+    }))
+    .spacing(SPACE_XS);
+
+    let picker_hint = text("Default color space when opening the color picker")
+        .size(11)
+        .color(TEXT_MUTED);
+
+    let color_dedupe_buttons = row(ColorDedupe::ALL.iter().map(|dedupe| {
+        button(text(dedupe.display_name()).size(12))
+            .on_press(Message::SettingsColorDedupeChanged(*dedupe))
             .padding([SPACE_XS, SPACE_SM])
-            .style(if settings.default_picker_mode == PickerMode::Oklch {
+            .style(if settings.color_dedupe == *dedupe {
                 primary_button_style
             } else {
                 secondary_button_style
+            })
+            .into()
+    }))
+    .spacing(SPACE_XS);
+
+    let color_dedupe_hint = text(match settings.color_dedupe {
+        ColorDedupe::Exact => "Colors are duplicates when RGB and alpha both match",
+        ColorDedupe::IgnoreAlpha => "Colors with the same RGB are duplicates, whatever the alpha",
+        ColorDedupe::Perceptual => "Colors that look the same (ΔE below 2) are duplicates",
+    })
+    .size(11)
+    .color(TEXT_MUTED);
+
+    // Copy templates: name, template, remove
+    let template_rows = column(
+        settings
+            .copy_templates
+            .iter()
+            .enumerate()
+            .map(|(index, t)| {
+                row![
+                    text_input("Name", &t.name)
+                        .on_input(move |name| Message::SettingsCopyTemplateNameChanged(index, name))
+                        .size(12)
+                        .padding(SPACE_XS)
+                        .width(Length::Fixed(100.0))
+                        .style(|theme, status| input_style(theme, status, false)),
+                    text_input("{label}: {hex}", &t.template)
+                        .on_input(move |text| Message::SettingsCopyTemplateChanged(index, text))
+                        .size(12)
+                        .padding(SPACE_XS)
+                        .width(Length::Fill)
+                        .font(iced::Font::MONOSPACE)
+                        .style(|theme, status| input_style(theme, status, false)),
+                    labeled(
+                        button(icons::x().size(12))
+                            .on_press(Message::SettingsCopyTemplateRemoved(index))
+                            .padding([SPACE_XS, SPACE_SM])
+                            .style(subtle_button_style),
+                        "Remove template"
+                    ),
+                ]
+                .spacing(SPACE_XS)
+                .align_y(iced::Alignment::Center)
+                .into()
             }),
+    )
+    .spacing(SPACE_XS);
+    let add_template_button = button(text("Add copy template").size(12))
+        .on_press(Message::SettingsCopyTemplateAdded)
+        .padding([SPACE_XS, SPACE_SM])
+        .style(secondary_button_style);
+    let template_hint = text(format!(
+        "Extra copy buttons on color cards and in the picker. {}",
+        CopyTemplate::PLACEHOLDERS
+            .iter()
+            .map(|(placeholder, meaning)| format!("{} {}", placeholder, meaning))
+            .collect::<Vec<_>>()
+            .join(", ")
+    ))
+    .size(11)
+    .color(TEXT_MUTED);
+
+    // Cards section
+    let cards_section_title = row![
+        icons::sliders().size(14).color(TEXT_SECONDARY),
+        text("Cards").size(14).color(TEXT_SECONDARY),
     ]
+    .spacing(SPACE_SM)
+    .align_y(iced::Alignment::Center);
+
+    let card_actions_buttons = row(CardActions::ALL.iter().map(|visibility| {
+        button(text(visibility.display_name()).size(12))
+            .on_press(Message::SettingsCardActionsChanged(*visibility))
+            .padding([SPACE_XS, SPACE_SM])
+            .style(if settings.card_actions == *visibility {
+                primary_button_style
+            } else {
+                secondary_button_style
+            })
+            .into()
+    }))
     .spacing(SPACE_XS);
 
-    let picker_hint = text("Default color space when opening the color picker")
+    let card_actions_hint = text("When action buttons are shown on snippet cards")
         .size(11)
         .color(TEXT_MUTED);
 
+    let favorites_bar_toggle = checkbox(settings.show_favorites_bar)
+        .label("Show favorites bar (right-click a card to add favorites)")
+        .on_toggle(Message::SettingsShowFavoritesBarChanged)
+        .text_size(12);
+
+    let source_badge_toggle = checkbox(settings.show_source_badge)
+        .label("Show the app captured snippets were copied from")
+        .on_toggle(Message::SettingsShowSourceBadgeChanged)
+        .text_size(12);
+
+    let combine_separator_buttons = row(CombineSeparator::ALL.iter().map(|separator| {
+        button(text(separator.display_name()).size(12))
+            .on_press(Message::SettingsCombineSeparatorChanged(*separator))
+            .padding([SPACE_XS, SPACE_SM])
+            .style(if settings.combine_separator == *separator {
+                primary_button_style
+            } else {
+                secondary_button_style
+            })
+            .into()
+    }))
+    .spacing(SPACE_XS);
+
+    let copy_format_buttons = row(CopyFormat::ALL.iter().map(|format| {
+        button(text(format.display_name()).size(12))
+            .on_press(Message::SettingsCopyFormatChanged(*format))
+            .padding([SPACE_XS, SPACE_SM])
+            .style(if settings.copy_format == *format {
+                primary_button_style
+            } else {
+                secondary_button_style
+            })
+            .into()
+    }))
+    .spacing(SPACE_XS);
+
+    let markdown_text_buttons = row(MarkdownText::ALL.iter().map(|style| {
+        button(text(style.display_name()).size(12))
+            .on_press(Message::SettingsMarkdownTextChanged(*style))
+            .padding([SPACE_XS, SPACE_SM])
+            .style(if settings.markdown_text == *style {
+                primary_button_style
+            } else {
+                secondary_button_style
+            })
+            .into()
+    }))
+    .spacing(SPACE_XS);
+
+    let code_theme_row = row![
+        text("Code theme:").size(12).color(TEXT_SECONDARY),
+        pick_list(
+            CodeTheme::ALL,
+            Some(settings.code_theme),
+            Message::SettingsCodeThemeChanged
+        )
+        .text_size(12)
+        .padding([SPACE_XS, SPACE_SM])
+        .style(pick_list_style),
+    ]
+    .spacing(SPACE_SM)
+    .align_y(iced::Alignment::Center);
+
+    let code_theme_hint = text("Syntax highlighting of code previews and the code editor")
+        .size(11)
+        .color(TEXT_MUTED);
+
+    let combine_separator_hint =
+        text("Between snippets copied with \"Copy combined\" in selection mode")
+            .size(11)
+            .color(TEXT_MUTED);
+
+    let copy_format_hint = text(
+        "What \"Copy\" puts on the clipboard for code, JSON and text; Markdown fences code with its language",
+    )
+    .size(11)
+    .color(TEXT_MUTED);
+
+    let markdown_text_hint = text("How text snippets are written when copied as Markdown")
+        .size(11)
+        .color(TEXT_MUTED);
+
+    // Pinned windows section
+    let windows_section_title = row![
+        icons::push_pin().size(14).color(TEXT_SECONDARY),
+        text("Popup Windows").size(14).color(TEXT_SECONDARY),
+    ]
+    .spacing(SPACE_SM)
+    .align_y(iced::Alignment::Center);
+
+    let appearance = settings.window_appearance;
+    let translucent_toggle = checkbox(appearance.translucent)
+        .label("Translucent background")
+        .on_toggle(move |translucent| {
+            Message::SettingsWindowAppearanceChanged(WindowAppearance {
+                translucent,
+                ..appearance
+            })
+        })
+        .text_size(12);
+
+    let opacity_row = row![
+        text("Opacity:").size(12).color(TEXT_SECONDARY),
+        slider(
+            WindowAppearance::MIN_OPACITY..=1.0,
+            appearance.opacity,
+            move |opacity| {
+                Message::SettingsWindowAppearanceChanged(WindowAppearance {
+                    opacity,
+                    ..appearance
+                })
+            }
+        )
+        .step(0.05)
+        .width(Length::Fixed(160.0)),
+        text(format!("{:.0}%", appearance.opacity * 100.0))
+            .size(12)
+            .color(TEXT_MUTED),
+    ]
+    .spacing(SPACE_SM)
+    .align_y(iced::Alignment::Center);
+
+    let blur_toggle = checkbox(appearance.blur)
+        .label("Blur what's behind (macOS and Linux)")
+        .on_toggle_maybe(appearance.translucent.then_some(move |blur| {
+            Message::SettingsWindowAppearanceChanged(WindowAppearance { blur, ..appearance })
+        }))
+        .text_size(12);
+
+    let borderless_toggle = checkbox(appearance.borderless)
+        .label("Borderless with rounded corners")
+        .on_toggle(move |borderless| {
+            Message::SettingsWindowAppearanceChanged(WindowAppearance {
+                borderless,
+                ..appearance
+            })
+        })
+        .text_size(12);
+
+    let windows_hint = text(
+        "Applies to pinned snippets, palette boards and the quick capture popup opened from now on",
+    )
+    .size(11)
+    .color(TEXT_MUTED);
+
+    // Capture section
+    let capture_section_title = row![
+        icons::tag().size(14).color(TEXT_SECONDARY),
+        text("Capture").size(14).color(TEXT_SECONDARY),
+    ]
+    .spacing(SPACE_SM)
+    .align_y(iced::Alignment::Center);
+
+    let mut capture_choices = vec![CapturePaletteChoice {
+        palette_id: None,
+        name: "No palette".to_string(),
+    }];
+    capture_choices.extend(palettes.iter().filter(|p| !p.is_smart()).map(|p| {
+        CapturePaletteChoice {
+            palette_id: Some(p.id),
+            name: p.name.clone(),
+        }
+    }));
+    let selected_capture = capture_choices
+        .iter()
+        .find(|c| c.palette_id == settings.capture_palette)
+        .cloned();
+    let capture_palette_row = row![
+        text("Capture into:").size(12).color(TEXT_SECONDARY),
+        pick_list(capture_choices, selected_capture, |choice| {
+            Message::SettingsCapturePaletteChanged(choice.palette_id)
+        })
+        .text_size(12)
+        .padding([SPACE_XS, SPACE_SM])
+        .style(pick_list_style),
+    ]
+    .spacing(SPACE_SM)
+    .align_y(iced::Alignment::Center);
+
+    let capture_hint = text("Pasted and auto-captured snippets are added to this palette")
+        .size(11)
+        .color(TEXT_MUTED);
+
+    let secret_policy_buttons = row(SecretPolicy::ALL.iter().map(|policy| {
+        button(text(policy.display_name()).size(12))
+            .on_press(Message::SettingsSecretPolicyChanged(*policy))
+            .padding([SPACE_XS, SPACE_SM])
+            .style(if settings.secret_policy == *policy {
+                primary_button_style
+            } else {
+                secondary_button_style
+            })
+            .into()
+    }))
+    .spacing(SPACE_XS);
+
+    let secret_policy_hint =
+        text("Auto-captured AWS keys, JWTs and private keys are warned about, redacted or skipped")
+            .size(11)
+            .color(TEXT_MUTED);
+
+    // Text expansion section
+    let expansion_section_title = row![
+        icons::text_aa().size(14).color(TEXT_SECONDARY),
+        text("Text Expansion").size(14).color(TEXT_SECONDARY),
+    ]
+    .spacing(SPACE_SM)
+    .align_y(iced::Alignment::Center);
+
+    let text_expansion_toggle = checkbox(settings.text_expansion)
+        .label("Expand abbreviations typed in any app")
+        .on_toggle(Message::SettingsTextExpansionChanged)
+        .text_size(12);
+
+    let text_expansion_hint = text(
+        "Set an abbreviation from a card's menu. Shard then reads every key typed, \
+         which on Linux needs the input group and wtype or xdotool.",
+    )
+    .size(11)
+    .color(TEXT_MUTED);
+
     // Data section - Export/Import
     let data_section_title = row![
         icons::export().size(14).color(TEXT_SECONDARY),
@@ -248,7 +791,173 @@ pub fn view_settings_modal(settings: &SettingsState) -> Element<'_, Message> {
         .padding([SPACE_XS, SPACE_SM])
         .style(secondary_button_style);
 
-    let data_buttons = row![export_button, import_button].spacing(SPACE_SM);
+    let export_files_button = button(text("Export code as files").size(12))
+        .on_press(Message::ExportCodeFiles(false))
+        .padding([SPACE_XS, SPACE_SM])
+        .style(secondary_button_style);
+
+    let export_files_grouped_button = button(text("…grouped by palette").size(12))
+        .on_press(Message::ExportCodeFiles(true))
+        .padding([SPACE_XS, SPACE_SM])
+        .style(secondary_button_style);
+
+    let import_code_files_button = button(text("Import code files").size(12))
+        .on_press(Message::ImportCodeFiles)
+        .padding([SPACE_XS, SPACE_SM])
+        .style(secondary_button_style);
+
+    let import_bookmarks_button = button(text("Import bookmarks").size(12))
+        .on_press(Message::ImportBookmarks(false))
+        .padding([SPACE_XS, SPACE_SM])
+        .style(secondary_button_style);
+
+    let import_bookmarks_grouped_button = button(text("…into palettes by folder").size(12))
+        .on_press(Message::ImportBookmarks(true))
+        .padding([SPACE_XS, SPACE_SM])
+        .style(secondary_button_style);
+
+    let import_gpl_button = button(text("Import GIMP palette (.gpl)").size(12))
+        .on_press(Message::ImportGplPalette)
+        .padding([SPACE_XS, SPACE_SM])
+        .style(secondary_button_style);
+
+    let import_tokens_button = button(text("Import design tokens (.json)").size(12))
+        .on_press(Message::ImportTokensPalette)
+        .padding([SPACE_XS, SPACE_SM])
+        .style(secondary_button_style);
+
+    let encryption_row: Element<'a, Message> = if database_encrypted {
+        row![
+            icons::check().size(12).color(TEXT_MUTED),
+            text("Database is encrypted").size(12).color(TEXT_MUTED)
+        ]
+        .spacing(SPACE_XS)
+        .align_y(iced::Alignment::Center)
+        .into()
+    } else if db::ENCRYPTION_SUPPORTED {
+        button(text("Encrypt database…").size(12))
+            .on_press(Message::OpenEncryptDatabase)
+            .padding([SPACE_XS, SPACE_SM])
+            .style(secondary_button_style)
+            .into()
+    } else {
+        iced::widget::Space::new().into()
+    };
+
+    let database_location = db::get_database_path()
+        .map(|path| path.display().to_string())
+        .unwrap_or_default();
+    let database_row = row![
+        text(database_location)
+            .size(11)
+            .color(TEXT_MUTED)
+            .width(Length::Fill),
+        button(text("Move…").size(12))
+            .on_press(Message::PickDatabaseFolder)
+            .padding([SPACE_XS, SPACE_SM])
+            .style(secondary_button_style),
+        button(text("Use default").size(12))
+            .on_press_maybe(
+                settings
+                    .database_path
+                    .is_some()
+                    .then_some(Message::ResetDatabaseFolder)
+            )
+            .padding([SPACE_XS, SPACE_SM])
+            .style(subtle_button_style),
+    ]
+    .spacing(SPACE_SM)
+    .align_y(iced::Alignment::Center);
+
+    let data_buttons = column![
+        database_row,
+        text("Moving to a folder that already has a Shard database opens that one")
+            .size(11)
+            .color(TEXT_MUTED),
+        row![export_button, import_button].spacing(SPACE_SM),
+        row![import_gpl_button, import_tokens_button].spacing(SPACE_SM),
+        row![import_bookmarks_button, import_bookmarks_grouped_button].spacing(SPACE_SM),
+        row![
+            export_files_button,
+            export_files_grouped_button,
+            import_code_files_button
+        ]
+        .spacing(SPACE_SM),
+        text("Code files use the current filter; exported and imported files stay linked")
+            .size(11)
+            .color(TEXT_MUTED),
+    ]
+    .spacing(SPACE_SM);
+
+    // Database maintenance section
+    let database_section_title = row![
+        icons::database().size(14).color(TEXT_SECONDARY),
+        text("Database").size(14).color(TEXT_SECONDARY),
+    ]
+    .spacing(SPACE_SM)
+    .align_y(iced::Alignment::Center);
+
+    let maintenance_button = |label, message: Message| {
+        button(text(label).size(12))
+            .on_press_maybe((!settings.maintenance_busy).then_some(message))
+            .padding([SPACE_XS, SPACE_SM])
+            .style(secondary_button_style)
+    };
+    let maintenance_buttons = row![
+        maintenance_button("Check integrity", Message::CheckDatabaseIntegrity),
+        maintenance_button("Compact (vacuum)", Message::VacuumDatabase),
+        maintenance_button("Show stats", Message::ShowDatabaseStats),
+    ]
+    .spacing(SPACE_SM);
+
+    let maintenance_report: Element<'_, Message> = match &settings.maintenance_report {
+        _ if settings.maintenance_busy => text("Working…").size(11).color(TEXT_MUTED).into(),
+        Some(Ok(report)) => text(report).size(11).color(TEXT_SECONDARY).into(),
+        Some(Err(e)) => text(e).size(11).color(DANGER).into(),
+        None => text("Compacting reclaims space left by deleted snippets")
+            .size(11)
+            .color(TEXT_MUTED)
+            .into(),
+    };
+
+    let crash_reports_toggle = checkbox(settings.crash_reports)
+        .label("Write a crash report if Shard crashes")
+        .on_toggle(Message::SettingsCrashReportsChanged)
+        .text_size(12);
+
+    let crash_reports_hint = text(
+        "Reports hold a backtrace, the app version and recent actions, never snippet content. \
+         You're offered to open one on the next start.",
+    )
+    .size(11)
+    .color(TEXT_MUTED);
+
+    let update_check_toggle = checkbox(settings.check_for_updates)
+        .label("Check for updates on startup")
+        .on_toggle(Message::SettingsCheckForUpdatesChanged)
+        .text_size(12);
+
+    let update_check_hint =
+        text("Asks GitHub for the latest release. Nothing about your snippets is sent.")
+            .size(11)
+            .color(TEXT_MUTED);
+
+    let trash_retention_buttons = row(TrashRetention::ALL.iter().map(|retention| {
+        button(text(retention.display_name()).size(12))
+            .on_press(Message::SettingsTrashRetentionChanged(*retention))
+            .padding([SPACE_XS, SPACE_SM])
+            .style(if settings.trash_retention == *retention {
+                primary_button_style
+            } else {
+                secondary_button_style
+            })
+            .into()
+    }))
+    .spacing(SPACE_XS);
+
+    let trash_retention_hint = text("How long deleted snippets stay in the trash")
+        .size(11)
+        .color(TEXT_MUTED);
 
     // Keyboard shortcuts section
     let keyboard_section_title = row![
@@ -266,10 +975,13 @@ pub fn view_settings_modal(settings: &SettingsState) -> Element<'_, Message> {
     let keyboard_section = column(shortcut_rows).spacing(SPACE_XS);
 
     let recording_hint: Element<'_, Message> = if settings.recording_action.is_some() {
-        text("Press any key combination to assign...")
-            .size(11)
-            .color(TEXT_MUTED)
-            .into()
+        match &settings.recording_feedback {
+            Some(feedback) => text(feedback).size(11).color(TEXT_SECONDARY).into(),
+            None => text("Press any key combination to assign...")
+                .size(11)
+                .color(TEXT_MUTED)
+                .into(),
+        }
     } else {
         container(text("")).into()
     };
@@ -296,10 +1008,58 @@ pub fn view_settings_modal(settings: &SettingsState) -> Element<'_, Message> {
             preset_row,
             command_preview,
             custom_command_section,
+            review_edits_toggle,
+            iced::widget::Space::new().height(Length::Fixed(SPACE_SM)),
+            editing_section_title,
+            word_wrap_toggle,
+            code_word_wrap_toggle,
+            tab_width_buttons,
+            insert_spaces_toggle,
+            iced::widget::Space::new().height(Length::Fixed(SPACE_SM)),
+            run_section_title,
+            run_command_rows,
+            add_run_command_button,
+            run_command_hint,
             iced::widget::Space::new().height(Length::Fixed(SPACE_SM)),
             picker_section_title,
             picker_mode_buttons,
             picker_hint,
+            color_dedupe_buttons,
+            color_dedupe_hint,
+            template_rows,
+            add_template_button,
+            template_hint,
+            iced::widget::Space::new().height(Length::Fixed(SPACE_SM)),
+            cards_section_title,
+            card_actions_buttons,
+            card_actions_hint,
+            favorites_bar_toggle,
+            source_badge_toggle,
+            combine_separator_buttons,
+            combine_separator_hint,
+            copy_format_buttons,
+            copy_format_hint,
+            markdown_text_buttons,
+            markdown_text_hint,
+            code_theme_row,
+            code_theme_hint,
+            iced::widget::Space::new().height(Length::Fixed(SPACE_SM)),
+            windows_section_title,
+            translucent_toggle,
+            opacity_row,
+            blur_toggle,
+            borderless_toggle,
+            windows_hint,
+            iced::widget::Space::new().height(Length::Fixed(SPACE_SM)),
+            capture_section_title,
+            capture_palette_row,
+            capture_hint,
+            secret_policy_buttons,
+            secret_policy_hint,
+            iced::widget::Space::new().height(Length::Fixed(SPACE_SM)),
+            expansion_section_title,
+            text_expansion_toggle,
+            text_expansion_hint,
             iced::widget::Space::new().height(Length::Fixed(SPACE_SM)),
             keyboard_section_title,
             keyboard_section,
@@ -307,6 +1067,17 @@ pub fn view_settings_modal(settings: &SettingsState) -> Element<'_, Message> {
             iced::widget::Space::new().height(Length::Fixed(SPACE_SM)),
             data_section_title,
             data_buttons,
+            encryption_row,
+            trash_retention_buttons,
+            trash_retention_hint,
+            iced::widget::Space::new().height(Length::Fixed(SPACE_SM)),
+            database_section_title,
+            maintenance_buttons,
+            maintenance_report,
+            crash_reports_toggle,
+            crash_reports_hint,
+            update_check_toggle,
+            update_check_hint,
         ]
         .spacing(SPACE_MD)
         .padding(SPACE_MD),
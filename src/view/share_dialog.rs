@@ -0,0 +1,155 @@
+//! Dialog for exporting a code snippet as a share image.
+
+use iced::highlighter;
+use iced::widget::{button, checkbox, column, container, mouse_area, opaque, pick_list, row, text};
+use iced::{Element, Length};
+
+use crate::icons;
+use crate::message::Message;
+use crate::share_image::ShareImageOptions;
+use crate::theme::{
+    modal_dialog_style, modal_overlay_style, pick_list_style, primary_button_style,
+    secondary_button_style, subtle_button_style, SPACE_MD, SPACE_SM, SPACE_XS, TEXT_MUTED,
+    TEXT_PRIMARY, TEXT_SECONDARY,
+};
+use crate::view::labeled;
+
+/// Pixel densities offered in the dialog.
+const SCALES: [u32; 3] = [1, 2, 3];
+
+/// Backdrop paddings offered in the dialog (label, pixels at 1x).
+const PADDINGS: [(&str, u32); 3] = [("S", 24), ("M", 48), ("L", 96)];
+
+/// State for the share image dialog.
+#[derive(Debug, Clone)]
+pub struct ShareImageState {
+    /// The code snippet being exported
+    pub snippet_id: i64,
+    pub options: ShareImageOptions,
+}
+
+impl ShareImageState {
+    /// Create dialog state for a snippet with default options.
+    pub fn new(snippet_id: i64) -> Self {
+        Self {
+            snippet_id,
+            options: ShareImageOptions::default(),
+        }
+    }
+}
+
+/// Render the share image dialog.
+pub fn view_share_image_modal(state: &ShareImageState) -> Element<'_, Message> {
+    let options = state.options;
+
+    let header_row = row![
+        text("Share as Image").size(20).color(TEXT_PRIMARY),
+        iced::widget::Space::new().width(Length::Fill),
+        labeled(
+            button(icons::x().size(16))
+                .on_press(Message::CloseShareImage)
+                .padding([SPACE_XS, SPACE_SM])
+                .style(subtle_button_style),
+            "Close"
+        ),
+    ]
+    .spacing(SPACE_XS)
+    .align_y(iced::Alignment::Center);
+
+    let theme_row = row![
+        text("Theme:").size(12).color(TEXT_SECONDARY),
+        pick_list(
+            highlighter::Theme::ALL,
+            Some(options.theme),
+            Message::ShareImageThemeChanged
+        )
+        .text_size(12)
+        .padding([SPACE_XS, SPACE_SM])
+        .style(pick_list_style),
+    ]
+    .spacing(SPACE_SM)
+    .align_y(iced::Alignment::Center);
+
+    let scale_buttons = row(SCALES.iter().map(|scale| {
+        button(text(format!("{}x", scale)).size(12))
+            .on_press(Message::ShareImageScaleChanged(*scale))
+            .padding([SPACE_XS, SPACE_SM])
+            .style(if options.scale == *scale {
+                primary_button_style
+            } else {
+                secondary_button_style
+            })
+            .into()
+    }))
+    .spacing(SPACE_XS);
+    let size_row = row![text("Size:").size(12).color(TEXT_SECONDARY), scale_buttons]
+        .spacing(SPACE_SM)
+        .align_y(iced::Alignment::Center);
+
+    let padding_buttons = row(PADDINGS.iter().map(|(label, padding)| {
+        button(text(*label).size(12))
+            .on_press(Message::ShareImagePaddingChanged(*padding))
+            .padding([SPACE_XS, SPACE_SM])
+            .style(if options.padding == *padding {
+                primary_button_style
+            } else {
+                secondary_button_style
+            })
+            .into()
+    }))
+    .spacing(SPACE_XS);
+    let padding_row = row![
+        text("Padding:").size(12).color(TEXT_SECONDARY),
+        padding_buttons
+    ]
+    .spacing(SPACE_SM)
+    .align_y(iced::Alignment::Center);
+
+    let chrome_toggle = checkbox(options.window_chrome)
+        .label("Window controls")
+        .on_toggle(Message::ShareImageChromeToggled)
+        .text_size(12);
+
+    let hint = text("Saves a syntax-highlighted PNG of the snippet")
+        .size(11)
+        .color(TEXT_MUTED);
+
+    let action_buttons = row![
+        button(text("Cancel").size(14))
+            .on_press(Message::CloseShareImage)
+            .padding(SPACE_SM)
+            .style(secondary_button_style),
+        button(text("Save PNG").size(14))
+            .on_press(Message::SaveShareImage)
+            .padding(SPACE_SM)
+            .style(primary_button_style),
+    ]
+    .spacing(SPACE_SM);
+
+    let modal_content = column![
+        header_row,
+        theme_row,
+        size_row,
+        padding_row,
+        chrome_toggle,
+        hint,
+        action_buttons,
+    ]
+    .spacing(SPACE_MD)
+    .padding(SPACE_MD)
+    .width(Length::Fixed(360.0));
+
+    let modal_dialog = container(modal_content).style(modal_dialog_style);
+
+    // Semi-transparent overlay
+    mouse_area(
+        container(opaque(modal_dialog))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x(Length::Fill)
+            .center_y(Length::Fill)
+            .style(modal_overlay_style),
+    )
+    .on_press(Message::CloseShareImage)
+    .into()
+}
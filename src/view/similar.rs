@@ -0,0 +1,187 @@
+//! Similar colors: every other color snippet ranked by how close it looks
+//! to one color, to find near-duplicates worth merging.
+
+use iced::widget::{button, column, container, mouse_area, opaque, row, scrollable, text, Canvas};
+use iced::{Element, Length};
+
+use crate::icons;
+use crate::message::Message;
+use crate::snippet::{ColorData, Snippet, SnippetContent};
+use crate::theme::{
+    danger_button_style, modal_dialog_style, modal_overlay_style, scrollbar_style,
+    secondary_button_style, subtle_button_style, SPACE_MD, SPACE_SM, SPACE_XS, TEXT_MUTED,
+    TEXT_PRIMARY, TEXT_SECONDARY,
+};
+use crate::view::labeled;
+use crate::widgets::ColorSwatch;
+
+/// Matches listed, closest first.
+const MAX_MATCHES: usize = 20;
+
+/// Below this Delta-E two colors look the same.
+const SAME_DELTA_E: f32 = 2.0;
+/// Below this Delta-E two colors are hard to tell apart side by side.
+const CLOSE_DELTA_E: f32 = 5.0;
+
+/// State of the similar colors panel.
+#[derive(Debug, Clone)]
+pub struct SimilarState {
+    /// Color snippet the others are compared with.
+    pub color_id: i64,
+}
+
+/// Rank the other color snippets by Delta-E to `color`, closest first.
+fn similar_colors<'a>(
+    snippets: &'a [Snippet],
+    color_id: i64,
+    color: &ColorData,
+) -> Vec<(&'a Snippet, &'a ColorData, f32)> {
+    let mut matches: Vec<_> = snippets
+        .iter()
+        .filter(|s| s.id != color_id)
+        .filter_map(|s| match &s.content {
+            SnippetContent::Color(other) => Some((s, other, color.delta_e(other))),
+            _ => None,
+        })
+        .collect();
+    matches.sort_by(|a, b| a.2.total_cmp(&b.2));
+    matches.truncate(MAX_MATCHES);
+    matches
+}
+
+/// Render the similar colors panel.
+pub fn view_similar_modal<'a>(
+    state: &'a SimilarState,
+    snippets: &'a [Snippet],
+) -> Element<'a, Message> {
+    let header_row = row![
+        text("Similar Colors").size(20).color(TEXT_PRIMARY),
+        iced::widget::Space::new().width(Length::Fill),
+        labeled(
+            button(icons::x().size(16))
+                .on_press(Message::CloseSimilarColors)
+                .padding([SPACE_XS, SPACE_SM])
+                .style(subtle_button_style),
+            "Close"
+        ),
+    ]
+    .spacing(SPACE_XS)
+    .align_y(iced::Alignment::Center);
+
+    let target = snippets
+        .iter()
+        .find(|s| s.id == state.color_id)
+        .and_then(|s| match &s.content {
+            SnippetContent::Color(color) => Some((s, color)),
+            _ => None,
+        });
+    let Some((target, color)) = target else {
+        return modal(
+            column![
+                header_row,
+                text("The color snippet no longer exists")
+                    .size(12)
+                    .color(TEXT_MUTED)
+            ]
+            .spacing(SPACE_MD)
+            .into(),
+        );
+    };
+
+    let swatch = |color: &ColorData, size: f32| {
+        Canvas::new(ColorSwatch {
+            color: color.to_iced_color(),
+        })
+        .width(size)
+        .height(size)
+    };
+    let target_row = row![
+        swatch(color, 32.0),
+        text(&target.label).size(14).color(TEXT_PRIMARY),
+        text(color.to_hex()).size(12).color(TEXT_MUTED),
+    ]
+    .spacing(SPACE_SM)
+    .align_y(iced::Alignment::Center);
+
+    let matches = similar_colors(snippets, state.color_id, color);
+    let list: Element<'a, Message> = if matches.is_empty() {
+        text("There are no other color snippets")
+            .size(12)
+            .color(TEXT_MUTED)
+            .into()
+    } else {
+        let rows = matches.into_iter().map(|(snippet, other, distance)| {
+            let verdict = if distance < SAME_DELTA_E {
+                "Looks the same"
+            } else if distance < CLOSE_DELTA_E {
+                "Very close"
+            } else {
+                ""
+            };
+            row![
+                swatch(other, 24.0),
+                column![
+                    text(&snippet.label).size(13).color(TEXT_PRIMARY),
+                    text(other.to_hex()).size(11).color(TEXT_MUTED),
+                ]
+                .width(Length::Fill),
+                text(verdict).size(11).color(TEXT_SECONDARY),
+                text(format!("ΔE {:.1}", distance))
+                    .size(12)
+                    .color(TEXT_PRIMARY)
+                    .width(56),
+                button(text("Show").size(12))
+                    .on_press(Message::ShowSimilarColor(snippet.id))
+                    .padding([SPACE_XS, SPACE_SM])
+                    .style(secondary_button_style),
+                labeled(
+                    button(icons::trash().size(14))
+                        .on_press(Message::DeleteSnippet(snippet.id))
+                        .padding([SPACE_XS, SPACE_SM])
+                        .style(danger_button_style),
+                    "Move to trash"
+                ),
+            ]
+            .spacing(SPACE_SM)
+            .align_y(iced::Alignment::Center)
+            .into()
+        });
+        scrollable(column(rows).spacing(SPACE_XS).padding([0.0, SPACE_SM]))
+            .height(Length::Fixed(320.0))
+            .style(scrollbar_style)
+            .into()
+    };
+
+    let hint = text(format!(
+        "Closest {} color snippets by ΔE (OKLab); below {} they look the same",
+        MAX_MATCHES, SAME_DELTA_E
+    ))
+    .size(11)
+    .color(TEXT_MUTED);
+
+    modal(
+        column![header_row, target_row, list, hint]
+            .spacing(SPACE_MD)
+            .into(),
+    )
+}
+
+/// Wrap the panel's content in the dialog and its overlay.
+fn modal(content: Element<'_, Message>) -> Element<'_, Message> {
+    let modal_dialog = container(content)
+        .padding(SPACE_MD)
+        .width(Length::Fixed(500.0))
+        .style(modal_dialog_style);
+
+    // Semi-transparent overlay
+    mouse_area(
+        container(opaque(modal_dialog))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x(Length::Fill)
+            .center_y(Length::Fill)
+            .style(modal_overlay_style),
+    )
+    .on_press(Message::CloseSimilarColors)
+    .into()
+}
@@ -0,0 +1,109 @@
+//! Filter bar of the second snippet pane.
+
+use iced::widget::{container, pick_list, row, text_input};
+use iced::{Element, Length};
+
+use crate::config::SplitPane;
+use crate::db::{self, Palette};
+use crate::message::Message;
+use crate::snippet::SnippetKind;
+use crate::theme::{header_style, input_style, pick_list_style, SPACE_MD, SPACE_SM, SPACE_XS};
+use crate::view::tab_button;
+
+/// A palette choice in the pane's palette dropdown.
+#[derive(Debug, Clone, PartialEq)]
+struct PaletteChoice {
+    palette_id: Option<i64>,
+    name: String,
+}
+
+impl std::fmt::Display for PaletteChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+/// Render the search, kind tabs and palette dropdown of the second pane.
+pub fn view_split_pane_bar<'a>(
+    split_pane: &'a SplitPane,
+    filter_text: &'a str,
+    palettes: &'a [Palette],
+) -> Element<'a, Message> {
+    let search = text_input("Search this pane...", filter_text)
+        .on_input(Message::SplitPaneFilterChanged)
+        .width(Length::Fill)
+        .padding([SPACE_XS, SPACE_SM])
+        .size(13)
+        .style(|theme, status| input_style(theme, status, false));
+
+    let kind = split_pane.kind.as_ref();
+    let tabs = row![
+        tab_button("All", kind.is_none(), Message::SplitPaneKindChanged(None)),
+        tab_button(
+            "Colors",
+            kind == Some(&SnippetKind::Color),
+            Message::SplitPaneKindChanged(Some(SnippetKind::Color))
+        ),
+        tab_button(
+            "Sets",
+            kind == Some(&SnippetKind::ColorSet),
+            Message::SplitPaneKindChanged(Some(SnippetKind::ColorSet))
+        ),
+        tab_button(
+            "Gradients",
+            kind == Some(&SnippetKind::Gradient),
+            Message::SplitPaneKindChanged(Some(SnippetKind::Gradient))
+        ),
+        tab_button(
+            "Code",
+            kind == Some(&SnippetKind::Code),
+            Message::SplitPaneKindChanged(Some(SnippetKind::Code))
+        ),
+        tab_button(
+            "JSON",
+            kind == Some(&SnippetKind::Json),
+            Message::SplitPaneKindChanged(Some(SnippetKind::Json))
+        ),
+        tab_button(
+            "Text",
+            kind == Some(&SnippetKind::Text),
+            Message::SplitPaneKindChanged(Some(SnippetKind::Text))
+        ),
+        tab_button(
+            "Files",
+            kind == Some(&SnippetKind::File),
+            Message::SplitPaneKindChanged(Some(SnippetKind::File))
+        ),
+    ]
+    .spacing(SPACE_XS);
+
+    let choices: Vec<PaletteChoice> = std::iter::once(PaletteChoice {
+        palette_id: None,
+        name: "All Palettes".to_string(),
+    })
+    .chain(palettes.iter().map(|p| PaletteChoice {
+        palette_id: Some(p.id),
+        name: db::palette_path(palettes, p.id),
+    }))
+    .collect();
+    let selected = choices
+        .iter()
+        .find(|choice| choice.palette_id == split_pane.palette)
+        .cloned();
+    let palette_picker = pick_list(choices, selected, |choice: PaletteChoice| {
+        Message::SplitPanePaletteChanged(choice.palette_id)
+    })
+    .text_size(12)
+    .padding([SPACE_XS, SPACE_SM])
+    .style(pick_list_style);
+
+    container(
+        row![search, tabs, palette_picker]
+            .spacing(SPACE_SM)
+            .align_y(iced::Alignment::Center),
+    )
+    .width(Length::Fill)
+    .padding([SPACE_SM, SPACE_MD])
+    .style(header_style)
+    .into()
+}
@@ -0,0 +1,378 @@
+//! Table layout for the snippet list with sortable, resizable columns.
+
+use std::collections::HashMap;
+
+use iced::widget::{button, checkbox, container, mouse_area, row, scrollable, table, text};
+use iced::{mouse, Element, Length};
+
+use crate::db::Palette;
+use crate::icons;
+use crate::message::Message;
+use crate::snippet::{relative_time, unix_now, Snippet, SnippetContent};
+use crate::theme::{
+    scrollbar_style, BORDER_ACCENT, BORDER_SUBTLE, SPACE_MD, SPACE_XS, TEXT_MUTED, TEXT_PRIMARY,
+    TEXT_SECONDARY,
+};
+
+/// Narrowest width a column can be resized to.
+const MIN_COLUMN_WIDTH: f32 = 60.0;
+
+/// Columns of the snippet table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TableColumn {
+    Label,
+    Kind,
+    Language,
+    Palettes,
+    Words,
+    Updated,
+    Uses,
+}
+
+impl TableColumn {
+    /// All columns in display order.
+    pub const ALL: [TableColumn; 7] = [
+        TableColumn::Label,
+        TableColumn::Kind,
+        TableColumn::Language,
+        TableColumn::Palettes,
+        TableColumn::Words,
+        TableColumn::Updated,
+        TableColumn::Uses,
+    ];
+
+    /// Get the header title for this column.
+    pub fn title(&self) -> &'static str {
+        match self {
+            TableColumn::Label => "Label",
+            TableColumn::Kind => "Kind",
+            TableColumn::Language => "Language",
+            TableColumn::Palettes => "Palettes",
+            TableColumn::Words => "Words",
+            TableColumn::Updated => "Updated",
+            TableColumn::Uses => "Uses",
+        }
+    }
+
+    /// Get the initial width of this column in pixels.
+    fn default_width(&self) -> f32 {
+        match self {
+            TableColumn::Label => 240.0,
+            TableColumn::Kind => 80.0,
+            TableColumn::Language => 110.0,
+            TableColumn::Palettes => 180.0,
+            TableColumn::Words => 80.0,
+            TableColumn::Updated => 100.0,
+            TableColumn::Uses => 70.0,
+        }
+    }
+}
+
+/// Sort order and column widths for the table view.
+#[derive(Debug, Clone)]
+pub struct TableState {
+    pub sort_column: TableColumn,
+    pub sort_ascending: bool,
+    /// Widths of columns the user has resized.
+    pub widths: HashMap<TableColumn, f32>,
+    /// Column being resized, with the drag anchor (cursor x, width) once the cursor moves.
+    pub resizing: Option<(TableColumn, Option<(f32, f32)>)>,
+}
+
+impl Default for TableState {
+    fn default() -> Self {
+        Self {
+            sort_column: TableColumn::Label,
+            sort_ascending: true,
+            widths: HashMap::new(),
+            resizing: None,
+        }
+    }
+}
+
+impl TableState {
+    /// Get the current width of a column.
+    pub fn width(&self, column: TableColumn) -> f32 {
+        self.widths
+            .get(&column)
+            .copied()
+            .unwrap_or_else(|| column.default_width())
+    }
+
+    /// Sort by a column, flipping the direction if it is already the sort column.
+    pub fn toggle_sort(&mut self, column: TableColumn) {
+        if self.sort_column == column {
+            self.sort_ascending = !self.sort_ascending;
+        } else {
+            self.sort_column = column;
+            self.sort_ascending = true;
+        }
+    }
+
+    /// Start dragging the right edge of a column.
+    pub fn start_resize(&mut self, column: TableColumn) {
+        self.resizing = Some((column, None));
+    }
+
+    /// Update the column being resized for a cursor position within the table.
+    pub fn drag_resize(&mut self, cursor_x: f32) {
+        let Some((column, anchor)) = self.resizing else {
+            return;
+        };
+        match anchor {
+            None => self.resizing = Some((column, Some((cursor_x, self.width(column))))),
+            Some((start_x, start_width)) => {
+                let width = (start_width + cursor_x - start_x).max(MIN_COLUMN_WIDTH);
+                self.widths.insert(column, width);
+            }
+        }
+    }
+
+    /// Stop resizing.
+    pub fn end_resize(&mut self) {
+        self.resizing = None;
+    }
+}
+
+/// Sortable value of a snippet for a column.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum SortKey {
+    Text(String),
+    Number(Option<i64>),
+}
+
+fn sort_key(column: TableColumn, snippet: &Snippet, palette_names: &str) -> SortKey {
+    match column {
+        TableColumn::Label => SortKey::Text(snippet.label.to_lowercase()),
+        TableColumn::Kind => SortKey::Text(snippet.kind().display_name().to_string()),
+        TableColumn::Language => SortKey::Text(language_of(snippet).to_string()),
+        TableColumn::Palettes => SortKey::Text(palette_names.to_lowercase()),
+        TableColumn::Words => SortKey::Number(word_count_of(snippet).map(|count| count as i64)),
+        TableColumn::Updated => SortKey::Number(Some(snippet.updated_at)),
+        TableColumn::Uses => SortKey::Number(Some(snippet.copy_count)),
+    }
+}
+
+fn language_of(snippet: &Snippet) -> &str {
+    match &snippet.content {
+        SnippetContent::Code(code) => &code.language,
+        SnippetContent::Json(_) => "json",
+        _ => "",
+    }
+}
+
+fn word_count_of(snippet: &Snippet) -> Option<usize> {
+    match &snippet.content {
+        SnippetContent::Text(text_data) => Some(text_data.word_count()),
+        _ => None,
+    }
+}
+
+/// Sort table rows (snippets with their palette names) by the current sort column.
+fn sort_rows(rows: &mut [(&Snippet, String)], state: &TableState) {
+    rows.sort_by(|(a, a_palettes), (b, b_palettes)| {
+        let ordering = sort_key(state.sort_column, a, a_palettes)
+            .cmp(&sort_key(state.sort_column, b, b_palettes))
+            .then_with(|| a.label.cmp(&b.label));
+        if state.sort_ascending {
+            ordering
+        } else {
+            ordering.reverse()
+        }
+    });
+}
+
+/// Comma-separated names of the palettes a snippet belongs to.
+fn palette_names(
+    snippet_id: i64,
+    palettes: &[Palette],
+    snippet_palettes: &HashMap<i64, Vec<i64>>,
+) -> String {
+    snippet_palettes
+        .get(&snippet_id)
+        .map(|ids| {
+            palettes
+                .iter()
+                .filter(|p| ids.contains(&p.id))
+                .map(|p| p.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        })
+        .unwrap_or_default()
+}
+
+/// Render the snippet list as a table.
+pub fn view_snippet_table<'a>(
+    snippets: Vec<&'a Snippet>,
+    state: &'a TableState,
+    selected_snippet: Option<i64>,
+    checked: Option<&'a [i64]>,
+    palettes: &'a [Palette],
+    snippet_palettes: &'a HashMap<i64, Vec<i64>>,
+) -> Element<'a, Message> {
+    let mut rows: Vec<(&Snippet, String)> = snippets
+        .into_iter()
+        .map(|s| (s, palette_names(s.id, palettes, snippet_palettes)))
+        .collect();
+    sort_rows(&mut rows, state);
+    let now = unix_now();
+
+    let columns = TableColumn::ALL.map(|column| {
+        table::column(
+            header_cell(column, state),
+            move |(snippet, palettes): (&'a Snippet, String)| -> Element<'a, Message> {
+                // While picking snippets, rows toggle their check instead of selecting
+                let is_checked = checked.map(|ids| ids.contains(&snippet.id));
+                let is_selected = is_checked.unwrap_or(selected_snippet == Some(snippet.id));
+                let value = match column {
+                    TableColumn::Label => snippet.label.clone(),
+                    TableColumn::Kind => snippet.kind().display_name().to_string(),
+                    TableColumn::Language => language_of(snippet).to_string(),
+                    TableColumn::Palettes => palettes,
+                    TableColumn::Words => word_count_of(snippet)
+                        .map(|count| count.to_string())
+                        .unwrap_or_default(),
+                    TableColumn::Updated => relative_time(snippet.updated_at, now),
+                    TableColumn::Uses => snippet.copy_count.to_string(),
+                };
+                let cell = button(text(value).size(12).color(if is_selected {
+                    TEXT_PRIMARY
+                } else {
+                    TEXT_SECONDARY
+                }))
+                .on_press(match is_checked {
+                    Some(_) => Message::ToggleSnippetChecked(snippet.id),
+                    None => Message::SelectSnippet(Some(snippet.id)),
+                })
+                .width(Length::Fill)
+                .padding(0)
+                .style(|_theme, _status| button::Style::default());
+                let cell =
+                    mouse_area(cell).on_right_press(Message::ToggleCardMenu(Some(snippet.id)));
+                match is_checked {
+                    Some(is_checked) if column == TableColumn::Label => {
+                        let id = snippet.id;
+                        row![
+                            checkbox(is_checked)
+                                .on_toggle(move |_| Message::ToggleSnippetChecked(id))
+                                .size(14),
+                            cell
+                        ]
+                        .spacing(SPACE_XS)
+                        .align_y(iced::Alignment::Center)
+                        .into()
+                    }
+                    _ => cell.into(),
+                }
+            },
+        )
+        .width(Length::Fixed(state.width(column)))
+    });
+
+    let grid = table(columns, rows).padding_x(SPACE_MD).padding_y(SPACE_XS);
+
+    // Track the cursor over the whole table so drags keep working past the handle
+    let resizable = mouse_area(grid)
+        .on_move(|point| Message::TableColumnResizeMoved(point.x))
+        .on_release(Message::TableColumnResizeEnded);
+
+    scrollable(container(resizable).padding(SPACE_MD))
+        .direction(scrollable::Direction::Both {
+            vertical: scrollable::Scrollbar::default(),
+            horizontal: scrollable::Scrollbar::default(),
+        })
+        .height(Length::Fill)
+        .style(scrollbar_style)
+        .into()
+}
+
+/// Render a clickable header with a sort indicator and a resize handle.
+fn header_cell(column: TableColumn, state: &TableState) -> Element<'static, Message> {
+    let is_sorted = state.sort_column == column;
+    let indicator: Element<'static, Message> = if is_sorted && state.sort_ascending {
+        icons::caret_up().size(10).color(TEXT_SECONDARY).into()
+    } else if is_sorted {
+        icons::caret_down().size(10).color(TEXT_SECONDARY).into()
+    } else {
+        text("").into()
+    };
+
+    let title = button(
+        row![text(column.title()).size(12).color(TEXT_MUTED), indicator]
+            .spacing(SPACE_XS)
+            .align_y(iced::Alignment::Center),
+    )
+    .on_press(Message::SortTable(column))
+    .width(Length::Fill)
+    .padding(0)
+    .style(|_theme, _status| button::Style::default());
+
+    let is_resizing = state.resizing.is_some_and(|(c, _)| c == column);
+    let handle = mouse_area(
+        container(text(""))
+            .width(Length::Fixed(3.0))
+            .height(Length::Fixed(16.0))
+            .style(move |_theme| {
+                iced::widget::container::Style::default().background(if is_resizing {
+                    BORDER_ACCENT
+                } else {
+                    BORDER_SUBTLE
+                })
+            }),
+    )
+    .on_press(Message::TableColumnResizeStarted(column))
+    .interaction(mouse::Interaction::ResizingHorizontally);
+
+    row![title, handle].align_y(iced::Alignment::Center).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snippet(label: &str, updated_at: i64, copy_count: i64) -> Snippet {
+        Snippet {
+            updated_at,
+            copy_count,
+            ..Snippet::text("text".to_string(), label.to_string())
+        }
+    }
+
+    fn sorted_labels(snippets: &[Snippet], column: TableColumn, ascending: bool) -> Vec<&str> {
+        let state = TableState {
+            sort_column: column,
+            sort_ascending: ascending,
+            ..TableState::default()
+        };
+        let mut rows: Vec<(&Snippet, String)> =
+            snippets.iter().map(|s| (s, String::new())).collect();
+        sort_rows(&mut rows, &state);
+        rows.iter().map(|(s, _)| s.label.as_str()).collect()
+    }
+
+    #[test]
+    fn test_sort_by_updated_and_uses() {
+        // Sort keys are numeric, so 100 sorts after 20
+        let snippets = [
+            snippet("a", 300, 20),
+            snippet("b", 100, 100),
+            snippet("c", 200, 3),
+        ];
+        assert_eq!(
+            sorted_labels(&snippets, TableColumn::Updated, true),
+            ["b", "c", "a"]
+        );
+        assert_eq!(
+            sorted_labels(&snippets, TableColumn::Updated, false),
+            ["a", "c", "b"]
+        );
+        assert_eq!(
+            sorted_labels(&snippets, TableColumn::Uses, true),
+            ["c", "a", "b"]
+        );
+        assert_eq!(
+            sorted_labels(&snippets, TableColumn::Uses, false),
+            ["b", "a", "c"]
+        );
+    }
+}
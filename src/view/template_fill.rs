@@ -0,0 +1,161 @@
+//! Dialog filling in a snippet's `${name:default}` placeholders before it is copied.
+
+use std::collections::HashMap;
+
+use iced::widget::{
+    button, column, container, mouse_area, opaque, row, scrollable, text, text_input,
+};
+use iced::{Element, Font, Length};
+
+use crate::icons;
+use crate::message::Message;
+use crate::snippet::{fill_placeholders, Placeholder};
+use crate::theme::{
+    input_style, modal_dialog_style, modal_overlay_style, primary_button_style, scrollbar_style,
+    secondary_button_style, subtle_button_style, BG_BASE, BORDER_SUBTLE, SPACE_MD, SPACE_SM,
+    SPACE_XS, TEXT_PRIMARY, TEXT_SECONDARY,
+};
+use crate::view::labeled;
+
+/// State of the placeholder form.
+#[derive(Debug, Clone)]
+pub struct TemplateFillState {
+    /// Snippet being copied.
+    pub snippet_id: i64,
+    pub label: String,
+    /// Snippet text with its placeholders, taken when the form opened.
+    pub template: String,
+    pub placeholders: Vec<Placeholder>,
+    /// One value per placeholder, starting at its default.
+    pub values: Vec<String>,
+}
+
+impl TemplateFillState {
+    /// Open the form on a snippet's placeholders, each set to its default.
+    pub fn new(
+        snippet_id: i64,
+        label: String,
+        template: String,
+        placeholders: Vec<Placeholder>,
+    ) -> Self {
+        let values = placeholders.iter().map(|p| p.default.clone()).collect();
+        Self {
+            snippet_id,
+            label,
+            template,
+            placeholders,
+            values,
+        }
+    }
+
+    /// The snippet text with the values filled in.
+    pub fn output(&self) -> String {
+        let values: HashMap<String, String> = self
+            .placeholders
+            .iter()
+            .map(|p| p.name.clone())
+            .zip(self.values.iter().cloned())
+            .collect();
+        fill_placeholders(&self.template, &values)
+    }
+}
+
+/// Render the placeholder form: one field per placeholder and a preview.
+pub fn view_template_fill_modal(state: &TemplateFillState) -> Element<'_, Message> {
+    let header_row = row![
+        text(format!("Copy \"{}\"", state.label))
+            .size(20)
+            .color(TEXT_PRIMARY),
+        iced::widget::Space::new().width(Length::Fill),
+        labeled(
+            button(icons::x().size(16))
+                .on_press(Message::CloseTemplateFill)
+                .padding([SPACE_XS, SPACE_SM])
+                .style(subtle_button_style),
+            "Close"
+        ),
+    ]
+    .spacing(SPACE_XS)
+    .align_y(iced::Alignment::Center);
+
+    let fields = column(
+        state
+            .placeholders
+            .iter()
+            .zip(&state.values)
+            .enumerate()
+            .map(|(index, (placeholder, value))| {
+                row![
+                    text(&placeholder.name)
+                        .size(12)
+                        .font(Font::MONOSPACE)
+                        .color(TEXT_SECONDARY)
+                        .width(120),
+                    text_input(&placeholder.default, value)
+                        .on_input(move |value| Message::TemplateValueChanged(index, value))
+                        .on_submit(Message::CopyTemplateFill)
+                        .padding(SPACE_SM)
+                        .width(Length::Fill)
+                        .style(|theme, status| input_style(theme, status, false)),
+                ]
+                .spacing(SPACE_SM)
+                .align_y(iced::Alignment::Center)
+                .into()
+            }),
+    )
+    .spacing(SPACE_SM);
+
+    let preview = container(
+        scrollable(
+            text(state.output())
+                .size(12)
+                .font(Font::MONOSPACE)
+                .color(TEXT_PRIMARY),
+        )
+        .width(Length::Fill)
+        .style(scrollbar_style),
+    )
+    .padding(SPACE_SM)
+    .width(Length::Fill)
+    .height(Length::Fixed(180.0))
+    .style(|_theme| {
+        container::Style::default().background(BG_BASE).border(
+            iced::Border::default()
+                .rounded(4.0)
+                .width(1.0)
+                .color(BORDER_SUBTLE),
+        )
+    });
+
+    let action_buttons = row![
+        iced::widget::Space::new().width(Length::Fill),
+        button(text("Cancel").size(14))
+            .on_press(Message::CloseTemplateFill)
+            .padding(SPACE_SM)
+            .style(secondary_button_style),
+        button(text("Copy").size(14))
+            .on_press(Message::CopyTemplateFill)
+            .padding(SPACE_SM)
+            .style(primary_button_style),
+    ]
+    .spacing(SPACE_SM);
+
+    let modal_content = column![header_row, fields, preview, action_buttons]
+        .spacing(SPACE_MD)
+        .padding(SPACE_MD)
+        .width(Length::Fixed(480.0));
+
+    let modal_dialog = container(modal_content).style(modal_dialog_style);
+
+    // Semi-transparent overlay
+    mouse_area(
+        container(opaque(modal_dialog))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x(Length::Fill)
+            .center_y(Length::Fill)
+            .style(modal_overlay_style),
+    )
+    .on_press(Message::CloseTemplateFill)
+    .into()
+}
@@ -3,6 +3,7 @@
 use iced::widget::{button, column, container, row, text};
 use iced::{Element, Length};
 
+use crate::config::CardActions;
 use crate::icons;
 use crate::message::Message;
 use crate::snippet::TextData;
@@ -10,33 +11,55 @@ use crate::theme::{
     card_style, danger_button_style, subtle_button_style, BG_SURFACE, SPACE_MD, SPACE_SM, SPACE_XS,
     TEXT_MUTED, TEXT_SECONDARY,
 };
+use crate::view::{
+    card_with_actions, labeled, view_age, view_card_header, view_preview_lines, ActionSet,
+    CardStats,
+};
 
-/// Render a text snippet card.
+/// Render a text snippet card, showing actions per the visibility setting.
 pub fn view_text_card<'a>(
     id: i64,
     label: &'a str,
+    icon: Option<&'a str>,
     text_data: &'a TextData,
+    stats: CardStats<'a>,
     is_selected: bool,
+    visibility: CardActions,
 ) -> Element<'a, Message> {
-    // Text icon (64x64 container)
-    let text_icon = container(
-        text(icons::TEXT_ICON)
-            .size(28)
-            .font(icons::ICON_FONT)
-            .color(TEXT_SECONDARY),
-    )
-    .width(64)
-    .height(64)
-    .center_x(64)
-    .center_y(64)
-    .style(|_theme| iced::widget::container::Style::default().background(BG_SURFACE));
+    card_with_actions(visibility, |actions| {
+        build_text_card(id, label, icon, text_data, stats, is_selected, actions)
+    })
+}
+
+/// Build a text snippet card with the given set of action buttons.
+fn build_text_card<'a>(
+    id: i64,
+    label: &'a str,
+    icon: Option<&'a str>,
+    text_data: &'a TextData,
+    stats: CardStats<'a>,
+    is_selected: bool,
+    actions: ActionSet,
+) -> Element<'a, Message> {
+    // Text icon, or the snippet's custom icon (64x64 container)
+    let kind_icon = match icon {
+        Some(icon) => icons::snippet_icon(icon),
+        None => text(icons::TEXT_ICON).font(icons::ICON_FONT),
+    };
+    let text_icon = container(kind_icon.size(28).color(TEXT_SECONDARY))
+        .width(64)
+        .height(64)
+        .center_x(64)
+        .center_y(64)
+        .style(|_theme| iced::widget::container::Style::default().background(BG_SURFACE));
 
     // Stats badge
     let stats_badge = container(
         text(format!(
-            "{} chars, {} lines",
-            text_data.char_count(),
-            text_data.line_count()
+            "{} words, {} lines, {} min read",
+            text_data.word_count(),
+            text_data.line_count(),
+            text_data.reading_minutes()
         ))
         .size(10)
         .color(TEXT_MUTED),
@@ -44,45 +67,89 @@ pub fn view_text_card<'a>(
     .padding([2, 6])
     .style(|_theme| iced::widget::container::Style::default().background(BG_SURFACE));
 
-    // Header row: label + stats badge
-    let header_row = row![text(label).size(14).color(TEXT_SECONDARY), stats_badge]
+    // Header row: label + stats badge (+ rich text badge)
+    let mut header_row = row![text(label).size(14).color(TEXT_SECONDARY), stats_badge]
         .spacing(SPACE_SM)
         .align_y(iced::Alignment::Center);
+    if text_data.html.is_some() {
+        header_row = header_row.push(
+            container(text("Rich text").size(10).color(TEXT_MUTED))
+                .padding([2, 6])
+                .style(|_theme| iced::widget::container::Style::default().background(BG_SURFACE)),
+        );
+    }
+    let header_row = header_row.push(view_age(stats));
 
     // Text preview (first 2 lines)
-    let preview = text_data.preview(2);
-    let preview_text = text(preview).size(11).color(TEXT_MUTED);
+    let preview_text = view_preview_lines(text_data.preview(2), 11.0);
 
     // Info column with header and preview
-    let info_column = column![header_row, preview_text]
+    let info_column = column![view_card_header(header_row, stats), preview_text]
         .spacing(SPACE_XS)
         .width(Length::Fill);
 
     // Action buttons
-    let action_row = row![
-        button(icons::copy().size(14))
-            .on_press(Message::CopySnippet(id))
-            .padding([SPACE_XS, SPACE_SM])
-            .style(subtle_button_style),
-        button(icons::push_pin().size(14))
-            .on_press(Message::PinSnippet(id))
-            .padding([SPACE_XS, SPACE_SM])
-            .style(subtle_button_style),
-        button(icons::arrow_square_out().size(14))
-            .on_press(Message::OpenInExternalEditor(id, false))
-            .padding([SPACE_XS, SPACE_SM])
-            .style(subtle_button_style),
-        button(icons::pencil().size(14))
-            .on_press(Message::OpenTextEditor(Some(id)))
-            .padding([SPACE_XS, SPACE_SM])
-            .style(subtle_button_style),
-        button(icons::trash().size(14))
-            .on_press(Message::DeleteSnippet(id))
-            .padding([SPACE_XS, SPACE_SM])
-            .style(danger_button_style),
-    ]
-    .spacing(SPACE_XS)
-    .align_y(iced::Alignment::Center);
+    let mut action_row = row![].spacing(SPACE_XS).align_y(iced::Alignment::Center);
+    if actions.shows_copy() {
+        action_row = action_row.push(labeled(
+            button(icons::copy().size(14))
+                .on_press(Message::CopySnippet(id))
+                .padding([SPACE_XS, SPACE_SM])
+                .style(subtle_button_style),
+            "Copy",
+        ));
+        if text_data.html.is_some() {
+            action_row = action_row.push(labeled(
+                button(icons::text_aa().size(14))
+                    .on_press(Message::CopyRichText(id))
+                    .padding([SPACE_XS, SPACE_SM])
+                    .style(subtle_button_style),
+                "Copy as rich text",
+            ));
+        }
+        action_row = action_row.push(labeled(
+            button(icons::markdown_logo().size(14))
+                .on_press(Message::CopyMarkdown(id))
+                .padding([SPACE_XS, SPACE_SM])
+                .style(subtle_button_style),
+            "Copy as Markdown",
+        ));
+    }
+    if actions == ActionSet::All {
+        action_row = action_row.push(
+            row![
+                labeled(
+                    button(icons::push_pin().size(14))
+                        .on_press(Message::PinSnippet(id))
+                        .padding([SPACE_XS, SPACE_SM])
+                        .style(subtle_button_style),
+                    "Pin in a window"
+                ),
+                labeled(
+                    button(icons::arrow_square_out().size(14))
+                        .on_press(Message::OpenInExternalEditor(id, false))
+                        .padding([SPACE_XS, SPACE_SM])
+                        .style(subtle_button_style),
+                    "Open in external editor"
+                ),
+                labeled(
+                    button(icons::pencil().size(14))
+                        .on_press(Message::OpenTextEditor(Some(id)))
+                        .padding([SPACE_XS, SPACE_SM])
+                        .style(subtle_button_style),
+                    "Edit"
+                ),
+                labeled(
+                    button(icons::trash().size(14))
+                        .on_press(Message::DeleteSnippet(id))
+                        .padding([SPACE_XS, SPACE_SM])
+                        .style(danger_button_style),
+                    "Move to trash"
+                ),
+            ]
+            .spacing(SPACE_XS),
+        );
+    }
 
     let card = row![text_icon, info_column, action_row]
         .spacing(SPACE_MD)
@@ -1,28 +1,35 @@
 //! Text editor modal for editing text snippets.
 
+use iced::widget::text::Wrapping;
 use iced::widget::{
     button, column, container, mouse_area, opaque, row, text, text_editor, text_input,
 };
-use iced::{Element, Length};
+use iced::{keyboard, Element, Length};
 
+use crate::config::EditingConfig;
 use crate::icons;
 use crate::message::Message;
-use crate::snippet::{Snippet, SnippetContent};
+use crate::snippet::{Snippet, SnippetContent, TextData};
 use crate::theme::{
     input_style, modal_dialog_style, modal_overlay_style, primary_button_style,
-    secondary_button_style, subtle_button_style, SPACE_MD, SPACE_SM, SPACE_XS, TEXT_PRIMARY,
-    TEXT_SECONDARY,
+    secondary_button_style, subtle_button_style, SPACE_MD, SPACE_SM, SPACE_XS, TEXT_MUTED,
+    TEXT_PRIMARY, TEXT_SECONDARY,
 };
+use crate::view::{description_content, labeled, view_content_hash, view_description_input};
 
 /// State for the text editor modal.
 #[derive(Debug, Clone)]
 pub struct TextEditorState {
     /// The snippet being edited (Some = editing existing, None = creating new)
     pub editing_id: Option<i64>,
+    /// Revision of the edited snippet when editing started.
+    pub revision: i64,
     /// The text content
     pub content: text_editor::Content,
     /// Label for the snippet
     pub label: String,
+    /// Notes on the snippet
+    pub description: text_editor::Content,
 }
 
 impl TextEditorState {
@@ -30,8 +37,10 @@ impl TextEditorState {
     pub fn new_text() -> Self {
         Self {
             editing_id: None,
+            revision: 0,
             content: text_editor::Content::new(),
             label: String::new(),
+            description: text_editor::Content::new(),
         }
     }
 
@@ -40,8 +49,10 @@ impl TextEditorState {
         if let SnippetContent::Text(text_data) = &snippet.content {
             Self {
                 editing_id: Some(snippet.id),
+                revision: snippet.revision,
                 content: text_editor::Content::with_text(&text_data.text),
                 label: snippet.label.clone(),
+                description: description_content(snippet),
             }
         } else {
             Self::new_text()
@@ -54,8 +65,37 @@ impl TextEditorState {
     }
 }
 
+/// Key bindings of the editor modals: Tab indents as configured at the
+/// cursor's column, other keys work as usual.
+pub fn editor_key_binding(
+    editing: EditingConfig,
+    column: usize,
+) -> impl Fn(text_editor::KeyPress) -> Option<text_editor::Binding<Message>> {
+    move |key_press| {
+        let is_tab = matches!(
+            key_press.key,
+            keyboard::Key::Named(keyboard::key::Named::Tab)
+        ) && !key_press.modifiers.shift()
+            && !key_press.modifiers.command()
+            && !key_press.modifiers.alt();
+        if is_tab && matches!(key_press.status, text_editor::Status::Focused { .. }) {
+            return Some(text_editor::Binding::Sequence(
+                editing
+                    .indent(column)
+                    .chars()
+                    .map(text_editor::Binding::Insert)
+                    .collect(),
+            ));
+        }
+        text_editor::Binding::from_key_press(key_press)
+    }
+}
+
 /// Render the text editor modal.
-pub fn view_text_editor_modal(editor: &TextEditorState) -> Element<'_, Message> {
+pub fn view_text_editor_modal(
+    editor: &TextEditorState,
+    editing: EditingConfig,
+) -> Element<'_, Message> {
     let title = if editor.editing_id.is_some() {
         "Edit Text Snippet"
     } else {
@@ -63,25 +103,29 @@ pub fn view_text_editor_modal(editor: &TextEditorState) -> Element<'_, Message>
     };
 
     // Header
-    let external_editor_button = if let Some(id) = editor.editing_id {
+    let external_editor_button = labeled(
         button(icons::arrow_square_out().size(14))
-            .on_press(Message::OpenInExternalEditor(id, false))
+            .on_press_maybe(
+                editor
+                    .editing_id
+                    .map(|id| Message::OpenInExternalEditor(id, false)),
+            )
             .padding([SPACE_XS, SPACE_SM])
-            .style(subtle_button_style)
-    } else {
-        button(icons::arrow_square_out().size(14))
-            .padding([SPACE_XS, SPACE_SM])
-            .style(subtle_button_style)
-    };
+            .style(subtle_button_style),
+        "Open in external editor",
+    );
 
     let header_row = row![
         text(title).size(20).color(TEXT_PRIMARY),
         iced::widget::Space::new().width(Length::Fill),
         external_editor_button,
-        button(icons::x().size(16))
-            .on_press(Message::CloseTextEditor)
-            .padding([SPACE_XS, SPACE_SM])
-            .style(subtle_button_style),
+        labeled(
+            button(icons::x().size(16))
+                .on_press(Message::CloseTextEditor)
+                .padding([SPACE_XS, SPACE_SM])
+                .style(subtle_button_style),
+            "Close"
+        ),
     ]
     .spacing(SPACE_XS)
     .align_y(iced::Alignment::Center);
@@ -89,8 +133,34 @@ pub fn view_text_editor_modal(editor: &TextEditorState) -> Element<'_, Message>
     // Text editor
     let the_text_editor = text_editor(&editor.content)
         .on_action(Message::TextEditorContentChanged)
+        .key_binding(editor_key_binding(
+            editing,
+            editor.content.cursor().position.column,
+        ))
         .height(Length::Fixed(300.0))
-        .padding(SPACE_SM);
+        .padding(SPACE_SM)
+        .wrapping(if editing.word_wrap {
+            Wrapping::Word
+        } else {
+            Wrapping::None
+        });
+
+    // Editor status bar (word/char/line counts and reading time)
+    let stats = TextData::new(editor.content.text());
+    let editor_status = row![
+        text(format!(
+            "{} words  •  {} chars  •  {} lines  •  {} min read",
+            stats.word_count(),
+            stats.char_count(),
+            stats.line_count(),
+            stats.reading_minutes()
+        ))
+        .size(11)
+        .color(TEXT_MUTED),
+        iced::widget::Space::new().width(Length::Fill),
+        view_content_hash(&stats.text, editor.editing_id),
+    ]
+    .align_y(iced::Alignment::Center);
 
     // Label input
     let label_input = row![
@@ -125,10 +195,17 @@ pub fn view_text_editor_modal(editor: &TextEditorState) -> Element<'_, Message>
     .spacing(SPACE_SM);
 
     // Modal content
-    let modal_content = column![header_row, the_text_editor, label_input, action_buttons,]
-        .spacing(SPACE_MD)
-        .padding(SPACE_MD)
-        .width(Length::Fixed(500.0));
+    let modal_content = column![
+        header_row,
+        the_text_editor,
+        editor_status,
+        label_input,
+        view_description_input(&editor.description, Message::TextEditorDescriptionChanged),
+        action_buttons,
+    ]
+    .spacing(SPACE_MD)
+    .padding(SPACE_MD)
+    .width(Length::Fixed(500.0));
 
     let modal_dialog = container(modal_content).style(modal_dialog_style);
 
@@ -0,0 +1,108 @@
+//! Trash list of soft-deleted snippets.
+
+use iced::widget::{button, column, container, row, scrollable, text};
+use iced::{Element, Length};
+
+use crate::config::TrashRetention;
+use crate::icons;
+use crate::message::Message;
+use crate::snippet::{relative_time, unix_now, Snippet};
+use crate::theme::{
+    danger_button_style, scrollbar_style, secondary_button_style, BG_ELEVATED, SPACE_MD, SPACE_SM,
+    SPACE_XS, TEXT_MUTED, TEXT_SECONDARY,
+};
+
+/// Render the trash list shown in place of the snippet list.
+pub fn view_trash(trash: &[Snippet], retention: TrashRetention) -> Element<'_, Message> {
+    let retention_hint = match retention.days() {
+        Some(days) => format!("Snippets are deleted forever after {} days", days),
+        None => "Snippets stay until the trash is emptied".to_string(),
+    };
+
+    let header_row = row![
+        text(retention_hint).size(12).color(TEXT_SECONDARY),
+        iced::widget::Space::new().width(Length::Fill),
+        button(text("Empty Trash").size(12))
+            .on_press_maybe((!trash.is_empty()).then_some(Message::EmptyTrash))
+            .padding([SPACE_XS, SPACE_SM])
+            .style(danger_button_style),
+    ]
+    .spacing(SPACE_SM)
+    .align_y(iced::Alignment::Center);
+
+    if trash.is_empty() {
+        return column![
+            container(header_row).padding([SPACE_SM, SPACE_MD]),
+            container(text("Trash is empty.").size(14).color(TEXT_SECONDARY))
+                .padding(SPACE_MD)
+                .center_x(Length::Fill),
+        ]
+        .height(Length::Fill)
+        .into();
+    }
+
+    let now = unix_now();
+    let items: Vec<Element<'_, Message>> = trash
+        .iter()
+        .map(|snippet| {
+            let deleted = snippet
+                .deleted_at
+                .map(|ts| format!("Deleted {}", relative_time(ts, now)))
+                .unwrap_or_default();
+
+            let summary = column![
+                row![
+                    text(&snippet.label).size(13),
+                    text(snippet.content.kind().display_name())
+                        .size(11)
+                        .color(TEXT_MUTED),
+                ]
+                .spacing(SPACE_SM)
+                .align_y(iced::Alignment::Center),
+                text(snippet.content.preview(60)).size(11).color(TEXT_MUTED),
+                text(deleted).size(10).color(TEXT_MUTED),
+            ]
+            .spacing(2)
+            .width(Length::Fill);
+
+            let restore_btn = button(
+                row![
+                    icons::arrow_counter_clockwise().size(12),
+                    text("Restore").size(12)
+                ]
+                .spacing(SPACE_XS)
+                .align_y(iced::Alignment::Center),
+            )
+            .on_press(Message::RestoreSnippet(snippet.id))
+            .padding([SPACE_XS, SPACE_SM])
+            .style(secondary_button_style);
+
+            let delete_btn = button(text("Delete Forever").size(12))
+                .on_press(Message::DeleteSnippetForever(snippet.id))
+                .padding([SPACE_XS, SPACE_SM])
+                .style(danger_button_style);
+
+            container(
+                row![summary, restore_btn, delete_btn]
+                    .spacing(SPACE_SM)
+                    .align_y(iced::Alignment::Center),
+            )
+            .padding(SPACE_SM)
+            .style(|_theme| {
+                iced::widget::container::Style::default()
+                    .background(BG_ELEVATED)
+                    .border(iced::Border::default().rounded(4.0))
+            })
+            .into()
+        })
+        .collect();
+
+    scrollable(
+        column![header_row, column(items).spacing(SPACE_SM)]
+            .spacing(SPACE_SM)
+            .padding(SPACE_MD),
+    )
+    .height(Length::Fill)
+    .style(scrollbar_style)
+    .into()
+}
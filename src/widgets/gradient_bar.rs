@@ -0,0 +1,211 @@
+//! Canvas widgets for editing a linear gradient: a bar with draggable stops,
+//! and a preview drawn at the gradient's angle.
+
+use iced::widget::canvas::{self, Event};
+use iced::{mouse, Rectangle, Renderer, Theme};
+
+use super::draw_checkerboard;
+use crate::message::Message;
+use crate::snippet::GradientData;
+
+/// Height of the strip of stop handles under the bar.
+const HANDLE_HEIGHT: f32 = 14.0;
+/// Half the width of a stop handle; presses this close to a stop grab it.
+const HANDLE_HALF_WIDTH: f32 = 6.0;
+
+/// A canvas program drawing a gradient with a handle per stop. Pressing a
+/// handle selects it for dragging; pressing elsewhere adds a stop there.
+pub struct GradientBar<'a> {
+    pub gradient: &'a GradientData,
+    /// Index of the selected stop.
+    pub selected: usize,
+}
+
+/// State to track if the user is currently dragging a stop.
+#[derive(Default)]
+pub struct GradientBarState {
+    is_dragging: bool,
+}
+
+impl GradientBar<'_> {
+    /// Convert cursor x position to a gradient position (0-1).
+    fn position_at(bounds: Rectangle, position: iced::Point) -> f32 {
+        (position.x / bounds.width).clamp(0.0, 1.0)
+    }
+
+    /// Find the stop whose handle is under an x position, nearest first.
+    fn stop_at(&self, bounds: Rectangle, x: f32) -> Option<usize> {
+        self.gradient
+            .stops
+            .iter()
+            .enumerate()
+            .map(|(index, stop)| (index, (stop.position * bounds.width - x).abs()))
+            .filter(|(_, distance)| *distance <= HANDLE_HALF_WIDTH)
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(index, _)| index)
+    }
+}
+
+impl canvas::Program<Message> for GradientBar<'_> {
+    type State = GradientBarState;
+
+    fn update(
+        &self,
+        state: &mut Self::State,
+        event: &Event,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> Option<canvas::Action<Message>> {
+        match event {
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                let cursor_position = cursor.position_in(bounds)?;
+                state.is_dragging = true;
+                let message = match self.stop_at(bounds, cursor_position.x) {
+                    Some(index) => Message::GradientStopSelected(index),
+                    None => Message::GradientStopAdded(Self::position_at(bounds, cursor_position)),
+                };
+                Some(canvas::Action::publish(message).and_capture())
+            }
+            Event::Mouse(mouse::Event::CursorMoved { position }) if state.is_dragging => {
+                // Keep following the cursor when it leaves the bar
+                let local = iced::Point::new(position.x - bounds.x, position.y - bounds.y);
+                let position = Self::position_at(bounds, local);
+                Some(canvas::Action::publish(Message::GradientStopMoved(position)).and_capture())
+            }
+            Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left))
+                if state.is_dragging =>
+            {
+                state.is_dragging = false;
+                Some(canvas::Action::capture())
+            }
+            _ => None,
+        }
+    }
+
+    fn mouse_interaction(
+        &self,
+        state: &Self::State,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> mouse::Interaction {
+        if state.is_dragging {
+            mouse::Interaction::Grabbing
+        } else if let Some(position) = cursor.position_in(bounds) {
+            if self.stop_at(bounds, position.x).is_some() {
+                mouse::Interaction::Grab
+            } else {
+                mouse::Interaction::Pointer
+            }
+        } else {
+            mouse::Interaction::default()
+        }
+    }
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        _theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<canvas::Geometry<Renderer>> {
+        let mut frame = canvas::Frame::new(renderer, bounds.size());
+        let bar = iced::Size::new(bounds.width, (bounds.height - HANDLE_HEIGHT).max(1.0));
+
+        // Draw the gradient over a checkerboard, one 2px strip at a time
+        draw_checkerboard(&mut frame, Rectangle::new(iced::Point::ORIGIN, bar), 8.0);
+        let strips = (bar.width / 2.0).ceil() as usize;
+        for i in 0..strips {
+            let x = i as f32 * 2.0;
+            let color = self.gradient.color_at((x + 1.0) / bar.width);
+            frame.fill_rectangle(
+                iced::Point::new(x, 0.0),
+                iced::Size::new(2.0, bar.height),
+                color.to_iced_color(),
+            );
+        }
+        frame.stroke(
+            &canvas::Path::rectangle(iced::Point::ORIGIN, bar),
+            canvas::Stroke::default()
+                .with_color(iced::Color::from_rgb(0.3, 0.3, 0.3))
+                .with_width(1.0),
+        );
+
+        // A handle under each stop, pointing up at it, the selected one outlined in white
+        for (index, stop) in self.gradient.stops.iter().enumerate() {
+            let x = stop.position * bounds.width;
+            let handle = canvas::Path::new(|path| {
+                path.move_to(iced::Point::new(x, bar.height));
+                path.line_to(iced::Point::new(x + HANDLE_HALF_WIDTH, bar.height + 6.0));
+                path.line_to(iced::Point::new(x + HANDLE_HALF_WIDTH, bounds.height - 1.0));
+                path.line_to(iced::Point::new(x - HANDLE_HALF_WIDTH, bounds.height - 1.0));
+                path.line_to(iced::Point::new(x - HANDLE_HALF_WIDTH, bar.height + 6.0));
+                path.close();
+            });
+            frame.fill(&handle, stop.color.to_iced_color());
+            let (outline, width) = if index == self.selected {
+                (iced::Color::WHITE, 2.0)
+            } else {
+                (iced::Color::from_rgb(0.3, 0.3, 0.3), 1.0)
+            };
+            frame.stroke(
+                &handle,
+                canvas::Stroke::default()
+                    .with_color(outline)
+                    .with_width(width),
+            );
+        }
+
+        vec![frame.into_geometry()]
+    }
+}
+
+/// A canvas program filling its bounds with a gradient at its angle, laid
+/// out the way CSS does.
+pub struct GradientPreview<'a> {
+    pub gradient: &'a GradientData,
+}
+
+impl canvas::Program<Message> for GradientPreview<'_> {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        _theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<canvas::Geometry<Renderer>> {
+        let mut frame = canvas::Frame::new(renderer, bounds.size());
+        draw_checkerboard(&mut frame, bounds, 8.0);
+
+        // The gradient line runs through the center, long enough that its
+        // ends touch the corners
+        let (sin, cos) = self.gradient.angle.to_radians().sin_cos();
+        let length = (bounds.width * sin).abs() + (bounds.height * cos).abs();
+        let cell = 4.0;
+        let columns = (bounds.width / cell).ceil() as usize;
+        let rows = (bounds.height / cell).ceil() as usize;
+        for row in 0..rows {
+            for col in 0..columns {
+                let x = col as f32 * cell + cell / 2.0 - bounds.width / 2.0;
+                let y = row as f32 * cell + cell / 2.0 - bounds.height / 2.0;
+                let position = (x * sin - y * cos) / length.max(1.0) + 0.5;
+                frame.fill_rectangle(
+                    iced::Point::new(col as f32 * cell, row as f32 * cell),
+                    iced::Size::new(cell, cell),
+                    self.gradient.color_at(position).to_iced_color(),
+                );
+            }
+        }
+
+        frame.stroke(
+            &canvas::Path::rectangle(iced::Point::ORIGIN, bounds.size()),
+            canvas::Stroke::default()
+                .with_color(iced::Color::from_rgb(0.3, 0.3, 0.3))
+                .with_width(1.0),
+        );
+        vec![frame.into_geometry()]
+    }
+}
@@ -50,7 +50,7 @@ impl canvas::Program<Message> for HueBar {
                 if state.is_dragging =>
             {
                 state.is_dragging = false;
-                Some(canvas::Action::capture())
+                Some(canvas::Action::publish(Message::PickerAdjustmentEnded).and_capture())
             }
             _ => None,
         }
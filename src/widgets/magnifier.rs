@@ -0,0 +1,147 @@
+//! A canvas widget covering the screen that samples a screenshot under a magnifier.
+
+use iced::widget::canvas::{self, Event};
+use iced::{mouse, Rectangle, Renderer, Theme};
+
+use crate::eyedropper::Screenshot;
+use crate::message::Message;
+
+/// Pixels shown across the magnifier (odd, so one is in the middle).
+const GRID: i64 = 11;
+/// Size of one magnified pixel.
+const CELL: f32 = 10.0;
+/// Gap between the cursor and the magnifier.
+const OFFSET: f32 = 24.0;
+
+/// A canvas program drawing a magnifier over the screen; a left click picks
+/// the pixel under the cursor and a right click cancels.
+pub struct Magnifier<'a> {
+    pub screenshot: &'a Screenshot,
+}
+
+impl canvas::Program<Message> for Magnifier<'_> {
+    type State = ();
+
+    fn update(
+        &self,
+        _state: &mut Self::State,
+        event: &Event,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> Option<canvas::Action<Message>> {
+        match event {
+            Event::Mouse(mouse::Event::CursorMoved { .. }) => {
+                Some(canvas::Action::request_redraw())
+            }
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                let (x, y) = self
+                    .screenshot
+                    .pixel_at(cursor.position_in(bounds)?, bounds.size())?;
+                let color = self.screenshot.pixel(x, y)?;
+                Some(canvas::Action::publish(Message::EyedropperPicked(color)).and_capture())
+            }
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Right)) => {
+                Some(canvas::Action::publish(Message::CancelEyedropper).and_capture())
+            }
+            _ => None,
+        }
+    }
+
+    fn mouse_interaction(
+        &self,
+        _state: &Self::State,
+        _bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> mouse::Interaction {
+        mouse::Interaction::Crosshair
+    }
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        _theme: &Theme,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> Vec<canvas::Geometry<Renderer>> {
+        let mut frame = canvas::Frame::new(renderer, bounds.size());
+
+        // An almost invisible fill, so clicks land on the window rather than
+        // passing through its transparent pixels
+        frame.fill_rectangle(
+            iced::Point::ORIGIN,
+            bounds.size(),
+            iced::Color::from_rgba(0.0, 0.0, 0.0, 0.01),
+        );
+
+        let Some(position) = cursor.position_in(bounds) else {
+            return vec![frame.into_geometry()];
+        };
+        let Some((x, y)) = self.screenshot.pixel_at(position, bounds.size()) else {
+            return vec![frame.into_geometry()];
+        };
+
+        // Below and right of the cursor, flipped where it would leave the screen
+        let size = GRID as f32 * CELL;
+        let label_height = 22.0;
+        let mut origin = iced::Point::new(position.x + OFFSET, position.y + OFFSET);
+        if origin.x + size > bounds.width {
+            origin.x = position.x - OFFSET - size;
+        }
+        if origin.y + size + label_height > bounds.height {
+            origin.y = position.y - OFFSET - size - label_height;
+        }
+
+        let half = GRID / 2;
+        for row in 0..GRID {
+            for col in 0..GRID {
+                let px = x as i64 + col - half;
+                let py = y as i64 + row - half;
+                let color = u32::try_from(px)
+                    .ok()
+                    .zip(u32::try_from(py).ok())
+                    .and_then(|(px, py)| self.screenshot.pixel(px, py))
+                    .map_or(iced::Color::BLACK, |c| c.to_iced_color());
+                frame.fill_rectangle(
+                    iced::Point::new(origin.x + col as f32 * CELL, origin.y + row as f32 * CELL),
+                    iced::Size::new(CELL, CELL),
+                    color,
+                );
+            }
+        }
+
+        // Outline the sampled pixel and the magnifier
+        let center = iced::Point::new(origin.x + half as f32 * CELL, origin.y + half as f32 * CELL);
+        frame.stroke(
+            &canvas::Path::rectangle(center, iced::Size::new(CELL, CELL)),
+            canvas::Stroke::default()
+                .with_color(iced::Color::WHITE)
+                .with_width(2.0),
+        );
+        frame.stroke(
+            &canvas::Path::rectangle(origin, iced::Size::new(size, size)),
+            canvas::Stroke::default()
+                .with_color(iced::Color::from_rgb(0.3, 0.3, 0.3))
+                .with_width(1.0),
+        );
+
+        // Hex value of the sampled pixel
+        if let Some(color) = self.screenshot.pixel(x, y) {
+            let label_origin = iced::Point::new(origin.x, origin.y + size);
+            frame.fill_rectangle(
+                label_origin,
+                iced::Size::new(size, label_height),
+                iced::Color::from_rgba(0.0, 0.0, 0.0, 0.8),
+            );
+            frame.fill_text(canvas::Text {
+                content: color.to_hex(),
+                position: iced::Point::new(label_origin.x + 6.0, label_origin.y + 4.0),
+                color: iced::Color::WHITE,
+                size: 12.into(),
+                ..canvas::Text::default()
+            });
+        }
+
+        vec![frame.into_geometry()]
+    }
+}
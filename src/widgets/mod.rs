@@ -3,14 +3,20 @@
 mod alpha_bar;
 mod cl_box;
 mod color_swatch;
+mod gradient_bar;
 mod hue_bar;
+mod magnifier;
 mod sl_box;
+mod sv_box;
 
 pub use alpha_bar::AlphaBar;
 pub use cl_box::ChromaLightnessBox;
 pub use color_swatch::ColorSwatch;
+pub use gradient_bar::{GradientBar, GradientPreview};
 pub use hue_bar::HueBar;
+pub use magnifier::Magnifier;
 pub use sl_box::SaturationLightnessBox;
+pub use sv_box::SaturationValueBox;
 
 use iced::widget::canvas;
 